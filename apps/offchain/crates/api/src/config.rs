@@ -0,0 +1,42 @@
+//! API settings, read from the environment.
+
+pub const DEFAULT_DATABASE: &str = "lumenpulse-events.db";
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:8080";
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ConfigError {
+    #[error("{name} is invalid: {reason}")]
+    Invalid { name: &'static str, reason: String },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// `API_DATABASE`, the indexer's SQLite file
+    pub database: String,
+    /// `API_LISTEN`, the address to serve on
+    pub listen: String,
+    /// `API_ALLOW_ORIGIN`, the origin browsers may call the API from, `*`
+    /// for any; none when empty
+    pub allow_origin: Option<String>,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let allow_origin = var("API_ALLOW_ORIGIN").unwrap_or_else(|| "*".to_string());
+        if !allow_origin.chars().all(|c| c.is_ascii_graphic()) {
+            return Err(ConfigError::Invalid {
+                name: "API_ALLOW_ORIGIN",
+                reason: "must be printable ASCII without spaces".to_string(),
+            });
+        }
+        Ok(Config {
+            database: var("API_DATABASE").unwrap_or_else(|| DEFAULT_DATABASE.to_string()),
+            listen: var("API_LISTEN").unwrap_or_else(|| DEFAULT_LISTEN.to_string()),
+            allow_origin: Some(allow_origin).filter(|origin| !origin.is_empty()),
+        })
+    }
+}
@@ -0,0 +1,457 @@
+//! What the API reads from the indexer's database.
+//!
+//! Everything here is folded from stored events, so it is as current as
+//! the indexer. Decoded events are parsed from their stored text rather
+//! than through `serde_json::Value`, which would round amounts past
+//! `u64` to floats.
+
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use serde::{de::DeserializeOwned, Deserialize, Serialize, Serializer};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+/// A token amount, which JSON carries as a string so that readers with
+/// 53-bit numbers get it exactly
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Amount(pub i128);
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+/// A stream as its events leave it
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Stream {
+    pub contract_id: String,
+    pub stream_id: u64,
+    pub sender: String,
+    pub recipient: String,
+    pub token: String,
+    /// Deposit after any top-ups
+    pub deposit: Amount,
+    pub withdrawn: Amount,
+    /// What the recipient has still to receive, accrued or not; nothing
+    /// once cancelled, as cancelling pays both sides out
+    pub remaining: Amount,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub cancelled: bool,
+    pub created_ledger: u32,
+    pub created_at: String,
+}
+
+/// Counts and volumes across every indexed contract. Volumes are per
+/// token contract.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct Stats {
+    /// Latest ledger the indexer had seen when it last saved its cursor
+    pub indexed_ledger: Option<u32>,
+    pub events: u64,
+    pub streams: StreamStats,
+    pub escrows: EscrowStats,
+    pub subscriptions: SubscriptionStats,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct StreamStats {
+    pub created: u64,
+    pub cancelled: u64,
+    pub withdrawals: u64,
+    /// Deposits and top-ups
+    pub deposited: BTreeMap<String, Amount>,
+    pub withdrawn: BTreeMap<String, Amount>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct EscrowStats {
+    pub created: u64,
+    pub released: u64,
+    pub refunded: u64,
+    pub disputed: u64,
+    /// Amounts escrowed at creation
+    pub escrowed: BTreeMap<String, Amount>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct SubscriptionStats {
+    pub plans: u64,
+    pub subscribed: u64,
+    pub charges: u64,
+    pub failed_charges: u64,
+    pub lapsed: u64,
+    pub charged: BTreeMap<String, Amount>,
+}
+
+/// A stage of a contract upgrade
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Upgrade {
+    pub contract_id: String,
+    /// `proposed`, `cancelled`, `executed` or `rolled_back`
+    pub stage: String,
+    pub version: u32,
+    pub wasm_hash: String,
+    /// When a proposed upgrade may execute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta: Option<u64>,
+    /// Version a rollback left
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_version: Option<u32>,
+    pub ledger: u32,
+    pub ledger_closed_at: String,
+    pub tx_hash: String,
+}
+
+#[derive(Deserialize)]
+struct StreamCreated {
+    stream_id: u64,
+    sender: String,
+    recipient: String,
+    token: String,
+    deposit: i128,
+    start_time: u64,
+    end_time: u64,
+}
+
+/// The fields a stream's later events share, as far as they matter here
+#[derive(Deserialize)]
+struct StreamChange {
+    stream_id: u64,
+    amount: Option<i128>,
+    deposit: Option<i128>,
+}
+
+#[derive(Deserialize)]
+struct EscrowCreated {
+    token: String,
+    amount: i128,
+}
+
+#[derive(Deserialize)]
+struct PlanCreated {
+    plan_id: u64,
+    token: String,
+}
+
+#[derive(Deserialize)]
+struct Subscribed {
+    subscription_id: u64,
+    plan_id: u64,
+}
+
+#[derive(Deserialize)]
+struct Charged {
+    subscription_id: u64,
+    amount: i128,
+}
+
+#[derive(Deserialize)]
+struct UpgradeStage {
+    version: u32,
+    wasm_hash: String,
+    eta: Option<u64>,
+    from_version: Option<u32>,
+}
+
+pub struct Index {
+    conn: Connection,
+}
+
+impl Index {
+    /// Open the indexer's database for reading, alongside the running
+    /// indexer
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        conn.busy_timeout(Duration::from_secs(5))?;
+        Ok(Index { conn })
+    }
+
+    pub fn with_connection(conn: Connection) -> Self {
+        Index { conn }
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Streams `address` sends or receives, oldest first
+    pub fn streams(&self, address: &str) -> rusqlite::Result<Vec<Stream>> {
+        let mut statement = self.conn.prepare(
+            "SELECT contract_id, ledger, ledger_closed_at, decoded FROM events
+             WHERE kind = 'streaming.stream_created'
+                AND ?1 IN (json_extract(decoded, '$.streaming.sender'),
+                    json_extract(decoded, '$.streaming.recipient'))
+             ORDER BY id",
+        )?;
+        let rows = statement.query_map([address], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, u32>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+        let mut streams = Vec::new();
+        let mut positions = HashMap::new();
+        for row in rows {
+            let (contract_id, ledger, ledger_closed_at, decoded) = row?;
+            let Some(created) = fields::<StreamCreated>(&decoded) else {
+                continue;
+            };
+            positions.insert((contract_id.clone(), created.stream_id), streams.len());
+            streams.push(Stream {
+                contract_id,
+                stream_id: created.stream_id,
+                sender: created.sender,
+                recipient: created.recipient,
+                token: created.token,
+                deposit: Amount(created.deposit),
+                withdrawn: Amount(0),
+                remaining: Amount(created.deposit),
+                start_time: created.start_time,
+                end_time: created.end_time,
+                cancelled: false,
+                created_ledger: ledger,
+                created_at: ledger_closed_at,
+            });
+        }
+        if streams.is_empty() {
+            return Ok(streams);
+        }
+
+        let ids: Vec<_> = streams.iter().map(|stream| stream.stream_id).collect();
+        for (contract_id, kind, decoded) in self.events(
+            &[
+                "streaming.withdrawn",
+                "streaming.topped_up",
+                "streaming.stream_cancelled",
+            ],
+            Some(&ids),
+        )? {
+            let Some(change) = fields::<StreamChange>(&decoded) else {
+                continue;
+            };
+            let Some(&position) = positions.get(&(contract_id, change.stream_id)) else {
+                continue;
+            };
+            let stream = &mut streams[position];
+            match kind.as_str() {
+                "streaming.withdrawn" => stream.withdrawn.0 += change.amount.unwrap_or_default(),
+                "streaming.topped_up" => {
+                    stream.deposit.0 = change.deposit.unwrap_or(stream.deposit.0)
+                }
+                _ => stream.cancelled = true,
+            }
+        }
+        for stream in &mut streams {
+            stream.remaining = if stream.cancelled {
+                Amount(0)
+            } else {
+                Amount((stream.deposit.0 - stream.withdrawn.0).max(0))
+            };
+        }
+        Ok(streams)
+    }
+
+    /// Streams paying `address` that still have something to pay it
+    pub fn claimables(&self, address: &str) -> rusqlite::Result<Vec<Stream>> {
+        let mut streams = self.streams(address)?;
+        streams.retain(|stream| stream.recipient == address && stream.remaining.0 > 0);
+        Ok(streams)
+    }
+
+    pub fn stats(&self) -> rusqlite::Result<Stats> {
+        let mut stats = Stats {
+            indexed_ledger: self
+                .conn
+                .query_row("SELECT max(ledger) FROM cursors", [], |row| row.get(0))
+                .optional()?
+                .flatten(),
+            events: self
+                .conn
+                .query_row("SELECT count(*) FROM events", [], |row| row.get(0))?,
+            ..Stats::default()
+        };
+
+        let mut statement = self
+            .conn
+            .prepare("SELECT kind, count(*) FROM events WHERE kind IS NOT NULL GROUP BY kind")?;
+        let counts = statement.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?))
+        })?;
+        for count in counts {
+            let (kind, count) = count?;
+            let counter = match kind.as_str() {
+                "streaming.stream_created" => &mut stats.streams.created,
+                "streaming.stream_cancelled" => &mut stats.streams.cancelled,
+                "streaming.withdrawn" => &mut stats.streams.withdrawals,
+                "escrow.escrow_created" => &mut stats.escrows.created,
+                "escrow.released" => &mut stats.escrows.released,
+                "escrow.refunded" => &mut stats.escrows.refunded,
+                "escrow.dispute_raised" => &mut stats.escrows.disputed,
+                "subscriptions.plan_created" => &mut stats.subscriptions.plans,
+                "subscriptions.subscribed" => &mut stats.subscriptions.subscribed,
+                "subscriptions.charged" => &mut stats.subscriptions.charges,
+                "subscriptions.charge_failed" => &mut stats.subscriptions.failed_charges,
+                "subscriptions.lapsed" => &mut stats.subscriptions.lapsed,
+                _ => continue,
+            };
+            *counter = count;
+        }
+
+        // Withdrawals and top-ups name the stream, not the token
+        let mut stream_tokens = HashMap::new();
+        // Charges name the subscription, which names the plan
+        let mut plan_tokens = HashMap::new();
+        let mut subscription_plans = HashMap::new();
+        for (contract_id, kind, decoded) in self.events(
+            &[
+                "streaming.stream_created",
+                "streaming.topped_up",
+                "streaming.withdrawn",
+                "escrow.escrow_created",
+                "subscriptions.plan_created",
+                "subscriptions.subscribed",
+                "subscriptions.charged",
+            ],
+            None,
+        )? {
+            match kind.as_str() {
+                "streaming.stream_created" => {
+                    let Some(created) = fields::<StreamCreated>(&decoded) else {
+                        continue;
+                    };
+                    add(
+                        &mut stats.streams.deposited,
+                        &created.token,
+                        created.deposit,
+                    );
+                    stream_tokens.insert((contract_id, created.stream_id), created.token);
+                }
+                "streaming.topped_up" | "streaming.withdrawn" => {
+                    let Some(change) = fields::<StreamChange>(&decoded) else {
+                        continue;
+                    };
+                    let Some(token) = stream_tokens.get(&(contract_id, change.stream_id)) else {
+                        continue;
+                    };
+                    let volume = if kind == "streaming.withdrawn" {
+                        &mut stats.streams.withdrawn
+                    } else {
+                        &mut stats.streams.deposited
+                    };
+                    add(volume, token, change.amount.unwrap_or_default());
+                }
+                "escrow.escrow_created" => {
+                    if let Some(created) = fields::<EscrowCreated>(&decoded) {
+                        add(&mut stats.escrows.escrowed, &created.token, created.amount);
+                    }
+                }
+                "subscriptions.plan_created" => {
+                    if let Some(plan) = fields::<PlanCreated>(&decoded) {
+                        plan_tokens.insert((contract_id, plan.plan_id), plan.token);
+                    }
+                }
+                "subscriptions.subscribed" => {
+                    if let Some(subscribed) = fields::<Subscribed>(&decoded) {
+                        subscription_plans.insert(
+                            (contract_id, subscribed.subscription_id),
+                            subscribed.plan_id,
+                        );
+                    }
+                }
+                _ => {
+                    let Some(charged) = fields::<Charged>(&decoded) else {
+                        continue;
+                    };
+                    let token = subscription_plans
+                        .get(&(contract_id.clone(), charged.subscription_id))
+                        .and_then(|plan_id| plan_tokens.get(&(contract_id, *plan_id)));
+                    if let Some(token) = token {
+                        add(&mut stats.subscriptions.charged, token, charged.amount);
+                    }
+                }
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Upgrade stages on `contract`, or on every contract, newest first
+    pub fn upgrades(&self, contract: Option<&str>) -> rusqlite::Result<Vec<Upgrade>> {
+        let mut statement = self.conn.prepare(
+            "SELECT contract_id, kind, ledger, ledger_closed_at, tx_hash, decoded FROM events
+             WHERE kind LIKE 'upgrade.%' AND (?1 IS NULL OR contract_id = ?1)
+             ORDER BY id DESC",
+        )?;
+        let rows = statement.query_map([contract], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, u32>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+            ))
+        })?;
+        let mut upgrades = Vec::new();
+        for row in rows {
+            let (contract_id, kind, ledger, ledger_closed_at, tx_hash, decoded) = row?;
+            let Some(stage) = fields::<UpgradeStage>(&decoded) else {
+                continue;
+            };
+            upgrades.push(Upgrade {
+                contract_id,
+                stage: kind
+                    .strip_prefix("upgrade.upgrade_")
+                    .unwrap_or(&kind)
+                    .to_string(),
+                version: stage.version,
+                wasm_hash: stage.wasm_hash,
+                eta: stage.eta,
+                from_version: stage.from_version,
+                ledger,
+                ledger_closed_at,
+                tx_hash,
+            });
+        }
+        Ok(upgrades)
+    }
+
+    /// `(contract_id, kind, decoded)` of events of `kinds` in the order
+    /// they happened, only those about `stream_ids` if given
+    fn events(
+        &self,
+        kinds: &[&str],
+        stream_ids: Option<&[u64]>,
+    ) -> rusqlite::Result<Vec<(String, String, String)>> {
+        let mut statement = self.conn.prepare(
+            "SELECT contract_id, kind, decoded FROM events
+             WHERE kind IN (SELECT value FROM json_each(?1))
+                AND (?2 IS NULL OR json_extract(decoded, '$.streaming.stream_id')
+                    IN (SELECT value FROM json_each(?2)))
+             ORDER BY id",
+        )?;
+        let kinds = serde_json::to_string(kinds).expect("strings serialize");
+        let stream_ids = stream_ids.map(|ids| serde_json::to_string(ids).expect("IDs serialize"));
+        let rows = statement.query_map(params![kinds, stream_ids], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?;
+        rows.collect()
+    }
+}
+
+/// The fields of a stored decoded event, which sit under its schema's
+/// name
+fn fields<T: DeserializeOwned>(decoded: &str) -> Option<T> {
+    serde_json::from_str::<HashMap<String, T>>(decoded)
+        .ok()?
+        .into_values()
+        .next()
+}
+
+fn add(volumes: &mut BTreeMap<String, Amount>, token: &str, amount: i128) {
+    volumes.entry(token.to_string()).or_default().0 += amount;
+}
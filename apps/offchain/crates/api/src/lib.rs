@@ -0,0 +1,134 @@
+//! Read-only HTTP API over the indexer's database.
+//!
+//! A page showing someone's streams would otherwise simulate a view call
+//! per stream. The API answers from the events the indexer has already
+//! stored instead, with one request per page:
+//!
+//! - `GET /streams?address=` streams an account or contract sends or
+//!   receives
+//! - `GET /claimables?address=` streams still paying it
+//! - `GET /stats` counts and volumes across the indexed contracts
+//! - `GET /upgrades` upgrade history, optionally `?contract=`
+//!
+//! Responses are JSON, and amounts are strings. Errors are
+//! `{"error": "..."}` with a 4xx or 5xx status.
+
+pub mod config;
+pub mod index;
+
+pub use config::Config;
+pub use index::{Amount, Index, Stats, Stream, Upgrade};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// A JSON response
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn ok(body: impl Serialize) -> Self {
+        Response {
+            status: 200,
+            body: serde_json::to_value(body).expect("responses serialize"),
+        }
+    }
+
+    fn error(status: u16, message: impl std::fmt::Display) -> Self {
+        Response {
+            status,
+            body: json!({ "error": message.to_string() }),
+        }
+    }
+}
+
+/// Answer `method` on `url`, a path with an optional query string
+pub fn handle(index: &Index, method: &str, url: &str) -> Response {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let route = path.trim_end_matches('/');
+    if !["/streams", "/claimables", "/stats", "/upgrades"].contains(&route) {
+        return Response::error(404, format!("no such endpoint `{path}`"));
+    }
+    if method != "GET" {
+        return Response::error(405, "only GET is supported");
+    }
+    let param = |name: &str| {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| address(value))
+            .transpose()
+    };
+
+    let result = match route {
+        "/streams" | "/claimables" => {
+            let address = match param("address") {
+                Ok(Some(address)) => address,
+                Ok(None) => return Response::error(400, "`address` is required"),
+                Err(response) => return response,
+            };
+            if route == "/streams" {
+                index
+                    .streams(address)
+                    .map(|streams| Response::ok(json!({ "streams": streams })))
+            } else {
+                index
+                    .claimables(address)
+                    .map(|claimables| Response::ok(json!({ "claimables": claimables })))
+            }
+        }
+        "/stats" => index.stats().map(Response::ok),
+        _ => match param("contract") {
+            Ok(contract) => index
+                .upgrades(contract)
+                .map(|upgrades| Response::ok(json!({ "upgrades": upgrades }))),
+            Err(response) => return response,
+        },
+    };
+    result.unwrap_or_else(|err| {
+        eprintln!("GET {url} failed: {err}");
+        Response::error(500, "the index could not be read")
+    })
+}
+
+/// A `G...` account or `C...` contract address
+fn address(value: &str) -> Result<&str, Response> {
+    match stellar_strkey::Strkey::from_string(value) {
+        Ok(stellar_strkey::Strkey::PublicKeyEd25519(_) | stellar_strkey::Strkey::Contract(_)) => {
+            Ok(value)
+        }
+        _ => Err(Response::error(
+            400,
+            format!("`{value}` is not an account or contract address"),
+        )),
+    }
+}
+
+/// Answer requests on `server` until it is shut down
+pub fn serve(server: &tiny_http::Server, index: &Index, config: &Config) {
+    let json = tiny_http::Header::from_bytes("Content-Type", "application/json")
+        .expect("the header is valid");
+    let origin = config.allow_origin.as_ref().map(|origin| {
+        tiny_http::Header::from_bytes("Access-Control-Allow-Origin", origin.as_bytes())
+            .expect("origins are checked when configured")
+    });
+    for request in server.incoming_requests() {
+        let response = handle(index, request.method().as_str(), request.url());
+        let mut reply = tiny_http::Response::from_string(response.body.to_string())
+            .with_status_code(response.status)
+            .with_header(json.clone());
+        if let Some(origin) = &origin {
+            reply = reply.with_header(origin.clone());
+        }
+        if let Err(err) = request.respond(reply) {
+            eprintln!("responding failed: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
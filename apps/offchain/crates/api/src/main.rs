@@ -0,0 +1,22 @@
+use lumenpulse_api::{Config, Index};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("lumenpulse-api: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::from_env()?;
+    let index = Index::open(&config.database)?;
+    let server = tiny_http::Server::http(&config.listen)
+        .map_err(|err| format!("cannot listen on {}: {err}", config.listen))?;
+    eprintln!("serving {} on {}", config.database, config.listen);
+    lumenpulse_api::serve(&server, &index, &config);
+    Ok(())
+}
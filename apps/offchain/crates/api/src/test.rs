@@ -0,0 +1,269 @@
+use crate::{handle, Config, Index};
+use rusqlite::{params, Connection};
+use serde_json::{json, Value};
+
+const STREAMING: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+const SUBSCRIPTIONS: &str = "CAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC526";
+const TOKEN: &str = "CABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAFNSZ";
+const ALICE: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+const BOB: &str = "GAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQDZ7H";
+const CAROL: &str = "GABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEJXA";
+
+/// The indexer's tables holding `events` as `(contract, kind, fields)`,
+/// `fields` being the decoded event without its `kind`. Amounts are
+/// spliced into the stored text as they are, so they may pass `u64`.
+fn index(events: &[(&str, &str, Value)]) -> Index {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE events (id TEXT PRIMARY KEY, ledger INTEGER, ledger_closed_at TEXT,
+            contract_id TEXT, tx_hash TEXT, kind TEXT, decoded TEXT);
+         CREATE TABLE cursors (name TEXT PRIMARY KEY, cursor TEXT, ledger INTEGER);
+         INSERT INTO cursors VALUES ('events', '0099', 120);",
+    )
+    .unwrap();
+    for (i, (contract, kind, fields)) in events.iter().enumerate() {
+        let (schema, event) = kind.split_once('.').unwrap();
+        let mut decoded = json!({ "kind": event });
+        decoded
+            .as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        let decoded = json!({ schema: decoded })
+            .to_string()
+            .replace("\"$BIG\"", "170141183460469231731687303715884105727");
+        conn.execute(
+            "INSERT INTO events VALUES (?1, ?2, '2026-01-01T00:00:00Z', ?3, ?4, ?5, ?6)",
+            params![
+                format!("{i:04}"),
+                i + 100,
+                contract,
+                format!("tx{i}"),
+                kind,
+                decoded
+            ],
+        )
+        .unwrap();
+    }
+    Index::with_connection(conn)
+}
+
+fn stream_created(stream_id: u64, sender: &str, recipient: &str, deposit: Value) -> Value {
+    json!({
+        "sender": sender,
+        "recipient": recipient,
+        "stream_id": stream_id,
+        "token": TOKEN,
+        "deposit": deposit,
+        "start_time": 1_000,
+        "end_time": 2_000,
+    })
+}
+
+fn streams() -> Index {
+    index(&[
+        (
+            STREAMING,
+            "streaming.stream_created",
+            stream_created(0, ALICE, BOB, json!(1_000)),
+        ),
+        (
+            STREAMING,
+            "streaming.stream_created",
+            stream_created(1, BOB, CAROL, json!(500)),
+        ),
+        (
+            STREAMING,
+            "streaming.stream_created",
+            stream_created(2, CAROL, BOB, json!(800)),
+        ),
+        (
+            STREAMING,
+            "streaming.withdrawn",
+            json!({ "recipient": BOB, "stream_id": 0, "amount": 300 }),
+        ),
+        (
+            STREAMING,
+            "streaming.topped_up",
+            json!({ "sender": ALICE, "stream_id": 0, "amount": 200, "deposit": 1_200 }),
+        ),
+        (
+            STREAMING,
+            "streaming.withdrawn",
+            json!({ "recipient": BOB, "stream_id": 2, "amount": 800 }),
+        ),
+        (
+            STREAMING,
+            "streaming.stream_cancelled",
+            json!({ "sender": BOB, "stream_id": 1, "recipient_amount": 100, "sender_amount": 400 }),
+        ),
+    ])
+}
+
+fn get(index: &Index, url: &str) -> (u16, Value) {
+    let response = handle(index, "GET", url);
+    (response.status, response.body)
+}
+
+#[test]
+fn streams_fold_their_events() {
+    let index = streams();
+    let (status, body) = get(&index, &format!("/streams?address={BOB}"));
+    assert_eq!(status, 200);
+    let streams = body["streams"].as_array().unwrap();
+    assert_eq!(streams.len(), 3);
+    assert_eq!(
+        streams[0],
+        json!({
+            "contract_id": STREAMING,
+            "stream_id": 0,
+            "sender": ALICE,
+            "recipient": BOB,
+            "token": TOKEN,
+            "deposit": "1200",
+            "withdrawn": "300",
+            "remaining": "900",
+            "start_time": 1_000,
+            "end_time": 2_000,
+            "cancelled": false,
+            "created_ledger": 100,
+            "created_at": "2026-01-01T00:00:00Z",
+        })
+    );
+    assert_eq!(streams[1]["cancelled"], true);
+    assert_eq!(streams[1]["remaining"], "0");
+    assert_eq!(streams[2]["remaining"], "0");
+
+    // Only streams Bob receives and can still be paid on are claimable
+    let (_, body) = get(&index, &format!("/claimables?address={BOB}"));
+    let claimables: Vec<_> = body["claimables"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|stream| stream["stream_id"].clone())
+        .collect();
+    assert_eq!(claimables, [json!(0)]);
+    let (_, body) = get(&index, &format!("/streams?address={ALICE}"));
+    assert_eq!(body["streams"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn stats_count_events_and_sum_volumes_exactly() {
+    let index = index(&[
+        (
+            STREAMING,
+            "streaming.stream_created",
+            stream_created(0, ALICE, BOB, json!("$BIG")),
+        ),
+        (
+            STREAMING,
+            "streaming.withdrawn",
+            json!({ "recipient": BOB, "stream_id": 0, "amount": 300 }),
+        ),
+        (
+            SUBSCRIPTIONS,
+            "subscriptions.plan_created",
+            json!({ "plan_id": 4, "merchant": CAROL, "token": TOKEN, "price": 50, "period": 60 }),
+        ),
+        (
+            SUBSCRIPTIONS,
+            "subscriptions.subscribed",
+            json!({ "subscription_id": 9, "subscriber": ALICE, "plan_id": 4 }),
+        ),
+        (
+            SUBSCRIPTIONS,
+            "subscriptions.charged",
+            json!({ "subscription_id": 9, "amount": 50, "next_charge": 60 }),
+        ),
+        (
+            SUBSCRIPTIONS,
+            "subscriptions.charged",
+            json!({ "subscription_id": 9, "amount": 50, "next_charge": 120 }),
+        ),
+        (
+            SUBSCRIPTIONS,
+            "subscriptions.charge_failed",
+            json!({ "subscription_id": 9, "amount": 50, "failures": 1, "retry_at": 180 }),
+        ),
+    ]);
+    let (status, body) = get(&index, "/stats");
+    assert_eq!(status, 200);
+    assert_eq!(body["indexed_ledger"], 120);
+    assert_eq!(body["events"], 7);
+    assert_eq!(body["streams"]["created"], 1);
+    assert_eq!(body["streams"]["withdrawals"], 1);
+    assert_eq!(
+        body["streams"]["deposited"][TOKEN],
+        "170141183460469231731687303715884105727"
+    );
+    assert_eq!(body["streams"]["withdrawn"][TOKEN], "300");
+    assert_eq!(body["subscriptions"]["plans"], 1);
+    assert_eq!(body["subscriptions"]["charges"], 2);
+    assert_eq!(body["subscriptions"]["failed_charges"], 1);
+    assert_eq!(body["subscriptions"]["charged"][TOKEN], "100");
+    assert_eq!(body["escrows"]["created"], 0);
+}
+
+#[test]
+fn upgrades_are_listed_newest_first() {
+    let hash = "ab".repeat(32);
+    let index = index(&[
+        (
+            STREAMING,
+            "upgrade.upgrade_proposed",
+            json!({ "version": 2, "wasm_hash": hash, "eta": 5_000 }),
+        ),
+        (
+            SUBSCRIPTIONS,
+            "upgrade.upgrade_executed",
+            json!({ "version": 3, "wasm_hash": hash }),
+        ),
+        (
+            STREAMING,
+            "upgrade.upgrade_executed",
+            json!({ "version": 2, "wasm_hash": hash }),
+        ),
+    ]);
+    let (_, body) = get(&index, "/upgrades");
+    let upgrades = body["upgrades"].as_array().unwrap();
+    assert_eq!(upgrades.len(), 3);
+    assert_eq!(upgrades[0]["contract_id"], STREAMING);
+    assert_eq!(upgrades[0]["stage"], "executed");
+    assert!(upgrades[0].get("eta").is_none());
+
+    let (_, body) = get(&index, &format!("/upgrades?contract={STREAMING}"));
+    assert_eq!(
+        body["upgrades"][1],
+        json!({
+            "contract_id": STREAMING,
+            "stage": "proposed",
+            "version": 2,
+            "wasm_hash": hash,
+            "eta": 5_000,
+            "ledger": 100,
+            "ledger_closed_at": "2026-01-01T00:00:00Z",
+            "tx_hash": "tx0",
+        })
+    );
+}
+
+#[test]
+fn bad_requests_are_refused() {
+    let index = streams();
+    assert_eq!(get(&index, "/streams").0, 400);
+    assert_eq!(get(&index, "/claimables?address=alice").0, 400);
+    assert_eq!(get(&index, "/upgrades?contract=CABC").0, 400);
+    assert_eq!(get(&index, "/accounts").0, 404);
+    assert_eq!(handle(&index, "POST", "/stats").status, 405);
+    assert_eq!(get(&index, "/stats/").0, 200);
+
+    // The index table is missing
+    let empty = Index::with_connection(Connection::open_in_memory().unwrap());
+    let (status, body) = get(&empty, "/stats");
+    assert_eq!(status, 500);
+    assert_eq!(body["error"], "the index could not be read");
+
+    let config = Config::from_vars(|_| None).unwrap();
+    assert_eq!(config.allow_origin.as_deref(), Some("*"));
+    let config = Config::from_vars(|name| (name == "API_ALLOW_ORIGIN").then(String::new));
+    assert_eq!(config.unwrap().allow_origin, None);
+}
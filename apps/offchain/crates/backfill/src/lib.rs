@@ -0,0 +1,201 @@
+//! Backfill of contract events from before the indexer started.
+//!
+//! A new indexer starts from the latest ledger, and the public RPCs keep
+//! only a few days of events. The backfill pages through `getEvents` over
+//! a past range of ledgers for the indexer's contracts and writes the
+//! events into the indexer's own database, decoded the same way. Point it
+//! at an RPC that retains the range, such as one run with a long history
+//! retention window, to go further back than the public ones.
+//!
+//! Everything the services know about a stream, escrow or subscription is
+//! folded from its events, so backfilling the events is all it takes to
+//! re-derive that state. Progress is checkpointed as a named cursor in
+//! the indexer's `cursors` table after every page, and events are inserted
+//! by ID, so an interrupted backfill resumes where it stopped and running
+//! one again stores nothing twice.
+
+use lumenpulse_events::Contract;
+use lumenpulse_indexer::{decode_events, Config, Cursor, Store};
+use lumenpulse_rpc::{Client, EventsPage, EventsRequest, Start};
+use std::{collections::BTreeMap, thread, time::Duration};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(#[from] lumenpulse_rpc::Error),
+    #[error("database error: {0}")]
+    Store(#[from] rusqlite::Error),
+    #[error("ledger {start} is older than the RPC retains, which is from ledger {oldest}; use an RPC that keeps more history")]
+    Retention { start: u32, oldest: u32 },
+    #[error("ledgers {from} to {to} are not a range the RPC has, which ends at ledger {latest}")]
+    Range { from: u32, to: u32, latest: u32 },
+}
+
+/// Where past events come from, an RPC in production
+pub trait History {
+    /// Oldest and latest ledgers events can be fetched from
+    fn ledgers(&self) -> Result<(u32, u32), lumenpulse_rpc::Error>;
+    fn events(&self, request: &EventsRequest) -> Result<EventsPage, lumenpulse_rpc::Error>;
+}
+
+impl History for Client {
+    fn ledgers(&self) -> Result<(u32, u32), lumenpulse_rpc::Error> {
+        let health = self.get_health()?;
+        Ok((health.oldest_ledger, health.latest_ledger))
+    }
+
+    fn events(&self, request: &EventsRequest) -> Result<EventsPage, lumenpulse_rpc::Error> {
+        self.get_events(request)
+    }
+}
+
+/// Name a backfill starting at `from` checkpoints under. It does not
+/// name the end, so a backfill run again with a later end carries on
+/// from the checkpoint.
+pub fn checkpoint(from: u32) -> String {
+    format!("backfill:{from}")
+}
+
+/// Ledger an event ID or cursor points into. Both start with a TOID,
+/// which holds the ledger in its top 32 bits.
+pub fn cursor_ledger(cursor: &str) -> Option<u32> {
+    let toid: u64 = cursor.split('-').next()?.parse().ok()?;
+    u32::try_from(toid >> 32).ok()
+}
+
+/// What one page of backfilling did
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Step {
+    /// Events stored that were not stored before
+    pub stored: usize,
+    /// Whether the page reached past the end of the range
+    pub done: bool,
+    /// Whether the page came back short, so the RPC has nothing more yet
+    pub caught_up: bool,
+}
+
+pub struct Backfill<H> {
+    history: H,
+    store: Store,
+    contracts: BTreeMap<String, Contract>,
+    page_limit: u32,
+    from: u32,
+    to: u32,
+}
+
+impl<H: History> Backfill<H> {
+    /// Backfill the indexer's contracts from ledger `from` to `to`, both
+    /// included, or to the latest ledger. Starts from the checkpoint if a
+    /// backfill from `from` ran before.
+    pub fn new(
+        history: H,
+        store: Store,
+        config: &Config,
+        from: u32,
+        to: Option<u32>,
+    ) -> Result<Self, Error> {
+        let (oldest, latest) = history.ledgers()?;
+        let to = to.unwrap_or(latest);
+        if from > to || to > latest {
+            return Err(Error::Range { from, to, latest });
+        }
+        let start = match store.cursor(&checkpoint(from))? {
+            Some(cursor) => cursor_ledger(&cursor.cursor).unwrap_or(from),
+            None => from,
+        };
+        if start < oldest {
+            return Err(Error::Retention { start, oldest });
+        }
+        Ok(Backfill {
+            history,
+            store,
+            contracts: config.contracts.clone(),
+            page_limit: config.page_limit,
+            from,
+            to,
+        })
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    pub fn into_store(self) -> Store {
+        self.store
+    }
+
+    /// Last ledger the backfill covers
+    pub fn to(&self) -> u32 {
+        self.to
+    }
+
+    /// Fetch and store the next page of events in the range
+    pub fn step(&mut self) -> Result<Step, Error> {
+        let name = checkpoint(self.from);
+        let start = match self.store.cursor(&name)? {
+            Some(cursor) => Start::Cursor(cursor.cursor),
+            None => Start::Ledger(self.from),
+        };
+        let page = self.history.events(&EventsRequest {
+            start,
+            contract_ids: self.contracts.keys().cloned().collect(),
+            limit: self.page_limit,
+        })?;
+
+        let caught_up = page.events.len() < self.page_limit as usize;
+        let past_end = |ledger: u32| ledger > self.to;
+        let done = page.events.iter().any(|event| past_end(event.ledger))
+            || cursor_ledger(&page.cursor).is_some_and(past_end);
+        let events: Vec<_> = page
+            .events
+            .into_iter()
+            .filter(|event| !past_end(event.ledger))
+            .collect();
+        // The RPC's cursor may point past the end of the range, and a
+        // later run with a later end must not skip what lies in between,
+        // so such a page checkpoints at the last event it kept
+        let cursor = match (done, events.last()) {
+            (false, _) => Some(page.cursor),
+            (true, Some(last)) => Some(last.id.clone()),
+            (true, None) => None,
+        };
+        let stored = match cursor {
+            Some(cursor) => self.store.save_page(
+                &name,
+                &decode_events(&self.contracts, events),
+                &Cursor {
+                    cursor,
+                    ledger: page.latest_ledger,
+                },
+            )?,
+            None => 0,
+        };
+        Ok(Step {
+            stored,
+            done,
+            caught_up,
+        })
+    }
+
+    /// Backfill to the end of the range, waiting `poll_interval` whenever
+    /// the RPC has nothing more yet. Returns how many events were new.
+    pub fn run(&mut self, poll_interval: Duration) -> Result<usize, Error> {
+        let mut stored = 0;
+        loop {
+            let step = self.step()?;
+            stored += step.stored;
+            if step.stored > 0 {
+                eprintln!("backfilled {} events", step.stored);
+            }
+            if step.done {
+                return Ok(stored);
+            }
+            if step.caught_up {
+                thread::sleep(poll_interval);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
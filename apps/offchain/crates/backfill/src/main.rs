@@ -0,0 +1,48 @@
+use clap::Parser;
+use lumenpulse_backfill::Backfill;
+use lumenpulse_indexer::{Config, Store};
+use lumenpulse_rpc::Client;
+use std::process::ExitCode;
+
+/// Store a past range of ledgers' events in the indexer's database. The
+/// contracts and database are the indexer's, from its environment.
+#[derive(Debug, Parser)]
+#[command(name = "lumenpulse-backfill")]
+struct Cli {
+    /// First ledger to backfill
+    #[arg(long)]
+    from: u32,
+    /// Last ledger to backfill, by default the latest
+    #[arg(long)]
+    to: Option<u32>,
+    /// RPC that retains the range, instead of `INDEXER_RPC_URL`
+    #[arg(long, env = "BACKFILL_RPC_URL")]
+    rpc_url: Option<String>,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("lumenpulse-backfill: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::from_env()?;
+    let rpc_url = cli.rpc_url.as_deref().unwrap_or(&config.rpc_url);
+    let store = Store::open(&config.database)?;
+    let mut backfill = Backfill::new(Client::new(rpc_url), store, &config, cli.from, cli.to)?;
+    eprintln!(
+        "backfilling ledgers {} to {} of {} contracts from {rpc_url} into {}",
+        cli.from,
+        backfill.to(),
+        config.contracts.len(),
+        config.database
+    );
+    let stored = backfill.run(config.poll_interval)?;
+    eprintln!("done, {stored} events were new");
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use crate::{checkpoint, cursor_ledger, Backfill, Error, History, Step};
+use lumenpulse_indexer::{Config, Store};
+use lumenpulse_rpc::{EventsPage, EventsRequest, RpcEvent, Start};
+use std::{cell::RefCell, collections::VecDeque};
+use stellar_xdr::curr::{ContractId, Hash, ScAddress, ScMap, ScMapEntry, ScSymbol, ScVal};
+
+const STREAMING: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+
+/// Serves canned pages over ledgers 100 to 2,000 and records what it was
+/// asked for
+#[derive(Default)]
+struct FakeHistory {
+    pages: RefCell<VecDeque<EventsPage>>,
+    requests: RefCell<Vec<EventsRequest>>,
+}
+
+impl History for &FakeHistory {
+    fn ledgers(&self) -> Result<(u32, u32), lumenpulse_rpc::Error> {
+        Ok((100, 2_000))
+    }
+
+    fn events(&self, request: &EventsRequest) -> Result<EventsPage, lumenpulse_rpc::Error> {
+        self.requests.borrow_mut().push(request.clone());
+        self.pages
+            .borrow_mut()
+            .pop_front()
+            .ok_or_else(|| lumenpulse_rpc::Error::Transport("no more pages".to_string()))
+    }
+}
+
+fn symbol(name: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(name.try_into().unwrap()))
+}
+
+/// The ID the RPC gives the first event of the first transaction in
+/// `ledger`
+fn id(ledger: u32) -> String {
+    format!("{:019}-{:010}", (u64::from(ledger) << 32) | (1 << 12), 0)
+}
+
+fn withdrawn(ledger: u32) -> RpcEvent {
+    let recipient = ScVal::Address(ScAddress::Contract(ContractId(Hash([7; 32]))));
+    let amount = ScMapEntry {
+        key: symbol("amount"),
+        val: 250i128.into(),
+    };
+    RpcEvent {
+        id: id(ledger),
+        ledger,
+        ledger_closed_at: "2026-01-05T10:00:00Z".to_string(),
+        contract_id: STREAMING.to_string(),
+        tx_hash: "ab01".to_string(),
+        topics: vec![symbol("withdrawn_event"), recipient, 3u64.into()],
+        value: ScVal::Map(Some(ScMap(vec![amount].try_into().unwrap()))),
+    }
+}
+
+fn page(events: Vec<RpcEvent>, cursor: String) -> EventsPage {
+    EventsPage {
+        events,
+        cursor,
+        latest_ledger: 2_000,
+    }
+}
+
+fn config() -> Config {
+    Config::from_vars(|name| match name {
+        "INDEXER_CONTRACTS" => Some(format!("streaming={STREAMING}")),
+        "INDEXER_PAGE_LIMIT" => Some("2".to_string()),
+        _ => None,
+    })
+    .unwrap()
+}
+
+fn stored(store: &Store) -> Vec<u32> {
+    let mut statement = store
+        .connection()
+        .prepare("SELECT ledger FROM events ORDER BY id")
+        .unwrap();
+    let ledgers = statement.query_map([], |row| row.get(0)).unwrap();
+    ledgers.map(Result::unwrap).collect()
+}
+
+#[test]
+fn backfills_the_range_and_checkpoints_inside_it() {
+    let history = FakeHistory::default();
+    history.pages.borrow_mut().extend([
+        page(vec![withdrawn(200), withdrawn(300)], id(300)),
+        page(vec![withdrawn(400), withdrawn(600)], id(600)),
+    ]);
+    let store = Store::in_memory().unwrap();
+    let mut backfill = Backfill::new(&history, store, &config(), 150, Some(500)).unwrap();
+
+    assert_eq!(
+        backfill.step().unwrap(),
+        Step {
+            stored: 2,
+            done: false,
+            caught_up: false,
+        }
+    );
+    assert_eq!(
+        backfill.step().unwrap(),
+        Step {
+            stored: 1,
+            done: true,
+            caught_up: false,
+        }
+    );
+    assert_eq!(stored(backfill.store()), [200, 300, 400]);
+    let requests = history.requests.borrow();
+    assert_eq!(requests[0].start, Start::Ledger(150));
+    assert_eq!(requests[1].start, Start::Cursor(id(300)));
+    // The event past the end is left for a later run to pick up
+    let cursor = backfill.store().cursor(&checkpoint(150)).unwrap().unwrap();
+    assert_eq!(cursor.cursor, id(400));
+    assert_eq!(
+        backfill.store().cursor(lumenpulse_indexer::CURSOR).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn a_later_run_resumes_and_stores_nothing_twice() {
+    let history = FakeHistory::default();
+    history.pages.borrow_mut().extend([
+        page(vec![withdrawn(200), withdrawn(300)], id(300)),
+        // The RPC found nothing more and moved its cursor past the end
+        page(vec![], id(1_500)),
+    ]);
+    let store = Store::in_memory().unwrap();
+    let mut backfill = Backfill::new(&history, store, &config(), 150, Some(1_000)).unwrap();
+    assert_eq!(backfill.run(std::time::Duration::ZERO).unwrap(), 2);
+    let cursor = backfill.store().cursor(&checkpoint(150)).unwrap().unwrap();
+    assert_eq!(cursor.cursor, id(300));
+
+    // Running again with a later end carries on from the checkpoint, and
+    // an event it is given again is ignored
+    let history = FakeHistory::default();
+    history.pages.borrow_mut().extend([
+        page(vec![withdrawn(300), withdrawn(1_200)], id(1_200)),
+        page(vec![withdrawn(1_900)], id(2_000)),
+        page(
+            vec![],
+            format!("{:019}-{:010}", u64::from(2_001u32) << 32, 0),
+        ),
+    ]);
+    let store = backfill.into_store();
+    let mut backfill = Backfill::new(&history, store, &config(), 150, None).unwrap();
+    assert_eq!(backfill.to(), 2_000);
+    assert_eq!(backfill.run(std::time::Duration::ZERO).unwrap(), 2);
+    assert_eq!(history.requests.borrow()[0].start, Start::Cursor(id(300)));
+    assert_eq!(stored(backfill.store()), [200, 300, 1_200, 1_900]);
+}
+
+#[test]
+fn refuses_ranges_the_rpc_does_not_have() {
+    let history = FakeHistory::default();
+    let err = Backfill::new(&history, Store::in_memory().unwrap(), &config(), 50, None);
+    assert!(matches!(
+        err,
+        Err(Error::Retention {
+            start: 50,
+            oldest: 100
+        })
+    ));
+    let err = Backfill::new(
+        &history,
+        Store::in_memory().unwrap(),
+        &config(),
+        150,
+        Some(2_500),
+    );
+    assert!(matches!(err, Err(Error::Range { latest: 2_000, .. })));
+    let err = Backfill::new(
+        &history,
+        Store::in_memory().unwrap(),
+        &config(),
+        500,
+        Some(400),
+    );
+    assert!(matches!(err, Err(Error::Range { .. })));
+
+    assert_eq!(cursor_ledger(&id(956)), Some(956));
+    assert_eq!(cursor_ledger("not a cursor"), None);
+}
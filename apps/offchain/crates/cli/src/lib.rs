@@ -0,0 +1,341 @@
+//! `lumenpulse`, the admin command line for Lumenpulse contracts.
+//!
+//! Each subcommand is one admin entrypoint from `lumenpulse-upgradeable`,
+//! `lumenpulse-pausable` or `lumenpulse-access`, called with the signing
+//! account as the caller. Every transaction is simulated first and its
+//! preflight report printed; with `--dry-run` that is all that happens,
+//! otherwise the transaction is signed, submitted and waited on. A call
+//! the simulation says would fail is never sent.
+//!
+//! `--contract` takes an address, or the name of a contract the deployer
+//! recorded in the lockfile for the network.
+
+use clap::{Args, Parser, Subcommand};
+use lumenpulse_deployer::{lock::DEFAULT_LOCKFILE, Lockfile};
+use lumenpulse_rpc::Client;
+use lumenpulse_tx::{network, Sender, Signer};
+use std::{fs, path::PathBuf, str::FromStr};
+use stellar_xdr::curr::{BytesM, Hash, HostFunction, ScAddress, ScBytes, ScVal};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] lumenpulse_tx::Error),
+    #[error(transparent)]
+    Rpc(#[from] lumenpulse_rpc::Error),
+    #[error(transparent)]
+    Lock(#[from] lumenpulse_deployer::LockError),
+    #[error("reading {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{0}")]
+    Usage(String),
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "lumenpulse",
+    about = "Run admin operations on Lumenpulse contracts"
+)]
+pub struct Cli {
+    #[command(flatten)]
+    pub network: NetworkArgs,
+    #[command(flatten)]
+    pub key: KeyArgs,
+    /// Simulate only, printing the preflight report without submitting
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Args)]
+pub struct NetworkArgs {
+    /// testnet, futurenet, mainnet or standalone
+    #[arg(
+        long,
+        env = "LUMENPULSE_NETWORK",
+        default_value = "testnet",
+        global = true
+    )]
+    pub network: String,
+    /// RPC to use instead of the network's public one
+    #[arg(long, env = "LUMENPULSE_RPC_URL", global = true)]
+    pub rpc_url: Option<String>,
+    /// Passphrase of a network that is not a preset; asked of the RPC if
+    /// not given
+    #[arg(long, env = "LUMENPULSE_NETWORK_PASSPHRASE", global = true)]
+    pub network_passphrase: Option<String>,
+    /// Lockfile contracts are named in
+    #[arg(
+        long,
+        env = "LUMENPULSE_LOCKFILE",
+        default_value = DEFAULT_LOCKFILE,
+        global = true
+    )]
+    pub lockfile: String,
+}
+
+#[derive(Debug, Args)]
+pub struct KeyArgs {
+    /// Environment variable holding the secret key (`S...`) that signs
+    #[arg(long, default_value = "LUMENPULSE_SECRET_KEY", global = true)]
+    pub secret_key_env: String,
+    /// File holding the secret key, instead of the environment
+    #[arg(long, global = true)]
+    pub secret_key_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum Command {
+    /// Queue a new release, to install once the upgrade delay passes
+    ProposeUpgrade {
+        #[arg(long)]
+        contract: String,
+        /// Hash of WASM already installed on the network
+        #[arg(long, required_unless_present = "wasm", conflicts_with = "wasm")]
+        wasm_hash: Option<String>,
+        /// WASM file to install before proposing it
+        #[arg(long)]
+        wasm: Option<PathBuf>,
+        /// Release version, higher than the installed one
+        #[arg(long)]
+        version: u32,
+    },
+    /// Install the queued release once its delay has passed
+    ExecuteUpgrade {
+        #[arg(long)]
+        contract: String,
+    },
+    /// Drop the queued release
+    CancelUpgrade {
+        #[arg(long)]
+        contract: String,
+    },
+    /// Reinstall the release the last upgrade replaced
+    Rollback {
+        #[arg(long)]
+        contract: String,
+    },
+    /// Stop the contract's guarded entrypoints
+    Pause {
+        #[arg(long)]
+        contract: String,
+    },
+    /// Resume the contract's guarded entrypoints
+    Unpause {
+        #[arg(long)]
+        contract: String,
+    },
+    /// Offer the admin role to another account, which takes over once it
+    /// runs `accept-admin`
+    TransferAdmin {
+        #[arg(long)]
+        contract: String,
+        #[arg(long)]
+        new_admin: String,
+    },
+    /// Take over as admin, as offered by `transfer-admin`
+    AcceptAdmin {
+        #[arg(long)]
+        contract: String,
+    },
+}
+
+/// One transaction a command sends
+pub struct Step {
+    pub description: String,
+    pub host_function: HostFunction,
+}
+
+fn contract(strkey: &str) -> Result<ScAddress, Error> {
+    lumenpulse_tx::contract_address(strkey)
+        .ok_or_else(|| Error::Usage(format!("`{strkey}` is not a contract address")))
+}
+
+impl Command {
+    /// The contract the command is run on
+    pub fn contract_mut(&mut self) -> &mut String {
+        match self {
+            Command::ProposeUpgrade { contract, .. }
+            | Command::ExecuteUpgrade { contract }
+            | Command::CancelUpgrade { contract }
+            | Command::Rollback { contract }
+            | Command::Pause { contract }
+            | Command::Unpause { contract }
+            | Command::TransferAdmin { contract, .. }
+            | Command::AcceptAdmin { contract } => contract,
+        }
+    }
+
+    /// Replace a contract named rather than given by address with its
+    /// address on `network` from `lockfile`
+    pub fn resolve_contract(&mut self, lockfile: &Lockfile, network: &str) -> Result<(), Error> {
+        let contract = self.contract_mut();
+        if lumenpulse_tx::contract_address(contract).is_some() {
+            return Ok(());
+        }
+        let id = lockfile.contract_id(network, contract).ok_or_else(|| {
+            Error::Usage(format!(
+                "`{contract}` is neither a contract address nor a contract the lockfile has for {network}"
+            ))
+        })?;
+        *contract = id.to_string();
+        Ok(())
+    }
+
+    /// The transactions that carry out the command for `caller`, in order
+    pub fn steps(&self, caller: &ScAddress) -> Result<Vec<Step>, Error> {
+        let caller = ScVal::Address(caller.clone());
+        let call = |contract_id: &str, function: &str, args: Vec<ScVal>| {
+            Ok::<_, Error>(Step {
+                description: format!("{function} on {contract_id}"),
+                host_function: lumenpulse_tx::invoke_contract(
+                    &contract(contract_id)?,
+                    function,
+                    args,
+                )?,
+            })
+        };
+        let steps = match self {
+            Command::ProposeUpgrade {
+                contract,
+                wasm_hash,
+                wasm,
+                version,
+            } => {
+                let mut steps = Vec::new();
+                let hash = match (wasm_hash, wasm) {
+                    (Some(hash), _) => Hash::from_str(hash)
+                        .map_err(|_| Error::Usage(format!("`{hash}` is not a 32-byte hex hash")))?,
+                    (None, Some(path)) => {
+                        let wasm = fs::read(path).map_err(|source| Error::Io {
+                            path: path.clone(),
+                            source,
+                        })?;
+                        let hash = lumenpulse_tx::wasm_hash(&wasm);
+                        steps.push(Step {
+                            description: format!("install WASM {hash}"),
+                            host_function: lumenpulse_tx::upload_wasm(&wasm)?,
+                        });
+                        hash
+                    }
+                    (None, None) => unreachable!("clap requires one"),
+                };
+                let hash = ScVal::Bytes(ScBytes(
+                    BytesM::try_from(hash.0.to_vec()).expect("32 bytes"),
+                ));
+                steps.push(call(
+                    contract,
+                    "propose_upgrade",
+                    vec![caller, hash, ScVal::U32(*version)],
+                )?);
+                steps
+            }
+            Command::ExecuteUpgrade { contract } => {
+                vec![call(contract, "execute_upgrade", vec![caller])?]
+            }
+            Command::CancelUpgrade { contract } => {
+                vec![call(contract, "cancel_upgrade", vec![caller])?]
+            }
+            Command::Rollback { contract } => {
+                vec![call(contract, "rollback_upgrade", vec![caller])?]
+            }
+            Command::Pause { contract } => vec![call(contract, "pause", vec![caller])?],
+            Command::Unpause { contract } => vec![call(contract, "unpause", vec![caller])?],
+            Command::TransferAdmin {
+                contract,
+                new_admin,
+            } => {
+                let new_admin = ScAddress::from_str(new_admin)
+                    .map_err(|_| Error::Usage(format!("`{new_admin}` is not an address")))?;
+                vec![call(
+                    contract,
+                    "propose_admin",
+                    vec![caller, ScVal::Address(new_admin)],
+                )?]
+            }
+            Command::AcceptAdmin { contract } => {
+                vec![call(contract, "accept_admin", vec![caller])?]
+            }
+        };
+        Ok(steps)
+    }
+}
+
+impl NetworkArgs {
+    /// RPC URL and passphrase to use
+    pub fn resolve(&self) -> Result<(String, Option<String>), Error> {
+        let preset = network::preset(&self.network);
+        let rpc_url = self
+            .rpc_url
+            .clone()
+            .or_else(|| preset.and_then(|preset| preset.rpc_url).map(String::from))
+            .ok_or_else(|| Error::Usage(format!("--rpc-url is needed for {}", self.network)))?;
+        let passphrase = self
+            .network_passphrase
+            .clone()
+            .or_else(|| preset.map(|preset| preset.passphrase.to_string()));
+        Ok((rpc_url, passphrase))
+    }
+}
+
+impl KeyArgs {
+    pub fn signer(&self) -> Result<Signer, Error> {
+        let secret = match &self.secret_key_file {
+            Some(path) => fs::read_to_string(path).map_err(|source| Error::Io {
+                path: path.clone(),
+                source,
+            })?,
+            None => std::env::var(&self.secret_key_env)
+                .map_err(|_| Error::Usage(format!("{} is not set", self.secret_key_env)))?,
+        };
+        Ok(Signer::from_secret(&secret)?)
+    }
+}
+
+fn json(val: &ScVal) -> String {
+    serde_json::to_string(val).unwrap_or_else(|_| format!("{val:?}"))
+}
+
+pub fn run(cli: &Cli) -> Result<(), Error> {
+    let (rpc_url, passphrase) = cli.network.resolve()?;
+    let client = Client::new(rpc_url);
+    let passphrase = match passphrase {
+        Some(passphrase) => passphrase,
+        None => client.get_network()?.passphrase,
+    };
+    let signer = cli.key.signer()?;
+    let sender = Sender::new(&client, &signer, &passphrase);
+
+    let mut command = cli.command.clone();
+    if lumenpulse_tx::contract_address(command.contract_mut()).is_none() {
+        let lockfile = Lockfile::read(&cli.network.lockfile)?;
+        command.resolve_contract(&lockfile, &cli.network.network)?;
+    }
+    for step in command.steps(&signer.address())? {
+        eprintln!("{} from {}", step.description, signer.account());
+        // The admin entrypoints fail with the shared crates' errors
+        let preflight = lumenpulse_preflight::preflight(&sender, step.host_function, &[])?;
+        eprint!("{}", preflight.report);
+        let prepared = preflight.prepare()?;
+        if cli.dry_run {
+            continue;
+        }
+        let submitted = sender.submit(prepared.tx)?;
+        eprintln!(
+            "  included in ledger {}: {}",
+            submitted.info.ledger.unwrap_or_default(),
+            submitted.hash
+        );
+        if let Some(value) = submitted.info.return_value() {
+            println!("{}", json(value));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,13 @@
+use clap::Parser;
+use lumenpulse_cli::Cli;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    match lumenpulse_cli::run(&Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("lumenpulse: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
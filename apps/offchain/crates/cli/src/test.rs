@@ -0,0 +1,193 @@
+use crate::{Cli, Command, Error};
+use clap::Parser;
+use lumenpulse_deployer::{Deployment, Lockfile};
+use stellar_xdr::curr::{HostFunction, ScAddress, ScVal};
+
+const CONTRACT: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAK3IM";
+const ADMIN: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAJXFF";
+
+fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+    Cli::try_parse_from(std::iter::once("lumenpulse").chain(args.iter().copied()))
+}
+
+/// Function name and arguments of each step, with `ADMIN` calling
+fn calls(command: &Command) -> Vec<(String, Vec<ScVal>)> {
+    let admin = ADMIN.parse().unwrap();
+    command
+        .steps(&admin)
+        .unwrap()
+        .into_iter()
+        .map(|step| match step.host_function {
+            HostFunction::InvokeContract(args) => (
+                args.function_name.0.to_utf8_string().unwrap(),
+                args.args.to_vec(),
+            ),
+            HostFunction::UploadContractWasm(wasm) => {
+                ("upload".to_string(), vec![ScVal::U32(wasm.len() as u32)])
+            }
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_commands_call_the_admin_entrypoints() {
+    let admin = ScVal::Address(ADMIN.parse().unwrap());
+    let hash = "ab".repeat(32);
+    let cli = parse(&[
+        "propose-upgrade",
+        "--contract",
+        CONTRACT,
+        "--wasm-hash",
+        &hash,
+        "--version",
+        "3",
+    ])
+    .unwrap();
+    let [(function, args)] = &calls(&cli.command)[..] else {
+        panic!("one step")
+    };
+    assert_eq!(function, "propose_upgrade");
+    assert_eq!(args[0], admin);
+    assert!(matches!(&args[1], ScVal::Bytes(bytes) if bytes.0.as_slice() == [0xab; 32]));
+    assert_eq!(args[2], ScVal::U32(3));
+
+    for (command, function) in [
+        ("execute-upgrade", "execute_upgrade"),
+        ("cancel-upgrade", "cancel_upgrade"),
+        ("rollback", "rollback_upgrade"),
+        ("pause", "pause"),
+        ("unpause", "unpause"),
+        ("accept-admin", "accept_admin"),
+    ] {
+        let cli = parse(&[command, "--contract", CONTRACT]).unwrap();
+        assert_eq!(
+            calls(&cli.command),
+            vec![(function.to_string(), vec![admin.clone()])]
+        );
+    }
+
+    let cli = parse(&[
+        "transfer-admin",
+        "--contract",
+        CONTRACT,
+        "--new-admin",
+        CONTRACT,
+    ])
+    .unwrap();
+    let new_admin = ScVal::Address(CONTRACT.parse::<ScAddress>().unwrap());
+    assert_eq!(
+        calls(&cli.command),
+        vec![("propose_admin".to_string(), vec![admin, new_admin])]
+    );
+}
+
+#[test]
+fn test_proposing_a_wasm_file_installs_it_first() {
+    let path = std::env::temp_dir().join("lumenpulse-cli-test.wasm");
+    std::fs::write(&path, b"\0asm\x01\0\0\0").unwrap();
+    let cli = parse(&[
+        "propose-upgrade",
+        "--contract",
+        CONTRACT,
+        "--wasm",
+        path.to_str().unwrap(),
+        "--version",
+        "2",
+    ])
+    .unwrap();
+    let calls = calls(&cli.command);
+    assert_eq!(calls[0], ("upload".to_string(), vec![ScVal::U32(8)]));
+    let ScVal::Bytes(hash) = &calls[1].1[1] else {
+        panic!("hash argument")
+    };
+    assert_eq!(
+        hash.0.as_slice(),
+        lumenpulse_tx::wasm_hash(b"\0asm\x01\0\0\0").0
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_rejects_bad_arguments() {
+    // One of --wasm and --wasm-hash is needed, and only one
+    assert!(parse(&["propose-upgrade", "--contract", CONTRACT, "--version", "2"]).is_err());
+    assert!(parse(&[
+        "propose-upgrade",
+        "--contract",
+        CONTRACT,
+        "--version",
+        "2",
+        "--wasm",
+        "a",
+        "--wasm-hash",
+        "b"
+    ])
+    .is_err());
+
+    let admin = ADMIN.parse().unwrap();
+    let cli = parse(&["pause", "--contract", ADMIN]).unwrap();
+    assert!(matches!(cli.command.steps(&admin), Err(Error::Usage(_))));
+    let cli = parse(&[
+        "propose-upgrade",
+        "--contract",
+        CONTRACT,
+        "--wasm-hash",
+        "xyz",
+        "--version",
+        "2",
+    ])
+    .unwrap();
+    assert!(matches!(cli.command.steps(&admin), Err(Error::Usage(_))));
+
+    // Mainnet has no public RPC to fall back on
+    let cli = parse(&["--network", "mainnet", "pause", "--contract", CONTRACT]).unwrap();
+    assert!(matches!(cli.network.resolve(), Err(Error::Usage(_))));
+    let cli = parse(&[
+        "--network",
+        "mainnet",
+        "--rpc-url",
+        "http://rpc",
+        "pause",
+        "--contract",
+        CONTRACT,
+    ])
+    .unwrap();
+    let (url, passphrase) = cli.network.resolve().unwrap();
+    assert_eq!(url, "http://rpc");
+    assert_eq!(
+        passphrase.unwrap(),
+        "Public Global Stellar Network ; September 2015"
+    );
+}
+
+#[test]
+fn test_contracts_can_be_named_from_the_lockfile() {
+    let mut lockfile = Lockfile::default();
+    let testnet = lockfile.networks.entry("testnet".to_string()).or_default();
+    testnet.contracts.insert(
+        "streaming".to_string(),
+        Deployment {
+            id: CONTRACT.to_string(),
+            wasm_hash: "ab".repeat(32),
+            salt: "streaming-1".to_string(),
+            initialized: true,
+        },
+    );
+
+    let mut cli = parse(&["pause", "--contract", "streaming"]).unwrap();
+    assert_eq!(cli.network.lockfile, "lumenpulse.lock.json");
+    cli.command.resolve_contract(&lockfile, "testnet").unwrap();
+    assert_eq!(cli.command.contract_mut(), CONTRACT);
+    // An address is used as it is
+    cli.command
+        .resolve_contract(&Lockfile::default(), "testnet")
+        .unwrap();
+    assert_eq!(cli.command.contract_mut(), CONTRACT);
+
+    let mut cli = parse(&["pause", "--contract", "streaming"]).unwrap();
+    assert!(matches!(
+        cli.command.resolve_contract(&lockfile, "mainnet"),
+        Err(Error::Usage(_))
+    ));
+}
@@ -0,0 +1,631 @@
+//! Generates a typed client for each contract from the spec in its WASM.
+//!
+//! The WASM is the fixture the on-chain tests deploy
+//! (`apps/onchain/contracts/factory/testdata`), so `make fixtures` there
+//! refreshes the clients along with the tests.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::io::Cursor;
+use std::{env, fs, path::Path};
+use stellar_xdr::curr::{
+    Limited, Limits, ReadXdr, ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0,
+    ScSpecUdtErrorEnumV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+};
+
+const FIXTURES: &str = "../../../onchain/contracts/factory/testdata";
+
+/// Module, fixture and client name of each generated client
+const CONTRACTS: [(&str, &str, &str); 4] = [
+    ("streaming", "streaming.wasm", "StreamingClient"),
+    ("escrow", "escrow.wasm", "EscrowClient"),
+    ("subscriptions", "subscriptions.wasm", "SubscriptionsClient"),
+    ("upgradable", "upgradable_contract.wasm", "UpgradableClient"),
+];
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let mut errors: BTreeMap<String, (ScSpecUdtErrorEnumV0, usize)> = BTreeMap::new();
+    for (module, wasm, client) in CONTRACTS {
+        let path = Path::new(FIXTURES).join(wasm);
+        println!("cargo:rerun-if-changed={}", path.display());
+        let wasm = fs::read(&path).unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+        let spec = spec(&wasm);
+        for entry in &spec {
+            if let ScSpecEntry::UdtErrorEnumV0(e) = entry {
+                errors.entry(e.name.to_string()).or_insert((e.clone(), 0)).1 += 1;
+            }
+        }
+        let code = generate(client, &spec);
+        fs::write(Path::new(&out_dir).join(format!("{module}.rs")), code).unwrap();
+    }
+
+    // Errors more than one contract declares come from the shared crates
+    let shared: Vec<_> = errors
+        .values()
+        .filter(|(_, contracts)| *contracts > 1)
+        .map(|(e, _)| e)
+        .collect();
+    let mut out =
+        String::from("// Generated by build.rs from the contract specs; do not edit.\n\n");
+    writeln!(
+        out,
+        "/// Errors from the shared crates, which every contract using them returns"
+    )
+    .unwrap();
+    error_table(&mut out, "SHARED_ERRORS", "crate::ContractError", &shared);
+    fs::write(Path::new(&out_dir).join("shared_errors.rs"), out).unwrap();
+}
+
+/// A `const` listing every case of `errors` as a `ContractError`
+fn error_table(out: &mut String, name: &str, ty: &str, errors: &[&ScSpecUdtErrorEnumV0]) {
+    writeln!(out, "pub const {name}: &[{ty}] = &[").unwrap();
+    for e in errors {
+        for case in e.cases.iter() {
+            writeln!(
+                out,
+                "    {ty} {{ code: {}, name: {:?}, doc: {:?} }},",
+                case.value,
+                format!("{}::{}", e.name, case.name),
+                case.doc.to_utf8_string_lossy().trim()
+            )
+            .unwrap();
+        }
+    }
+    out.push_str("];\n\n");
+}
+
+/// Entries of the `contractspecv0` custom section, without repeats
+fn spec(wasm: &[u8]) -> Vec<ScSpecEntry> {
+    let mut rest = &wasm[8..];
+    while !rest.is_empty() {
+        let id = rest[0];
+        rest = &rest[1..];
+        let size = leb128(&mut rest);
+        let (mut section, tail) = rest.split_at(size);
+        rest = tail;
+        if id != 0 {
+            continue;
+        }
+        let name_len = leb128(&mut section);
+        let (name, content) = section.split_at(name_len);
+        if name == b"contractspecv0" {
+            let mut reader = Limited::new(Cursor::new(content), Limits::none());
+            let mut seen = BTreeSet::new();
+            return ScSpecEntry::read_xdr_iter(&mut reader)
+                .map(|entry| entry.expect("valid spec entry"))
+                .filter(|entry| seen.insert(entry_name(entry)))
+                .collect();
+        }
+    }
+    panic!("no contract spec in WASM");
+}
+
+fn leb128(bytes: &mut &[u8]) -> usize {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[0];
+        *bytes = &bytes[1..];
+        value |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+fn entry_name(entry: &ScSpecEntry) -> String {
+    match entry {
+        ScSpecEntry::FunctionV0(f) => format!("fn {}", f.name.0),
+        ScSpecEntry::UdtStructV0(s) => s.name.to_string(),
+        ScSpecEntry::UdtUnionV0(u) => u.name.to_string(),
+        ScSpecEntry::UdtEnumV0(e) => e.name.to_string(),
+        ScSpecEntry::UdtErrorEnumV0(e) => e.name.to_string(),
+        ScSpecEntry::EventV0(e) => format!("event {}", e.name.0),
+    }
+}
+
+const KEYWORDS: [&str; 20] = [
+    "as", "break", "const", "continue", "crate", "else", "enum", "fn", "for", "if", "impl", "in",
+    "loop", "match", "mod", "move", "ref", "return", "type", "use",
+];
+
+fn ident(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("r#{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+fn docs(out: &mut String, indent: &str, doc: &str) {
+    for line in doc.lines() {
+        writeln!(out, "{indent}/// {line}").unwrap();
+    }
+}
+
+/// Rust type a spec type is read into and written from
+fn rust_type(ty: &ScSpecTypeDef) -> String {
+    match ty {
+        ScSpecTypeDef::Bool => "bool".into(),
+        ScSpecTypeDef::Void => "()".into(),
+        ScSpecTypeDef::U32 => "u32".into(),
+        ScSpecTypeDef::I32 => "i32".into(),
+        ScSpecTypeDef::U64 => "u64".into(),
+        ScSpecTypeDef::I64 => "i64".into(),
+        ScSpecTypeDef::U128 => "u128".into(),
+        ScSpecTypeDef::I128 => "i128".into(),
+        ScSpecTypeDef::Timepoint => "crate::Timepoint".into(),
+        ScSpecTypeDef::Duration => "crate::Duration".into(),
+        // Values the client does not interpret pass through as they are
+        ScSpecTypeDef::Val | ScSpecTypeDef::Error | ScSpecTypeDef::U256 | ScSpecTypeDef::I256 => {
+            "stellar_xdr::curr::ScVal".into()
+        }
+        ScSpecTypeDef::Bytes => "crate::Bytes".into(),
+        ScSpecTypeDef::BytesN(n) => format!("[u8; {}]", n.n),
+        ScSpecTypeDef::String => "String".into(),
+        ScSpecTypeDef::Symbol => "crate::Symbol".into(),
+        ScSpecTypeDef::Address | ScSpecTypeDef::MuxedAddress => {
+            "stellar_xdr::curr::ScAddress".into()
+        }
+        ScSpecTypeDef::Option(option) => format!("Option<{}>", rust_type(&option.value_type)),
+        // Contract errors surface as failed simulations, so a call returns
+        // what it returns on success
+        ScSpecTypeDef::Result(result) => rust_type(&result.ok_type),
+        ScSpecTypeDef::Vec(vec) => format!("Vec<{}>", rust_type(&vec.element_type)),
+        ScSpecTypeDef::Map(map) => format!(
+            "std::collections::BTreeMap<{}, {}>",
+            rust_type(&map.key_type),
+            rust_type(&map.value_type)
+        ),
+        // `()` is an empty tuple in specs
+        ScSpecTypeDef::Tuple(tuple) if tuple.value_types.is_empty() => "()".into(),
+        ScSpecTypeDef::Tuple(tuple) => {
+            let types: Vec<_> = tuple.value_types.iter().map(rust_type).collect();
+            format!("({},)", types.join(", "))
+        }
+        ScSpecTypeDef::Udt(udt) => udt.name.to_string(),
+    }
+}
+
+/// Whether arguments of the type are taken by value rather than reference
+fn is_copy(ty: &ScSpecTypeDef, int_enums: &BTreeSet<String>) -> bool {
+    match ty {
+        ScSpecTypeDef::Bool
+        | ScSpecTypeDef::U32
+        | ScSpecTypeDef::I32
+        | ScSpecTypeDef::U64
+        | ScSpecTypeDef::I64
+        | ScSpecTypeDef::U128
+        | ScSpecTypeDef::I128 => true,
+        ScSpecTypeDef::Udt(udt) => int_enums.contains(&udt.name.to_string()),
+        _ => false,
+    }
+}
+
+fn generate(client: &str, spec: &[ScSpecEntry]) -> String {
+    let int_enums: BTreeSet<String> = spec
+        .iter()
+        .filter_map(|entry| match entry {
+            ScSpecEntry::UdtEnumV0(e) => Some(e.name.to_string()),
+            ScSpecEntry::UdtErrorEnumV0(e) => Some(e.name.to_string()),
+            _ => None,
+        })
+        .collect();
+
+    let mut out = String::new();
+    out.push_str("// Generated by build.rs from the contract spec; do not edit.\n\n");
+    out.push_str("#[allow(unused_imports)]\nuse crate::{FromScVal as _, ToScVal as _};\n\n");
+    for entry in spec {
+        match entry {
+            ScSpecEntry::UdtStructV0(s) => struct_type(&mut out, s),
+            ScSpecEntry::UdtUnionV0(u) => union_type(&mut out, u),
+            ScSpecEntry::UdtEnumV0(e) => enum_type(&mut out, e),
+            ScSpecEntry::UdtErrorEnumV0(e) => error_type(&mut out, e),
+            ScSpecEntry::FunctionV0(_) | ScSpecEntry::EventV0(_) => {}
+        }
+    }
+
+    let errors: Vec<_> = spec
+        .iter()
+        .filter_map(|entry| match entry {
+            ScSpecEntry::UdtErrorEnumV0(e) => Some(e),
+            _ => None,
+        })
+        .collect();
+    writeln!(out, "/// Every error the contract returns").unwrap();
+    error_table(&mut out, "ERRORS", "crate::ContractError", &errors);
+
+    writeln!(out, "/// Typed calls to the contract at `contract`").unwrap();
+    writeln!(out, "#[derive(Clone, Debug, Eq, PartialEq)]").unwrap();
+    writeln!(
+        out,
+        "pub struct {client} {{\n    pub contract: stellar_xdr::curr::ScAddress,\n}}\n"
+    )
+    .unwrap();
+    // Methods take what the entrypoints take, however many arguments that is
+    writeln!(
+        out,
+        "#[allow(clippy::too_many_arguments)]\nimpl {client} {{"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    pub fn new(contract: stellar_xdr::curr::ScAddress) -> Self {{\n        {client} {{ contract }}\n    }}"
+    )
+    .unwrap();
+    for entry in spec {
+        if let ScSpecEntry::FunctionV0(f) = entry {
+            function(&mut out, f, &int_enums);
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn struct_type(out: &mut String, s: &ScSpecUdtStructV0) {
+    let name = s.name.to_string();
+    // Tuple structs have fields named by position and travel as a vec
+    let is_tuple = s
+        .fields
+        .iter()
+        .all(|field| field.name.to_string().parse::<u32>().is_ok());
+    docs(out, "", &s.doc.to_utf8_string_lossy());
+    writeln!(out, "#[derive(Clone, Debug, Eq, PartialEq)]").unwrap();
+    if is_tuple {
+        let types: Vec<_> = s
+            .fields
+            .iter()
+            .map(|f| format!("pub {}", rust_type(&f.type_)))
+            .collect();
+        writeln!(out, "pub struct {name}({});\n", types.join(", ")).unwrap();
+        writeln!(out, "impl crate::ToScVal for {name} {{").unwrap();
+        writeln!(
+            out,
+            "    fn to_sc_val(&self) -> Result<stellar_xdr::curr::ScVal, crate::Error> {{"
+        )
+        .unwrap();
+        let items: Vec<_> = (0..s.fields.len())
+            .map(|i| format!("self.{i}.to_sc_val()?"))
+            .collect();
+        writeln!(
+            out,
+            "        crate::vec_val(vec![{}])\n    }}\n}}\n",
+            items.join(", ")
+        )
+        .unwrap();
+        writeln!(out, "impl crate::FromScVal for {name} {{").unwrap();
+        writeln!(
+            out,
+            "    fn from_sc_val(val: &stellar_xdr::curr::ScVal) -> Result<Self, crate::Error> {{"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        let items = crate::vec_items(val, \"{name}\")?;"
+        )
+        .unwrap();
+        let items: Vec<_> = (0..s.fields.len())
+            .map(|i| format!("crate::item(items, {i}, \"{name}\")?"))
+            .collect();
+        writeln!(
+            out,
+            "        Ok({name}({}))\n    }}\n}}\n",
+            items.join(", ")
+        )
+        .unwrap();
+        return;
+    }
+
+    writeln!(out, "pub struct {name} {{").unwrap();
+    for field in s.fields.iter() {
+        docs(out, "    ", &field.doc.to_utf8_string_lossy());
+        writeln!(
+            out,
+            "    pub {}: {},",
+            ident(&field.name.to_string()),
+            rust_type(&field.type_)
+        )
+        .unwrap();
+    }
+    out.push_str("}\n\n");
+
+    // Maps are keyed by field name in sorted order, as the host requires
+    let mut fields: Vec<_> = s
+        .fields
+        .iter()
+        .map(|field| field.name.to_string())
+        .collect();
+    fields.sort();
+    writeln!(out, "impl crate::ToScVal for {name} {{").unwrap();
+    writeln!(
+        out,
+        "    fn to_sc_val(&self) -> Result<stellar_xdr::curr::ScVal, crate::Error> {{"
+    )
+    .unwrap();
+    writeln!(out, "        crate::map_val(vec![").unwrap();
+    for field in &fields {
+        writeln!(
+            out,
+            "            (\"{field}\", self.{}.to_sc_val()?),",
+            ident(field)
+        )
+        .unwrap();
+    }
+    writeln!(out, "        ])\n    }}\n}}\n").unwrap();
+    writeln!(out, "impl crate::FromScVal for {name} {{").unwrap();
+    writeln!(
+        out,
+        "    fn from_sc_val(val: &stellar_xdr::curr::ScVal) -> Result<Self, crate::Error> {{"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        let map = crate::map_entries(val, \"{name}\")?;"
+    )
+    .unwrap();
+    writeln!(out, "        Ok({name} {{").unwrap();
+    for field in s.fields.iter() {
+        let field = field.name.to_string();
+        writeln!(
+            out,
+            "            {}: crate::field(map, \"{field}\")?,",
+            ident(&field)
+        )
+        .unwrap();
+    }
+    writeln!(out, "        }})\n    }}\n}}\n").unwrap();
+}
+
+fn union_type(out: &mut String, u: &ScSpecUdtUnionV0) {
+    let name = u.name.to_string();
+    docs(out, "", &u.doc.to_utf8_string_lossy());
+    writeln!(
+        out,
+        "#[derive(Clone, Debug, Eq, PartialEq)]\npub enum {name} {{"
+    )
+    .unwrap();
+    for case in u.cases.iter() {
+        match case {
+            ScSpecUdtUnionCaseV0::VoidV0(case) => {
+                docs(out, "    ", &case.doc.to_utf8_string_lossy());
+                writeln!(out, "    {},", case.name).unwrap();
+            }
+            ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                docs(out, "    ", &case.doc.to_utf8_string_lossy());
+                let types: Vec<_> = case.type_.iter().map(rust_type).collect();
+                writeln!(out, "    {}({}),", case.name, types.join(", ")).unwrap();
+            }
+        }
+    }
+    out.push_str("}\n\n");
+
+    // A case travels as a vec of its name followed by its values
+    writeln!(out, "impl crate::ToScVal for {name} {{").unwrap();
+    writeln!(
+        out,
+        "    fn to_sc_val(&self) -> Result<stellar_xdr::curr::ScVal, crate::Error> {{"
+    )
+    .unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for case in u.cases.iter() {
+        match case {
+            ScSpecUdtUnionCaseV0::VoidV0(case) => writeln!(
+                out,
+                "            {name}::{0} => crate::vec_val(vec![crate::Symbol::from(\"{0}\").to_sc_val()?]),",
+                case.name
+            )
+            .unwrap(),
+            ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                let bindings: Vec<_> = (0..case.type_.len()).map(|i| format!("v{i}")).collect();
+                let values: Vec<_> = bindings.iter().map(|b| format!("{b}.to_sc_val()?")).collect();
+                writeln!(
+                    out,
+                    "            {name}::{0}({1}) => crate::vec_val(vec![crate::Symbol::from(\"{0}\").to_sc_val()?, {2}]),",
+                    case.name,
+                    bindings.join(", "),
+                    values.join(", ")
+                )
+                .unwrap();
+            }
+        }
+    }
+    writeln!(out, "        }}\n    }}\n}}\n").unwrap();
+
+    writeln!(out, "impl crate::FromScVal for {name} {{").unwrap();
+    writeln!(
+        out,
+        "    fn from_sc_val(val: &stellar_xdr::curr::ScVal) -> Result<Self, crate::Error> {{"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        let items = crate::vec_items(val, \"{name}\")?;"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        let case: crate::Symbol = crate::item(items, 0, \"{name}\")?;"
+    )
+    .unwrap();
+    writeln!(out, "        match case.0.as_str() {{").unwrap();
+    for case in u.cases.iter() {
+        match case {
+            ScSpecUdtUnionCaseV0::VoidV0(case) => {
+                writeln!(out, "            \"{0}\" => Ok({name}::{0}),", case.name).unwrap()
+            }
+            ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                let values: Vec<_> = (1..=case.type_.len())
+                    .map(|i| format!("crate::item(items, {i}, \"{name}\")?"))
+                    .collect();
+                writeln!(
+                    out,
+                    "            \"{0}\" => Ok({name}::{0}({1})),",
+                    case.name,
+                    values.join(", ")
+                )
+                .unwrap();
+            }
+        }
+    }
+    writeln!(
+        out,
+        "            _ => Err(crate::Error::WrongType(\"{name}\")),"
+    )
+    .unwrap();
+    writeln!(out, "        }}\n    }}\n}}\n").unwrap();
+}
+
+fn enum_type(out: &mut String, e: &ScSpecUdtEnumV0) {
+    let name = e.name.to_string();
+    docs(out, "", &e.doc.to_utf8_string_lossy());
+    writeln!(
+        out,
+        "#[derive(Clone, Copy, Debug, Eq, PartialEq)]\n#[repr(u32)]\npub enum {name} {{"
+    )
+    .unwrap();
+    for case in e.cases.iter() {
+        docs(out, "    ", &case.doc.to_utf8_string_lossy());
+        writeln!(out, "    {} = {},", case.name, case.value).unwrap();
+    }
+    out.push_str("}\n\n");
+    writeln!(out, "impl crate::ToScVal for {name} {{").unwrap();
+    writeln!(
+        out,
+        "    fn to_sc_val(&self) -> Result<stellar_xdr::curr::ScVal, crate::Error> {{"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        Ok(stellar_xdr::curr::ScVal::U32(*self as u32))\n    }}\n}}\n"
+    )
+    .unwrap();
+    writeln!(out, "impl crate::FromScVal for {name} {{").unwrap();
+    writeln!(
+        out,
+        "    fn from_sc_val(val: &stellar_xdr::curr::ScVal) -> Result<Self, crate::Error> {{"
+    )
+    .unwrap();
+    writeln!(out, "        match u32::from_sc_val(val)? {{").unwrap();
+    for case in e.cases.iter() {
+        writeln!(
+            out,
+            "            {} => Ok({name}::{}),",
+            case.value, case.name
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "            _ => Err(crate::Error::WrongType(\"{name}\")),"
+    )
+    .unwrap();
+    writeln!(out, "        }}\n    }}\n}}\n").unwrap();
+}
+
+fn error_type(out: &mut String, e: &ScSpecUdtErrorEnumV0) {
+    let name = e.name.to_string();
+    docs(out, "", &e.doc.to_utf8_string_lossy());
+    writeln!(
+        out,
+        "#[derive(Clone, Copy, Debug, Eq, PartialEq)]\n#[repr(u32)]\npub enum {name} {{"
+    )
+    .unwrap();
+    for case in e.cases.iter() {
+        docs(out, "    ", &case.doc.to_utf8_string_lossy());
+        writeln!(out, "    {} = {},", case.name, case.value).unwrap();
+    }
+    out.push_str("}\n\n");
+    writeln!(out, "impl {name} {{").unwrap();
+    writeln!(out, "    /// The error a contract error code stands for").unwrap();
+    writeln!(
+        out,
+        "    pub fn from_code(code: u32) -> Option<Self> {{\n        match code {{"
+    )
+    .unwrap();
+    for case in e.cases.iter() {
+        writeln!(
+            out,
+            "            {} => Some({name}::{}),",
+            case.value, case.name
+        )
+        .unwrap();
+    }
+    writeln!(out, "            _ => None,\n        }}\n    }}\n}}\n").unwrap();
+    writeln!(out, "impl crate::ToScVal for {name} {{").unwrap();
+    writeln!(
+        out,
+        "    fn to_sc_val(&self) -> Result<stellar_xdr::curr::ScVal, crate::Error> {{"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        Ok(stellar_xdr::curr::ScVal::Error(stellar_xdr::curr::ScError::Contract(*self as u32)))\n    }}\n}}\n"
+    )
+    .unwrap();
+    writeln!(out, "impl crate::FromScVal for {name} {{").unwrap();
+    writeln!(
+        out,
+        "    fn from_sc_val(val: &stellar_xdr::curr::ScVal) -> Result<Self, crate::Error> {{"
+    )
+    .unwrap();
+    writeln!(out, "        match val {{").unwrap();
+    writeln!(
+        out,
+        "            stellar_xdr::curr::ScVal::Error(stellar_xdr::curr::ScError::Contract(code)) => {name}::from_code(*code).ok_or(crate::Error::WrongType(\"{name}\")),"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "            _ => Err(crate::Error::WrongType(\"{name}\")),"
+    )
+    .unwrap();
+    writeln!(out, "        }}\n    }}\n}}\n").unwrap();
+}
+
+fn function(out: &mut String, f: &ScSpecFunctionV0, int_enums: &BTreeSet<String>) {
+    let name = f.name.0.to_string();
+    // The constructor runs at deployment, not as a call
+    if name.starts_with("__") {
+        return;
+    }
+    let returns = f
+        .outputs
+        .first()
+        .map(rust_type)
+        .unwrap_or_else(|| "()".into());
+    let params: Vec<_> = f
+        .inputs
+        .iter()
+        .map(|input| {
+            let ty = match &input.type_ {
+                _ if is_copy(&input.type_, int_enums) => rust_type(&input.type_),
+                ScSpecTypeDef::Vec(vec) => format!("&[{}]", rust_type(&vec.element_type)),
+                ty => format!("&{}", rust_type(ty)),
+            };
+            format!("{}: {ty}", ident(&input.name.to_string()))
+        })
+        .collect();
+    let args: Vec<_> = f
+        .inputs
+        .iter()
+        .map(|input| format!("{}.to_sc_val()?", ident(&input.name.to_string())))
+        .collect();
+
+    out.push('\n');
+    docs(out, "    ", &f.doc.to_utf8_string_lossy());
+    writeln!(
+        out,
+        "    pub fn {}(&self{}) -> Result<crate::Call<{returns}>, crate::Error> {{",
+        ident(&name),
+        params.iter().map(|p| format!(", {p}")).collect::<String>()
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        crate::Call::new(&self.contract, \"{name}\", vec![{}])\n    }}",
+        args.join(", ")
+    )
+    .unwrap();
+}
@@ -0,0 +1,37 @@
+//! What a stream has accrued, computed the way the contract computes it.
+
+use crate::streaming::{StreamData, StreamKind};
+
+impl StreamData {
+    /// Tokens streamed to the recipient by `now`, withdrawn or not. `None`
+    /// for USD streams, whose tokens depend on the price at withdrawal.
+    pub fn accrued_at(&self, now: u64) -> Option<i128> {
+        match &self.kind {
+            StreamKind::Linear => Some(lumenpulse_accrual::linear(
+                self.deposit,
+                self.start_time,
+                self.end_time,
+                now,
+            )),
+            StreamKind::Rate(rate) => Some(lumenpulse_accrual::rate(
+                self.deposit,
+                rate.amount,
+                rate.period,
+                self.start_time,
+                now,
+            )),
+            StreamKind::Usd(_) => None,
+        }
+    }
+
+    /// What `withdrawable_amount` returns at `now`
+    pub fn withdrawable_at(&self, now: u64) -> Option<i128> {
+        if self.cancelled {
+            return Some(0);
+        }
+        Some(lumenpulse_accrual::claimable(
+            self.accrued_at(now)?,
+            self.withdrawn,
+        ))
+    }
+}
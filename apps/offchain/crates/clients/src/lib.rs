@@ -0,0 +1,492 @@
+//! Typed clients for the Lumenpulse contracts.
+//!
+//! `build.rs` reads the spec each contract exports in its WASM and
+//! generates a module per contract: a Rust type for every type in the
+//! spec and a client with one method per entrypoint. A method checks its
+//! arguments at compile time, converts them to `ScVal`s and returns a
+//! `Call`, which simulates or sends the invocation through a
+//! `lumenpulse_tx::Sender` and decodes the return value.
+//!
+//! Entrypoints that return a `Result` return their `Ok` type here; a
+//! contract error fails the simulation and comes back as `Error::Tx`.
+//!
+//! Each module also lists the contract's errors in `ERRORS`, and
+//! `SHARED_ERRORS` lists those of the shared crates such as
+//! `lumenpulse-upgradeable`, so a failed call can be reported by name.
+//!
+//! `StreamData` also computes what a stream has accrued with
+//! `lumenpulse-accrual`, the crate the contract pays out with, so an
+//! amount shown off chain matches the contract without a round trip.
+
+use lumenpulse_tx::Sender;
+use std::{collections::BTreeMap, marker::PhantomData};
+use stellar_xdr::curr::{
+    HostFunction, Int128Parts, ScAddress, ScBytes, ScMap, ScMapEntry, ScString, ScSymbol, ScVal,
+    ScVec, UInt128Parts,
+};
+
+mod accrual;
+
+/// Client for `contracts/streaming`
+pub mod streaming {
+    include!(concat!(env!("OUT_DIR"), "/streaming.rs"));
+}
+
+/// Client for `contracts/escrow`
+pub mod escrow {
+    include!(concat!(env!("OUT_DIR"), "/escrow.rs"));
+}
+
+/// Client for `contracts/subscriptions`
+pub mod subscriptions {
+    include!(concat!(env!("OUT_DIR"), "/subscriptions.rs"));
+}
+
+/// Client for `contracts/upgradable-contract`
+pub mod upgradable {
+    include!(concat!(env!("OUT_DIR"), "/upgradable.rs"));
+}
+
+include!(concat!(env!("OUT_DIR"), "/shared_errors.rs"));
+
+pub use escrow::EscrowClient;
+pub use streaming::StreamingClient;
+pub use subscriptions::SubscriptionsClient;
+pub use upgradable::UpgradableClient;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("expected {0}")]
+    WrongType(&'static str),
+    #[error("invalid XDR: {0}")]
+    Xdr(#[from] stellar_xdr::curr::Error),
+    #[error(transparent)]
+    Tx(#[from] lumenpulse_tx::Error),
+    #[error("the invocation returned no value")]
+    NoReturnValue,
+}
+
+/// One case of an error enum in a contract spec, for reporting a contract
+/// error code by name
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ContractError {
+    pub code: u32,
+    /// Enum and case, such as `StreamError::StreamNotFound`
+    pub name: &'static str,
+    pub doc: &'static str,
+}
+
+impl ContractError {
+    /// The case of `errors` for `code`
+    pub fn find(errors: &[ContractError], code: u32) -> Option<ContractError> {
+        errors.iter().find(|error| error.code == code).copied()
+    }
+}
+
+/// Conversion of a contract argument into the value the host sees
+pub trait ToScVal {
+    fn to_sc_val(&self) -> Result<ScVal, Error>;
+}
+
+/// Conversion of a value the host returns into its Rust type
+pub trait FromScVal: Sized {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error>;
+}
+
+/// A Soroban `Symbol`, as opposed to a `String`
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Symbol(pub String);
+
+impl From<&str> for Symbol {
+    fn from(symbol: &str) -> Self {
+        Symbol(symbol.to_string())
+    }
+}
+
+/// Soroban `Bytes` of any length
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Bytes(pub Vec<u8>);
+
+/// Seconds since the Unix epoch, as a `Timepoint`
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Timepoint(pub u64);
+
+/// A span of seconds, as a `Duration`
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Duration(pub u64);
+
+macro_rules! scalar {
+    ($ty:ty, $variant:ident, $name:literal) => {
+        impl ToScVal for $ty {
+            fn to_sc_val(&self) -> Result<ScVal, Error> {
+                Ok(ScVal::$variant(*self))
+            }
+        }
+
+        impl FromScVal for $ty {
+            fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+                match val {
+                    ScVal::$variant(value) => Ok(*value),
+                    _ => Err(Error::WrongType($name)),
+                }
+            }
+        }
+    };
+}
+
+scalar!(bool, Bool, "bool");
+scalar!(u32, U32, "u32");
+scalar!(i32, I32, "i32");
+scalar!(u64, U64, "u64");
+scalar!(i64, I64, "i64");
+
+impl ToScVal for u128 {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        Ok(ScVal::U128(UInt128Parts {
+            hi: (*self >> 64) as u64,
+            lo: *self as u64,
+        }))
+    }
+}
+
+impl FromScVal for u128 {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        match val {
+            ScVal::U128(parts) => Ok(((parts.hi as u128) << 64) | parts.lo as u128),
+            _ => Err(Error::WrongType("u128")),
+        }
+    }
+}
+
+impl ToScVal for i128 {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        Ok(ScVal::I128(Int128Parts {
+            hi: (*self >> 64) as i64,
+            lo: *self as u64,
+        }))
+    }
+}
+
+impl FromScVal for i128 {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        match val {
+            ScVal::I128(parts) => Ok(((parts.hi as i128) << 64) | parts.lo as i128),
+            _ => Err(Error::WrongType("i128")),
+        }
+    }
+}
+
+impl ToScVal for () {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        Ok(ScVal::Void)
+    }
+}
+
+impl FromScVal for () {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        match val {
+            ScVal::Void => Ok(()),
+            _ => Err(Error::WrongType("void")),
+        }
+    }
+}
+
+impl ToScVal for Timepoint {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        Ok(ScVal::Timepoint(self.0.into()))
+    }
+}
+
+impl FromScVal for Timepoint {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        match val {
+            ScVal::Timepoint(time) => Ok(Timepoint(time.0)),
+            _ => Err(Error::WrongType("timepoint")),
+        }
+    }
+}
+
+impl ToScVal for Duration {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        Ok(ScVal::Duration(self.0.into()))
+    }
+}
+
+impl FromScVal for Duration {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        match val {
+            ScVal::Duration(duration) => Ok(Duration(duration.0)),
+            _ => Err(Error::WrongType("duration")),
+        }
+    }
+}
+
+impl ToScVal for String {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        Ok(ScVal::String(ScString(self.as_str().try_into()?)))
+    }
+}
+
+impl FromScVal for String {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        match val {
+            ScVal::String(string) => Ok(string.0.to_utf8_string_lossy()),
+            _ => Err(Error::WrongType("string")),
+        }
+    }
+}
+
+impl ToScVal for Symbol {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        Ok(ScVal::Symbol(ScSymbol(self.0.as_str().try_into()?)))
+    }
+}
+
+impl FromScVal for Symbol {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        match val {
+            ScVal::Symbol(symbol) => Ok(Symbol(symbol.0.to_utf8_string_lossy())),
+            _ => Err(Error::WrongType("symbol")),
+        }
+    }
+}
+
+impl ToScVal for Bytes {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        Ok(ScVal::Bytes(ScBytes(self.0.clone().try_into()?)))
+    }
+}
+
+impl FromScVal for Bytes {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        match val {
+            ScVal::Bytes(bytes) => Ok(Bytes(bytes.0.to_vec())),
+            _ => Err(Error::WrongType("bytes")),
+        }
+    }
+}
+
+impl<const N: usize> ToScVal for [u8; N] {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        Ok(ScVal::Bytes(ScBytes(self.to_vec().try_into()?)))
+    }
+}
+
+impl<const N: usize> FromScVal for [u8; N] {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        match val {
+            ScVal::Bytes(bytes) => bytes
+                .0
+                .as_slice()
+                .try_into()
+                .map_err(|_| Error::WrongType("fixed-length bytes")),
+            _ => Err(Error::WrongType("bytes")),
+        }
+    }
+}
+
+impl ToScVal for ScAddress {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        Ok(ScVal::Address(self.clone()))
+    }
+}
+
+impl FromScVal for ScAddress {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        match val {
+            ScVal::Address(address) => Ok(address.clone()),
+            _ => Err(Error::WrongType("address")),
+        }
+    }
+}
+
+impl ToScVal for ScVal {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        Ok(self.clone())
+    }
+}
+
+impl FromScVal for ScVal {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        Ok(val.clone())
+    }
+}
+
+impl<T: ToScVal> ToScVal for Option<T> {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        match self {
+            Some(value) => value.to_sc_val(),
+            None => Ok(ScVal::Void),
+        }
+    }
+}
+
+impl<T: FromScVal> FromScVal for Option<T> {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        match val {
+            ScVal::Void => Ok(None),
+            val => T::from_sc_val(val).map(Some),
+        }
+    }
+}
+
+impl<T: ToScVal> ToScVal for [T] {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        vec_val(self.iter().map(T::to_sc_val).collect::<Result<_, _>>()?)
+    }
+}
+
+impl<T: ToScVal> ToScVal for Vec<T> {
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        self.as_slice().to_sc_val()
+    }
+}
+
+impl<T: FromScVal> FromScVal for Vec<T> {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        vec_items(val, "vec")?.iter().map(T::from_sc_val).collect()
+    }
+}
+
+impl<K: ToScVal, V: ToScVal> ToScVal for BTreeMap<K, V> {
+    // Keys sort the same way in Rust and on the host for the key types
+    // contracts use, so the entries are already in the order the host wants
+    fn to_sc_val(&self) -> Result<ScVal, Error> {
+        let entries = self
+            .iter()
+            .map(|(key, val)| {
+                Ok(ScMapEntry {
+                    key: key.to_sc_val()?,
+                    val: val.to_sc_val()?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(ScVal::Map(Some(ScMap(entries.try_into()?))))
+    }
+}
+
+impl<K: FromScVal + Ord, V: FromScVal> FromScVal for BTreeMap<K, V> {
+    fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+        map_entries(val, "map")?
+            .iter()
+            .map(|entry| Ok((K::from_sc_val(&entry.key)?, V::from_sc_val(&entry.val)?)))
+            .collect()
+    }
+}
+
+macro_rules! tuple {
+    ($($ty:ident $index:tt),+) => {
+        impl<$($ty: ToScVal),+> ToScVal for ($($ty,)+) {
+            fn to_sc_val(&self) -> Result<ScVal, Error> {
+                vec_val(vec![$(self.$index.to_sc_val()?),+])
+            }
+        }
+
+        impl<$($ty: FromScVal),+> FromScVal for ($($ty,)+) {
+            fn from_sc_val(val: &ScVal) -> Result<Self, Error> {
+                let items = vec_items(val, "tuple")?;
+                Ok(($(item(items, $index, "tuple")?,)+))
+            }
+        }
+    };
+}
+
+tuple!(A 0);
+tuple!(A 0, B 1);
+tuple!(A 0, B 1, C 2);
+tuple!(A 0, B 1, C 2, D 3);
+
+/// A struct's fields as a map keyed by symbol, which the host requires in
+/// key order
+pub(crate) fn map_val(fields: Vec<(&str, ScVal)>) -> Result<ScVal, Error> {
+    let entries = fields
+        .into_iter()
+        .map(|(name, val)| {
+            Ok(ScMapEntry {
+                key: ScVal::Symbol(ScSymbol(name.try_into()?)),
+                val,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(ScVal::Map(Some(ScMap(entries.try_into()?))))
+}
+
+pub(crate) fn map_entries<'a>(
+    val: &'a ScVal,
+    name: &'static str,
+) -> Result<&'a [ScMapEntry], Error> {
+    match val {
+        ScVal::Map(Some(map)) => Ok(map.0.as_slice()),
+        _ => Err(Error::WrongType(name)),
+    }
+}
+
+/// The field called `name` in a struct's map
+pub(crate) fn field<T: FromScVal>(entries: &[ScMapEntry], name: &'static str) -> Result<T, Error> {
+    let entry = entries
+        .iter()
+        .find(|entry| matches!(&entry.key, ScVal::Symbol(symbol) if symbol.0.as_slice() == name.as_bytes()))
+        .ok_or(Error::WrongType(name))?;
+    T::from_sc_val(&entry.val)
+}
+
+pub(crate) fn vec_val(items: Vec<ScVal>) -> Result<ScVal, Error> {
+    Ok(ScVal::Vec(Some(ScVec(items.try_into()?))))
+}
+
+pub(crate) fn vec_items<'a>(val: &'a ScVal, name: &'static str) -> Result<&'a [ScVal], Error> {
+    match val {
+        ScVal::Vec(Some(vec)) => Ok(vec.0.as_slice()),
+        _ => Err(Error::WrongType(name)),
+    }
+}
+
+pub(crate) fn item<T: FromScVal>(
+    items: &[ScVal],
+    index: usize,
+    name: &'static str,
+) -> Result<T, Error> {
+    T::from_sc_val(items.get(index).ok_or(Error::WrongType(name))?)
+}
+
+/// An invocation of a contract entrypoint that returns an `R`
+#[derive(Clone, Debug)]
+pub struct Call<R> {
+    pub host_function: HostFunction,
+    returns: PhantomData<fn() -> R>,
+}
+
+impl<R: FromScVal> Call<R> {
+    pub(crate) fn new(
+        contract: &ScAddress,
+        function: &str,
+        args: Vec<ScVal>,
+    ) -> Result<Self, Error> {
+        Ok(Call {
+            host_function: lumenpulse_tx::invoke_contract(contract, function, args)?,
+            returns: PhantomData,
+        })
+    }
+
+    /// What the call would return against the latest ledger, without
+    /// sending it
+    pub fn simulate(self, sender: &Sender) -> Result<R, Error> {
+        let prepared = sender.prepare(self.host_function)?;
+        Self::decode(prepared.simulation.return_value.as_ref())
+    }
+
+    /// Send the call and return what it returned once it lands
+    pub fn send(self, sender: &Sender) -> Result<R, Error> {
+        let prepared = sender.prepare(self.host_function)?;
+        let submitted = sender.submit(prepared.tx)?;
+        Self::decode(submitted.info.return_value())
+    }
+
+    /// The return value `val` as an `R`
+    pub fn decode(val: Option<&ScVal>) -> Result<R, Error> {
+        R::from_sc_val(val.ok_or(Error::NoReturnValue)?)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,103 @@
+use crate::streaming::{StreamData, StreamKind, StreamRate};
+use crate::{Call, Error, FromScVal, StreamingClient, ToScVal};
+use stellar_xdr::curr::{ContractId, Hash, HostFunction, ScAddress, ScVal};
+
+fn address(byte: u8) -> ScAddress {
+    ScAddress::Contract(ContractId(Hash([byte; 32])))
+}
+
+/// What `get_stream` returned in the streaming contract's golden transcript
+fn golden_stream() -> ScVal {
+    let transcript =
+        include_str!("../../../../onchain/contracts/streaming/goldens/stream_lifecycle.json");
+    let calls: Vec<serde_json::Value> = serde_json::from_str(transcript).unwrap();
+    let call = calls
+        .into_iter()
+        .find(|call| call["call"] == "get_stream")
+        .unwrap();
+    serde_json::from_value(call["returned"].clone()).unwrap()
+}
+
+#[test]
+fn methods_build_the_invocation_from_typed_arguments() {
+    let client = StreamingClient::new(address(1));
+    let call = client
+        .create_stream(&address(2), &address(3), &address(4), 1_000, 100, 200)
+        .unwrap();
+
+    let HostFunction::InvokeContract(invoke) = call.host_function else {
+        panic!("not a contract invocation");
+    };
+    assert_eq!(invoke.contract_address, address(1));
+    assert_eq!(
+        invoke.function_name.0.to_utf8_string_lossy(),
+        "create_stream"
+    );
+    assert_eq!(
+        invoke.args.to_vec(),
+        vec![
+            ScVal::Address(address(2)),
+            ScVal::Address(address(3)),
+            ScVal::Address(address(4)),
+            1_000i128.to_sc_val().unwrap(),
+            ScVal::U64(100),
+            ScVal::U64(200),
+        ]
+    );
+}
+
+#[test]
+fn return_values_decode_into_the_contract_types() {
+    let stream: StreamData = Call::decode(Some(&golden_stream())).unwrap();
+    assert_eq!(stream.id, 0);
+    assert_eq!(stream.deposit, 1_000_000);
+    assert_eq!(stream.end_time, 1_100);
+    assert_eq!(stream.kind, StreamKind::Linear);
+    assert_eq!(stream.max_total, None);
+    assert!(stream.cancelled);
+
+    // Structs encode back to the map they came from
+    assert_eq!(stream.to_sc_val().unwrap(), golden_stream());
+    assert!(matches!(
+        Call::<StreamData>::decode(None),
+        Err(Error::NoReturnValue)
+    ));
+    assert!(matches!(
+        u64::from_sc_val(&golden_stream()),
+        Err(Error::WrongType("u64"))
+    ));
+}
+
+#[test]
+fn unions_round_trip_with_their_values() {
+    let kind = StreamKind::Rate(StreamRate {
+        amount: -5,
+        period: 60,
+    });
+    let val = kind.to_sc_val().unwrap();
+    assert_eq!(StreamKind::from_sc_val(&val).unwrap(), kind);
+}
+
+#[test]
+fn streams_accrue_as_the_contract_pays() {
+    let mut stream: StreamData = Call::decode(Some(&golden_stream())).unwrap();
+    stream.cancelled = false;
+    stream.withdrawn = 0;
+
+    // The golden stream runs from 100 to 1_100 and withdrew half at 600
+    assert_eq!(stream.start_time, 100);
+    assert_eq!(stream.accrued_at(50), Some(0));
+    assert_eq!(stream.accrued_at(600), Some(500_000));
+    assert_eq!(stream.accrued_at(2_000), Some(1_000_000));
+    stream.withdrawn = 500_000;
+    assert_eq!(stream.withdrawable_at(850), Some(250_000));
+    stream.cancelled = true;
+    assert_eq!(stream.withdrawable_at(850), Some(0));
+
+    stream.cancelled = false;
+    stream.kind = StreamKind::Rate(StreamRate {
+        amount: 10,
+        period: 60,
+    });
+    assert_eq!(stream.accrued_at(130), Some(5));
+}
@@ -0,0 +1,284 @@
+//! Deployment of the contracts from a declarative manifest.
+//!
+//! For each contract in the manifest, in order, the deployer installs its
+//! WASM unless the network has it, creates the contract unless it exists,
+//! and sends its initializing call unless that went through before. A
+//! contract's address follows from the network, the deploying account and
+//! the contract's salt, so it is known before anything is sent and
+//! running the deployer again creates nothing twice. Every address is
+//! written to the lockfile as soon as it exists, so a run that stops
+//! halfway picks up where it left off.
+//!
+//! The deployer only ever creates contracts. One that already runs other
+//! WASM than the manifest's is left as it is, to be upgraded through its
+//! own upgrade delay with `lumenpulse propose-upgrade`.
+
+pub mod lock;
+pub mod manifest;
+
+pub use lock::{Deployment, LockError, Lockfile, NetworkLock};
+pub use manifest::{Arg, Manifest, ManifestError, Network};
+
+use lumenpulse_tx::{network, Sender};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, path::PathBuf};
+use stellar_xdr::curr::{
+    ContractDataDurability, ContractExecutable, Hash, HostFunction, LedgerEntryData, LedgerKey,
+    LedgerKeyContractCode, LedgerKeyContractData, ScAddress, ScVal,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] lumenpulse_tx::Error),
+    #[error(transparent)]
+    Rpc(#[from] lumenpulse_rpc::Error),
+    #[error(transparent)]
+    Lock(#[from] LockError),
+    #[error("reading {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{0} is not deployed yet")]
+    NotDeployed(String),
+}
+
+/// The network contracts are deployed to, through an RPC in production
+pub trait Chain {
+    /// Whether WASM with `hash` is installed
+    fn installed(&self, hash: &Hash) -> Result<bool, Error>;
+    /// Hash of the WASM the contract at `contract` runs, if there is one
+    fn deployed(&self, contract: &ScAddress) -> Result<Option<Hash>, Error>;
+    /// Send `host_function` from the deploying account and wait for it to
+    /// land
+    fn send(&self, host_function: HostFunction) -> Result<(), Error>;
+}
+
+impl Chain for Sender<'_> {
+    fn installed(&self, hash: &Hash) -> Result<bool, Error> {
+        let key = LedgerKey::ContractCode(LedgerKeyContractCode { hash: hash.clone() });
+        Ok(!self.client().get_ledger_entries(&[key])?.is_empty())
+    }
+
+    fn deployed(&self, contract: &ScAddress) -> Result<Option<Hash>, Error> {
+        let key = LedgerKey::ContractData(LedgerKeyContractData {
+            contract: contract.clone(),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+        });
+        let instance = self.client().get_ledger_entries(&[key])?.pop();
+        Ok(instance.and_then(|instance| match instance.data {
+            LedgerEntryData::ContractData(data) => match data.val {
+                ScVal::ContractInstance(instance) => match instance.executable {
+                    ContractExecutable::Wasm(hash) => Some(hash),
+                    ContractExecutable::StellarAsset => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }))
+    }
+
+    fn send(&self, host_function: HostFunction) -> Result<(), Error> {
+        let preflight = lumenpulse_preflight::preflight(self, host_function, &[])?;
+        eprint!("{}", preflight.report);
+        let submitted = self.submit(preflight.prepare()?.tx)?;
+        eprintln!(
+            "  included in ledger {}: {}",
+            submitted.info.ledger.unwrap_or_default(),
+            submitted.hash
+        );
+        Ok(())
+    }
+}
+
+/// Salt a contract's salt text stands for
+pub fn salt(text: &str) -> [u8; 32] {
+    Sha256::digest(text.as_bytes()).into()
+}
+
+/// What deploying one contract took
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Outcome {
+    pub name: String,
+    /// Contract address (`C...`)
+    pub id: String,
+    /// Whether its WASM had to be installed
+    pub installed: bool,
+    /// Whether the contract had to be created
+    pub created: bool,
+    /// Whether its initializing call was sent
+    pub initialized: bool,
+    /// Hash of the WASM it runs when that is not the manifest's, because
+    /// it was upgraded or the manifest's WASM changed since
+    pub runs_other_wasm: Option<String>,
+}
+
+pub struct Deployer<C> {
+    chain: C,
+    network: Network,
+    network_id: Hash,
+    deployer: ScAddress,
+}
+
+impl<C: Chain> Deployer<C> {
+    /// Deploy to `network` from `deployer`, the account `chain` sends from
+    pub fn new(chain: C, network: &Network, deployer: ScAddress) -> Self {
+        Deployer {
+            chain,
+            network_id: network::network_id(&network.passphrase),
+            network: network.clone(),
+            deployer,
+        }
+    }
+
+    /// Address the contract with salt text `salt` gets
+    pub fn address(&self, salt: &str) -> Result<ScAddress, Error> {
+        Ok(lumenpulse_tx::created_address(
+            &self.network_id,
+            &self.deployer,
+            self::salt(salt),
+        )?)
+    }
+
+    /// Deploy every contract in `manifest` that is not deployed yet,
+    /// recording them in `lockfile` and calling `save` whenever it changes
+    pub fn deploy(
+        &self,
+        manifest: &Manifest,
+        lockfile: &mut Lockfile,
+        mut save: impl FnMut(&Lockfile) -> Result<(), Error>,
+    ) -> Result<Vec<Outcome>, Error> {
+        let locked = lockfile
+            .networks
+            .entry(self.network.name.clone())
+            .or_default();
+        locked.passphrase = self.network.passphrase.clone();
+        locked.deployer = self.deployer.to_string();
+        manifest
+            .contracts
+            .iter()
+            .map(|contract| self.deploy_contract(contract, lockfile, &mut save))
+            .collect()
+    }
+
+    fn deploy_contract(
+        &self,
+        contract: &manifest::Contract,
+        lockfile: &mut Lockfile,
+        save: &mut impl FnMut(&Lockfile) -> Result<(), Error>,
+    ) -> Result<Outcome, Error> {
+        let wasm = std::fs::read(&contract.wasm).map_err(|source| Error::Io {
+            path: contract.wasm.clone(),
+            source,
+        })?;
+        let hash = lumenpulse_tx::wasm_hash(&wasm);
+        let address = self.address(&contract.salt)?;
+        let mut outcome = Outcome {
+            name: contract.name.clone(),
+            id: address.to_string(),
+            installed: false,
+            created: false,
+            initialized: false,
+            runs_other_wasm: None,
+        };
+
+        let runs = match self.chain.deployed(&address)? {
+            Some(runs) => runs,
+            None => {
+                if !self.chain.installed(&hash)? {
+                    eprintln!("installing {} WASM {hash}", contract.name);
+                    self.chain.send(lumenpulse_tx::upload_wasm(&wasm)?)?;
+                    outcome.installed = true;
+                }
+                eprintln!("creating {} at {}", contract.name, outcome.id);
+                let salt = salt(&contract.salt);
+                self.chain.send(lumenpulse_tx::create_contract(
+                    &self.deployer,
+                    salt,
+                    hash.clone(),
+                ))?;
+                outcome.created = true;
+                hash.clone()
+            }
+        };
+        if runs != hash {
+            outcome.runs_other_wasm = Some(runs.to_string());
+        }
+
+        // A contract found at its address with no record of it was created
+        // by a run that stopped before saving, so it still needs its call
+        let initialized = !outcome.created
+            && self
+                .locked(lockfile)
+                .get(&contract.name)
+                .is_some_and(|locked| locked.id == outcome.id && locked.initialized);
+        let mut deployment = Deployment {
+            id: outcome.id.clone(),
+            wasm_hash: runs.to_string(),
+            salt: contract.salt.clone(),
+            initialized: initialized || contract.init.is_none(),
+        };
+        self.record(lockfile, &contract.name, deployment.clone(), save)?;
+
+        if let (false, Some(init)) = (deployment.initialized, &contract.init) {
+            let args = init
+                .args
+                .iter()
+                .map(|arg| self.arg(arg, lockfile))
+                .collect::<Result<_, _>>()?;
+            eprintln!("initializing {} with {}", contract.name, init.function);
+            self.chain.send(lumenpulse_tx::invoke_contract(
+                &address,
+                &init.function,
+                args,
+            )?)?;
+            outcome.initialized = true;
+            deployment.initialized = true;
+            self.record(lockfile, &contract.name, deployment, save)?;
+        }
+        Ok(outcome)
+    }
+
+    fn locked<'l>(&self, lockfile: &'l Lockfile) -> &'l BTreeMap<String, Deployment> {
+        &lockfile.networks[&self.network.name].contracts
+    }
+
+    fn record(
+        &self,
+        lockfile: &mut Lockfile,
+        name: &str,
+        deployment: Deployment,
+        save: &mut impl FnMut(&Lockfile) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let locked = lockfile
+            .networks
+            .get_mut(&self.network.name)
+            .expect("deploy adds the network");
+        if locked.contracts.get(name) != Some(&deployment) {
+            locked.contracts.insert(name.to_string(), deployment);
+            save(lockfile)?;
+        }
+        Ok(())
+    }
+
+    fn arg(&self, arg: &Arg, lockfile: &Lockfile) -> Result<ScVal, Error> {
+        Ok(match arg {
+            Arg::Deployer => ScVal::Address(self.deployer.clone()),
+            Arg::Contract(name) => {
+                let id = lockfile
+                    .contract_id(&self.network.name, name)
+                    .ok_or_else(|| Error::NotDeployed(name.clone()))?;
+                ScVal::Address(
+                    lumenpulse_tx::contract_address(id)
+                        .ok_or_else(|| Error::NotDeployed(name.clone()))?,
+                )
+            }
+            Arg::Value(value) => value.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test;
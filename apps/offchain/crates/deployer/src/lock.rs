@@ -0,0 +1,89 @@
+//! Where the contracts were deployed, kept in a JSON lockfile.
+//!
+//! The deployer writes the lockfile and the other tools read it, so they
+//! can name a contract, such as `streaming`, instead of giving its
+//! address on every network. It is meant to be committed next to the
+//! manifest.
+
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, io::ErrorKind};
+
+pub const DEFAULT_LOCKFILE: &str = "lumenpulse.lock.json";
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum LockError {
+    #[error("cannot read {path}: {reason}")]
+    Read { path: String, reason: String },
+    #[error("cannot write {path}: {reason}")]
+    Write { path: String, reason: String },
+    #[error("invalid lockfile {path}: {reason}")]
+    Parse { path: String, reason: String },
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Lockfile {
+    pub networks: BTreeMap<String, NetworkLock>,
+}
+
+/// The contracts on one network
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkLock {
+    pub passphrase: String,
+    /// Account that deployed the contracts (`G...`)
+    pub deployer: String,
+    pub contracts: BTreeMap<String, Deployment>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Deployment {
+    /// Contract address (`C...`)
+    pub id: String,
+    /// Hex hash of the WASM the contract ran as of the last deploy
+    pub wasm_hash: String,
+    pub salt: String,
+    /// Whether its initializing call went through, or it needs none
+    pub initialized: bool,
+}
+
+impl Lockfile {
+    /// The lockfile at `path`, or an empty one if there is none yet
+    pub fn read(path: &str) -> Result<Self, LockError> {
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Lockfile::default()),
+            Err(err) => {
+                return Err(LockError::Read {
+                    path: path.to_string(),
+                    reason: err.to_string(),
+                })
+            }
+        };
+        serde_json::from_str(&json).map_err(|err| LockError::Parse {
+            path: path.to_string(),
+            reason: err.to_string(),
+        })
+    }
+
+    /// Write the lockfile to `path`, replacing it whole so a reader never
+    /// sees half of it
+    pub fn write(&self, path: &str) -> Result<(), LockError> {
+        let error = |err: std::io::Error| LockError::Write {
+            path: path.to_string(),
+            reason: err.to_string(),
+        };
+        let mut json = serde_json::to_string_pretty(self).expect("a lockfile serializes");
+        json.push('\n');
+        let partial = format!("{path}.partial");
+        std::fs::write(&partial, json).map_err(error)?;
+        std::fs::rename(&partial, path).map_err(error)
+    }
+
+    /// Address of the contract called `name` on `network`
+    pub fn contract_id(&self, network: &str, name: &str) -> Option<&str> {
+        let deployment = self.networks.get(network)?.contracts.get(name)?;
+        Some(&deployment.id)
+    }
+}
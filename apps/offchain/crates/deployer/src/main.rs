@@ -0,0 +1,74 @@
+use clap::Parser;
+use lumenpulse_deployer::{
+    lock::DEFAULT_LOCKFILE, manifest::DEFAULT_MANIFEST, Deployer, Lockfile, Manifest,
+};
+use lumenpulse_rpc::Client;
+use lumenpulse_tx::{Sender, Signer};
+use std::process::ExitCode;
+
+/// Deploy the contracts in a manifest to one of its networks and record
+/// their addresses in the lockfile
+#[derive(Debug, Parser)]
+#[command(name = "lumenpulse-deploy")]
+struct Cli {
+    /// Network in the manifest to deploy to
+    #[arg(long, env = "LUMENPULSE_NETWORK")]
+    network: String,
+    #[arg(long, env = "LUMENPULSE_DEPLOY_MANIFEST", default_value = DEFAULT_MANIFEST)]
+    manifest: String,
+    #[arg(long, env = "LUMENPULSE_LOCKFILE", default_value = DEFAULT_LOCKFILE)]
+    lockfile: String,
+    /// Environment variable holding the secret key (`S...`) that deploys
+    /// and pays
+    #[arg(long, default_value = "LUMENPULSE_SECRET_KEY")]
+    secret_key_env: String,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("lumenpulse-deploy: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let manifest = Manifest::from_file(&cli.manifest)?;
+    let network = manifest
+        .network(&cli.network)
+        .ok_or_else(|| format!("{} has no network {}", cli.manifest, cli.network))?;
+    let secret = std::env::var(&cli.secret_key_env)
+        .map_err(|_| format!("{} is not set", cli.secret_key_env))?;
+    let signer = Signer::from_secret(&secret)?;
+    let client = Client::new(&network.rpc_url);
+    let sender = Sender::new(&client, &signer, &network.passphrase);
+
+    let mut lockfile = Lockfile::read(&cli.lockfile)?;
+    eprintln!(
+        "deploying {} contracts to {} from {}",
+        manifest.contracts.len(),
+        network.name,
+        signer.account()
+    );
+    let deployer = Deployer::new(sender, network, signer.address());
+    let outcomes = deployer.deploy(&manifest, &mut lockfile, |lockfile| {
+        Ok(lockfile.write(&cli.lockfile)?)
+    })?;
+    for outcome in outcomes {
+        let done = match (outcome.created, outcome.initialized) {
+            (true, _) => "created",
+            (false, true) => "initialized",
+            (false, false) => "unchanged",
+        };
+        println!("{} {} {done}", outcome.name, outcome.id);
+        if let Some(hash) = outcome.runs_other_wasm {
+            eprintln!(
+                "  {} runs WASM {hash}, not the manifest's; upgrade it with `lumenpulse propose-upgrade`",
+                outcome.name
+            );
+        }
+    }
+    Ok(())
+}
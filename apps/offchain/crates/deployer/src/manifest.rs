@@ -0,0 +1,180 @@
+//! What to deploy, read from a JSON manifest.
+//!
+//! The manifest lists the networks the contracts go to and the contracts
+//! in the order they are deployed, each with its WASM, its salt and the
+//! call that initializes it. A network named after a preset in
+//! `lumenpulse_tx::network` may leave out its RPC and passphrase. WASM
+//! paths are relative to the manifest.
+
+use lumenpulse_tx::network;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use stellar_xdr::curr::ScVal;
+
+pub const DEFAULT_MANIFEST: &str = "lumenpulse.deploy.json";
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ManifestError {
+    #[error("cannot read {path}: {reason}")]
+    Read { path: String, reason: String },
+    #[error("invalid manifest: {0}")]
+    Parse(String),
+    #[error("{name} is invalid: {reason}")]
+    Invalid { name: String, reason: String },
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub networks: Vec<Network>,
+    pub contracts: Vec<Contract>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Network {
+    pub name: String,
+    /// The preset's when left out
+    #[serde(default)]
+    pub rpc_url: String,
+    /// The preset's when left out
+    #[serde(default)]
+    pub passphrase: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Contract {
+    /// What the lockfile calls the contract, such as `streaming`
+    pub name: String,
+    pub wasm: PathBuf,
+    /// Any text; its SHA-256 hash is the salt. With the deploying account it
+    /// fixes the contract's address, so changing it deploys a new contract.
+    pub salt: String,
+    /// Left out for contracts that need no initializing
+    #[serde(default)]
+    pub init: Option<Init>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Init {
+    #[serde(default = "default_function")]
+    pub function: String,
+    #[serde(default)]
+    pub args: Vec<Arg>,
+}
+
+/// An argument to an initializing call
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(try_from = "Value")]
+pub enum Arg {
+    /// `"deployer"`, the deploying account's address
+    Deployer,
+    /// `{"contract": "<name>"}`, the address of a contract deployed before
+    /// this one
+    Contract(String),
+    /// Any other value, as `stellar-xdr` writes an `ScVal` in JSON, such as
+    /// `{"u32": 7}` or `{"address": "GA..."}`
+    Value(ScVal),
+}
+
+impl TryFrom<Value> for Arg {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        if value == "deployer" {
+            return Ok(Arg::Deployer);
+        }
+        if let Some(object) = value.as_object() {
+            if let (1, Some(Value::String(name))) = (object.len(), object.get("contract")) {
+                return Ok(Arg::Contract(name.clone()));
+            }
+        }
+        serde_json::from_value(value.clone())
+            .map(Arg::Value)
+            .map_err(|err| format!("`{value}` is not an argument: {err}"))
+    }
+}
+
+impl Manifest {
+    pub fn from_file(path: &str) -> Result<Self, ManifestError> {
+        let json = std::fs::read_to_string(path).map_err(|err| ManifestError::Read {
+            path: path.to_string(),
+            reason: err.to_string(),
+        })?;
+        let mut manifest = Self::from_json(&json)?;
+        let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+        for contract in &mut manifest.contracts {
+            contract.wasm = dir.join(&contract.wasm);
+        }
+        Ok(manifest)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ManifestError> {
+        let mut manifest: Manifest =
+            serde_json::from_str(json).map_err(|err| ManifestError::Parse(err.to_string()))?;
+        if manifest.networks.is_empty() {
+            return Err(ManifestError::Parse("no networks".to_string()));
+        }
+        for (i, network) in manifest.networks.iter().enumerate() {
+            if manifest.networks[..i]
+                .iter()
+                .any(|n| n.name == network.name)
+            {
+                return Err(ManifestError::Invalid {
+                    name: network.name.clone(),
+                    reason: "is listed twice".to_string(),
+                });
+            }
+        }
+        for network in &mut manifest.networks {
+            let preset = network::preset(&network.name);
+            let name = network.name.clone();
+            let invalid = |reason: &str| ManifestError::Invalid {
+                name: name.clone(),
+                reason: reason.to_string(),
+            };
+            if network.rpc_url.is_empty() {
+                network.rpc_url = preset
+                    .and_then(|preset| preset.rpc_url)
+                    .ok_or_else(|| invalid("needs an rpc_url"))?
+                    .to_string();
+            }
+            if network.passphrase.is_empty() {
+                network.passphrase = preset
+                    .map(|preset| preset.passphrase.to_string())
+                    .ok_or_else(|| invalid("needs a passphrase"))?;
+            }
+        }
+        for (i, contract) in manifest.contracts.iter().enumerate() {
+            let earlier = &manifest.contracts[..i];
+            let invalid = |reason: String| ManifestError::Invalid {
+                name: contract.name.clone(),
+                reason,
+            };
+            if earlier.iter().any(|c| c.name == contract.name) {
+                return Err(invalid("is listed twice".to_string()));
+            }
+            for arg in contract.init.iter().flat_map(|init| &init.args) {
+                if let Arg::Contract(name) = arg {
+                    if !earlier.iter().any(|c| &c.name == name) {
+                        return Err(invalid(format!(
+                            "refers to `{name}`, which is not deployed before it"
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(manifest)
+    }
+
+    pub fn network(&self, name: &str) -> Option<&Network> {
+        self.networks.iter().find(|network| network.name == name)
+    }
+}
+
+fn default_function() -> String {
+    "initialize".to_string()
+}
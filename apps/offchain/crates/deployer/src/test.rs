@@ -0,0 +1,299 @@
+use crate::{Arg, Chain, Deployer, Error, Lockfile, Manifest, ManifestError};
+use lumenpulse_tx::network::network_id;
+use std::{cell::RefCell, collections::BTreeMap, path::PathBuf};
+use stellar_xdr::curr::{
+    ContractExecutable, ContractIdPreimage, Hash, HostFunction, ScAddress, ScString, ScVal,
+};
+
+const DEPLOYER: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+/// A network that runs whatever it is sent, failing the sends it is told
+/// to fail
+#[derive(Default)]
+struct FakeChain {
+    installed: RefCell<Vec<Hash>>,
+    deployed: RefCell<BTreeMap<String, Hash>>,
+    /// What was sent, as `upload`, `create` or the function called
+    sent: RefCell<Vec<(String, Vec<ScVal>)>>,
+    fail: RefCell<Option<String>>,
+}
+
+impl Chain for &FakeChain {
+    fn installed(&self, hash: &Hash) -> Result<bool, Error> {
+        Ok(self.installed.borrow().contains(hash))
+    }
+
+    fn deployed(&self, contract: &ScAddress) -> Result<Option<Hash>, Error> {
+        Ok(self.deployed.borrow().get(&contract.to_string()).cloned())
+    }
+
+    fn send(&self, host_function: HostFunction) -> Result<(), Error> {
+        let sent = match host_function {
+            HostFunction::UploadContractWasm(wasm) => {
+                let hash = lumenpulse_tx::wasm_hash(&wasm);
+                self.installed.borrow_mut().push(hash);
+                ("upload".to_string(), Vec::new())
+            }
+            HostFunction::CreateContract(args) => {
+                let (ContractIdPreimage::Address(preimage), ContractExecutable::Wasm(hash)) =
+                    (args.contract_id_preimage, args.executable)
+                else {
+                    unreachable!()
+                };
+                assert!(self.installed.borrow().contains(&hash));
+                let address = lumenpulse_tx::created_address(
+                    &network_id(PASSPHRASE),
+                    &preimage.address,
+                    preimage.salt.0,
+                )
+                .unwrap();
+                self.deployed.borrow_mut().insert(address.to_string(), hash);
+                ("create".to_string(), Vec::new())
+            }
+            HostFunction::InvokeContract(args) => (
+                args.function_name.0.to_utf8_string().unwrap(),
+                args.args.to_vec(),
+            ),
+            _ => unreachable!(),
+        };
+        if self.fail.borrow().as_deref() == Some(&sent.0) {
+            return Err(lumenpulse_rpc::Error::Transport("the fake failed".to_string()).into());
+        }
+        self.sent.borrow_mut().push(sent);
+        Ok(())
+    }
+}
+
+impl FakeChain {
+    fn sent(&self) -> Vec<String> {
+        let sent = self.sent.take();
+        sent.into_iter().map(|(function, _)| function).collect()
+    }
+}
+
+/// A file holding `contents` as a contract's WASM
+fn wasm(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("lumenpulse-deployer-test-{name}.wasm"));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+/// A token, a second token from the same WASM, and a streaming contract
+/// initialized with the first token, with WASM files of their own for
+/// each `test`
+fn manifest(test: &str) -> Manifest {
+    let token = wasm(&format!("{test}-token"), b"token");
+    let streaming = wasm(&format!("{test}-streaming"), b"streaming");
+    Manifest::from_json(&format!(
+        r#"{{
+            "networks": [{{ "name": "testnet" }}],
+            "contracts": [
+                {{ "name": "token", "wasm": {token:?}, "salt": "token-1",
+                   "init": {{ "args": ["deployer", {{ "u32": 7 }}, {{ "string": "Pulse" }}] }} }},
+                {{ "name": "token_b", "wasm": {token:?}, "salt": "token-b-1" }},
+                {{ "name": "streaming", "wasm": {streaming:?}, "salt": "streaming-1",
+                   "init": {{ "function": "init", "args": [{{ "contract": "token" }}] }} }}
+            ]
+        }}"#
+    ))
+    .unwrap()
+}
+
+fn deployer<'a>(chain: &'a FakeChain, manifest: &Manifest) -> Deployer<&'a FakeChain> {
+    let network = manifest.network("testnet").unwrap();
+    Deployer::new(chain, network, DEPLOYER.parse().unwrap())
+}
+
+#[test]
+fn deploys_in_order_and_records_every_address() {
+    let chain = FakeChain::default();
+    let manifest = manifest("deploys");
+    let deployer = deployer(&chain, &manifest);
+    let mut lockfile = Lockfile::default();
+    let mut saves = 0;
+    let outcomes = deployer
+        .deploy(&manifest, &mut lockfile, |_| {
+            saves += 1;
+            Ok(())
+        })
+        .unwrap();
+
+    // The second token reuses the WASM the first installed
+    let sent = chain.sent.borrow().clone();
+    let functions: Vec<_> = sent.iter().map(|(function, _)| function.as_str()).collect();
+    assert_eq!(
+        functions,
+        [
+            "upload",
+            "create",
+            "initialize",
+            "create",
+            "upload",
+            "create",
+            "init"
+        ]
+    );
+    let token = lockfile.contract_id("testnet", "token").unwrap();
+    assert_eq!(
+        sent[2].1,
+        [
+            ScVal::Address(DEPLOYER.parse().unwrap()),
+            ScVal::U32(7),
+            ScVal::String(ScString("Pulse".try_into().unwrap())),
+        ]
+    );
+    assert_eq!(sent[6].1, [ScVal::Address(token.parse().unwrap())]);
+
+    assert_eq!(outcomes.len(), 3);
+    assert!(outcomes[0].installed && outcomes[0].created && outcomes[0].initialized);
+    assert!(!outcomes[1].installed && outcomes[1].created && !outcomes[1].initialized);
+    assert_eq!(outcomes[1].runs_other_wasm, None);
+    // As the network derives it from the deployer, the salt and testnet's
+    // ID
+    let address = "CC5T76B32SDIX2ENEOK4PXEUAIFLNVXWE6E3YEF5BJ2TU52456SF62EI";
+    assert_eq!(deployer.address("token-1").unwrap().to_string(), address);
+    assert_eq!(outcomes[0].id, address);
+    assert_eq!(token, address);
+
+    let locked = &lockfile.networks["testnet"];
+    assert_eq!(locked.passphrase, PASSPHRASE);
+    assert_eq!(locked.deployer, DEPLOYER);
+    assert!(locked
+        .contracts
+        .values()
+        .all(|contract| contract.initialized));
+    assert_eq!(
+        locked.contracts["token_b"].wasm_hash,
+        lumenpulse_tx::wasm_hash(b"token").to_string()
+    );
+    // Created and initialized contracts each save once more
+    assert_eq!(saves, 5);
+}
+
+#[test]
+fn a_later_run_resumes_and_sends_nothing_twice() {
+    let chain = FakeChain::default();
+    let manifest = manifest("resumes");
+    let deployer = deployer(&chain, &manifest);
+    let mut lockfile = Lockfile::default();
+    *chain.fail.borrow_mut() = Some("init".to_string());
+    let mut saved = Lockfile::default();
+    let err = deployer.deploy(&manifest, &mut lockfile, |lockfile| {
+        saved = lockfile.clone();
+        Ok(())
+    });
+    assert!(err.is_err());
+    // Streaming was created and saved before its call failed
+    assert!(!saved.networks["testnet"].contracts["streaming"].initialized);
+    chain.sent();
+
+    *chain.fail.borrow_mut() = None;
+    let outcomes = deployer.deploy(&manifest, &mut saved, |_| Ok(())).unwrap();
+    assert_eq!(chain.sent(), ["init"]);
+    assert!(outcomes[2].initialized && !outcomes[2].created);
+    deployer.deploy(&manifest, &mut saved, |_| Ok(())).unwrap();
+    assert_eq!(chain.sent(), Vec::<String>::new());
+
+    // A network that was reset, as futurenet is, gets everything again
+    let reset = FakeChain::default();
+    let deployer = self::deployer(&reset, &manifest);
+    deployer.deploy(&manifest, &mut saved, |_| Ok(())).unwrap();
+    assert_eq!(reset.sent().len(), 7);
+}
+
+#[test]
+fn upgraded_contracts_are_left_alone() {
+    let chain = FakeChain::default();
+    let manifest = manifest("upgraded");
+    let deployer = deployer(&chain, &manifest);
+    let mut lockfile = Lockfile::default();
+    deployer
+        .deploy(&manifest, &mut lockfile, |_| Ok(()))
+        .unwrap();
+    chain.sent();
+
+    // The token was upgraded since, and the manifest's streaming WASM
+    // changed
+    let upgraded = Hash([9; 32]);
+    let token = lockfile
+        .contract_id("testnet", "token")
+        .unwrap()
+        .to_string();
+    chain.deployed.borrow_mut().insert(token, upgraded.clone());
+    wasm("upgraded-streaming", b"streaming v2");
+    let outcomes = deployer
+        .deploy(&manifest, &mut lockfile, |_| Ok(()))
+        .unwrap();
+    assert_eq!(chain.sent(), Vec::<String>::new());
+    assert_eq!(outcomes[0].runs_other_wasm, Some(upgraded.to_string()));
+    assert_eq!(
+        outcomes[2].runs_other_wasm,
+        Some(lumenpulse_tx::wasm_hash(b"streaming").to_string())
+    );
+    assert_eq!(
+        lockfile.networks["testnet"].contracts["token"].wasm_hash,
+        upgraded.to_string()
+    );
+}
+
+#[test]
+fn manifests_and_lockfiles_are_checked() {
+    let manifest = manifest("checked");
+    let testnet = manifest.network("testnet").unwrap();
+    assert_eq!(testnet.rpc_url, "https://soroban-testnet.stellar.org");
+    assert_eq!(testnet.passphrase, PASSPHRASE);
+    assert_eq!(
+        manifest.contracts[2].init.as_ref().unwrap().args,
+        [Arg::Contract("token".to_string())]
+    );
+    assert_eq!(
+        manifest.contracts[0].init.as_ref().unwrap().function,
+        "initialize"
+    );
+
+    let invalid = |json: &str| Manifest::from_json(json).unwrap_err();
+    assert_eq!(
+        invalid(r#"{"networks": [{"name": "mainnet"}], "contracts": []}"#),
+        ManifestError::Invalid {
+            name: "mainnet".to_string(),
+            reason: "needs an rpc_url".to_string()
+        }
+    );
+    assert!(matches!(
+        invalid(r#"{"networks": [{"name": "local", "rpc_url": "http://localhost:8000"}], "contracts": []}"#),
+        ManifestError::Invalid { reason, .. } if reason == "needs a passphrase"
+    ));
+    assert!(matches!(
+        invalid(
+            r#"{"networks": [{"name": "testnet"}], "contracts": [
+                {"name": "streaming", "wasm": "s.wasm", "salt": "s", "init": {"args": [{"contract": "token"}]}},
+                {"name": "token", "wasm": "t.wasm", "salt": "t"}
+            ]}"#
+        ),
+        ManifestError::Invalid { name, .. } if name == "streaming"
+    ));
+    assert!(matches!(
+        invalid(
+            r#"{"networks": [{"name": "testnet"}], "contracts": [
+                {"name": "token", "wasm": "t.wasm", "salt": "t", "init": {"args": [{"u32": -1}]}}
+            ]}"#
+        ),
+        ManifestError::Parse(_)
+    ));
+
+    let path = std::env::temp_dir().join("lumenpulse-deployer-test.lock.json");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+    assert_eq!(Lockfile::read(path).unwrap(), Lockfile::default());
+    let chain = FakeChain::default();
+    let mut lockfile = Lockfile::default();
+    deployer(&chain, &manifest)
+        .deploy(&manifest, &mut lockfile, |lockfile| {
+            Ok(lockfile.write(path)?)
+        })
+        .unwrap();
+    assert_eq!(Lockfile::read(path).unwrap(), lockfile);
+    assert_eq!(lockfile.contract_id("mainnet", "token"), None);
+    assert_eq!(lockfile.contract_id("testnet", "vault"), None);
+}
@@ -0,0 +1,77 @@
+//! Events published by the escrow contract.
+
+use crate::Address;
+
+events! {
+    /// An event published by the escrow contract
+    pub enum EscrowEvent {
+        Initialized = "initialized" {
+            topics {}
+            data { admin: Address }
+        }
+        EscrowCreated = "escrow_created" {
+            topics { depositor: Address, payee: Address }
+            data { escrow_id: u64, arbiter: Address, token: Address, amount: i128 }
+        }
+        Released = "released" {
+            topics { payee: Address, escrow_id: u64 }
+            data { amount: i128 }
+        }
+        Refunded = "refunded" {
+            topics { depositor: Address, escrow_id: u64 }
+            data { amount: i128 }
+        }
+        DisputeRaised = "dispute_raised" {
+            topics { raised_by: Address, escrow_id: u64 }
+            data {}
+        }
+        DisputeResolved = "dispute_resolved" {
+            topics { arbiter: Address, escrow_id: u64 }
+            data { released_to_payee: bool }
+        }
+        MilestoneApproved = "milestone_approved" {
+            topics { payee: Address, escrow_id: u64 }
+            data { milestone: u32, amount: i128 }
+        }
+        PartialReleased = "partial_released" {
+            topics { payee: Address, escrow_id: u64 }
+            data { amount: i128, remaining: i128 }
+        }
+        Contributed = "contributed" {
+            topics { contributor: Address, escrow_id: u64 }
+            data { amount: i128 }
+        }
+        ArbitrationConfigured = "arbitration_configured" {
+            topics {}
+            data { bond_token: Address, min_bond: i128, appeal_window: u64 }
+        }
+        BondStaked = "bond_staked" {
+            topics { arbiter: Address }
+            data { amount: i128, staked: i128 }
+        }
+        BondUnstaked = "bond_unstaked" {
+            topics { arbiter: Address }
+            data { amount: i128, staked: i128 }
+        }
+        RulingIssued = "ruling_issued" {
+            topics { arbiter: Address, escrow_id: u64 }
+            data { release_to_payee: bool, appeal_deadline: u64 }
+        }
+        RulingAppealed = "ruling_appealed" {
+            topics { appellant: Address, escrow_id: u64 }
+            data {}
+        }
+        AppealDecided = "appeal_decided" {
+            topics { escrow_id: u64 }
+            data { release_to_payee: bool, overturned: bool, slashed: i128 }
+        }
+        FeeConfigUpdated = "fee_config_updated" {
+            topics {}
+            data { protocol_bps: u32, arbiter_bps: u32, recipient: Address }
+        }
+        FeesCharged = "fees_charged" {
+            topics { escrow_id: u64 }
+            data { protocol_fee: i128, arbiter_fee: i128 }
+        }
+    }
+}
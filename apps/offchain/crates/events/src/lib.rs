@@ -0,0 +1,152 @@
+//! Event schema for the Lumenpulse contracts, shared by the off-chain
+//! services that read them.
+//!
+//! Contracts publish events as a list of topics, the first being the
+//! event's name, and a data value, usually a map of the remaining fields.
+//! `decode` turns those back into typed events. Names are not unique
+//! across contracts (the escrow and subscriptions contracts both publish
+//! `refunded_event`, in different shapes), so it takes the kind of
+//! contract that published the event.
+//!
+//! Events a schema does not know decode to `None` rather than an error, so
+//! a reader keeps working when a contract adds an event, and can store the
+//! raw topics and data to decode once the schema catches up.
+
+#[macro_use]
+mod macros;
+
+pub mod escrow;
+pub mod shared;
+pub mod streaming;
+pub mod subscriptions;
+mod token;
+mod value;
+
+pub use escrow::EscrowEvent;
+pub use shared::{AccessEvent, PauseEvent, UpgradeEvent};
+pub use streaming::StreamingEvent;
+pub use subscriptions::SubscriptionsEvent;
+pub use token::TokenEvent;
+pub use value::{Address, DecodeError, FromScVal, Hash};
+
+use serde::Serialize;
+use std::{fmt, str::FromStr};
+use stellar_xdr::curr::ScVal;
+
+/// Kind of contract an event came from, which picks the schema it is
+/// decoded with
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Contract {
+    Streaming,
+    Escrow,
+    Subscriptions,
+    /// A SEP-41 token, including Stellar Asset Contracts
+    Token,
+}
+
+impl Contract {
+    pub const ALL: [Contract; 4] = [
+        Contract::Streaming,
+        Contract::Escrow,
+        Contract::Subscriptions,
+        Contract::Token,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Contract::Streaming => "streaming",
+            Contract::Escrow => "escrow",
+            Contract::Subscriptions => "subscriptions",
+            Contract::Token => "token",
+        }
+    }
+}
+
+impl fmt::Display for Contract {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+#[error("unknown contract kind `{0}`")]
+pub struct UnknownContract(pub String);
+
+impl FromStr for Contract {
+    type Err = UnknownContract;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Contract::ALL
+            .into_iter()
+            .find(|contract| contract.as_str() == s)
+            .ok_or_else(|| UnknownContract(s.to_string()))
+    }
+}
+
+/// A decoded event, under the schema that defines it
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Event {
+    Streaming(StreamingEvent),
+    Escrow(EscrowEvent),
+    Subscriptions(SubscriptionsEvent),
+    Upgrade(UpgradeEvent),
+    Pause(PauseEvent),
+    Access(AccessEvent),
+    Token(TokenEvent),
+}
+
+impl Event {
+    /// Schema and event name, such as `streaming.withdrawn`
+    pub fn kind(&self) -> String {
+        let (schema, kind) = match self {
+            Event::Streaming(event) => ("streaming", event.kind()),
+            Event::Escrow(event) => ("escrow", event.kind()),
+            Event::Subscriptions(event) => ("subscriptions", event.kind()),
+            Event::Upgrade(event) => ("upgrade", event.kind()),
+            Event::Pause(event) => ("pause", event.kind()),
+            Event::Access(event) => ("access", event.kind()),
+            Event::Token(event) => ("token", event.kind()),
+        };
+        format!("{schema}.{kind}")
+    }
+}
+
+/// Decode an event `contract` published, or `None` if it is not one the
+/// schema knows. An event with a known name but the wrong shape is an
+/// error, as it means the schema and the contract have drifted apart.
+pub fn decode(
+    contract: Contract,
+    topics: &[ScVal],
+    data: &ScVal,
+) -> Result<Option<Event>, DecodeError> {
+    let Some((ScVal::Symbol(name), topics)) = topics.split_first() else {
+        return Ok(None);
+    };
+    let Ok(name) = name.0.to_utf8_string() else {
+        return Ok(None);
+    };
+    let own = match contract {
+        Contract::Streaming => {
+            StreamingEvent::decode(&name, topics, data).map(|e| e.map(Event::Streaming))
+        }
+        Contract::Escrow => EscrowEvent::decode(&name, topics, data).map(|e| e.map(Event::Escrow)),
+        Contract::Subscriptions => {
+            SubscriptionsEvent::decode(&name, topics, data).map(|e| e.map(Event::Subscriptions))
+        }
+        // Tokens have their own naming and none of the shared events
+        Contract::Token => {
+            return TokenEvent::decode(&name, topics, data)
+                .map(|e| e.map(Event::Token))
+                .transpose()
+        }
+    };
+    own.or_else(|| UpgradeEvent::decode(&name, topics, data).map(|e| e.map(Event::Upgrade)))
+        .or_else(|| PauseEvent::decode(&name, topics, data).map(|e| e.map(Event::Pause)))
+        .or_else(|| AccessEvent::decode(&name, topics, data).map(|e| e.map(Event::Access)))
+        .transpose()
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,66 @@
+/// Declare the events one contract publishes, mirroring its
+/// `#[contractevent]` structs: `topics` lists the `#[topic]` fields in
+/// order and `data` the fields of the data map that readers care about.
+/// `kind` is the struct name in snake case without the `Event` suffix, so
+/// `StreamCreatedEvent` is published under `stream_created_event` and
+/// decoded as kind `stream_created`.
+macro_rules! events {
+    (
+        $(#[$enum_meta:meta])*
+        pub enum $enum:ident {
+            $(
+                $(#[$meta:meta])*
+                $name:ident = $kind:literal {
+                    topics { $($topic:ident: $topic_ty:ty),* $(,)? }
+                    data { $($field:ident: $field_ty:ty),* $(,)? }
+                }
+            )*
+        }
+    ) => {
+        $(
+            $(#[$meta])*
+            #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+            pub struct $name {
+                $(pub $topic: $topic_ty,)*
+                $(pub $field: $field_ty,)*
+            }
+        )*
+
+        $(#[$enum_meta])*
+        #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+        #[serde(tag = "kind", rename_all = "snake_case")]
+        pub enum $enum {
+            $($name($name),)*
+        }
+
+        impl $enum {
+            pub fn kind(&self) -> &'static str {
+                match self {
+                    $(Self::$name(_) => $kind,)*
+                }
+            }
+
+            /// Decode an event published under `name`, or `None` if this
+            /// schema has no event of that name
+            pub(crate) fn decode(
+                name: &str,
+                topics: &[stellar_xdr::curr::ScVal],
+                data: &stellar_xdr::curr::ScVal,
+            ) -> Option<Result<Self, $crate::DecodeError>> {
+                let decoded = match name.strip_suffix("_event")? {
+                    $($kind => (|| {
+                        #[allow(unused_mut)]
+                        let mut _topics = $crate::value::Topics::new(topics);
+                        let _fields = $crate::value::Fields::new(data)?;
+                        Ok(Self::$name($name {
+                            $($topic: _topics.next(stringify!($topic))?,)*
+                            $($field: _fields.get(stringify!($field))?,)*
+                        }))
+                    })(),)*
+                    _ => return None,
+                };
+                Some(decoded)
+            }
+        }
+    };
+}
@@ -0,0 +1,71 @@
+//! Events every upgradeable, pausable Lumenpulse contract can publish,
+//! from the `lumenpulse-upgradeable`, `lumenpulse-pausable` and
+//! `lumenpulse-access` crates.
+
+use crate::{Address, Hash};
+
+events! {
+    /// A timelocked upgrade moving through its stages
+    pub enum UpgradeEvent {
+        UpgradeProposed = "upgrade_proposed" {
+            topics { version: u32 }
+            data { wasm_hash: Hash, eta: u64 }
+        }
+        UpgradeCancelled = "upgrade_cancelled" {
+            topics { version: u32 }
+            data { wasm_hash: Hash }
+        }
+        UpgradeExecuted = "upgrade_executed" {
+            topics { version: u32 }
+            data { wasm_hash: Hash }
+        }
+        UpgradeRolledBack = "upgrade_rolled_back" {
+            topics { version: u32 }
+            data { wasm_hash: Hash, from_version: u32 }
+        }
+    }
+}
+
+events! {
+    /// The circuit breaker tripping or resetting
+    pub enum PauseEvent {
+        Paused = "paused" {
+            topics { by: Address }
+            data {}
+        }
+        Unpaused = "unpaused" {
+            topics { by: Address }
+            data {}
+        }
+        PauserGranted = "pauser_granted" {
+            topics { account: Address }
+            data {}
+        }
+        PauserRevoked = "pauser_revoked" {
+            topics { account: Address }
+            data {}
+        }
+    }
+}
+
+events! {
+    /// Admin handover and role changes
+    pub enum AccessEvent {
+        AdminProposed = "admin_proposed" {
+            topics { admin: Address }
+            data { pending_admin: Address }
+        }
+        AdminTransferred = "admin_transferred" {
+            topics { previous_admin: Address }
+            data { admin: Address }
+        }
+        RoleGranted = "role_granted" {
+            topics { role: String, account: Address }
+            data {}
+        }
+        RoleRevoked = "role_revoked" {
+            topics { role: String, account: Address }
+            data {}
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Events published by the streaming contract.
+
+use crate::{Address, Hash};
+
+events! {
+    /// An event published by the streaming contract
+    pub enum StreamingEvent {
+        Initialized = "initialized" {
+            topics {}
+            data { admin: Address }
+        }
+        StreamCreated = "stream_created" {
+            topics { sender: Address, recipient: Address }
+            data {
+                stream_id: u64,
+                token: Address,
+                deposit: i128,
+                start_time: u64,
+                end_time: u64,
+            }
+        }
+        Withdrawn = "withdrawn" {
+            topics { recipient: Address, stream_id: u64 }
+            data { amount: i128 }
+        }
+        StreamCancelled = "stream_cancelled" {
+            topics { sender: Address, stream_id: u64 }
+            data { recipient_amount: i128, sender_amount: i128 }
+        }
+        BalanceDeposited = "balance_deposited" {
+            topics { sender: Address, token: Address }
+            data { amount: i128 }
+        }
+        BalanceWithdrawn = "balance_withdrawn" {
+            topics { sender: Address, token: Address }
+            data { amount: i128 }
+        }
+        OperatorApproved = "operator_approved" {
+            topics { sender: Address, operator: Address }
+            data { token: Address, amount: i128, expiration_ledger: u32 }
+        }
+        /// `deposit` is the stream's deposit after the top-up
+        ToppedUp = "topped_up" {
+            topics { sender: Address, stream_id: u64 }
+            data { amount: i128, deposit: i128 }
+        }
+        UpgraderSet = "upgrader_set" {
+            topics {}
+            data { upgrader: Address }
+        }
+        Upgraded = "upgraded" {
+            topics {}
+            data { new_wasm_hash: Hash }
+        }
+        DexRouterSet = "dex_router_set" {
+            topics {}
+            data { router: Address }
+        }
+        PairEnabled = "pair_enabled" {
+            topics { token_in: Address, token_out: Address }
+            data { enabled: bool }
+        }
+        PaymentSwapped = "payment_swapped" {
+            topics { sender: Address, stream_id: u64 }
+            data { token_in: Address, amount_in: i128, amount_out: i128 }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Events published by the subscriptions contract.
+
+use crate::Address;
+
+events! {
+    /// An event published by the subscriptions contract
+    pub enum SubscriptionsEvent {
+        /// The plan's dunning and billing settings are left out; read them
+        /// from the contract when they are needed
+        PlanCreated = "plan_created" {
+            topics { plan_id: u64, merchant: Address }
+            data { token: Address, price: i128, period: u64 }
+        }
+        PlanUpdated = "plan_updated" {
+            topics { plan_id: u64 }
+            data { active: bool }
+        }
+        Subscribed = "subscribed" {
+            topics { subscription_id: u64, subscriber: Address }
+            data { plan_id: u64 }
+        }
+        Charged = "charged" {
+            topics { subscription_id: u64 }
+            data { amount: i128, next_charge: u64 }
+        }
+        /// `failures` counts failed attempts in a row, including this one
+        ChargeFailed = "charge_failed" {
+            topics { subscription_id: u64 }
+            data { amount: i128, failures: u32, retry_at: u64 }
+        }
+        Recovered = "recovered" {
+            topics { subscription_id: u64 }
+            data { failures: u32 }
+        }
+        Lapsed = "lapsed" {
+            topics { subscription_id: u64 }
+            data { failures: u32 }
+        }
+        Cancelled = "cancelled" {
+            topics { subscription_id: u64 }
+            data {}
+        }
+        UsageReported = "usage_reported" {
+            topics { subscription_id: u64 }
+            data { period: u32, units: u64, total: u64 }
+        }
+        PlanChanged = "plan_changed" {
+            topics { subscription_id: u64 }
+            data { from_plan: u64, to_plan: u64, charged: i128 }
+        }
+        /// A `plan_id` of `None` means the change was withdrawn
+        PlanChangeScheduled = "plan_change_scheduled" {
+            topics { subscription_id: u64 }
+            data { plan_id: Option<u64>, effective_at: u64 }
+        }
+        RefundReserveUpdated = "refund_reserve_updated" {
+            topics { merchant: Address, token: Address }
+            data { reserve: i128 }
+        }
+        Refunded = "refunded" {
+            topics { subscription_id: u64 }
+            data { amount: i128 }
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use crate::streaming::StreamCreated;
+use crate::{
+    decode, Address, Contract, DecodeError, EscrowEvent, Event, PauseEvent, StreamingEvent,
+    SubscriptionsEvent, TokenEvent,
+};
+use serde_json::json;
+use stellar_xdr::curr::{
+    ContractId, Hash, ScAddress, ScMap, ScMapEntry, ScString, ScSymbol, ScVal,
+};
+
+fn symbol(name: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(name.try_into().unwrap()))
+}
+
+fn address(byte: u8) -> ScVal {
+    ScVal::Address(ScAddress::Contract(ContractId(Hash([byte; 32]))))
+}
+
+fn map(fields: &[(&str, ScVal)]) -> ScVal {
+    let entries: Vec<_> = fields
+        .iter()
+        .map(|(key, val)| ScMapEntry {
+            key: symbol(key),
+            val: val.clone(),
+        })
+        .collect();
+    ScVal::Map(Some(ScMap(entries.try_into().unwrap())))
+}
+
+fn strkey(byte: u8) -> Address {
+    let ScVal::Address(address) = address(byte) else {
+        unreachable!()
+    };
+    Address(address.to_string())
+}
+
+/// Events the contracts published in their golden transcripts
+fn golden_events(transcript: &str) -> Vec<(ScVal, Vec<ScVal>, ScVal)> {
+    let calls: Vec<serde_json::Value> = serde_json::from_str(transcript).unwrap();
+    calls
+        .iter()
+        .flat_map(|call| call["events"].as_array().unwrap().clone())
+        .map(|event| {
+            (
+                serde_json::from_value(event["contract"].clone()).unwrap(),
+                serde_json::from_value(event["topics"].clone()).unwrap(),
+                serde_json::from_value(event["data"].clone()).unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_decodes_stream_created_and_serializes_by_kind() {
+    let topics = [symbol("stream_created_event"), address(1), address(2)];
+    let data = map(&[
+        ("deposit", 1_000i128.into()),
+        ("end_time", 1_100u64.into()),
+        ("start_time", 100u64.into()),
+        ("stream_id", 7u64.into()),
+        ("token", address(3)),
+        // Fields the schema does not know are ignored
+        ("memo", symbol("rent")),
+    ]);
+
+    let event = decode(Contract::Streaming, &topics, &data)
+        .unwrap()
+        .unwrap();
+    let expected = StreamCreated {
+        sender: strkey(1),
+        recipient: strkey(2),
+        stream_id: 7,
+        token: strkey(3),
+        deposit: 1_000,
+        start_time: 100,
+        end_time: 1_100,
+    };
+    assert_eq!(
+        event,
+        Event::Streaming(StreamingEvent::StreamCreated(expected))
+    );
+    assert_eq!(event.kind(), "streaming.stream_created");
+    assert_eq!(
+        serde_json::to_value(&event).unwrap(),
+        json!({
+            "streaming": {
+                "kind": "stream_created",
+                "sender": strkey(1).0,
+                "recipient": strkey(2).0,
+                "stream_id": 7,
+                "token": strkey(3).0,
+                "deposit": 1_000,
+                "start_time": 100,
+                "end_time": 1_100,
+            }
+        })
+    );
+}
+
+#[test]
+fn test_contract_kind_picks_the_schema() {
+    // Escrow and subscriptions both publish `refunded_event`
+    let refunded = [symbol("refunded_event"), address(1), 4u64.into()];
+    let data = map(&[("amount", 50i128.into())]);
+    let event = decode(Contract::Escrow, &refunded, &data).unwrap().unwrap();
+    assert!(matches!(event, Event::Escrow(EscrowEvent::Refunded(_))));
+    let refunded = [symbol("refunded_event"), 4u64.into()];
+    let event = decode(Contract::Subscriptions, &refunded, &data)
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        event,
+        Event::Subscriptions(SubscriptionsEvent::Refunded(_))
+    ));
+
+    // Shared events decode for every Lumenpulse contract but not tokens
+    let paused = [symbol("paused_event"), address(9)];
+    let event = decode(Contract::Escrow, &paused, &map(&[])).unwrap();
+    assert_eq!(
+        event.map(|event| event.kind()),
+        Some("pause.paused".to_string())
+    );
+    assert!(matches!(
+        decode(Contract::Streaming, &paused, &map(&[])),
+        Ok(Some(Event::Pause(PauseEvent::Paused(_))))
+    ));
+    assert_eq!(decode(Contract::Token, &paused, &map(&[])), Ok(None));
+
+    // Unknown names and unnamed events are left for the caller to keep raw
+    let unknown = [symbol("oracle_set_event")];
+    assert_eq!(decode(Contract::Streaming, &unknown, &map(&[])), Ok(None));
+    assert_eq!(decode(Contract::Streaming, &[], &map(&[])), Ok(None));
+}
+
+#[test]
+fn test_drifted_events_are_errors() {
+    let topics = [symbol("withdrawn_event"), address(1), 4u32.into()];
+    let data = map(&[("amount", 50i128.into())]);
+    assert_eq!(
+        decode(Contract::Streaming, &topics, &data),
+        Err(DecodeError::WrongType {
+            name: "stream_id",
+            expected: "u64"
+        })
+    );
+    let topics = [symbol("withdrawn_event"), address(1)];
+    assert_eq!(
+        decode(Contract::Streaming, &topics, &data),
+        Err(DecodeError::MissingTopic("stream_id"))
+    );
+    let topics = [symbol("withdrawn_event"), address(1), 4u64.into()];
+    assert_eq!(
+        decode(Contract::Streaming, &topics, &map(&[])),
+        Err(DecodeError::MissingField("amount"))
+    );
+    assert_eq!(
+        decode(Contract::Streaming, &topics, &50i128.into()),
+        Err(DecodeError::NotAMap)
+    );
+}
+
+#[test]
+fn test_token_transfers() {
+    // The Stellar Asset Contract names the asset in a last topic
+    let asset = ScVal::String(ScString("native".try_into().unwrap()));
+    let topics = [symbol("transfer"), address(1), address(2), asset];
+    let event = decode(Contract::Token, &topics, &(-5i128 << 70).into())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        event,
+        Event::Token(TokenEvent::Transfer {
+            from: strkey(1),
+            to: strkey(2),
+            to_muxed_id: None,
+            amount: -5i128 << 70,
+        })
+    );
+    // Transfers to muxed accounts carry the amount in a map
+    let data = map(&[("amount", 3i128.into()), ("to_muxed_id", 77u64.into())]);
+    let event = decode(Contract::Token, &topics[..3], &data)
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        event,
+        Event::Token(TokenEvent::Transfer {
+            to_muxed_id: Some(77),
+            amount: 3,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_schema_matches_golden_transcripts() {
+    let goldens = [
+        (
+            Contract::Streaming,
+            include_str!("../../../../onchain/contracts/streaming/goldens/stream_lifecycle.json"),
+        ),
+        (
+            Contract::Escrow,
+            include_str!("../../../../onchain/contracts/escrow/goldens/escrow_lifecycle.json"),
+        ),
+    ];
+    for (contract, transcript) in goldens {
+        let events = golden_events(transcript);
+        let first_contract = &events
+            .iter()
+            .find(|(_, topics, _)| topics[0] != symbol("transfer"))
+            .unwrap()
+            .0;
+        let mut kinds = Vec::new();
+        for (emitter, topics, data) in &events {
+            let kind = if emitter == first_contract {
+                contract
+            } else {
+                Contract::Token
+            };
+            let event = decode(kind, topics, data).unwrap();
+            kinds.push(event.expect("every golden event is in the schema").kind());
+        }
+        let expected: &[&str] = match contract {
+            Contract::Streaming => &[
+                "token.transfer",
+                "streaming.stream_created",
+                "token.transfer",
+                "streaming.withdrawn",
+                "token.transfer",
+                "token.transfer",
+                "streaming.stream_cancelled",
+            ],
+            _ => &[
+                "token.transfer",
+                "escrow.escrow_created",
+                "token.transfer",
+                "escrow.partial_released",
+                "escrow.dispute_raised",
+                "token.transfer",
+                "escrow.released",
+                "escrow.dispute_resolved",
+            ],
+        };
+        assert_eq!(kinds, expected);
+    }
+}
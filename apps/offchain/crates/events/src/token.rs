@@ -0,0 +1,94 @@
+//! SEP-41 token events, as the Stellar Asset Contract and the Lumenpulse
+//! token publish them. Unlike contract events these are named by a bare
+//! symbol and carry the amount as the whole data value.
+
+use crate::value::{FromScVal, Topics};
+use crate::{Address, DecodeError};
+use serde::Serialize;
+use stellar_xdr::curr::ScVal;
+
+/// A token moving between holders, entering or leaving supply. The Stellar
+/// Asset Contract adds the asset's name as a last topic, which is ignored.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TokenEvent {
+    Transfer {
+        from: Address,
+        to: Address,
+        /// Set when `to` was a muxed account
+        to_muxed_id: Option<u64>,
+        amount: i128,
+    },
+    Mint {
+        to: Address,
+        amount: i128,
+    },
+    Burn {
+        from: Address,
+        amount: i128,
+    },
+    Clawback {
+        from: Address,
+        amount: i128,
+    },
+}
+
+impl TokenEvent {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TokenEvent::Transfer { .. } => "transfer",
+            TokenEvent::Mint { .. } => "mint",
+            TokenEvent::Burn { .. } => "burn",
+            TokenEvent::Clawback { .. } => "clawback",
+        }
+    }
+
+    pub(crate) fn decode(
+        name: &str,
+        topics: &[ScVal],
+        data: &ScVal,
+    ) -> Option<Result<Self, DecodeError>> {
+        let mut topics = Topics::new(topics);
+        let decoded = match name {
+            "transfer" => (|| {
+                let from = topics.next("from")?;
+                let to = topics.next("to")?;
+                // A transfer to a muxed account moves the amount into a map
+                // alongside the mux id
+                let (to_muxed_id, amount) = match data {
+                    ScVal::Map(_) => {
+                        let fields = crate::value::Fields::new(data)?;
+                        (Some(fields.get("to_muxed_id")?), fields.get("amount")?)
+                    }
+                    data => (None, i128::decode("amount", data)?),
+                };
+                Ok(TokenEvent::Transfer {
+                    from,
+                    to,
+                    to_muxed_id,
+                    amount,
+                })
+            })(),
+            "mint" => topics.next("to").and_then(|to| {
+                Ok(TokenEvent::Mint {
+                    to,
+                    amount: i128::decode("amount", data)?,
+                })
+            }),
+            "burn" => topics.next("from").and_then(|from| {
+                Ok(TokenEvent::Burn {
+                    from,
+                    amount: i128::decode("amount", data)?,
+                })
+            }),
+            "clawback" => topics.next("from").and_then(|from| {
+                Ok(TokenEvent::Clawback {
+                    from,
+                    amount: i128::decode("amount", data)?,
+                })
+            }),
+            _ => return None,
+        };
+        Some(decoded)
+    }
+}
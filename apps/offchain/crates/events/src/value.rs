@@ -0,0 +1,191 @@
+//! Reading Rust values back out of the `ScVal`s contracts publish.
+
+use serde::{Serialize, Serializer};
+use std::fmt;
+use stellar_xdr::curr::{ScMap, ScVal};
+
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum DecodeError {
+    #[error("missing topic `{0}`")]
+    MissingTopic(&'static str),
+    #[error("missing field `{0}`")]
+    MissingField(&'static str),
+    #[error("`{name}` is not a {expected}")]
+    WrongType {
+        name: &'static str,
+        expected: &'static str,
+    },
+    #[error("event data is not a map")]
+    NotAMap,
+}
+
+/// A value an event field can hold
+pub trait FromScVal: Sized {
+    /// Name of the type in errors
+    const TYPE: &'static str;
+
+    /// The value `val` holds, or `None` if it holds another type
+    fn from_sc_val(val: &ScVal) -> Option<Self>;
+
+    fn decode(name: &'static str, val: &ScVal) -> Result<Self, DecodeError> {
+        Self::from_sc_val(val).ok_or(DecodeError::WrongType {
+            name,
+            expected: Self::TYPE,
+        })
+    }
+}
+
+/// A contract or account address as its strkey (`C...` or `G...`)
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+pub struct Address(pub String);
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromScVal for Address {
+    const TYPE: &'static str = "address";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::Address(address) => Some(Address(address.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// A 32-byte hash, such as a WASM hash, written out as hex
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Hash(pub [u8; 32]);
+
+impl fmt::Display for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl Serialize for Hash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl FromScVal for Hash {
+    const TYPE: &'static str = "32-byte hash";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::Bytes(bytes) => bytes.0.as_slice().try_into().ok().map(Hash),
+            _ => None,
+        }
+    }
+}
+
+impl FromScVal for i128 {
+    const TYPE: &'static str = "i128";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::I128(parts) => Some(((parts.hi as i128) << 64) | parts.lo as i128),
+            _ => None,
+        }
+    }
+}
+
+impl FromScVal for u64 {
+    const TYPE: &'static str = "u64";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::U64(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl FromScVal for u32 {
+    const TYPE: &'static str = "u32";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::U32(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl FromScVal for bool {
+    const TYPE: &'static str = "bool";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// A `String` or `Symbol`, which contracts use interchangeably for names
+impl FromScVal for String {
+    const TYPE: &'static str = "string";
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::String(value) => value.0.to_utf8_string().ok(),
+            ScVal::Symbol(value) => value.0.to_utf8_string().ok(),
+            _ => None,
+        }
+    }
+}
+
+/// `None` is published as `Void`
+impl<T: FromScVal> FromScVal for Option<T> {
+    const TYPE: &'static str = T::TYPE;
+
+    fn from_sc_val(val: &ScVal) -> Option<Self> {
+        match val {
+            ScVal::Void => Some(None),
+            val => T::from_sc_val(val).map(Some),
+        }
+    }
+}
+
+/// An event's topics after its name, read in order
+pub(crate) struct Topics<'a>(std::slice::Iter<'a, ScVal>);
+
+impl<'a> Topics<'a> {
+    pub(crate) fn new(topics: &'a [ScVal]) -> Self {
+        Topics(topics.iter())
+    }
+
+    pub(crate) fn next<T: FromScVal>(&mut self, name: &'static str) -> Result<T, DecodeError> {
+        let val = self.0.next().ok_or(DecodeError::MissingTopic(name))?;
+        T::decode(name, val)
+    }
+}
+
+/// An event's data map, read by field name. Fields the schema does not
+/// name are ignored, so a contract can add fields without breaking readers.
+pub(crate) struct Fields<'a>(Option<&'a ScMap>);
+
+impl<'a> Fields<'a> {
+    pub(crate) fn new(data: &'a ScVal) -> Result<Self, DecodeError> {
+        match data {
+            ScVal::Map(map) => Ok(Fields(map.as_ref())),
+            _ => Err(DecodeError::NotAMap),
+        }
+    }
+
+    pub(crate) fn get<T: FromScVal>(&self, name: &'static str) -> Result<T, DecodeError> {
+        let entry = self
+            .0
+            .into_iter()
+            .flat_map(|map| map.0.iter())
+            .find(|entry| matches!(&entry.key, ScVal::Symbol(key) if key.0.as_slice() == name.as_bytes()))
+            .ok_or(DecodeError::MissingField(name))?;
+        T::decode(name, &entry.val)
+    }
+}
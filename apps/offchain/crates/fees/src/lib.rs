@@ -0,0 +1,467 @@
+//! Cost estimates for the common Lumenpulse operations.
+//!
+//! `estimate` simulates an operation as its sender would send it and
+//! reads the network's current fee settings, so a UI can show what
+//! signing will cost before anything is signed: the inclusion fee and
+//! the resource fee the simulation asks for, and, when the entries the
+//! operation leaves behind should stay live until some ledger, the rent
+//! of extending each one that would expire sooner.
+//!
+//! The resource fee includes the rent of entries the operation creates
+//! and of the TTL extensions the contracts make themselves; part of it is
+//! refunded when the transaction uses less than it declared. Entries are
+//! taken to live as long as they do now, or the network's minimum TTL
+//! when new, so a contract that extends them as it writes makes the
+//! extension cost less than estimated, never more.
+
+pub mod settings;
+
+pub use settings::{NetworkSettings, RentChange};
+
+use lumenpulse_clients::{streaming, ContractError, StreamingClient, ToScVal};
+use lumenpulse_preflight::{render_key, Report};
+use lumenpulse_rpc::{Client, LedgerEntryResult, Simulation};
+use lumenpulse_tx::BASE_FEE;
+use std::{collections::BTreeMap, fmt};
+use stellar_xdr::curr::{
+    AccountId, ContractDataDurability, DecoratedSignature, ExtendFootprintTtlOp, ExtensionPoint,
+    HostFunction, LedgerEntry, LedgerEntryData, LedgerEntryExt, LedgerFootprint, LedgerKey, Limits,
+    Memo, MuxedAccount, Operation as XdrOperation, OperationBody, Preconditions, PublicKey,
+    ScAddress, SequenceNumber, SignatureHint, SorobanResources, SorobanTransactionData,
+    SorobanTransactionDataExt, Transaction, TransactionEnvelope, TransactionExt,
+    TransactionV1Envelope, WriteXdr,
+};
+
+#[cfg(test)]
+mod test;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(#[from] lumenpulse_rpc::Error),
+    #[error(transparent)]
+    Tx(#[from] lumenpulse_tx::Error),
+    #[error(transparent)]
+    Client(#[from] lumenpulse_clients::Error),
+    #[error("invalid XDR: {0}")]
+    Xdr(#[from] stellar_xdr::curr::Error),
+    #[error("the network has no {0} setting")]
+    MissingSetting(&'static str),
+    #[error("{} {}", .0.call, .0.outcome)]
+    Fails(Box<Report>),
+}
+
+/// What estimates are made against; `Client` for a real network
+pub trait Network {
+    /// Simulation of `host_function` sent from `source`
+    fn simulate(
+        &self,
+        source: &AccountId,
+        host_function: HostFunction,
+    ) -> Result<Simulation, Error>;
+    /// Those of the entries with `keys` that exist
+    fn entries(&self, keys: &[LedgerKey]) -> Result<Vec<LedgerEntryResult>, Error>;
+}
+
+impl Network for Client {
+    fn simulate(
+        &self,
+        source: &AccountId,
+        host_function: HostFunction,
+    ) -> Result<Simulation, Error> {
+        Ok(lumenpulse_tx::simulate_as(self, source, host_function)?.1)
+    }
+
+    fn entries(&self, keys: &[LedgerKey]) -> Result<Vec<LedgerEntryResult>, Error> {
+        Ok(self.get_ledger_entries(keys)?)
+    }
+}
+
+/// An operation to estimate
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Operation {
+    CreateStream {
+        streaming: ScAddress,
+        sender: ScAddress,
+        recipient: ScAddress,
+        token: ScAddress,
+        amount: i128,
+        start_time: u64,
+        end_time: u64,
+    },
+    Withdraw {
+        streaming: ScAddress,
+        stream_id: u64,
+    },
+    /// A merkle distributor claim, with the proof from the round's
+    /// distribution
+    Claim {
+        distributor: ScAddress,
+        account: ScAddress,
+        round_id: u32,
+        index: u32,
+        amount: i128,
+        proof: Vec<[u8; 32]>,
+    },
+    /// Upload of the WASM an upgrade installs
+    InstallWasm { wasm: Vec<u8> },
+    /// A proposal to a contract with `lumenpulse-upgradeable`'s upgrade
+    /// entrypoints, such as streaming or escrow
+    ProposeUpgrade {
+        contract: ScAddress,
+        caller: ScAddress,
+        wasm_hash: [u8; 32],
+        version: u32,
+    },
+    /// Only simulates once the proposal's delay has passed; before then it
+    /// fails as the contract would
+    ExecuteUpgrade {
+        contract: ScAddress,
+        caller: ScAddress,
+    },
+}
+
+impl Operation {
+    pub fn host_function(&self) -> Result<HostFunction, Error> {
+        let invoke = |contract, function, args| {
+            Ok::<_, Error>(lumenpulse_tx::invoke_contract(contract, function, args)?)
+        };
+        match self {
+            Operation::CreateStream {
+                streaming,
+                sender,
+                recipient,
+                token,
+                amount,
+                start_time,
+                end_time,
+            } => Ok(StreamingClient::new(streaming.clone())
+                .create_stream(sender, recipient, token, *amount, *start_time, *end_time)?
+                .host_function),
+            Operation::Withdraw {
+                streaming,
+                stream_id,
+            } => Ok(StreamingClient::new(streaming.clone())
+                .withdraw(*stream_id)?
+                .host_function),
+            Operation::Claim {
+                distributor,
+                account,
+                round_id,
+                index,
+                amount,
+                proof,
+            } => invoke(
+                distributor,
+                "claim",
+                vec![
+                    account.to_sc_val()?,
+                    round_id.to_sc_val()?,
+                    index.to_sc_val()?,
+                    amount.to_sc_val()?,
+                    proof.to_sc_val()?,
+                ],
+            ),
+            Operation::InstallWasm { wasm } => Ok(lumenpulse_tx::upload_wasm(wasm)?),
+            Operation::ProposeUpgrade {
+                contract,
+                caller,
+                wasm_hash,
+                version,
+            } => invoke(
+                contract,
+                "propose_upgrade",
+                vec![
+                    caller.to_sc_val()?,
+                    wasm_hash.to_sc_val()?,
+                    version.to_sc_val()?,
+                ],
+            ),
+            Operation::ExecuteUpgrade { contract, caller } => {
+                invoke(contract, "execute_upgrade", vec![caller.to_sc_val()?])
+            }
+        }
+    }
+
+    /// Errors the invoked contract's spec names; upgrades fail with the
+    /// shared errors, which reports name anyway, and the distributor has no
+    /// client, so its errors are reported by code
+    fn errors(&self) -> &'static [ContractError] {
+        match self {
+            Operation::CreateStream { .. } | Operation::Withdraw { .. } => streaming::ERRORS,
+            Operation::Claim { .. }
+            | Operation::InstallWasm { .. }
+            | Operation::ProposeUpgrade { .. }
+            | Operation::ExecuteUpgrade { .. } => &[],
+        }
+    }
+
+    /// The contract whose entries the operation leaves behind
+    fn contract(&self) -> Option<&ScAddress> {
+        match self {
+            Operation::CreateStream { streaming, .. } | Operation::Withdraw { streaming, .. } => {
+                Some(streaming)
+            }
+            Operation::Claim { distributor, .. } => Some(distributor),
+            Operation::InstallWasm { .. } => None,
+            Operation::ProposeUpgrade { contract, .. }
+            | Operation::ExecuteUpgrade { contract, .. } => Some(contract),
+        }
+    }
+}
+
+/// What an operation would cost
+#[derive(Clone, Debug, PartialEq)]
+pub struct Estimate {
+    /// The simulation's report, with the inclusion and resource fees
+    pub report: Report,
+    /// Ledger the operation's entries are kept live until, if asked and
+    /// within the longest TTL the network allows
+    pub keep_until: Option<u32>,
+    pub bumps: Vec<Bump>,
+    /// Resource fee of the transaction that makes `bumps`, in stroops
+    pub bump_fee: i64,
+}
+
+/// One of the operation's entries that would expire before `keep_until`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bump {
+    pub key: LedgerKey,
+    /// Size rent is charged for, in bytes
+    pub size: u32,
+    pub live_until_ledger: u32,
+}
+
+impl Estimate {
+    /// Stroops to send the operation and, if any are needed, its bumps
+    pub fn total(&self) -> i64 {
+        if self.bumps.is_empty() {
+            self.report.fee()
+        } else {
+            self.report.fee() + i64::from(BASE_FEE) + self.bump_fee
+        }
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.report)?;
+        if let (Some(keep_until), false) = (self.keep_until, self.bumps.is_empty()) {
+            writeln!(
+                f,
+                "  keeping {} entries live until ledger {keep_until}: {} stroops ({BASE_FEE} inclusion, {} resources)",
+                self.bumps.len(),
+                i64::from(BASE_FEE) + self.bump_fee,
+                self.bump_fee
+            )?;
+            for bump in &self.bumps {
+                writeln!(
+                    f,
+                    "    {}, {} bytes live until {}",
+                    render_key(&bump.key),
+                    bump.size,
+                    bump.live_until_ledger
+                )?;
+            }
+        }
+        let total = self.total();
+        writeln!(
+            f,
+            "  total: {total} stroops ({}.{:07} XLM)",
+            total / 10_000_000,
+            total % 10_000_000
+        )
+    }
+}
+
+/// Estimate of `operation` sent from `source`, with the rent of keeping
+/// its contract's entries live until `keep_until`, if given. Fails with the
+/// report if the operation would fail.
+pub fn estimate(
+    network: &impl Network,
+    settings: &NetworkSettings,
+    source: &AccountId,
+    operation: &Operation,
+    keep_until: Option<u32>,
+) -> Result<Estimate, Error> {
+    let host_function = operation.host_function()?;
+    let simulation = network.simulate(source, host_function.clone())?;
+    let report = Report::new(&host_function, &simulation, operation.errors());
+    if !report.succeeds() {
+        return Err(Error::Fails(Box::new(report)));
+    }
+
+    // The ledger the operation lands in, and the furthest an entry can
+    // live from there
+    let current = simulation.latest_ledger + 1;
+    let keep_until =
+        keep_until.map(|ledger| ledger.min((current + settings.max_entry_ttl).saturating_sub(1)));
+    let (bumps, bump_fee) = match (keep_until, operation.contract()) {
+        (Some(keep_until), Some(contract)) => {
+            let bumps = bumps(network, settings, &simulation, contract, keep_until)?;
+            let fee = bump_fee(settings, source, &bumps, current, keep_until)?;
+            (bumps, fee)
+        }
+        _ => (Vec::new(), 0),
+    };
+    Ok(Estimate {
+        report,
+        keep_until,
+        bumps,
+        bump_fee,
+    })
+}
+
+/// Entries of `contract` in the footprint that exist after the operation
+/// and would expire before `keep_until`
+fn bumps(
+    network: &impl Network,
+    settings: &NetworkSettings,
+    simulation: &Simulation,
+    contract: &ScAddress,
+    keep_until: u32,
+) -> Result<Vec<Bump>, Error> {
+    let footprint = match &simulation.transaction_data {
+        Some(data) => &data.resources.footprint,
+        None => return Ok(Vec::new()),
+    };
+    let keys: Vec<LedgerKey> = footprint
+        .read_only
+        .iter()
+        .chain(footprint.read_write.iter())
+        .filter(|key| matches!(key, LedgerKey::ContractData(data) if data.contract == *contract))
+        .cloned()
+        .collect();
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let current: BTreeMap<LedgerKey, LedgerEntryResult> = network
+        .entries(&keys)?
+        .into_iter()
+        .map(|entry| (entry.key.clone(), entry))
+        .collect();
+    let changes: BTreeMap<&LedgerKey, _> = simulation
+        .state_changes
+        .iter()
+        .map(|change| (&change.key, &change.after))
+        .collect();
+    let created = simulation.latest_ledger + 1;
+
+    let mut bumps = Vec::new();
+    for key in keys {
+        let LedgerKey::ContractData(data) = &key else {
+            continue;
+        };
+        let existing = current.get(&key);
+        let data_after = match changes.get(&key) {
+            Some(Some(entry)) => &entry.data,
+            // Deleted by the operation
+            Some(None) => continue,
+            None => match existing {
+                Some(existing) => &existing.data,
+                None => continue,
+            },
+        };
+        let size = entry_size(data_after)?;
+        let live_until_ledger = match existing.and_then(|existing| existing.live_until_ledger) {
+            Some(ledger) => ledger,
+            None => match data.durability {
+                ContractDataDurability::Persistent => {
+                    (created + settings.min_persistent_ttl).saturating_sub(1)
+                }
+                ContractDataDurability::Temporary => {
+                    (created + settings.min_temporary_ttl).saturating_sub(1)
+                }
+            },
+        };
+        if live_until_ledger < keep_until {
+            bumps.push(Bump {
+                key,
+                size,
+                live_until_ledger,
+            });
+        }
+    }
+    Ok(bumps)
+}
+
+/// Size of the entry holding `data`, which rent is charged by
+fn entry_size(data: &LedgerEntryData) -> Result<u32, Error> {
+    let entry = LedgerEntry {
+        last_modified_ledger_seq: 0,
+        data: data.clone(),
+        ext: LedgerEntryExt::V0,
+    };
+    Ok(entry.to_xdr(Limits::none())?.len() as u32)
+}
+
+/// Resource fee of one transaction extending every entry in `bumps` to
+/// live until `keep_until`: their rent and the transaction's size
+fn bump_fee(
+    settings: &NetworkSettings,
+    source: &AccountId,
+    bumps: &[Bump],
+    current: u32,
+    keep_until: u32,
+) -> Result<i64, Error> {
+    if bumps.is_empty() {
+        return Ok(0);
+    }
+    let changes: Vec<RentChange> = bumps
+        .iter()
+        .map(|bump| RentChange {
+            persistent: matches!(
+                &bump.key,
+                LedgerKey::ContractData(data) if data.durability == ContractDataDurability::Persistent
+            ),
+            code: false,
+            old_size: bump.size,
+            new_size: bump.size,
+            old_live_until_ledger: bump.live_until_ledger,
+            new_live_until_ledger: keep_until,
+        })
+        .collect();
+    let rent = settings.rent_fee(&changes, current);
+
+    let AccountId(PublicKey::PublicKeyTypeEd25519(key)) = source;
+    let keys: Vec<LedgerKey> = bumps.iter().map(|bump| bump.key.clone()).collect();
+    let operation = XdrOperation {
+        source_account: None,
+        body: OperationBody::ExtendFootprintTtl(ExtendFootprintTtlOp {
+            ext: ExtensionPoint::V0,
+            extend_to: keep_until.saturating_sub(current),
+        }),
+    };
+    let tx = Transaction {
+        source_account: MuxedAccount::Ed25519(key.clone()),
+        fee: BASE_FEE,
+        seq_num: SequenceNumber(1),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![operation].try_into()?,
+        ext: TransactionExt::V1(SorobanTransactionData {
+            ext: SorobanTransactionDataExt::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: keys.try_into()?,
+                    read_write: Default::default(),
+                },
+                instructions: 0,
+                disk_read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: rent,
+        }),
+    };
+    // Signed by the source alone
+    let signature = DecoratedSignature {
+        hint: SignatureHint([0; 4]),
+        signature: vec![0; 64].try_into()?,
+    };
+    let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx,
+        signatures: vec![signature].try_into()?,
+    });
+    let size = envelope.to_xdr(Limits::none())?.len() as u32;
+    Ok(rent.saturating_add(settings.size_fee(size)))
+}
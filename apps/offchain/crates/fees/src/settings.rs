@@ -0,0 +1,215 @@
+//! The network settings fees are charged by, and the rent formula.
+//!
+//! The formulas are those of `soroban-env-host`'s `fees` module for
+//! protocol 23, which validators charge by; keep them in step with it.
+
+use crate::{Error, Network};
+use stellar_xdr::curr::{
+    ConfigSettingEntry, ConfigSettingId, LedgerEntryData, LedgerKey, LedgerKeyConfigSetting,
+};
+
+/// Size the rent formula charges each TTL entry written at
+const TTL_ENTRY_SIZE: u32 = 48;
+/// Size of a history entry on top of the transaction itself
+const TX_BASE_RESULT_SIZE: u32 = 300;
+const MINIMUM_RENT_WRITE_FEE_PER_1KB: i64 = 1000;
+const CODE_ENTRY_RENT_DISCOUNT_FACTOR: i64 = 3;
+
+/// What the network charges for rent and transaction size, in stroops
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NetworkSettings {
+    pub fee_per_write_entry: i64,
+    pub fee_per_write_1kb: i64,
+    /// Rent for 1KB over `persistent_rent_rate_denominator` ledgers, which
+    /// rises with the size of the live Soroban state
+    pub fee_per_rent_1kb: i64,
+    pub fee_per_historical_1kb: i64,
+    pub fee_per_transaction_size_1kb: i64,
+    pub persistent_rent_rate_denominator: i64,
+    pub temporary_rent_rate_denominator: i64,
+    /// Ledgers a new persistent entry lives for before any extension
+    pub min_persistent_ttl: u32,
+    pub min_temporary_ttl: u32,
+    /// Furthest ahead of the current ledger an entry may live
+    pub max_entry_ttl: u32,
+}
+
+/// One entry's size and TTL before and after a transaction; zero for a
+/// side where it does not exist
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RentChange {
+    pub persistent: bool,
+    pub code: bool,
+    pub old_size: u32,
+    pub new_size: u32,
+    pub old_live_until_ledger: u32,
+    pub new_live_until_ledger: u32,
+}
+
+/// The settings `NetworkSettings` is read from
+const IDS: [ConfigSettingId; 6] = [
+    ConfigSettingId::ContractLedgerCostV0,
+    ConfigSettingId::ContractLedgerCostExtV0,
+    ConfigSettingId::ContractHistoricalDataV0,
+    ConfigSettingId::ContractBandwidthV0,
+    ConfigSettingId::StateArchival,
+    ConfigSettingId::LiveSorobanStateSizeWindow,
+];
+
+impl NetworkSettings {
+    /// The network's current settings
+    pub fn read(network: &impl Network) -> Result<Self, Error> {
+        let keys: Vec<LedgerKey> = IDS
+            .into_iter()
+            .map(|config_setting_id| {
+                LedgerKey::ConfigSetting(LedgerKeyConfigSetting { config_setting_id })
+            })
+            .collect();
+        let entries: Vec<ConfigSettingEntry> = network
+            .entries(&keys)?
+            .into_iter()
+            .filter_map(|entry| match entry.data {
+                LedgerEntryData::ConfigSetting(setting) => Some(setting),
+                _ => None,
+            })
+            .collect();
+        Self::from_entries(&entries)
+    }
+
+    /// Settings from the network's config setting entries
+    pub fn from_entries(entries: &[ConfigSettingEntry]) -> Result<Self, Error> {
+        let (mut ledger, mut ledger_ext, mut historical) = (None, None, None);
+        let (mut bandwidth, mut archival, mut window) = (None, None, None);
+        for entry in entries {
+            match entry {
+                ConfigSettingEntry::ContractLedgerCostV0(setting) => ledger = Some(setting),
+                ConfigSettingEntry::ContractLedgerCostExtV0(setting) => ledger_ext = Some(setting),
+                ConfigSettingEntry::ContractHistoricalDataV0(setting) => historical = Some(setting),
+                ConfigSettingEntry::ContractBandwidthV0(setting) => bandwidth = Some(setting),
+                ConfigSettingEntry::StateArchival(setting) => archival = Some(setting),
+                ConfigSettingEntry::LiveSorobanStateSizeWindow(setting) => window = Some(setting),
+                _ => {}
+            }
+        }
+        let missing = |id: ConfigSettingId| Error::MissingSetting(id.name());
+        let ledger = ledger.ok_or_else(|| missing(ConfigSettingId::ContractLedgerCostV0))?;
+        let ledger_ext =
+            ledger_ext.ok_or_else(|| missing(ConfigSettingId::ContractLedgerCostExtV0))?;
+        let historical =
+            historical.ok_or_else(|| missing(ConfigSettingId::ContractHistoricalDataV0))?;
+        let bandwidth = bandwidth.ok_or_else(|| missing(ConfigSettingId::ContractBandwidthV0))?;
+        let archival = archival.ok_or_else(|| missing(ConfigSettingId::StateArchival))?;
+        let window = window.ok_or_else(|| missing(ConfigSettingId::LiveSorobanStateSizeWindow))?;
+
+        // Rent is charged by the average of the recent state sizes
+        let state_size = match window.len() {
+            0 => 0,
+            samples => window.iter().map(|&size| i128::from(size)).sum::<i128>() / samples as i128,
+        };
+        let target = i128::from(ledger.soroban_state_target_size_bytes);
+        let low = ledger.rent_fee1_kb_soroban_state_size_low;
+        let high = ledger.rent_fee1_kb_soroban_state_size_high;
+        let multiplier = i128::from(clamp(i128::from(high) - i128::from(low)));
+        let fee_per_rent_1kb = if state_size < target {
+            clamp(div_ceil(multiplier * state_size, target.max(1))).saturating_add(low)
+        } else {
+            let growth = i128::from(ledger.soroban_state_rent_fee_growth_factor);
+            high.saturating_add(clamp(div_ceil(
+                multiplier.saturating_mul(state_size - target) * growth,
+                target.max(1),
+            )))
+        };
+
+        Ok(NetworkSettings {
+            fee_per_write_entry: ledger.fee_write_ledger_entry,
+            fee_per_write_1kb: ledger_ext.fee_write1_kb,
+            fee_per_rent_1kb: fee_per_rent_1kb.max(MINIMUM_RENT_WRITE_FEE_PER_1KB),
+            fee_per_historical_1kb: historical.fee_historical1_kb,
+            fee_per_transaction_size_1kb: bandwidth.fee_tx_size1_kb,
+            persistent_rent_rate_denominator: archival.persistent_rent_rate_denominator,
+            temporary_rent_rate_denominator: archival.temp_rent_rate_denominator,
+            min_persistent_ttl: archival.min_persistent_ttl,
+            min_temporary_ttl: archival.min_temporary_ttl,
+            max_entry_ttl: archival.max_entry_ttl,
+        })
+    }
+
+    /// Rent for `changes` applied at `current_ledger`: the extended ledgers
+    /// at each entry's new size, a top up of the ledgers already paid for
+    /// when an entry grows, and the write of each extended TTL
+    pub fn rent_fee(&self, changes: &[RentChange], current_ledger: u32) -> i64 {
+        let mut fee: i64 = 0;
+        let mut extended: u32 = 0;
+        for change in changes {
+            let new = change.old_size == 0 && change.old_live_until_ledger == 0;
+            let mut entry_fee: i64 = 0;
+            let before_extension = if new {
+                current_ledger.saturating_sub(1)
+            } else {
+                change.old_live_until_ledger
+            };
+            if let Some(ledgers) = change.new_live_until_ledger.checked_sub(before_extension) {
+                entry_fee = entry_fee.saturating_add(self.rent(change, change.new_size, ledgers));
+            }
+            // The ledgers from now to the old live-until, inclusive
+            let prepaid = (!new)
+                .then(|| change.old_live_until_ledger.checked_sub(current_ledger))
+                .flatten()
+                .map(|ledgers| ledgers.saturating_add(1));
+            if let (Some(ledgers), Some(growth)) =
+                (prepaid, change.new_size.checked_sub(change.old_size))
+            {
+                entry_fee = entry_fee.saturating_add(self.rent(change, growth, ledgers));
+            }
+            if change.code {
+                entry_fee /= CODE_ENTRY_RENT_DISCOUNT_FACTOR;
+            }
+            fee = fee.saturating_add(entry_fee);
+            if change.old_live_until_ledger < change.new_live_until_ledger {
+                extended += 1;
+            }
+        }
+        fee.saturating_add(self.fee_per_write_entry.saturating_mul(extended.into()))
+            .saturating_add(per_1kb(
+                extended.saturating_mul(TTL_ENTRY_SIZE),
+                self.fee_per_write_1kb,
+            ))
+    }
+
+    /// History and bandwidth fees of a transaction whose envelope is `size`
+    /// bytes
+    pub fn size_fee(&self, size: u32) -> i64 {
+        per_1kb(
+            size.saturating_add(TX_BASE_RESULT_SIZE),
+            self.fee_per_historical_1kb,
+        )
+        .saturating_add(per_1kb(size, self.fee_per_transaction_size_1kb))
+    }
+
+    fn rent(&self, change: &RentChange, size: u32, ledgers: u32) -> i64 {
+        let denominator = if change.persistent {
+            self.persistent_rent_rate_denominator
+        } else {
+            self.temporary_rent_rate_denominator
+        };
+        let fee = i128::from(size) * i128::from(self.fee_per_rent_1kb) * i128::from(ledgers);
+        clamp(div_ceil(fee, 1024 * i128::from(denominator).max(1)))
+    }
+}
+
+fn per_1kb(size: u32, fee: i64) -> i64 {
+    clamp(div_ceil(i128::from(size) * i128::from(fee), 1024))
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    (a + b - 1).div_euclid(b)
+}
+
+/// Fees past what an `i64` holds, or negative from a broken setting, as
+/// the most that can be charged
+fn clamp(fee: i128) -> i64 {
+    match fee {
+        ..0 => i64::MAX,
+        fee => i64::try_from(fee).unwrap_or(i64::MAX),
+    }
+}
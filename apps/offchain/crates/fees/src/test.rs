@@ -0,0 +1,390 @@
+use crate::{estimate, Bump, Error, Network, NetworkSettings, Operation, RentChange};
+use lumenpulse_rpc::{LedgerEntryResult, Simulation, StateChange};
+use lumenpulse_tx::contract_address;
+use std::cell::RefCell;
+use stellar_xdr::curr::{
+    AccountId, ConfigSettingContractBandwidthV0, ConfigSettingContractHistoricalDataV0,
+    ConfigSettingContractLedgerCostExtV0, ConfigSettingContractLedgerCostV0, ConfigSettingEntry,
+    ContractDataDurability, ContractDataEntry, ExtensionPoint, HostFunction, LedgerEntry,
+    LedgerEntryData, LedgerEntryExt, LedgerFootprint, LedgerKey, LedgerKeyContractData, Limits,
+    PublicKey, ScAddress, ScSymbol, ScVal, SorobanResources, SorobanTransactionData,
+    SorobanTransactionDataExt, StateArchivalSettings, Uint256, WriteXdr,
+};
+
+const STREAMING: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+const TOKEN: &str = "CAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC526";
+
+fn sender() -> AccountId {
+    AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([1; 32])))
+}
+
+fn settings() -> NetworkSettings {
+    NetworkSettings {
+        fee_per_write_entry: 1_000,
+        fee_per_write_1kb: 2_000,
+        fee_per_rent_1kb: 10_000,
+        fee_per_historical_1kb: 16_235,
+        fee_per_transaction_size_1kb: 1_624,
+        persistent_rent_rate_denominator: 2_103,
+        temporary_rent_rate_denominator: 4_206,
+        min_persistent_ttl: 100,
+        min_temporary_ttl: 16,
+        max_entry_ttl: 10_000,
+    }
+}
+
+fn entries(window: Vec<u64>) -> Vec<ConfigSettingEntry> {
+    vec![
+        ConfigSettingEntry::ContractLedgerCostV0(ConfigSettingContractLedgerCostV0 {
+            ledger_max_disk_read_entries: 0,
+            ledger_max_disk_read_bytes: 0,
+            ledger_max_write_ledger_entries: 0,
+            ledger_max_write_bytes: 0,
+            tx_max_disk_read_entries: 0,
+            tx_max_disk_read_bytes: 0,
+            tx_max_write_ledger_entries: 0,
+            tx_max_write_bytes: 0,
+            fee_disk_read_ledger_entry: 0,
+            fee_write_ledger_entry: 1_000,
+            fee_disk_read1_kb: 0,
+            soroban_state_target_size_bytes: 1_000_000,
+            rent_fee1_kb_soroban_state_size_low: 2_000,
+            rent_fee1_kb_soroban_state_size_high: 12_000,
+            soroban_state_rent_fee_growth_factor: 5,
+        }),
+        ConfigSettingEntry::ContractLedgerCostExtV0(ConfigSettingContractLedgerCostExtV0 {
+            tx_max_footprint_entries: 0,
+            fee_write1_kb: 2_000,
+        }),
+        ConfigSettingEntry::ContractHistoricalDataV0(ConfigSettingContractHistoricalDataV0 {
+            fee_historical1_kb: 16_235,
+        }),
+        ConfigSettingEntry::ContractBandwidthV0(ConfigSettingContractBandwidthV0 {
+            ledger_max_txs_size_bytes: 0,
+            tx_max_size_bytes: 0,
+            fee_tx_size1_kb: 1_624,
+        }),
+        ConfigSettingEntry::StateArchival(StateArchivalSettings {
+            max_entry_ttl: 10_000,
+            min_temporary_ttl: 16,
+            min_persistent_ttl: 100,
+            persistent_rent_rate_denominator: 2_103,
+            temp_rent_rate_denominator: 4_206,
+            max_entries_to_archive: 0,
+            live_soroban_state_size_window_sample_size: 0,
+            live_soroban_state_size_window_sample_period: 0,
+            eviction_scan_size: 0,
+            starting_eviction_scan_level: 0,
+        }),
+        ConfigSettingEntry::LiveSorobanStateSizeWindow(window.try_into().unwrap()),
+    ]
+}
+
+fn data_key(contract: &str, name: &str) -> LedgerKey {
+    LedgerKey::ContractData(LedgerKeyContractData {
+        contract: contract_address(contract).unwrap(),
+        key: ScVal::Symbol(ScSymbol(name.try_into().unwrap())),
+        durability: ContractDataDurability::Persistent,
+    })
+}
+
+fn data_entry(key: &LedgerKey, val: ScVal) -> LedgerEntry {
+    let LedgerKey::ContractData(key) = key else {
+        unreachable!()
+    };
+    LedgerEntry {
+        last_modified_ledger_seq: 1_000,
+        data: LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract: key.contract.clone(),
+            key: key.key.clone(),
+            durability: key.durability,
+            val,
+        }),
+        ext: LedgerEntryExt::V0,
+    }
+}
+
+fn size(entry: &LedgerEntry) -> u32 {
+    entry.to_xdr(Limits::none()).unwrap().len() as u32
+}
+
+/// A network at ledger 1000 where creating a stream writes a new stream
+/// entry and the contract's existing counter, and reads the token
+struct FakeNetwork {
+    error: Option<String>,
+    simulated: RefCell<Vec<(AccountId, HostFunction)>>,
+}
+
+impl FakeNetwork {
+    fn new(error: Option<&str>) -> Self {
+        FakeNetwork {
+            error: error.map(String::from),
+            simulated: RefCell::default(),
+        }
+    }
+
+    fn stream() -> LedgerEntry {
+        data_entry(&data_key(STREAMING, "Stream"), ScVal::U64(500))
+    }
+
+    fn counter() -> LedgerEntry {
+        data_entry(&data_key(STREAMING, "Counter"), ScVal::U64(6))
+    }
+}
+
+impl Network for FakeNetwork {
+    fn simulate(
+        &self,
+        source: &AccountId,
+        host_function: HostFunction,
+    ) -> Result<Simulation, Error> {
+        self.simulated
+            .borrow_mut()
+            .push((source.clone(), host_function));
+        let footprint = LedgerFootprint {
+            read_only: vec![data_key(TOKEN, "Balance")].try_into().unwrap(),
+            read_write: vec![
+                data_key(STREAMING, "Stream"),
+                data_key(STREAMING, "Counter"),
+            ]
+            .try_into()
+            .unwrap(),
+        };
+        let counter = data_entry(&data_key(STREAMING, "Counter"), ScVal::U64(7));
+        Ok(Simulation {
+            latest_ledger: 1_000,
+            error: self.error.clone(),
+            transaction_data: Some(SorobanTransactionData {
+                ext: SorobanTransactionDataExt::V0,
+                resources: SorobanResources {
+                    footprint,
+                    instructions: 3_000_000,
+                    disk_read_bytes: 0,
+                    write_bytes: 400,
+                },
+                resource_fee: 90_000,
+            }),
+            min_resource_fee: 90_000,
+            auth: vec![],
+            return_value: Some(ScVal::U64(6)),
+            events: vec![],
+            restore_preamble: None,
+            state_changes: vec![
+                StateChange {
+                    key: data_key(STREAMING, "Stream"),
+                    before: None,
+                    after: Some(Self::stream()),
+                },
+                StateChange {
+                    key: data_key(STREAMING, "Counter"),
+                    before: Some(Self::counter()),
+                    after: Some(counter),
+                },
+            ],
+        })
+    }
+
+    fn entries(&self, keys: &[LedgerKey]) -> Result<Vec<LedgerEntryResult>, Error> {
+        let counter = Self::counter();
+        Ok(keys
+            .iter()
+            .filter(|key| **key == data_key(STREAMING, "Counter"))
+            .map(|key| LedgerEntryResult {
+                key: key.clone(),
+                data: counter.data.clone(),
+                last_modified_ledger: 900,
+                live_until_ledger: Some(1_300),
+            })
+            .collect())
+    }
+}
+
+fn create_stream() -> Operation {
+    let address = |strkey: &str| contract_address(strkey).unwrap();
+    Operation::CreateStream {
+        streaming: address(STREAMING),
+        sender: ScAddress::Account(sender()),
+        recipient: ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([2; 32])))),
+        token: address(TOKEN),
+        amount: 1_000,
+        start_time: 0,
+        end_time: 100,
+    }
+}
+
+#[test]
+fn rent_follows_the_host_formula() {
+    let settings = settings();
+    // 1KB created at ledger 1000 to live 100 ledgers, a 512-byte entry
+    // growing to 1KB with 50 ledgers paid for, and the created one as code
+    let created = RentChange {
+        persistent: true,
+        code: false,
+        old_size: 0,
+        new_size: 1_024,
+        old_live_until_ledger: 0,
+        new_live_until_ledger: 1_099,
+    };
+    let grown = RentChange {
+        old_size: 512,
+        old_live_until_ledger: 1_049,
+        new_live_until_ledger: 1_049,
+        ..created
+    };
+    let code = RentChange {
+        code: true,
+        ..created
+    };
+    // ceil(1000000 / 2103), then the TTL entry's write
+    assert_eq!(settings.rent_fee(&[created], 1_000), 476 + 1_000 + 94);
+    // ceil(512 * 10000 * 50 / (1024 * 2103)), with no TTL written
+    assert_eq!(settings.rent_fee(&[grown], 1_000), 119);
+    assert_eq!(settings.rent_fee(&[code], 1_000), 476 / 3 + 1_000 + 94);
+    assert_eq!(
+        settings.rent_fee(&[created, grown], 1_000),
+        476 + 119 + 1_000 + 94
+    );
+    // Temporary entries pay half the rent
+    let temporary = RentChange {
+        persistent: false,
+        ..created
+    };
+    assert_eq!(settings.rent_fee(&[temporary], 1_000), 238 + 1_000 + 94);
+    assert_eq!(settings.rent_fee(&[], 1_000), 0);
+    // 500 bytes of history on top of the envelope, and 200 of bandwidth
+    assert_eq!(settings.size_fee(200), 7_928 + 318);
+}
+
+#[test]
+fn settings_are_read_from_the_config_entries() {
+    // The rent rate rises from low to high as the average state size
+    // reaches the target, then by the growth factor past it
+    let read = |window: Vec<u64>| NetworkSettings::from_entries(&entries(window)).unwrap();
+    assert_eq!(read(vec![250_000, 750_000]), settings_with_rent(7_000));
+    assert_eq!(read(vec![1_200_000]), settings_with_rent(22_000));
+    assert_eq!(read(vec![]), settings_with_rent(2_000));
+
+    let mut missing = entries(vec![]);
+    missing.retain(|entry| !matches!(entry, ConfigSettingEntry::StateArchival(_)));
+    assert!(matches!(
+        NetworkSettings::from_entries(&missing),
+        Err(Error::MissingSetting("StateArchival"))
+    ));
+}
+
+fn settings_with_rent(fee_per_rent_1kb: i64) -> NetworkSettings {
+    NetworkSettings {
+        fee_per_rent_1kb,
+        ..settings()
+    }
+}
+
+#[test]
+fn creating_a_stream_estimates_its_fee_and_bumps() {
+    let network = FakeNetwork::new(None);
+    let settings = settings();
+    let estimate = estimate(
+        &network,
+        &settings,
+        &sender(),
+        &create_stream(),
+        Some(5_000),
+    )
+    .unwrap();
+    assert_eq!(
+        network.simulated.borrow()[0],
+        (sender(), create_stream().host_function().unwrap())
+    );
+
+    // The new stream lives the minimum TTL from the ledger it lands in, the
+    // counter as long as it does now; the token's entries are not kept
+    assert_eq!(estimate.report.fee(), 90_100);
+    assert_eq!(estimate.keep_until, Some(5_000));
+    let counter = data_entry(&data_key(STREAMING, "Counter"), ScVal::U64(7));
+    assert_eq!(
+        estimate.bumps,
+        vec![
+            Bump {
+                key: data_key(STREAMING, "Stream"),
+                size: size(&FakeNetwork::stream()),
+                live_until_ledger: 1_100,
+            },
+            Bump {
+                key: data_key(STREAMING, "Counter"),
+                size: size(&counter),
+                live_until_ledger: 1_300,
+            },
+        ]
+    );
+    let rent = settings.rent_fee(
+        &estimate
+            .bumps
+            .iter()
+            .map(|bump| RentChange {
+                persistent: true,
+                code: false,
+                old_size: bump.size,
+                new_size: bump.size,
+                old_live_until_ledger: bump.live_until_ledger,
+                new_live_until_ledger: 5_000,
+            })
+            .collect::<Vec<_>>(),
+        1_001,
+    );
+    assert!(estimate.bump_fee > rent);
+    assert!(estimate.bump_fee < rent + settings.size_fee(1_000));
+    assert_eq!(estimate.total(), 90_100 + 100 + estimate.bump_fee);
+    let text = estimate.to_string();
+    assert!(text.contains("keeping 2 entries live until ledger 5000"));
+    assert!(text.ends_with(&format!(
+        "  total: {} stroops (0.0{} XLM)\n",
+        estimate.total(),
+        estimate.total()
+    )));
+
+    // Past the longest TTL, entries are kept as long as they may be;
+    // without a ledger to keep them to, none are
+    let far = super::estimate(
+        &network,
+        &settings,
+        &sender(),
+        &create_stream(),
+        Some(1_000_000),
+    )
+    .unwrap();
+    assert_eq!(far.keep_until, Some(11_000));
+    let near = super::estimate(&network, &settings, &sender(), &create_stream(), None).unwrap();
+    assert!(near.bumps.is_empty());
+    assert_eq!(near.total(), 90_100);
+    let soon = super::estimate(
+        &network,
+        &settings,
+        &sender(),
+        &create_stream(),
+        Some(1_200),
+    )
+    .unwrap();
+    assert_eq!(soon.bumps.len(), 1);
+}
+
+#[test]
+fn an_operation_that_would_fail_has_no_estimate() {
+    let network = FakeNetwork::new(Some("HostError: Error(Contract, #6)"));
+    let err = estimate(&network, &settings(), &sender(), &create_stream(), None).unwrap_err();
+    let Error::Fails(report) = &err else {
+        panic!("{err}")
+    };
+    assert!(!report.succeeds());
+    assert_eq!(
+        err.to_string(),
+        format!("create_stream on {STREAMING} fails with StreamError::InvalidTimeRange (6)")
+    );
+
+    let withdraw = Operation::Withdraw {
+        streaming: contract_address(STREAMING).unwrap(),
+        stream_id: 6,
+    };
+    let err = estimate(&network, &settings(), &sender(), &withdraw, None).unwrap_err();
+    assert!(err.to_string().starts_with("withdraw on"));
+}
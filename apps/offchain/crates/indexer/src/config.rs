@@ -0,0 +1,92 @@
+//! Indexer settings, read from the environment.
+
+use lumenpulse_events::Contract;
+use std::{collections::BTreeMap, time::Duration};
+
+pub const DEFAULT_RPC_URL: &str = "https://soroban-testnet.stellar.org";
+pub const DEFAULT_DATABASE: &str = "lumenpulse-events.db";
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ConfigError {
+    #[error("{0} is not set")]
+    Missing(&'static str),
+    #[error("{name} is invalid: {reason}")]
+    Invalid { name: &'static str, reason: String },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// `INDEXER_RPC_URL`
+    pub rpc_url: String,
+    /// `INDEXER_DATABASE`, a path to the SQLite file
+    pub database: String,
+    /// `INDEXER_CONTRACTS`, as `kind=contract_id` pairs separated by commas,
+    /// such as `streaming=CA...,token=CB...`
+    pub contracts: BTreeMap<String, Contract>,
+    /// `INDEXER_START_LEDGER`, where a fresh database starts. Defaults to
+    /// the latest ledger; a database with a cursor resumes from it instead.
+    pub start_ledger: Option<u32>,
+    /// `INDEXER_PAGE_LIMIT`, events fetched per request
+    pub page_limit: u32,
+    /// `INDEXER_POLL_SECONDS`, how long to wait once caught up
+    pub poll_interval: Duration,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let contracts =
+            var("INDEXER_CONTRACTS").ok_or(ConfigError::Missing("INDEXER_CONTRACTS"))?;
+        let contracts = contracts
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (kind, id) = pair.split_once('=').ok_or_else(|| ConfigError::Invalid {
+                    name: "INDEXER_CONTRACTS",
+                    reason: format!("`{pair}` is not `kind=contract_id`"),
+                })?;
+                let kind = kind.trim().parse().map_err(|err| ConfigError::Invalid {
+                    name: "INDEXER_CONTRACTS",
+                    reason: format!("{err}"),
+                })?;
+                Ok((id.trim().to_string(), kind))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+        if contracts.is_empty() {
+            return Err(ConfigError::Missing("INDEXER_CONTRACTS"));
+        }
+
+        Ok(Config {
+            rpc_url: var("INDEXER_RPC_URL").unwrap_or_else(|| DEFAULT_RPC_URL.to_string()),
+            database: var("INDEXER_DATABASE").unwrap_or_else(|| DEFAULT_DATABASE.to_string()),
+            contracts,
+            start_ledger: parse(&var, "INDEXER_START_LEDGER")?,
+            page_limit: parse(&var, "INDEXER_PAGE_LIMIT")?.unwrap_or(200),
+            poll_interval: Duration::from_secs(parse(&var, "INDEXER_POLL_SECONDS")?.unwrap_or(5)),
+        })
+    }
+}
+
+fn parse<T: std::str::FromStr>(
+    var: &impl Fn(&str) -> Option<String>,
+    name: &'static str,
+) -> Result<Option<T>, ConfigError>
+where
+    T::Err: std::fmt::Display,
+{
+    var(name)
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|err: T::Err| ConfigError::Invalid {
+                    name,
+                    reason: err.to_string(),
+                })
+        })
+        .transpose()
+}
@@ -0,0 +1,149 @@
+//! Indexer for the events Lumenpulse contracts publish.
+//!
+//! The indexer pages through Soroban RPC `getEvents` for a configured set
+//! of contracts, decodes each event with `lumenpulse-events` and writes it
+//! to SQLite. The RPC's cursor is saved with every page, so a restarted
+//! indexer resumes where it stopped. The RPC only keeps a few days of
+//! events, so an indexer that stays down longer than that, or starts after
+//! the contracts were deployed, has a gap for `lumenpulse-backfill` to
+//! fill.
+
+pub mod config;
+pub mod store;
+
+pub use config::Config;
+pub use store::{Cursor, IndexedEvent, Store};
+
+use lumenpulse_events::Contract;
+use lumenpulse_rpc::{Client, EventsPage, EventsRequest, RpcEvent, Start};
+use std::{collections::BTreeMap, thread, time::Duration};
+
+/// Name the indexer saves its cursor under
+pub const CURSOR: &str = "events";
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(#[from] lumenpulse_rpc::Error),
+    #[error("database error: {0}")]
+    Store(#[from] rusqlite::Error),
+}
+
+/// Where events come from, the RPC in production
+pub trait EventSource {
+    fn latest_ledger(&self) -> Result<u32, lumenpulse_rpc::Error>;
+    fn events(&self, request: &EventsRequest) -> Result<EventsPage, lumenpulse_rpc::Error>;
+}
+
+impl EventSource for Client {
+    fn latest_ledger(&self) -> Result<u32, lumenpulse_rpc::Error> {
+        Ok(self.get_latest_ledger()?.sequence)
+    }
+
+    fn events(&self, request: &EventsRequest) -> Result<EventsPage, lumenpulse_rpc::Error> {
+        self.get_events(request)
+    }
+}
+
+/// What one step of indexing did
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Step {
+    /// Events stored that were not stored before
+    pub stored: usize,
+    /// Whether the page came back short, so there is nothing more to fetch
+    /// until new ledgers close
+    pub caught_up: bool,
+}
+
+pub struct Indexer<S> {
+    source: S,
+    store: Store,
+    /// Contract ID to the kind of contract it is
+    contracts: BTreeMap<String, Contract>,
+    start_ledger: Option<u32>,
+    page_limit: u32,
+}
+
+impl<S: EventSource> Indexer<S> {
+    pub fn new(source: S, store: Store, config: &Config) -> Self {
+        Indexer {
+            source,
+            store,
+            contracts: config.contracts.clone(),
+            start_ledger: config.start_ledger,
+            page_limit: config.page_limit,
+        }
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// Fetch and store the next page of events
+    pub fn step(&mut self) -> Result<Step, Error> {
+        let start = match self.store.cursor(CURSOR)? {
+            Some(cursor) => Start::Cursor(cursor.cursor),
+            None => match self.start_ledger {
+                Some(ledger) => Start::Ledger(ledger),
+                None => Start::Ledger(self.source.latest_ledger()?),
+            },
+        };
+        let page = self.source.events(&EventsRequest {
+            start,
+            contract_ids: self.contracts.keys().cloned().collect(),
+            limit: self.page_limit,
+        })?;
+
+        let caught_up = page.events.len() < self.page_limit as usize;
+        let events = decode_events(&self.contracts, page.events);
+        let cursor = Cursor {
+            cursor: page.cursor,
+            ledger: page.latest_ledger,
+        };
+        let stored = self.store.save_page(CURSOR, &events, &cursor)?;
+        Ok(Step { stored, caught_up })
+    }
+
+    /// Index until the store fails, waiting `poll_interval` whenever the
+    /// indexer catches up. RPC failures are reported and retried, as the
+    /// cursor makes every attempt start from the same place.
+    pub fn run(&mut self, poll_interval: Duration) -> Result<(), Error> {
+        loop {
+            match self.step() {
+                Ok(step) => {
+                    if step.stored > 0 {
+                        eprintln!("indexed {} events", step.stored);
+                    }
+                    if step.caught_up {
+                        thread::sleep(poll_interval);
+                    }
+                }
+                Err(Error::Rpc(err)) => {
+                    eprintln!("getEvents failed, retrying: {err}");
+                    thread::sleep(poll_interval);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Decode `events` with the schema of the contract that published each.
+/// The RPC only returns the contracts asked for, but an event from
+/// elsewhere would have no schema to decode with, so it is left out.
+pub fn decode_events(
+    contracts: &BTreeMap<String, Contract>,
+    events: Vec<RpcEvent>,
+) -> Vec<IndexedEvent> {
+    events
+        .into_iter()
+        .filter_map(|event| {
+            let contract = *contracts.get(&event.contract_id)?;
+            let decoded = lumenpulse_events::decode(contract, &event.topics, &event.value);
+            Some(IndexedEvent { event, decoded })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,27 @@
+use lumenpulse_indexer::{Config, Indexer, Store};
+use lumenpulse_rpc::Client;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("lumenpulse-indexer: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::from_env()?;
+    let store = Store::open(&config.database)?;
+    eprintln!(
+        "indexing {} contracts from {} into {}",
+        config.contracts.len(),
+        config.rpc_url,
+        config.database
+    );
+    let mut indexer = Indexer::new(Client::new(&config.rpc_url), store, &config);
+    indexer.run(config.poll_interval)?;
+    Ok(())
+}
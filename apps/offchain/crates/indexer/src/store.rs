@@ -0,0 +1,144 @@
+//! SQLite store for indexed events and the cursor to resume from.
+//!
+//! Each page of events is written in one transaction along with the cursor
+//! after it, so a crash between pages loses nothing and a restart picks up
+//! where the last committed page ended. Inserts ignore events already
+//! stored, which makes replaying a page harmless.
+
+use lumenpulse_events::{DecodeError, Event};
+use lumenpulse_rpc::RpcEvent;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::time::Duration;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    id TEXT PRIMARY KEY,
+    ledger INTEGER NOT NULL,
+    ledger_closed_at TEXT NOT NULL,
+    contract_id TEXT NOT NULL,
+    tx_hash TEXT NOT NULL,
+    -- Topics and data as JSON ScVals, kept whether or not they decode
+    topics TEXT NOT NULL,
+    data TEXT NOT NULL,
+    -- Schema and name, such as `streaming.withdrawn`, and the decoded event
+    -- as JSON; both NULL when the schema does not know the event
+    kind TEXT,
+    decoded TEXT,
+    -- Why a known event failed to decode
+    error TEXT
+);
+CREATE INDEX IF NOT EXISTS events_by_contract ON events (contract_id, ledger);
+CREATE INDEX IF NOT EXISTS events_by_kind ON events (kind, ledger);
+CREATE TABLE IF NOT EXISTS cursors (
+    name TEXT PRIMARY KEY,
+    cursor TEXT NOT NULL,
+    ledger INTEGER NOT NULL
+);
+";
+
+/// An event from the RPC with what the schema made of it
+pub struct IndexedEvent {
+    pub event: RpcEvent,
+    pub decoded: Result<Option<Event>, DecodeError>,
+}
+
+/// How far a named cursor has got
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cursor {
+    pub cursor: String,
+    /// Latest ledger the RPC had closed when the cursor was saved
+    pub ledger: u32,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    /// Open the database at `path`, waiting out a backfill writing to it
+    /// at the same time
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.busy_timeout(Duration::from_secs(5))?;
+        Self::with_connection(conn)
+    }
+
+    pub fn in_memory() -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Store { conn })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    pub fn cursor(&self, name: &str) -> rusqlite::Result<Option<Cursor>> {
+        self.conn
+            .query_row(
+                "SELECT cursor, ledger FROM cursors WHERE name = ?1",
+                [name],
+                |row| {
+                    Ok(Cursor {
+                        cursor: row.get(0)?,
+                        ledger: row.get(1)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    /// Store a page of events and move cursor `name` past them, all or
+    /// nothing. Returns how many events were new.
+    pub fn save_page(
+        &mut self,
+        name: &str,
+        events: &[IndexedEvent],
+        cursor: &Cursor,
+    ) -> rusqlite::Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut inserted = 0;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR IGNORE INTO events
+                    (id, ledger, ledger_closed_at, contract_id, tx_hash, topics, data, kind, decoded, error)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+            for IndexedEvent { event, decoded } in events {
+                let (kind, decoded, error) = match decoded {
+                    Ok(Some(decoded)) => (Some(decoded.kind()), Some(to_json(decoded)), None),
+                    Ok(None) => (None, None, None),
+                    Err(err) => (None, None, Some(err.to_string())),
+                };
+                inserted += insert.execute(params![
+                    event.id,
+                    event.ledger,
+                    event.ledger_closed_at,
+                    event.contract_id,
+                    event.tx_hash,
+                    to_json(&event.topics),
+                    to_json(&event.value),
+                    kind,
+                    decoded,
+                    error,
+                ])?;
+            }
+        }
+        tx.execute(
+            "INSERT INTO cursors (name, cursor, ledger) VALUES (?1, ?2, ?3)
+             ON CONFLICT (name) DO UPDATE SET cursor = excluded.cursor, ledger = excluded.ledger",
+            params![name, cursor.cursor, cursor.ledger],
+        )?;
+        tx.commit()?;
+        Ok(inserted)
+    }
+}
+
+fn to_json(value: &impl serde::Serialize) -> String {
+    // ScVals and events only hold strings, numbers and maps with string
+    // keys, which always serialize
+    serde_json::to_string(value).expect("serializable as JSON")
+}
@@ -0,0 +1,178 @@
+use crate::{Config, EventSource, Indexer, Step, Store, CURSOR};
+use lumenpulse_events::Contract;
+use lumenpulse_rpc::{EventsPage, EventsRequest, RpcEvent, Start};
+use std::{cell::RefCell, collections::VecDeque};
+use stellar_xdr::curr::{ContractId, Hash, ScAddress, ScMap, ScMapEntry, ScSymbol, ScVal};
+
+const STREAMING: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAK3IM";
+
+/// Serves canned pages and records what it was asked for
+#[derive(Default)]
+struct FakeSource {
+    pages: RefCell<VecDeque<EventsPage>>,
+    requests: RefCell<Vec<EventsRequest>>,
+}
+
+impl EventSource for &FakeSource {
+    fn latest_ledger(&self) -> Result<u32, lumenpulse_rpc::Error> {
+        Ok(5_000)
+    }
+
+    fn events(&self, request: &EventsRequest) -> Result<EventsPage, lumenpulse_rpc::Error> {
+        self.requests.borrow_mut().push(request.clone());
+        self.pages
+            .borrow_mut()
+            .pop_front()
+            .ok_or_else(|| lumenpulse_rpc::Error::Transport("no more pages".to_string()))
+    }
+}
+
+fn symbol(name: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(name.try_into().unwrap()))
+}
+
+fn withdrawn(id: &str, stream_id: ScVal) -> RpcEvent {
+    let recipient = ScVal::Address(ScAddress::Contract(ContractId(Hash([7; 32]))));
+    let amount = ScMapEntry {
+        key: symbol("amount"),
+        val: 250i128.into(),
+    };
+    RpcEvent {
+        id: id.to_string(),
+        ledger: 1_000,
+        ledger_closed_at: "2026-01-05T10:00:00Z".to_string(),
+        contract_id: STREAMING.to_string(),
+        tx_hash: "ab01".to_string(),
+        topics: vec![symbol("withdrawn_event"), recipient, stream_id],
+        value: ScVal::Map(Some(ScMap(vec![amount].try_into().unwrap()))),
+    }
+}
+
+fn page(events: Vec<RpcEvent>, cursor: &str) -> EventsPage {
+    EventsPage {
+        events,
+        cursor: cursor.to_string(),
+        latest_ledger: 1_010,
+    }
+}
+
+fn config() -> Config {
+    Config::from_vars(|name| match name {
+        "INDEXER_CONTRACTS" => Some(format!("streaming={STREAMING}")),
+        "INDEXER_START_LEDGER" => Some("900".to_string()),
+        "INDEXER_PAGE_LIMIT" => Some("2".to_string()),
+        _ => None,
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_config_from_vars() {
+    let config = config();
+    assert_eq!(config.contracts.get(STREAMING), Some(&Contract::Streaming));
+    assert_eq!(config.start_ledger, Some(900));
+    assert_eq!(config.rpc_url, crate::config::DEFAULT_RPC_URL);
+
+    let err = Config::from_vars(|name| (name == "INDEXER_CONTRACTS").then(|| "vault=C1".into()));
+    assert!(err
+        .unwrap_err()
+        .to_string()
+        .contains("unknown contract kind `vault`"));
+    let err = Config::from_vars(|_| None);
+    assert_eq!(
+        err,
+        Err(crate::config::ConfigError::Missing("INDEXER_CONTRACTS"))
+    );
+}
+
+#[test]
+fn test_indexes_pages_and_resumes_from_cursor() {
+    let source = FakeSource::default();
+    source.pages.borrow_mut().extend([
+        page(
+            vec![
+                withdrawn("0001", ScVal::U64(4)),
+                RpcEvent {
+                    topics: vec![symbol("oracle_set_event")],
+                    ..withdrawn("0002", ScVal::U64(4))
+                },
+            ],
+            "0002",
+        ),
+        // The next page repeats an event, as a replay after a crash would
+        page(
+            vec![
+                withdrawn("0002", ScVal::U64(4)),
+                withdrawn("0003", ScVal::U32(4)),
+            ],
+            "0003",
+        ),
+    ]);
+
+    let mut indexer = Indexer::new(&source, Store::in_memory().unwrap(), &config());
+    assert_eq!(
+        indexer.step().unwrap(),
+        Step {
+            stored: 2,
+            caught_up: false
+        }
+    );
+    assert_eq!(
+        indexer.step().unwrap(),
+        Step {
+            stored: 1,
+            caught_up: false
+        }
+    );
+    let requests = source.requests.borrow();
+    assert_eq!(requests[0].start, Start::Ledger(900));
+    assert_eq!(requests[0].contract_ids, vec![STREAMING.to_string()]);
+    assert_eq!(requests[1].start, Start::Cursor("0002".to_string()));
+    drop(requests);
+
+    let conn = indexer.store().connection();
+    let rows: Vec<(String, Option<String>, Option<String>)> = conn
+        .prepare("SELECT id, kind, error FROM events ORDER BY id")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            ("0001".into(), Some("streaming.withdrawn".into()), None),
+            // Unknown events are kept raw and drifted ones with the reason
+            ("0002".into(), None, None),
+            ("0003".into(), None, Some("`stream_id` is not a u64".into())),
+        ]
+    );
+    let decoded: String = conn
+        .query_row("SELECT decoded FROM events WHERE id = '0001'", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    let decoded: serde_json::Value = serde_json::from_str(&decoded).unwrap();
+    assert_eq!(decoded["streaming"]["amount"], 250);
+    assert_eq!(decoded["streaming"]["stream_id"], 4);
+
+    // A failed fetch leaves the cursor where the last page put it, and a
+    // fresh indexer over the same store picks up from there
+    assert!(indexer.step().is_err());
+    let cursor = indexer.store().cursor(CURSOR).unwrap().unwrap();
+    assert_eq!((cursor.cursor.as_str(), cursor.ledger), ("0003", 1_010));
+
+    let mut resumed = Indexer::new(&source, indexer.store, &config());
+    source.pages.borrow_mut().push_back(page(vec![], "0009"));
+    assert_eq!(
+        resumed.step().unwrap(),
+        Step {
+            stored: 0,
+            caught_up: true
+        }
+    );
+    assert_eq!(
+        source.requests.borrow().last().unwrap().start,
+        Start::Cursor("0003".to_string())
+    );
+}
@@ -0,0 +1,156 @@
+//! Keeper settings, read from the environment.
+//!
+//! `KEEPER_NETWORK` picks a preset from `lumenpulse_tx::network`, which
+//! supplies the RPC and passphrase; `KEEPER_RPC_URL` and
+//! `KEEPER_NETWORK_PASSPHRASE` override them, so one keeper binary runs
+//! against any network with its own set of variables.
+//!
+//! With `KEEPER_LOCKFILE` set, the contracts default to the ones named
+//! `streaming`, `subscriptions` and `escrow` in the deployer's lockfile
+//! for `KEEPER_NETWORK`.
+
+use lumenpulse_deployer::Lockfile;
+use lumenpulse_tx::network;
+use std::time::Duration;
+
+pub const DEFAULT_NETWORK: &str = "testnet";
+pub const DEFAULT_DATABASE: &str = "lumenpulse-events.db";
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ConfigError {
+    #[error("{0} is not set")]
+    Missing(&'static str),
+    #[error("{name} is invalid: {reason}")]
+    Invalid { name: &'static str, reason: String },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// `KEEPER_NETWORK`, a preset such as `testnet` or `mainnet`
+    pub network: String,
+    /// `KEEPER_RPC_URL`, or the preset's
+    pub rpc_url: String,
+    /// `KEEPER_NETWORK_PASSPHRASE`, or the preset's. Asked of the RPC when
+    /// neither is known.
+    pub passphrase: Option<String>,
+    /// `KEEPER_DATABASE`, the indexer's SQLite file
+    pub database: String,
+    /// `KEEPER_STREAMING`, the streaming contract to withdraw on
+    pub streaming: Option<String>,
+    /// `KEEPER_SUBSCRIPTIONS`, the subscriptions contract to process
+    pub subscriptions: Option<String>,
+    /// `KEEPER_ESCROW`, the escrow contract to finalize on
+    pub escrow: Option<String>,
+    /// `KEEPER_INTERVAL_SECONDS`, how long to wait between cycles
+    pub interval: Duration,
+    /// `KEEPER_MAX_BACKOFF_SECONDS`, the longest a failing job waits
+    pub max_backoff: Duration,
+    /// `KEEPER_BATCH_SIZE`, streams or subscriptions per call, at most
+    /// `MAX_BATCH`
+    pub batch_size: u32,
+    /// `KEEPER_MIN_WITHDRAWAL`, the smallest amount worth withdrawing from
+    /// a stream
+    pub min_withdrawal: i128,
+    /// `KEEPER_MAX_FEE`, stroops one transaction may pay; larger batches
+    /// are split
+    pub max_fee: u32,
+    /// `KEEPER_CYCLE_BUDGET`, stroops one cycle may spend in all
+    pub cycle_budget: u64,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let network = var("KEEPER_NETWORK").unwrap_or_else(|| DEFAULT_NETWORK.to_string());
+        let preset = network::preset(&network);
+        let rpc_url = var("KEEPER_RPC_URL")
+            .or_else(|| preset.and_then(|preset| preset.rpc_url).map(String::from))
+            .ok_or(ConfigError::Missing("KEEPER_RPC_URL"))?;
+        let passphrase = var("KEEPER_NETWORK_PASSPHRASE")
+            .or_else(|| preset.map(|preset| preset.passphrase.to_string()));
+
+        let lockfile = var("KEEPER_LOCKFILE")
+            .map(|path| {
+                Lockfile::read(&path).map_err(|err| ConfigError::Invalid {
+                    name: "KEEPER_LOCKFILE",
+                    reason: err.to_string(),
+                })
+            })
+            .transpose()?;
+        let contract = |name: &'static str, locked: &str| {
+            var(name)
+                .or_else(|| {
+                    let lockfile = lockfile.as_ref()?;
+                    lockfile.contract_id(&network, locked).map(String::from)
+                })
+                .map(|id| {
+                    let id = id.trim().to_string();
+                    match lumenpulse_tx::contract_address(&id) {
+                        Some(_) => Ok(id),
+                        None => Err(ConfigError::Invalid {
+                            name,
+                            reason: format!("`{id}` is not a contract address"),
+                        }),
+                    }
+                })
+                .transpose()
+        };
+        let streaming = contract("KEEPER_STREAMING", "streaming")?;
+        let subscriptions = contract("KEEPER_SUBSCRIPTIONS", "subscriptions")?;
+        let escrow = contract("KEEPER_ESCROW", "escrow")?;
+        if streaming.is_none() && subscriptions.is_none() && escrow.is_none() {
+            return Err(ConfigError::Missing(
+                "KEEPER_STREAMING, KEEPER_SUBSCRIPTIONS or KEEPER_ESCROW",
+            ));
+        }
+
+        let batch_size = parse(&var, "KEEPER_BATCH_SIZE")?.unwrap_or(crate::MAX_BATCH);
+        if batch_size == 0 || batch_size > crate::MAX_BATCH {
+            return Err(ConfigError::Invalid {
+                name: "KEEPER_BATCH_SIZE",
+                reason: format!("must be between 1 and {}", crate::MAX_BATCH),
+            });
+        }
+
+        Ok(Config {
+            network,
+            rpc_url,
+            passphrase,
+            database: var("KEEPER_DATABASE").unwrap_or_else(|| DEFAULT_DATABASE.to_string()),
+            streaming,
+            subscriptions,
+            escrow,
+            interval: Duration::from_secs(parse(&var, "KEEPER_INTERVAL_SECONDS")?.unwrap_or(60)),
+            max_backoff: Duration::from_secs(
+                parse(&var, "KEEPER_MAX_BACKOFF_SECONDS")?.unwrap_or(900),
+            ),
+            batch_size,
+            min_withdrawal: parse(&var, "KEEPER_MIN_WITHDRAWAL")?.unwrap_or(1),
+            max_fee: parse(&var, "KEEPER_MAX_FEE")?.unwrap_or(10_000_000),
+            cycle_budget: parse(&var, "KEEPER_CYCLE_BUDGET")?.unwrap_or(100_000_000),
+        })
+    }
+}
+
+fn parse<T: std::str::FromStr>(
+    var: &impl Fn(&str) -> Option<String>,
+    name: &'static str,
+) -> Result<Option<T>, ConfigError>
+where
+    T::Err: std::fmt::Display,
+{
+    var(name)
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|err: T::Err| ConfigError::Invalid {
+                    name,
+                    reason: err.to_string(),
+                })
+        })
+        .transpose()
+}
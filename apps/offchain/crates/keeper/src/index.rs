@@ -0,0 +1,115 @@
+//! Work the keeper finds in the indexer's database.
+//!
+//! The index says which streams and escrows exist and which have ended;
+//! whether one has anything to do right now is left to simulation, which
+//! sees the contract's current state rather than the last indexed page.
+
+use rusqlite::{params, Connection, OpenFlags};
+use serde_json::Value;
+use std::{collections::BTreeMap, time::Duration};
+
+pub struct Index {
+    conn: Connection,
+}
+
+/// A stream the keeper may withdraw on
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OpenStream {
+    pub stream_id: u64,
+    pub end_time: u64,
+}
+
+impl Index {
+    /// Open the indexer's database for reading, alongside the running
+    /// indexer
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        conn.busy_timeout(Duration::from_secs(5))?;
+        Ok(Index { conn })
+    }
+
+    pub fn with_connection(conn: Connection) -> Self {
+        Index { conn }
+    }
+
+    /// Streams created on `contract` that have not been cancelled
+    pub fn open_streams(&self, contract: &str) -> rusqlite::Result<Vec<OpenStream>> {
+        let mut streams = BTreeMap::new();
+        for (kind, event) in self.events(
+            contract,
+            "streaming",
+            &["streaming.stream_created", "streaming.stream_cancelled"],
+        )? {
+            let Some(stream_id) = event["stream_id"].as_u64() else {
+                continue;
+            };
+            if kind == "streaming.stream_created" {
+                let end_time = event["end_time"].as_u64().unwrap_or(u64::MAX);
+                streams.insert(
+                    stream_id,
+                    OpenStream {
+                        stream_id,
+                        end_time,
+                    },
+                );
+            } else {
+                streams.remove(&stream_id);
+            }
+        }
+        Ok(streams.into_values().collect())
+    }
+
+    /// Escrows created on `contract` that have not been released or
+    /// refunded
+    pub fn open_escrows(&self, contract: &str) -> rusqlite::Result<Vec<u64>> {
+        let mut escrows = Vec::new();
+        for (kind, event) in self.events(
+            contract,
+            "escrow",
+            &[
+                "escrow.escrow_created",
+                "escrow.released",
+                "escrow.refunded",
+            ],
+        )? {
+            let Some(escrow_id) = event["escrow_id"].as_u64() else {
+                continue;
+            };
+            if kind == "escrow.escrow_created" {
+                escrows.push(escrow_id);
+            } else {
+                escrows.retain(|id| *id != escrow_id);
+            }
+        }
+        escrows.sort_unstable();
+        escrows.dedup();
+        Ok(escrows)
+    }
+
+    /// Decoded events of `kinds` from `contract` in the order they
+    /// happened, as the `schema` part of the stored JSON
+    fn events(
+        &self,
+        contract: &str,
+        schema: &str,
+        kinds: &[&str],
+    ) -> rusqlite::Result<Vec<(String, Value)>> {
+        let mut statement = self.conn.prepare(
+            "SELECT kind, decoded FROM events
+             WHERE contract_id = ?1 AND kind IN (SELECT value FROM json_each(?2))
+             ORDER BY ledger, id",
+        )?;
+        let kinds = serde_json::to_string(kinds).expect("strings serialize");
+        let rows = statement.query_map(params![contract, kinds], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        let mut events = Vec::new();
+        for row in rows {
+            let (kind, decoded) = row?;
+            if let Ok(mut decoded) = serde_json::from_str::<Value>(&decoded) {
+                events.push((kind, decoded[schema].take()));
+            }
+        }
+        Ok(events)
+    }
+}
@@ -0,0 +1,512 @@
+//! Keeper for the Lumenpulse entrypoints anyone may call.
+//!
+//! Some work on the contracts only happens when someone sends a
+//! transaction for it: streams pay out on `withdraw_many`, subscriptions
+//! charge on `process_due` and timed escrows release on `finalize`. The
+//! keeper finds candidates in the indexer's database, simulates each call
+//! to see whether it would do anything, and sends the ones that would.
+//! Batches whose fee is over `max_fee` are split, and a cycle stops
+//! sending once it has spent its budget. A job that fails backs off
+//! exponentially while the others carry on.
+
+pub mod config;
+pub mod index;
+
+pub use config::Config;
+pub use index::{Index, OpenStream};
+
+use lumenpulse_clients::{
+    escrow::{self, EscrowData, EscrowStatus},
+    streaming::{self, WithdrawResult, WithdrawStatus},
+    subscriptions::{self, ProcessReport},
+    Call, ContractError, EscrowClient, FromScVal, StreamingClient, SubscriptionsClient,
+};
+use lumenpulse_tx::{Prepared, Sender};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use stellar_xdr::curr::{HostFunction, ScAddress, ScVal};
+
+/// Most streams `withdraw_many`, and subscriptions `process_due`, take in
+/// one call
+pub const MAX_BATCH: u32 = 50;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] lumenpulse_tx::Error),
+    #[error(transparent)]
+    Client(#[from] lumenpulse_clients::Error),
+    #[error("database error: {0}")]
+    Index(#[from] rusqlite::Error),
+}
+
+/// What simulating an invocation found
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simulated {
+    pub return_value: Option<ScVal>,
+    /// Fee the transaction would pay, in stroops
+    pub fee: u32,
+}
+
+/// Where invocations go, a `Preflighted` sender in production
+pub trait Chain {
+    fn simulate(&self, host_function: &HostFunction) -> Result<Simulated, lumenpulse_tx::Error>;
+    /// Send `host_function` and wait for it to land, returning its hash
+    fn send(&self, host_function: HostFunction) -> Result<String, lumenpulse_tx::Error>;
+}
+
+/// Sends through a `Sender`, preflighting every call and printing the
+/// report of any that would fail, with errors named from the spec of the
+/// contract called
+pub struct Preflighted<'a> {
+    sender: Sender<'a>,
+    errors: Vec<(ScAddress, &'static [ContractError])>,
+}
+
+impl<'a> Preflighted<'a> {
+    pub fn new(sender: Sender<'a>, config: &Config) -> Self {
+        let errors = [
+            (&config.streaming, streaming::ERRORS),
+            (&config.subscriptions, subscriptions::ERRORS),
+            (&config.escrow, escrow::ERRORS),
+        ]
+        .into_iter()
+        .filter_map(|(contract, errors)| {
+            let contract = lumenpulse_tx::contract_address(contract.as_deref()?)?;
+            Some((contract, errors))
+        })
+        .collect();
+        Preflighted { sender, errors }
+    }
+
+    fn prepare(&self, host_function: HostFunction) -> Result<Prepared, lumenpulse_tx::Error> {
+        let errors = match &host_function {
+            HostFunction::InvokeContract(invoke) => self
+                .errors
+                .iter()
+                .find(|(contract, _)| *contract == invoke.contract_address)
+                .map_or(&[][..], |(_, errors)| errors),
+            _ => &[],
+        };
+        let preflight = lumenpulse_preflight::preflight(&self.sender, host_function, errors)?;
+        if !preflight.report.succeeds() {
+            eprint!("{}", preflight.report);
+        }
+        preflight.prepare()
+    }
+}
+
+impl Chain for Preflighted<'_> {
+    fn simulate(&self, host_function: &HostFunction) -> Result<Simulated, lumenpulse_tx::Error> {
+        let prepared = self.prepare(host_function.clone())?;
+        Ok(Simulated {
+            return_value: prepared.simulation.return_value,
+            fee: prepared.tx.fee,
+        })
+    }
+
+    // Sending prepares again, so the transaction carries the footprint of
+    // the ledger it is sent against
+    fn send(&self, host_function: HostFunction) -> Result<String, lumenpulse_tx::Error> {
+        let prepared = self.prepare(host_function)?;
+        Ok(self.sender.submit(prepared.tx)?.hash)
+    }
+}
+
+/// What one cycle did
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Cycle {
+    /// Transactions sent
+    pub sent: u32,
+    /// Fees those transactions bid, in stroops
+    pub fees: u64,
+    /// Jobs that failed and are backing off
+    pub failed: u32,
+    /// Whether the cycle left work undone for lack of budget
+    pub over_budget: bool,
+}
+
+enum Work {
+    Withdraw {
+        client: StreamingClient,
+        /// Streams with nothing left to pay, which are not asked again
+        finished: BTreeSet<u64>,
+    },
+    Process {
+        client: SubscriptionsClient,
+    },
+    Finalize {
+        client: EscrowClient,
+        /// Release time of each escrow read so far, `None` for escrows that
+        /// are not timed and never need finalizing
+        deadlines: BTreeMap<u64, Option<u64>>,
+    },
+}
+
+struct Job {
+    contract: String,
+    work: Work,
+    failures: u32,
+    retry_at: u64,
+}
+
+impl Job {
+    fn name(&self) -> &'static str {
+        match self.work {
+            Work::Withdraw { .. } => "withdraw_many",
+            Work::Process { .. } => "process_due",
+            Work::Finalize { .. } => "finalize",
+        }
+    }
+}
+
+struct Limits {
+    batch_size: u32,
+    min_withdrawal: i128,
+    max_fee: u32,
+}
+
+/// State shared by the jobs of one cycle
+struct Run<'a, C> {
+    chain: &'a C,
+    limits: &'a Limits,
+    now: u64,
+    budget: u64,
+    cycle: Cycle,
+}
+
+pub struct Keeper<C> {
+    chain: C,
+    index: Index,
+    jobs: Vec<Job>,
+    limits: Limits,
+    cycle_budget: u64,
+    interval: Duration,
+    max_backoff: Duration,
+}
+
+impl<C: Chain> Keeper<C> {
+    pub fn new(chain: C, index: Index, config: &Config) -> Self {
+        let job = |contract: &str, work: fn(stellar_xdr::curr::ScAddress) -> Work| Job {
+            contract: contract.to_string(),
+            work: work(lumenpulse_tx::contract_address(contract).expect("checked by Config")),
+            failures: 0,
+            retry_at: 0,
+        };
+        let mut jobs = Vec::new();
+        if let Some(contract) = &config.streaming {
+            jobs.push(job(contract, |address| Work::Withdraw {
+                client: StreamingClient::new(address),
+                finished: BTreeSet::new(),
+            }));
+        }
+        if let Some(contract) = &config.subscriptions {
+            jobs.push(job(contract, |address| Work::Process {
+                client: SubscriptionsClient::new(address),
+            }));
+        }
+        if let Some(contract) = &config.escrow {
+            jobs.push(job(contract, |address| Work::Finalize {
+                client: EscrowClient::new(address),
+                deadlines: BTreeMap::new(),
+            }));
+        }
+        Keeper {
+            chain,
+            index,
+            jobs,
+            limits: Limits {
+                batch_size: config.batch_size,
+                min_withdrawal: config.min_withdrawal,
+                max_fee: config.max_fee,
+            },
+            cycle_budget: config.cycle_budget,
+            interval: config.interval,
+            max_backoff: config.max_backoff,
+        }
+    }
+
+    /// Run every job that is not backing off, as of `now` in seconds since
+    /// the Unix epoch
+    pub fn cycle(&mut self, now: u64) -> Cycle {
+        let mut run = Run {
+            chain: &self.chain,
+            limits: &self.limits,
+            now,
+            budget: self.cycle_budget,
+            cycle: Cycle::default(),
+        };
+        for job in &mut self.jobs {
+            if job.retry_at > now {
+                continue;
+            }
+            let result = match &mut job.work {
+                Work::Withdraw { client, finished } => {
+                    run.withdraw(&self.index, &job.contract, client, finished)
+                }
+                Work::Process { client } => run.process(client),
+                Work::Finalize { client, deadlines } => {
+                    run.finalize(&self.index, &job.contract, client, deadlines)
+                }
+            };
+            match result {
+                Ok(()) => job.failures = 0,
+                Err(err) => {
+                    job.failures += 1;
+                    let delay = backoff(self.interval, self.max_backoff, job.failures);
+                    job.retry_at = now + delay.as_secs();
+                    run.cycle.failed += 1;
+                    eprintln!(
+                        "{} on {} failed, retrying in {}s: {err}",
+                        job.name(),
+                        job.contract,
+                        delay.as_secs()
+                    );
+                }
+            }
+        }
+        run.cycle
+    }
+
+    /// Run a cycle every `interval`, forever
+    pub fn run(&mut self) {
+        loop {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let cycle = self.cycle(now);
+            if cycle.sent > 0 || cycle.over_budget {
+                eprintln!(
+                    "sent {} transactions bidding {} stroops{}",
+                    cycle.sent,
+                    cycle.fees,
+                    if cycle.over_budget {
+                        ", stopped at the cycle budget"
+                    } else {
+                        ""
+                    }
+                );
+            }
+            thread::sleep(self.interval);
+        }
+    }
+}
+
+/// Wait after the `failures`th failure in a row: `interval` doubled for
+/// each failure after the first, up to `max`
+pub fn backoff(interval: Duration, max: Duration, failures: u32) -> Duration {
+    let factor = 1u32
+        .checked_shl(failures.saturating_sub(1))
+        .unwrap_or(u32::MAX);
+    interval.saturating_mul(factor).min(max)
+}
+
+fn decode<R: FromScVal>(simulated: &Simulated) -> Result<R, Error> {
+    Ok(Call::<R>::decode(simulated.return_value.as_ref())?)
+}
+
+impl<C: Chain> Run<'_, C> {
+    /// Send `call` if it fits in the per-transaction cap and what is left
+    /// of the budget. Returns whether it was sent.
+    fn send(&mut self, call: HostFunction, fee: u32, what: &str) -> Result<bool, Error> {
+        if fee > self.limits.max_fee {
+            eprintln!("skipping {what}: its fee of {fee} is over the cap");
+            return Ok(false);
+        }
+        if u64::from(fee) > self.budget {
+            self.cycle.over_budget = true;
+            return Ok(false);
+        }
+        let hash = self.chain.send(call)?;
+        self.budget -= u64::from(fee);
+        self.cycle.sent += 1;
+        self.cycle.fees += u64::from(fee);
+        eprintln!("{what}: {hash}");
+        Ok(true)
+    }
+
+    fn withdraw(
+        &mut self,
+        index: &Index,
+        contract: &str,
+        client: &StreamingClient,
+        finished: &mut BTreeSet<u64>,
+    ) -> Result<(), Error> {
+        let streams: BTreeMap<u64, u64> = index
+            .open_streams(contract)?
+            .into_iter()
+            .filter(|stream| !finished.contains(&stream.stream_id))
+            .map(|stream| (stream.stream_id, stream.end_time))
+            .collect();
+        let ids: Vec<u64> = streams.keys().copied().collect();
+        for batch in ids.chunks(self.limits.batch_size as usize) {
+            self.withdraw_batch(client, batch.to_vec(), &streams, finished)?;
+            if self.cycle.over_budget {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Withdraw on the streams of `batch` that have enough to pay,
+    /// splitting it while its fee is over the cap
+    fn withdraw_batch(
+        &mut self,
+        client: &StreamingClient,
+        mut batch: Vec<u64>,
+        end_times: &BTreeMap<u64, u64>,
+        finished: &mut BTreeSet<u64>,
+    ) -> Result<(), Error> {
+        loop {
+            let call = client.withdraw_many(&batch)?;
+            let simulated = self.chain.simulate(&call.host_function)?;
+            let results: Vec<WithdrawResult> = decode(&simulated)?;
+
+            let mut worth = Vec::new();
+            for result in results {
+                match result.status {
+                    WithdrawStatus::Withdrawn if result.amount >= self.limits.min_withdrawal => {
+                        worth.push(result.stream_id)
+                    }
+                    WithdrawStatus::Withdrawn => {}
+                    WithdrawStatus::NothingToWithdraw => {
+                        let ended = end_times
+                            .get(&result.stream_id)
+                            .is_some_and(|end_time| self.now >= *end_time);
+                        if ended {
+                            finished.insert(result.stream_id);
+                        }
+                    }
+                    WithdrawStatus::StreamNotFound | WithdrawStatus::StreamCancelled => {
+                        finished.insert(result.stream_id);
+                    }
+                    // Retried once the oracle prices the stream again
+                    WithdrawStatus::PriceUnavailable => {}
+                }
+            }
+            if worth.is_empty() {
+                return Ok(());
+            }
+            // Simulate again without the streams not worth paying, so the
+            // fee is that of the batch sent
+            if worth.len() < batch.len() {
+                batch = worth;
+                continue;
+            }
+
+            if simulated.fee > self.limits.max_fee && batch.len() > 1 {
+                let second = batch.split_off(batch.len() / 2);
+                self.withdraw_batch(client, batch, end_times, finished)?;
+                return self.withdraw_batch(client, second, end_times, finished);
+            }
+            let what = format!("withdraw_many on {} streams", batch.len());
+            self.send(call.host_function, simulated.fee, &what)?;
+            return Ok(());
+        }
+    }
+
+    fn process(&mut self, client: &SubscriptionsClient) -> Result<(), Error> {
+        let count: u64 = decode(
+            &self
+                .chain
+                .simulate(&client.subscription_count()?.host_function)?,
+        )?;
+        let mut cursor = 0;
+        while cursor < count && !self.cycle.over_budget {
+            self.process_page(client, cursor, self.limits.batch_size)?;
+            cursor += u64::from(self.limits.batch_size);
+        }
+        Ok(())
+    }
+
+    /// Charge what is due among `limit` subscriptions from `cursor`,
+    /// splitting the page while its fee is over the cap
+    fn process_page(
+        &mut self,
+        client: &SubscriptionsClient,
+        cursor: u64,
+        limit: u32,
+    ) -> Result<(), Error> {
+        let call = client.process_due(cursor, limit)?;
+        let simulated = self.chain.simulate(&call.host_function)?;
+        let report: ProcessReport = decode(&simulated)?;
+        if report.charged + report.failed + report.lapsed == 0 {
+            return Ok(());
+        }
+        if simulated.fee > self.limits.max_fee && limit > 1 {
+            let half = limit / 2;
+            self.process_page(client, cursor, half)?;
+            return self.process_page(client, cursor + u64::from(half), limit - half);
+        }
+        let what = format!("process_due from {cursor} charging {}", report.charged);
+        self.send(call.host_function, simulated.fee, &what)?;
+        Ok(())
+    }
+
+    fn finalize(
+        &mut self,
+        index: &Index,
+        contract: &str,
+        client: &EscrowClient,
+        deadlines: &mut BTreeMap<u64, Option<u64>>,
+    ) -> Result<(), Error> {
+        let open = index.open_escrows(contract)?;
+        deadlines.retain(|escrow_id, _| open.binary_search(escrow_id).is_ok());
+        for escrow_id in open {
+            if self.cycle.over_budget {
+                break;
+            }
+            let deadline = match deadlines.get(&escrow_id) {
+                Some(deadline) => *deadline,
+                None => {
+                    let escrow: EscrowData = decode(
+                        &self
+                            .chain
+                            .simulate(&client.get_escrow(escrow_id)?.host_function)?,
+                    )?;
+                    // A disputed escrow is read again next time, as the
+                    // dispute may have been settled by then
+                    match (escrow.status, escrow.release_after) {
+                        (_, None) => deadlines.insert(escrow_id, None),
+                        (EscrowStatus::Active, Some(at)) => deadlines.insert(escrow_id, Some(at)),
+                        _ => continue,
+                    };
+                    escrow.release_after
+                }
+            };
+            let Some(deadline) = deadline else {
+                continue;
+            };
+            if self.now < deadline {
+                continue;
+            }
+
+            let call = client.finalize(escrow_id)?;
+            let simulated = match self.chain.simulate(&call.host_function) {
+                Ok(simulated) => simulated,
+                // The ledger may lag the clock, or the escrow may have been
+                // disputed since it was read; read it again next time
+                Err(lumenpulse_tx::Error::Simulation(_)) => {
+                    deadlines.remove(&escrow_id);
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+            if self.send(
+                call.host_function,
+                simulated.fee,
+                &format!("finalize escrow {escrow_id}"),
+            )? {
+                deadlines.remove(&escrow_id);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
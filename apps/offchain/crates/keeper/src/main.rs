@@ -0,0 +1,35 @@
+use lumenpulse_keeper::{Config, Index, Keeper, Preflighted};
+use lumenpulse_rpc::Client;
+use lumenpulse_tx::{Sender, Signer};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("lumenpulse-keeper: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::from_env()?;
+    let secret = std::env::var("KEEPER_SECRET_KEY").map_err(|_| "KEEPER_SECRET_KEY is not set")?;
+    let signer = Signer::from_secret(&secret)?;
+    let client = Client::new(&config.rpc_url);
+    let passphrase = match &config.passphrase {
+        Some(passphrase) => passphrase.clone(),
+        None => client.get_network()?.passphrase,
+    };
+    let index = Index::open(&config.database)?;
+    eprintln!(
+        "keeping {} from {} as {}",
+        config.network,
+        config.database,
+        signer.account()
+    );
+    let sender = Sender::new(&client, &signer, &passphrase);
+    Keeper::new(Preflighted::new(sender, &config), index, &config).run();
+    Ok(())
+}
@@ -0,0 +1,347 @@
+use crate::config::ConfigError;
+use crate::{backoff, Chain, Config, Cycle, Index, Keeper, Simulated};
+use lumenpulse_clients::{
+    escrow::{EscrowData, EscrowStatus},
+    streaming::{WithdrawResult, WithdrawStatus},
+    subscriptions::ProcessReport,
+    ToScVal,
+};
+use lumenpulse_deployer::{Deployment, Lockfile};
+use rusqlite::{params, Connection};
+use serde_json::json;
+use std::{cell::RefCell, time::Duration};
+use stellar_xdr::curr::{ContractId, Hash, HostFunction, ScAddress, ScVal};
+
+const STREAMING: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+const ESCROW: &str = "CAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC526";
+const SUBSCRIPTIONS: &str = "CABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAFNSZ";
+
+type Respond = dyn Fn(&str, &[ScVal]) -> Result<Simulated, lumenpulse_tx::Error>;
+
+/// Answers simulations with `respond` and records every call
+struct FakeChain {
+    respond: Box<Respond>,
+    simulated: RefCell<Vec<(String, Vec<ScVal>)>>,
+    sent: RefCell<Vec<(String, Vec<ScVal>)>>,
+}
+
+fn call(host_function: &HostFunction) -> (String, Vec<ScVal>) {
+    let HostFunction::InvokeContract(invoke) = host_function else {
+        panic!("not a contract invocation");
+    };
+    (
+        invoke.function_name.0.to_utf8_string_lossy(),
+        invoke.args.to_vec(),
+    )
+}
+
+impl FakeChain {
+    fn new(
+        respond: impl Fn(&str, &[ScVal]) -> Result<Simulated, lumenpulse_tx::Error> + 'static,
+    ) -> Self {
+        FakeChain {
+            respond: Box::new(respond),
+            simulated: RefCell::default(),
+            sent: RefCell::default(),
+        }
+    }
+}
+
+impl Chain for &FakeChain {
+    fn simulate(&self, host_function: &HostFunction) -> Result<Simulated, lumenpulse_tx::Error> {
+        let (function, args) = call(host_function);
+        self.simulated
+            .borrow_mut()
+            .push((function.clone(), args.clone()));
+        (self.respond)(&function, &args)
+    }
+
+    fn send(&self, host_function: HostFunction) -> Result<String, lumenpulse_tx::Error> {
+        self.sent.borrow_mut().push(call(&host_function));
+        Ok("ab01".to_string())
+    }
+}
+
+fn returns(value: impl ToScVal, fee: u32) -> Result<Simulated, lumenpulse_tx::Error> {
+    Ok(Simulated {
+        return_value: Some(value.to_sc_val().unwrap()),
+        fee,
+    })
+}
+
+fn ids(args: &[ScVal]) -> Vec<u64> {
+    let ScVal::Vec(Some(ids)) = &args[0] else {
+        panic!("not a list of IDs");
+    };
+    ids.iter()
+        .map(|id| match id {
+            ScVal::U64(id) => *id,
+            _ => panic!("not an ID"),
+        })
+        .collect()
+}
+
+/// The indexer's events table holding `events` as `(contract, kind,
+/// decoded)`
+fn index(events: &[(&str, &str, serde_json::Value)]) -> Index {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE events (id TEXT PRIMARY KEY, ledger INTEGER, contract_id TEXT, kind TEXT, decoded TEXT)",
+    )
+    .unwrap();
+    for (i, (contract, kind, decoded)) in events.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO events VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                format!("{i:04}"),
+                i as i64,
+                contract,
+                kind,
+                decoded.to_string()
+            ],
+        )
+        .unwrap();
+    }
+    Index::with_connection(conn)
+}
+
+fn stream_created(
+    stream_id: u64,
+    end_time: u64,
+) -> (&'static str, &'static str, serde_json::Value) {
+    (
+        STREAMING,
+        "streaming.stream_created",
+        json!({ "streaming": { "kind": "stream_created", "stream_id": stream_id, "end_time": end_time } }),
+    )
+}
+
+fn config(vars: &[(&str, &str)]) -> Config {
+    Config::from_vars(|name| {
+        vars.iter()
+            .find(|(var, _)| *var == name)
+            .map(|(_, value)| value.to_string())
+    })
+    .unwrap()
+}
+
+#[test]
+fn withdraws_only_streams_worth_paying_and_splits_costly_batches() {
+    let index = index(&[
+        stream_created(0, 1_000),
+        stream_created(1, 1_000),
+        stream_created(2, 500),
+        stream_created(3, 1_000),
+        (
+            STREAMING,
+            "streaming.stream_cancelled",
+            json!({ "streaming": { "kind": "stream_cancelled", "stream_id": 3 } }),
+        ),
+    ]);
+    // Stream 2 ended with nothing left; the others have 100 each, and a
+    // batch costs 600 stroops a stream
+    let chain = FakeChain::new(|function, args| {
+        assert_eq!(function, "withdraw_many");
+        let ids = ids(args);
+        let results: Vec<_> = ids
+            .iter()
+            .map(|&stream_id| WithdrawResult {
+                stream_id,
+                amount: if stream_id == 2 { 0 } else { 100 },
+                status: if stream_id == 2 {
+                    WithdrawStatus::NothingToWithdraw
+                } else {
+                    WithdrawStatus::Withdrawn
+                },
+            })
+            .collect();
+        returns(results, 600 * ids.len() as u32)
+    });
+    let config = config(&[("KEEPER_STREAMING", STREAMING), ("KEEPER_MAX_FEE", "1000")]);
+    let mut keeper = Keeper::new(&chain, index, &config);
+
+    let cycle = keeper.cycle(800);
+    assert_eq!(
+        cycle,
+        Cycle {
+            sent: 2,
+            fees: 1_200,
+            failed: 0,
+            over_budget: false,
+        }
+    );
+    let sent: Vec<_> = chain
+        .sent
+        .borrow()
+        .iter()
+        .map(|(_, args)| ids(args))
+        .collect();
+    assert_eq!(sent, vec![vec![0], vec![1]]);
+
+    // The ended stream is not asked about again
+    chain.simulated.borrow_mut().clear();
+    keeper.cycle(900);
+    assert_eq!(ids(&chain.simulated.borrow()[0].1), vec![0, 1]);
+}
+
+#[test]
+fn processes_pages_with_work_within_the_budget() {
+    let chain = FakeChain::new(|function, args| match function {
+        "subscription_count" => returns(5u64, 100),
+        "process_due" => {
+            let charged = match args[0] {
+                ScVal::U64(4) => 0,
+                _ => 1,
+            };
+            returns(
+                ProcessReport {
+                    charged,
+                    failed: 0,
+                    lapsed: 0,
+                },
+                400,
+            )
+        }
+        _ => unreachable!(),
+    });
+    let config = config(&[
+        ("KEEPER_SUBSCRIPTIONS", SUBSCRIPTIONS),
+        ("KEEPER_BATCH_SIZE", "2"),
+        ("KEEPER_CYCLE_BUDGET", "500"),
+    ]);
+    let mut keeper = Keeper::new(&chain, index(&[]), &config);
+
+    // Subscriptions 0-1 and 2-3 have charges due, but only one fits
+    let cycle = keeper.cycle(0);
+    assert_eq!(cycle.sent, 1);
+    assert!(cycle.over_budget);
+    assert_eq!(
+        chain.sent.borrow()[0],
+        (
+            "process_due".to_string(),
+            vec![ScVal::U64(0), ScVal::U32(2)]
+        )
+    );
+}
+
+#[test]
+fn finalizes_timed_escrows_once_due() {
+    let address = ScAddress::Contract(ContractId(Hash([1; 32])));
+    let index = index(&[
+        (
+            ESCROW,
+            "escrow.escrow_created",
+            json!({ "escrow": { "kind": "escrow_created", "escrow_id": 0 } }),
+        ),
+        (
+            ESCROW,
+            "escrow.escrow_created",
+            json!({ "escrow": { "kind": "escrow_created", "escrow_id": 1 } }),
+        ),
+    ]);
+    let chain = FakeChain::new(move |function, args| match function {
+        "get_escrow" => {
+            let ScVal::U64(id) = args[0] else {
+                unreachable!()
+            };
+            returns(
+                EscrowData {
+                    id,
+                    depositor: address.clone(),
+                    payee: address.clone(),
+                    arbiter: address.clone(),
+                    token: address.clone(),
+                    amount: 1_000,
+                    released: 0,
+                    status: EscrowStatus::Active,
+                    release_after: (id == 0).then_some(2_000),
+                },
+                100,
+            )
+        }
+        "finalize" => returns((), 300),
+        _ => unreachable!(),
+    });
+    let config = config(&[("KEEPER_ESCROW", ESCROW)]);
+    let mut keeper = Keeper::new(&chain, index, &config);
+
+    assert_eq!(keeper.cycle(1_999).sent, 0);
+    assert_eq!(keeper.cycle(2_000).sent, 1);
+    assert_eq!(
+        chain.sent.borrow()[0],
+        ("finalize".to_string(), vec![ScVal::U64(0)])
+    );
+    // Each escrow was read once; the untimed one is never finalized
+    let reads = chain
+        .simulated
+        .borrow()
+        .iter()
+        .filter(|(function, _)| function == "get_escrow")
+        .count();
+    assert_eq!(reads, 2);
+}
+
+#[test]
+fn failing_jobs_back_off() {
+    let chain = FakeChain::new(|_, _| {
+        Err(lumenpulse_tx::Error::Rpc(lumenpulse_rpc::Error::Transport(
+            "connection refused".to_string(),
+        )))
+    });
+    let config = config(&[
+        ("KEEPER_SUBSCRIPTIONS", SUBSCRIPTIONS),
+        ("KEEPER_INTERVAL_SECONDS", "10"),
+    ]);
+    let mut keeper = Keeper::new(&chain, index(&[]), &config);
+
+    assert_eq!(keeper.cycle(0).failed, 1);
+    // The second failure waits twice as long as the first
+    assert_eq!(keeper.cycle(9).failed, 0);
+    assert_eq!(keeper.cycle(10).failed, 1);
+    assert_eq!(keeper.cycle(29).failed, 0);
+    assert_eq!(keeper.cycle(30).failed, 1);
+    assert_eq!(chain.simulated.borrow().len(), 3);
+
+    let second = Duration::from_secs(1);
+    assert_eq!(backoff(second, 60 * second, 1), second);
+    assert_eq!(backoff(second, 60 * second, 3), 4 * second);
+    assert_eq!(backoff(second, 60 * second, 40), 60 * second);
+}
+
+#[test]
+fn contracts_default_to_the_lockfile() {
+    let mut lockfile = Lockfile::default();
+    let futurenet = lockfile
+        .networks
+        .entry("futurenet".to_string())
+        .or_default();
+    for (name, id) in [("streaming", STREAMING), ("escrow", ESCROW)] {
+        futurenet.contracts.insert(
+            name.to_string(),
+            Deployment {
+                id: id.to_string(),
+                wasm_hash: "ab".repeat(32),
+                salt: format!("{name}-1"),
+                initialized: true,
+            },
+        );
+    }
+    let path = std::env::temp_dir().join("lumenpulse-keeper-test.lock.json");
+    let path = path.to_str().unwrap();
+    lockfile.write(path).unwrap();
+
+    let config = config(&[
+        ("KEEPER_NETWORK", "futurenet"),
+        ("KEEPER_LOCKFILE", path),
+        ("KEEPER_ESCROW", SUBSCRIPTIONS),
+    ]);
+    assert_eq!(config.streaming.as_deref(), Some(STREAMING));
+    assert_eq!(config.subscriptions, None);
+    // A variable still wins over the lockfile
+    assert_eq!(config.escrow.as_deref(), Some(SUBSCRIPTIONS));
+
+    // Testnet has none of them
+    let err = Config::from_vars(|name| (name == "KEEPER_LOCKFILE").then(|| path.to_string()));
+    assert!(matches!(err, Err(ConfigError::Missing(_))));
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,134 @@
+//! Allocations read from CSV or JSON.
+//!
+//! CSV has an `account,amount` header and one allocation per line. JSON
+//! is an array of `{"account": "G...", "amount": "1000"}`, with the
+//! amount as a string or a number. Each allocation's claim index is its
+//! position in the file.
+
+use crate::Error;
+use serde::Deserialize;
+use std::{collections::BTreeSet, path::Path};
+use stellar_xdr::curr::ScAddress;
+
+/// What one account may claim
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Allocation {
+    pub account: ScAddress,
+    pub amount: i128,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Raw {
+    account: String,
+    amount: Amount,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Amount {
+    Number(u64),
+    Text(String),
+}
+
+/// The allocations in the file at `path`, read as CSV or JSON by its
+/// extension
+pub fn from_file(path: impl AsRef<Path>) -> Result<Vec<Allocation>, Error> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(|err| Error::Read {
+        path: path.display().to_string(),
+        reason: err.to_string(),
+    })?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("csv") => from_csv(&text),
+        Some("json") => from_json(&text),
+        _ => Err(Error::Read {
+            path: path.display().to_string(),
+            reason: "expected a .csv or .json file".to_string(),
+        }),
+    }
+}
+
+pub fn from_csv(text: &str) -> Result<Vec<Allocation>, Error> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+    match lines.next() {
+        Some((_, "account,amount")) => {}
+        Some((line, _)) => {
+            return Err(Error::Parse {
+                line,
+                reason: "expected an `account,amount` header".to_string(),
+            })
+        }
+        None => return Err(Error::Empty),
+    }
+    let raw = lines
+        .map(
+            |(line, text)| match text.split(',').collect::<Vec<_>>()[..] {
+                [account, amount] => Ok((
+                    line,
+                    Raw {
+                        account: account.trim().to_string(),
+                        amount: Amount::Text(amount.trim().to_string()),
+                    },
+                )),
+                _ => Err(Error::Parse {
+                    line,
+                    reason: "expected an account and an amount".to_string(),
+                }),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+    check(raw)
+}
+
+pub fn from_json(text: &str) -> Result<Vec<Allocation>, Error> {
+    let raw: Vec<Raw> = serde_json::from_str(text).map_err(|err| Error::Parse {
+        line: err.line(),
+        reason: err.to_string(),
+    })?;
+    check(raw.into_iter().map(|raw| (0, raw)).collect())
+}
+
+/// Allocations from `raw`, each with the line it was read from if known
+fn check(raw: Vec<(usize, Raw)>) -> Result<Vec<Allocation>, Error> {
+    if raw.is_empty() {
+        return Err(Error::Empty);
+    }
+    let mut seen = BTreeSet::new();
+    let mut allocations = Vec::with_capacity(raw.len());
+    for (line, Raw { account, amount }) in raw {
+        let invalid = |reason: &str| {
+            let reason = match line {
+                0 => reason.to_string(),
+                line => format!("{reason} (line {line})"),
+            };
+            Error::Invalid {
+                account: account.clone(),
+                reason,
+            }
+        };
+        let address: ScAddress = account
+            .parse()
+            .map_err(|_| invalid("not an account or contract address"))?;
+        let amount = match amount {
+            Amount::Number(amount) => i128::from(amount),
+            Amount::Text(amount) => amount.parse().map_err(|_| invalid("invalid amount"))?,
+        };
+        // The contract refuses to pay out nothing
+        if amount <= 0 {
+            return Err(invalid("amount must be positive"));
+        }
+        if !seen.insert(account.clone()) {
+            return Err(invalid("listed twice"));
+        }
+        allocations.push(Allocation {
+            account: address,
+            amount,
+        });
+    }
+    Ok(allocations)
+}
@@ -0,0 +1,218 @@
+//! Merkle trees of allocations for the merkle distributor contract.
+//!
+//! The tree is built the way the contract verifies it
+//! (`apps/onchain/contracts/merkle-distributor/src/merkle.rs`): each leaf
+//! is the SHA-256 of a `Claim`'s XDR encoding, and each parent the SHA-256
+//! of its two children in ascending byte order, so proofs need no
+//! left/right flags. A node left without a sibling at the end of a level
+//! moves up as it is. The distribution lists the root to create the round
+//! with, and each claim with the proof to pass to `claim`.
+
+pub mod allocations;
+
+pub use allocations::Allocation;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{
+    Int128Parts, Limits, ScAddress, ScMap, ScMapEntry, ScSymbol, ScVal, WriteXdr,
+};
+
+#[cfg(test)]
+mod test;
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Error {
+    #[error("cannot read {path}: {reason}")]
+    Read { path: String, reason: String },
+    #[error("line {line}: {reason}")]
+    Parse { line: usize, reason: String },
+    #[error("{account}: {reason}")]
+    Invalid { account: String, reason: String },
+    #[error("no allocations")]
+    Empty,
+}
+
+/// One leaf of the tree, as the contract's `Claim`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub index: u32,
+    pub account: ScAddress,
+    pub amount: i128,
+}
+
+impl Claim {
+    /// The claim as the contract type encodes it: a map of its fields,
+    /// sorted by name
+    pub fn to_sc_val(&self) -> ScVal {
+        let field = |name: &str, val| ScMapEntry {
+            key: ScVal::Symbol(ScSymbol(name.try_into().expect("a valid symbol"))),
+            val,
+        };
+        let amount = ScVal::I128(Int128Parts {
+            hi: (self.amount >> 64) as i64,
+            lo: self.amount as u64,
+        });
+        let fields = vec![
+            field("account", ScVal::Address(self.account.clone())),
+            field("amount", amount),
+            field("index", ScVal::U32(self.index)),
+        ];
+        ScVal::Map(Some(ScMap(fields.try_into().expect("three fields fit"))))
+    }
+}
+
+/// Leaf hash of `claim`
+pub fn leaf(claim: &Claim) -> [u8; 32] {
+    let xdr = claim
+        .to_sc_val()
+        .to_xdr(Limits::none())
+        .expect("a claim encodes");
+    Sha256::digest(xdr).into()
+}
+
+/// Parent of two nodes
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha256::new();
+    hasher.update(first);
+    hasher.update(second);
+    hasher.finalize().into()
+}
+
+/// Whether `proof` leads from `leaf` up to `root`
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let node = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+    node == *root
+}
+
+pub struct Tree {
+    claims: Vec<Claim>,
+    total: i128,
+    /// Leaves first, the root last
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl Tree {
+    /// The tree of `allocations`, each claimable at its position
+    pub fn new(allocations: &[Allocation]) -> Result<Self, Error> {
+        if allocations.is_empty() {
+            return Err(Error::Empty);
+        }
+        if u32::try_from(allocations.len() - 1).is_err() {
+            return Err(Error::Invalid {
+                account: String::new(),
+                reason: "more allocations than claim indexes".to_string(),
+            });
+        }
+        let claims: Vec<Claim> = allocations
+            .iter()
+            .enumerate()
+            .map(|(index, allocation)| Claim {
+                index: index as u32,
+                account: allocation.account.clone(),
+                amount: allocation.amount,
+            })
+            .collect();
+        let total = claims
+            .iter()
+            .try_fold(0i128, |total, claim| total.checked_add(claim.amount))
+            .ok_or_else(|| Error::Invalid {
+                account: String::new(),
+                reason: "total overflows an i128".to_string(),
+            })?;
+        let mut levels = vec![claims.iter().map(leaf).collect::<Vec<_>>()];
+        while levels.last().expect("one level at least").len() > 1 {
+            let level = levels.last().expect("one level at least");
+            let parents = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(parents);
+        }
+        Ok(Tree {
+            claims,
+            total,
+            levels,
+        })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().expect("one level at least")[0]
+    }
+
+    pub fn claims(&self) -> &[Claim] {
+        &self.claims
+    }
+
+    /// What the claims add up to
+    pub fn total(&self) -> i128 {
+        self.total
+    }
+
+    /// Sibling hashes from the leaf of claim `index` up to the root
+    pub fn proof(&self, index: u32) -> Option<Vec<[u8; 32]>> {
+        let mut position = usize::try_from(index).ok()?;
+        if position >= self.claims.len() {
+            return None;
+        }
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                proof.push(*sibling);
+            }
+            position /= 2;
+        }
+        Some(proof)
+    }
+
+    pub fn distribution(&self) -> Distribution {
+        let claims = self
+            .claims
+            .iter()
+            .map(|claim| ClaimProof {
+                index: claim.index,
+                account: claim.account.to_string(),
+                amount: claim.amount.to_string(),
+                proof: self
+                    .proof(claim.index)
+                    .expect("every claim has a proof")
+                    .iter()
+                    .map(hex::encode)
+                    .collect(),
+            })
+            .collect();
+        Distribution {
+            root: hex::encode(self.root()),
+            total: self.total.to_string(),
+            claims,
+        }
+    }
+}
+
+/// A tree's root and what each claimant passes to `claim`
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Distribution {
+    /// Hex `merkle_root` for the round's config
+    pub root: String,
+    /// What to fund the round with; amounts are decimal strings, as they
+    /// may exceed what JSON numbers hold
+    pub total: String,
+    pub claims: Vec<ClaimProof>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ClaimProof {
+    pub index: u32,
+    /// Account or contract address that claims
+    pub account: String,
+    pub amount: String,
+    /// Hex sibling hashes, leaf first
+    pub proof: Vec<String>,
+}
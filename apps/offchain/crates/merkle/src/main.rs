@@ -0,0 +1,43 @@
+use clap::Parser;
+use lumenpulse_merkle::{allocations, Tree};
+use std::{path::PathBuf, process::ExitCode};
+
+/// Build the Merkle tree of a distribution round's allocations, and write
+/// its root and every claim's proof as JSON
+#[derive(Debug, Parser)]
+#[command(name = "lumenpulse-merkle")]
+struct Cli {
+    /// Allocations as `.csv` (`account,amount`) or `.json`
+    allocations: PathBuf,
+    /// File to write the distribution to, instead of stdout
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("lumenpulse-merkle: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let allocations = allocations::from_file(&cli.allocations)?;
+    let tree = Tree::new(&allocations)?;
+    let json = serde_json::to_string_pretty(&tree.distribution())? + "\n";
+    match &cli.out {
+        Some(path) => std::fs::write(path, json)
+            .map_err(|err| format!("cannot write {}: {err}", path.display()))?,
+        None => print!("{json}"),
+    }
+    eprintln!(
+        "{} claims totalling {}, root {}",
+        tree.claims().len(),
+        tree.total(),
+        hex::encode(tree.root())
+    );
+    Ok(())
+}
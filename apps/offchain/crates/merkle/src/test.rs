@@ -0,0 +1,154 @@
+use crate::{allocations, hash_pair, leaf, verify, Allocation, Claim, Distribution, Error, Tree};
+use std::path::Path;
+
+/// Five claimants, one of them a contract and one allocated more than a
+/// `u64` holds. The contract's tests claim every allocation in the
+/// distribution they make.
+const ALLOCATIONS: &str = "account,amount
+GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF,100
+GAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQDZ7H,250
+GABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEJXA,18446744073709551616
+CAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC526,1
+CABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAFNSZ,7000
+";
+
+fn tree() -> Tree {
+    Tree::new(&allocations::from_csv(ALLOCATIONS).unwrap()).unwrap()
+}
+
+#[test]
+fn distribution_matches_the_golden_the_contract_claims_from() {
+    let distribution = tree().distribution();
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("goldens/distribution.json");
+    let actual = serde_json::to_string_pretty(&distribution).unwrap() + "\n";
+    if std::env::var_os("UPDATE_GOLDENS").is_some() {
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap();
+    assert!(
+        actual == expected,
+        "{} no longer matches; if the change is intended, run with \
+         UPDATE_GOLDENS=1 and commit the new file\n--- expected\n{expected}--- actual\n{actual}",
+        path.display()
+    );
+    let read: Distribution = serde_json::from_str(&expected).unwrap();
+    assert_eq!(read, distribution);
+    assert_eq!(distribution.total, "18446744073709558967");
+}
+
+#[test]
+fn every_proof_leads_to_the_root() {
+    let tree = tree();
+    let root = tree.root();
+    for claim in tree.claims() {
+        let proof = tree.proof(claim.index).unwrap();
+        assert!(verify(&proof, &root, leaf(claim)));
+
+        // Another amount, account or index is another leaf
+        let mut other = claim.clone();
+        other.amount += 1;
+        assert!(!verify(&proof, &root, leaf(&other)));
+        let mut other = claim.clone();
+        other.index ^= 1;
+        assert!(!verify(&proof, &root, leaf(&other)));
+        let mut other = claim.clone();
+        other.account = tree.claims()[(claim.index as usize + 1) % 5]
+            .account
+            .clone();
+        assert!(!verify(&proof, &root, leaf(&other)));
+    }
+
+    // The fifth leaf has no sibling until the top, so it takes the first
+    // four's parent alone
+    let leaves: Vec<[u8; 32]> = tree.claims().iter().map(leaf).collect();
+    let first_four = hash_pair(
+        &hash_pair(&leaves[0], &leaves[1]),
+        &hash_pair(&leaves[2], &leaves[3]),
+    );
+    assert_eq!(tree.proof(4), Some(vec![first_four]));
+    assert_eq!(root, hash_pair(&first_four, &leaves[4]));
+    assert_eq!(tree.proof(1).unwrap().len(), 3);
+    assert_eq!(tree.proof(5), None);
+    assert_eq!(
+        hash_pair(&leaves[0], &leaves[1]),
+        hash_pair(&leaves[1], &leaves[0])
+    );
+}
+
+#[test]
+fn a_single_claim_is_its_own_root() {
+    let claim = Claim {
+        index: 0,
+        account: "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF"
+            .parse()
+            .unwrap(),
+        amount: -1,
+    };
+    let tree = Tree::new(&[Allocation {
+        account: claim.account.clone(),
+        amount: claim.amount,
+    }])
+    .unwrap();
+    assert_eq!(tree.root(), leaf(&claim));
+    assert_eq!(tree.proof(0), Some(Vec::new()));
+    assert!(verify(&[], &tree.root(), leaf(&claim)));
+    assert!(matches!(Tree::new(&[]), Err(Error::Empty)));
+}
+
+#[test]
+fn allocations_are_read_from_csv_and_json_and_checked() {
+    let json = r#"[
+        {"account": "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF", "amount": 100},
+        {"account": "GAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQDZ7H", "amount": "250"}
+    ]"#;
+    let csv = ALLOCATIONS.lines().take(3).collect::<Vec<_>>().join("\n");
+    assert_eq!(
+        allocations::from_json(json).unwrap(),
+        allocations::from_csv(&csv).unwrap()
+    );
+
+    let invalid = |csv: &str| allocations::from_csv(csv).unwrap_err();
+    assert_eq!(invalid(""), Error::Empty);
+    assert_eq!(invalid("account,amount\n"), Error::Empty);
+    assert!(matches!(
+        invalid("address,amount\n"),
+        Error::Parse { line: 1, .. }
+    ));
+    assert!(matches!(
+        invalid("account,amount\n\nGAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF\n"),
+        Error::Parse { line: 3, .. }
+    ));
+    let reason = |csv: &str| match invalid(csv) {
+        Error::Invalid { reason, .. } => reason,
+        err => panic!("{err}"),
+    };
+    assert_eq!(
+        reason("account,amount\nG123,5"),
+        "not an account or contract address (line 2)"
+    );
+    assert_eq!(
+        reason("account,amount\nGAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF,0"),
+        "amount must be positive (line 2)"
+    );
+    assert_eq!(
+        reason("account,amount\nGAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF,1.5"),
+        "invalid amount (line 2)"
+    );
+    assert_eq!(
+        reason(
+            "account,amount
+GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF,1
+GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF,2"
+        ),
+        "listed twice (line 3)"
+    );
+    assert!(matches!(
+        allocations::from_json(r#"[{"account": "G", "amount": 1, "index": 0}]"#),
+        Err(Error::Parse { .. })
+    ));
+    assert!(matches!(
+        allocations::from_file("allocations.txt"),
+        Err(Error::Read { .. })
+    ));
+}
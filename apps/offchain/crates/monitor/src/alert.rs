@@ -0,0 +1,155 @@
+//! What the monitor reports, and where it reports it.
+//!
+//! Each event is one JSON object, printed as a line on stdout and posted
+//! to the webhook when there is one:
+//!
+//! ```json
+//! {"state":"firing","network":"testnet","contract":"streaming","contract_id":"CA...","condition":"paused"}
+//! ```
+
+use serde::Serialize;
+use std::time::Duration;
+
+/// A condition that holds on a network, or on one contract there
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Alert {
+    pub network: String,
+    /// The contract's name in the config, unless the alert is about the
+    /// network
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_id: Option<String>,
+    #[serde(flatten)]
+    pub condition: Condition,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "condition", rename_all = "snake_case")]
+pub enum Condition {
+    /// The RPC did not answer, or is not keeping up with the network
+    RpcUnhealthy {
+        reason: String,
+    },
+    Paused,
+    /// `entry` lives to `live_until_ledger`, too soon after
+    /// `latest_ledger`
+    TtlExpiring {
+        entry: Entry,
+        live_until_ledger: u32,
+        latest_ledger: u32,
+    },
+    /// `entry` is not on the ledger, so the contract cannot be called
+    EntryMissing {
+        entry: Entry,
+    },
+    AdminChanged {
+        expected: String,
+        actual: String,
+    },
+    /// A view or entry the checks read could not be read
+    CheckFailed {
+        check: Check,
+        reason: String,
+    },
+}
+
+/// A ledger entry every contract needs
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Entry {
+    Instance,
+    Code,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Check {
+    Paused,
+    Admin,
+    Ttl,
+}
+
+impl Check {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Check::Paused => "paused",
+            Check::Admin => "admin",
+            Check::Ttl => "ttl",
+        }
+    }
+}
+
+impl Entry {
+    fn name(self) -> &'static str {
+        match self {
+            Entry::Instance => "instance",
+            Entry::Code => "code",
+        }
+    }
+}
+
+impl Alert {
+    /// Identifies the alert from one poll to the next, whatever ledger
+    /// it was seen at. Alerts from a check start with the check's name
+    /// after the contract, so a failed check can keep them.
+    pub(crate) fn key(&self) -> String {
+        let condition = match &self.condition {
+            Condition::RpcUnhealthy { .. } => "rpc".to_string(),
+            Condition::Paused => "paused".to_string(),
+            Condition::TtlExpiring { entry, .. } => format!("ttl/{}/expiring", entry.name()),
+            Condition::EntryMissing { entry } => format!("ttl/{}/missing", entry.name()),
+            Condition::AdminChanged { actual, .. } => format!("admin/{actual}"),
+            Condition::CheckFailed { check, .. } => format!("failed/{}", check.name()),
+        };
+        format!(
+            "{}/{}/{condition}",
+            self.network,
+            self.contract_id.as_deref().unwrap_or_default()
+        )
+    }
+}
+
+/// An alert starting or stopping
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Event {
+    pub state: State,
+    #[serde(flatten)]
+    pub alert: Alert,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum State {
+    Firing,
+    Resolved,
+}
+
+/// Prints events and posts them to a webhook
+pub struct Notifier {
+    webhook: Option<(String, ureq::Agent)>,
+}
+
+impl Notifier {
+    pub fn new(webhook_url: Option<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .build();
+        Notifier {
+            webhook: webhook_url.map(|url| (url, agent)),
+        }
+    }
+
+    /// Print `event` as a JSON line, then post it to the webhook
+    pub fn notify(&self, event: &Event) -> Result<(), String> {
+        let json = serde_json::to_value(event).expect("events serialize");
+        println!("{json}");
+        if let Some((url, agent)) = &self.webhook {
+            agent
+                .post(url)
+                .send_json(json)
+                .map_err(|err| format!("webhook failed: {err}"))?;
+        }
+        Ok(())
+    }
+}
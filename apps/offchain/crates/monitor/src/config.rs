@@ -0,0 +1,139 @@
+//! Monitor settings, read from a JSON file.
+//!
+//! One monitor watches every network the contracts are deployed on, so
+//! the contracts are listed per network in a file rather than in
+//! variables. A network named after a preset in `lumenpulse_tx::network`
+//! may leave out its RPC.
+
+use lumenpulse_tx::network;
+use serde::Deserialize;
+
+pub const DEFAULT_CONFIG: &str = "lumenpulse-monitor.json";
+
+/// A week of ledgers at five seconds each
+pub const DEFAULT_TTL_WARNING_LEDGERS: u32 = 120_960;
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ConfigError {
+    #[error("cannot read {path}: {reason}")]
+    Read { path: String, reason: String },
+    #[error("invalid config: {0}")]
+    Parse(String),
+    #[error("{name} is invalid: {reason}")]
+    Invalid { name: String, reason: String },
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub networks: Vec<Network>,
+    /// Alert when an instance or its code has fewer ledgers than this left
+    /// to live
+    #[serde(default = "default_ttl_warning_ledgers")]
+    pub ttl_warning_ledgers: u32,
+    /// How long to wait between polls
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: u64,
+    /// Where to post alerts as JSON, besides stdout
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Network {
+    pub name: String,
+    /// The preset's when left out
+    #[serde(default)]
+    pub rpc_url: String,
+    pub contracts: Vec<Contract>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Contract {
+    /// What alerts call the contract, such as `streaming`
+    pub name: String,
+    /// Contract address (`C...`)
+    pub id: String,
+    /// Whether the contract has the `paused` view
+    #[serde(default = "yes")]
+    pub pausable: bool,
+    /// The admin the contract should have. Its admin is only checked when
+    /// set.
+    #[serde(default)]
+    pub admin: Option<String>,
+    /// View that returns the admin
+    #[serde(default = "default_admin_view")]
+    pub admin_view: String,
+}
+
+impl Config {
+    pub fn from_file(path: &str) -> Result<Self, ConfigError> {
+        let json = std::fs::read_to_string(path).map_err(|err| ConfigError::Read {
+            path: path.to_string(),
+            reason: err.to_string(),
+        })?;
+        Self::from_json(&json)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        let mut config: Config =
+            serde_json::from_str(json).map_err(|err| ConfigError::Parse(err.to_string()))?;
+        if config.networks.is_empty() {
+            return Err(ConfigError::Parse("no networks".to_string()));
+        }
+        for network in &mut config.networks {
+            if network.rpc_url.is_empty() {
+                network.rpc_url = network::preset(&network.name)
+                    .and_then(|preset| preset.rpc_url)
+                    .ok_or_else(|| ConfigError::Invalid {
+                        name: network.name.clone(),
+                        reason: "needs an rpc_url".to_string(),
+                    })?
+                    .to_string();
+            }
+            for contract in &network.contracts {
+                let name = format!("{}.{}", network.name, contract.name);
+                if lumenpulse_tx::contract_address(&contract.id).is_none() {
+                    return Err(ConfigError::Invalid {
+                        name,
+                        reason: format!("`{}` is not a contract address", contract.id),
+                    });
+                }
+                if let Some(admin) = &contract.admin {
+                    if !is_address(admin) {
+                        return Err(ConfigError::Invalid {
+                            name,
+                            reason: format!("`{admin}` is not an account or contract address"),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn is_address(strkey: &str) -> bool {
+    matches!(
+        stellar_strkey::Strkey::from_string(strkey),
+        Ok(stellar_strkey::Strkey::PublicKeyEd25519(_) | stellar_strkey::Strkey::Contract(_))
+    )
+}
+
+fn default_ttl_warning_ledgers() -> u32 {
+    DEFAULT_TTL_WARNING_LEDGERS
+}
+
+fn default_interval_seconds() -> u64 {
+    300
+}
+
+fn default_admin_view() -> String {
+    "get_admin".to_string()
+}
+
+fn yes() -> bool {
+    true
+}
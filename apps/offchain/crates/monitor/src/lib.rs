@@ -0,0 +1,298 @@
+//! Monitor for the deployed Lumenpulse contracts.
+//!
+//! The contracts have no single status view, so each poll reads what they
+//! share: `paused` from `lumenpulse-pausable` and the admin view, both by
+//! simulation, and the TTLs of each contract's instance and code from
+//! `getLedgerEntries`. An alert fires when its condition first holds and
+//! resolves when it stops, so a paused contract is reported once rather
+//! than on every poll. While a network's RPC or one of a contract's
+//! checks fails, the alerts it raised keep firing rather than resolve
+//! unseen.
+
+pub mod alert;
+pub mod config;
+
+pub use alert::{Alert, Check, Condition, Entry, Event, Notifier, State};
+pub use config::Config;
+
+use config::{Contract, Network};
+use lumenpulse_rpc::Client;
+use std::{collections::BTreeMap, thread, time::Duration};
+use stellar_xdr::curr::{
+    ContractDataDurability, ContractExecutable, LedgerEntryData, LedgerKey, LedgerKeyContractCode,
+    LedgerKeyContractData, ScAddress, ScVal,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(#[from] lumenpulse_rpc::Error),
+    #[error(transparent)]
+    Tx(#[from] lumenpulse_tx::Error),
+    #[error("the RPC reports itself {0}")]
+    Unhealthy(String),
+    #[error("`{view}` returned {value}")]
+    Unexpected { view: String, value: String },
+}
+
+/// What the monitor reads from one network, a `Client` in production
+pub trait Probe {
+    /// Latest ledger the RPC has, failing unless it is healthy
+    fn latest_ledger(&self) -> Result<u32, Error>;
+    /// What `function` on `contract` returns when called with no
+    /// arguments
+    fn view(&self, contract: &ScAddress, function: &str) -> Result<Option<ScVal>, Error>;
+    /// Last ledger each entry `contract` needs lives to, `None` for
+    /// entries that are not on the ledger
+    fn ttls(&self, contract: &ScAddress) -> Result<Vec<(Entry, Option<u32>)>, Error>;
+}
+
+impl Probe for Client {
+    fn latest_ledger(&self) -> Result<u32, Error> {
+        let health = self.get_health()?;
+        if health.status != "healthy" {
+            return Err(Error::Unhealthy(health.status));
+        }
+        Ok(health.latest_ledger)
+    }
+
+    fn view(&self, contract: &ScAddress, function: &str) -> Result<Option<ScVal>, Error> {
+        let host_function = lumenpulse_tx::invoke_contract(contract, function, Vec::new())?;
+        Ok(lumenpulse_tx::view(self, host_function)?)
+    }
+
+    fn ttls(&self, contract: &ScAddress) -> Result<Vec<(Entry, Option<u32>)>, Error> {
+        let key = LedgerKey::ContractData(LedgerKeyContractData {
+            contract: contract.clone(),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+        });
+        let Some(instance) = self.get_ledger_entries(&[key])?.pop() else {
+            return Ok(vec![(Entry::Instance, None)]);
+        };
+        // Contract data and code always carry a TTL
+        let mut ttls = vec![(Entry::Instance, instance.live_until_ledger)];
+        if let LedgerEntryData::ContractData(data) = &instance.data {
+            if let ScVal::ContractInstance(instance) = &data.val {
+                // Built-in contracts such as Stellar assets have no code
+                if let ContractExecutable::Wasm(hash) = &instance.executable {
+                    let key = LedgerKey::ContractCode(LedgerKeyContractCode { hash: hash.clone() });
+                    let code = self.get_ledger_entries(&[key])?.pop();
+                    ttls.push((Entry::Code, code.and_then(|code| code.live_until_ledger)));
+                }
+            }
+        }
+        Ok(ttls)
+    }
+}
+
+pub struct Monitor<P> {
+    networks: Vec<(Network, P)>,
+    ttl_warning_ledgers: u32,
+    interval: Duration,
+    /// Alerts that have fired and not resolved, by key
+    firing: BTreeMap<String, Alert>,
+}
+
+/// What one poll of a network found
+struct Findings {
+    alerts: Vec<Alert>,
+    /// Key prefixes of alerts that could not be checked, and so stand
+    unchecked: Vec<String>,
+}
+
+impl<P: Probe> Monitor<P> {
+    /// A monitor reading each network in `config` through the probe
+    /// `connect` gives for it
+    pub fn new(config: &Config, connect: impl Fn(&Network) -> P) -> Self {
+        Monitor {
+            networks: config
+                .networks
+                .iter()
+                .map(|network| (network.clone(), connect(network)))
+                .collect(),
+            ttl_warning_ledgers: config.ttl_warning_ledgers,
+            interval: Duration::from_secs(config.interval_seconds),
+            firing: BTreeMap::new(),
+        }
+    }
+
+    /// Alerts currently firing
+    pub fn firing(&self) -> impl Iterator<Item = &Alert> {
+        self.firing.values()
+    }
+
+    /// Check every network and return the alerts that fired or resolved
+    /// since the last poll
+    pub fn poll(&mut self) -> Vec<Event> {
+        let mut findings = Findings {
+            alerts: Vec::new(),
+            unchecked: Vec::new(),
+        };
+        for (network, probe) in &self.networks {
+            self.check_network(network, probe, &mut findings);
+        }
+
+        let mut firing: BTreeMap<_, _> = findings
+            .alerts
+            .into_iter()
+            .map(|alert| (alert.key(), alert))
+            .collect();
+        let mut events = Vec::new();
+        for (key, alert) in &firing {
+            if !self.firing.contains_key(key) {
+                events.push(Event {
+                    state: State::Firing,
+                    alert: alert.clone(),
+                });
+            }
+        }
+        for (key, alert) in std::mem::take(&mut self.firing) {
+            if firing.contains_key(&key) {
+                continue;
+            }
+            if findings
+                .unchecked
+                .iter()
+                .any(|prefix| key.starts_with(prefix))
+            {
+                firing.insert(key, alert);
+            } else {
+                events.push(Event {
+                    state: State::Resolved,
+                    alert,
+                });
+            }
+        }
+        self.firing = firing;
+        events
+    }
+
+    /// Poll every interval, handing each event to `notifier`
+    pub fn run(&mut self, notifier: &Notifier) {
+        loop {
+            for event in self.poll() {
+                if let Err(err) = notifier.notify(&event) {
+                    eprintln!("{err}");
+                }
+            }
+            thread::sleep(self.interval);
+        }
+    }
+
+    fn check_network(&self, network: &Network, probe: &P, findings: &mut Findings) {
+        let latest_ledger = match probe.latest_ledger() {
+            Ok(latest_ledger) => latest_ledger,
+            Err(err) => {
+                findings.alerts.push(Alert {
+                    network: network.name.clone(),
+                    contract: None,
+                    contract_id: None,
+                    condition: Condition::RpcUnhealthy {
+                        reason: err.to_string(),
+                    },
+                });
+                findings.unchecked.push(format!("{}/", network.name));
+                return;
+            }
+        };
+        for contract in &network.contracts {
+            let address =
+                lumenpulse_tx::contract_address(&contract.id).expect("config checks addresses");
+            let alert = |condition| Alert {
+                network: network.name.clone(),
+                contract: Some(contract.name.clone()),
+                contract_id: Some(contract.id.clone()),
+                condition,
+            };
+            let mut checked = |check: Check, result: Result<Vec<Condition>, Error>| match result {
+                Ok(conditions) => findings.alerts.extend(conditions.into_iter().map(alert)),
+                Err(err) => {
+                    findings.alerts.push(alert(Condition::CheckFailed {
+                        check,
+                        reason: err.to_string(),
+                    }));
+                    findings.unchecked.push(format!(
+                        "{}/{}/{}",
+                        network.name,
+                        contract.id,
+                        check.name()
+                    ));
+                }
+            };
+            if contract.pausable {
+                checked(Check::Paused, paused(probe, &address));
+            }
+            if let Some(expected) = &contract.admin {
+                checked(Check::Admin, admin(probe, &address, contract, expected));
+            }
+            checked(
+                Check::Ttl,
+                ttls(probe, &address, latest_ledger, self.ttl_warning_ledgers),
+            );
+        }
+    }
+}
+
+fn paused(probe: &impl Probe, address: &ScAddress) -> Result<Vec<Condition>, Error> {
+    match probe.view(address, "paused")? {
+        Some(ScVal::Bool(true)) => Ok(vec![Condition::Paused]),
+        Some(ScVal::Bool(false)) => Ok(Vec::new()),
+        other => Err(unexpected("paused", other)),
+    }
+}
+
+fn admin(
+    probe: &impl Probe,
+    address: &ScAddress,
+    contract: &Contract,
+    expected: &str,
+) -> Result<Vec<Condition>, Error> {
+    match probe.view(address, &contract.admin_view)? {
+        Some(ScVal::Address(admin)) if admin.to_string() == expected => Ok(Vec::new()),
+        Some(ScVal::Address(admin)) => Ok(vec![Condition::AdminChanged {
+            expected: expected.to_string(),
+            actual: admin.to_string(),
+        }]),
+        other => Err(unexpected(&contract.admin_view, other)),
+    }
+}
+
+fn ttls(
+    probe: &impl Probe,
+    address: &ScAddress,
+    latest_ledger: u32,
+    warning_ledgers: u32,
+) -> Result<Vec<Condition>, Error> {
+    Ok(probe
+        .ttls(address)?
+        .into_iter()
+        .filter_map(|(entry, live_until_ledger)| match live_until_ledger {
+            None => Some(Condition::EntryMissing { entry }),
+            Some(live_until_ledger)
+                if live_until_ledger < latest_ledger.saturating_add(warning_ledgers) =>
+            {
+                Some(Condition::TtlExpiring {
+                    entry,
+                    live_until_ledger,
+                    latest_ledger,
+                })
+            }
+            Some(_) => None,
+        })
+        .collect())
+}
+
+fn unexpected(view: &str, value: Option<ScVal>) -> Error {
+    let value = match value {
+        Some(value) => format!("{value:?}"),
+        None => "nothing".to_string(),
+    };
+    Error::Unexpected {
+        view: view.to_string(),
+        value,
+    }
+}
+
+#[cfg(test)]
+mod test;
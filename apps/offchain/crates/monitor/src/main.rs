@@ -0,0 +1,30 @@
+use lumenpulse_monitor::{config::DEFAULT_CONFIG, Config, Monitor, Notifier};
+use lumenpulse_rpc::Client;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("lumenpulse-monitor: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::var("MONITOR_CONFIG").unwrap_or_else(|_| DEFAULT_CONFIG.to_string());
+    let config = Config::from_file(&path)?;
+    let contracts: usize = config
+        .networks
+        .iter()
+        .map(|network| network.contracts.len())
+        .sum();
+    eprintln!(
+        "monitoring {contracts} contracts on {} networks",
+        config.networks.len()
+    );
+    let notifier = Notifier::new(config.webhook_url.clone());
+    Monitor::new(&config, |network| Client::new(&network.rpc_url)).run(&notifier);
+    Ok(())
+}
@@ -0,0 +1,326 @@
+use crate::{
+    config::ConfigError, Alert, Check, Condition, Config, Entry, Error, Event, Monitor, Probe,
+    State,
+};
+use serde_json::json;
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+use stellar_xdr::curr::{AccountId, PublicKey, ScAddress, ScVal, Uint256};
+
+const STREAMING: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+const ESCROW: &str = "CAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC526";
+
+fn account(byte: u8) -> ScAddress {
+    ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+        [byte; 32],
+    ))))
+}
+
+/// What a network looks like to the monitor, changed between polls
+#[derive(Default)]
+struct Ledger {
+    healthy: bool,
+    latest_ledger: u32,
+    /// Values of `(contract, view)`; a missing view fails
+    views: BTreeMap<(String, String), ScVal>,
+    ttls: BTreeMap<String, Vec<(Entry, Option<u32>)>>,
+}
+
+#[derive(Clone)]
+struct FakeProbe(Rc<RefCell<Ledger>>);
+
+impl FakeProbe {
+    fn set_view(&self, contract: &str, view: &str, value: ScVal) {
+        self.0
+            .borrow_mut()
+            .views
+            .insert((contract.to_string(), view.to_string()), value);
+    }
+}
+
+impl Probe for FakeProbe {
+    fn latest_ledger(&self) -> Result<u32, Error> {
+        let ledger = self.0.borrow();
+        if !ledger.healthy {
+            return Err(Error::Unhealthy("unhealthy".to_string()));
+        }
+        Ok(ledger.latest_ledger)
+    }
+
+    fn view(&self, contract: &ScAddress, function: &str) -> Result<Option<ScVal>, Error> {
+        self.0
+            .borrow()
+            .views
+            .get(&(contract.to_string(), function.to_string()))
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| {
+                Error::Tx(lumenpulse_tx::Error::Simulation(
+                    "HostError: Error(WasmVm, MissingValue)".to_string(),
+                ))
+            })
+    }
+
+    fn ttls(&self, contract: &ScAddress) -> Result<Vec<(Entry, Option<u32>)>, Error> {
+        Ok(self
+            .0
+            .borrow()
+            .ttls
+            .get(&contract.to_string())
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+fn config() -> Config {
+    Config::from_json(
+        &json!({
+            "networks": [{
+                "name": "testnet",
+                "contracts": [
+                    {
+                        "name": "streaming",
+                        "id": STREAMING,
+                        "admin": account(1).to_string(),
+                    },
+                    { "name": "escrow", "id": ESCROW, "pausable": false },
+                ],
+            }],
+            "ttl_warning_ledgers": 1_000,
+        })
+        .to_string(),
+    )
+    .unwrap()
+}
+
+/// A healthy network where nothing is wrong
+fn ledger() -> Ledger {
+    let mut ledger = Ledger {
+        healthy: true,
+        latest_ledger: 10_000,
+        ..Ledger::default()
+    };
+    for id in [STREAMING, ESCROW] {
+        ledger.ttls.insert(
+            id.to_string(),
+            vec![(Entry::Instance, Some(50_000)), (Entry::Code, Some(90_000))],
+        );
+    }
+    ledger.views.insert(
+        (STREAMING.to_string(), "paused".to_string()),
+        ScVal::Bool(false),
+    );
+    ledger.views.insert(
+        (STREAMING.to_string(), "get_admin".to_string()),
+        ScVal::Address(account(1)),
+    );
+    ledger
+}
+
+fn probe() -> FakeProbe {
+    FakeProbe(Rc::new(RefCell::new(ledger())))
+}
+
+fn conditions(events: &[Event]) -> Vec<(State, Condition)> {
+    events
+        .iter()
+        .map(|event| (event.state, event.alert.condition.clone()))
+        .collect()
+}
+
+#[test]
+fn alerts_fire_once_and_resolve() {
+    let probe = probe();
+    let mut monitor = Monitor::new(&config(), |_| probe.clone());
+    assert_eq!(monitor.poll(), vec![]);
+
+    probe.set_view(STREAMING, "paused", ScVal::Bool(true));
+    probe.set_view(STREAMING, "get_admin", ScVal::Address(account(2)));
+    let events = monitor.poll();
+    assert_eq!(
+        conditions(&events),
+        vec![
+            (
+                State::Firing,
+                Condition::AdminChanged {
+                    expected: account(1).to_string(),
+                    actual: account(2).to_string(),
+                }
+            ),
+            (State::Firing, Condition::Paused),
+        ]
+    );
+    assert_eq!(events[1].alert.contract.as_deref(), Some("streaming"));
+    // Still paused, so nothing new
+    assert_eq!(monitor.poll(), vec![]);
+
+    probe.set_view(STREAMING, "paused", ScVal::Bool(false));
+    assert_eq!(
+        conditions(&monitor.poll()),
+        vec![(State::Resolved, Condition::Paused)]
+    );
+    assert_eq!(monitor.firing().count(), 1);
+}
+
+#[test]
+fn ttls_alert_before_they_run_out() {
+    let probe = probe();
+    let mut monitor = Monitor::new(&config(), |_| probe.clone());
+    {
+        let mut ledger = probe.0.borrow_mut();
+        ledger.latest_ledger = 49_001;
+        ledger
+            .ttls
+            .insert(ESCROW.to_string(), vec![(Entry::Instance, None)]);
+    }
+    let events = monitor.poll();
+    assert_eq!(
+        conditions(&events),
+        vec![
+            (
+                State::Firing,
+                Condition::TtlExpiring {
+                    entry: Entry::Instance,
+                    live_until_ledger: 50_000,
+                    latest_ledger: 49_001,
+                }
+            ),
+            (
+                State::Firing,
+                Condition::EntryMissing {
+                    entry: Entry::Instance
+                }
+            ),
+        ]
+    );
+    assert_eq!(events[0].alert.contract_id.as_deref(), Some(STREAMING));
+
+    // A later ledger is the same alert; an extended TTL resolves it
+    probe.0.borrow_mut().latest_ledger = 49_500;
+    assert_eq!(monitor.poll(), vec![]);
+    probe
+        .0
+        .borrow_mut()
+        .ttls
+        .insert(STREAMING.to_string(), vec![(Entry::Instance, Some(80_000))]);
+    assert_eq!(conditions(&monitor.poll())[0].0, State::Resolved);
+}
+
+#[test]
+fn failed_checks_keep_their_alerts() {
+    let probe = probe();
+    let mut monitor = Monitor::new(&config(), |_| probe.clone());
+    probe.set_view(STREAMING, "paused", ScVal::Bool(true));
+    monitor.poll();
+
+    // The paused view fails, then the whole RPC: the contract may still be
+    // paused, so the alert stands alongside the failure
+    probe.0.borrow_mut().views.clear();
+    let events = monitor.poll();
+    assert_eq!(
+        events
+            .iter()
+            .map(|event| (event.state, &event.alert.condition))
+            .filter(|(_, condition)| matches!(
+                condition,
+                Condition::CheckFailed {
+                    check: Check::Paused,
+                    ..
+                }
+            ))
+            .count(),
+        1
+    );
+    assert!(events.iter().all(|event| event.state == State::Firing));
+
+    probe.0.borrow_mut().healthy = false;
+    let events = monitor.poll();
+    assert_eq!(
+        events[0].alert,
+        Alert {
+            network: "testnet".to_string(),
+            contract: None,
+            contract_id: None,
+            condition: Condition::RpcUnhealthy {
+                reason: "the RPC reports itself unhealthy".to_string(),
+            },
+        }
+    );
+    assert!(monitor
+        .firing()
+        .any(|alert| alert.condition == Condition::Paused));
+
+    // Back up and no longer paused, everything resolves
+    *probe.0.borrow_mut() = ledger();
+    let events = monitor.poll();
+    assert!(events.iter().all(|event| event.state == State::Resolved));
+    assert_eq!(monitor.firing().count(), 0);
+}
+
+#[test]
+fn events_serialize_flat() {
+    let event = Event {
+        state: State::Firing,
+        alert: Alert {
+            network: "testnet".to_string(),
+            contract: Some("streaming".to_string()),
+            contract_id: Some(STREAMING.to_string()),
+            condition: Condition::TtlExpiring {
+                entry: Entry::Code,
+                live_until_ledger: 50_000,
+                latest_ledger: 49_001,
+            },
+        },
+    };
+    assert_eq!(
+        serde_json::to_value(&event).unwrap(),
+        json!({
+            "state": "firing",
+            "network": "testnet",
+            "contract": "streaming",
+            "contract_id": STREAMING,
+            "condition": "ttl_expiring",
+            "entry": "code",
+            "live_until_ledger": 50_000,
+            "latest_ledger": 49_001,
+        })
+    );
+
+    let paused = Event {
+        state: State::Resolved,
+        alert: Alert {
+            condition: Condition::Paused,
+            ..event.alert
+        },
+    };
+    assert_eq!(
+        serde_json::to_value(&paused).unwrap()["condition"],
+        "paused"
+    );
+}
+
+#[test]
+fn config_fills_in_and_checks_networks() {
+    let config = config();
+    assert_eq!(
+        config.networks[0].rpc_url,
+        "https://soroban-testnet.stellar.org"
+    );
+    assert_eq!(config.networks[0].contracts[1].admin_view, "get_admin");
+    assert!(config.networks[0].contracts[0].pausable);
+
+    let invalid = |networks: serde_json::Value| {
+        Config::from_json(&json!({ "networks": networks }).to_string()).unwrap_err()
+    };
+    assert!(matches!(
+        invalid(json!([{ "name": "mainnet", "contracts": [] }])),
+        ConfigError::Invalid { name, .. } if name == "mainnet"
+    ));
+    assert!(matches!(
+        invalid(json!([{
+            "name": "testnet",
+            "contracts": [{ "name": "escrow", "id": ESCROW, "admin": "nobody" }],
+        }])),
+        ConfigError::Invalid { name, .. } if name == "testnet.escrow"
+    ));
+    assert!(matches!(invalid(json!([])), ConfigError::Parse(_)));
+}
@@ -0,0 +1,81 @@
+//! Notifier settings, read from the environment.
+
+use std::time::Duration;
+
+pub const DEFAULT_DATABASE: &str = "lumenpulse-notifier.db";
+pub const DEFAULT_EVENTS_DATABASE: &str = "lumenpulse-events.db";
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ConfigError {
+    #[error("{name} is invalid: {reason}")]
+    Invalid { name: &'static str, reason: String },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    /// `NOTIFIER_DATABASE`, the notifier's own SQLite file
+    pub database: String,
+    /// `NOTIFIER_EVENTS_DATABASE`, the indexer's SQLite file
+    pub events_database: String,
+    /// `NOTIFIER_POLL_SECONDS`, how long to wait when there is nothing to
+    /// send
+    pub poll_interval: Duration,
+    /// `NOTIFIER_MAX_ATTEMPTS`, attempts at a delivery before giving up
+    pub max_attempts: u32,
+    /// `NOTIFIER_RETRY_SECONDS`, wait after the first failed attempt,
+    /// doubled after each one after that
+    pub retry: Duration,
+    /// `NOTIFIER_MAX_RETRY_SECONDS`, the longest wait between attempts
+    pub max_retry: Duration,
+    /// `NOTIFIER_TIMEOUT_SECONDS`, how long an endpoint has to answer
+    pub timeout: Duration,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let max_attempts = parse(&var, "NOTIFIER_MAX_ATTEMPTS")?.unwrap_or(8);
+        if max_attempts == 0 {
+            return Err(ConfigError::Invalid {
+                name: "NOTIFIER_MAX_ATTEMPTS",
+                reason: "must be at least 1".to_string(),
+            });
+        }
+        let seconds = |name, default| -> Result<Duration, ConfigError> {
+            Ok(Duration::from_secs(parse(&var, name)?.unwrap_or(default)))
+        };
+        Ok(Config {
+            database: var("NOTIFIER_DATABASE").unwrap_or_else(|| DEFAULT_DATABASE.to_string()),
+            events_database: var("NOTIFIER_EVENTS_DATABASE")
+                .unwrap_or_else(|| DEFAULT_EVENTS_DATABASE.to_string()),
+            poll_interval: seconds("NOTIFIER_POLL_SECONDS", 5)?,
+            max_attempts,
+            retry: seconds("NOTIFIER_RETRY_SECONDS", 30)?,
+            max_retry: seconds("NOTIFIER_MAX_RETRY_SECONDS", 6 * 60 * 60)?,
+            timeout: seconds("NOTIFIER_TIMEOUT_SECONDS", 10)?,
+        })
+    }
+}
+
+fn parse<T: std::str::FromStr>(
+    var: &impl Fn(&str) -> Option<String>,
+    name: &'static str,
+) -> Result<Option<T>, ConfigError>
+where
+    T::Err: std::fmt::Display,
+{
+    var(name)
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|err: T::Err| ConfigError::Invalid {
+                    name,
+                    reason: err.to_string(),
+                })
+        })
+        .transpose()
+}
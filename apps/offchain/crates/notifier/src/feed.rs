@@ -0,0 +1,101 @@
+//! Events to notify about, read from the indexer's database.
+
+use rusqlite::{params, Connection, OpenFlags};
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Webhook types and the indexed event kinds they are sent for
+pub const TYPES: [(&str, &str); 4] = [
+    ("stream.created", "streaming.stream_created"),
+    ("stream.withdrawn", "streaming.withdrawn"),
+    ("payment.failed", "subscriptions.charge_failed"),
+    ("upgrade.executed", "upgrade.upgrade_executed"),
+];
+
+/// The body of a webhook
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Notification {
+    /// The indexed event's ID, the same on every retry
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_: &'static str,
+    pub ledger: u32,
+    pub ledger_closed_at: String,
+    pub contract_id: String,
+    pub tx_hash: String,
+    /// The decoded event, as the indexer stores it under its schema
+    pub data: Value,
+}
+
+pub struct Feed {
+    conn: Connection,
+}
+
+impl Feed {
+    /// Open the indexer's database for reading, alongside the running
+    /// indexer
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        conn.busy_timeout(Duration::from_secs(5))?;
+        Ok(Feed { conn })
+    }
+
+    pub fn with_connection(conn: Connection) -> Self {
+        Feed { conn }
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// ID of the latest event indexed, of any kind
+    pub fn latest(&self) -> rusqlite::Result<Option<String>> {
+        self.conn
+            .query_row("SELECT max(id) FROM events", [], |row| row.get(0))
+    }
+
+    /// Up to `limit` events of the notified kinds after the event with ID
+    /// `after`, in the order they happened. Event IDs sort in that order.
+    pub fn after(&self, after: Option<&str>, limit: u32) -> rusqlite::Result<Vec<Notification>> {
+        let mut statement = self.conn.prepare(
+            "SELECT id, ledger, ledger_closed_at, contract_id, tx_hash, kind, decoded FROM events
+             WHERE id > ?1 AND kind IN (SELECT value FROM json_each(?2))
+             ORDER BY id LIMIT ?3",
+        )?;
+        let kinds: Vec<_> = TYPES.iter().map(|(_, kind)| kind).collect();
+        let kinds = serde_json::to_string(&kinds).expect("strings serialize");
+        let rows = statement.query_map(params![after.unwrap_or(""), kinds, limit], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, u32>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, String>(6)?,
+            ))
+        })?;
+        let mut notifications = Vec::new();
+        for row in rows {
+            let (id, ledger, ledger_closed_at, contract_id, tx_hash, kind, decoded) = row?;
+            let Some(&(type_, _)) = TYPES.iter().find(|(_, known)| *known == kind) else {
+                continue;
+            };
+            let schema = kind.split('.').next().unwrap_or_default();
+            let data = serde_json::from_str::<Value>(&decoded)
+                .map(|mut decoded| decoded[schema].take())
+                .unwrap_or(Value::Null);
+            notifications.push(Notification {
+                id,
+                type_,
+                ledger,
+                ledger_closed_at,
+                contract_id,
+                tx_hash,
+                data,
+            });
+        }
+        Ok(notifications)
+    }
+}
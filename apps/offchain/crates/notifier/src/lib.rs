@@ -0,0 +1,196 @@
+//! Webhooks for Lumenpulse contract events.
+//!
+//! The notifier reads the events the indexer has stored and delivers the
+//! ones merchants care about (streams created and withdrawn from, failed
+//! subscription payments and executed upgrades) to the endpoints
+//! registered for them, so an integration needs no indexer of its own.
+//! Each request is signed with the endpoint's secret (see [`sign`]) and
+//! carries the event's ID, which stays the same across retries so a
+//! receiver can drop duplicates. A delivery that fails is retried with
+//! exponential backoff until it succeeds or runs out of attempts.
+
+pub mod config;
+pub mod feed;
+pub mod sign;
+pub mod store;
+
+pub use config::Config;
+pub use feed::{Feed, Notification};
+pub use store::{Delivery, Endpoint, EndpointError, Store};
+
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Most events read, and deliveries attempted, in one step
+pub const BATCH: u32 = 100;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Store(#[from] rusqlite::Error),
+    #[error(transparent)]
+    Endpoint(#[from] EndpointError),
+}
+
+/// Sends a signed webhook, succeeding on a 2xx answer
+pub trait Transport {
+    fn post(&self, url: &str, signature: &str, body: &str) -> Result<(), String>;
+}
+
+impl Transport for ureq::Agent {
+    fn post(&self, url: &str, signature: &str, body: &str) -> Result<(), String> {
+        let response = self
+            .post(url)
+            .set("Content-Type", "application/json")
+            .set(sign::HEADER, signature)
+            .send_string(body)
+            .map_err(|err| match err {
+                ureq::Error::Status(status, _) => format!("answered {status}"),
+                ureq::Error::Transport(err) => err.to_string(),
+            })?;
+        match response.status() {
+            200..=299 => Ok(()),
+            status => Err(format!("answered {status}")),
+        }
+    }
+}
+
+/// What a step did
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Step {
+    /// Deliveries queued for newly indexed events
+    pub queued: usize,
+    pub delivered: usize,
+    /// Failed attempts that will be retried
+    pub failed: usize,
+    /// Failed attempts that were the last
+    pub gave_up: usize,
+    /// Whether the step left nothing to read and nothing due
+    pub idle: bool,
+}
+
+pub struct Notifier<T> {
+    transport: T,
+    feed: Feed,
+    store: Store,
+    poll_interval: Duration,
+    max_attempts: u32,
+    retry: Duration,
+    max_retry: Duration,
+}
+
+impl<T: Transport> Notifier<T> {
+    pub fn new(transport: T, feed: Feed, store: Store, config: &Config) -> Self {
+        Notifier {
+            transport,
+            feed,
+            store,
+            poll_interval: config.poll_interval,
+            max_attempts: config.max_attempts,
+            retry: config.retry,
+            max_retry: config.max_retry,
+        }
+    }
+
+    pub fn feed(&self) -> &Feed {
+        &self.feed
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// Queue deliveries for the next batch of indexed events, then attempt
+    /// the deliveries due at `now`. On its first step the notifier starts
+    /// from the latest indexed event rather than replaying history.
+    pub fn step(&mut self, now: u64) -> Result<Step, Error> {
+        let cursor = match self.store.cursor()? {
+            Some(cursor) => Some(cursor),
+            None => {
+                let latest = self.feed.latest()?;
+                if let Some(latest) = &latest {
+                    self.store.set_cursor(latest)?;
+                }
+                latest
+            }
+        };
+        let notifications = self.feed.after(cursor.as_deref(), BATCH)?;
+        let mut step = Step {
+            queued: self.store.enqueue(&notifications, now)?,
+            ..Step::default()
+        };
+
+        let due = self.store.due(now, BATCH)?;
+        for delivery in &due {
+            let signature = sign::sign(&delivery.secret, now, &delivery.body);
+            match self
+                .transport
+                .post(&delivery.url, &signature, &delivery.body)
+            {
+                Ok(()) => {
+                    self.store.delivered(delivery.id, now)?;
+                    step.delivered += 1;
+                }
+                Err(err) => {
+                    let attempts = delivery.attempts + 1;
+                    if attempts >= self.max_attempts {
+                        self.store.failed(delivery.id, &err, None)?;
+                        step.gave_up += 1;
+                        eprintln!(
+                            "gave up on {} to {} after {attempts} attempts: {err}",
+                            delivery.event_id, delivery.url
+                        );
+                    } else {
+                        let delay = backoff(self.retry, self.max_retry, attempts);
+                        self.store
+                            .failed(delivery.id, &err, Some(now + delay.as_secs()))?;
+                        step.failed += 1;
+                        eprintln!(
+                            "delivering {} to {} failed, retrying in {}s: {err}",
+                            delivery.event_id,
+                            delivery.url,
+                            delay.as_secs()
+                        );
+                    }
+                }
+            }
+        }
+        step.idle = notifications.len() < BATCH as usize && due.len() < BATCH as usize;
+        Ok(step)
+    }
+
+    /// Step until the store fails, waiting `poll_interval` whenever there
+    /// is nothing left to do
+    pub fn run(&mut self) -> Result<(), Error> {
+        loop {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let step = self.step(now)?;
+            if step.queued > 0 || step.delivered > 0 {
+                eprintln!(
+                    "queued {} and delivered {} webhooks",
+                    step.queued, step.delivered
+                );
+            }
+            if step.idle {
+                thread::sleep(self.poll_interval);
+            }
+        }
+    }
+}
+
+/// Wait after the `attempts`th failed attempt: `retry` doubled for each
+/// attempt after the first, up to `max`
+pub fn backoff(retry: Duration, max: Duration, attempts: u32) -> Duration {
+    let factor = 1u32
+        .checked_shl(attempts.saturating_sub(1))
+        .unwrap_or(u32::MAX);
+    retry.saturating_mul(factor).min(max)
+}
+
+#[cfg(test)]
+mod test;
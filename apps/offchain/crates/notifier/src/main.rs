@@ -0,0 +1,95 @@
+use clap::{Parser, Subcommand};
+use lumenpulse_notifier::{sign, Config, Feed, Notifier, Store};
+use std::process::ExitCode;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "lumenpulse-notifier",
+    about = "Deliver signed webhooks for Lumenpulse contract events"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Deliver webhooks until stopped
+    Run,
+    /// Register an endpoint, printing the secret its requests are signed
+    /// with
+    Add {
+        url: String,
+        /// Webhook type to send it; every type if none are given
+        #[arg(long = "type")]
+        types: Vec<String>,
+        /// Contract whose events to send it; every contract if none are
+        /// given
+        #[arg(long = "contract")]
+        contracts: Vec<String>,
+    },
+    /// List the registered endpoints
+    List,
+    /// Remove an endpoint and what it is still owed
+    Remove { id: i64 },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("lumenpulse-notifier: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::from_env()?;
+    let mut store = Store::open(&config.database)?;
+    match cli.command {
+        Command::Run => {
+            let feed = Feed::open(&config.events_database)?;
+            let agent = ureq::AgentBuilder::new().timeout(config.timeout).build();
+            eprintln!(
+                "notifying {} endpoints of events in {}",
+                store.endpoints()?.len(),
+                config.events_database
+            );
+            Notifier::new(agent, feed, store, &config).run()?;
+        }
+        Command::Add {
+            url,
+            types,
+            contracts,
+        } => {
+            let endpoint = store.add_endpoint(&url, &sign::secret(), &types, &contracts)?;
+            println!("{}\t{}\t{}", endpoint.id, endpoint.url, endpoint.secret);
+        }
+        Command::List => {
+            for endpoint in store.endpoints()? {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    endpoint.id,
+                    endpoint.url,
+                    list(&endpoint.types, "all types"),
+                    list(&endpoint.contracts, "all contracts"),
+                );
+            }
+        }
+        Command::Remove { id } => {
+            if !store.remove_endpoint(id)? {
+                return Err(format!("there is no endpoint {id}").into());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn list(values: &[String], empty: &str) -> String {
+    if values.is_empty() {
+        empty.to_string()
+    } else {
+        values.join(",")
+    }
+}
@@ -0,0 +1,256 @@
+//! The notifier's own database: endpoints, the deliveries owed to them
+//! and how far through the indexer's events the notifier has got.
+
+use crate::feed::{Notification, TYPES};
+use rusqlite::{params, Connection, OptionalExtension};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS endpoints (
+    id INTEGER PRIMARY KEY,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    -- JSON arrays of webhook types and contract IDs; empty for all
+    types TEXT NOT NULL,
+    contracts TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS deliveries (
+    id INTEGER PRIMARY KEY,
+    endpoint_id INTEGER NOT NULL,
+    event_id TEXT NOT NULL,
+    body TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    -- Unix seconds; NULL once delivered or given up on
+    next_attempt_at INTEGER,
+    delivered_at INTEGER,
+    last_error TEXT,
+    UNIQUE (endpoint_id, event_id)
+);
+CREATE INDEX IF NOT EXISTS deliveries_due ON deliveries (next_attempt_at)
+    WHERE next_attempt_at IS NOT NULL;
+CREATE TABLE IF NOT EXISTS cursor (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    event_id TEXT NOT NULL
+);
+";
+
+const SET_CURSOR: &str = "INSERT INTO cursor (id, event_id) VALUES (0, ?1)
+    ON CONFLICT (id) DO UPDATE SET event_id = excluded.event_id";
+
+/// Where webhooks go, and which
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Endpoint {
+    pub id: i64,
+    pub url: String,
+    /// Key the endpoint's requests are signed with
+    pub secret: String,
+    /// Webhook types it takes, or every type when empty
+    pub types: Vec<String>,
+    /// Contracts whose events it takes, or every contract when empty
+    pub contracts: Vec<String>,
+}
+
+impl Endpoint {
+    pub fn wants(&self, notification: &Notification) -> bool {
+        (self.types.is_empty() || self.types.iter().any(|t| t == notification.type_))
+            && (self.contracts.is_empty() || self.contracts.contains(&notification.contract_id))
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum EndpointError {
+    #[error("`{0}` is not an http(s) URL")]
+    Url(String),
+    #[error("unknown webhook type `{0}`; the types are {types}", types = type_names())]
+    Type(String),
+    #[error("`{0}` is not a contract address")]
+    Contract(String),
+}
+
+fn type_names() -> String {
+    TYPES.map(|(type_, _)| type_).join(", ")
+}
+
+/// A delivery whose next attempt is due
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Delivery {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    pub event_id: String,
+    pub body: String,
+    /// Attempts made before this one
+    pub attempts: u32,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Store { conn })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Register `url` for `types` of events from `contracts`, signing with
+    /// `secret`. It receives events indexed from now on.
+    pub fn add_endpoint(
+        &self,
+        url: &str,
+        secret: &str,
+        types: &[String],
+        contracts: &[String],
+    ) -> Result<Endpoint, crate::Error> {
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Err(EndpointError::Url(url.to_string()).into());
+        }
+        if let Some(type_) = types
+            .iter()
+            .find(|type_| !TYPES.iter().any(|(known, _)| known == type_))
+        {
+            return Err(EndpointError::Type(type_.clone()).into());
+        }
+        if let Some(contract) = contracts
+            .iter()
+            .find(|contract| stellar_strkey::Contract::from_string(contract).is_err())
+        {
+            return Err(EndpointError::Contract(contract.clone()).into());
+        }
+        self.conn.execute(
+            "INSERT INTO endpoints (url, secret, types, contracts) VALUES (?1, ?2, ?3, ?4)",
+            params![url, secret, json(types), json(contracts)],
+        )?;
+        Ok(Endpoint {
+            id: self.conn.last_insert_rowid(),
+            url: url.to_string(),
+            secret: secret.to_string(),
+            types: types.to_vec(),
+            contracts: contracts.to_vec(),
+        })
+    }
+
+    /// Remove endpoint `id` and what it is still owed. Returns whether
+    /// there was one.
+    pub fn remove_endpoint(&mut self, id: i64) -> rusqlite::Result<bool> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM deliveries WHERE endpoint_id = ?1", [id])?;
+        let removed = tx.execute("DELETE FROM endpoints WHERE id = ?1", [id])? > 0;
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    pub fn endpoints(&self) -> rusqlite::Result<Vec<Endpoint>> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT id, url, secret, types, contracts FROM endpoints ORDER BY id")?;
+        let rows = statement.query_map([], |row| {
+            Ok(Endpoint {
+                id: row.get(0)?,
+                url: row.get(1)?,
+                secret: row.get(2)?,
+                types: serde_json::from_str(&row.get::<_, String>(3)?).unwrap_or_default(),
+                contracts: serde_json::from_str(&row.get::<_, String>(4)?).unwrap_or_default(),
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// ID of the last indexed event the notifier has queued deliveries for
+    pub fn cursor(&self) -> rusqlite::Result<Option<String>> {
+        self.conn
+            .query_row("SELECT event_id FROM cursor WHERE id = 0", [], |row| {
+                row.get(0)
+            })
+            .optional()
+    }
+
+    pub fn set_cursor(&self, event_id: &str) -> rusqlite::Result<()> {
+        self.conn.execute(SET_CURSOR, [event_id])?;
+        Ok(())
+    }
+
+    /// Queue a delivery of each notification to every endpoint that wants
+    /// it, due at `now`, and move the cursor past them, all or nothing.
+    /// Returns how many deliveries were queued.
+    pub fn enqueue(&mut self, notifications: &[Notification], now: u64) -> rusqlite::Result<usize> {
+        let Some(last) = notifications.last() else {
+            return Ok(0);
+        };
+        let endpoints = self.endpoints()?;
+        let tx = self.conn.transaction()?;
+        let mut queued = 0;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR IGNORE INTO deliveries (endpoint_id, event_id, body, next_attempt_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for notification in notifications {
+                let body = serde_json::to_string(notification).expect("notifications serialize");
+                for endpoint in endpoints.iter().filter(|e| e.wants(notification)) {
+                    queued +=
+                        insert.execute(params![endpoint.id, notification.id, body, now as i64])?;
+                }
+            }
+        }
+        tx.execute(SET_CURSOR, [&last.id])?;
+        tx.commit()?;
+        Ok(queued)
+    }
+
+    /// Up to `limit` deliveries due by `now`, oldest first
+    pub fn due(&self, now: u64, limit: u32) -> rusqlite::Result<Vec<Delivery>> {
+        let mut statement = self.conn.prepare(
+            "SELECT d.id, e.url, e.secret, d.event_id, d.body, d.attempts
+             FROM deliveries d JOIN endpoints e ON e.id = d.endpoint_id
+             WHERE d.next_attempt_at <= ?1
+             ORDER BY d.next_attempt_at, d.id LIMIT ?2",
+        )?;
+        let rows = statement.query_map(params![now as i64, limit], |row| {
+            Ok(Delivery {
+                id: row.get(0)?,
+                url: row.get(1)?,
+                secret: row.get(2)?,
+                event_id: row.get(3)?,
+                body: row.get(4)?,
+                attempts: row.get(5)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    pub fn delivered(&self, id: i64, now: u64) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "UPDATE deliveries SET attempts = attempts + 1, next_attempt_at = NULL,
+                delivered_at = ?2, last_error = NULL
+             WHERE id = ?1",
+            params![id, now as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Record a failed attempt, to be retried at `retry_at` or never
+    pub fn failed(&self, id: i64, error: &str, retry_at: Option<u64>) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "UPDATE deliveries SET attempts = attempts + 1, next_attempt_at = ?2, last_error = ?3
+             WHERE id = ?1",
+            params![id, retry_at.map(|at| at as i64), error],
+        )?;
+        Ok(())
+    }
+}
+
+fn json(values: &[String]) -> String {
+    serde_json::to_string(values).expect("strings serialize")
+}
@@ -0,0 +1,285 @@
+use crate::{backoff, sign, Config, Error, Feed, Notifier, Step, Store, Transport};
+use rusqlite::{params, Connection};
+use serde_json::{json, Value};
+use std::{
+    cell::{Cell, RefCell},
+    time::Duration,
+};
+
+const STREAMING: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+const SUBSCRIPTIONS: &str = "CAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC526";
+const SECRET: &str = "whsec_test";
+
+/// Records every request, failing the first `failures` of them
+#[derive(Default)]
+struct FakeTransport {
+    failures: Cell<u32>,
+    posted: RefCell<Vec<(String, String, Value)>>,
+}
+
+impl Transport for &FakeTransport {
+    fn post(&self, url: &str, signature: &str, body: &str) -> Result<(), String> {
+        assert!(sign::verify(SECRET, signature, body, 1_000, 300));
+        self.posted.borrow_mut().push((
+            url.to_string(),
+            signature.to_string(),
+            serde_json::from_str(body).unwrap(),
+        ));
+        match self.failures.get() {
+            0 => Ok(()),
+            failures => {
+                self.failures.set(failures - 1);
+                Err("answered 503".to_string())
+            }
+        }
+    }
+}
+
+/// The indexer's events table, empty
+fn feed() -> Feed {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE events (id TEXT PRIMARY KEY, ledger INTEGER, ledger_closed_at TEXT,
+            contract_id TEXT, tx_hash TEXT, kind TEXT, decoded TEXT)",
+    )
+    .unwrap();
+    Feed::with_connection(conn)
+}
+
+/// Index an event of `kind` with `fields`, as ID `id`
+fn index(feed: &Feed, id: u32, contract: &str, kind: &str, fields: Value) {
+    let (schema, event) = kind.split_once('.').unwrap();
+    let mut decoded = json!({ "kind": event });
+    decoded
+        .as_object_mut()
+        .unwrap()
+        .extend(fields.as_object().unwrap().clone());
+    feed.connection()
+        .execute(
+            "INSERT INTO events VALUES (?1, ?2, '2026-01-01T00:00:00Z', ?3, 'ab01', ?4, ?5)",
+            params![
+                format!("{id:04}"),
+                id,
+                contract,
+                kind,
+                json!({ schema: decoded }).to_string()
+            ],
+        )
+        .unwrap();
+}
+
+fn config(vars: &[(&str, &str)]) -> Config {
+    Config::from_vars(|name| {
+        vars.iter()
+            .find(|(var, _)| *var == name)
+            .map(|(_, value)| value.to_string())
+    })
+    .unwrap()
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn signatures_cover_the_body_and_expire() {
+    let body = r#"{"id":"0001"}"#;
+    let header = sign::sign(SECRET, 1_000, body);
+    assert!(header.starts_with("t=1000,v1="));
+    assert!(sign::verify(SECRET, &header, body, 1_100, 300));
+    assert!(!sign::verify(
+        SECRET,
+        &header,
+        r#"{"id":"0002"}"#,
+        1_100,
+        300
+    ));
+    assert!(!sign::verify("whsec_other", &header, body, 1_100, 300));
+    assert!(!sign::verify(SECRET, &header, body, 1_301, 300));
+    // The timestamp is signed too
+    let moved = header.replace("t=1000", "t=1100");
+    assert!(!sign::verify(SECRET, &moved, body, 1_100, 300));
+
+    let secret = sign::secret();
+    assert!(secret.starts_with(sign::SECRET_PREFIX));
+    assert_ne!(secret, sign::secret());
+}
+
+#[test]
+fn delivers_new_events_to_the_endpoints_that_want_them() {
+    let feed = feed();
+    index(
+        &feed,
+        1,
+        STREAMING,
+        "streaming.withdrawn",
+        json!({ "stream_id": 0, "amount": "5" }),
+    );
+    let store = Store::in_memory().unwrap();
+    let every = store
+        .add_endpoint("https://a.example", SECRET, &[], &[])
+        .unwrap();
+    store
+        .add_endpoint(
+            "https://b.example",
+            SECRET,
+            &strings(&["payment.failed"]),
+            &[],
+        )
+        .unwrap();
+    store
+        .add_endpoint("https://c.example", SECRET, &[], &strings(&[STREAMING]))
+        .unwrap();
+    let transport = FakeTransport::default();
+    let mut notifier = Notifier::new(&transport, feed, store, &config(&[]));
+
+    // Events indexed before the notifier first ran are not replayed
+    assert_eq!(
+        notifier.step(1_000).unwrap(),
+        Step {
+            idle: true,
+            ..Step::default()
+        }
+    );
+    assert_eq!(notifier.store().cursor().unwrap(), Some("0001".to_string()));
+
+    index(
+        notifier.feed(),
+        2,
+        STREAMING,
+        "streaming.stream_created",
+        json!({ "stream_id": 1 }),
+    );
+    index(
+        notifier.feed(),
+        3,
+        SUBSCRIPTIONS,
+        "subscriptions.charge_failed",
+        json!({ "subscription_id": 7, "failures": 1 }),
+    );
+    index(
+        notifier.feed(),
+        4,
+        STREAMING,
+        "streaming.stream_paused",
+        json!({ "stream_id": 1 }),
+    );
+    let step = notifier.step(1_000).unwrap();
+    assert_eq!((step.queued, step.delivered, step.failed), (4, 4, 0));
+    let posted: Vec<_> = transport
+        .posted
+        .borrow()
+        .iter()
+        .map(|(url, _, body)| (url.clone(), body["id"].clone(), body["type"].clone()))
+        .collect();
+    assert_eq!(
+        posted,
+        [
+            ("https://a.example", "0002", "stream.created"),
+            ("https://c.example", "0002", "stream.created"),
+            ("https://a.example", "0003", "payment.failed"),
+            ("https://b.example", "0003", "payment.failed"),
+        ]
+        .map(|(url, id, type_)| (url.to_string(), json!(id), json!(type_)))
+    );
+    let (_, _, body) = &transport.posted.borrow()[2];
+    assert_eq!(body["contract_id"], SUBSCRIPTIONS);
+    assert_eq!(body["ledger"], 3);
+    assert_eq!(body["data"]["subscription_id"], 7);
+
+    // Nothing is sent twice, and the cursor rests on the last event sent
+    assert_eq!(notifier.step(1_000).unwrap().delivered, 0);
+    assert_eq!(notifier.store().cursor().unwrap(), Some("0003".to_string()));
+    assert_eq!(notifier.store().endpoints().unwrap()[0], every);
+}
+
+#[test]
+fn failed_deliveries_back_off_then_give_up() {
+    let feed = feed();
+    let store = Store::in_memory().unwrap();
+    store
+        .add_endpoint("https://a.example", SECRET, &[], &[])
+        .unwrap();
+    let transport = FakeTransport {
+        failures: Cell::new(u32::MAX),
+        ..FakeTransport::default()
+    };
+    let config = config(&[
+        ("NOTIFIER_MAX_ATTEMPTS", "3"),
+        ("NOTIFIER_RETRY_SECONDS", "10"),
+    ]);
+    index(
+        &feed,
+        1,
+        STREAMING,
+        "streaming.stream_created",
+        json!({ "stream_id": 0 }),
+    );
+    // A store that has run before picks up from its cursor
+    store.set_cursor("0000").unwrap();
+    let mut notifier = Notifier::new(&transport, feed, store, &config);
+
+    assert_eq!(notifier.step(1_000).unwrap().failed, 1);
+    assert_eq!(notifier.step(1_009).unwrap().failed, 0);
+    assert_eq!(notifier.step(1_010).unwrap().failed, 1);
+    assert_eq!(notifier.step(1_029).unwrap().failed, 0);
+    assert_eq!(notifier.step(1_030).unwrap().gave_up, 1);
+    assert_eq!(
+        notifier.step(100_000).unwrap(),
+        Step {
+            idle: true,
+            ..Step::default()
+        }
+    );
+    // Every attempt carries the same event, signed when it was sent
+    let posted = transport.posted.borrow();
+    assert_eq!(posted.len(), 3);
+    assert!(posted.iter().all(|(_, _, body)| body["id"] == "0001"));
+    assert!(posted[1].1.starts_with("t=1010,"));
+
+    assert_eq!(
+        backoff(Duration::from_secs(30), Duration::from_secs(3600), 40),
+        Duration::from_secs(3600)
+    );
+}
+
+#[test]
+fn endpoints_are_checked_and_removed_with_their_deliveries() {
+    let mut store = Store::in_memory().unwrap();
+    assert!(matches!(
+        store.add_endpoint("ftp://a.example", SECRET, &[], &[]),
+        Err(Error::Endpoint(_))
+    ));
+    assert!(matches!(
+        store.add_endpoint(
+            "https://a.example",
+            SECRET,
+            &strings(&["stream.paused"]),
+            &[]
+        ),
+        Err(Error::Endpoint(_))
+    ));
+    assert!(matches!(
+        store.add_endpoint("https://a.example", SECRET, &[], &strings(&["GABC"])),
+        Err(Error::Endpoint(_))
+    ));
+
+    let endpoint = store
+        .add_endpoint("https://a.example", SECRET, &[], &[])
+        .unwrap();
+    let feed = feed();
+    index(
+        &feed,
+        1,
+        STREAMING,
+        "streaming.stream_created",
+        json!({ "stream_id": 0 }),
+    );
+    let notifications = feed.after(None, 10).unwrap();
+    assert_eq!(store.enqueue(&notifications, 1_000).unwrap(), 1);
+    assert_eq!(store.due(1_000, 10).unwrap().len(), 1);
+    assert!(store.remove_endpoint(endpoint.id).unwrap());
+    assert!(!store.remove_endpoint(endpoint.id).unwrap());
+    assert!(store.due(1_000, 10).unwrap().is_empty());
+    assert!(store.endpoints().unwrap().is_empty());
+}
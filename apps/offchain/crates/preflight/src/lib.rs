@@ -0,0 +1,279 @@
+//! Preflight reports for contract invocations.
+//!
+//! Before a transaction goes out, `preflight` simulates it and reports
+//! what the network found: the return value, or the contract error by
+//! name from the contract's spec; the fee and resources; the ledger
+//! entries it reads and writes; and anyone else who has to authorize it.
+//! `Preflight::prepare` only hands back a transaction to sign when the
+//! simulation succeeded, so a call that would fail costs nothing.
+
+mod render;
+
+pub use render::{key as render_key, value as render_value};
+
+use lumenpulse_clients::{ContractError, SHARED_ERRORS};
+use lumenpulse_rpc::Simulation;
+use lumenpulse_tx::{Prepared, Sender, BASE_FEE};
+use std::fmt;
+use stellar_xdr::curr::{
+    ContractEventBody, HostFunction, ScError, ScVal, SorobanCredentials, Transaction,
+};
+
+/// What simulating one invocation found
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// What is called, such as `pause on CA...`
+    pub call: String,
+    pub latest_ledger: u32,
+    pub outcome: Outcome,
+    /// Resource fee on top of the inclusion fee, in stroops
+    pub resource_fee: i64,
+    pub resources: Option<Resources>,
+    /// Footprint entries read, as `render_key` writes them
+    pub reads: Vec<String>,
+    pub writes: Vec<String>,
+    /// Addresses other than the source account that have to authorize
+    /// the invocation
+    pub signers: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Resources {
+    pub instructions: u32,
+    pub read_bytes: u32,
+    pub write_bytes: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    /// The invocation would succeed, returning this
+    Success(Option<ScVal>),
+    /// The contract would return error `code`, known if the spec lists it
+    ContractError {
+        code: u32,
+        error: Option<ContractError>,
+    },
+    /// The host would fail the invocation for another reason
+    HostError(String),
+    /// Entries the invocation reads are archived and have to be restored
+    RestoreNeeded,
+}
+
+impl Report {
+    /// Report on `simulation` of `host_function`, naming contract errors
+    /// from `errors` or the shared crates' errors
+    pub fn new(
+        host_function: &HostFunction,
+        simulation: &Simulation,
+        errors: &[ContractError],
+    ) -> Self {
+        let outcome = match &simulation.error {
+            Some(message) => match contract_error(message, simulation) {
+                Some(code) => Outcome::ContractError {
+                    code,
+                    error: ContractError::find(errors, code)
+                        .or_else(|| ContractError::find(SHARED_ERRORS, code)),
+                },
+                // The rest of the message is the event log
+                None => {
+                    let first = message.lines().next().unwrap_or_default();
+                    Outcome::HostError(first.to_string())
+                }
+            },
+            None if simulation.restore_preamble.is_some() => Outcome::RestoreNeeded,
+            None => Outcome::Success(simulation.return_value.clone()),
+        };
+        let resources = simulation
+            .transaction_data
+            .as_ref()
+            .map(|data| &data.resources);
+        Report {
+            call: describe(host_function),
+            latest_ledger: simulation.latest_ledger,
+            outcome,
+            resource_fee: simulation.min_resource_fee,
+            resources: resources.map(|resources| Resources {
+                instructions: resources.instructions,
+                read_bytes: resources.disk_read_bytes,
+                write_bytes: resources.write_bytes,
+            }),
+            reads: resources
+                .map(|resources| {
+                    resources
+                        .footprint
+                        .read_only
+                        .iter()
+                        .map(render::key)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            writes: resources
+                .map(|resources| {
+                    resources
+                        .footprint
+                        .read_write
+                        .iter()
+                        .map(render::key)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            signers: simulation
+                .auth
+                .iter()
+                .filter_map(|auth| match &auth.credentials {
+                    SorobanCredentials::Address(credentials) => {
+                        Some(credentials.address.to_string())
+                    }
+                    SorobanCredentials::SourceAccount => None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn succeeds(&self) -> bool {
+        matches!(self.outcome, Outcome::Success(_))
+    }
+
+    /// Total fee the transaction would bid, in stroops
+    pub fn fee(&self) -> i64 {
+        i64::from(BASE_FEE) + self.resource_fee
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Success(Some(value)) => write!(f, "returns {}", render::value(value)),
+            Outcome::Success(None) => write!(f, "succeeds"),
+            Outcome::ContractError {
+                code,
+                error: Some(error),
+            } if error.doc.is_empty() => write!(f, "fails with {} ({code})", error.name),
+            Outcome::ContractError {
+                code,
+                error: Some(error),
+            } => {
+                let doc = error.doc.split_whitespace().collect::<Vec<_>>().join(" ");
+                write!(f, "fails with {} ({code}): {doc}", error.name)
+            }
+            Outcome::ContractError { code, error: None } => {
+                write!(f, "fails with contract error {code}")
+            }
+            Outcome::HostError(message) => write!(f, "fails: {message}"),
+            Outcome::RestoreNeeded => write!(f, "reads archived entries, which need restoring"),
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} at ledger {}", self.call, self.latest_ledger)?;
+        writeln!(f, "  {}", self.outcome)?;
+        if !self.succeeds() {
+            return Ok(());
+        }
+        writeln!(
+            f,
+            "  fee: {} stroops ({BASE_FEE} inclusion, {} resources)",
+            self.fee(),
+            self.resource_fee
+        )?;
+        if let Some(resources) = &self.resources {
+            writeln!(
+                f,
+                "  resources: {} instructions, {} bytes read, {} bytes written",
+                resources.instructions, resources.read_bytes, resources.write_bytes
+            )?;
+        }
+        for read in &self.reads {
+            writeln!(f, "  reads {read}")?;
+        }
+        for write in &self.writes {
+            writeln!(f, "  writes {write}")?;
+        }
+        for signer in &self.signers {
+            writeln!(f, "  needs authorization from {signer}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An invocation simulated and reported on
+pub struct Preflight {
+    pub report: Report,
+    tx: Transaction,
+    simulation: Simulation,
+}
+
+/// Simulate `host_function` from `sender` and report on it, naming
+/// contract errors from `errors`, such as `streaming::ERRORS`
+pub fn preflight(
+    sender: &Sender,
+    host_function: HostFunction,
+    errors: &[ContractError],
+) -> Result<Preflight, lumenpulse_tx::Error> {
+    let (tx, simulation) = sender.simulate(host_function.clone())?;
+    Ok(Preflight {
+        report: Report::new(&host_function, &simulation, errors),
+        tx,
+        simulation,
+    })
+}
+
+impl Preflight {
+    /// The transaction with what the simulation found attached, unless it
+    /// would fail
+    pub fn prepare(self) -> Result<Prepared, lumenpulse_tx::Error> {
+        match &self.report.outcome {
+            Outcome::Success(_) => Ok(Prepared {
+                tx: lumenpulse_tx::assemble(self.tx, &self.simulation)?,
+                simulation: self.simulation,
+            }),
+            Outcome::RestoreNeeded => Err(lumenpulse_tx::Error::RestoreNeeded),
+            outcome => Err(lumenpulse_tx::Error::Simulation(outcome.to_string())),
+        }
+    }
+}
+
+/// What a call is, for the first line of a report
+fn describe(host_function: &HostFunction) -> String {
+    match host_function {
+        HostFunction::InvokeContract(invoke) => format!(
+            "{} on {}",
+            invoke.function_name.0.to_utf8_string_lossy(),
+            invoke.contract_address
+        ),
+        HostFunction::UploadContractWasm(wasm) => {
+            format!("upload of {} bytes of WASM", wasm.len())
+        }
+        HostFunction::CreateContract(_) | HostFunction::CreateContractV2(_) => {
+            "contract creation".to_string()
+        }
+    }
+}
+
+/// The contract error code a failed simulation reports. The message
+/// carries it as `Error(Contract, #code)`; the diagnostic events carry it
+/// as an error topic.
+fn contract_error(message: &str, simulation: &Simulation) -> Option<u32> {
+    const MARKER: &str = "Error(Contract, #";
+    if let Some(start) = message.find(MARKER) {
+        let digits: String = message[start + MARKER.len()..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        if let Ok(code) = digits.parse() {
+            return Some(code);
+        }
+    }
+    simulation.events.iter().find_map(|event| {
+        let ContractEventBody::V0(body) = &event.event.body;
+        body.topics.iter().find_map(|topic| match topic {
+            ScVal::Error(ScError::Contract(code)) => Some(*code),
+            _ => None,
+        })
+    })
+}
+
+#[cfg(test)]
+mod test;
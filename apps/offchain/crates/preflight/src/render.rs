@@ -0,0 +1,83 @@
+//! Values and ledger keys written the way the contracts declare them.
+//!
+//! A `#[contracttype]` enum case is a vector headed by its name, so
+//! `[Symbol("Stream"), 4]` reads as `Stream(4)`, the key a contract
+//! stores stream 4 under.
+
+use stellar_xdr::curr::{
+    ContractDataDurability, Int128Parts, LedgerKey, ScVal, TrustLineAsset, UInt128Parts,
+};
+
+pub fn value(val: &ScVal) -> String {
+    match val {
+        ScVal::Bool(b) => b.to_string(),
+        ScVal::Void => "()".to_string(),
+        ScVal::U32(n) => n.to_string(),
+        ScVal::I32(n) => n.to_string(),
+        ScVal::U64(n) => n.to_string(),
+        ScVal::I64(n) => n.to_string(),
+        ScVal::Timepoint(t) => t.0.to_string(),
+        ScVal::Duration(d) => d.0.to_string(),
+        ScVal::U128(UInt128Parts { hi, lo }) => {
+            ((u128::from(*hi) << 64) | u128::from(*lo)).to_string()
+        }
+        ScVal::I128(Int128Parts { hi, lo }) => {
+            ((i128::from(*hi) << 64) | i128::from(*lo)).to_string()
+        }
+        ScVal::Symbol(symbol) => symbol.0.to_utf8_string_lossy(),
+        ScVal::String(string) => format!("{:?}", string.0.to_utf8_string_lossy()),
+        ScVal::Address(address) => address.to_string(),
+        ScVal::Bytes(bytes) => hex(bytes.as_slice()),
+        ScVal::Vec(Some(items)) => match items.split_first() {
+            Some((ScVal::Symbol(name), fields)) if !fields.is_empty() => {
+                format!("{}({})", name.0.to_utf8_string_lossy(), list(fields))
+            }
+            _ => format!("[{}]", list(items)),
+        },
+        ScVal::Map(Some(entries)) => {
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|entry| format!("{}: {}", value(&entry.key), value(&entry.val)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        ScVal::LedgerKeyContractInstance => "instance".to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+pub fn key(key: &LedgerKey) -> String {
+    match key {
+        LedgerKey::Account(account) => format!("account {}", account.account_id),
+        LedgerKey::Trustline(line) => {
+            let asset = match &line.asset {
+                TrustLineAsset::Native => "XLM".to_string(),
+                TrustLineAsset::CreditAlphanum4(asset) => {
+                    format!("{}:{}", asset.asset_code, asset.issuer)
+                }
+                TrustLineAsset::CreditAlphanum12(asset) => {
+                    format!("{}:{}", asset.asset_code, asset.issuer)
+                }
+                TrustLineAsset::PoolShare(pool) => format!("pool {}", hex(&pool.0 .0)),
+            };
+            format!("trustline {} {asset}", line.account_id)
+        }
+        LedgerKey::ContractData(data) => {
+            let durability = match data.durability {
+                ContractDataDurability::Persistent => "persistent",
+                ContractDataDurability::Temporary => "temporary",
+            };
+            format!("{durability} {} {}", data.contract, value(&data.key))
+        }
+        LedgerKey::ContractCode(code) => format!("code {}", hex(&code.hash.0)),
+        other => format!("{other:?}"),
+    }
+}
+
+fn list(items: &[ScVal]) -> String {
+    items.iter().map(value).collect::<Vec<_>>().join(", ")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -0,0 +1,213 @@
+use crate::{Outcome, Preflight, Report, Resources};
+use lumenpulse_clients::streaming;
+use lumenpulse_rpc::Simulation;
+use lumenpulse_tx::{contract_address, invoke_contract, Signer};
+use stellar_xdr::curr::{
+    AccountId, ContractDataDurability, ContractEvent, ContractEventBody, ContractEventType,
+    ContractEventV0, DiagnosticEvent, ExtensionPoint, HostFunction, LedgerFootprint, LedgerKey,
+    LedgerKeyAccount, LedgerKeyContractData, PublicKey, ScAddress, ScError, ScSymbol, ScVal,
+    SorobanAddressCredentials, SorobanAuthorizationEntry, SorobanAuthorizedFunction,
+    SorobanAuthorizedInvocation, SorobanCredentials, SorobanResources, SorobanTransactionData,
+    SorobanTransactionDataExt, TransactionExt, Uint256, VecM,
+};
+
+const CONTRACT: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+
+fn contract() -> ScAddress {
+    contract_address(CONTRACT).unwrap()
+}
+
+fn account() -> AccountId {
+    AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([1; 32])))
+}
+
+fn cancel() -> HostFunction {
+    invoke_contract(&contract(), "cancel_stream", vec![ScVal::U64(4)]).unwrap()
+}
+
+fn symbol(name: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(name.try_into().unwrap()))
+}
+
+fn data(key: ScVal) -> LedgerKey {
+    LedgerKey::ContractData(LedgerKeyContractData {
+        contract: contract(),
+        key,
+        durability: ContractDataDurability::Persistent,
+    })
+}
+
+/// A simulation of `cancel_stream(4)` that succeeds with the sender's
+/// authorization
+fn success() -> Simulation {
+    let HostFunction::InvokeContract(function) = cancel() else {
+        unreachable!()
+    };
+    Simulation {
+        latest_ledger: 1_210,
+        error: None,
+        transaction_data: Some(SorobanTransactionData {
+            ext: SorobanTransactionDataExt::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: vec![data(ScVal::LedgerKeyContractInstance)]
+                        .try_into()
+                        .unwrap(),
+                    read_write: vec![
+                        data(ScVal::Vec(Some(
+                            vec![symbol("Stream"), ScVal::U64(4)].try_into().unwrap(),
+                        ))),
+                        LedgerKey::Account(LedgerKeyAccount {
+                            account_id: account(),
+                        }),
+                    ]
+                    .try_into()
+                    .unwrap(),
+                },
+                instructions: 2_500_000,
+                disk_read_bytes: 0,
+                write_bytes: 312,
+            },
+            resource_fee: 48_000,
+        }),
+        min_resource_fee: 48_000,
+        auth: vec![SorobanAuthorizationEntry {
+            credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+                address: ScAddress::Account(account()),
+                nonce: 0,
+                signature_expiration_ledger: 0,
+                signature: ScVal::Void,
+            }),
+            root_invocation: SorobanAuthorizedInvocation {
+                function: SorobanAuthorizedFunction::ContractFn(function),
+                sub_invocations: VecM::default(),
+            },
+        }],
+        return_value: Some(ScVal::Void),
+        events: vec![],
+        restore_preamble: None,
+        state_changes: vec![],
+    }
+}
+
+fn failure(error: &str, events: Vec<DiagnosticEvent>) -> Simulation {
+    Simulation {
+        error: Some(error.to_string()),
+        transaction_data: None,
+        min_resource_fee: 0,
+        auth: vec![],
+        return_value: None,
+        events,
+        ..success()
+    }
+}
+
+#[test]
+fn reports_the_footprint_fee_and_signers() {
+    let report = Report::new(&cancel(), &success(), streaming::ERRORS);
+    assert!(report.succeeds());
+    assert_eq!(report.fee(), 48_100);
+    assert_eq!(
+        report.resources,
+        Some(Resources {
+            instructions: 2_500_000,
+            read_bytes: 0,
+            write_bytes: 312,
+        })
+    );
+    let signer = ScAddress::Account(account()).to_string();
+    assert_eq!(
+        report.to_string(),
+        format!(
+            "cancel_stream on {CONTRACT} at ledger 1210
+  returns ()
+  fee: 48100 stroops (100 inclusion, 48000 resources)
+  resources: 2500000 instructions, 0 bytes read, 312 bytes written
+  reads persistent {CONTRACT} instance
+  writes persistent {CONTRACT} Stream(4)
+  writes account {signer}
+  needs authorization from {signer}
+"
+        )
+    );
+}
+
+#[test]
+fn names_contract_errors_from_the_spec() {
+    // The contract's own errors come from its spec
+    let simulation = failure(
+        "HostError: Error(Contract, #4)\n\nEvent log (newest first):\n   0: ...",
+        vec![],
+    );
+    let report = Report::new(&cancel(), &simulation, streaming::ERRORS);
+    assert_eq!(
+        report.to_string(),
+        format!(
+            "cancel_stream on {CONTRACT} at ledger 1210\n  fails with StreamError::StreamNotFound (4)\n"
+        )
+    );
+
+    // The shared crates' errors are known whatever the contract
+    let simulation = failure("HostError: Error(Contract, #1101)", vec![]);
+    let report = Report::new(&cancel(), &simulation, &[]);
+    assert!(matches!(
+        report.outcome,
+        Outcome::ContractError { code: 1101, error: Some(error) } if error.name == "PauseError::Paused"
+    ));
+
+    // A code the message leaves out is taken from the diagnostic events
+    let event = DiagnosticEvent {
+        in_successful_contract_call: false,
+        event: ContractEvent {
+            ext: ExtensionPoint::V0,
+            contract_id: None,
+            type_: ContractEventType::Diagnostic,
+            body: ContractEventBody::V0(ContractEventV0 {
+                topics: vec![symbol("error"), ScVal::Error(ScError::Contract(77))]
+                    .try_into()
+                    .unwrap(),
+                data: ScVal::Void,
+            }),
+        },
+    };
+    let simulation = failure("HostError: Error(WasmVm, InvalidAction)", vec![event]);
+    assert_eq!(
+        Report::new(&cancel(), &simulation, streaming::ERRORS).outcome,
+        Outcome::ContractError {
+            code: 77,
+            error: None,
+        }
+    );
+
+    let simulation = failure("HostError: Error(Budget, ExceededLimit)\n...", vec![]);
+    assert_eq!(
+        Report::new(&cancel(), &simulation, &[]).outcome,
+        Outcome::HostError("HostError: Error(Budget, ExceededLimit)".to_string())
+    );
+}
+
+#[test]
+fn only_successful_calls_are_prepared() {
+    let seed = stellar_strkey::ed25519::PrivateKey([7; 32]).to_string();
+    let signer = Signer::from_secret(&seed).unwrap();
+    let preflight = |simulation: Simulation| Preflight {
+        report: Report::new(&cancel(), &simulation, streaming::ERRORS),
+        tx: lumenpulse_tx::build(&signer, 2, cancel()),
+        simulation,
+    };
+
+    let error = preflight(failure("HostError: Error(Contract, #4)", vec![]))
+        .prepare()
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "simulation failed: fails with StreamError::StreamNotFound (4)"
+    );
+
+    let mut simulation = success();
+    simulation.auth.clear();
+    let prepared = preflight(simulation).prepare().unwrap();
+    assert_eq!(prepared.tx.fee, 48_100);
+    assert!(matches!(prepared.tx.ext, TransactionExt::V1(_)));
+}
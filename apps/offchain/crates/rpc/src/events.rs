@@ -0,0 +1,130 @@
+//! `getEvents`, paged by ledger or cursor.
+
+use crate::{from_base64, Client, Error};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use stellar_xdr::curr::ScVal;
+
+/// Contract IDs one `getEvents` filter may list
+pub const MAX_CONTRACTS_PER_FILTER: usize = 5;
+/// Filters one `getEvents` request may carry
+pub const MAX_FILTERS: usize = 5;
+
+impl Client {
+    pub fn get_events(&self, request: &EventsRequest) -> Result<EventsPage, Error> {
+        let page: RawEventsPage = self.call("getEvents", request.params()?)?;
+        page.decode()
+    }
+}
+
+/// Where a page of events starts
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Start {
+    /// The first event in a ledger, which the RPC must still retain
+    Ledger(u32),
+    /// Just after the last event of a previous page
+    Cursor(String),
+}
+
+/// A `getEvents` request for the events of some contracts
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventsRequest {
+    pub start: Start,
+    pub contract_ids: Vec<String>,
+    pub limit: u32,
+}
+
+impl EventsRequest {
+    pub fn params(&self) -> Result<Value, Error> {
+        if self.contract_ids.len() > MAX_CONTRACTS_PER_FILTER * MAX_FILTERS {
+            return Err(Error::TooManyContracts);
+        }
+        let filters: Vec<_> = self
+            .contract_ids
+            .chunks(MAX_CONTRACTS_PER_FILTER)
+            .map(|ids| json!({"type": "contract", "contractIds": ids}))
+            .collect();
+        let mut params = json!({
+            "filters": filters,
+            "pagination": {"limit": self.limit},
+            "xdrFormat": "base64",
+        });
+        // A cursor takes the place of the start ledger, the RPC refuses both
+        match &self.start {
+            Start::Ledger(ledger) => params["startLedger"] = json!(ledger),
+            Start::Cursor(cursor) => params["pagination"]["cursor"] = json!(cursor),
+        }
+        Ok(params)
+    }
+}
+
+/// A contract event as the RPC returned it
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct RpcEvent {
+    /// Unique and ordered across the network's events
+    pub id: String,
+    pub ledger: u32,
+    pub ledger_closed_at: String,
+    pub contract_id: String,
+    pub tx_hash: String,
+    pub topics: Vec<ScVal>,
+    pub value: ScVal,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventsPage {
+    pub events: Vec<RpcEvent>,
+    /// Where the next page starts. The RPC moves it past the ledgers it
+    /// searched even when none matched, so polling with it never rescans.
+    pub cursor: String,
+    pub latest_ledger: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RawEventsPage {
+    events: Vec<RawEvent>,
+    cursor: String,
+    latest_ledger: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEvent {
+    id: String,
+    ledger: u32,
+    ledger_closed_at: String,
+    contract_id: String,
+    tx_hash: String,
+    topic: Vec<String>,
+    value: String,
+}
+
+impl RawEventsPage {
+    pub(crate) fn decode(self) -> Result<EventsPage, Error> {
+        let events = self
+            .events
+            .into_iter()
+            .map(|event| {
+                Ok(RpcEvent {
+                    topics: event
+                        .topic
+                        .iter()
+                        .map(|topic| from_base64(topic))
+                        .collect::<Result<_, _>>()?,
+                    value: from_base64(&event.value)?,
+                    id: event.id,
+                    ledger: event.ledger,
+                    ledger_closed_at: event.ledger_closed_at,
+                    contract_id: event.contract_id,
+                    tx_hash: event.tx_hash,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(EventsPage {
+            events,
+            cursor: self.cursor,
+            latest_ledger: self.latest_ledger,
+        })
+    }
+}
@@ -0,0 +1,110 @@
+//! The network, its latest ledger and the entries in it.
+
+use crate::{from_base64, to_base64, Client, Error};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use stellar_xdr::curr::{AccountEntry, AccountId, LedgerEntryData, LedgerKey, LedgerKeyAccount};
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LatestLedger {
+    pub id: String,
+    pub protocol_version: u32,
+    pub sequence: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInfo {
+    pub passphrase: String,
+    pub protocol_version: u32,
+    #[serde(default)]
+    pub friendbot_url: Option<String>,
+}
+
+/// How the RPC node sees itself. `status` is `healthy` while it keeps up
+/// with the network.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Health {
+    pub status: String,
+    pub latest_ledger: u32,
+    pub oldest_ledger: u32,
+    pub ledger_retention_window: u32,
+}
+
+/// A ledger entry as it stands in the latest ledger
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LedgerEntryResult {
+    pub key: LedgerKey,
+    pub data: LedgerEntryData,
+    pub last_modified_ledger: u32,
+    /// Last ledger the entry lives to, for contract data and code
+    pub live_until_ledger: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RawLedgerEntries {
+    pub(crate) entries: Option<Vec<RawLedgerEntry>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RawLedgerEntry {
+    key: String,
+    xdr: String,
+    last_modified_ledger_seq: u32,
+    live_until_ledger_seq: Option<u32>,
+}
+
+impl RawLedgerEntry {
+    pub(crate) fn decode(&self) -> Result<LedgerEntryResult, Error> {
+        Ok(LedgerEntryResult {
+            key: from_base64(&self.key)?,
+            data: from_base64(&self.xdr)?,
+            last_modified_ledger: self.last_modified_ledger_seq,
+            live_until_ledger: self.live_until_ledger_seq,
+        })
+    }
+}
+
+impl Client {
+    pub fn get_latest_ledger(&self) -> Result<LatestLedger, Error> {
+        self.call("getLatestLedger", Value::Null)
+    }
+
+    pub fn get_health(&self) -> Result<Health, Error> {
+        self.call("getHealth", Value::Null)
+    }
+
+    pub fn get_network(&self) -> Result<NetworkInfo, Error> {
+        self.call("getNetwork", Value::Null)
+    }
+
+    /// The entries under `keys` that exist, in no particular order
+    pub fn get_ledger_entries(&self, keys: &[LedgerKey]) -> Result<Vec<LedgerEntryResult>, Error> {
+        let keys = keys.iter().map(to_base64).collect::<Result<Vec<_>, _>>()?;
+        let entries: RawLedgerEntries = self.call("getLedgerEntries", json!({"keys": keys}))?;
+        entries
+            .entries
+            .unwrap_or_default()
+            .iter()
+            .map(RawLedgerEntry::decode)
+            .collect()
+    }
+
+    pub fn get_account(&self, account_id: &AccountId) -> Result<AccountEntry, Error> {
+        let key = LedgerKey::Account(LedgerKeyAccount {
+            account_id: account_id.clone(),
+        });
+        match self
+            .get_ledger_entries(&[key])?
+            .pop()
+            .map(|entry| entry.data)
+        {
+            Some(LedgerEntryData::Account(account)) => Ok(account),
+            _ => Err(Error::NoAccount(account_id.to_string())),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Blocking client for the Soroban RPC methods Lumenpulse services use.
+//!
+//! Calls are JSON-RPC 2.0 over HTTP. XDR in requests and responses is
+//! encoded and decoded here, so callers deal in `stellar-xdr` types rather
+//! than base64 strings.
+
+mod events;
+mod ledger;
+mod transactions;
+
+pub use events::*;
+pub use ledger::*;
+pub use transactions::*;
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use stellar_xdr::curr::{Limits, ReadXdr, WriteXdr};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("request failed: {0}")]
+    Transport(String),
+    #[error("RPC error {code}: {message}")]
+    Rpc { code: i64, message: String },
+    #[error("unexpected response: {0}")]
+    Response(String),
+    #[error("invalid XDR in response: {0}")]
+    Xdr(#[from] stellar_xdr::curr::Error),
+    #[error("a request can filter on at most {} contracts", MAX_CONTRACTS_PER_FILTER * MAX_FILTERS)]
+    TooManyContracts,
+    #[error("account {0} does not exist")]
+    NoAccount(String),
+}
+
+pub struct Client {
+    url: String,
+    agent: ureq::Agent,
+    next_id: AtomicU64,
+}
+
+impl Client {
+    pub fn new(url: impl Into<String>) -> Self {
+        Client {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Call `method` with `params` and return its result
+    pub fn call<R: DeserializeOwned>(&self, method: &str, params: Value) -> Result<R, Error> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut body = json!({"jsonrpc": "2.0", "id": id, "method": method});
+        if !params.is_null() {
+            body["params"] = params;
+        }
+        // JSON-RPC errors arrive with a 200, so any other status is the
+        // transport failing
+        let response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(body)
+            .map_err(|err| Error::Transport(err.to_string()))?
+            .into_json()
+            .map_err(|err| Error::Response(err.to_string()))?;
+        result(response)
+    }
+}
+
+/// The result of a JSON-RPC response, or the error it carries
+pub fn result<R: DeserializeOwned>(mut response: Value) -> Result<R, Error> {
+    if let Some(error) = response.get("error") {
+        return Err(Error::Rpc {
+            code: error["code"].as_i64().unwrap_or_default(),
+            message: error["message"].as_str().unwrap_or_default().to_string(),
+        });
+    }
+    let result = response
+        .get_mut("result")
+        .map(Value::take)
+        .ok_or_else(|| Error::Response("no result".to_string()))?;
+    serde_json::from_value(result).map_err(|err| Error::Response(err.to_string()))
+}
+
+pub(crate) fn to_base64(value: &impl WriteXdr) -> Result<String, Error> {
+    Ok(value.to_xdr_base64(Limits::none())?)
+}
+
+pub(crate) fn from_base64<T: ReadXdr>(value: &str) -> Result<T, Error> {
+    Ok(T::from_xdr_base64(value, Limits::none())?)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,261 @@
+use crate::{
+    result, Error, EventsRequest, Health, LatestLedger, RawEventsPage, RawSendResult,
+    RawSimulation, RawTransactionInfo, SendStatus, Start, TransactionStatus,
+};
+use serde_json::json;
+use stellar_xdr::curr::{
+    ContractDataDurability, ContractDataEntry, ContractId, ExtensionPoint, Hash, LedgerEntry,
+    LedgerEntryChanges, LedgerEntryData, LedgerEntryExt, LedgerFootprint, LedgerKey,
+    LedgerKeyContractData, Limits, ScAddress, ScSymbol, ScVal, SorobanResources,
+    SorobanTransactionData, SorobanTransactionDataExt, SorobanTransactionMetaExt,
+    SorobanTransactionMetaV2, TransactionMeta, TransactionMetaV4, VecM, WriteXdr,
+};
+
+fn base64(val: &ScVal) -> String {
+    val.to_xdr_base64(Limits::none()).unwrap()
+}
+
+#[test]
+fn test_events_request_params() {
+    let ids: Vec<_> = (0..7).map(|n| format!("C{n}")).collect();
+    let request = EventsRequest {
+        start: Start::Ledger(1_000),
+        contract_ids: ids.clone(),
+        limit: 100,
+    };
+    // Contract IDs are split across filters, five to a filter
+    assert_eq!(
+        request.params().unwrap(),
+        json!({
+            "startLedger": 1_000,
+            "filters": [
+                {"type": "contract", "contractIds": ["C0", "C1", "C2", "C3", "C4"]},
+                {"type": "contract", "contractIds": ["C5", "C6"]},
+            ],
+            "pagination": {"limit": 100},
+            "xdrFormat": "base64",
+        })
+    );
+
+    let request = EventsRequest {
+        start: Start::Cursor("0004-0001".to_string()),
+        ..request
+    };
+    let params = request.params().unwrap();
+    assert_eq!(params.get("startLedger"), None);
+    assert_eq!(params["pagination"]["cursor"], "0004-0001");
+
+    let request = EventsRequest {
+        contract_ids: (0..26).map(|n| format!("C{n}")).collect(),
+        ..request
+    };
+    assert!(matches!(request.params(), Err(Error::TooManyContracts)));
+}
+
+#[test]
+fn test_parses_responses() {
+    let name = ScVal::Symbol(ScSymbol("withdrawn_event".try_into().unwrap()));
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": {
+            "events": [{
+                "type": "contract",
+                "ledger": 1_204,
+                "ledgerClosedAt": "2026-01-05T10:00:00Z",
+                "contractId": "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAK3IM",
+                "id": "0005171140464640000-0000000001",
+                "operationIndex": 0,
+                "transactionIndex": 1,
+                "txHash": "ab01",
+                "topic": [base64(&name), base64(&ScVal::U64(4))],
+                "value": base64(&ScVal::Void),
+            }],
+            "latestLedger": 1_210,
+            "oldestLedger": 10,
+            "cursor": "0005171140464640000-0000000001",
+        }
+    });
+    let page = result::<RawEventsPage>(response).unwrap().decode().unwrap();
+    assert_eq!(page.latest_ledger, 1_210);
+    assert_eq!(page.cursor, "0005171140464640000-0000000001");
+    assert_eq!(page.events.len(), 1);
+    assert_eq!(page.events[0].topics, vec![name, ScVal::U64(4)]);
+    assert_eq!(page.events[0].value, ScVal::Void);
+    assert_eq!(page.events[0].ledger, 1_204);
+
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "result": {"id": "ff", "protocolVersion": 23, "sequence": 1_210},
+    });
+    assert_eq!(
+        result::<LatestLedger>(response).unwrap(),
+        LatestLedger {
+            id: "ff".to_string(),
+            protocol_version: 23,
+            sequence: 1_210,
+        }
+    );
+
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "result": {
+            "status": "healthy",
+            "latestLedger": 1_210,
+            "oldestLedger": 10,
+            "ledgerRetentionWindow": 1_200,
+        },
+    });
+    assert_eq!(
+        result::<Health>(response).unwrap(),
+        Health {
+            status: "healthy".to_string(),
+            latest_ledger: 1_210,
+            oldest_ledger: 10,
+            ledger_retention_window: 1_200,
+        }
+    );
+
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "error": {"code": -32600, "message": "startLedger must be within the ledger range"},
+    });
+    assert!(matches!(
+        result::<LatestLedger>(response),
+        Err(Error::Rpc { code: -32600, .. })
+    ));
+}
+
+#[test]
+fn test_parses_transaction_responses() {
+    let data = SorobanTransactionData {
+        ext: SorobanTransactionDataExt::V0,
+        resources: SorobanResources {
+            footprint: LedgerFootprint {
+                read_only: VecM::default(),
+                read_write: VecM::default(),
+            },
+            instructions: 2_000_000,
+            disk_read_bytes: 0,
+            write_bytes: 120,
+        },
+        resource_fee: 61_000,
+    };
+    let data = data.to_xdr_base64(Limits::none()).unwrap();
+    let contract = ScAddress::Contract(ContractId(Hash([1; 32])));
+    let key = LedgerKey::ContractData(LedgerKeyContractData {
+        contract: contract.clone(),
+        key: ScVal::U32(7),
+        durability: ContractDataDurability::Persistent,
+    });
+    let created = LedgerEntry {
+        last_modified_ledger_seq: 1_211,
+        data: LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract,
+            key: ScVal::U32(7),
+            durability: ContractDataDurability::Persistent,
+            val: ScVal::U64(172_800),
+        }),
+        ext: LedgerEntryExt::V0,
+    };
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": {
+            "latestLedger": 1_210,
+            "minResourceFee": "61000",
+            "transactionData": data,
+            "results": [{"auth": [], "xdr": base64(&ScVal::U64(172_800))}],
+            "stateChanges": [{
+                "type": "created",
+                "key": key.to_xdr_base64(Limits::none()).unwrap(),
+                "before": null,
+                "after": created.to_xdr_base64(Limits::none()).unwrap(),
+            }],
+        }
+    });
+    let simulation = result::<RawSimulation>(response).unwrap().decode().unwrap();
+    assert_eq!(simulation.error, None);
+    assert_eq!(simulation.min_resource_fee, 61_000);
+    assert_eq!(simulation.return_value, Some(ScVal::U64(172_800)));
+    assert_eq!(
+        simulation.transaction_data.unwrap().resources.write_bytes,
+        120
+    );
+    assert_eq!(simulation.state_changes.len(), 1);
+    assert_eq!(simulation.state_changes[0].key, key);
+    assert_eq!(simulation.state_changes[0].before, None);
+    assert_eq!(simulation.state_changes[0].after, Some(created));
+
+    // A failed simulation carries only the error
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "result": {"latestLedger": 1_210, "error": "HostError: Error(Contract, #1100)"},
+    });
+    let simulation = result::<RawSimulation>(response).unwrap().decode().unwrap();
+    assert!(simulation.error.unwrap().contains("#1100"));
+    assert_eq!(simulation.transaction_data, None);
+    assert!(simulation.state_changes.is_empty());
+
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "result": {
+            "status": "healthy",
+            "latestLedger": 1_210,
+            "oldestLedger": 10,
+            "ledgerRetentionWindow": 1_200,
+        },
+    });
+    assert_eq!(
+        result::<Health>(response).unwrap(),
+        Health {
+            status: "healthy".to_string(),
+            latest_ledger: 1_210,
+            oldest_ledger: 10,
+            ledger_retention_window: 1_200,
+        }
+    );
+
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "result": {"status": "TRY_AGAIN_LATER", "hash": "ab", "latestLedger": 1_210},
+    });
+    let sent = result::<RawSendResult>(response).unwrap().decode().unwrap();
+    assert_eq!(sent.status, SendStatus::TryAgainLater);
+
+    let meta = TransactionMeta::V4(TransactionMetaV4 {
+        ext: ExtensionPoint::V0,
+        tx_changes_before: LedgerEntryChanges::default(),
+        operations: VecM::default(),
+        tx_changes_after: LedgerEntryChanges::default(),
+        soroban_meta: Some(SorobanTransactionMetaV2 {
+            ext: SorobanTransactionMetaExt::V0,
+            return_value: Some(ScVal::U32(2)),
+        }),
+        events: VecM::default(),
+        diagnostic_events: VecM::default(),
+    });
+    let response = json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "result": {
+            "status": "SUCCESS",
+            "latestLedger": 1_212,
+            "ledger": 1_211,
+            "resultMetaXdr": meta.to_xdr_base64(Limits::none()).unwrap(),
+        },
+    });
+    let info = result::<RawTransactionInfo>(response)
+        .unwrap()
+        .decode()
+        .unwrap();
+    assert_eq!(info.status, TransactionStatus::Success);
+    assert_eq!(info.return_value(), Some(&ScVal::U32(2)));
+}
@@ -0,0 +1,262 @@
+//! Simulating, sending and looking up transactions.
+
+use crate::{from_base64, to_base64, Client, Error};
+use serde::Deserialize;
+use serde_json::json;
+use stellar_xdr::curr::{
+    DiagnosticEvent, LedgerEntry, LedgerKey, ScVal, SorobanAuthorizationEntry,
+    SorobanTransactionData, TransactionEnvelope, TransactionMeta, TransactionResult,
+};
+
+/// What `simulateTransaction` found running a transaction against the
+/// latest ledger
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Simulation {
+    pub latest_ledger: u32,
+    /// Why the transaction would fail, if it would
+    pub error: Option<String>,
+    /// Footprint and resources to attach to the transaction
+    pub transaction_data: Option<SorobanTransactionData>,
+    pub min_resource_fee: i64,
+    /// Authorizations the invocation needs, to attach to its operation
+    pub auth: Vec<SorobanAuthorizationEntry>,
+    pub return_value: Option<ScVal>,
+    pub events: Vec<DiagnosticEvent>,
+    /// Set when entries the transaction reads are archived and have to be
+    /// restored first
+    pub restore_preamble: Option<RestorePreamble>,
+    /// Entries the transaction would create, update or delete
+    pub state_changes: Vec<StateChange>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RestorePreamble {
+    pub transaction_data: SorobanTransactionData,
+    pub min_resource_fee: i64,
+}
+
+/// An entry as it stood before a simulated transaction and after it; none
+/// on the side where it does not exist
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateChange {
+    pub key: LedgerKey,
+    pub before: Option<LedgerEntry>,
+    pub after: Option<LedgerEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RawSimulation {
+    latest_ledger: u32,
+    error: Option<String>,
+    transaction_data: Option<String>,
+    min_resource_fee: Option<String>,
+    #[serde(default)]
+    results: Vec<RawSimulationResult>,
+    #[serde(default)]
+    events: Vec<String>,
+    restore_preamble: Option<RawRestorePreamble>,
+    #[serde(default)]
+    state_changes: Vec<RawStateChange>,
+}
+
+#[derive(Deserialize)]
+struct RawStateChange {
+    key: String,
+    before: Option<String>,
+    after: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawSimulationResult {
+    #[serde(default)]
+    auth: Vec<String>,
+    xdr: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRestorePreamble {
+    transaction_data: String,
+    min_resource_fee: String,
+}
+
+fn fee(fee: &str) -> Result<i64, Error> {
+    fee.parse()
+        .map_err(|_| Error::Response(format!("fee `{fee}` is not a number")))
+}
+
+impl RawSimulation {
+    pub(crate) fn decode(self) -> Result<Simulation, Error> {
+        let result = self.results.into_iter().next();
+        Ok(Simulation {
+            latest_ledger: self.latest_ledger,
+            error: self.error,
+            transaction_data: self
+                .transaction_data
+                .filter(|data| !data.is_empty())
+                .map(|data| from_base64(&data))
+                .transpose()?,
+            min_resource_fee: self
+                .min_resource_fee
+                .as_deref()
+                .map(fee)
+                .transpose()?
+                .unwrap_or(0),
+            auth: match &result {
+                Some(result) => result
+                    .auth
+                    .iter()
+                    .map(|auth| from_base64(auth))
+                    .collect::<Result<_, _>>()?,
+                None => Vec::new(),
+            },
+            return_value: result.map(|result| from_base64(&result.xdr)).transpose()?,
+            events: self
+                .events
+                .iter()
+                .map(|event| from_base64(event))
+                .collect::<Result<_, _>>()?,
+            restore_preamble: self
+                .restore_preamble
+                .map(|preamble| {
+                    Ok::<_, Error>(RestorePreamble {
+                        transaction_data: from_base64(&preamble.transaction_data)?,
+                        min_resource_fee: fee(&preamble.min_resource_fee)?,
+                    })
+                })
+                .transpose()?,
+            state_changes: self
+                .state_changes
+                .iter()
+                .map(|change| {
+                    Ok::<_, Error>(StateChange {
+                        key: from_base64(&change.key)?,
+                        before: change.before.as_deref().map(from_base64).transpose()?,
+                        after: change.after.as_deref().map(from_base64).transpose()?,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SendStatus {
+    Pending,
+    Duplicate,
+    TryAgainLater,
+    Error,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SendResult {
+    pub status: SendStatus,
+    pub hash: String,
+    pub latest_ledger: u32,
+    /// Why the transaction was rejected, for `SendStatus::Error`
+    pub error_result: Option<TransactionResult>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RawSendResult {
+    status: SendStatus,
+    hash: String,
+    latest_ledger: u32,
+    error_result_xdr: Option<String>,
+}
+
+impl RawSendResult {
+    pub(crate) fn decode(self) -> Result<SendResult, Error> {
+        Ok(SendResult {
+            status: self.status,
+            hash: self.hash,
+            latest_ledger: self.latest_ledger,
+            error_result: self
+                .error_result_xdr
+                .map(|result| from_base64(&result))
+                .transpose()?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionStatus {
+    Success,
+    Failed,
+    /// Not in a ledger yet, or too old for the RPC to remember
+    NotFound,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionInfo {
+    pub status: TransactionStatus,
+    pub latest_ledger: u32,
+    pub ledger: Option<u32>,
+    pub result: Option<TransactionResult>,
+    pub meta: Option<TransactionMeta>,
+}
+
+impl TransactionInfo {
+    /// Value the transaction's contract invocation returned
+    pub fn return_value(&self) -> Option<&ScVal> {
+        match self.meta.as_ref()? {
+            TransactionMeta::V3(meta) => meta.soroban_meta.as_ref().map(|meta| &meta.return_value),
+            TransactionMeta::V4(meta) => meta.soroban_meta.as_ref()?.return_value.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RawTransactionInfo {
+    status: TransactionStatus,
+    latest_ledger: u32,
+    ledger: Option<u32>,
+    result_xdr: Option<String>,
+    result_meta_xdr: Option<String>,
+}
+
+impl RawTransactionInfo {
+    pub(crate) fn decode(self) -> Result<TransactionInfo, Error> {
+        Ok(TransactionInfo {
+            status: self.status,
+            latest_ledger: self.latest_ledger,
+            ledger: self.ledger,
+            result: self
+                .result_xdr
+                .map(|result| from_base64(&result))
+                .transpose()?,
+            meta: self
+                .result_meta_xdr
+                .map(|meta| from_base64(&meta))
+                .transpose()?,
+        })
+    }
+}
+
+impl Client {
+    pub fn simulate_transaction(
+        &self,
+        envelope: &TransactionEnvelope,
+    ) -> Result<Simulation, Error> {
+        let params = json!({"transaction": to_base64(envelope)?});
+        self.call::<RawSimulation>("simulateTransaction", params)?
+            .decode()
+    }
+
+    pub fn send_transaction(&self, envelope: &TransactionEnvelope) -> Result<SendResult, Error> {
+        let params = json!({"transaction": to_base64(envelope)?});
+        self.call::<RawSendResult>("sendTransaction", params)?
+            .decode()
+    }
+
+    pub fn get_transaction(&self, hash: &str) -> Result<TransactionInfo, Error> {
+        self.call::<RawTransactionInfo>("getTransaction", json!({"hash": hash}))?
+            .decode()
+    }
+}
@@ -0,0 +1,265 @@
+//! What changed in a contract's storage between two snapshots.
+
+use crate::snapshot::{Entry, Snapshot, Storage};
+use crate::types::typed;
+use crate::Error;
+use lumenpulse_events::Contract;
+use lumenpulse_preflight::render_value;
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt};
+use stellar_xdr::curr::ScVal;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One entry that is not the same in both snapshots
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Change {
+    pub kind: Kind,
+    pub storage: Storage,
+    /// The key as the contract declares it, such as `Stream(4)`
+    pub key: String,
+    /// Key type the key decodes as, such as `DataKey`; none for a key no
+    /// type of the contract describes
+    pub key_type: Option<&'static str>,
+    /// Type stored under the key, such as `StreamData`
+    pub value_type: Option<&'static str>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// The fields that changed, when the value is a struct on both sides
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldChange>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// An entry in the later snapshot whose value is not of the type the
+/// contract stores under its key
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Undecodable {
+    pub storage: Storage,
+    pub key: String,
+    pub value_type: &'static str,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Diff {
+    pub contract_id: String,
+    pub before_ledger: u32,
+    pub after_ledger: u32,
+    pub before_wasm_hash: Option<String>,
+    pub after_wasm_hash: Option<String>,
+    pub changes: Vec<Change>,
+    /// Only checked when the contract's kind is known
+    pub undecodable: Vec<Undecodable>,
+}
+
+/// Compare two snapshots of the same contract, typing entries with the
+/// types of `contract` if given
+pub fn diff(
+    before: &Snapshot,
+    after: &Snapshot,
+    contract: Option<Contract>,
+) -> Result<Diff, Error> {
+    if before.contract_id != after.contract_id {
+        return Err(Error::Mismatch {
+            before: before.contract_id.clone(),
+            after: after.contract_id.clone(),
+        });
+    }
+    let by_key = |snapshot: &Snapshot| -> BTreeMap<(Storage, ScVal), ScVal> {
+        snapshot
+            .entries
+            .iter()
+            .map(|entry| ((entry.storage, entry.key.clone()), entry.val.clone()))
+            .collect()
+    };
+    let (old, new) = (by_key(before), by_key(after));
+
+    let mut changes = Vec::new();
+    for ((storage, key), old_val) in &old {
+        let new_val = new.get(&(*storage, key.clone()));
+        if new_val == Some(old_val) {
+            continue;
+        }
+        let kind = match new_val {
+            Some(_) => Kind::Changed,
+            None => Kind::Removed,
+        };
+        changes.push(change(
+            kind,
+            *storage,
+            key,
+            Some(old_val),
+            new_val,
+            contract,
+        ));
+    }
+    for ((storage, key), new_val) in &new {
+        if !old.contains_key(&(*storage, key.clone())) {
+            changes.push(change(
+                Kind::Added,
+                *storage,
+                key,
+                None,
+                Some(new_val),
+                contract,
+            ));
+        }
+    }
+    changes.sort_by(|a, b| (a.storage, &a.key).cmp(&(b.storage, &b.key)));
+
+    let undecodable = after
+        .entries
+        .iter()
+        .filter_map(
+            |Entry {
+                 storage, key, val, ..
+             }| {
+                let typed = typed(contract?, key, val)?;
+                (!typed.decodes).then(|| Undecodable {
+                    storage: *storage,
+                    key: render_value(key),
+                    value_type: typed.value_type,
+                })
+            },
+        )
+        .collect();
+
+    Ok(Diff {
+        contract_id: after.contract_id.clone(),
+        before_ledger: before.ledger,
+        after_ledger: after.ledger,
+        before_wasm_hash: before.wasm_hash.clone(),
+        after_wasm_hash: after.wasm_hash.clone(),
+        changes,
+        undecodable,
+    })
+}
+
+fn change(
+    kind: Kind,
+    storage: Storage,
+    key: &ScVal,
+    before: Option<&ScVal>,
+    after: Option<&ScVal>,
+    contract: Option<Contract>,
+) -> Change {
+    let typed = contract.and_then(|contract| {
+        let val = after.or(before).expect("one side has a value");
+        typed(contract, key, val)
+    });
+    Change {
+        kind,
+        storage,
+        key: render_value(key),
+        key_type: typed.map(|typed| typed.key_type),
+        value_type: typed.map(|typed| typed.value_type),
+        before: before.map(render_value),
+        after: after.map(render_value),
+        fields: match (before, after) {
+            (Some(ScVal::Map(Some(before))), Some(ScVal::Map(Some(after)))) => {
+                let fields = |map: &stellar_xdr::curr::ScMap| -> BTreeMap<String, String> {
+                    map.iter()
+                        .map(|entry| (render_value(&entry.key), render_value(&entry.val)))
+                        .collect()
+                };
+                let (before, after) = (fields(before), fields(after));
+                let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+                names.sort();
+                names.dedup();
+                names
+                    .into_iter()
+                    .filter(|name| before.get(*name) != after.get(*name))
+                    .map(|name| FieldChange {
+                        field: name.clone(),
+                        before: before.get(name).cloned(),
+                        after: after.get(name).cloned(),
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        },
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} from ledger {} to {}",
+            self.contract_id, self.before_ledger, self.after_ledger
+        )?;
+        if self.before_wasm_hash != self.after_wasm_hash {
+            let hash = |hash: &Option<String>| hash.clone().unwrap_or_else(|| "none".to_string());
+            writeln!(
+                f,
+                "  code {} -> {}",
+                hash(&self.before_wasm_hash),
+                hash(&self.after_wasm_hash)
+            )?;
+        }
+        if self.changes.is_empty() {
+            writeln!(f, "  no entries changed")?;
+        }
+        for change in &self.changes {
+            let storage = match change.storage {
+                Storage::Instance => "instance",
+                Storage::Persistent => "persistent",
+                Storage::Temporary => "temporary",
+            };
+            let key = match change.key_type {
+                Some(key_type) => format!("{storage} {key_type}::{}", change.key),
+                None => format!("{storage} {}", change.key),
+            };
+            let none = String::new();
+            match change.kind {
+                Kind::Added => {
+                    writeln!(f, "  + {key} = {}", change.after.as_ref().unwrap_or(&none))?
+                }
+                Kind::Removed => {
+                    writeln!(f, "  - {key} = {}", change.before.as_ref().unwrap_or(&none))?
+                }
+                Kind::Changed if change.fields.is_empty() => writeln!(
+                    f,
+                    "  ~ {key}: {} -> {}",
+                    change.before.as_ref().unwrap_or(&none),
+                    change.after.as_ref().unwrap_or(&none)
+                )?,
+                Kind::Changed => {
+                    writeln!(f, "  ~ {key}")?;
+                    for field in &change.fields {
+                        let side = |side: &Option<String>| {
+                            side.clone().unwrap_or_else(|| "(none)".to_string())
+                        };
+                        writeln!(
+                            f,
+                            "      {}: {} -> {}",
+                            field.field,
+                            side(&field.before),
+                            side(&field.after)
+                        )?;
+                    }
+                }
+            }
+        }
+        for entry in &self.undecodable {
+            writeln!(
+                f,
+                "  ! {} does not decode as {}",
+                entry.key, entry.value_type
+            )?;
+        }
+        Ok(())
+    }
+}
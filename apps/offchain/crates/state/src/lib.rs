@@ -0,0 +1,79 @@
+//! Snapshots of a contract's storage, and typed diffs between them.
+//!
+//! A snapshot is taken before an upgrade or migration and another after,
+//! and the diff between them lists the entries added, removed and changed,
+//! each read with the contract's own types. An entry the later snapshot
+//! holds that no longer decodes as the type stored under its key is
+//! flagged, as that is what a missed migration looks like.
+//!
+//! The RPC cannot list a contract's storage, so beyond the instance
+//! storage, which is always read whole, a snapshot reads the keys it
+//! is given: those of a previous snapshot, and those the metadata of
+//! given transactions records the contract touching, such as the
+//! transactions of the contract's events in the indexer's database. The
+//! RPC only keeps transactions for its retention window, so a snapshot
+//! only covers an entry last touched earlier than that if a previous
+//! snapshot had it.
+
+pub mod diff;
+pub mod snapshot;
+pub mod types;
+
+pub use diff::{diff, Change, Diff, FieldChange, Kind, Undecodable};
+pub use snapshot::{take, touched, Entry, Snapshot, Storage};
+
+use lumenpulse_rpc::{Client, LedgerEntryResult, TransactionInfo};
+use rusqlite::{params, Connection};
+use stellar_xdr::curr::LedgerKey;
+
+#[cfg(test)]
+mod test;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(#[from] lumenpulse_rpc::Error),
+    #[error(transparent)]
+    Store(#[from] rusqlite::Error),
+    #[error("no contract is deployed at {0}")]
+    NoContract(String),
+    #[error("{0} is not a contract address")]
+    NotAContract(String),
+    #[error("the snapshots are of different contracts, {before} and {after}")]
+    Mismatch { before: String, after: String },
+}
+
+/// The ledger snapshots are read from, through an RPC in production
+pub trait Ledger {
+    fn latest_ledger(&self) -> Result<u32, lumenpulse_rpc::Error>;
+    /// Those of the entries with `keys` that exist
+    fn entries(&self, keys: &[LedgerKey]) -> Result<Vec<LedgerEntryResult>, lumenpulse_rpc::Error>;
+    fn transaction(&self, hash: &str) -> Result<TransactionInfo, lumenpulse_rpc::Error>;
+}
+
+impl Ledger for Client {
+    fn latest_ledger(&self) -> Result<u32, lumenpulse_rpc::Error> {
+        Ok(self.get_latest_ledger()?.sequence)
+    }
+
+    fn entries(&self, keys: &[LedgerKey]) -> Result<Vec<LedgerEntryResult>, lumenpulse_rpc::Error> {
+        self.get_ledger_entries(keys)
+    }
+
+    fn transaction(&self, hash: &str) -> Result<TransactionInfo, lumenpulse_rpc::Error> {
+        self.get_transaction(hash)
+    }
+}
+
+/// Hashes of the transactions that emitted the events of `contract_id` in
+/// an indexer database, latest first
+pub fn indexed_transactions(conn: &Connection, contract_id: &str) -> Result<Vec<String>, Error> {
+    let mut statement = conn.prepare(
+        "SELECT tx_hash FROM events WHERE contract_id = ?1
+         GROUP BY tx_hash ORDER BY MAX(ledger) DESC",
+    )?;
+    let hashes = statement
+        .query_map(params![contract_id], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    Ok(hashes)
+}
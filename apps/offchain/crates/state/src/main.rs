@@ -0,0 +1,168 @@
+use clap::{Parser, Subcommand};
+use lumenpulse_deployer::{lock::DEFAULT_LOCKFILE, Lockfile};
+use lumenpulse_events::Contract;
+use lumenpulse_rpc::Client;
+use lumenpulse_state::{Snapshot, Storage};
+use rusqlite::{Connection, OpenFlags};
+use std::{path::PathBuf, process::ExitCode};
+
+/// Snapshot a contract's storage before and after an upgrade, and diff
+/// the snapshots
+#[derive(Debug, Parser)]
+#[command(name = "lumenpulse-state")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Write a snapshot of the contract's storage to stdout as JSON
+    Snapshot {
+        /// Contract address, or the name of a contract in the lockfile
+        #[arg(long)]
+        contract: String,
+        /// Earlier snapshot whose entries to read again
+        #[arg(long)]
+        previous: Option<PathBuf>,
+        /// Transaction whose touched entries to read, repeatable
+        #[arg(long = "tx")]
+        transactions: Vec<String>,
+        /// Indexer database whose transactions for the contract to read the
+        /// touched entries of
+        #[arg(long)]
+        events: Option<String>,
+        /// testnet, futurenet, mainnet or standalone
+        #[arg(long, env = "LUMENPULSE_NETWORK", default_value = "testnet")]
+        network: String,
+        /// RPC to use instead of the network's public one
+        #[arg(long, env = "LUMENPULSE_RPC_URL")]
+        rpc_url: Option<String>,
+        #[arg(long, env = "LUMENPULSE_LOCKFILE", default_value = DEFAULT_LOCKFILE)]
+        lockfile: String,
+    },
+    /// Print what changed between two snapshots
+    Diff {
+        before: PathBuf,
+        after: PathBuf,
+        /// streaming, escrow or subscriptions, to read entries with that
+        /// contract's types
+        #[arg(long)]
+        kind: Option<String>,
+        /// Print the diff as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("lumenpulse-state: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn read(path: &PathBuf) -> Result<Snapshot, Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+    Ok(serde_json::from_str(&json)
+        .map_err(|err| format!("invalid snapshot {}: {err}", path.display()))?)
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    match cli.command {
+        Command::Snapshot {
+            contract,
+            previous,
+            transactions,
+            events,
+            network,
+            rpc_url,
+            lockfile,
+        } => {
+            let contract = match lumenpulse_tx::contract_address(&contract) {
+                Some(_) => contract,
+                None => Lockfile::read(&lockfile)?
+                    .contract_id(&network, &contract)
+                    .ok_or_else(|| {
+                        format!("`{contract}` is neither a contract address nor a contract the lockfile has for {network}")
+                    })?
+                    .to_string(),
+            };
+            let address = lumenpulse_tx::contract_address(&contract)
+                .ok_or_else(|| lumenpulse_state::Error::NotAContract(contract.clone()))?;
+            let rpc_url = rpc_url
+                .or_else(|| {
+                    lumenpulse_tx::network::preset(&network)
+                        .and_then(|preset| preset.rpc_url)
+                        .map(String::from)
+                })
+                .ok_or_else(|| format!("--rpc-url is needed for {network}"))?;
+            let client = Client::new(rpc_url);
+
+            let mut keys = match &previous {
+                Some(path) => read(path)?.keys()?,
+                None => Default::default(),
+            };
+            let mut hashes = transactions;
+            if let Some(path) = &events {
+                let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+                hashes.extend(lumenpulse_state::indexed_transactions(&conn, &contract)?);
+            }
+            let (touched, missing) = lumenpulse_state::touched(&client, &address, &hashes)?;
+            if missing > 0 {
+                eprintln!(
+                    "{missing} of {} transactions are past the RPC's retention; entries only they touched are not read",
+                    hashes.len()
+                );
+            }
+            keys.extend(touched);
+
+            let snapshot = lumenpulse_state::take(&client, &address, &keys)?;
+            let stored = snapshot
+                .entries
+                .iter()
+                .filter(|entry| entry.storage != Storage::Instance)
+                .count();
+            eprintln!(
+                "{contract} at ledger {}: {} instance entries, {stored} of {} other keys exist",
+                snapshot.ledger,
+                snapshot.entries.len() - stored,
+                keys.len()
+            );
+            println!("{}", serde_json::to_string_pretty(&snapshot)?);
+        }
+        Command::Diff {
+            before,
+            after,
+            kind,
+            json,
+        } => {
+            let contract = kind
+                .map(|kind| {
+                    Contract::ALL
+                        .into_iter()
+                        .find(|contract| contract.as_str() == kind)
+                        .ok_or_else(|| format!("unknown contract kind {kind}"))
+                })
+                .transpose()?;
+            let diff = lumenpulse_state::diff(&read(&before)?, &read(&after)?, contract)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&diff)?);
+            } else {
+                print!("{diff}");
+            }
+            if !diff.undecodable.is_empty() {
+                return Err(format!(
+                    "{} entries do not decode with the contract's types",
+                    diff.undecodable.len()
+                )
+                .into());
+            }
+        }
+    }
+    Ok(())
+}
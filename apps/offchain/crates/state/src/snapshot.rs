@@ -0,0 +1,189 @@
+//! A contract's storage as it stood in one ledger.
+
+use crate::{Error, Ledger};
+use lumenpulse_rpc::TransactionStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use stellar_xdr::curr::{
+    ContractDataDurability, ContractExecutable, LedgerEntryChange, LedgerEntryData, LedgerKey,
+    LedgerKeyContractData, ScAddress, ScVal, TransactionMeta,
+};
+
+/// Most keys `getLedgerEntries` takes at once
+const MAX_KEYS: usize = 200;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Storage {
+    /// Kept in the contract instance, and living as long as it does
+    Instance,
+    Persistent,
+    Temporary,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Entry {
+    pub storage: Storage,
+    pub key: ScVal,
+    pub val: ScVal,
+    /// Last ledger a persistent or temporary entry lives to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub live_until_ledger: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Snapshot {
+    /// Contract address (`C...`)
+    pub contract_id: String,
+    /// Latest ledger when the snapshot was taken
+    pub ledger: u32,
+    /// Hex hash of the WASM the contract ran, none for a built-in contract
+    pub wasm_hash: Option<String>,
+    /// Sorted by storage, then key
+    pub entries: Vec<Entry>,
+}
+
+impl Snapshot {
+    /// Keys of the persistent and temporary entries, for a later snapshot
+    /// to read again
+    pub fn keys(&self) -> Result<BTreeSet<LedgerKey>, Error> {
+        let contract = self
+            .contract_id
+            .parse()
+            .map_err(|_| Error::NotAContract(self.contract_id.clone()))?;
+        Ok(self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let durability = match entry.storage {
+                    Storage::Instance => return None,
+                    Storage::Persistent => ContractDataDurability::Persistent,
+                    Storage::Temporary => ContractDataDurability::Temporary,
+                };
+                Some(data_key(&contract, entry.key.clone(), durability))
+            })
+            .collect())
+    }
+}
+
+fn data_key(contract: &ScAddress, key: ScVal, durability: ContractDataDurability) -> LedgerKey {
+    LedgerKey::ContractData(LedgerKeyContractData {
+        contract: contract.clone(),
+        key,
+        durability,
+    })
+}
+
+/// Keys of the entries of `contract` that the transactions with `hashes`
+/// created, changed, restored or removed, as their metadata records it.
+/// Also returns how many of the transactions the RPC no longer has.
+pub fn touched(
+    ledger: &impl Ledger,
+    contract: &ScAddress,
+    hashes: &[String],
+) -> Result<(BTreeSet<LedgerKey>, usize), Error> {
+    let mut keys = BTreeSet::new();
+    let mut missing = 0;
+    for hash in hashes {
+        let info = ledger.transaction(hash)?;
+        if info.status == TransactionStatus::NotFound {
+            missing += 1;
+            continue;
+        }
+        let changes: Vec<&LedgerEntryChange> = match &info.meta {
+            Some(TransactionMeta::V3(meta)) => meta
+                .operations
+                .iter()
+                .flat_map(|operation| operation.changes.iter())
+                .collect(),
+            Some(TransactionMeta::V4(meta)) => meta
+                .operations
+                .iter()
+                .flat_map(|operation| operation.changes.iter())
+                .collect(),
+            _ => Vec::new(),
+        };
+        for change in changes {
+            let key = match change {
+                LedgerEntryChange::Created(entry)
+                | LedgerEntryChange::Updated(entry)
+                | LedgerEntryChange::State(entry)
+                | LedgerEntryChange::Restored(entry) => entry.to_key(),
+                LedgerEntryChange::Removed(key) => key.clone(),
+            };
+            if let LedgerKey::ContractData(data) = &key {
+                // The instance is always read
+                if &data.contract == contract && data.key != ScVal::LedgerKeyContractInstance {
+                    keys.insert(key);
+                }
+            }
+        }
+    }
+    Ok((keys, missing))
+}
+
+/// Read the instance of `contract`, the storage in it, and those of `keys`
+/// that exist
+pub fn take(
+    ledger: &impl Ledger,
+    contract: &ScAddress,
+    keys: &BTreeSet<LedgerKey>,
+) -> Result<Snapshot, Error> {
+    let latest = ledger.latest_ledger()?;
+    let instance_key = data_key(
+        contract,
+        ScVal::LedgerKeyContractInstance,
+        ContractDataDurability::Persistent,
+    );
+    let instance = ledger
+        .entries(&[instance_key])?
+        .pop()
+        .and_then(|entry| match entry.data {
+            LedgerEntryData::ContractData(data) => match data.val {
+                ScVal::ContractInstance(instance) => Some(instance),
+                _ => None,
+            },
+            _ => None,
+        })
+        .ok_or_else(|| Error::NoContract(contract.to_string()))?;
+
+    let wasm_hash = match &instance.executable {
+        ContractExecutable::Wasm(hash) => Some(hash.to_string()),
+        ContractExecutable::StellarAsset => None,
+    };
+    let mut entries: Vec<Entry> = instance
+        .storage
+        .iter()
+        .flat_map(|storage| storage.iter())
+        .map(|entry| Entry {
+            storage: Storage::Instance,
+            key: entry.key.clone(),
+            val: entry.val.clone(),
+            live_until_ledger: None,
+        })
+        .collect();
+    let keys: Vec<LedgerKey> = keys.iter().cloned().collect();
+    for chunk in keys.chunks(MAX_KEYS) {
+        for entry in ledger.entries(chunk)? {
+            if let LedgerEntryData::ContractData(data) = entry.data {
+                let storage = match data.durability {
+                    ContractDataDurability::Persistent => Storage::Persistent,
+                    ContractDataDurability::Temporary => Storage::Temporary,
+                };
+                entries.push(Entry {
+                    storage,
+                    key: data.key,
+                    val: data.val,
+                    live_until_ledger: entry.live_until_ledger,
+                });
+            }
+        }
+    }
+    entries.sort_by(|a, b| (a.storage, &a.key).cmp(&(b.storage, &b.key)));
+    Ok(Snapshot {
+        contract_id: contract.to_string(),
+        ledger: latest,
+        wasm_hash,
+        entries,
+    })
+}
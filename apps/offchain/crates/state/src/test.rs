@@ -0,0 +1,354 @@
+use crate::{diff, indexed_transactions, take, touched, Error, Kind, Ledger, Snapshot, Storage};
+use lumenpulse_clients::{streaming::DataKey, ToScVal};
+use lumenpulse_events::Contract;
+use lumenpulse_rpc::{LedgerEntryResult, TransactionInfo, TransactionStatus};
+use std::collections::{BTreeMap, BTreeSet};
+use stellar_xdr::curr::{
+    ContractDataDurability, ContractDataEntry, ContractExecutable, ContractId, ExtensionPoint,
+    Hash, LedgerEntry, LedgerEntryChange, LedgerEntryChanges, LedgerEntryData, LedgerEntryExt,
+    LedgerKey, OperationMeta, ScAddress, ScContractInstance, ScMap, ScMapEntry, ScSymbol, ScVal,
+    TransactionMeta, TransactionMetaV3,
+};
+
+const CONTRACT: &str = "CAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC526";
+
+fn contract() -> ScAddress {
+    CONTRACT.parse().unwrap()
+}
+
+fn stream_key(id: u64) -> ScVal {
+    DataKey::Stream(id).to_sc_val().unwrap()
+}
+
+/// What `get_stream` returned in the streaming contract's golden transcript
+fn golden_stream() -> ScVal {
+    let transcript =
+        include_str!("../../../../onchain/contracts/streaming/goldens/stream_lifecycle.json");
+    let calls: Vec<serde_json::Value> = serde_json::from_str(transcript).unwrap();
+    let call = calls
+        .into_iter()
+        .find(|call| call["call"] == "get_stream")
+        .unwrap();
+    serde_json::from_value(call["returned"].clone()).unwrap()
+}
+
+/// `stream` with its `field` set to `val`
+fn with_field(stream: &ScVal, field: &str, val: ScVal) -> ScVal {
+    let ScVal::Map(Some(map)) = stream else {
+        panic!("not a struct");
+    };
+    let entries: Vec<ScMapEntry> = map
+        .iter()
+        .map(|entry| match &entry.key {
+            ScVal::Symbol(name) if name.0.as_slice() == field.as_bytes() => ScMapEntry {
+                key: entry.key.clone(),
+                val: val.clone(),
+            },
+            _ => entry.clone(),
+        })
+        .collect();
+    ScVal::Map(Some(ScMap(entries.try_into().unwrap())))
+}
+
+fn data(contract: &ScAddress, key: ScVal, val: ScVal) -> LedgerEntry {
+    LedgerEntry {
+        last_modified_ledger_seq: 90,
+        data: LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract: contract.clone(),
+            key,
+            durability: ContractDataDurability::Persistent,
+            val,
+        }),
+        ext: LedgerEntryExt::V0,
+    }
+}
+
+/// A ledger holding `entries`, and the transactions that touched them
+#[derive(Default)]
+struct FakeLedger {
+    entries: BTreeMap<LedgerKey, LedgerEntry>,
+    transactions: BTreeMap<String, Vec<LedgerEntryChange>>,
+}
+
+impl FakeLedger {
+    /// A streaming contract with `storage` in its instance and
+    /// persistent `entries`
+    fn with(storage: Vec<(ScVal, ScVal)>, entries: Vec<(ScVal, ScVal)>) -> Self {
+        let storage: Vec<ScMapEntry> = storage
+            .into_iter()
+            .map(|(key, val)| ScMapEntry { key, val })
+            .collect();
+        let instance = ScVal::ContractInstance(ScContractInstance {
+            executable: ContractExecutable::Wasm(Hash([7; 32])),
+            storage: Some(ScMap(storage.try_into().unwrap())),
+        });
+        let mut ledger = FakeLedger::default();
+        for (key, val) in
+            std::iter::once((ScVal::LedgerKeyContractInstance, instance)).chain(entries)
+        {
+            let entry = data(&contract(), key, val);
+            ledger.entries.insert(entry.to_key(), entry);
+        }
+        ledger
+    }
+}
+
+impl Ledger for FakeLedger {
+    fn latest_ledger(&self) -> Result<u32, lumenpulse_rpc::Error> {
+        Ok(100)
+    }
+
+    fn entries(&self, keys: &[LedgerKey]) -> Result<Vec<LedgerEntryResult>, lumenpulse_rpc::Error> {
+        Ok(keys
+            .iter()
+            .filter_map(|key| self.entries.get(key))
+            .map(|entry| LedgerEntryResult {
+                key: entry.to_key(),
+                data: entry.data.clone(),
+                last_modified_ledger: entry.last_modified_ledger_seq,
+                live_until_ledger: Some(5_000),
+            })
+            .collect())
+    }
+
+    fn transaction(&self, hash: &str) -> Result<TransactionInfo, lumenpulse_rpc::Error> {
+        let Some(changes) = self.transactions.get(hash) else {
+            return Ok(TransactionInfo {
+                status: TransactionStatus::NotFound,
+                latest_ledger: 100,
+                ledger: None,
+                result: None,
+                meta: None,
+            });
+        };
+        let meta = TransactionMetaV3 {
+            ext: ExtensionPoint::V0,
+            tx_changes_before: LedgerEntryChanges::default(),
+            operations: vec![OperationMeta {
+                changes: LedgerEntryChanges(changes.clone().try_into().unwrap()),
+            }]
+            .try_into()
+            .unwrap(),
+            tx_changes_after: LedgerEntryChanges::default(),
+            soroban_meta: None,
+        };
+        Ok(TransactionInfo {
+            status: TransactionStatus::Success,
+            latest_ledger: 100,
+            ledger: Some(90),
+            result: None,
+            meta: Some(TransactionMeta::V3(meta)),
+        })
+    }
+}
+
+fn snapshot(wasm: u8, instance: Vec<(ScVal, ScVal)>, persistent: Vec<(ScVal, ScVal)>) -> Snapshot {
+    let entry = |storage, (key, val)| crate::Entry {
+        storage,
+        key,
+        val,
+        live_until_ledger: None,
+    };
+    Snapshot {
+        contract_id: CONTRACT.to_string(),
+        ledger: u32::from(wasm) * 100,
+        wasm_hash: Some(Hash([wasm; 32]).to_string()),
+        entries: instance
+            .into_iter()
+            .map(|pair| entry(Storage::Instance, pair))
+            .chain(
+                persistent
+                    .into_iter()
+                    .map(|pair| entry(Storage::Persistent, pair)),
+            )
+            .collect(),
+    }
+}
+
+#[test]
+fn snapshots_read_the_instance_and_the_touched_keys() {
+    let next_id = DataKey::NextStreamId.to_sc_val().unwrap();
+    let mut ledger = FakeLedger::with(
+        vec![(next_id.clone(), ScVal::U64(6))],
+        vec![
+            (stream_key(4), golden_stream()),
+            (stream_key(5), golden_stream()),
+        ],
+    );
+    let other = ScAddress::Contract(ContractId(Hash([2; 32])));
+    let changes = vec![
+        LedgerEntryChange::State(data(&contract(), stream_key(4), golden_stream())),
+        LedgerEntryChange::Updated(data(&contract(), stream_key(4), golden_stream())),
+        LedgerEntryChange::Updated(data(
+            &contract(),
+            ScVal::LedgerKeyContractInstance,
+            ScVal::Void,
+        )),
+        LedgerEntryChange::Created(data(&other, stream_key(1), ScVal::Void)),
+        LedgerEntryChange::Removed(data(&contract(), stream_key(9), ScVal::Void).to_key()),
+    ];
+    ledger.transactions.insert("aa".to_string(), changes);
+
+    let hashes = ["aa".to_string(), "old".to_string()];
+    let (keys, missing) = touched(&ledger, &contract(), &hashes).unwrap();
+    assert_eq!(missing, 1);
+    let touched_keys: Vec<&ScVal> = keys
+        .iter()
+        .map(|key| match key {
+            LedgerKey::ContractData(data) => &data.key,
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(touched_keys, [&stream_key(4), &stream_key(9)]);
+
+    // Stream 9 was removed, and stream 5 was never touched
+    let snapshot = take(&ledger, &contract(), &keys).unwrap();
+    assert_eq!(snapshot.ledger, 100);
+    assert_eq!(snapshot.wasm_hash, Some(Hash([7; 32]).to_string()));
+    let entries: Vec<(Storage, &ScVal)> = snapshot
+        .entries
+        .iter()
+        .map(|entry| (entry.storage, &entry.key))
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            (Storage::Instance, &next_id),
+            (Storage::Persistent, &stream_key(4))
+        ]
+    );
+    assert_eq!(snapshot.entries[1].live_until_ledger, Some(5_000));
+
+    // A later snapshot reads the same keys, through its JSON
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let read: Snapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(read, snapshot);
+    let previous = read.keys().unwrap();
+    assert_eq!(previous.len(), 1);
+    assert!(keys.is_superset(&previous));
+}
+
+#[test]
+fn diffs_list_added_removed_and_changed_entries_by_field() {
+    let stream = golden_stream();
+    let admin = DataKey::Admin.to_sc_val().unwrap();
+    let address = ScVal::Address(contract());
+    let before = snapshot(
+        1,
+        vec![(admin.clone(), address.clone())],
+        vec![
+            (stream_key(4), stream.clone()),
+            (stream_key(5), stream.clone()),
+        ],
+    );
+    let withdrawn = with_field(&stream, "withdrawn", 900i128.to_sc_val().unwrap());
+    let after = snapshot(
+        2,
+        vec![(admin, address)],
+        vec![(stream_key(4), withdrawn), (stream_key(6), stream)],
+    );
+
+    let diff = diff(&before, &after, Some(Contract::Streaming)).unwrap();
+    assert_eq!((diff.before_ledger, diff.after_ledger), (100, 200));
+    let changes: Vec<(Kind, &str)> = diff
+        .changes
+        .iter()
+        .map(|change| (change.kind, change.key.as_str()))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            (Kind::Changed, "Stream(4)"),
+            (Kind::Removed, "Stream(5)"),
+            (Kind::Added, "Stream(6)")
+        ]
+    );
+    let changed = &diff.changes[0];
+    assert_eq!(changed.key_type, Some("DataKey"));
+    assert_eq!(changed.value_type, Some("StreamData"));
+    assert_eq!(changed.fields.len(), 1);
+    assert_eq!(changed.fields[0].field, "withdrawn");
+    assert_eq!(changed.fields[0].after.as_deref(), Some("900"));
+    assert!(diff.undecodable.is_empty());
+
+    let report = diff.to_string();
+    assert!(report.contains(&format!("  code {} -> {}", Hash([1; 32]), Hash([2; 32]))));
+    assert!(report.contains("  ~ persistent DataKey::Stream(4)\n      withdrawn: "));
+    assert!(report.contains("  - persistent DataKey::Stream(5) = {"));
+    assert!(report.contains("  + persistent DataKey::Stream(6) = {"));
+
+    // Without the contract's types, the entries are still compared
+    let untyped = crate::diff(&before, &after, None).unwrap();
+    assert_eq!(untyped.changes.len(), 3);
+    assert_eq!(untyped.changes[0].key_type, None);
+}
+
+#[test]
+fn entries_that_no_longer_decode_are_flagged() {
+    let stream = golden_stream();
+    let extra = ScVal::Symbol(ScSymbol("Extra".try_into().unwrap()));
+    let before = snapshot(1, vec![], vec![(stream_key(4), stream.clone())]);
+    // The upgrade added a field a migration never filled in, and an entry
+    // under a key no type describes
+    let ScVal::Map(Some(map)) = &stream else {
+        unreachable!()
+    };
+    let mut fields = map.to_vec();
+    fields.retain(|entry| entry.key != ScVal::Symbol(ScSymbol("withdrawn".try_into().unwrap())));
+    let migrated = ScVal::Map(Some(ScMap(fields.try_into().unwrap())));
+    let after = snapshot(
+        2,
+        vec![],
+        vec![(stream_key(4), migrated), (extra, ScVal::U32(1))],
+    );
+
+    let diff = diff(&before, &after, Some(Contract::Streaming)).unwrap();
+    assert_eq!(diff.undecodable.len(), 1);
+    assert_eq!(diff.undecodable[0].key, "Stream(4)");
+    assert_eq!(diff.undecodable[0].value_type, "StreamData");
+    let extra = &diff.changes[0];
+    assert_eq!(diff.changes[1].fields[0].field, "withdrawn");
+    assert_eq!(diff.changes[1].fields[0].after, None);
+    assert_eq!((extra.kind, extra.key_type), (Kind::Added, None));
+    assert!(diff
+        .to_string()
+        .contains("  ! Stream(4) does not decode as StreamData\n"));
+
+    // Tokens have no storage types to check against
+    let token = crate::diff(&before, &after, Some(Contract::Token)).unwrap();
+    assert!(token.undecodable.is_empty());
+}
+
+#[test]
+fn other_contracts_and_missing_ones_are_errors() {
+    let before = snapshot(1, vec![], vec![]);
+    let mut after = snapshot(1, vec![], vec![]);
+    after.contract_id = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4".to_string();
+    assert!(matches!(
+        diff(&before, &after, None),
+        Err(Error::Mismatch { .. })
+    ));
+    assert!(diff(&before, &before, None)
+        .unwrap()
+        .to_string()
+        .ends_with("  no entries changed\n"));
+
+    let empty = FakeLedger::default();
+    assert!(matches!(
+        take(&empty, &contract(), &BTreeSet::new()),
+        Err(Error::NoContract(id)) if id == CONTRACT
+    ));
+    after.contract_id = "streaming".to_string();
+    assert!(matches!(after.keys(), Err(Error::NotAContract(_))));
+
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+    conn.execute_batch(&format!(
+        "CREATE TABLE events (contract_id TEXT, tx_hash TEXT, ledger INTEGER);
+         INSERT INTO events VALUES
+             ('{CONTRACT}', 'aa', 10), ('{CONTRACT}', 'bb', 12),
+             ('{CONTRACT}', 'aa', 14), ('other', 'cc', 20);"
+    ))
+    .unwrap();
+    assert_eq!(indexed_transactions(&conn, CONTRACT).unwrap(), ["aa", "bb"]);
+}
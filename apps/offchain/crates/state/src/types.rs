@@ -0,0 +1,127 @@
+//! Stored entries read with the types from `lumenpulse-clients`.
+//!
+//! The spec types each key, but not what is stored under it, so the
+//! value each key case holds is written out here from the contracts'
+//! storage modules. A value that no longer decodes as that type after an
+//! upgrade is one a migration missed.
+
+use lumenpulse_clients::{escrow, streaming, subscriptions, FromScVal};
+use lumenpulse_events::Contract;
+use stellar_xdr::curr::{ScAddress, ScVal};
+
+/// What the contract's types make of one stored entry
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Typed {
+    /// Key type the key decodes as, such as `DataKey`
+    pub key_type: &'static str,
+    /// Type the contract stores under the key, such as `StreamData`
+    pub value_type: &'static str,
+    /// Whether the value decodes as `value_type`
+    pub decodes: bool,
+}
+
+/// The types of an entry `contract` stores, for keys one of its key
+/// types describes
+pub fn typed(contract: Contract, key: &ScVal, val: &ScVal) -> Option<Typed> {
+    match contract {
+        Contract::Streaming => streaming_entry(key, val),
+        Contract::Escrow => escrow_entry(key, val),
+        Contract::Subscriptions => subscriptions_entry(key, val),
+        Contract::Token => None,
+    }
+}
+
+fn check<T: FromScVal>(key_type: &'static str, value_type: &'static str, val: &ScVal) -> Typed {
+    Typed {
+        key_type,
+        value_type,
+        decodes: T::from_sc_val(val).is_ok(),
+    }
+}
+
+/// Keys of `lumenpulse-upgradeable` and `lumenpulse-pausable`, which each
+/// contract's spec carries as its own types
+macro_rules! shared_entry {
+    ($module:ident, $key:expr, $val:expr) => {{
+        use $module::{PauseKey, PendingUpgrade, Release, UpgradeKey};
+        if let Ok(key) = UpgradeKey::from_sc_val($key) {
+            Some(match key {
+                UpgradeKey::PendingUpgrade => {
+                    check::<PendingUpgrade>("UpgradeKey", "PendingUpgrade", $val)
+                }
+                UpgradeKey::CurrentRelease | UpgradeKey::PreviousRelease => {
+                    check::<Release>("UpgradeKey", "Release", $val)
+                }
+            })
+        } else if PauseKey::from_sc_val($key).is_ok() {
+            Some(check::<bool>("PauseKey", "bool", $val))
+        } else {
+            None
+        }
+    }};
+}
+
+fn streaming_entry(key: &ScVal, val: &ScVal) -> Option<Typed> {
+    use streaming::{DataKey, OperatorAllowance, OracleConfig, StreamData};
+    let Ok(key) = DataKey::from_sc_val(key) else {
+        return shared_entry!(streaming, key, val);
+    };
+    Some(match key {
+        DataKey::Admin | DataKey::Upgrader | DataKey::DexRouter => {
+            check::<ScAddress>("DataKey", "Address", val)
+        }
+        DataKey::NextStreamId => check::<u64>("DataKey", "u64", val),
+        DataKey::Stream(_) => check::<StreamData>("DataKey", "StreamData", val),
+        DataKey::SenderBalance(..) => check::<i128>("DataKey", "i128", val),
+        DataKey::Operator(_) => check::<OperatorAllowance>("DataKey", "OperatorAllowance", val),
+        DataKey::Oracle => check::<OracleConfig>("DataKey", "OracleConfig", val),
+        DataKey::DexPair(..) => check::<bool>("DataKey", "bool", val),
+    })
+}
+
+fn escrow_entry(key: &ScVal, val: &ScVal) -> Option<Typed> {
+    use escrow::{
+        ArbiterBond, ArbitrationConfig, BadgeGate, Contribution, DataKey, EscrowData, FeeConfig,
+        Milestone, OracleConfig, Ruling,
+    };
+    let Ok(key) = DataKey::from_sc_val(key) else {
+        return shared_entry!(escrow, key, val);
+    };
+    Some(match key {
+        DataKey::Admin => check::<ScAddress>("DataKey", "Address", val),
+        DataKey::NextEscrowId => check::<u64>("DataKey", "u64", val),
+        DataKey::Escrow(_) => check::<EscrowData>("DataKey", "EscrowData", val),
+        DataKey::Milestones(_) => check::<Vec<Milestone>>("DataKey", "Vec<Milestone>", val),
+        DataKey::Contributions(_) => {
+            check::<Vec<Contribution>>("DataKey", "Vec<Contribution>", val)
+        }
+        DataKey::ArbitrationConfig => {
+            check::<ArbitrationConfig>("DataKey", "ArbitrationConfig", val)
+        }
+        DataKey::ArbiterBond(_) => check::<ArbiterBond>("DataKey", "ArbiterBond", val),
+        DataKey::DisputeBond(_) | DataKey::BondsStaked => check::<i128>("DataKey", "i128", val),
+        DataKey::Ruling(_) => check::<Ruling>("DataKey", "Ruling", val),
+        DataKey::FeeConfig => check::<FeeConfig>("DataKey", "FeeConfig", val),
+        DataKey::Oracle => check::<OracleConfig>("DataKey", "OracleConfig", val),
+        DataKey::ArbiterGate => check::<BadgeGate>("DataKey", "BadgeGate", val),
+    })
+}
+
+fn subscriptions_entry(key: &ScVal, val: &ScVal) -> Option<Typed> {
+    use subscriptions::{DataKey, Plan, RefundKey, RefundPolicy, Subscription};
+    if let Ok(key) = RefundKey::from_sc_val(key) {
+        return Some(match key {
+            RefundKey::RefundPolicy(_) => check::<RefundPolicy>("RefundKey", "RefundPolicy", val),
+            RefundKey::RefundReserve(..) => check::<i128>("RefundKey", "i128", val),
+        });
+    }
+    let key = DataKey::from_sc_val(key).ok()?;
+    Some(match key {
+        DataKey::NextPlanId | DataKey::NextSubscriptionId | DataKey::SubscriberPlan(..) => {
+            check::<u64>("DataKey", "u64", val)
+        }
+        DataKey::Plan(_) => check::<Plan>("DataKey", "Plan", val),
+        DataKey::Subscription(_) => check::<Subscription>("DataKey", "Subscription", val),
+        DataKey::Usage(..) => check::<u64>("DataKey", "u64", val),
+    })
+}
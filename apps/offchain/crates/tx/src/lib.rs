@@ -0,0 +1,342 @@
+//! Building, simulating, signing and submitting Soroban transactions.
+//!
+//! A contract invocation goes through the same steps whoever sends it:
+//! build a transaction with the source account's next sequence number,
+//! simulate it to learn its footprint, resource fee and authorizations,
+//! attach those, sign, send, and poll until it lands in a ledger.
+//! `Sender` runs them against one network for one signer.
+//!
+//! Only the source account signs, so invocations may only need the
+//! source account's authorization. That covers admin calls, which take
+//! the admin as the caller and send from the admin's account.
+
+pub mod network;
+mod signer;
+
+pub use signer::Signer;
+
+use lumenpulse_rpc::{Client, SendStatus, Simulation, TransactionInfo, TransactionStatus};
+use sha2::{Digest, Sha256};
+use std::{thread, time::Duration};
+use stellar_xdr::curr::{
+    AccountId, BytesM, ContractExecutable, ContractId, ContractIdPreimage,
+    ContractIdPreimageFromAddress, CreateContractArgs, Hash, HashIdPreimage,
+    HashIdPreimageContractId, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo,
+    MuxedAccount, Operation, OperationBody, Preconditions, PublicKey, ScAddress, ScSymbol, ScVal,
+    SequenceNumber, SorobanCredentials, Transaction, TransactionEnvelope, TransactionExt,
+    TransactionResult, TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction,
+    TransactionV1Envelope, Uint256, VecM, WriteXdr,
+};
+
+/// Inclusion fee bid on top of the resource fee, in stroops
+pub const BASE_FEE: u32 = 100;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(#[from] lumenpulse_rpc::Error),
+    #[error("invalid XDR: {0}")]
+    Xdr(#[from] stellar_xdr::curr::Error),
+    #[error("not a secret key")]
+    InvalidKey,
+    #[error("simulation failed: {0}")]
+    Simulation(String),
+    #[error("the transaction reads archived entries, which have to be restored first")]
+    RestoreNeeded,
+    #[error("the invocation needs authorization from {0}, which is not the source account")]
+    OtherSigner(String),
+    #[error("the fee does not fit in a transaction")]
+    FeeTooHigh,
+    #[error("transaction rejected ({status:?}): {result:?}")]
+    Rejected {
+        status: SendStatus,
+        result: Option<TransactionResult>,
+    },
+    #[error("transaction {hash} failed: {result:?}")]
+    Failed {
+        hash: String,
+        result: Option<TransactionResult>,
+    },
+    #[error("transaction {0} was not included in time")]
+    Timeout(String),
+}
+
+/// Call `function` on `contract` with `args`
+pub fn invoke_contract(
+    contract: &ScAddress,
+    function: &str,
+    args: Vec<ScVal>,
+) -> Result<HostFunction, Error> {
+    Ok(HostFunction::InvokeContract(InvokeContractArgs {
+        contract_address: contract.clone(),
+        function_name: ScSymbol(function.try_into()?),
+        args: args.try_into()?,
+    }))
+}
+
+/// Install `wasm` on the network so contracts can run or upgrade to it
+pub fn upload_wasm(wasm: &[u8]) -> Result<HostFunction, Error> {
+    Ok(HostFunction::UploadContractWasm(BytesM::try_from(wasm)?))
+}
+
+/// Hash a WASM blob is installed under
+pub fn wasm_hash(wasm: &[u8]) -> Hash {
+    Hash(Sha256::digest(wasm).into())
+}
+
+/// Create a contract running the installed WASM `wasm_hash`, at the
+/// address `deployer` and `salt` determine
+pub fn create_contract(deployer: &ScAddress, salt: [u8; 32], wasm_hash: Hash) -> HostFunction {
+    HostFunction::CreateContract(CreateContractArgs {
+        contract_id_preimage: preimage(deployer, salt),
+        executable: ContractExecutable::Wasm(wasm_hash),
+    })
+}
+
+/// Address of the contract `create_contract` creates from `deployer` with
+/// `salt` on the network with ID `network_id`, whatever its WASM
+pub fn created_address(
+    network_id: &Hash,
+    deployer: &ScAddress,
+    salt: [u8; 32],
+) -> Result<ScAddress, Error> {
+    let preimage = HashIdPreimage::ContractId(HashIdPreimageContractId {
+        network_id: network_id.clone(),
+        contract_id_preimage: preimage(deployer, salt),
+    });
+    let id = Sha256::digest(preimage.to_xdr(Limits::none())?);
+    Ok(ScAddress::Contract(ContractId(Hash(id.into()))))
+}
+
+fn preimage(deployer: &ScAddress, salt: [u8; 32]) -> ContractIdPreimage {
+    ContractIdPreimage::Address(ContractIdPreimageFromAddress {
+        address: deployer.clone(),
+        salt: Uint256(salt),
+    })
+}
+
+/// Contract address for a contract strkey (`C...`)
+pub fn contract_address(strkey: &str) -> Option<ScAddress> {
+    let contract = stellar_strkey::Contract::from_string(strkey.trim()).ok()?;
+    Some(ScAddress::Contract(ContractId(Hash(contract.0))))
+}
+
+/// Hash identifying `tx` on the network with ID `network_id`, as
+/// `sendTransaction` reports it
+pub fn transaction_hash(network_id: &Hash, tx: &Transaction) -> Result<[u8; 32], Error> {
+    let payload = TransactionSignaturePayload {
+        network_id: network_id.clone(),
+        tagged_transaction: TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+    };
+    Ok(Sha256::digest(payload.to_xdr(Limits::none())?).into())
+}
+
+/// A transaction from `source` at sequence number `sequence` running
+/// `host_function`, with no resources or fee set yet
+pub fn build(source: &Signer, sequence: i64, host_function: HostFunction) -> Transaction {
+    transaction(source.muxed_account(), sequence, host_function)
+}
+
+/// What `host_function` returns against the latest ledger, for views that
+/// need no authorization. Simulation checks neither the source account nor
+/// its sequence number, so this needs no signer and sends nothing.
+pub fn view(client: &Client, host_function: HostFunction) -> Result<Option<ScVal>, Error> {
+    let source = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([0; 32])));
+    let (_, simulation) = simulate_as(client, &source, host_function)?;
+    if let Some(error) = simulation.error {
+        return Err(Error::Simulation(error));
+    }
+    if simulation.restore_preamble.is_some() {
+        return Err(Error::RestoreNeeded);
+    }
+    Ok(simulation.return_value)
+}
+
+/// Build and simulate `host_function` as sent from `source`, without its
+/// key or sequence number; authorizations `source` gives come back as the
+/// source account's, as they would for its signer
+pub fn simulate_as(
+    client: &Client,
+    source: &AccountId,
+    host_function: HostFunction,
+) -> Result<(Transaction, Simulation), Error> {
+    let AccountId(PublicKey::PublicKeyTypeEd25519(key)) = source;
+    let tx = transaction(MuxedAccount::Ed25519(key.clone()), 1, host_function);
+    let simulation = client.simulate_transaction(&envelope(tx.clone(), Vec::new())?)?;
+    Ok((tx, simulation))
+}
+
+fn transaction(source: MuxedAccount, sequence: i64, host_function: HostFunction) -> Transaction {
+    let operation = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function,
+            auth: VecM::default(),
+        }),
+    };
+    Transaction {
+        source_account: source,
+        fee: BASE_FEE,
+        seq_num: SequenceNumber(sequence),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![operation].try_into().expect("one operation"),
+        ext: TransactionExt::V0,
+    }
+}
+
+/// `tx` with the footprint, fee and authorizations `simulation` found
+pub fn assemble(mut tx: Transaction, simulation: &Simulation) -> Result<Transaction, Error> {
+    if let Some(error) = &simulation.error {
+        return Err(Error::Simulation(error.clone()));
+    }
+    if simulation.restore_preamble.is_some() {
+        return Err(Error::RestoreNeeded);
+    }
+    let data = simulation
+        .transaction_data
+        .clone()
+        .ok_or_else(|| Error::Simulation("no transaction data".to_string()))?;
+    for auth in &simulation.auth {
+        if let SorobanCredentials::Address(credentials) = &auth.credentials {
+            return Err(Error::OtherSigner(credentials.address.to_string()));
+        }
+    }
+
+    let resource_fee = u32::try_from(simulation.min_resource_fee).map_err(|_| Error::FeeTooHigh)?;
+    tx.fee = BASE_FEE
+        .checked_add(resource_fee)
+        .ok_or(Error::FeeTooHigh)?;
+    tx.ext = TransactionExt::V1(data);
+    if let Some(Operation {
+        body: OperationBody::InvokeHostFunction(op),
+        ..
+    }) = tx.operations.first().cloned()
+    {
+        let op = InvokeHostFunctionOp {
+            auth: simulation.auth.clone().try_into()?,
+            ..op
+        };
+        tx.operations = vec![Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(op),
+        }]
+        .try_into()?;
+    }
+    Ok(tx)
+}
+
+/// Sends invocations from one signer to one network
+pub struct Sender<'a> {
+    client: &'a Client,
+    signer: &'a Signer,
+    network_id: Hash,
+    /// How long to wait for a sent transaction to land
+    pub timeout: Duration,
+    pub poll_interval: Duration,
+}
+
+/// A transaction checked against the latest ledger and ready to sign
+pub struct Prepared {
+    pub tx: Transaction,
+    pub simulation: Simulation,
+}
+
+/// A transaction that made it into a ledger
+#[derive(Debug)]
+pub struct Submitted {
+    pub hash: String,
+    pub info: TransactionInfo,
+}
+
+impl<'a> Sender<'a> {
+    pub fn new(client: &'a Client, signer: &'a Signer, passphrase: &str) -> Self {
+        Sender {
+            client,
+            signer,
+            network_id: network::network_id(passphrase),
+            timeout: Duration::from_secs(60),
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    pub fn client(&self) -> &'a Client {
+        self.client
+    }
+
+    /// Build and simulate `host_function`, ready to submit
+    pub fn prepare(&self, host_function: HostFunction) -> Result<Prepared, Error> {
+        let (tx, simulation) = self.simulate(host_function)?;
+        let tx = assemble(tx, &simulation)?;
+        Ok(Prepared { tx, simulation })
+    }
+
+    /// Build and simulate `host_function`, returning the transaction as
+    /// built whatever the simulation found
+    pub fn simulate(
+        &self,
+        host_function: HostFunction,
+    ) -> Result<(Transaction, Simulation), Error> {
+        let account = self.client.get_account(&self.signer.account_id())?;
+        let tx = build(self.signer, account.seq_num.0 + 1, host_function);
+        let envelope = envelope(tx.clone(), Vec::new())?;
+        let simulation = self.client.simulate_transaction(&envelope)?;
+        Ok((tx, simulation))
+    }
+
+    /// Sign and send `tx`, then wait for it to land
+    pub fn submit(&self, tx: Transaction) -> Result<Submitted, Error> {
+        let signature = self.signer.sign(&self.network_id, &tx)?;
+        let sent = self
+            .client
+            .send_transaction(&envelope(tx, vec![signature])?)?;
+        match sent.status {
+            SendStatus::Pending | SendStatus::Duplicate => {}
+            status => {
+                return Err(Error::Rejected {
+                    status,
+                    result: sent.error_result,
+                })
+            }
+        }
+
+        let mut waited = Duration::ZERO;
+        loop {
+            let info = self.client.get_transaction(&sent.hash)?;
+            match info.status {
+                TransactionStatus::Success => {
+                    return Ok(Submitted {
+                        hash: sent.hash,
+                        info,
+                    })
+                }
+                TransactionStatus::Failed => {
+                    return Err(Error::Failed {
+                        hash: sent.hash,
+                        result: info.result,
+                    })
+                }
+                TransactionStatus::NotFound if waited >= self.timeout => {
+                    return Err(Error::Timeout(sent.hash))
+                }
+                TransactionStatus::NotFound => {
+                    thread::sleep(self.poll_interval);
+                    waited += self.poll_interval;
+                }
+            }
+        }
+    }
+}
+
+fn envelope(
+    tx: Transaction,
+    signatures: Vec<stellar_xdr::curr::DecoratedSignature>,
+) -> Result<TransactionEnvelope, Error> {
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx,
+        signatures: signatures.try_into()?,
+    }))
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,47 @@
+//! Networks transactions are signed for.
+
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::Hash;
+
+/// A network with a publicly known passphrase, and the RPC to reach it
+/// where there is a public one
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Preset {
+    pub name: &'static str,
+    pub rpc_url: Option<&'static str>,
+    pub passphrase: &'static str,
+}
+
+pub const PRESETS: [Preset; 4] = [
+    Preset {
+        name: "testnet",
+        rpc_url: Some("https://soroban-testnet.stellar.org"),
+        passphrase: "Test SDF Network ; September 2015",
+    },
+    Preset {
+        name: "futurenet",
+        rpc_url: Some("https://rpc-futurenet.stellar.org"),
+        passphrase: "Test SDF Future Network ; October 2022",
+    },
+    // SDF runs no public mainnet RPC, so one has to be given
+    Preset {
+        name: "mainnet",
+        rpc_url: None,
+        passphrase: "Public Global Stellar Network ; September 2015",
+    },
+    // The quickstart image run with `--local`
+    Preset {
+        name: "standalone",
+        rpc_url: Some("http://localhost:8000/soroban/rpc"),
+        passphrase: "Standalone Network ; February 2017",
+    },
+];
+
+pub fn preset(name: &str) -> Option<Preset> {
+    PRESETS.into_iter().find(|preset| preset.name == name)
+}
+
+/// Network ID a passphrase stands for, which every signature commits to
+pub fn network_id(passphrase: &str) -> Hash {
+    Hash(Sha256::digest(passphrase.as_bytes()).into())
+}
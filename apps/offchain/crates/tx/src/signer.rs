@@ -0,0 +1,56 @@
+//! Ed25519 keys that sign transactions.
+
+use crate::Error;
+use ed25519_dalek::{Signer as _, SigningKey};
+use stellar_xdr::curr::{
+    AccountId, DecoratedSignature, Hash, MuxedAccount, PublicKey, ScAddress, Signature,
+    SignatureHint, Transaction, Uint256,
+};
+
+pub struct Signer {
+    key: SigningKey,
+}
+
+impl Signer {
+    /// Signer for a secret seed (`S...`)
+    pub fn from_secret(secret: &str) -> Result<Self, Error> {
+        let seed = stellar_strkey::ed25519::PrivateKey::from_string(secret.trim())
+            .map_err(|_| Error::InvalidKey)?;
+        Ok(Signer {
+            key: SigningKey::from_bytes(&seed.0),
+        })
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        self.key.verifying_key().to_bytes()
+    }
+
+    /// The account's strkey (`G...`)
+    pub fn account(&self) -> String {
+        stellar_strkey::ed25519::PublicKey(self.public_key()).to_string()
+    }
+
+    pub fn account_id(&self) -> AccountId {
+        AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(self.public_key())))
+    }
+
+    pub fn muxed_account(&self) -> MuxedAccount {
+        MuxedAccount::Ed25519(Uint256(self.public_key()))
+    }
+
+    /// The account as a contract argument
+    pub fn address(&self) -> ScAddress {
+        ScAddress::Account(self.account_id())
+    }
+
+    /// Sign `tx` for the network with ID `network_id`
+    pub fn sign(&self, network_id: &Hash, tx: &Transaction) -> Result<DecoratedSignature, Error> {
+        let hash = crate::transaction_hash(network_id, tx)?;
+        let signature = self.key.sign(&hash).to_bytes();
+        let public_key = self.public_key();
+        Ok(DecoratedSignature {
+            hint: SignatureHint(public_key[28..].try_into().expect("4 bytes")),
+            signature: Signature(signature.to_vec().try_into()?),
+        })
+    }
+}
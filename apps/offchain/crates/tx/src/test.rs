@@ -0,0 +1,160 @@
+use crate::network::{self, network_id};
+use crate::{assemble, build, contract_address, invoke_contract, transaction_hash, Error, Signer};
+use ed25519_dalek::{Signature, VerifyingKey};
+use lumenpulse_rpc::{RestorePreamble, Simulation};
+use stellar_xdr::curr::{
+    Hash, LedgerFootprint, OperationBody, ScVal, SorobanAddressCredentials,
+    SorobanAuthorizationEntry, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+    SorobanCredentials, SorobanResources, SorobanTransactionData, SorobanTransactionDataExt,
+    TransactionExt, VecM,
+};
+
+const CONTRACT: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAK3IM";
+
+fn signer() -> Signer {
+    let seed = stellar_strkey::ed25519::PrivateKey([7; 32]).to_string();
+    Signer::from_secret(&seed).unwrap()
+}
+
+fn simulation(credentials: SorobanCredentials) -> Simulation {
+    let contract = contract_address(CONTRACT).unwrap();
+    let function = match invoke_contract(&contract, "pause", vec![]).unwrap() {
+        stellar_xdr::curr::HostFunction::InvokeContract(args) => args,
+        _ => unreachable!(),
+    };
+    Simulation {
+        latest_ledger: 100,
+        error: None,
+        transaction_data: Some(SorobanTransactionData {
+            ext: SorobanTransactionDataExt::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: VecM::default(),
+                    read_write: VecM::default(),
+                },
+                instructions: 1_000,
+                disk_read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 5_000,
+        }),
+        min_resource_fee: 5_000,
+        auth: vec![SorobanAuthorizationEntry {
+            credentials,
+            root_invocation: SorobanAuthorizedInvocation {
+                function: SorobanAuthorizedFunction::ContractFn(function),
+                sub_invocations: VecM::default(),
+            },
+        }],
+        return_value: Some(ScVal::Void),
+        events: vec![],
+        restore_preamble: None,
+        state_changes: vec![],
+    }
+}
+
+#[test]
+fn test_networks_and_addresses() {
+    // The testnet ID every wallet signs for
+    assert_eq!(
+        network_id(network::preset("testnet").unwrap().passphrase).to_string(),
+        "cee0302d59844d32bdca915c8203dd44b33fbb7edc19051ea37abedf28ecd472"
+    );
+    assert_eq!(network::preset("mainnet").unwrap().rpc_url, None);
+    assert_eq!(network::preset("devnet"), None);
+    assert_eq!(contract_address(CONTRACT).unwrap().to_string(), CONTRACT);
+    assert_eq!(contract_address("GABC"), None);
+    assert!(matches!(
+        Signer::from_secret(CONTRACT),
+        Err(Error::InvalidKey)
+    ));
+}
+
+#[test]
+fn test_signatures_verify_against_the_account() {
+    let signer = signer();
+    assert!(signer.account().starts_with('G'));
+    let contract = contract_address(CONTRACT).unwrap();
+    let call = invoke_contract(&contract, "pause", vec![ScVal::Address(signer.address())]);
+    let tx = build(&signer, 42, call.unwrap());
+    assert_eq!(tx.seq_num.0, 42);
+
+    let id = network_id("Test SDF Network ; September 2015");
+    let signature = signer.sign(&id, &tx).unwrap();
+    assert_eq!(signature.hint.0, signer.public_key()[28..]);
+    let key = VerifyingKey::from_bytes(&signer.public_key()).unwrap();
+    let bytes: [u8; 64] = signature.signature.0.as_slice().try_into().unwrap();
+    let hash = transaction_hash(&id, &tx).unwrap();
+    assert!(key
+        .verify_strict(&hash, &Signature::from_bytes(&bytes))
+        .is_ok());
+    // The same transaction on another network is a different payload
+    let other = transaction_hash(&Hash([0; 32]), &tx).unwrap();
+    assert!(key
+        .verify_strict(&other, &Signature::from_bytes(&bytes))
+        .is_err());
+}
+
+#[test]
+fn test_assemble_applies_the_simulation() {
+    let signer = signer();
+    let contract = contract_address(CONTRACT).unwrap();
+    let tx = || {
+        build(
+            &signer,
+            1,
+            invoke_contract(&contract, "pause", vec![]).unwrap(),
+        )
+    };
+
+    let tx = assemble(tx(), &simulation(SorobanCredentials::SourceAccount)).unwrap();
+    assert_eq!(tx.fee, 5_100);
+    assert!(matches!(&tx.ext, TransactionExt::V1(data) if data.resource_fee == 5_000));
+    let OperationBody::InvokeHostFunction(op) = &tx.operations[0].body else {
+        unreachable!()
+    };
+    assert_eq!(op.auth.len(), 1);
+}
+
+#[test]
+fn test_assemble_refuses_what_it_cannot_send() {
+    let signer = signer();
+    let contract = contract_address(CONTRACT).unwrap();
+    let tx = || {
+        build(
+            &signer,
+            1,
+            invoke_contract(&contract, "pause", vec![]).unwrap(),
+        )
+    };
+
+    let other = SorobanCredentials::Address(SorobanAddressCredentials {
+        address: contract.clone(),
+        nonce: 0,
+        signature_expiration_ledger: 0,
+        signature: ScVal::Void,
+    });
+    assert!(matches!(
+        assemble(tx(), &simulation(other)),
+        Err(Error::OtherSigner(address)) if address == CONTRACT
+    ));
+
+    let failed = Simulation {
+        error: Some("HostError: Error(Contract, #1100)".to_string()),
+        ..simulation(SorobanCredentials::SourceAccount)
+    };
+    assert!(matches!(assemble(tx(), &failed), Err(Error::Simulation(_))));
+
+    let ok = simulation(SorobanCredentials::SourceAccount);
+    let archived = Simulation {
+        restore_preamble: Some(RestorePreamble {
+            transaction_data: ok.transaction_data.clone().unwrap(),
+            min_resource_fee: 100,
+        }),
+        ..ok
+    };
+    assert!(matches!(
+        assemble(tx(), &archived),
+        Err(Error::RestoreNeeded)
+    ));
+}
@@ -0,0 +1,36 @@
+use soroban_sdk::{contractevent, Address, String};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeCreatedEvent {
+    #[topic]
+    pub badge_id: u32,
+    pub name: String,
+    pub issuer: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerChangedEvent {
+    #[topic]
+    pub badge_id: u32,
+    pub issuer: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuedEvent {
+    #[topic]
+    pub badge_id: u32,
+    #[topic]
+    pub holder: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevokedEvent {
+    #[topic]
+    pub badge_id: u32,
+    #[topic]
+    pub holder: Address,
+}
@@ -0,0 +1,186 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+
+pub use errors::BadgeError;
+pub use storage::{Badge, Holding};
+
+use soroban_sdk::{contract, contractimpl, Address, Env, String};
+use storage::DataKey;
+
+/// Soulbound credentials such as arbiter, keeper or long-term staker. The
+/// admin defines each badge and names its issuer; the issuer awards it to
+/// holders and may revoke it at any time. Badges are bound to the address
+/// they were issued to: there is no transfer or approval of any kind.
+#[contract]
+pub struct BadgeContract;
+
+#[contractimpl]
+impl BadgeContract {
+    /// Initialize the contract with the admin that defines badges
+    pub fn initialize(env: Env, admin: Address) -> Result<(), BadgeError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(BadgeError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::NextBadgeId, &0u32);
+
+        Ok(())
+    }
+
+    /// Define a badge awarded by `issuer` (admin only). Returns its ID.
+    pub fn create_badge(
+        env: Env,
+        admin: Address,
+        name: String,
+        issuer: Address,
+    ) -> Result<u32, BadgeError> {
+        Self::require_admin(&env, &admin)?;
+
+        let badge_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextBadgeId)
+            .unwrap_or(0);
+        let badge = Badge {
+            id: badge_id,
+            name: name.clone(),
+            issuer: issuer.clone(),
+            holders: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Badge(badge_id), &badge);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextBadgeId, &(badge_id + 1));
+
+        events::BadgeCreatedEvent {
+            badge_id,
+            name,
+            issuer,
+        }
+        .publish(&env);
+
+        Ok(badge_id)
+    }
+
+    /// Hand a badge to a new issuer (admin only)
+    pub fn set_issuer(
+        env: Env,
+        admin: Address,
+        badge_id: u32,
+        issuer: Address,
+    ) -> Result<(), BadgeError> {
+        Self::require_admin(&env, &admin)?;
+
+        let mut badge = Self::get_badge(env.clone(), badge_id)?;
+        badge.issuer = issuer.clone();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Badge(badge_id), &badge);
+
+        events::IssuerChangedEvent { badge_id, issuer }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Award a badge to `holder` (issuer only)
+    pub fn issue(env: Env, badge_id: u32, holder: Address) -> Result<(), BadgeError> {
+        let mut badge = Self::get_badge(env.clone(), badge_id)?;
+        badge.issuer.require_auth();
+
+        let key = DataKey::Holding(badge_id, holder.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(BadgeError::AlreadyHeld);
+        }
+
+        let holding = Holding {
+            badge_id,
+            holder: holder.clone(),
+            issued_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &holding);
+        badge.holders += 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Badge(badge_id), &badge);
+
+        events::IssuedEvent { badge_id, holder }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Take a badge back from `holder` (issuer only)
+    pub fn revoke(env: Env, badge_id: u32, holder: Address) -> Result<(), BadgeError> {
+        let mut badge = Self::get_badge(env.clone(), badge_id)?;
+        badge.issuer.require_auth();
+
+        let key = DataKey::Holding(badge_id, holder.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(BadgeError::NotHeld);
+        }
+
+        env.storage().persistent().remove(&key);
+        badge.holders -= 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Badge(badge_id), &badge);
+
+        events::RevokedEvent { badge_id, holder }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Whether `holder` currently holds a badge
+    pub fn has_badge(env: Env, holder: Address, badge_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Holding(badge_id, holder))
+    }
+
+    /// `holder`'s copy of a badge
+    pub fn get_holding(env: Env, holder: Address, badge_id: u32) -> Result<Holding, BadgeError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Holding(badge_id, holder))
+            .ok_or(BadgeError::NotHeld)
+    }
+
+    /// Get a badge by ID
+    pub fn get_badge(env: Env, badge_id: u32) -> Result<Badge, BadgeError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Badge(badge_id))
+            .ok_or(BadgeError::BadgeNotFound)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, BadgeError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(BadgeError::NotInitialized)
+    }
+}
+
+impl BadgeContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), BadgeError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(BadgeError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
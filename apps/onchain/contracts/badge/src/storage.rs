@@ -0,0 +1,31 @@
+use soroban_sdk::{contracttype, Address, String};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                 // -> Address
+    NextBadgeId,           // -> u32
+    Badge(u32),            // badge_id -> Badge
+    Holding(u32, Address), // (badge_id, holder) -> Holding
+}
+
+/// A kind of credential, such as "arbiter", and who may award it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Badge {
+    pub id: u32,
+    pub name: String,
+    /// Issues and revokes this badge
+    pub issuer: Address,
+    /// Number of addresses currently holding it
+    pub holders: u32,
+}
+
+/// One holder's copy of a badge
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Holding {
+    pub badge_id: u32,
+    pub holder: Address,
+    pub issued_at: u64,
+}
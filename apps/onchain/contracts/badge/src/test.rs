@@ -0,0 +1,83 @@
+use crate::{BadgeContract, BadgeContractClient, BadgeError};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    Address, Env, IntoVal, String,
+};
+
+struct Setup<'a> {
+    client: BadgeContractClient<'a>,
+    admin: Address,
+    issuer: Address,
+    badge_id: u32,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let issuer = Address::generate(env);
+    let client = BadgeContractClient::new(env, &env.register(BadgeContract, ()));
+    client.initialize(&admin);
+    let badge_id = client.create_badge(&admin, &String::from_str(env, "arbiter"), &issuer);
+
+    Setup {
+        client,
+        admin,
+        issuer,
+        badge_id,
+    }
+}
+
+#[test]
+fn test_issue_and_revoke() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let holder = Address::generate(&env);
+    env.ledger().set_timestamp(500);
+
+    s.client.issue(&s.badge_id, &holder);
+    assert!(s.client.has_badge(&holder, &s.badge_id));
+    assert_eq!(s.client.get_holding(&holder, &s.badge_id).issued_at, 500);
+    assert_eq!(s.client.get_badge(&s.badge_id).holders, 1);
+    let result = s.client.try_issue(&s.badge_id, &holder);
+    assert_eq!(result, Err(Ok(BadgeError::AlreadyHeld)));
+
+    s.client.revoke(&s.badge_id, &holder);
+    assert!(!s.client.has_badge(&holder, &s.badge_id));
+    assert_eq!(s.client.get_badge(&s.badge_id).holders, 0);
+    let result = s.client.try_revoke(&s.badge_id, &holder);
+    assert_eq!(result, Err(Ok(BadgeError::NotHeld)));
+
+    let result = s.client.try_issue(&7, &holder);
+    assert_eq!(result, Err(Ok(BadgeError::BadgeNotFound)));
+}
+
+#[test]
+fn test_only_issuer_awards() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let holder = Address::generate(&env);
+    let replacement = Address::generate(&env);
+
+    s.client.set_issuer(&s.admin, &s.badge_id, &replacement);
+    assert_eq!(s.client.get_badge(&s.badge_id).issuer, replacement);
+    let result = s.client.try_set_issuer(&s.issuer, &s.badge_id, &s.issuer);
+    assert_eq!(result, Err(Ok(BadgeError::Unauthorized)));
+
+    // The previous issuer's authorization no longer counts
+    env.set_auths(&[]);
+    let result = s
+        .client
+        .mock_auths(&[MockAuth {
+            address: &s.issuer,
+            invoke: &MockAuthInvoke {
+                contract: &s.client.address,
+                fn_name: "issue",
+                args: (s.badge_id, holder.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_issue(&s.badge_id, &holder);
+    assert!(result.is_err());
+    assert!(!s.client.has_badge(&holder, &s.badge_id));
+}
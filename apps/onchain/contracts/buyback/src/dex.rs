@@ -0,0 +1,57 @@
+use crate::errors::BuybackError;
+use crate::storage::BuybackConfig;
+use lumenpulse_dex::DexError;
+use lumenpulse_fixed::{mul_div, Rounding, BPS_DENOMINATOR};
+use lumenpulse_oracle::Asset;
+use soroban_sdk::{Address, Env};
+
+/// Swap `amount` of `fee_token` for the Lumenpulse token, accepting a fill
+/// down to `config.max_slippage_bps` below what the oracle's time-weighted
+/// prices make it worth. Returns what arrived.
+///
+/// The router's own quote is not used as the bound, since a pool skewed
+/// within the same transaction would skew its quote too.
+pub fn buy(
+    env: &Env,
+    config: &BuybackConfig,
+    fee_token: &Address,
+    amount: i128,
+) -> Result<i128, BuybackError> {
+    let fee_price = twap(env, config, fee_token)?;
+    let lumen_price = twap(env, config, &config.lumen)?;
+    // Both prices share the feed's base and decimals, which cancel out.
+    // Rounding down keeps the bound on the side of letting a fair fill
+    // through.
+    let reference = mul_div(amount, fee_price, lumen_price, Rounding::Down)
+        .ok_or(BuybackError::PriceUnavailable)?;
+    let min_out = mul_div(
+        reference,
+        BPS_DENOMINATOR - config.max_slippage_bps as i128,
+        BPS_DENOMINATOR,
+        Rounding::Down,
+    )
+    .ok_or(BuybackError::PriceUnavailable)?;
+    lumenpulse_dex::swap_exact_in(
+        env,
+        &config.router,
+        fee_token,
+        &config.lumen,
+        amount,
+        min_out,
+    )
+    .map_err(dex_error)
+}
+
+fn twap(env: &Env, config: &BuybackConfig, token: &Address) -> Result<i128, BuybackError> {
+    let asset = Asset::Stellar(token.clone());
+    lumenpulse_oracle::twap(env, &config.oracle, &asset, config.twap_window)
+        .map_err(|_| BuybackError::PriceUnavailable)
+}
+
+fn dex_error(error: DexError) -> BuybackError {
+    match error {
+        DexError::InvalidAmount => BuybackError::NothingToBuy,
+        DexError::SlippageExceeded => BuybackError::SlippageExceeded,
+        _ => BuybackError::SwapFailed,
+    }
+}
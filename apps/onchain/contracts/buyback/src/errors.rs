@@ -0,0 +1,17 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BuybackError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    InvalidConfig = 4,
+    TokenNotEnabled = 5,
+    CooldownActive = 6,
+    NothingToBuy = 7,
+    SwapFailed = 8,
+    SlippageExceeded = 9,
+    PriceUnavailable = 10,
+}
@@ -0,0 +1,29 @@
+use crate::storage::BuybackConfig;
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigUpdatedEvent {
+    pub config: BuybackConfig,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTokenSetEvent {
+    #[topic]
+    pub token: Address,
+    pub max_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuybackExecutedEvent {
+    #[topic]
+    pub fee_token: Address,
+    #[topic]
+    pub keeper: Address,
+    pub spent: i128,
+    pub bought: i128,
+    pub keeper_reward: i128,
+    pub burned: i128,
+}
@@ -0,0 +1,216 @@
+#![no_std]
+
+mod dex;
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::BuybackError;
+use lumenpulse_fixed::{bps, Rounding, BPS_DENOMINATOR};
+use lumenpulse_pausable::Pausable;
+use soroban_sdk::{contract, contractimpl, Address, Env};
+use storage::{BuybackConfig, BuybackResult, DataKey};
+
+/// Buys back and burns the Lumenpulse token with collected protocol fees.
+/// Fees are sent here in any enabled token, and anyone may execute a
+/// buyback, which swaps them through the DEX router, pays the caller a
+/// keeper reward out of the proceeds and burns the rest. Swaps must fill
+/// close to the oracle's time-weighted price, and each execution spends at
+/// most the token's cap and waits out a cooldown, so a manipulated pool
+/// can only ever move a bounded amount.
+#[contract]
+pub struct BuybackContract;
+
+#[contractimpl]
+impl BuybackContract {
+    /// Initialize the contract with an admin address and config
+    pub fn initialize(env: Env, admin: Address, config: BuybackConfig) -> Result<(), BuybackError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(BuybackError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        Self::store_config(&env, config)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::TotalBurned, &0i128);
+
+        Ok(())
+    }
+
+    /// Replace the config (admin only)
+    pub fn set_config(env: Env, admin: Address, config: BuybackConfig) -> Result<(), BuybackError> {
+        Self::require_admin(&env, &admin)?;
+        Self::store_config(&env, config)
+    }
+
+    /// Allow buybacks with `token`, spending at most `max_amount` per
+    /// execution, or stop them if `max_amount` is zero (admin only)
+    pub fn set_fee_token(
+        env: Env,
+        admin: Address,
+        token: Address,
+        max_amount: i128,
+    ) -> Result<(), BuybackError> {
+        Self::require_admin(&env, &admin)?;
+
+        if max_amount < 0 {
+            return Err(BuybackError::InvalidConfig);
+        }
+        let key = DataKey::FeeToken(token.clone());
+        if max_amount == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &max_amount);
+        }
+
+        events::FeeTokenSetEvent { token, max_amount }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Buy back and burn with the contract's `fee_token` balance, up to the
+    /// token's cap. `keeper` receives the keeper reward. Fees already in the
+    /// Lumenpulse token are burned without a swap.
+    pub fn execute(
+        env: Env,
+        keeper: Address,
+        fee_token: Address,
+    ) -> Result<BuybackResult, BuybackError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        keeper.require_auth();
+
+        let config = Self::get_config(env.clone())?;
+        let max_amount = Self::max_amount(env.clone(), fee_token.clone())
+            .ok_or(BuybackError::TokenNotEnabled)?;
+        let now = env.ledger().timestamp();
+        let last_key = DataKey::LastExecution(fee_token.clone());
+        if let Some(last) = env.storage().persistent().get::<_, u64>(&last_key) {
+            if now < last + config.cooldown {
+                return Err(BuybackError::CooldownActive);
+            }
+        }
+
+        let this = env.current_contract_address();
+        let spent = token::balance(&env, &fee_token, &this).min(max_amount);
+        if spent <= 0 {
+            return Err(BuybackError::NothingToBuy);
+        }
+        env.storage().persistent().set(&last_key, &now);
+
+        let bought = if fee_token == config.lumen {
+            spent
+        } else {
+            dex::buy(&env, &config, &fee_token, spent)?
+        };
+        // The reward rounds down, and its dust is burned with the rest
+        let keeper_reward =
+            bps(bought, config.keeper_bps, Rounding::Down).expect("arithmetic overflow");
+        let burned = bought - keeper_reward;
+
+        let total = Self::total_burned(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalBurned, &(total + burned));
+
+        token::transfer(&env, &config.lumen, &this, &keeper, &keeper_reward);
+        if burned > 0 {
+            token::burn(&env, &config.lumen, &burned);
+        }
+
+        events::BuybackExecutedEvent {
+            fee_token,
+            keeper,
+            spent,
+            bought,
+            keeper_reward,
+            burned,
+        }
+        .publish(&env);
+
+        Ok(BuybackResult {
+            spent,
+            bought,
+            keeper_reward,
+            burned,
+        })
+    }
+
+    /// Most of `token` one execution spends, if buybacks with it are enabled
+    pub fn max_amount(env: Env, token: Address) -> Option<i128> {
+        env.storage().persistent().get(&DataKey::FeeToken(token))
+    }
+
+    /// Earliest time `token` can be executed again
+    pub fn next_execution(env: Env, token: Address) -> Result<u64, BuybackError> {
+        let config = Self::get_config(env.clone())?;
+        let last: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastExecution(token));
+        Ok(last.map_or(0, |last| last + config.cooldown))
+    }
+
+    /// Lumenpulse tokens burned by every execution so far
+    pub fn total_burned(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalBurned)
+            .unwrap_or(0)
+    }
+
+    /// Get the current config
+    pub fn get_config(env: Env) -> Result<BuybackConfig, BuybackError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(BuybackError::NotInitialized)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, BuybackError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(BuybackError::NotInitialized)
+    }
+}
+
+impl BuybackContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), BuybackError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(BuybackError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn store_config(env: &Env, config: BuybackConfig) -> Result<(), BuybackError> {
+        if config.keeper_bps as i128 > BPS_DENOMINATOR
+            || config.max_slippage_bps as i128 >= BPS_DENOMINATOR
+            || config.twap_window == 0
+        {
+            return Err(BuybackError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        events::ConfigUpdatedEvent { config }.publish(env);
+
+        Ok(())
+    }
+}
+
+impl Pausable for BuybackContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(BuybackContract);
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,41 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                  // -> Address
+    Config,                 // -> BuybackConfig
+    FeeToken(Address),      // token -> i128 (most spent per execution)
+    LastExecution(Address), // token -> u64
+    TotalBurned,            // -> i128
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuybackConfig {
+    /// DEX router fees are swapped through
+    pub router: Address,
+    /// Lumenpulse token bought and burned
+    pub lumen: Address,
+    /// Share of each buyback, in basis points, paid to whoever executes it
+    pub keeper_bps: u32,
+    /// SEP-40 feed pricing both the fee tokens and the Lumenpulse token
+    pub oracle: Address,
+    /// Seconds of price history the reference price of a swap averages
+    pub twap_window: u64,
+    /// How far below the reference price a swap may fill, in basis points
+    pub max_slippage_bps: u32,
+    /// Seconds between executions for the same fee token
+    pub cooldown: u64,
+}
+
+/// What one execution did, in Lumenpulse tokens unless noted
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuybackResult {
+    /// Fee tokens swapped
+    pub spent: i128,
+    pub bought: i128,
+    pub keeper_reward: i128,
+    pub burned: i128,
+}
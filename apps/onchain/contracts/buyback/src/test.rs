@@ -0,0 +1,181 @@
+use crate::errors::BuybackError;
+use crate::storage::{BuybackConfig, BuybackResult};
+use crate::{BuybackContract, BuybackContractClient};
+use lumenpulse_oracle::Asset;
+use mock_oracle::{MockOracleContract, MockOracleContractClient};
+use mock_router::{MockRouterContract, MockRouterContractClient, RATE_SCALE};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const COOLDOWN: u64 = 3_600;
+const TWAP_WINDOW: u64 = 1_800;
+
+struct Setup<'a> {
+    client: BuybackContractClient<'a>,
+    admin: Address,
+    keeper: Address,
+    lumen: TokenClient<'a>,
+    usdc: TokenClient<'a>,
+    usdc_admin: StellarAssetClient<'a>,
+    oracle: MockOracleContractClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let keeper = Address::generate(env);
+    let lumen = env.register_stellar_asset_contract_v2(Address::generate(env));
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(env));
+
+    // 1 USDC = 4 LUMEN, both at the oracle and at the router. Prices have
+    // been steady since time zero, so the window is covered from the start.
+    let oracle = MockOracleContractClient::new(env, &env.register(MockOracleContract, ()));
+    oracle.initialize(&Asset::Other(symbol_short!("USD")), &7, &300);
+    oracle.set_price(&Asset::Stellar(usdc.address()), &10_000_000);
+    oracle.set_price(&Asset::Stellar(lumen.address()), &2_500_000);
+    env.ledger().set_timestamp(TWAP_WINDOW);
+    let router = MockRouterContractClient::new(env, &env.register(MockRouterContract, ()));
+    router.set_rate(&usdc.address(), &lumen.address(), &(4 * RATE_SCALE));
+    StellarAssetClient::new(env, &lumen.address()).mint(&router.address, &1_000_000);
+
+    let client = BuybackContractClient::new(env, &env.register(BuybackContract, ()));
+    client.initialize(
+        &admin,
+        &BuybackConfig {
+            router: router.address.clone(),
+            lumen: lumen.address(),
+            oracle: oracle.address.clone(),
+            twap_window: TWAP_WINDOW,
+            keeper_bps: 100,
+            max_slippage_bps: 50,
+            cooldown: COOLDOWN,
+        },
+    );
+    client.set_fee_token(&admin, &usdc.address(), &1_000);
+
+    Setup {
+        client,
+        admin,
+        keeper,
+        lumen: TokenClient::new(env, &lumen.address()),
+        usdc: TokenClient::new(env, &usdc.address()),
+        usdc_admin: StellarAssetClient::new(env, &usdc.address()),
+        oracle,
+    }
+}
+
+#[test]
+fn test_execute_buys_and_burns() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    s.usdc_admin.mint(&s.client.address, &1_500);
+
+    // The cap limits one execution to 1,000 USDC
+    let result = s.client.execute(&s.keeper, &s.usdc.address);
+    assert_eq!(
+        result,
+        BuybackResult {
+            spent: 1_000,
+            bought: 4_000,
+            keeper_reward: 40,
+            burned: 3_960,
+        }
+    );
+    assert_eq!(s.usdc.balance(&s.client.address), 500);
+    assert_eq!(s.lumen.balance(&s.keeper), 40);
+    assert_eq!(s.lumen.balance(&s.client.address), 0);
+    assert_eq!(s.client.total_burned(), 3_960);
+
+    let result = s.client.try_execute(&s.keeper, &s.usdc.address);
+    assert_eq!(result, Err(Ok(BuybackError::CooldownActive)));
+    assert_eq!(
+        s.client.next_execution(&s.usdc.address),
+        TWAP_WINDOW + COOLDOWN
+    );
+
+    env.ledger().set_timestamp(TWAP_WINDOW + COOLDOWN);
+    assert_eq!(s.client.execute(&s.keeper, &s.usdc.address).spent, 500);
+    assert_eq!(s.client.total_burned(), 3_960 + 1_980);
+}
+
+#[test]
+fn test_lumen_fees_burn_without_swap() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let result = s.client.try_execute(&s.keeper, &s.lumen.address);
+    assert_eq!(result, Err(Ok(BuybackError::TokenNotEnabled)));
+
+    s.client.set_fee_token(&s.admin, &s.lumen.address, &10_000);
+    let result = s.client.try_execute(&s.keeper, &s.lumen.address);
+    assert_eq!(result, Err(Ok(BuybackError::NothingToBuy)));
+
+    StellarAssetClient::new(&env, &s.lumen.address).mint(&s.client.address, &1_000);
+    let result = s.client.execute(&s.keeper, &s.lumen.address);
+    assert_eq!((result.bought, result.burned), (1_000, 990));
+    assert_eq!(s.lumen.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_failed_swap_keeps_fees() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    s.usdc_admin.mint(&s.client.address, &1_000);
+
+    // A router without liquidity cannot fill the swap
+    let dry = MockRouterContractClient::new(&env, &env.register(MockRouterContract, ()));
+    dry.set_rate(&s.usdc.address, &s.lumen.address, &(4 * RATE_SCALE));
+    let mut config = s.client.get_config();
+    config.router = dry.address.clone();
+    s.client.set_config(&s.admin, &config);
+
+    let result = s.client.try_execute(&s.keeper, &s.usdc.address);
+    assert_eq!(result, Err(Ok(BuybackError::SwapFailed)));
+    assert_eq!(s.usdc.balance(&s.client.address), 1_000);
+
+    config.keeper_bps = 10_001;
+    let result = s.client.try_set_config(&s.admin, &config);
+    assert_eq!(result, Err(Ok(BuybackError::InvalidConfig)));
+}
+
+#[test]
+fn test_swap_is_bounded_by_the_oracle_twap() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    s.usdc_admin.mint(&s.client.address, &1_000);
+
+    // A pool pushed to 2 LUMEN per USDC quotes that rate too, but the
+    // swap must still fill near the oracle's 4
+    let skewed = MockRouterContractClient::new(&env, &env.register(MockRouterContract, ()));
+    skewed.set_rate(&s.usdc.address, &s.lumen.address, &(2 * RATE_SCALE));
+    StellarAssetClient::new(&env, &s.lumen.address).mint(&skewed.address, &1_000_000);
+    let mut config = s.client.get_config();
+    config.router = skewed.address.clone();
+    s.client.set_config(&s.admin, &config);
+
+    let result = s.client.try_execute(&s.keeper, &s.usdc.address);
+    assert_eq!(result, Err(Ok(BuybackError::SwapFailed)));
+    assert_eq!(s.usdc.balance(&s.client.address), 1_000);
+
+    // Nor does a fresh oracle print agreeing with the pool, which has not
+    // been in force for any of the window
+    s.oracle
+        .set_price(&Asset::Stellar(s.usdc.address.clone()), &5_000_000);
+    let result = s.client.try_execute(&s.keeper, &s.usdc.address);
+    assert_eq!(result, Err(Ok(BuybackError::SwapFailed)));
+
+    // Without enough price history there is no reference to swap against
+    config.twap_window = 2 * TWAP_WINDOW;
+    s.client.set_config(&s.admin, &config);
+    let result = s.client.try_execute(&s.keeper, &s.usdc.address);
+    assert_eq!(result, Err(Ok(BuybackError::PriceUnavailable)));
+
+    config.twap_window = 0;
+    let result = s.client.try_set_config(&s.admin, &config);
+    assert_eq!(result, Err(Ok(BuybackError::InvalidConfig)));
+}
@@ -0,0 +1,11 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ConfigError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    ParameterNotFound = 3,
+    TypeMismatch = 4,
+}
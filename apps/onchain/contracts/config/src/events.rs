@@ -0,0 +1,29 @@
+use crate::storage::ConfigValue;
+use soroban_sdk::{contractevent, Address, Symbol};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub governance: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParameterSetEvent {
+    #[topic]
+    pub name: Symbol,
+    pub value: ConfigValue,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParameterRemovedEvent {
+    #[topic]
+    pub name: Symbol,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceChangedEvent {
+    pub governance: Address,
+}
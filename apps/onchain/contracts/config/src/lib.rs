@@ -0,0 +1,145 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+
+pub use errors::ConfigError;
+pub use storage::ConfigValue;
+
+use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
+use storage::DataKey;
+
+/// Registry of named protocol parameters (fees, limits, addresses) shared
+/// by the Lumenpulse contracts. Only governance may change them; anyone may
+/// read them.
+#[contract]
+pub struct ConfigContract;
+
+#[contractimpl]
+impl ConfigContract {
+    /// Initialize the registry under `governance`, which must authorize it
+    /// so nobody can claim the registry between deployment and setup
+    pub fn initialize(env: Env, governance: Address) -> Result<(), ConfigError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Governance) {
+            return Err(ConfigError::AlreadyInitialized);
+        }
+
+        // Require governance authorization
+        governance.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Governance, &governance);
+
+        events::InitializedEvent { governance }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Set a parameter (governance only)
+    pub fn set(env: Env, name: Symbol, value: ConfigValue) -> Result<(), ConfigError> {
+        Self::require_governance(&env)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Param(name.clone()), &value);
+
+        events::ParameterSetEvent { name, value }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Remove a parameter (governance only)
+    pub fn remove(env: Env, name: Symbol) -> Result<(), ConfigError> {
+        Self::require_governance(&env)?;
+
+        let key = DataKey::Param(name.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(ConfigError::ParameterNotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        events::ParameterRemovedEvent { name }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Hand control of the registry to a new governance contract
+    /// (governance only)
+    pub fn set_governance(env: Env, governance: Address) -> Result<(), ConfigError> {
+        Self::require_governance(&env)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Governance, &governance);
+
+        events::GovernanceChangedEvent { governance }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get a parameter of any type, if set
+    pub fn get(env: Env, name: Symbol) -> Option<ConfigValue> {
+        env.storage().persistent().get(&DataKey::Param(name))
+    }
+
+    /// Get a `u32` parameter such as a fee in basis points
+    pub fn get_u32(env: Env, name: Symbol) -> Result<u32, ConfigError> {
+        match Self::load(&env, name)? {
+            ConfigValue::U32(value) => Ok(value),
+            _ => Err(ConfigError::TypeMismatch),
+        }
+    }
+
+    /// Get an `i128` parameter such as an amount limit
+    pub fn get_i128(env: Env, name: Symbol) -> Result<i128, ConfigError> {
+        match Self::load(&env, name)? {
+            ConfigValue::I128(value) => Ok(value),
+            _ => Err(ConfigError::TypeMismatch),
+        }
+    }
+
+    /// Get a `bool` parameter such as a feature switch
+    pub fn get_bool(env: Env, name: Symbol) -> Result<bool, ConfigError> {
+        match Self::load(&env, name)? {
+            ConfigValue::Bool(value) => Ok(value),
+            _ => Err(ConfigError::TypeMismatch),
+        }
+    }
+
+    /// Get an address parameter such as a treasury
+    pub fn get_address(env: Env, name: Symbol) -> Result<Address, ConfigError> {
+        match Self::load(&env, name)? {
+            ConfigValue::Address(value) => Ok(value),
+            _ => Err(ConfigError::TypeMismatch),
+        }
+    }
+
+    /// Get the governance address allowed to change parameters
+    pub fn get_governance(env: Env) -> Result<Address, ConfigError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Governance)
+            .ok_or(ConfigError::NotInitialized)
+    }
+}
+
+impl ConfigContract {
+    fn require_governance(env: &Env) -> Result<(), ConfigError> {
+        let governance = Self::get_governance(env.clone())?;
+        governance.require_auth();
+        Ok(())
+    }
+
+    fn load(env: &Env, name: Symbol) -> Result<ConfigValue, ConfigError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Param(name))
+            .ok_or(ConfigError::ParameterNotFound)
+    }
+}
+
+#[cfg(test)]
+mod test;
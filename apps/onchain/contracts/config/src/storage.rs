@@ -0,0 +1,19 @@
+use soroban_sdk::{contracttype, Address, Symbol};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Governance,    // -> Address
+    Param(Symbol), // name -> ConfigValue
+}
+
+/// A protocol parameter. Typed variants let readers reject a value of the
+/// wrong kind instead of misreading it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigValue {
+    U32(u32),
+    I128(i128),
+    Bool(bool),
+    Address(Address),
+}
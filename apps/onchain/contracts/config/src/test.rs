@@ -0,0 +1,216 @@
+use crate::{ConfigContract, ConfigContractClient, ConfigError, ConfigValue};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    Address, Env, IntoVal,
+};
+
+/// Contract that reads its fee from the registry instead of hardcoding it
+#[contract]
+struct MockConsumer;
+
+#[contractimpl]
+impl MockConsumer {
+    pub fn fee_for(env: Env, config: Address, amount: i128) -> i128 {
+        let bps = ConfigContractClient::new(&env, &config).get_u32(&symbol_short!("fee_bps"));
+        amount * bps as i128 / 10_000
+    }
+}
+
+struct Setup<'a> {
+    client: ConfigContractClient<'a>,
+    governance: Address,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    let governance = Address::generate(env);
+    let contract_id = env.register(ConfigContract, ());
+    let client = ConfigContractClient::new(env, &contract_id);
+    client.mock_all_auths().initialize(&governance);
+
+    Setup { client, governance }
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+
+    assert_eq!(setup.client.get_governance(), setup.governance);
+
+    let result = setup.client.try_initialize(&setup.governance);
+    assert_eq!(result, Err(Ok(ConfigError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_initialize_requires_governance() {
+    let env = Env::default();
+    let contract_id = env.register(ConfigContract, ());
+    let client = ConfigContractClient::new(&env, &contract_id);
+    let governance = Address::generate(&env);
+
+    // Someone watching the deployment cannot claim the registry for an
+    // address that never agreed to it
+    let result = client.try_initialize(&governance);
+    assert!(result.is_err());
+    assert_eq!(
+        client.try_get_governance(),
+        Err(Ok(ConfigError::NotInitialized))
+    );
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &governance,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "initialize",
+                args: (governance.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .initialize(&governance);
+    assert_eq!(client.get_governance(), governance);
+}
+
+#[test]
+fn test_set_and_get() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup_test(&env);
+    let treasury = Address::generate(&env);
+
+    setup
+        .client
+        .set(&symbol_short!("fee_bps"), &ConfigValue::U32(250));
+    setup
+        .client
+        .set(&symbol_short!("max_amt"), &ConfigValue::I128(1_000_000));
+    setup
+        .client
+        .set(&symbol_short!("paused"), &ConfigValue::Bool(false));
+    setup.client.set(
+        &symbol_short!("treasury"),
+        &ConfigValue::Address(treasury.clone()),
+    );
+
+    assert_eq!(setup.client.get_u32(&symbol_short!("fee_bps")), 250);
+    assert_eq!(setup.client.get_i128(&symbol_short!("max_amt")), 1_000_000);
+    assert!(!setup.client.get_bool(&symbol_short!("paused")));
+    assert_eq!(
+        setup.client.get_address(&symbol_short!("treasury")),
+        treasury
+    );
+    assert_eq!(
+        setup.client.get(&symbol_short!("fee_bps")),
+        Some(ConfigValue::U32(250))
+    );
+
+    // Overwriting replaces the value
+    setup
+        .client
+        .set(&symbol_short!("fee_bps"), &ConfigValue::U32(100));
+    assert_eq!(setup.client.get_u32(&symbol_short!("fee_bps")), 100);
+}
+
+#[test]
+fn test_typed_get_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup_test(&env);
+
+    setup
+        .client
+        .set(&symbol_short!("fee_bps"), &ConfigValue::U32(250));
+
+    let result = setup.client.try_get_i128(&symbol_short!("fee_bps"));
+    assert_eq!(result, Err(Ok(ConfigError::TypeMismatch)));
+
+    let result = setup.client.try_get_u32(&symbol_short!("missing"));
+    assert_eq!(result, Err(Ok(ConfigError::ParameterNotFound)));
+    assert_eq!(setup.client.get(&symbol_short!("missing")), None);
+}
+
+#[test]
+fn test_remove() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup_test(&env);
+
+    setup
+        .client
+        .set(&symbol_short!("fee_bps"), &ConfigValue::U32(250));
+    setup.client.remove(&symbol_short!("fee_bps"));
+    assert_eq!(setup.client.get(&symbol_short!("fee_bps")), None);
+
+    let result = setup.client.try_remove(&symbol_short!("fee_bps"));
+    assert_eq!(result, Err(Ok(ConfigError::ParameterNotFound)));
+}
+
+#[test]
+fn test_only_governance_can_write() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let stranger = Address::generate(&env);
+    let name = symbol_short!("fee_bps");
+    let value = ConfigValue::U32(250);
+
+    let result = setup
+        .client
+        .mock_auths(&[MockAuth {
+            address: &stranger,
+            invoke: &MockAuthInvoke {
+                contract: &setup.client.address,
+                fn_name: "set",
+                args: (name.clone(), value.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_set(&name, &value);
+    assert!(result.is_err());
+
+    setup
+        .client
+        .mock_auths(&[MockAuth {
+            address: &setup.governance,
+            invoke: &MockAuthInvoke {
+                contract: &setup.client.address,
+                fn_name: "set",
+                args: (name.clone(), value.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .set(&name, &value);
+    assert_eq!(setup.client.get(&name), Some(value));
+}
+
+#[test]
+fn test_set_governance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup_test(&env);
+    let new_governance = Address::generate(&env);
+
+    setup.client.set_governance(&new_governance);
+    assert_eq!(setup.client.get_governance(), new_governance);
+}
+
+#[test]
+fn test_consumer_reads_parameter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup_test(&env);
+
+    let consumer_id = env.register(MockConsumer, ());
+    let consumer = MockConsumerClient::new(&env, &consumer_id);
+
+    setup
+        .client
+        .set(&symbol_short!("fee_bps"), &ConfigValue::U32(250));
+    assert_eq!(consumer.fee_for(&setup.client.address, &10_000), 250);
+
+    // A governance change takes effect without touching the consumer
+    setup
+        .client
+        .set(&symbol_short!("fee_bps"), &ConfigValue::U32(50));
+    assert_eq!(consumer.fee_for(&setup.client.address, &10_000), 50);
+}
@@ -7,6 +7,7 @@ mod storage;
 mod token;
 
 use errors::CrowdfundError;
+use lumenpulse_pausable::Pausable;
 use math::{sqrt_scaled, unscale};
 use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
 use storage::{DataKey, ProjectData};
@@ -117,6 +118,7 @@ impl CrowdfundVaultContract {
         project_id: u64,
         amount: i128,
     ) -> Result<(), CrowdfundError> {
+        lumenpulse_pausable::when_not_paused(&env);
         // Check if contract is initialized
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(CrowdfundError::NotInitialized);
@@ -255,6 +257,7 @@ impl CrowdfundVaultContract {
 
     /// Withdraw funds from a project (owner only, requires milestone approval)
     pub fn withdraw(env: Env, project_id: u64, amount: i128) -> Result<(), CrowdfundError> {
+        lumenpulse_pausable::when_not_paused(&env);
         // Check if contract is initialized
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(CrowdfundError::NotInitialized);
@@ -577,6 +580,7 @@ impl CrowdfundVaultContract {
 
     /// Distribute matching funds from matching pool to project balance
     pub fn distribute_match(env: Env, project_id: u64) -> Result<i128, CrowdfundError> {
+        lumenpulse_pausable::when_not_paused(&env);
         // Check if contract is initialized
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(CrowdfundError::NotInitialized);
@@ -700,5 +704,13 @@ impl CrowdfundVaultContract {
     }
 }
 
+impl Pausable for CrowdfundVaultContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(CrowdfundVaultContract);
+
 #[cfg(test)]
 mod test;
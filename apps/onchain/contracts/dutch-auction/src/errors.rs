@@ -0,0 +1,14 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AuctionError {
+    AuctionNotFound = 1,
+    InvalidAmount = 2,
+    InvalidCurve = 3,
+    NotStarted = 4,
+    AuctionClosed = 5,
+    InsufficientSupply = 6,
+    PriceAboveMax = 7,
+}
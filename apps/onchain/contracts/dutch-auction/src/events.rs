@@ -0,0 +1,32 @@
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionCreatedEvent {
+    #[topic]
+    pub seller: Address,
+    pub auction_id: u64,
+    pub sale_token: Address,
+    pub payment_token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurchasedEvent {
+    #[topic]
+    pub auction_id: u64,
+    #[topic]
+    pub buyer: Address,
+    pub amount: i128,
+    pub price: i128,
+    pub cost: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionClosedEvent {
+    #[topic]
+    pub auction_id: u64,
+    pub unsold: i128,
+}
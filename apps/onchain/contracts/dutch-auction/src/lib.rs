@@ -0,0 +1,221 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod math;
+mod storage;
+mod token;
+
+use errors::AuctionError;
+use soroban_sdk::{contract, contractimpl, Address, Env};
+use storage::{Auction, DataKey, Decay, PriceCurve};
+
+pub use math::PRICE_SCALE;
+
+/// Descending-price auction for treasury sales. The seller deposits a lot
+/// of sale tokens and the price per token falls along a curve from the
+/// start price to a floor. Buyers take any part of the lot at the current
+/// price, paid in the auction's payment token straight to the seller.
+///
+/// There is no circuit breaker, as the contract has no admin to hold one:
+/// each lot answers only to its seller, who can close the auction and take
+/// back the unsold part at any time.
+#[contract]
+pub struct DutchAuctionContract;
+
+#[contractimpl]
+impl DutchAuctionContract {
+    /// Put `amount` of `sale_token` up for sale, priced in `payment_token`
+    /// along `curve`. The lot is deposited now and stays on sale at the
+    /// floor price until it sells out or the seller closes the auction.
+    pub fn create_auction(
+        env: Env,
+        seller: Address,
+        sale_token: Address,
+        payment_token: Address,
+        amount: i128,
+        curve: PriceCurve,
+    ) -> Result<u64, AuctionError> {
+        seller.require_auth();
+
+        if amount <= 0 {
+            return Err(AuctionError::InvalidAmount);
+        }
+        Self::validate_curve(&env, &curve)?;
+
+        token::transfer(
+            &env,
+            &sale_token,
+            &seller,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let auction_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextAuctionId)
+            .unwrap_or(0);
+        let auction = Auction {
+            id: auction_id,
+            seller: seller.clone(),
+            sale_token: sale_token.clone(),
+            payment_token: payment_token.clone(),
+            remaining: amount,
+            sold: 0,
+            proceeds: 0,
+            curve,
+            closed: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(auction_id), &auction);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextAuctionId, &(auction_id + 1));
+
+        events::AuctionCreatedEvent {
+            seller,
+            auction_id,
+            sale_token,
+            payment_token,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(auction_id)
+    }
+
+    /// Buy `amount` sale tokens at the current price, paying no more than
+    /// `max_price` per token. Returns the cost in payment tokens.
+    pub fn buy(
+        env: Env,
+        buyer: Address,
+        auction_id: u64,
+        amount: i128,
+        max_price: i128,
+    ) -> Result<i128, AuctionError> {
+        buyer.require_auth();
+
+        let mut auction = Self::get_auction(env.clone(), auction_id)?;
+        if auction.closed {
+            return Err(AuctionError::AuctionClosed);
+        }
+        let now = env.ledger().timestamp();
+        if now < auction.curve.start_time {
+            return Err(AuctionError::NotStarted);
+        }
+        if amount <= 0 {
+            return Err(AuctionError::InvalidAmount);
+        }
+        if amount > auction.remaining {
+            return Err(AuctionError::InsufficientSupply);
+        }
+
+        let price = math::price_at(&auction.curve, now);
+        if price > max_price {
+            return Err(AuctionError::PriceAboveMax);
+        }
+        let cost = math::cost(amount, price).ok_or(AuctionError::InvalidAmount)?;
+
+        auction.remaining -= amount;
+        auction.sold += amount;
+        auction.proceeds += cost;
+        auction.closed = auction.remaining == 0;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(auction_id), &auction);
+
+        token::transfer(&env, &auction.payment_token, &buyer, &auction.seller, &cost);
+        token::transfer(
+            &env,
+            &auction.sale_token,
+            &env.current_contract_address(),
+            &buyer,
+            &amount,
+        );
+
+        events::PurchasedEvent {
+            auction_id,
+            buyer,
+            amount,
+            price,
+            cost,
+        }
+        .publish(&env);
+
+        Ok(cost)
+    }
+
+    /// End the auction early and return the unsold part of the lot to the
+    /// seller. Returns the amount returned.
+    pub fn close(env: Env, auction_id: u64) -> Result<i128, AuctionError> {
+        let mut auction = Self::get_auction(env.clone(), auction_id)?;
+        auction.seller.require_auth();
+        if auction.closed {
+            return Err(AuctionError::AuctionClosed);
+        }
+
+        let unsold = auction.remaining;
+        auction.remaining = 0;
+        auction.closed = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(auction_id), &auction);
+
+        if unsold > 0 {
+            token::transfer(
+                &env,
+                &auction.sale_token,
+                &env.current_contract_address(),
+                &auction.seller,
+                &unsold,
+            );
+        }
+
+        events::AuctionClosedEvent { auction_id, unsold }.publish(&env);
+
+        Ok(unsold)
+    }
+
+    /// Current price per whole sale token, scaled by `PRICE_SCALE`
+    pub fn current_price(env: Env, auction_id: u64) -> Result<i128, AuctionError> {
+        let auction = Self::get_auction(env.clone(), auction_id)?;
+        Ok(math::price_at(&auction.curve, env.ledger().timestamp()))
+    }
+
+    /// What buying `amount` sale tokens would cost right now
+    pub fn quote(env: Env, auction_id: u64, amount: i128) -> Result<i128, AuctionError> {
+        if amount <= 0 {
+            return Err(AuctionError::InvalidAmount);
+        }
+        let price = Self::current_price(env, auction_id)?;
+        math::cost(amount, price).ok_or(AuctionError::InvalidAmount)
+    }
+
+    /// Get an auction by ID
+    pub fn get_auction(env: Env, auction_id: u64) -> Result<Auction, AuctionError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Auction(auction_id))
+            .ok_or(AuctionError::AuctionNotFound)
+    }
+}
+
+impl DutchAuctionContract {
+    fn validate_curve(env: &Env, curve: &PriceCurve) -> Result<(), AuctionError> {
+        if curve.floor_price <= 0 || curve.start_price < curve.floor_price {
+            return Err(AuctionError::InvalidCurve);
+        }
+        if curve.duration == 0 || curve.start_time < env.ledger().timestamp() {
+            return Err(AuctionError::InvalidCurve);
+        }
+        if curve.decay == Decay::Exponential(0) {
+            return Err(AuctionError::InvalidCurve);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
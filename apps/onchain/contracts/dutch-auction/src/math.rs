@@ -0,0 +1,52 @@
+//! Descending price curve math
+
+use crate::storage::{Decay, PriceCurve};
+use lumenpulse_fixed::{mul_div, Rounding};
+
+/// Prices are quoted per whole sale token with 7 decimals
+pub const PRICE_SCALE: i128 = 10_000_000;
+
+/// Price on `curve` at `now`. Before the start it is the start price, and
+/// at or after the end of the duration it is the floor.
+pub fn price_at(curve: &PriceCurve, now: u64) -> i128 {
+    let elapsed = now.saturating_sub(curve.start_time);
+    if elapsed >= curve.duration {
+        return curve.floor_price;
+    }
+
+    let premium = curve.start_price - curve.floor_price;
+    let remaining = match curve.decay {
+        Decay::Linear => floor(
+            premium,
+            (curve.duration - elapsed) as i128,
+            curve.duration as i128,
+        ),
+        Decay::Exponential(half_life) => halve(premium, elapsed, half_life),
+    };
+    curve.floor_price + remaining
+}
+
+/// `value` halved once per `half_life` seconds in `elapsed`, interpolated
+/// linearly within the current half-life so the curve has no steps. Every
+/// step rounds down, so the result is deterministic.
+fn halve(value: i128, elapsed: u64, half_life: u64) -> i128 {
+    let halvings = elapsed / half_life;
+    if halvings >= 127 {
+        return 0;
+    }
+    let high = value >> halvings;
+    let low = high >> 1;
+    let into = (elapsed % half_life) as i128;
+    high - floor(high - low, into, half_life as i128)
+}
+
+/// Cost of `amount` sale tokens at `price`, rounded up so the seller never
+/// gets less than the quoted price
+pub fn cost(amount: i128, price: i128) -> Option<i128> {
+    mul_div(amount, price, PRICE_SCALE, Rounding::Up)
+}
+
+/// Compute `a * b / denominator`, rounding down
+fn floor(a: i128, b: i128, denominator: i128) -> i128 {
+    mul_div(a, b, denominator, Rounding::Down).expect("arithmetic overflow")
+}
@@ -0,0 +1,49 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextAuctionId, // -> u64
+    Auction(u64),  // auction_id -> Auction
+}
+
+/// How the price falls from the start price towards the floor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Decay {
+    /// Falls at a constant rate, reaching the floor at the end of the
+    /// duration
+    Linear,
+    /// The premium over the floor halves every given number of seconds,
+    /// and is dropped at the end of the duration
+    Exponential(u64),
+}
+
+/// Price per whole sale token, scaled by `PRICE_SCALE`, over time
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceCurve {
+    pub start_price: i128,
+    pub floor_price: i128,
+    pub start_time: u64,
+    /// Seconds until the price reaches the floor
+    pub duration: u64,
+    pub decay: Decay,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auction {
+    pub id: u64,
+    pub seller: Address,
+    pub sale_token: Address,
+    /// Token buyers pay in
+    pub payment_token: Address,
+    /// Sale tokens still for sale
+    pub remaining: i128,
+    pub sold: i128,
+    /// Payment tokens paid to the seller
+    pub proceeds: i128,
+    pub curve: PriceCurve,
+    pub closed: bool,
+}
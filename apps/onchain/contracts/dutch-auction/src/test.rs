@@ -0,0 +1,184 @@
+use crate::errors::AuctionError;
+use crate::storage::{Decay, PriceCurve};
+use crate::{DutchAuctionContract, DutchAuctionContractClient, PRICE_SCALE};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const START: u64 = 1_000;
+const DURATION: u64 = 1_000;
+
+struct Setup<'a> {
+    client: DutchAuctionContractClient<'a>,
+    seller: Address,
+    buyer: Address,
+    sale: TokenClient<'a>,
+    usdc: TokenClient<'a>,
+}
+
+fn create_token<'a>(env: &Env, holder: &Address, amount: i128) -> TokenClient<'a> {
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &sac.address()).mint(holder, &amount);
+    TokenClient::new(env, &sac.address())
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let seller = Address::generate(env);
+    let buyer = Address::generate(env);
+    let sale = create_token(env, &seller, 1_000);
+    let usdc = create_token(env, &buyer, 100_000);
+
+    let contract_id = env.register(DutchAuctionContract, ());
+    let client = DutchAuctionContractClient::new(env, &contract_id);
+
+    Setup {
+        client,
+        seller,
+        buyer,
+        sale,
+        usdc,
+    }
+}
+
+fn curve(decay: Decay) -> PriceCurve {
+    // From 100 down to 20 payment units per sale token
+    PriceCurve {
+        start_price: 100 * PRICE_SCALE,
+        floor_price: 20 * PRICE_SCALE,
+        start_time: START,
+        duration: DURATION,
+        decay,
+    }
+}
+
+#[test]
+fn test_price_follows_curve() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let linear = s.client.create_auction(
+        &s.seller,
+        &s.sale.address,
+        &s.usdc.address,
+        &500,
+        &curve(Decay::Linear),
+    );
+    let exponential = s.client.create_auction(
+        &s.seller,
+        &s.sale.address,
+        &s.usdc.address,
+        &500,
+        &curve(Decay::Exponential(200)),
+    );
+
+    // Before the start the price holds at the start price
+    assert_eq!(s.client.current_price(&linear), 100 * PRICE_SCALE);
+
+    env.ledger().set_timestamp(START + 250);
+    assert_eq!(s.client.current_price(&linear), 80 * PRICE_SCALE);
+    // One and a quarter half-lives: the premium of 80 halves to 40, then
+    // falls a quarter of the way to 20
+    assert_eq!(s.client.current_price(&exponential), 55 * PRICE_SCALE);
+
+    env.ledger().set_timestamp(START + 400);
+    assert_eq!(s.client.current_price(&exponential), 40 * PRICE_SCALE);
+
+    // Both reach the floor at the end and stay there
+    env.ledger().set_timestamp(START + DURATION);
+    assert_eq!(s.client.current_price(&linear), 20 * PRICE_SCALE);
+    assert_eq!(s.client.current_price(&exponential), 20 * PRICE_SCALE);
+    env.ledger().set_timestamp(START + 5 * DURATION);
+    assert_eq!(s.client.current_price(&linear), 20 * PRICE_SCALE);
+}
+
+#[test]
+fn test_buy_and_close() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let auction_id = s.client.create_auction(
+        &s.seller,
+        &s.sale.address,
+        &s.usdc.address,
+        &1_000,
+        &curve(Decay::Linear),
+    );
+    assert_eq!(s.sale.balance(&s.client.address), 1_000);
+
+    assert_eq!(
+        s.client
+            .try_buy(&s.buyer, &auction_id, &10, &(100 * PRICE_SCALE)),
+        Err(Ok(AuctionError::NotStarted))
+    );
+
+    env.ledger().set_timestamp(START + 500);
+    assert_eq!(s.client.quote(&auction_id, &100), 6_000);
+    // Slippage protection
+    assert_eq!(
+        s.client
+            .try_buy(&s.buyer, &auction_id, &100, &(59 * PRICE_SCALE)),
+        Err(Ok(AuctionError::PriceAboveMax))
+    );
+    assert_eq!(
+        s.client
+            .buy(&s.buyer, &auction_id, &100, &(60 * PRICE_SCALE)),
+        6_000
+    );
+    assert_eq!(s.sale.balance(&s.buyer), 100);
+    assert_eq!(s.usdc.balance(&s.seller), 6_000);
+
+    assert_eq!(
+        s.client
+            .try_buy(&s.buyer, &auction_id, &901, &(60 * PRICE_SCALE)),
+        Err(Ok(AuctionError::InsufficientSupply))
+    );
+
+    // The seller takes back what is unsold
+    assert_eq!(s.client.close(&auction_id), 900);
+    assert_eq!(s.sale.balance(&s.seller), 900);
+    let auction = s.client.get_auction(&auction_id);
+    assert_eq!((auction.sold, auction.proceeds), (100, 6_000));
+    assert!(auction.closed);
+    assert_eq!(
+        s.client
+            .try_buy(&s.buyer, &auction_id, &1, &(60 * PRICE_SCALE)),
+        Err(Ok(AuctionError::AuctionClosed))
+    );
+}
+
+#[test]
+fn test_sell_out_and_rounding() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let mut cheap = curve(Decay::Linear);
+    cheap.start_price = PRICE_SCALE / 3;
+    cheap.floor_price = PRICE_SCALE / 3;
+    let auction_id =
+        s.client
+            .create_auction(&s.seller, &s.sale.address, &s.usdc.address, &10, &cheap);
+
+    env.ledger().set_timestamp(START);
+    // 10 tokens at 0.3333333 cost 3.333333, rounded up in the seller's favour
+    assert_eq!(s.client.buy(&s.buyer, &auction_id, &10, &PRICE_SCALE), 4);
+    assert!(s.client.get_auction(&auction_id).closed);
+
+    let mut bad = curve(Decay::Linear);
+    bad.floor_price = 200 * PRICE_SCALE;
+    assert_eq!(
+        s.client
+            .try_create_auction(&s.seller, &s.sale.address, &s.usdc.address, &10, &bad),
+        Err(Ok(AuctionError::InvalidCurve))
+    );
+    assert_eq!(
+        s.client.try_create_auction(
+            &s.seller,
+            &s.sale.address,
+            &s.usdc.address,
+            &10,
+            &curve(Decay::Exponential(0)),
+        ),
+        Err(Ok(AuctionError::InvalidCurve))
+    );
+}
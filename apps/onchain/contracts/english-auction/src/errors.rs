@@ -0,0 +1,16 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AuctionError {
+    AuctionNotFound = 1,
+    InvalidAmount = 2,
+    InvalidTerms = 3,
+    BidTooLow = 4,
+    AuctionEnded = 5,
+    AuctionNotEnded = 6,
+    AuctionClosed = 7,
+    HasBids = 8,
+    NothingToWithdraw = 9,
+}
@@ -0,0 +1,61 @@
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionCreatedEvent {
+    #[topic]
+    pub seller: Address,
+    pub auction_id: u64,
+    pub lot_token: Address,
+    pub lot_amount: i128,
+    pub bid_token: Address,
+    pub end_time: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BidPlacedEvent {
+    #[topic]
+    pub auction_id: u64,
+    #[topic]
+    pub bidder: Address,
+    pub amount: i128,
+    pub end_time: u64,
+}
+
+/// A beaten bid was credited to its bidder to withdraw
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BidRefundedEvent {
+    #[topic]
+    pub auction_id: u64,
+    #[topic]
+    pub bidder: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundWithdrawnEvent {
+    #[topic]
+    pub bidder: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionSettledEvent {
+    #[topic]
+    pub auction_id: u64,
+    /// None when the auction ended without bids and the lot went back
+    pub winner: Option<Address>,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionCancelledEvent {
+    #[topic]
+    pub auction_id: u64,
+}
@@ -0,0 +1,302 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::AuctionError;
+use soroban_sdk::{contract, contractimpl, Address, Env};
+use storage::{Auction, AuctionStatus, AuctionTerms, DataKey};
+
+/// Longest a late bid may push the end back by
+const MAX_EXTENSION: u64 = 86_400;
+
+/// Ascending auction for a lot of tokens. Bids are escrowed by the
+/// contract, and a beaten bid is credited to its bidder, who takes it out
+/// with `withdraw`. Paying it back inside the call that beats it would let
+/// a bidder whose account cannot receive the token block every later bid.
+/// Bids close to the end extend the auction so a bidder can always answer
+/// a last-second bid.
+///
+/// There is no circuit breaker, as the contract has no admin to hold one.
+/// A pause would also strand bidders' escrowed bids behind whoever held
+/// it, while `settle` and `withdraw` only ever pay the seller and the
+/// bidders their own bids.
+#[contract]
+pub struct EnglishAuctionContract;
+
+#[contractimpl]
+impl EnglishAuctionContract {
+    /// Auction `lot_amount` of `lot_token` for bids in `bid_token`. The lot
+    /// is deposited now.
+    pub fn create_auction(
+        env: Env,
+        seller: Address,
+        lot_token: Address,
+        lot_amount: i128,
+        bid_token: Address,
+        terms: AuctionTerms,
+    ) -> Result<u64, AuctionError> {
+        seller.require_auth();
+
+        if lot_amount <= 0 {
+            return Err(AuctionError::InvalidAmount);
+        }
+        if terms.reserve_price <= 0
+            || terms.min_increment <= 0
+            || terms.end_time <= env.ledger().timestamp()
+            || terms.extension > MAX_EXTENSION
+        {
+            return Err(AuctionError::InvalidTerms);
+        }
+
+        token::transfer(
+            &env,
+            &lot_token,
+            &seller,
+            &env.current_contract_address(),
+            &lot_amount,
+        );
+
+        let auction_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextAuctionId)
+            .unwrap_or(0);
+        let end_time = terms.end_time;
+        let auction = Auction {
+            id: auction_id,
+            seller: seller.clone(),
+            lot_token: lot_token.clone(),
+            lot_amount,
+            bid_token: bid_token.clone(),
+            terms,
+            end_time,
+            highest_bidder: None,
+            highest_bid: 0,
+            status: AuctionStatus::Open,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(auction_id), &auction);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextAuctionId, &(auction_id + 1));
+
+        events::AuctionCreatedEvent {
+            seller,
+            auction_id,
+            lot_token,
+            lot_amount,
+            bid_token,
+            end_time,
+        }
+        .publish(&env);
+
+        Ok(auction_id)
+    }
+
+    /// Bid `amount`, which must meet the reserve or beat the highest bid by
+    /// the minimum increment. The bid is escrowed and the bid it beats is
+    /// credited to its bidder to `withdraw`. Returns the auction's end time
+    /// afterwards.
+    pub fn bid(
+        env: Env,
+        bidder: Address,
+        auction_id: u64,
+        amount: i128,
+    ) -> Result<u64, AuctionError> {
+        bidder.require_auth();
+
+        let mut auction = Self::get_auction(env.clone(), auction_id)?;
+        if auction.status != AuctionStatus::Open {
+            return Err(AuctionError::AuctionClosed);
+        }
+        let now = env.ledger().timestamp();
+        if now >= auction.end_time {
+            return Err(AuctionError::AuctionEnded);
+        }
+        let min_bid = match auction.highest_bidder {
+            Some(_) => auction
+                .highest_bid
+                .checked_add(auction.terms.min_increment)
+                .ok_or(AuctionError::InvalidAmount)?,
+            None => auction.terms.reserve_price,
+        };
+        if amount < min_bid {
+            return Err(AuctionError::BidTooLow);
+        }
+
+        token::transfer(
+            &env,
+            &auction.bid_token,
+            &bidder,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let outbid = auction.highest_bidder.replace(bidder.clone());
+        let refund = auction.highest_bid;
+        auction.highest_bid = amount;
+        if auction.end_time - now < auction.terms.extension {
+            auction.end_time = now
+                .checked_add(auction.terms.extension)
+                .ok_or(AuctionError::InvalidTerms)?;
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(auction_id), &auction);
+
+        if let Some(outbid) = outbid {
+            let key = DataKey::Refund(outbid.clone(), auction.bid_token.clone());
+            let credited: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(credited + refund));
+            events::BidRefundedEvent {
+                auction_id,
+                bidder: outbid,
+                amount: refund,
+            }
+            .publish(&env);
+        }
+
+        events::BidPlacedEvent {
+            auction_id,
+            bidder,
+            amount,
+            end_time: auction.end_time,
+        }
+        .publish(&env);
+
+        Ok(auction.end_time)
+    }
+
+    /// Settle an auction that has ended: the lot goes to the highest
+    /// bidder and the winning bid to the seller, or the lot goes back to
+    /// the seller if nobody bid. Anyone may call this once the auction has
+    /// ended, as the lot and the bid can only go to the winner and the
+    /// seller.
+    pub fn settle(env: Env, auction_id: u64) -> Result<(), AuctionError> {
+        let mut auction = Self::get_auction(env.clone(), auction_id)?;
+        if auction.status != AuctionStatus::Open {
+            return Err(AuctionError::AuctionClosed);
+        }
+        if env.ledger().timestamp() < auction.end_time {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+
+        auction.status = AuctionStatus::Settled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(auction_id), &auction);
+
+        let contract_address = env.current_contract_address();
+        let lot_recipient = match &auction.highest_bidder {
+            Some(winner) => {
+                token::transfer(
+                    &env,
+                    &auction.bid_token,
+                    &contract_address,
+                    &auction.seller,
+                    &auction.highest_bid,
+                );
+                winner
+            }
+            None => &auction.seller,
+        };
+        token::transfer(
+            &env,
+            &auction.lot_token,
+            &contract_address,
+            lot_recipient,
+            &auction.lot_amount,
+        );
+
+        events::AuctionSettledEvent {
+            auction_id,
+            winner: auction.highest_bidder,
+            amount: auction.highest_bid,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Cancel an auction nobody has bid on yet and return the lot
+    pub fn cancel(env: Env, auction_id: u64) -> Result<(), AuctionError> {
+        let mut auction = Self::get_auction(env.clone(), auction_id)?;
+        auction.seller.require_auth();
+        if auction.status != AuctionStatus::Open {
+            return Err(AuctionError::AuctionClosed);
+        }
+        if auction.highest_bidder.is_some() {
+            return Err(AuctionError::HasBids);
+        }
+
+        auction.status = AuctionStatus::Cancelled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Auction(auction_id), &auction);
+
+        token::transfer(
+            &env,
+            &auction.lot_token,
+            &env.current_contract_address(),
+            &auction.seller,
+            &auction.lot_amount,
+        );
+
+        events::AuctionCancelledEvent { auction_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Pay `bidder` every beaten bid of theirs in `token`. Returns the
+    /// amount paid.
+    pub fn withdraw(env: Env, bidder: Address, token: Address) -> Result<i128, AuctionError> {
+        bidder.require_auth();
+
+        let key = DataKey::Refund(bidder.clone(), token.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount == 0 {
+            return Err(AuctionError::NothingToWithdraw);
+        }
+        env.storage().persistent().remove(&key);
+
+        token::transfer(
+            &env,
+            &token,
+            &env.current_contract_address(),
+            &bidder,
+            &amount,
+        );
+
+        events::RefundWithdrawnEvent {
+            bidder,
+            token,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Beaten bids of `bidder` in `token` waiting to be withdrawn
+    pub fn refund_of(env: Env, bidder: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Refund(bidder, token))
+            .unwrap_or(0)
+    }
+
+    /// Get an auction by ID
+    pub fn get_auction(env: Env, auction_id: u64) -> Result<Auction, AuctionError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Auction(auction_id))
+            .ok_or(AuctionError::AuctionNotFound)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,51 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextAuctionId,            // -> u64
+    Auction(u64),             // auction_id -> Auction
+    Refund(Address, Address), // (bidder, bid token) -> i128 beaten bids to withdraw
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionTerms {
+    /// Lowest acceptable first bid
+    pub reserve_price: i128,
+    /// How much each bid must beat the highest bid by
+    pub min_increment: i128,
+    /// Scheduled end, before any extensions
+    pub end_time: u64,
+    /// A bid landing less than this many seconds before the end pushes the
+    /// end to this many seconds after the bid; at most a day
+    pub extension: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AuctionStatus {
+    Open,
+    Settled,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auction {
+    pub id: u64,
+    pub seller: Address,
+    /// Token being auctioned
+    pub lot_token: Address,
+    pub lot_amount: i128,
+    /// Token bids are paid in
+    pub bid_token: Address,
+    pub terms: AuctionTerms,
+    /// Current end, including extensions
+    pub end_time: u64,
+    pub highest_bidder: Option<Address>,
+    /// Escrowed by the contract until the bid is settled, or beaten and
+    /// withdrawn
+    pub highest_bid: i128,
+    pub status: AuctionStatus,
+}
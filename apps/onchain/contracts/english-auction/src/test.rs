@@ -0,0 +1,181 @@
+use crate::errors::AuctionError;
+use crate::storage::{AuctionStatus, AuctionTerms};
+use crate::{EnglishAuctionContract, EnglishAuctionContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const END: u64 = 1_000;
+const EXTENSION: u64 = 100;
+
+struct Setup<'a> {
+    client: EnglishAuctionContractClient<'a>,
+    seller: Address,
+    alice: Address,
+    bob: Address,
+    lot: TokenClient<'a>,
+    usdc: TokenClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let seller = Address::generate(env);
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+
+    let lot = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &lot.address()).mint(&seller, &10);
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(env));
+    let usdc_admin = StellarAssetClient::new(env, &usdc.address());
+    usdc_admin.mint(&alice, &10_000);
+    usdc_admin.mint(&bob, &10_000);
+
+    let contract_id = env.register(EnglishAuctionContract, ());
+    let client = EnglishAuctionContractClient::new(env, &contract_id);
+
+    Setup {
+        client,
+        seller,
+        alice,
+        bob,
+        lot: TokenClient::new(env, &lot.address()),
+        usdc: TokenClient::new(env, &usdc.address()),
+    }
+}
+
+fn create_auction(s: &Setup) -> u64 {
+    let terms = AuctionTerms {
+        reserve_price: 1_000,
+        min_increment: 100,
+        end_time: END,
+        extension: EXTENSION,
+    };
+    s.client
+        .create_auction(&s.seller, &s.lot.address, &10, &s.usdc.address, &terms)
+}
+
+#[test]
+fn test_outbid_is_credited_and_winner_settles() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let auction_id = create_auction(&s);
+    assert_eq!(s.lot.balance(&s.client.address), 10);
+
+    assert_eq!(
+        s.client.try_bid(&s.alice, &auction_id, &999),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.bid(&s.alice, &auction_id, &1_000);
+    assert_eq!(s.usdc.balance(&s.alice), 9_000);
+
+    // A bid has to beat the highest by the increment
+    assert_eq!(
+        s.client.try_bid(&s.bob, &auction_id, &1_099),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.bid(&s.bob, &auction_id, &1_100);
+    assert_eq!(s.client.refund_of(&s.alice, &s.usdc.address), 1_000);
+    assert_eq!(s.usdc.balance(&s.client.address), 2_100);
+
+    // The beaten bid is the bidder's to withdraw, once
+    assert_eq!(s.client.withdraw(&s.alice, &s.usdc.address), 1_000);
+    assert_eq!(s.usdc.balance(&s.alice), 10_000);
+    assert_eq!(
+        s.client.try_withdraw(&s.alice, &s.usdc.address),
+        Err(Ok(AuctionError::NothingToWithdraw))
+    );
+
+    assert_eq!(
+        s.client.try_settle(&auction_id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    env.ledger().set_timestamp(END);
+    assert_eq!(
+        s.client.try_bid(&s.alice, &auction_id, &2_000),
+        Err(Ok(AuctionError::AuctionEnded))
+    );
+    s.client.settle(&auction_id);
+
+    assert_eq!(s.lot.balance(&s.bob), 10);
+    assert_eq!(s.usdc.balance(&s.seller), 1_100);
+    assert_eq!(
+        s.client.get_auction(&auction_id).status,
+        AuctionStatus::Settled
+    );
+    assert_eq!(
+        s.client.try_settle(&auction_id),
+        Err(Ok(AuctionError::AuctionClosed))
+    );
+}
+
+#[test]
+fn test_late_bid_extends_auction() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let auction_id = create_auction(&s);
+
+    // Outside the window the end stays put
+    env.ledger().set_timestamp(END - EXTENSION);
+    assert_eq!(s.client.bid(&s.alice, &auction_id, &1_000), END);
+
+    env.ledger().set_timestamp(END - 10);
+    assert_eq!(s.client.bid(&s.bob, &auction_id, &1_100), END + 90);
+
+    // The original end no longer closes the auction
+    env.ledger().set_timestamp(END);
+    assert_eq!(
+        s.client.try_settle(&auction_id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    assert_eq!(s.client.bid(&s.alice, &auction_id, &1_200), END + EXTENSION);
+
+    env.ledger().set_timestamp(END + EXTENSION);
+    s.client.settle(&auction_id);
+    assert_eq!(s.lot.balance(&s.alice), 10);
+    // Alice was beaten once before winning
+    assert_eq!(s.client.refund_of(&s.alice, &s.usdc.address), 1_000);
+    s.client.withdraw(&s.bob, &s.usdc.address);
+    assert_eq!(s.usdc.balance(&s.bob), 10_000);
+}
+
+#[test]
+fn test_extension_is_bounded() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let terms = AuctionTerms {
+        reserve_price: 1_000,
+        min_increment: 100,
+        end_time: END,
+        extension: 86_401,
+    };
+    let result =
+        s.client
+            .try_create_auction(&s.seller, &s.lot.address, &10, &s.usdc.address, &terms);
+    assert_eq!(result, Err(Ok(AuctionError::InvalidTerms)));
+}
+
+#[test]
+fn test_unsold_lot_returns_to_seller() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let auction_id = create_auction(&s);
+    env.ledger().set_timestamp(END);
+    s.client.settle(&auction_id);
+    assert_eq!(s.lot.balance(&s.seller), 10);
+
+    env.ledger().set_timestamp(0);
+    let auction_id = create_auction(&s);
+    s.client.cancel(&auction_id);
+    assert_eq!(s.lot.balance(&s.seller), 10);
+
+    let auction_id = create_auction(&s);
+    s.client.bid(&s.alice, &auction_id, &1_000);
+    assert_eq!(
+        s.client.try_cancel(&auction_id),
+        Err(Ok(AuctionError::HasBids))
+    );
+}
@@ -0,0 +1 @@
+pub use lumenpulse_escrow_interface::EscrowError;
@@ -0,0 +1,178 @@
+use lumenpulse_badges::BadgeGate;
+use lumenpulse_oracle::OracleConfig;
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowCreatedEvent {
+    #[topic]
+    pub depositor: Address,
+    #[topic]
+    pub payee: Address,
+    pub escrow_id: u64,
+    pub arbiter: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleasedEvent {
+    #[topic]
+    pub payee: Address,
+    #[topic]
+    pub escrow_id: u64,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundedEvent {
+    #[topic]
+    pub depositor: Address,
+    #[topic]
+    pub escrow_id: u64,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeRaisedEvent {
+    #[topic]
+    pub raised_by: Address,
+    #[topic]
+    pub escrow_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeResolvedEvent {
+    #[topic]
+    pub arbiter: Address,
+    #[topic]
+    pub escrow_id: u64,
+    pub released_to_payee: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneApprovedEvent {
+    #[topic]
+    pub payee: Address,
+    #[topic]
+    pub escrow_id: u64,
+    pub milestone: u32,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartialReleasedEvent {
+    #[topic]
+    pub payee: Address,
+    #[topic]
+    pub escrow_id: u64,
+    pub amount: i128,
+    pub remaining: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributedEvent {
+    #[topic]
+    pub contributor: Address,
+    #[topic]
+    pub escrow_id: u64,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbitrationConfiguredEvent {
+    pub bond_token: Address,
+    pub min_bond: i128,
+    pub appeal_window: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondStakedEvent {
+    #[topic]
+    pub arbiter: Address,
+    pub amount: i128,
+    pub staked: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondUnstakedEvent {
+    #[topic]
+    pub arbiter: Address,
+    pub amount: i128,
+    pub staked: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RulingIssuedEvent {
+    #[topic]
+    pub arbiter: Address,
+    #[topic]
+    pub escrow_id: u64,
+    pub release_to_payee: bool,
+    pub appeal_deadline: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RulingAppealedEvent {
+    #[topic]
+    pub appellant: Address,
+    #[topic]
+    pub escrow_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppealDecidedEvent {
+    #[topic]
+    pub escrow_id: u64,
+    pub release_to_payee: bool,
+    pub overturned: bool,
+    pub slashed: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfigUpdatedEvent {
+    pub protocol_bps: u32,
+    pub arbiter_bps: u32,
+    pub recipient: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeesChargedEvent {
+    #[topic]
+    pub escrow_id: u64,
+    pub protocol_fee: i128,
+    pub arbiter_fee: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleSetEvent {
+    pub config: OracleConfig,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbiterGateSetEvent {
+    pub gate: Option<BadgeGate>,
+}
@@ -0,0 +1,1163 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod math;
+mod oracle;
+mod storage;
+mod token;
+
+use errors::EscrowError;
+use lumenpulse_badges::{BadgeGate, GateError};
+use lumenpulse_oracle::OracleConfig;
+use lumenpulse_pausable::Pausable;
+use lumenpulse_upgradeable::Upgradeable;
+use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+use storage::{
+    ArbiterBond, ArbitrationConfig, Contribution, DataKey, EscrowData, EscrowStatus, FeeConfig,
+    FeeQuote, FeeRate, Milestone, Ruling,
+};
+
+/// Upper bound on milestones per escrow
+const MAX_MILESTONES: u32 = 20;
+
+/// Upper bound on distinct contributors per escrow
+const MAX_CONTRIBUTORS: u32 = 50;
+
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+    /// Initialize the contract with an admin address
+    pub fn initialize(env: Env, admin: Address) -> Result<(), EscrowError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(EscrowError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        // Store admin address and initialize escrow ID counter
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::NextEscrowId, &0u64);
+
+        events::InitializedEvent { admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Fund a new escrow for `payee`. The arbiter may settle the escrow either
+    /// way if the parties raise a dispute.
+    pub fn create_escrow(
+        env: Env,
+        depositor: Address,
+        payee: Address,
+        arbiter: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<u64, EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::open_escrow(&env, depositor, payee, arbiter, token, amount, None)
+    }
+
+    /// Fund an escrow that becomes releasable to the payee by anyone via
+    /// `finalize` once `release_after` has passed, unless a dispute has been
+    /// raised before then
+    pub fn create_timed_escrow(
+        env: Env,
+        depositor: Address,
+        payee: Address,
+        arbiter: Address,
+        token: Address,
+        amount: i128,
+        release_after: u64,
+    ) -> Result<u64, EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        if release_after <= env.ledger().timestamp() {
+            return Err(EscrowError::InvalidDeadline);
+        }
+        Self::open_escrow(
+            &env,
+            depositor,
+            payee,
+            arbiter,
+            token,
+            amount,
+            Some(release_after),
+        )
+    }
+
+    /// Fund an escrow split into milestones. Each milestone is paid to the
+    /// payee as the depositor approves it; the escrow total is their sum.
+    pub fn create_milestone_escrow(
+        env: Env,
+        depositor: Address,
+        payee: Address,
+        arbiter: Address,
+        token: Address,
+        amounts: Vec<i128>,
+    ) -> Result<u64, EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        if amounts.is_empty() {
+            return Err(EscrowError::InvalidAmount);
+        }
+        if amounts.len() > MAX_MILESTONES {
+            return Err(EscrowError::TooManyMilestones);
+        }
+
+        let mut total: i128 = 0;
+        let mut milestones = Vec::new(&env);
+        for amount in amounts.iter() {
+            if amount <= 0 {
+                return Err(EscrowError::InvalidAmount);
+            }
+            total = total
+                .checked_add(amount)
+                .ok_or(EscrowError::InvalidAmount)?;
+            milestones.push_back(Milestone {
+                amount,
+                approved: false,
+            });
+        }
+
+        let escrow_id = Self::open_escrow(&env, depositor, payee, arbiter, token, total, None)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Milestones(escrow_id), &milestones);
+
+        Ok(escrow_id)
+    }
+
+    /// Approve a milestone and pay its amount to the payee (depositor only).
+    /// The escrow is released once every milestone has been approved.
+    pub fn approve_milestone(env: Env, escrow_id: u64, milestone: u32) -> Result<(), EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let mut escrow = Self::load_escrow(&env, escrow_id)?;
+
+        // Require depositor authorization
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(EscrowError::NotActive);
+        }
+
+        let mut milestones: Vec<Milestone> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Milestones(escrow_id))
+            .ok_or(EscrowError::NotMilestoneEscrow)?;
+        let mut entry = milestones
+            .get(milestone)
+            .ok_or(EscrowError::MilestoneNotFound)?;
+        if entry.approved {
+            return Err(EscrowError::MilestoneAlreadyApproved);
+        }
+
+        entry.approved = true;
+        milestones.set(milestone, entry.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Milestones(escrow_id), &milestones);
+
+        Self::pay_payee(&env, &mut escrow, entry.amount);
+        if escrow.released == escrow.amount {
+            escrow.status = EscrowStatus::Released;
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+
+        events::MilestoneApprovedEvent {
+            payee: escrow.payee,
+            escrow_id,
+            milestone,
+            amount: entry.amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Release the escrowed funds to the payee (depositor only)
+    pub fn release(env: Env, escrow_id: u64) -> Result<(), EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let escrow = Self::load_escrow(&env, escrow_id)?;
+
+        // Require depositor authorization
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(EscrowError::NotActive);
+        }
+
+        Self::settle(&env, escrow, true);
+        Ok(())
+    }
+
+    /// Add funds to an active escrow. Each contributor's share is tracked so
+    /// that a refund returns contributions proportionally. Milestone
+    /// escrows have a fixed total and cannot take contributions.
+    pub fn contribute(
+        env: Env,
+        contributor: Address,
+        escrow_id: u64,
+        amount: i128,
+    ) -> Result<(), EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let mut escrow = Self::load_escrow(&env, escrow_id)?;
+
+        // Require contributor authorization
+        contributor.require_auth();
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(EscrowError::NotActive);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Milestones(escrow_id))
+        {
+            return Err(EscrowError::HasMilestones);
+        }
+        if amount <= 0 {
+            return Err(EscrowError::InvalidAmount);
+        }
+        if contributor == escrow.payee || contributor == escrow.arbiter {
+            return Err(EscrowError::InvalidParties);
+        }
+
+        let mut contributions = Self::load_contributions(&env, escrow_id);
+        match contributions
+            .iter()
+            .position(|c| c.contributor == contributor)
+        {
+            Some(i) => {
+                let i = i as u32;
+                let mut existing = contributions.get_unchecked(i);
+                existing.amount += amount;
+                contributions.set(i, existing);
+            }
+            None => {
+                if contributions.len() >= MAX_CONTRIBUTORS {
+                    return Err(EscrowError::TooManyContributors);
+                }
+                contributions.push_back(Contribution {
+                    contributor: contributor.clone(),
+                    amount,
+                });
+            }
+        }
+
+        token::transfer(
+            &env,
+            &escrow.token,
+            &contributor,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        escrow.amount += amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Contributions(escrow_id), &contributions);
+
+        events::ContributedEvent {
+            contributor,
+            escrow_id,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Release part of the escrow to the payee, keeping the rest escrowed
+    /// (depositor only). Releasing the full remainder closes the escrow.
+    /// Milestone escrows are paid through `approve_milestone` instead.
+    pub fn release_partial(env: Env, escrow_id: u64, amount: i128) -> Result<(), EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let mut escrow = Self::load_escrow(&env, escrow_id)?;
+
+        // Require depositor authorization
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(EscrowError::NotActive);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Milestones(escrow_id))
+        {
+            return Err(EscrowError::HasMilestones);
+        }
+        if amount <= 0 {
+            return Err(EscrowError::InvalidAmount);
+        }
+        if amount > escrow.amount - escrow.released {
+            return Err(EscrowError::ExceedsRemaining);
+        }
+
+        Self::pay_payee(&env, &mut escrow, amount);
+        if escrow.released == escrow.amount {
+            escrow.status = EscrowStatus::Released;
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+
+        events::PartialReleasedEvent {
+            payee: escrow.payee,
+            escrow_id,
+            amount,
+            remaining: escrow.amount - escrow.released,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the escrowed funds to the depositor (payee only)
+    pub fn refund(env: Env, escrow_id: u64) -> Result<(), EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let escrow = Self::load_escrow(&env, escrow_id)?;
+
+        // Require payee authorization
+        escrow.payee.require_auth();
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(EscrowError::NotActive);
+        }
+
+        Self::settle(&env, escrow, false);
+        Ok(())
+    }
+
+    /// Raise a dispute, freezing the escrow until the arbiter rules
+    /// (depositor or payee only). A timed escrow can only be disputed
+    /// before `release_after`.
+    pub fn dispute(env: Env, caller: Address, escrow_id: u64) -> Result<(), EscrowError> {
+        let mut escrow = Self::load_escrow(&env, escrow_id)?;
+
+        if caller != escrow.depositor && caller != escrow.payee {
+            return Err(EscrowError::Unauthorized);
+        }
+
+        // Require caller authorization
+        caller.require_auth();
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(EscrowError::NotActive);
+        }
+        // Past the delivery window the payee is owed the funds, whether or
+        // not anyone has called `finalize` yet
+        if escrow
+            .release_after
+            .is_some_and(|release_after| env.ledger().timestamp() >= release_after)
+        {
+            return Err(EscrowError::DisputeWindowClosed);
+        }
+
+        // Under bonded arbitration the dispute is only assigned to an arbiter
+        // with enough free bond, which stays locked until the dispute closes
+        if let Some(config) = Self::arbitration_config(&env) {
+            let mut bond = Self::arbiter_bond(&env, &escrow.arbiter);
+            if bond.staked - bond.locked < config.min_bond {
+                return Err(EscrowError::InsufficientBond);
+            }
+            bond.locked += config.min_bond;
+            env.storage()
+                .persistent()
+                .set(&DataKey::ArbiterBond(escrow.arbiter.clone()), &bond);
+            env.storage()
+                .persistent()
+                .set(&DataKey::DisputeBond(escrow_id), &config.min_bond);
+        }
+
+        escrow.status = EscrowStatus::Disputed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+
+        events::DisputeRaisedEvent {
+            raised_by: caller,
+            escrow_id,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Settle a disputed escrow in favour of the payee or the depositor
+    /// (arbiter only). Bonded disputes record the ruling instead, which takes
+    /// effect through `execute_ruling` once the appeal window has passed.
+    /// While an arbiter gate is set, the arbiter must hold its badge.
+    pub fn resolve(env: Env, escrow_id: u64, release_to_payee: bool) -> Result<(), EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let mut escrow = Self::load_escrow(&env, escrow_id)?;
+
+        // Require arbiter authorization
+        escrow.arbiter.require_auth();
+        Self::check_arbiter_badge(&env, &escrow.arbiter)?;
+
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(EscrowError::NotDisputed);
+        }
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::DisputeBond(escrow_id))
+        {
+            let config =
+                Self::arbitration_config(&env).ok_or(EscrowError::ArbitrationNotConfigured)?;
+            let appeal_deadline = env.ledger().timestamp() + config.appeal_window;
+            env.storage().persistent().set(
+                &DataKey::Ruling(escrow_id),
+                &Ruling {
+                    release_to_payee,
+                    appeal_deadline,
+                    appellant: None,
+                },
+            );
+            escrow.status = EscrowStatus::Ruled;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Escrow(escrow_id), &escrow);
+
+            events::RulingIssuedEvent {
+                arbiter: escrow.arbiter,
+                escrow_id,
+                release_to_payee,
+                appeal_deadline,
+            }
+            .publish(&env);
+
+            return Ok(());
+        }
+
+        let arbiter = escrow.arbiter.clone();
+        Self::settle(&env, escrow, release_to_payee);
+
+        events::DisputeResolvedEvent {
+            arbiter,
+            escrow_id,
+            released_to_payee: release_to_payee,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Configure bonded arbitration (admin only). Applies to disputes raised
+    /// from now on. The bond token cannot change while any bond is staked,
+    /// which is held in the old token.
+    pub fn configure_arbitration(
+        env: Env,
+        admin: Address,
+        bond_token: Address,
+        min_bond: i128,
+        appeal_window: u64,
+    ) -> Result<(), EscrowError> {
+        Self::require_admin(&env, &admin)?;
+
+        if min_bond <= 0 || appeal_window == 0 {
+            return Err(EscrowError::InvalidConfig);
+        }
+        if let Some(current) = Self::arbitration_config(&env) {
+            if current.bond_token != bond_token && Self::bonds_staked(&env) > 0 {
+                return Err(EscrowError::BondsOutstanding);
+            }
+        }
+
+        let config = ArbitrationConfig {
+            bond_token: bond_token.clone(),
+            min_bond,
+            appeal_window,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::ArbitrationConfig, &config);
+
+        events::ArbitrationConfiguredEvent {
+            bond_token,
+            min_bond,
+            appeal_window,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Stake bond so the arbiter can take disputes
+    pub fn stake_bond(env: Env, arbiter: Address, amount: i128) -> Result<(), EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let config = Self::arbitration_config(&env).ok_or(EscrowError::ArbitrationNotConfigured)?;
+
+        // Require arbiter authorization
+        arbiter.require_auth();
+
+        if amount <= 0 {
+            return Err(EscrowError::InvalidAmount);
+        }
+
+        token::transfer(
+            &env,
+            &config.bond_token,
+            &arbiter,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let mut bond = Self::arbiter_bond(&env, &arbiter);
+        bond.staked += amount;
+        Self::add_bonds_staked(&env, amount);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ArbiterBond(arbiter.clone()), &bond);
+
+        events::BondStakedEvent {
+            arbiter,
+            amount,
+            staked: bond.staked,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Withdraw bond that is not backing an open dispute
+    pub fn unstake_bond(env: Env, arbiter: Address, amount: i128) -> Result<(), EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let config = Self::arbitration_config(&env).ok_or(EscrowError::ArbitrationNotConfigured)?;
+
+        // Require arbiter authorization
+        arbiter.require_auth();
+
+        if amount <= 0 {
+            return Err(EscrowError::InvalidAmount);
+        }
+
+        let mut bond = Self::arbiter_bond(&env, &arbiter);
+        if amount > bond.staked - bond.locked {
+            return Err(EscrowError::InsufficientBond);
+        }
+        bond.staked -= amount;
+        Self::add_bonds_staked(&env, -amount);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ArbiterBond(arbiter.clone()), &bond);
+
+        token::transfer(
+            &env,
+            &config.bond_token,
+            &env.current_contract_address(),
+            &arbiter,
+            &amount,
+        );
+
+        events::BondUnstakedEvent {
+            arbiter,
+            amount,
+            staked: bond.staked,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Appeal a bonded ruling to the admin before its deadline
+    /// (depositor or payee only)
+    pub fn appeal(env: Env, caller: Address, escrow_id: u64) -> Result<(), EscrowError> {
+        let mut escrow = Self::load_escrow(&env, escrow_id)?;
+
+        if caller != escrow.depositor && caller != escrow.payee {
+            return Err(EscrowError::Unauthorized);
+        }
+
+        // Require caller authorization
+        caller.require_auth();
+
+        if escrow.status != EscrowStatus::Ruled {
+            return Err(EscrowError::NotRuled);
+        }
+
+        let mut ruling = Self::load_ruling(&env, escrow_id)?;
+        if env.ledger().timestamp() >= ruling.appeal_deadline {
+            return Err(EscrowError::AppealWindowClosed);
+        }
+
+        ruling.appellant = Some(caller.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Ruling(escrow_id), &ruling);
+        escrow.status = EscrowStatus::Appealed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+
+        events::RulingAppealedEvent {
+            appellant: caller,
+            escrow_id,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Carry out an unappealed ruling, releasing the arbiter's bond. Anyone
+    /// may call this once the appeal window has passed; funds only go to
+    /// the ruled party.
+    pub fn execute_ruling(env: Env, escrow_id: u64) -> Result<(), EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let escrow = Self::load_escrow(&env, escrow_id)?;
+
+        if escrow.status != EscrowStatus::Ruled {
+            return Err(EscrowError::NotRuled);
+        }
+
+        let ruling = Self::load_ruling(&env, escrow_id)?;
+        if env.ledger().timestamp() < ruling.appeal_deadline {
+            return Err(EscrowError::AppealWindowOpen);
+        }
+
+        Self::unlock_bond(&env, &escrow);
+        let arbiter = escrow.arbiter.clone();
+        Self::settle(&env, escrow, ruling.release_to_payee);
+
+        events::DisputeResolvedEvent {
+            arbiter,
+            escrow_id,
+            released_to_payee: ruling.release_to_payee,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Decide an appealed ruling (admin only). Overturning the ruling slashes
+    /// the arbiter's locked bond to the appellant; upholding releases it.
+    pub fn decide_appeal(
+        env: Env,
+        admin: Address,
+        escrow_id: u64,
+        release_to_payee: bool,
+    ) -> Result<(), EscrowError> {
+        Self::require_admin(&env, &admin)?;
+
+        let escrow = Self::load_escrow(&env, escrow_id)?;
+        if escrow.status != EscrowStatus::Appealed {
+            return Err(EscrowError::NotAppealed);
+        }
+
+        let ruling = Self::load_ruling(&env, escrow_id)?;
+        let overturned = ruling.release_to_payee != release_to_payee;
+
+        let locked = Self::unlock_bond(&env, &escrow);
+        let mut slashed = 0;
+        if overturned {
+            let config =
+                Self::arbitration_config(&env).ok_or(EscrowError::ArbitrationNotConfigured)?;
+            let appellant = ruling.appellant.ok_or(EscrowError::NotAppealed)?;
+
+            let mut bond = Self::arbiter_bond(&env, &escrow.arbiter);
+            bond.staked -= locked;
+            Self::add_bonds_staked(&env, -locked);
+            env.storage()
+                .persistent()
+                .set(&DataKey::ArbiterBond(escrow.arbiter.clone()), &bond);
+
+            token::transfer(
+                &env,
+                &config.bond_token,
+                &env.current_contract_address(),
+                &appellant,
+                &locked,
+            );
+            slashed = locked;
+        }
+
+        Self::settle(&env, escrow, release_to_payee);
+
+        events::AppealDecidedEvent {
+            escrow_id,
+            release_to_payee,
+            overturned,
+            slashed,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Set the protocol and arbiter fees withheld from payouts (admin only)
+    pub fn set_fee_config(env: Env, admin: Address, config: FeeConfig) -> Result<(), EscrowError> {
+        Self::require_admin(&env, &admin)?;
+
+        let valid = |rate: &FeeRate| {
+            rate.bps as i128 <= math::BPS_DENOMINATOR && rate.min >= 0 && rate.min <= rate.max
+        };
+        if !valid(&config.protocol)
+            || !valid(&config.arbiter)
+            || config.protocol.bps + config.arbiter.bps > math::BPS_DENOMINATOR as u32
+        {
+            return Err(EscrowError::InvalidFeeConfig);
+        }
+
+        env.storage().instance().set(&DataKey::FeeConfig, &config);
+
+        events::FeeConfigUpdatedEvent {
+            protocol_bps: config.protocol.bps,
+            arbiter_bps: config.arbiter.bps,
+            recipient: config.recipient,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the fee configuration, if any
+    pub fn get_fee_config(env: Env) -> Option<FeeConfig> {
+        env.storage().instance().get(&DataKey::FeeConfig)
+    }
+
+    /// Fees that would be withheld from a payout of `amount` to the payee
+    pub fn quote_fees(env: Env, amount: i128) -> FeeQuote {
+        Self::quote(&Self::get_fee_config(env), amount)
+    }
+
+    /// Only let arbiters holding `gate`'s badge rule on disputes, or drop
+    /// the requirement with `None` (admin only)
+    pub fn set_arbiter_gate(
+        env: Env,
+        admin: Address,
+        gate: Option<BadgeGate>,
+    ) -> Result<(), EscrowError> {
+        Self::require_admin(&env, &admin)?;
+
+        match &gate {
+            Some(gate) => env.storage().instance().set(&DataKey::ArbiterGate, gate),
+            None => env.storage().instance().remove(&DataKey::ArbiterGate),
+        }
+
+        events::ArbiterGateSetEvent { gate }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Badge arbiters must hold to rule, if any
+    pub fn get_arbiter_gate(env: Env) -> Option<BadgeGate> {
+        env.storage().instance().get(&DataKey::ArbiterGate)
+    }
+
+    /// Set the SEP-40 price feeds used for price conversions on this
+    /// network and the oldest price they may give (admin only)
+    pub fn set_oracle(env: Env, admin: Address, config: OracleConfig) -> Result<(), EscrowError> {
+        Self::require_admin(&env, &admin)?;
+
+        if !config.is_valid() {
+            return Err(EscrowError::InvalidOracleConfig);
+        }
+        env.storage().instance().set(&DataKey::Oracle, &config);
+
+        events::OracleSetEvent { config }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Price feeds used for price conversions, if any
+    pub fn get_oracle(env: Env) -> Option<OracleConfig> {
+        env.storage().instance().get(&DataKey::Oracle)
+    }
+
+    /// Value of `amount` of `token` in the feeds' base asset, at the latest
+    /// fresh price. Falls back through the configured feeds.
+    pub fn quote_value(env: Env, token: Address, amount: i128) -> Result<i128, EscrowError> {
+        let config = Self::get_oracle(env.clone()).ok_or(EscrowError::OracleNotSet)?;
+        oracle::value_of(&env, &config, &token, amount)
+    }
+
+    /// Get an arbiter's staked and locked bond
+    pub fn get_arbiter_bond(env: Env, arbiter: Address) -> ArbiterBond {
+        Self::arbiter_bond(&env, &arbiter)
+    }
+
+    /// Get the pending ruling on a bonded dispute
+    pub fn get_ruling(env: Env, escrow_id: u64) -> Result<Ruling, EscrowError> {
+        Self::load_ruling(&env, escrow_id)
+    }
+
+    /// Release a timed escrow to the payee once its deadline has passed.
+    /// Needs no authorization, as the deadline was agreed at creation;
+    /// disputed escrows are left to the arbiter.
+    pub fn finalize(env: Env, escrow_id: u64) -> Result<(), EscrowError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let escrow = Self::load_escrow(&env, escrow_id)?;
+
+        let release_after = escrow.release_after.ok_or(EscrowError::NotTimed)?;
+        if escrow.status != EscrowStatus::Active {
+            return Err(EscrowError::NotActive);
+        }
+        if env.ledger().timestamp() < release_after {
+            return Err(EscrowError::DeadlineNotReached);
+        }
+
+        Self::settle(&env, escrow, true);
+        Ok(())
+    }
+
+    /// Get escrow data
+    pub fn get_escrow(env: Env, escrow_id: u64) -> Result<EscrowData, EscrowError> {
+        Self::load_escrow(&env, escrow_id)
+    }
+
+    /// Get the amount still held in escrow
+    pub fn remaining_amount(env: Env, escrow_id: u64) -> Result<i128, EscrowError> {
+        let escrow = Self::load_escrow(&env, escrow_id)?;
+        Ok(escrow.amount - escrow.released)
+    }
+
+    /// Get each contributor's share of an escrow
+    pub fn get_contributions(env: Env, escrow_id: u64) -> Result<Vec<Contribution>, EscrowError> {
+        Self::load_escrow(&env, escrow_id)?;
+        Ok(Self::load_contributions(&env, escrow_id))
+    }
+
+    /// Get the milestones of a milestone escrow
+    pub fn get_milestones(env: Env, escrow_id: u64) -> Result<Vec<Milestone>, EscrowError> {
+        Self::load_escrow(&env, escrow_id)?;
+        env.storage()
+            .persistent()
+            .get(&DataKey::Milestones(escrow_id))
+            .ok_or(EscrowError::NotMilestoneEscrow)
+    }
+
+    /// Get admin address
+    pub fn get_admin(env: Env) -> Result<Address, EscrowError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(EscrowError::NotInitialized)
+    }
+}
+
+impl EscrowContract {
+    fn require_initialized(env: &Env) -> Result<(), EscrowError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(EscrowError::NotInitialized);
+        }
+        Ok(())
+    }
+
+    fn open_escrow(
+        env: &Env,
+        depositor: Address,
+        payee: Address,
+        arbiter: Address,
+        token: Address,
+        amount: i128,
+        release_after: Option<u64>,
+    ) -> Result<u64, EscrowError> {
+        Self::require_initialized(env)?;
+
+        // Require depositor authorization
+        depositor.require_auth();
+
+        if amount <= 0 {
+            return Err(EscrowError::InvalidAmount);
+        }
+
+        // The arbiter must be independent of both parties
+        if depositor == payee || arbiter == depositor || arbiter == payee {
+            return Err(EscrowError::InvalidParties);
+        }
+
+        // Transfer tokens from depositor to contract
+        token::transfer(
+            env,
+            &token,
+            &depositor,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        // Get next escrow ID
+        let escrow_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextEscrowId)
+            .unwrap_or(0);
+
+        let escrow = EscrowData {
+            id: escrow_id,
+            depositor: depositor.clone(),
+            payee: payee.clone(),
+            arbiter: arbiter.clone(),
+            token: token.clone(),
+            amount,
+            released: 0,
+            status: EscrowStatus::Active,
+            release_after,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextEscrowId, &(escrow_id + 1));
+
+        // The depositor is the first contributor
+        let mut contributions = Vec::new(env);
+        contributions.push_back(Contribution {
+            contributor: depositor.clone(),
+            amount,
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::Contributions(escrow_id), &contributions);
+
+        events::EscrowCreatedEvent {
+            depositor,
+            payee,
+            escrow_id,
+            arbiter,
+            token,
+            amount,
+        }
+        .publish(env);
+
+        Ok(escrow_id)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), EscrowError> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(EscrowError::NotInitialized)?;
+        if *admin != stored_admin {
+            return Err(EscrowError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn check_arbiter_badge(env: &Env, arbiter: &Address) -> Result<(), EscrowError> {
+        let Some(gate) = Self::get_arbiter_gate(env.clone()) else {
+            return Ok(());
+        };
+        lumenpulse_badges::requires_badge(env, &gate, arbiter).map_err(|error| match error {
+            GateError::MissingBadge => EscrowError::MissingBadge,
+            GateError::Unavailable => EscrowError::BadgeUnavailable,
+        })
+    }
+
+    fn arbitration_config(env: &Env) -> Option<ArbitrationConfig> {
+        env.storage().instance().get(&DataKey::ArbitrationConfig)
+    }
+
+    /// Total bond staked by all arbiters
+    fn bonds_staked(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::BondsStaked)
+            .unwrap_or(0)
+    }
+
+    fn add_bonds_staked(env: &Env, amount: i128) {
+        let total = Self::bonds_staked(env) + amount;
+        env.storage().instance().set(&DataKey::BondsStaked, &total);
+    }
+
+    fn arbiter_bond(env: &Env, arbiter: &Address) -> ArbiterBond {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ArbiterBond(arbiter.clone()))
+            .unwrap_or_default()
+    }
+
+    fn load_ruling(env: &Env, escrow_id: u64) -> Result<Ruling, EscrowError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Ruling(escrow_id))
+            .ok_or(EscrowError::NotRuled)
+    }
+
+    /// Release the bond locked for an escrow's dispute and return its amount
+    fn unlock_bond(env: &Env, escrow: &EscrowData) -> i128 {
+        let key = DataKey::DisputeBond(escrow.id);
+        let locked: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().remove(&key);
+
+        let mut bond = Self::arbiter_bond(env, &escrow.arbiter);
+        bond.locked -= locked;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ArbiterBond(escrow.arbiter.clone()), &bond);
+
+        locked
+    }
+
+    fn load_escrow(env: &Env, escrow_id: u64) -> Result<EscrowData, EscrowError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(EscrowError::EscrowNotFound)
+    }
+
+    /// Transfer part of the escrow to the payee and record it as released.
+    /// The caller persists the escrow.
+    fn pay_payee(env: &Env, escrow: &mut EscrowData, amount: i128) {
+        let config: Option<FeeConfig> = env.storage().instance().get(&DataKey::FeeConfig);
+        let quote = Self::quote(&config, amount);
+        let contract = env.current_contract_address();
+
+        token::transfer(
+            env,
+            &escrow.token,
+            &contract,
+            &escrow.payee,
+            &quote.net_amount,
+        );
+        if let Some(config) = config {
+            if quote.protocol_fee > 0 {
+                token::transfer(
+                    env,
+                    &escrow.token,
+                    &contract,
+                    &config.recipient,
+                    &quote.protocol_fee,
+                );
+            }
+            if quote.arbiter_fee > 0 {
+                token::transfer(
+                    env,
+                    &escrow.token,
+                    &contract,
+                    &escrow.arbiter,
+                    &quote.arbiter_fee,
+                );
+            }
+            events::FeesChargedEvent {
+                escrow_id: escrow.id,
+                protocol_fee: quote.protocol_fee,
+                arbiter_fee: quote.arbiter_fee,
+            }
+            .publish(env);
+        }
+        escrow.released += amount;
+    }
+
+    /// Split a payout into protocol fee, arbiter fee and the payee's net.
+    /// Minimums apply per payout, so neither fee can exceed what is left.
+    fn quote(config: &Option<FeeConfig>, amount: i128) -> FeeQuote {
+        let (protocol_fee, arbiter_fee) = match config {
+            Some(config) => {
+                let protocol = &config.protocol;
+                let arbiter = &config.arbiter;
+                let protocol_fee =
+                    math::capped_fee(amount, protocol.bps, protocol.min, protocol.max);
+                let arbiter_fee = math::capped_fee(amount, arbiter.bps, arbiter.min, arbiter.max)
+                    .min(amount - protocol_fee);
+                (protocol_fee, arbiter_fee)
+            }
+            None => (0, 0),
+        };
+
+        FeeQuote {
+            protocol_fee,
+            arbiter_fee,
+            net_amount: amount - protocol_fee - arbiter_fee,
+        }
+    }
+
+    /// Pay out whatever is still escrowed to the payee or back to the
+    /// depositor and close the escrow
+    fn settle(env: &Env, mut escrow: EscrowData, to_payee: bool) {
+        let remaining = escrow.amount - escrow.released;
+
+        if to_payee {
+            escrow.status = EscrowStatus::Released;
+            Self::pay_payee(env, &mut escrow, remaining);
+            events::ReleasedEvent {
+                payee: escrow.payee.clone(),
+                escrow_id: escrow.id,
+                amount: remaining,
+            }
+            .publish(env);
+        } else {
+            escrow.status = EscrowStatus::Refunded;
+            Self::refund_contributors(env, &escrow, remaining);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow.id), &escrow);
+    }
+
+    /// Return `remaining` to the contributors in proportion to what each put
+    /// in, each share rounded down. The depositor, as the first contributor,
+    /// takes the rounding dust.
+    fn refund_contributors(env: &Env, escrow: &EscrowData, remaining: i128) {
+        let contributions = Self::load_contributions(env, escrow.id);
+        let shares: Vec<i128> = Vec::from_iter(
+            env,
+            contributions
+                .iter()
+                .map(|c| math::pro_rata(remaining, c.amount, escrow.amount)),
+        );
+        let dust = remaining - shares.iter().sum::<i128>();
+
+        for (i, contribution) in contributions.iter().enumerate() {
+            let share = shares.get_unchecked(i as u32) + if i == 0 { dust } else { 0 };
+            if share == 0 {
+                continue;
+            }
+
+            token::transfer(
+                env,
+                &escrow.token,
+                &env.current_contract_address(),
+                &contribution.contributor,
+                &share,
+            );
+            events::RefundedEvent {
+                depositor: contribution.contributor,
+                escrow_id: escrow.id,
+                amount: share,
+            }
+            .publish(env);
+        }
+    }
+
+    fn load_contributions(env: &Env, escrow_id: u64) -> Vec<Contribution> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contributions(escrow_id))
+            .unwrap_or(Vec::new(env))
+    }
+}
+
+impl Upgradeable for EscrowContract {
+    fn upgrade_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_upgradeable::upgradeable!(EscrowContract);
+
+impl Pausable for EscrowContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(EscrowContract);
+
+#[cfg(test)]
+mod test;
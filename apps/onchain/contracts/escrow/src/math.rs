@@ -0,0 +1,20 @@
+//! Fee and refund arithmetic for escrow payouts
+
+pub use lumenpulse_fixed::BPS_DENOMINATOR;
+use lumenpulse_fixed::{bps, mul_div, Rounding};
+
+/// Fee for `amount` at `bps`, clamped to `[min, max]` and never more than
+/// `amount` itself
+pub fn capped_fee(amount: i128, rate: u32, min: i128, max: i128) -> i128 {
+    if amount <= 0 {
+        return 0;
+    }
+
+    let fee = bps(amount, rate, Rounding::Down).expect("arithmetic overflow");
+    fee.clamp(min, max).min(amount)
+}
+
+/// The share of `amount` due to `part` of `total`, rounded down
+pub fn pro_rata(amount: i128, part: i128, total: i128) -> i128 {
+    mul_div(amount, part, total, Rounding::Down).expect("arithmetic overflow")
+}
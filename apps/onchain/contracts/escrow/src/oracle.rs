@@ -0,0 +1,23 @@
+use crate::errors::EscrowError;
+use lumenpulse_oracle::{Asset, OracleConfig, OracleError};
+use soroban_sdk::{Address, Env};
+
+/// Value of `amount` of `token` in the feeds' base asset, at the latest
+/// price no older than the config allows
+pub fn value_of(
+    env: &Env,
+    config: &OracleConfig,
+    token: &Address,
+    amount: i128,
+) -> Result<i128, EscrowError> {
+    let asset = Asset::Stellar(token.clone());
+    let price = lumenpulse_oracle::fresh_price(env, config, &asset).map_err(price_error)?;
+    lumenpulse_oracle::to_base(amount, price.price, price.decimals).map_err(price_error)
+}
+
+fn price_error(error: OracleError) -> EscrowError {
+    match error {
+        OracleError::StalePrice => EscrowError::StalePrice,
+        _ => EscrowError::PriceUnavailable,
+    }
+}
@@ -0,0 +1,24 @@
+use soroban_sdk::{contracttype, Address};
+
+pub use lumenpulse_escrow_interface::{
+    ArbiterBond, ArbitrationConfig, Contribution, EscrowData, EscrowStatus, FeeConfig, FeeQuote,
+    FeeRate, Milestone, Ruling,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                // -> Address
+    NextEscrowId,         // -> u64
+    Escrow(u64),          // escrow_id -> EscrowData
+    Milestones(u64),      // escrow_id -> Vec<Milestone>
+    Contributions(u64),   // escrow_id -> Vec<Contribution>
+    ArbitrationConfig,    // -> ArbitrationConfig
+    ArbiterBond(Address), // arbiter -> ArbiterBond
+    BondsStaked,          // -> i128 total staked by all arbiters
+    DisputeBond(u64),     // escrow_id -> i128 bond locked for the dispute
+    Ruling(u64),          // escrow_id -> Ruling
+    FeeConfig,            // -> FeeConfig
+    Oracle,               // -> OracleConfig
+    ArbiterGate,          // -> BadgeGate
+}
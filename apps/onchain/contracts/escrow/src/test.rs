@@ -0,0 +1,926 @@
+use crate::errors::EscrowError;
+use crate::storage::{EscrowStatus, FeeConfig, FeeRate};
+use crate::{EscrowContract, EscrowContractClient};
+use badge::{BadgeContract, BadgeContractClient};
+use lumenpulse_badges::BadgeGate;
+use lumenpulse_escrow_interface::EscrowClient;
+use lumenpulse_oracle::{Asset, OracleConfig};
+use lumenpulse_test_utils::{assert_golden, golden::Transcript};
+use mock_oracle::{MockOracleContract, MockOracleContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, String,
+};
+
+fn create_token_contract<'a>(
+    env: &Env,
+    admin: &Address,
+) -> (TokenClient<'a>, StellarAssetClient<'a>) {
+    let contract_address = env.register_stellar_asset_contract_v2(admin.clone());
+    (
+        TokenClient::new(env, &contract_address.address()),
+        StellarAssetClient::new(env, &contract_address.address()),
+    )
+}
+
+struct Setup<'a> {
+    client: EscrowContractClient<'a>,
+    admin: Address,
+    depositor: Address,
+    payee: Address,
+    arbiter: Address,
+    token: TokenClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    let admin = Address::generate(env);
+    let depositor = Address::generate(env);
+    let payee = Address::generate(env);
+    let arbiter = Address::generate(env);
+
+    // Create token and fund the depositor
+    let (token, token_admin) = create_token_contract(env, &admin);
+    token_admin.mint(&depositor, &10_000_000);
+
+    // Register and initialize contract
+    let contract_id = env.register(EscrowContract, ());
+    let client = EscrowContractClient::new(env, &contract_id);
+    client.initialize(&admin);
+
+    Setup {
+        client,
+        admin,
+        depositor,
+        payee,
+        arbiter,
+        token,
+    }
+}
+
+/// USD feed with 7 decimals
+fn mock_oracle(env: &Env) -> MockOracleContractClient<'_> {
+    let oracle = MockOracleContractClient::new(env, &env.register(MockOracleContract, ()));
+    oracle.initialize(&Asset::Other(symbol_short!("USD")), &7, &300);
+    oracle
+}
+
+fn create_escrow(s: &Setup, amount: i128) -> u64 {
+    s.client.create_escrow(
+        &s.depositor,
+        &s.payee,
+        &s.arbiter,
+        &s.token.address,
+        &amount,
+    )
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+
+    assert_eq!(s.client.get_admin(), s.admin);
+    let result = s.client.try_initialize(&s.admin);
+    assert_eq!(result, Err(Ok(EscrowError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_create_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let escrow_id = create_escrow(&s, 1_000);
+
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.depositor, s.depositor);
+    assert_eq!(escrow.payee, s.payee);
+    assert_eq!(escrow.arbiter, s.arbiter);
+    assert_eq!(escrow.amount, 1_000);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(s.token.balance(&s.client.address), 1_000);
+}
+
+#[test]
+fn test_create_escrow_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+
+    let result =
+        s.client
+            .try_create_escrow(&s.depositor, &s.payee, &s.arbiter, &s.token.address, &0);
+    assert_eq!(result, Err(Ok(EscrowError::InvalidAmount)));
+
+    let result =
+        s.client
+            .try_create_escrow(&s.depositor, &s.payee, &s.payee, &s.token.address, &1_000);
+    assert_eq!(result, Err(Ok(EscrowError::InvalidParties)));
+}
+
+#[test]
+fn test_release_pays_payee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let escrow_id = create_escrow(&s, 1_000);
+
+    s.client.release(&escrow_id);
+    assert_eq!(s.token.balance(&s.payee), 1_000);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+
+    // A settled escrow cannot be settled again
+    let result = s.client.try_refund(&escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::NotActive)));
+}
+
+#[test]
+fn test_refund_returns_to_depositor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let escrow_id = create_escrow(&s, 1_000);
+
+    s.client.refund(&escrow_id);
+    assert_eq!(s.token.balance(&s.depositor), 10_000_000);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_dispute_resolved_by_arbiter() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let escrow_id = create_escrow(&s, 1_000);
+
+    // Resolution requires a dispute first
+    let result = s.client.try_resolve(&escrow_id, &true);
+    assert_eq!(result, Err(Ok(EscrowError::NotDisputed)));
+
+    s.client.dispute(&s.payee, &escrow_id);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Disputed
+    );
+
+    // Parties can no longer settle unilaterally
+    let result = s.client.try_release(&escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::NotActive)));
+
+    s.client.resolve(&escrow_id, &false);
+    assert_eq!(s.token.balance(&s.depositor), 10_000_000);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_ruling_requires_arbiter_badge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let badges = BadgeContractClient::new(&env, &env.register(BadgeContract, ()));
+    badges.initialize(&s.admin);
+    let badge_id = badges.create_badge(&s.admin, &String::from_str(&env, "arbiter"), &s.admin);
+    let gate = BadgeGate {
+        contract: badges.address.clone(),
+        badge_id,
+    };
+    s.client.set_arbiter_gate(&s.admin, &Some(gate.clone()));
+    assert_eq!(s.client.get_arbiter_gate(), Some(gate));
+
+    let escrow_id = create_escrow(&s, 1_000);
+    s.client.dispute(&s.payee, &escrow_id);
+    let result = s.client.try_resolve(&escrow_id, &true);
+    assert_eq!(result, Err(Ok(EscrowError::MissingBadge)));
+
+    badges.issue(&badge_id, &s.arbiter);
+    s.client.resolve(&escrow_id, &true);
+    assert_eq!(s.token.balance(&s.payee), 1_000);
+
+    // Clearing the gate lets any arbiter rule again
+    badges.revoke(&badge_id, &s.arbiter);
+    s.client.set_arbiter_gate(&s.admin, &None);
+    let escrow_id = create_escrow(&s, 1_000);
+    s.client.dispute(&s.payee, &escrow_id);
+    s.client.resolve(&escrow_id, &false);
+}
+
+#[test]
+fn test_dispute_only_by_parties() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let escrow_id = create_escrow(&s, 1_000);
+
+    let outsider = Address::generate(&env);
+    let result = s.client.try_dispute(&outsider, &escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::Unauthorized)));
+
+    let result = s.client.try_dispute(&s.arbiter, &escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::Unauthorized)));
+}
+
+#[test]
+fn test_escrow_not_found() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+
+    let result = s.client.try_release(&7);
+    assert_eq!(result, Err(Ok(EscrowError::EscrowNotFound)));
+}
+
+#[test]
+fn test_timed_escrow_finalizes_after_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let release_after = env.ledger().timestamp() + 7 * 86_400;
+    let escrow_id = s.client.create_timed_escrow(
+        &s.depositor,
+        &s.payee,
+        &s.arbiter,
+        &s.token.address,
+        &1_000,
+        &release_after,
+    );
+
+    let result = s.client.try_finalize(&escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::DeadlineNotReached)));
+
+    env.ledger().set_timestamp(release_after);
+    s.client.finalize(&escrow_id);
+    assert_eq!(s.token.balance(&s.payee), 1_000);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_timed_escrow_dispute_blocks_finalize() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let release_after = env.ledger().timestamp() + 86_400;
+    let escrow_id = s.client.create_timed_escrow(
+        &s.depositor,
+        &s.payee,
+        &s.arbiter,
+        &s.token.address,
+        &1_000,
+        &release_after,
+    );
+
+    s.client.dispute(&s.depositor, &escrow_id);
+
+    env.ledger().set_timestamp(release_after + 1);
+    let result = s.client.try_finalize(&escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::NotActive)));
+
+    // The arbiter still decides the outcome
+    s.client.resolve(&escrow_id, &true);
+    assert_eq!(s.token.balance(&s.payee), 1_000);
+}
+
+#[test]
+fn test_timed_escrow_cannot_be_disputed_after_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let release_after = env.ledger().timestamp() + 86_400;
+    let escrow_id = s.client.create_timed_escrow(
+        &s.depositor,
+        &s.payee,
+        &s.arbiter,
+        &s.token.address,
+        &1_000,
+        &release_after,
+    );
+
+    // Nobody has finalized yet, but the delivery window has passed
+    env.ledger().set_timestamp(release_after);
+    let result = s.client.try_dispute(&s.depositor, &escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::DisputeWindowClosed)));
+
+    s.client.finalize(&escrow_id);
+    assert_eq!(s.token.balance(&s.payee), 1_000);
+}
+
+#[test]
+fn test_finalize_requires_timed_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let escrow_id = create_escrow(&s, 1_000);
+
+    let result = s.client.try_finalize(&escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::NotTimed)));
+
+    let result = s.client.try_create_timed_escrow(
+        &s.depositor,
+        &s.payee,
+        &s.arbiter,
+        &s.token.address,
+        &1_000,
+        &env.ledger().timestamp(),
+    );
+    assert_eq!(result, Err(Ok(EscrowError::InvalidDeadline)));
+}
+
+#[test]
+fn test_milestone_escrow_pays_incrementally() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let escrow_id = s.client.create_milestone_escrow(
+        &s.depositor,
+        &s.payee,
+        &s.arbiter,
+        &s.token.address,
+        &vec![&env, 300, 700],
+    );
+    assert_eq!(s.client.get_escrow(&escrow_id).amount, 1_000);
+
+    s.client.approve_milestone(&escrow_id, &1);
+    assert_eq!(s.token.balance(&s.payee), 700);
+    let escrow = s.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.released, 700);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+
+    let result = s.client.try_approve_milestone(&escrow_id, &1);
+    assert_eq!(result, Err(Ok(EscrowError::MilestoneAlreadyApproved)));
+    let result = s.client.try_approve_milestone(&escrow_id, &2);
+    assert_eq!(result, Err(Ok(EscrowError::MilestoneNotFound)));
+
+    s.client.approve_milestone(&escrow_id, &0);
+    assert_eq!(s.token.balance(&s.payee), 1_000);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+    assert!(s
+        .client
+        .get_milestones(&escrow_id)
+        .iter()
+        .all(|m| m.approved));
+}
+
+#[test]
+fn test_milestone_escrow_refunds_unapproved_remainder() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let depositor_start = s.token.balance(&s.depositor);
+    let escrow_id = s.client.create_milestone_escrow(
+        &s.depositor,
+        &s.payee,
+        &s.arbiter,
+        &s.token.address,
+        &vec![&env, 400, 600],
+    );
+
+    s.client.approve_milestone(&escrow_id, &0);
+    s.client.dispute(&s.payee, &escrow_id);
+
+    // Milestones cannot be approved while disputed
+    let result = s.client.try_approve_milestone(&escrow_id, &1);
+    assert_eq!(result, Err(Ok(EscrowError::NotActive)));
+
+    s.client.resolve(&escrow_id, &false);
+    assert_eq!(s.token.balance(&s.payee), 400);
+    assert_eq!(s.token.balance(&s.depositor), depositor_start - 400);
+}
+
+#[test]
+fn test_milestone_escrow_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+
+    let result = s.client.try_create_milestone_escrow(
+        &s.depositor,
+        &s.payee,
+        &s.arbiter,
+        &s.token.address,
+        &vec![&env],
+    );
+    assert_eq!(result, Err(Ok(EscrowError::InvalidAmount)));
+
+    let result = s.client.try_create_milestone_escrow(
+        &s.depositor,
+        &s.payee,
+        &s.arbiter,
+        &s.token.address,
+        &vec![&env, 100, 0],
+    );
+    assert_eq!(result, Err(Ok(EscrowError::InvalidAmount)));
+
+    let escrow_id = create_escrow(&s, 1_000);
+    let result = s.client.try_approve_milestone(&escrow_id, &0);
+    assert_eq!(result, Err(Ok(EscrowError::NotMilestoneEscrow)));
+}
+
+#[test]
+fn test_release_partial_tracks_remaining() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let depositor_start = s.token.balance(&s.depositor);
+    let escrow_id = create_escrow(&s, 1_000);
+
+    s.client.release_partial(&escrow_id, &250);
+    assert_eq!(s.token.balance(&s.payee), 250);
+    assert_eq!(s.client.get_escrow(&escrow_id).released, 250);
+    assert_eq!(s.client.remaining_amount(&escrow_id), 750);
+
+    let result = s.client.try_release_partial(&escrow_id, &751);
+    assert_eq!(result, Err(Ok(EscrowError::ExceedsRemaining)));
+    let result = s.client.try_release_partial(&escrow_id, &0);
+    assert_eq!(result, Err(Ok(EscrowError::InvalidAmount)));
+
+    // A refund only returns what is still escrowed
+    s.client.refund(&escrow_id);
+    assert_eq!(s.token.balance(&s.depositor), depositor_start - 250);
+    assert_eq!(s.client.remaining_amount(&escrow_id), 750);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Refunded
+    );
+}
+
+#[test]
+fn test_release_partial_full_remainder_closes_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let escrow_id = create_escrow(&s, 1_000);
+
+    s.client.release_partial(&escrow_id, &400);
+    s.client.release_partial(&escrow_id, &600);
+    assert_eq!(s.token.balance(&s.payee), 1_000);
+    assert_eq!(s.client.remaining_amount(&escrow_id), 0);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+
+    let milestone_id = s.client.create_milestone_escrow(
+        &s.depositor,
+        &s.payee,
+        &s.arbiter,
+        &s.token.address,
+        &vec![&env, 500],
+    );
+    let result = s.client.try_release_partial(&milestone_id, &100);
+    assert_eq!(result, Err(Ok(EscrowError::HasMilestones)));
+}
+
+#[test]
+fn test_multi_depositor_refund_is_proportional() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let depositor_start = s.token.balance(&s.depositor);
+    let backer = Address::generate(&env);
+    StellarAssetClient::new(&env, &s.token.address).mint(&backer, &10_000);
+
+    let escrow_id = create_escrow(&s, 1_000);
+    s.client.contribute(&backer, &escrow_id, &3_000);
+    s.client.contribute(&backer, &escrow_id, &1_000);
+
+    let contributions = s.client.get_contributions(&escrow_id);
+    assert_eq!(contributions.len(), 2);
+    assert_eq!(contributions.get(1).unwrap().amount, 4_000);
+    assert_eq!(s.client.get_escrow(&escrow_id).amount, 5_000);
+
+    // Pay out 20% first; the refund covers the remaining 80% pro rata
+    s.client.release_partial(&escrow_id, &1_000);
+    s.client.refund(&escrow_id);
+
+    assert_eq!(s.token.balance(&s.payee), 1_000);
+    assert_eq!(s.token.balance(&s.depositor), depositor_start - 1_000 + 800);
+    assert_eq!(s.token.balance(&backer), 10_000 - 4_000 + 3_200);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_multi_depositor_refund_rounding() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let backer = Address::generate(&env);
+    StellarAssetClient::new(&env, &s.token.address).mint(&backer, &10_000);
+
+    let depositor_start = s.token.balance(&s.depositor);
+    let escrow_id = create_escrow(&s, 1);
+    s.client.contribute(&backer, &escrow_id, &2);
+    s.client.release_partial(&escrow_id, &1);
+    s.client.refund(&escrow_id);
+
+    // Shares of 2 are 2/3 and 4/3, rounded down; the depositor takes the dust
+    assert_eq!(s.token.balance(&s.depositor), depositor_start);
+    assert_eq!(s.token.balance(&backer), 10_000 - 2 + 1);
+    // Nothing is left stranded in the contract
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_contribute_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let escrow_id = create_escrow(&s, 1_000);
+
+    let result = s.client.try_contribute(&s.payee, &escrow_id, &100);
+    assert_eq!(result, Err(Ok(EscrowError::InvalidParties)));
+    let result = s.client.try_contribute(&s.depositor, &escrow_id, &0);
+    assert_eq!(result, Err(Ok(EscrowError::InvalidAmount)));
+
+    s.client.release(&escrow_id);
+    let result = s.client.try_contribute(&s.depositor, &escrow_id, &100);
+    assert_eq!(result, Err(Ok(EscrowError::NotActive)));
+}
+
+fn setup_arbitration(env: &Env, s: &Setup, appeal_window: u64) -> TokenClient<'static> {
+    let (bond_token, bond_admin) = create_token_contract(env, &s.admin);
+    bond_admin.mint(&s.arbiter, &1_000);
+    s.client
+        .configure_arbitration(&s.admin, &bond_token.address, &500, &appeal_window);
+    bond_token
+}
+
+#[test]
+fn test_bonded_dispute_requires_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let bond_token = setup_arbitration(&env, &s, 86_400);
+    let escrow_id = create_escrow(&s, 1_000);
+
+    let result = s.client.try_dispute(&s.payee, &escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::InsufficientBond)));
+
+    s.client.stake_bond(&s.arbiter, &500);
+    s.client.dispute(&s.payee, &escrow_id);
+    let bond = s.client.get_arbiter_bond(&s.arbiter);
+    assert_eq!(bond.staked, 500);
+    assert_eq!(bond.locked, 500);
+
+    // Locked bond cannot be withdrawn while the dispute is open
+    let result = s.client.try_unstake_bond(&s.arbiter, &1);
+    assert_eq!(result, Err(Ok(EscrowError::InsufficientBond)));
+    assert_eq!(bond_token.balance(&s.client.address), 500);
+}
+
+#[test]
+fn test_bonded_ruling_executes_after_appeal_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let bond_token = setup_arbitration(&env, &s, 86_400);
+    s.client.stake_bond(&s.arbiter, &500);
+    let escrow_id = create_escrow(&s, 1_000);
+    s.client.dispute(&s.depositor, &escrow_id);
+
+    s.client.resolve(&escrow_id, &true);
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Ruled);
+    assert_eq!(s.token.balance(&s.payee), 0);
+
+    let result = s.client.try_execute_ruling(&escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::AppealWindowOpen)));
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 86_400);
+    let result = s.client.try_appeal(&s.depositor, &escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::AppealWindowClosed)));
+
+    s.client.execute_ruling(&escrow_id);
+    assert_eq!(s.token.balance(&s.payee), 1_000);
+    assert_eq!(s.client.get_arbiter_bond(&s.arbiter).locked, 0);
+
+    s.client.unstake_bond(&s.arbiter, &500);
+    assert_eq!(bond_token.balance(&s.arbiter), 1_000);
+}
+
+#[test]
+fn test_overturned_appeal_slashes_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let bond_token = setup_arbitration(&env, &s, 86_400);
+    s.client.stake_bond(&s.arbiter, &800);
+    let depositor_start = s.token.balance(&s.depositor);
+    let escrow_id = create_escrow(&s, 1_000);
+    s.client.dispute(&s.depositor, &escrow_id);
+
+    s.client.resolve(&escrow_id, &true);
+    s.client.appeal(&s.depositor, &escrow_id);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Appealed
+    );
+
+    let result = s.client.try_execute_ruling(&escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::NotRuled)));
+
+    s.client.decide_appeal(&s.admin, &escrow_id, &false);
+    assert_eq!(s.token.balance(&s.depositor), depositor_start);
+    assert_eq!(bond_token.balance(&s.depositor), 500);
+
+    let bond = s.client.get_arbiter_bond(&s.arbiter);
+    assert_eq!(bond.staked, 300);
+    assert_eq!(bond.locked, 0);
+}
+
+#[test]
+fn test_upheld_appeal_releases_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    setup_arbitration(&env, &s, 86_400);
+    s.client.stake_bond(&s.arbiter, &500);
+    let escrow_id = create_escrow(&s, 1_000);
+    s.client.dispute(&s.payee, &escrow_id);
+    s.client.resolve(&escrow_id, &true);
+    s.client.appeal(&s.depositor, &escrow_id);
+
+    let outsider = Address::generate(&env);
+    let result = s.client.try_decide_appeal(&outsider, &escrow_id, &true);
+    assert_eq!(result, Err(Ok(EscrowError::Unauthorized)));
+
+    s.client.decide_appeal(&s.admin, &escrow_id, &true);
+    assert_eq!(s.token.balance(&s.payee), 1_000);
+    let bond = s.client.get_arbiter_bond(&s.arbiter);
+    assert_eq!(bond.staked, 500);
+    assert_eq!(bond.locked, 0);
+}
+
+#[test]
+fn test_bond_token_is_fixed_while_bonds_are_staked() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let bond_token = setup_arbitration(&env, &s, 86_400);
+    s.client.stake_bond(&s.arbiter, &500);
+    let escrow_id = create_escrow(&s, 1_000);
+    s.client.dispute(&s.payee, &escrow_id);
+
+    let (other_token, _) = create_token_contract(&env, &s.admin);
+    let result = s
+        .client
+        .try_configure_arbitration(&s.admin, &other_token.address, &500, &86_400);
+    assert_eq!(result, Err(Ok(EscrowError::BondsOutstanding)));
+
+    // The rest of the config may still change
+    s.client
+        .configure_arbitration(&s.admin, &bond_token.address, &100, &3_600);
+
+    s.client.resolve(&escrow_id, &true);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 86_400);
+    s.client.execute_ruling(&escrow_id);
+    s.client.unstake_bond(&s.arbiter, &500);
+    s.client
+        .configure_arbitration(&s.admin, &other_token.address, &500, &86_400);
+}
+
+fn set_fees(s: &Setup, recipient: &Address) {
+    s.client.set_fee_config(
+        &s.admin,
+        &FeeConfig {
+            protocol: FeeRate {
+                bps: 100,
+                min: 5,
+                max: 50,
+            },
+            arbiter: FeeRate {
+                bps: 50,
+                min: 0,
+                max: 1_000,
+            },
+            recipient: recipient.clone(),
+        },
+    );
+}
+
+#[test]
+fn test_quote_fees_applies_caps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    assert_eq!(s.client.quote_fees(&1_000).net_amount, 1_000);
+
+    let recipient = Address::generate(&env);
+    set_fees(&s, &recipient);
+
+    // 1% protocol fee, 0.5% arbiter fee
+    let quote = s.client.quote_fees(&2_000);
+    assert_eq!(quote.protocol_fee, 20);
+    assert_eq!(quote.arbiter_fee, 10);
+    assert_eq!(quote.net_amount, 1_970);
+
+    // Protocol fee floor and ceiling
+    assert_eq!(s.client.quote_fees(&100).protocol_fee, 5);
+    assert_eq!(s.client.quote_fees(&100_000).protocol_fee, 50);
+
+    // Fees never exceed the payout
+    let quote = s.client.quote_fees(&3);
+    assert_eq!(quote.protocol_fee, 3);
+    assert_eq!(quote.net_amount, 0);
+}
+
+#[test]
+fn test_fees_withheld_on_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let recipient = Address::generate(&env);
+    set_fees(&s, &recipient);
+
+    let escrow_id = create_escrow(&s, 2_000);
+    s.client.release_partial(&escrow_id, &1_000);
+    s.client.release(&escrow_id);
+
+    assert_eq!(s.token.balance(&s.payee), 2 * 985);
+    assert_eq!(s.token.balance(&recipient), 2 * 10);
+    assert_eq!(s.token.balance(&s.arbiter), 2 * 5);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+    assert_eq!(s.client.get_escrow(&escrow_id).released, 2_000);
+}
+
+#[test]
+fn test_refund_is_fee_free() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let recipient = Address::generate(&env);
+    set_fees(&s, &recipient);
+    let depositor_start = s.token.balance(&s.depositor);
+
+    let escrow_id = create_escrow(&s, 2_000);
+    s.client.refund(&escrow_id);
+    assert_eq!(s.token.balance(&s.depositor), depositor_start);
+    assert_eq!(s.token.balance(&recipient), 0);
+}
+
+#[test]
+fn test_fee_config_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let recipient = Address::generate(&env);
+    let rate = |bps, min, max| FeeRate { bps, min, max };
+
+    let result = s.client.try_set_fee_config(
+        &s.admin,
+        &FeeConfig {
+            protocol: rate(100, 50, 10),
+            arbiter: rate(0, 0, 0),
+            recipient: recipient.clone(),
+        },
+    );
+    assert_eq!(result, Err(Ok(EscrowError::InvalidFeeConfig)));
+
+    let result = s.client.try_set_fee_config(
+        &s.admin,
+        &FeeConfig {
+            protocol: rate(6_000, 0, 10),
+            arbiter: rate(6_000, 0, 10),
+            recipient: recipient.clone(),
+        },
+    );
+    assert_eq!(result, Err(Ok(EscrowError::InvalidFeeConfig)));
+
+    let outsider = Address::generate(&env);
+    let result = s.client.try_set_fee_config(
+        &outsider,
+        &FeeConfig {
+            protocol: rate(100, 0, 10),
+            arbiter: rate(0, 0, 0),
+            recipient,
+        },
+    );
+    assert_eq!(result, Err(Ok(EscrowError::Unauthorized)));
+}
+
+#[test]
+fn test_oracle_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let result = s.client.try_quote_value(&s.token.address, &1_000);
+    assert_eq!(result, Err(Ok(EscrowError::OracleNotSet)));
+
+    let config = OracleConfig {
+        feeds: vec![&env, s.arbiter.clone()],
+        max_age: 300,
+    };
+    let result = s.client.try_set_oracle(&s.payee, &config);
+    assert_eq!(result, Err(Ok(EscrowError::Unauthorized)));
+
+    // The primary feed has no price yet, so the fallback answers
+    let primary = mock_oracle(&env);
+    let fallback = mock_oracle(&env);
+    let asset = Asset::Stellar(s.token.address.clone());
+    let config = OracleConfig {
+        feeds: vec![&env, primary.address.clone(), fallback.address.clone()],
+        max_age: 300,
+    };
+    s.client.set_oracle(&s.admin, &config);
+    assert_eq!(s.client.get_oracle(), Some(config));
+    let result = s.client.try_quote_value(&s.token.address, &1_000);
+    assert_eq!(result, Err(Ok(EscrowError::StalePrice)));
+
+    fallback.set_price(&asset, &2_500_000);
+    assert_eq!(s.client.quote_value(&s.token.address, &1_000), 250);
+
+    primary.set_price(&asset, &5_000_000);
+    assert_eq!(s.client.quote_value(&s.token.address, &1_000), 500);
+
+    // Once both go stale there is no price to quote
+    env.ledger().with_mut(|li| li.timestamp += 301);
+    let result = s.client.try_quote_value(&s.token.address, &1_000);
+    assert_eq!(result, Err(Ok(EscrowError::StalePrice)));
+}
+
+#[test]
+fn test_interface_client_drives_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let escrow = EscrowClient::new(&env, &s.client.address);
+
+    let escrow_id =
+        escrow.create_escrow(&s.depositor, &s.payee, &s.arbiter, &s.token.address, &1_000);
+    escrow.release(&escrow_id);
+    assert_eq!(escrow.get_escrow(&escrow_id).status, EscrowStatus::Released);
+    let result = escrow.try_release(&escrow_id);
+    assert_eq!(result, Err(Ok(EscrowError::NotActive)));
+}
+
+#[test]
+fn test_lifecycle_matches_golden() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let mut transcript = Transcript::new();
+
+    let escrow_id =
+        s.client
+            .create_escrow(&s.depositor, &s.payee, &s.arbiter, &s.token.address, &1_000);
+    transcript.record(&env, "create_escrow", &escrow_id);
+    s.client.release_partial(&escrow_id, &400);
+    transcript.record(&env, "release_partial", &());
+    s.client.dispute(&s.payee, &escrow_id);
+    transcript.record(&env, "dispute", &());
+    s.client.resolve(&escrow_id, &true);
+    transcript.record(&env, "resolve", &());
+    let escrow = s.client.get_escrow(&escrow_id);
+    transcript.record(&env, "get_escrow", &escrow);
+
+    assert_golden!("escrow_lifecycle", transcript);
+}
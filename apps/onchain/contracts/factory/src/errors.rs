@@ -0,0 +1,16 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FactoryError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    SaltInUse = 4,
+    ChildNotFound = 5,
+    BatchTooLarge = 6,
+    ReleaseExists = 7,
+    ReleaseNotFound = 8,
+    InvalidRelease = 9,
+}
@@ -0,0 +1,43 @@
+use crate::storage::{ReleaseEntry, UpgradeStatus};
+use soroban_sdk::{contractevent, Address, BytesN, String, Vec};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub admin: Address,
+    pub streaming_wasm: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamingWasmSetEvent {
+    pub streaming_wasm: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChildDeployedEvent {
+    #[topic]
+    pub owner: Address,
+    pub child_id: u64,
+    pub address: Address,
+    pub salt: BytesN<32>,
+}
+
+/// Outcome of upgrading one child in a batch
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChildUpgradeEvent {
+    #[topic]
+    pub child_id: u64,
+    pub new_wasm_hash: BytesN<32>,
+    pub status: UpgradeStatus,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleasePublishedEvent {
+    #[topic]
+    pub version: String,
+    pub entries: Vec<ReleaseEntry>,
+}
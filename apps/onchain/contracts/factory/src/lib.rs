@@ -0,0 +1,353 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+
+use errors::FactoryError;
+use lumenpulse_streaming_interface::StreamingClient;
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
+use storage::{Child, DataKey, Release, ReleaseEntry, UpgradeResult, UpgradeStatus};
+
+/// Upper bound on the children returned by one `list_children` call
+const MAX_PAGE: u32 = 50;
+
+/// Upper bound on the children upgraded in one call
+const MAX_UPGRADE_BATCH: u32 = 25;
+
+/// Upper bound on the contracts listed in one release
+const MAX_RELEASE_ENTRIES: u32 = 50;
+
+/// Deploys isolated streaming contract instances for organizations, so
+/// each one keeps its own admin, storage and upgrade cadence. Every child
+/// is recorded in an on-chain registry that can be enumerated in full or
+/// by owner. The factory is each child's upgrader, so the admin (protocol
+/// governance) can roll new WASM out to many children at once.
+///
+/// The factory also keeps the protocol's deployment manifest: each
+/// published release records which contract addresses and WASM hashes make
+/// up that version, so integrators can look the current set up on-chain.
+#[contract]
+pub struct FactoryContract;
+
+#[contractimpl]
+impl FactoryContract {
+    /// Initialize the factory with the streaming WASM it deploys
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        streaming_wasm: BytesN<32>,
+    ) -> Result<(), FactoryError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(FactoryError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::StreamingWasm, &streaming_wasm);
+        env.storage().instance().set(&DataKey::NextChildId, &0u64);
+
+        events::InitializedEvent {
+            admin,
+            streaming_wasm,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Change the WASM later deployments use (admin only). Instances
+    /// already deployed are not affected.
+    pub fn set_streaming_wasm(
+        env: Env,
+        admin: Address,
+        streaming_wasm: BytesN<32>,
+    ) -> Result<(), FactoryError> {
+        Self::require_admin(&env, &admin)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::StreamingWasm, &streaming_wasm);
+
+        events::StreamingWasmSetEvent { streaming_wasm }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Deploy a streaming instance for an organization and initialize it
+    /// with `org_admin`, who must authorize as well, and with the factory
+    /// as its upgrader (admin only). Each salt can be used once, and the
+    /// address depends only on the factory and the salt.
+    pub fn deploy(
+        env: Env,
+        admin: Address,
+        org_admin: Address,
+        salt: BytesN<32>,
+    ) -> Result<Address, FactoryError> {
+        Self::require_admin(&env, &admin)?;
+        org_admin.require_auth();
+
+        let salt_key = DataKey::ChildBySalt(salt.clone());
+        if env.storage().persistent().has(&salt_key) {
+            return Err(FactoryError::SaltInUse);
+        }
+
+        let wasm_hash = Self::get_streaming_wasm(env.clone())?;
+        let address = env
+            .deployer()
+            .with_current_contract(salt.clone())
+            .deploy_v2(wasm_hash.clone(), ());
+        let streaming = StreamingClient::new(&env, &address);
+        streaming.initialize(&org_admin);
+        streaming.set_upgrader(&env.current_contract_address());
+
+        let child_id: u64 = env.storage().instance().get(&DataKey::NextChildId).unwrap();
+        let child = Child {
+            id: child_id,
+            address: address.clone(),
+            owner: org_admin.clone(),
+            salt: salt.clone(),
+            wasm_hash,
+            created_ledger: env.ledger().sequence(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Child(child_id), &child);
+        env.storage().persistent().set(&salt_key, &child_id);
+        let owner_key = DataKey::OwnerChildren(org_admin.clone());
+        let mut owned: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&owner_key)
+            .unwrap_or(Vec::new(&env));
+        owned.push_back(child_id);
+        env.storage().persistent().set(&owner_key, &owned);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextChildId, &(child_id + 1));
+
+        events::ChildDeployedEvent {
+            owner: org_admin,
+            child_id,
+            address: address.clone(),
+            salt,
+        }
+        .publish(&env);
+
+        Ok(address)
+    }
+
+    /// Upgrade each listed child to `new_wasm_hash` (admin only). A child
+    /// that fails to upgrade is reported and skipped rather than reverting
+    /// the rest of the batch.
+    pub fn upgrade_children(
+        env: Env,
+        child_ids: Vec<u64>,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<Vec<UpgradeResult>, FactoryError> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if child_ids.len() > MAX_UPGRADE_BATCH {
+            return Err(FactoryError::BatchTooLarge);
+        }
+
+        let mut results = Vec::new(&env);
+        for child_id in child_ids.iter() {
+            let status = match Self::get_child(env.clone(), child_id) {
+                Ok(mut child) => {
+                    let streaming = StreamingClient::new(&env, &child.address);
+                    match streaming.try_upgrade(&env.current_contract_address(), &new_wasm_hash) {
+                        Ok(Ok(())) => {
+                            child.wasm_hash = new_wasm_hash.clone();
+                            env.storage()
+                                .persistent()
+                                .set(&DataKey::Child(child_id), &child);
+                            UpgradeStatus::Upgraded
+                        }
+                        _ => UpgradeStatus::Failed,
+                    }
+                }
+                Err(_) => UpgradeStatus::ChildNotFound,
+            };
+
+            events::ChildUpgradeEvent {
+                child_id,
+                new_wasm_hash: new_wasm_hash.clone(),
+                status,
+            }
+            .publish(&env);
+            results.push_back(UpgradeResult { child_id, status });
+        }
+
+        Ok(results)
+    }
+
+    /// Record the contracts that make up `version` (admin only). A release
+    /// cannot be changed once published; a fix ships as a new version.
+    pub fn publish_release(
+        env: Env,
+        version: String,
+        entries: Vec<ReleaseEntry>,
+    ) -> Result<(), FactoryError> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if version.is_empty() || entries.is_empty() || entries.len() > MAX_RELEASE_ENTRIES {
+            return Err(FactoryError::InvalidRelease);
+        }
+        // Each role appears once, so lookups by name are unambiguous
+        for (i, entry) in entries.iter().enumerate() {
+            if entries
+                .iter()
+                .skip(i + 1)
+                .any(|other| other.name == entry.name)
+            {
+                return Err(FactoryError::InvalidRelease);
+            }
+        }
+
+        let key = DataKey::Release(version.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(FactoryError::ReleaseExists);
+        }
+
+        let release = Release {
+            version: version.clone(),
+            entries: entries.clone(),
+            published_ledger: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&key, &release);
+        let mut versions = Self::releases(env.clone());
+        versions.push_back(version.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Releases, &versions);
+
+        events::ReleasePublishedEvent { version, entries }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Contracts that make up `version`
+    pub fn release(env: Env, version: String) -> Result<Release, FactoryError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Release(version))
+            .ok_or(FactoryError::ReleaseNotFound)
+    }
+
+    /// Most recently published release
+    pub fn latest_release(env: Env) -> Result<Release, FactoryError> {
+        let version = Self::releases(env.clone())
+            .last()
+            .ok_or(FactoryError::ReleaseNotFound)?;
+        Self::release(env, version)
+    }
+
+    /// Every published version, oldest first
+    pub fn releases(env: Env) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Releases)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Address `deploy` will give the instance for `salt`, so integrators
+    /// can reference or pre-fund it before it exists
+    pub fn predict_address(env: Env, salt: BytesN<32>) -> Address {
+        env.deployer()
+            .with_current_contract(salt)
+            .deployed_address()
+    }
+
+    /// Get a child by ID
+    pub fn get_child(env: Env, child_id: u64) -> Result<Child, FactoryError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Child(child_id))
+            .ok_or(FactoryError::ChildNotFound)
+    }
+
+    /// Get the child deployed with `salt`
+    pub fn child_by_salt(env: Env, salt: BytesN<32>) -> Result<Child, FactoryError> {
+        let child_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ChildBySalt(salt))
+            .ok_or(FactoryError::ChildNotFound)?;
+        Self::get_child(env, child_id)
+    }
+
+    /// Up to `limit` children in deployment order, starting from ID `start`
+    pub fn list_children(env: Env, start: u64, limit: u32) -> Vec<Child> {
+        let end =
+            Self::child_count(env.clone()).min(start.saturating_add(limit.min(MAX_PAGE) as u64));
+        let mut children = Vec::new(&env);
+        for child_id in start..end {
+            if let Ok(child) = Self::get_child(env.clone(), child_id) {
+                children.push_back(child);
+            }
+        }
+        children
+    }
+
+    /// Children deployed for `owner`, in deployment order
+    pub fn children_of(env: Env, owner: Address) -> Vec<Child> {
+        let owned: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OwnerChildren(owner))
+            .unwrap_or(Vec::new(&env));
+        let mut children = Vec::new(&env);
+        for child_id in owned.iter() {
+            if let Ok(child) = Self::get_child(env.clone(), child_id) {
+                children.push_back(child);
+            }
+        }
+        children
+    }
+
+    /// Number of children deployed so far
+    pub fn child_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::NextChildId)
+            .unwrap_or(0)
+    }
+
+    /// WASM new instances are deployed from
+    pub fn get_streaming_wasm(env: Env) -> Result<BytesN<32>, FactoryError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::StreamingWasm)
+            .ok_or(FactoryError::NotInitialized)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, FactoryError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(FactoryError::NotInitialized)
+    }
+}
+
+impl FactoryContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), FactoryError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(FactoryError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
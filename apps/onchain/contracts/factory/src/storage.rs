@@ -0,0 +1,62 @@
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                   // -> Address
+    StreamingWasm,           // -> BytesN<32>
+    NextChildId,             // -> u64
+    Child(u64),              // child_id -> Child
+    ChildBySalt(BytesN<32>), // salt -> u64 (child_id)
+    OwnerChildren(Address),  // owner -> Vec<u64> (child_ids)
+    Release(String),         // version -> Release
+    Releases,                // -> Vec<String> (versions)
+}
+
+/// Streaming contract deployed for one organization
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Child {
+    pub id: u64,
+    pub address: Address,
+    /// Organization admin the instance was initialized with
+    pub owner: Address,
+    pub salt: BytesN<32>,
+    /// WASM the instance currently runs
+    pub wasm_hash: BytesN<32>,
+    pub created_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpgradeStatus {
+    Upgraded,
+    Failed,
+    ChildNotFound,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeResult {
+    pub child_id: u64,
+    pub status: UpgradeStatus,
+}
+
+/// One contract that is part of a release
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseEntry {
+    /// Role the contract plays, such as `governance` or `staking`
+    pub name: Symbol,
+    pub address: Address,
+    pub wasm_hash: BytesN<32>,
+}
+
+/// The set of contracts that together make up one protocol version
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Release {
+    pub version: String,
+    pub entries: Vec<ReleaseEntry>,
+    pub published_ledger: u32,
+}
@@ -0,0 +1,257 @@
+use crate::errors::FactoryError;
+use crate::storage::{ReleaseEntry, UpgradeResult, UpgradeStatus};
+use crate::{FactoryContract, FactoryContractClient};
+use hello_world::ContractClient as HelloClient;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, String,
+};
+use streaming::StreamingContractClient;
+
+/// Built from `contracts/streaming`; `make fixtures` refreshes it
+const STREAMING_WASM: &[u8] = include_bytes!("../testdata/streaming.wasm");
+
+/// Built from `contracts/hello-world`, as a stand-in for a new release
+const HELLO_WASM: &[u8] = include_bytes!("../testdata/hello_world.wasm");
+
+struct Setup<'a> {
+    env: &'a Env,
+    client: FactoryContractClient<'a>,
+    admin: Address,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let wasm_hash = env.deployer().upload_contract_wasm(STREAMING_WASM);
+    let contract_id = env.register(FactoryContract, ());
+    let client = FactoryContractClient::new(env, &contract_id);
+    client.initialize(&admin, &wasm_hash);
+
+    Setup { env, client, admin }
+}
+
+fn salt(env: &Env, seed: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[seed; 32])
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+
+    assert_eq!(setup.client.get_admin(), setup.admin);
+
+    let wasm_hash = setup.client.get_streaming_wasm();
+    let result = setup.client.try_initialize(&setup.admin, &wasm_hash);
+    assert_eq!(result, Err(Ok(FactoryError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_deploy_instance() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let org_admin = Address::generate(&env);
+
+    let address = setup
+        .client
+        .deploy(&setup.admin, &org_admin, &salt(&env, 1));
+
+    // The instance is a working streaming contract run by the org
+    let instance = StreamingContractClient::new(setup.env, &address);
+    assert_eq!(instance.get_admin(), org_admin);
+
+    let child = setup.client.child_by_salt(&salt(&env, 1));
+    assert_eq!(child.address, address);
+    assert_eq!(child.owner, org_admin);
+
+    let other = setup
+        .client
+        .deploy(&setup.admin, &org_admin, &salt(&env, 2));
+    assert_ne!(other, address);
+}
+
+#[test]
+fn test_deploy_errors() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let org_admin = Address::generate(&env);
+
+    setup
+        .client
+        .deploy(&setup.admin, &org_admin, &salt(&env, 1));
+    let result = setup
+        .client
+        .try_deploy(&setup.admin, &org_admin, &salt(&env, 1));
+    assert_eq!(result, Err(Ok(FactoryError::SaltInUse)));
+
+    let outsider = Address::generate(&env);
+    let result = setup
+        .client
+        .try_deploy(&outsider, &org_admin, &salt(&env, 2));
+    assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+
+    let result = setup.client.try_child_by_salt(&salt(&env, 2));
+    assert_eq!(result, Err(Ok(FactoryError::ChildNotFound)));
+}
+
+#[test]
+fn test_predict_address() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let org_admin = Address::generate(&env);
+
+    let predicted = setup.client.predict_address(&salt(&env, 1));
+    assert_ne!(predicted, setup.client.predict_address(&salt(&env, 2)));
+
+    let address = setup
+        .client
+        .deploy(&setup.admin, &org_admin, &salt(&env, 1));
+    assert_eq!(address, predicted);
+    assert_eq!(setup.client.predict_address(&salt(&env, 1)), predicted);
+}
+
+#[test]
+fn test_child_registry() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let wasm_hash = setup.client.get_streaming_wasm();
+
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    setup.client.deploy(&setup.admin, &alice, &salt(&env, 1));
+    env.ledger().with_mut(|li| li.sequence_number = 20);
+    setup.client.deploy(&setup.admin, &bob, &salt(&env, 2));
+    let last = setup.client.deploy(&setup.admin, &alice, &salt(&env, 3));
+    assert_eq!(setup.client.child_count(), 3);
+
+    let child = setup.client.get_child(&2);
+    assert_eq!(child.address, last);
+    assert_eq!(child.owner, alice);
+    assert_eq!(child.salt, salt(&env, 3));
+    assert_eq!(child.wasm_hash, wasm_hash);
+    assert_eq!(child.created_ledger, 20);
+    assert_eq!(setup.client.get_child(&0).created_ledger, 10);
+
+    let page = setup.client.list_children(&0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(1).unwrap().owner, bob);
+    let page = setup.client.list_children(&2, &2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, 2);
+    assert_eq!(setup.client.list_children(&3, &2).len(), 0);
+    assert_eq!(setup.client.list_children(&u64::MAX, &2).len(), 0);
+
+    let owned = setup.client.children_of(&alice);
+    assert_eq!(owned.len(), 2);
+    assert_eq!(owned.get(0).unwrap().id, 0);
+    assert_eq!(owned.get(1).unwrap().id, 2);
+    assert_eq!(setup.client.children_of(&setup.admin).len(), 0);
+}
+
+#[test]
+fn test_upgrade_children() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let org_admin = Address::generate(&env);
+
+    let first = setup
+        .client
+        .deploy(&setup.admin, &org_admin, &salt(&env, 1));
+    setup
+        .client
+        .deploy(&setup.admin, &org_admin, &salt(&env, 2));
+    assert_eq!(
+        StreamingContractClient::new(&env, &first).get_upgrader(),
+        Some(setup.client.address.clone())
+    );
+
+    let new_wasm = env.deployer().upload_contract_wasm(HELLO_WASM);
+    let results = setup.client.upgrade_children(&vec![&env, 0, 7], &new_wasm);
+    assert_eq!(
+        results,
+        vec![
+            &env,
+            UpgradeResult {
+                child_id: 0,
+                status: UpgradeStatus::Upgraded,
+            },
+            UpgradeResult {
+                child_id: 7,
+                status: UpgradeStatus::ChildNotFound,
+            },
+        ]
+    );
+
+    // The child now runs the new code at the same address
+    let hello = HelloClient::new(&env, &first);
+    let greeting = hello.hello(&String::from_str(&env, "Lumen"));
+    assert_eq!(greeting.len(), 2);
+    assert_eq!(setup.client.get_child(&0).wasm_hash, new_wasm);
+
+    // A hash that was never uploaded fails for that child only
+    let missing = BytesN::from_array(&env, &[9; 32]);
+    let results = setup.client.upgrade_children(&vec![&env, 1], &missing);
+    assert_eq!(results.get(0).unwrap().status, UpgradeStatus::Failed);
+    assert_eq!(
+        setup.client.get_child(&1).wasm_hash,
+        setup.client.get_streaming_wasm()
+    );
+}
+
+#[test]
+fn test_release_manifest() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let org_admin = Address::generate(&env);
+
+    let child = setup
+        .client
+        .deploy(&setup.admin, &org_admin, &salt(&env, 1));
+    let streaming = ReleaseEntry {
+        name: symbol_short!("streaming"),
+        address: child,
+        wasm_hash: setup.client.get_streaming_wasm(),
+    };
+    let factory = ReleaseEntry {
+        name: symbol_short!("factory"),
+        address: setup.client.address.clone(),
+        wasm_hash: BytesN::from_array(&env, &[3; 32]),
+    };
+    let v1 = String::from_str(&env, "1.0");
+
+    let result = setup.client.try_latest_release();
+    assert_eq!(result, Err(Ok(FactoryError::ReleaseNotFound)));
+
+    let entries = vec![&env, factory.clone(), streaming.clone()];
+    setup.client.publish_release(&v1, &entries);
+    let release = setup.client.release(&v1);
+    assert_eq!(release.entries, entries);
+    assert_eq!(release.published_ledger, env.ledger().sequence());
+
+    // Published releases are immutable
+    let result = setup
+        .client
+        .try_publish_release(&v1, &vec![&env, factory.clone()]);
+    assert_eq!(result, Err(Ok(FactoryError::ReleaseExists)));
+
+    // Each role may appear once
+    let v2 = String::from_str(&env, "1.1");
+    let result = setup
+        .client
+        .try_publish_release(&v2, &vec![&env, factory.clone(), factory.clone()]);
+    assert_eq!(result, Err(Ok(FactoryError::InvalidRelease)));
+    let result = setup.client.try_publish_release(&v2, &vec![&env]);
+    assert_eq!(result, Err(Ok(FactoryError::InvalidRelease)));
+
+    setup.client.publish_release(&v2, &vec![&env, factory]);
+    assert_eq!(setup.client.latest_release().version, v2);
+    assert_eq!(setup.client.releases(), vec![&env, v1.clone(), v2]);
+    assert_eq!(setup.client.release(&v1).entries.len(), 2);
+
+    let result = setup.client.try_release(&String::from_str(&env, "2.0"));
+    assert_eq!(result, Err(Ok(FactoryError::ReleaseNotFound)));
+}
@@ -0,0 +1,16 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FaucetError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    InvalidConfig = 4,
+    NotAllowlisted = 5,
+    CooldownActive = 6,
+    DailyCapReached = 7,
+    FaucetEmpty = 8,
+    BatchTooLarge = 9,
+}
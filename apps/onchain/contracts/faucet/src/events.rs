@@ -0,0 +1,24 @@
+use crate::storage::FaucetConfig;
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigUpdatedEvent {
+    pub config: FaucetConfig,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowlistUpdatedEvent {
+    #[topic]
+    pub account: Address,
+    pub allowed: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DripEvent {
+    #[topic]
+    pub account: Address,
+    pub amount: i128,
+}
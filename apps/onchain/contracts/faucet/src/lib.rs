@@ -0,0 +1,204 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::FaucetError;
+use lumenpulse_pausable::Pausable;
+use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+use storage::{DataKey, Dispensed, FaucetConfig, DAY, MAX_BATCH_SIZE};
+
+/// Testnet faucet that dispenses a configured test token to allowlisted
+/// addresses, so integration partners can fund themselves against our
+/// deployment. Each address waits out a cooldown between drips and may
+/// receive at most a daily cap per day; the faucet pays out of whatever
+/// balance it has been topped up with.
+#[contract]
+pub struct FaucetContract;
+
+#[contractimpl]
+impl FaucetContract {
+    /// Initialize the faucet with an admin, the token it dispenses and its
+    /// limits
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        config: FaucetConfig,
+    ) -> Result<(), FaucetError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(FaucetError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        Self::store_config(&env, config)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+
+        Ok(())
+    }
+
+    /// Change the drip amount, cooldown and daily cap (admin only)
+    pub fn set_config(env: Env, admin: Address, config: FaucetConfig) -> Result<(), FaucetError> {
+        Self::require_admin(&env, &admin)?;
+        Self::store_config(&env, config)
+    }
+
+    /// Add `accounts` to the allowlist, or remove them (admin only)
+    pub fn set_allowed(
+        env: Env,
+        admin: Address,
+        accounts: Vec<Address>,
+        allowed: bool,
+    ) -> Result<(), FaucetError> {
+        Self::require_admin(&env, &admin)?;
+
+        if accounts.len() > MAX_BATCH_SIZE {
+            return Err(FaucetError::BatchTooLarge);
+        }
+        for account in accounts.iter() {
+            let key = DataKey::Allowed(account.clone());
+            if allowed {
+                env.storage().persistent().set(&key, &true);
+            } else {
+                env.storage().persistent().remove(&key);
+            }
+            events::AllowlistUpdatedEvent { account, allowed }.publish(&env);
+        }
+
+        Ok(())
+    }
+
+    /// Send the drip amount to an allowlisted `account`, or as much of it
+    /// as its daily cap has left. Returns the amount sent.
+    pub fn drip(env: Env, account: Address) -> Result<i128, FaucetError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        account.require_auth();
+
+        let config = Self::get_config(env.clone())?;
+        if !Self::is_allowed(env.clone(), account.clone()) {
+            return Err(FaucetError::NotAllowlisted);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut dispensed = Self::dispensed(env.clone(), account.clone());
+        if dispensed.total > 0 && now < dispensed.last_drip + config.cooldown {
+            return Err(FaucetError::CooldownActive);
+        }
+        if dispensed.day != now / DAY {
+            dispensed.day = now / DAY;
+            dispensed.today = 0;
+        }
+        let amount = config.drip_amount.min(config.daily_cap - dispensed.today);
+        if amount <= 0 {
+            return Err(FaucetError::DailyCapReached);
+        }
+
+        let token = Self::get_token(env.clone())?;
+        let this = env.current_contract_address();
+        if token::balance(&env, &token, &this) < amount {
+            return Err(FaucetError::FaucetEmpty);
+        }
+
+        dispensed.last_drip = now;
+        dispensed.today += amount;
+        dispensed.total += amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Dispensed(account.clone()), &dispensed);
+
+        token::transfer(&env, &token, &this, &account, &amount);
+
+        events::DripEvent { account, amount }.publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Earliest time `account` may drip again, ignoring its daily cap
+    pub fn next_drip(env: Env, account: Address) -> Result<u64, FaucetError> {
+        let config = Self::get_config(env.clone())?;
+        let dispensed = Self::dispensed(env, account);
+        if dispensed.total == 0 {
+            return Ok(0);
+        }
+        Ok(dispensed.last_drip + config.cooldown)
+    }
+
+    /// Whether `account` is on the allowlist
+    pub fn is_allowed(env: Env, account: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Allowed(account))
+            .unwrap_or(false)
+    }
+
+    /// What `account` has received so far
+    pub fn dispensed(env: Env, account: Address) -> Dispensed {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Dispensed(account))
+            .unwrap_or_default()
+    }
+
+    /// Get the faucet's limits
+    pub fn get_config(env: Env) -> Result<FaucetConfig, FaucetError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(FaucetError::NotInitialized)
+    }
+
+    /// Get the token the faucet dispenses
+    pub fn get_token(env: Env) -> Result<Address, FaucetError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(FaucetError::NotInitialized)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, FaucetError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(FaucetError::NotInitialized)
+    }
+}
+
+impl FaucetContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), FaucetError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(FaucetError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn store_config(env: &Env, config: FaucetConfig) -> Result<(), FaucetError> {
+        if config.drip_amount <= 0 || config.daily_cap < config.drip_amount {
+            return Err(FaucetError::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        events::ConfigUpdatedEvent { config }.publish(env);
+
+        Ok(())
+    }
+}
+
+impl Pausable for FaucetContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(FaucetContract);
+
+#[cfg(test)]
+mod test;
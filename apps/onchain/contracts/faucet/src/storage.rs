@@ -0,0 +1,39 @@
+use soroban_sdk::{contracttype, Address};
+
+/// Length of the day daily caps reset on, in seconds
+pub const DAY: u64 = 24 * 60 * 60;
+
+/// Most addresses one allowlist update may change
+pub const MAX_BATCH_SIZE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,              // -> Address
+    Token,              // -> Address
+    Config,             // -> FaucetConfig
+    Allowed(Address),   // account -> bool
+    Dispensed(Address), // account -> Dispensed
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FaucetConfig {
+    /// Tokens sent by each drip
+    pub drip_amount: i128,
+    /// Seconds an address must wait between drips
+    pub cooldown: u64,
+    /// Most tokens one address may receive in a day
+    pub daily_cap: i128,
+}
+
+/// What an address has received so far
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Dispensed {
+    pub last_drip: u64,
+    /// Day, counted from the epoch, `today` was received in
+    pub day: u64,
+    pub today: i128,
+    pub total: i128,
+}
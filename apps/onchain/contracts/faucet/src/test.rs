@@ -0,0 +1,119 @@
+use crate::errors::FaucetError;
+use crate::storage::{FaucetConfig, DAY};
+use crate::{FaucetContract, FaucetContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+
+const COOLDOWN: u64 = 3_600;
+
+struct Setup<'a> {
+    client: FaucetContractClient<'a>,
+    admin: Address,
+    partner: Address,
+    token: TokenClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let partner = Address::generate(env);
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+
+    let client = FaucetContractClient::new(env, &env.register(FaucetContract, ()));
+    let config = FaucetConfig {
+        drip_amount: 100,
+        cooldown: COOLDOWN,
+        daily_cap: 250,
+    };
+    client.initialize(&admin, &sac.address(), &config);
+    client.set_allowed(&admin, &vec![env, partner.clone()], &true);
+    StellarAssetClient::new(env, &sac.address()).mint(&client.address, &1_000);
+
+    Setup {
+        client,
+        admin,
+        partner,
+        token: TokenClient::new(env, &sac.address()),
+    }
+}
+
+#[test]
+fn test_drip_respects_cooldown() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    env.ledger().set_timestamp(DAY);
+
+    assert_eq!(s.client.drip(&s.partner), 100);
+    assert_eq!(s.token.balance(&s.partner), 100);
+    assert_eq!(s.client.next_drip(&s.partner), DAY + COOLDOWN);
+
+    let result = s.client.try_drip(&s.partner);
+    assert_eq!(result, Err(Ok(FaucetError::CooldownActive)));
+
+    env.ledger().set_timestamp(DAY + COOLDOWN);
+    assert_eq!(s.client.drip(&s.partner), 100);
+    assert_eq!(s.client.dispensed(&s.partner).total, 200);
+}
+
+#[test]
+fn test_daily_cap_resets() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    env.ledger().set_timestamp(DAY);
+
+    s.client.drip(&s.partner);
+    env.ledger().set_timestamp(DAY + COOLDOWN);
+    s.client.drip(&s.partner);
+
+    // The last drip of the day is cut down to what the cap has left
+    env.ledger().set_timestamp(DAY + 2 * COOLDOWN);
+    assert_eq!(s.client.drip(&s.partner), 50);
+    env.ledger().set_timestamp(DAY + 3 * COOLDOWN);
+    let result = s.client.try_drip(&s.partner);
+    assert_eq!(result, Err(Ok(FaucetError::DailyCapReached)));
+
+    env.ledger().set_timestamp(2 * DAY);
+    assert_eq!(s.client.drip(&s.partner), 100);
+    assert_eq!(s.token.balance(&s.partner), 350);
+}
+
+#[test]
+fn test_allowlist_and_balance_enforced() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let stranger = Address::generate(&env);
+
+    let result = s.client.try_drip(&stranger);
+    assert_eq!(result, Err(Ok(FaucetError::NotAllowlisted)));
+    let result = s
+        .client
+        .try_set_allowed(&stranger, &vec![&env, stranger.clone()], &true);
+    assert_eq!(result, Err(Ok(FaucetError::Unauthorized)));
+
+    s.client
+        .set_allowed(&s.admin, &vec![&env, s.partner.clone()], &false);
+    let result = s.client.try_drip(&s.partner);
+    assert_eq!(result, Err(Ok(FaucetError::NotAllowlisted)));
+
+    let config = FaucetConfig {
+        drip_amount: 2_000,
+        cooldown: COOLDOWN,
+        daily_cap: 2_000,
+    };
+    s.client.set_config(&s.admin, &config);
+    s.client
+        .set_allowed(&s.admin, &vec![&env, stranger.clone()], &true);
+    let result = s.client.try_drip(&stranger);
+    assert_eq!(result, Err(Ok(FaucetError::FaucetEmpty)));
+
+    let config = FaucetConfig {
+        daily_cap: 1_999,
+        ..config
+    };
+    let result = s.client.try_set_config(&s.admin, &config);
+    assert_eq!(result, Err(Ok(FaucetError::InvalidConfig)));
+}
@@ -0,0 +1 @@
+pub use lumenpulse_governance_interface::GovernanceError;
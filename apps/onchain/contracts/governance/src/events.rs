@@ -0,0 +1,89 @@
+use crate::storage::VoteType;
+use soroban_sdk::{contractevent, Address, BytesN, Symbol};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub admin: Address,
+    pub token: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCreatedEvent {
+    #[topic]
+    pub proposer: Address,
+    pub proposal_id: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteCastEvent {
+    #[topic]
+    pub voter: Address,
+    #[topic]
+    pub proposal_id: u64,
+    pub support: VoteType,
+    pub weight: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCanceledEvent {
+    #[topic]
+    pub proposal_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalExecutedEvent {
+    #[topic]
+    pub proposal_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeSetEvent {
+    pub badge: Option<Address>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalQueuedEvent {
+    #[topic]
+    pub proposal_id: u64,
+    pub eta: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SecurityCouncilSetEvent {
+    pub council: Option<Address>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActionDelaySetEvent {
+    #[topic]
+    pub function: Symbol,
+    pub delay: Option<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteKeyRegisteredEvent {
+    #[topic]
+    pub voter: Address,
+    pub public_key: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalVetoedEvent {
+    #[topic]
+    pub proposal_id: u64,
+    pub council: Address,
+    pub resubmit_after: u64,
+}
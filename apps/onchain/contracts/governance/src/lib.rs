@@ -0,0 +1,628 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod math;
+mod storage;
+mod token;
+
+use errors::GovernanceError;
+use math::BPS_DENOMINATOR;
+use soroban_sdk::{
+    contract, contractimpl, xdr::ToXdr, Address, BytesN, Env, String, Symbol, Val, Vec,
+};
+use storage::{
+    DataKey, GovernanceConfig, Proposal, ProposalAction, ProposalState, SignedVote, VetoRecord,
+    VoteMessage, VoteType, VotingMode,
+};
+
+/// Upper bound on the calls a single proposal may make
+const MAX_ACTIONS: u32 = 10;
+
+/// Upper bound on signed votes tallied in one call
+const MAX_SIGNED_VOTES: u32 = 50;
+
+#[contract]
+pub struct GovernanceContract;
+
+#[contractimpl]
+impl GovernanceContract {
+    /// Initialize governance over holders of `token`
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        config: GovernanceConfig,
+    ) -> Result<(), GovernanceError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(GovernanceError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        Self::validate_config(&config)?;
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &0u64);
+
+        events::InitializedEvent { admin, token }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Create a proposal to make `actions`. Voting opens immediately and
+    /// runs for the configured voting period. Voting power is fixed at the
+    /// last closed ledger, so tokens acquired afterwards carry no weight.
+    /// Quadratic proposals need a badge contract to gate who may vote.
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        description: String,
+        actions: Vec<ProposalAction>,
+        mode: VotingMode,
+    ) -> Result<u64, GovernanceError> {
+        let config = Self::get_config(env.clone())?;
+
+        // Require proposer authorization
+        proposer.require_auth();
+
+        if actions.is_empty() {
+            return Err(GovernanceError::NoActions);
+        }
+        if actions.len() > MAX_ACTIONS {
+            return Err(GovernanceError::TooManyActions);
+        }
+        let actions_hash = Self::actions_hash(&env, &actions);
+        let cooldown_end: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VetoedActions(actions_hash))
+            .unwrap_or(0);
+        if env.ledger().timestamp() < cooldown_end {
+            return Err(GovernanceError::ResubmissionCooldown);
+        }
+        if mode == VotingMode::Quadratic && Self::get_badge(env.clone()).is_none() {
+            return Err(GovernanceError::BadgeNotConfigured);
+        }
+        let snapshot_ledger = env.ledger().sequence().saturating_sub(1);
+        if Self::voting_power(&env, &proposer, snapshot_ledger) < config.proposal_threshold {
+            return Err(GovernanceError::BelowProposalThreshold);
+        }
+
+        let proposal_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .unwrap_or(0);
+
+        let start_time = env.ledger().timestamp();
+        let end_time = start_time + config.voting_period;
+        let proposal = Proposal {
+            id: proposal_id,
+            proposer: proposer.clone(),
+            description,
+            actions,
+            mode,
+            snapshot_ledger,
+            start_time,
+            end_time,
+            for_votes: 0,
+            against_votes: 0,
+            abstain_votes: 0,
+            participation: 0,
+            queued_at: None,
+            eta: None,
+            canceled: false,
+            executed: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &(proposal_id + 1));
+
+        events::ProposalCreatedEvent {
+            proposer,
+            proposal_id,
+            start_time,
+            end_time,
+        }
+        .publish(&env);
+
+        Ok(proposal_id)
+    }
+
+    /// Vote on an active proposal with the voter's full voting power as of
+    /// the proposal's snapshot ledger. Returns the weight tallied, which on
+    /// quadratic proposals is the square root of that power.
+    pub fn cast_vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        support: VoteType,
+    ) -> Result<i128, GovernanceError> {
+        // Require voter authorization
+        voter.require_auth();
+
+        Self::record_vote(&env, voter, proposal_id, support)
+    }
+
+    /// Register the ed25519 key `voter` signs off-chain votes with
+    pub fn register_vote_key(env: Env, voter: Address, public_key: BytesN<32>) {
+        // Require voter authorization
+        voter.require_auth();
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::VoteKey(voter.clone()), &public_key);
+
+        events::VoteKeyRegisteredEvent { voter, public_key }.publish(&env);
+    }
+
+    /// Get the key `voter` signs off-chain votes with, if registered
+    pub fn get_vote_key(env: Env, voter: Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::VoteKey(voter))
+    }
+
+    /// Tally a batch of off-chain signed votes, so a relayer can pay for
+    /// many small holders at once. Every signature must verify against
+    /// the voter's registered key; one bad entry rejects the whole batch.
+    pub fn cast_votes_by_sig(env: Env, votes: Vec<SignedVote>) -> Result<u32, GovernanceError> {
+        if votes.is_empty() || votes.len() > MAX_SIGNED_VOTES {
+            return Err(GovernanceError::InvalidBatch);
+        }
+
+        for vote in votes.iter() {
+            let public_key = Self::get_vote_key(env.clone(), vote.voter.clone())
+                .ok_or(GovernanceError::VoteKeyNotRegistered)?;
+            let message = VoteMessage {
+                governance: env.current_contract_address(),
+                proposal_id: vote.proposal_id,
+                support: vote.support,
+            };
+            env.crypto()
+                .ed25519_verify(&public_key, &message.to_xdr(&env), &vote.signature);
+
+            Self::record_vote(&env, vote.voter, vote.proposal_id, vote.support)?;
+        }
+
+        Ok(votes.len())
+    }
+
+    /// Withdraw a proposal while it is still being voted on (proposer only)
+    pub fn cancel(env: Env, proposal_id: u64) -> Result<(), GovernanceError> {
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+
+        // Require proposer authorization
+        proposal.proposer.require_auth();
+
+        if Self::state(env.clone(), proposal_id)? != ProposalState::Active {
+            return Err(GovernanceError::InvalidState);
+        }
+
+        proposal.canceled = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        events::ProposalCanceledEvent { proposal_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Move a passed proposal into the timelock. It becomes executable
+    /// after the longest delay among its actions. Until it executes the
+    /// security council may cancel it, and within the veto window veto it.
+    pub fn queue(env: Env, proposal_id: u64) -> Result<u64, GovernanceError> {
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+        let config = Self::get_config(env.clone())?;
+
+        if Self::state(env.clone(), proposal_id)? != ProposalState::Succeeded {
+            return Err(GovernanceError::InvalidState);
+        }
+
+        let mut delay = 0;
+        for action in proposal.actions.iter() {
+            delay = delay.max(Self::action_delay(&env, &action.function, &config));
+        }
+        let now = env.ledger().timestamp();
+        let eta = now + delay;
+
+        proposal.queued_at = Some(now);
+        proposal.eta = Some(eta);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        events::ProposalQueuedEvent { proposal_id, eta }.publish(&env);
+
+        Ok(eta)
+    }
+
+    /// Perform a queued proposal's calls once its timelock has elapsed.
+    /// Anyone may trigger this; the calls run with governance as the
+    /// invoker, so targets that name governance as admin accept them.
+    pub fn execute(env: Env, proposal_id: u64) -> Result<(), GovernanceError> {
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+
+        if Self::state(env.clone(), proposal_id)? != ProposalState::Queued {
+            return Err(GovernanceError::InvalidState);
+        }
+        if env.ledger().timestamp() < proposal.eta.unwrap() {
+            return Err(GovernanceError::TimelockNotElapsed);
+        }
+
+        // Mark executed before calling out so a target cannot re-enter
+        proposal.executed = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        for action in proposal.actions.iter() {
+            env.invoke_contract::<Val>(&action.target, &action.function, action.args);
+        }
+
+        events::ProposalExecutedEvent { proposal_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Stop a queued proposal at any point before it executes (security
+    /// council only). Unlike a veto nothing is recorded against its
+    /// actions, which may be proposed again at once.
+    pub fn cancel_queued(
+        env: Env,
+        council: Address,
+        proposal_id: u64,
+    ) -> Result<(), GovernanceError> {
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+        Self::require_council(&env, &council)?;
+
+        if Self::state(env.clone(), proposal_id)? != ProposalState::Queued {
+            return Err(GovernanceError::InvalidState);
+        }
+
+        proposal.canceled = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        events::ProposalCanceledEvent { proposal_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Veto a queued proposal within the veto window (security council
+    /// only). The veto is recorded, and the same actions cannot be proposed
+    /// again until the resubmission cooldown has passed.
+    pub fn veto(env: Env, council: Address, proposal_id: u64) -> Result<(), GovernanceError> {
+        let proposal = Self::load_proposal(&env, proposal_id)?;
+        let config = Self::get_config(env.clone())?;
+        Self::require_council(&env, &council)?;
+
+        if Self::state(env.clone(), proposal_id)? != ProposalState::Queued {
+            return Err(GovernanceError::InvalidState);
+        }
+        let now = env.ledger().timestamp();
+        if now >= proposal.queued_at.unwrap() + config.veto_window {
+            return Err(GovernanceError::VetoWindowClosed);
+        }
+
+        let resubmit_after = now + config.resubmit_cooldown;
+        let record = VetoRecord {
+            council: council.clone(),
+            vetoed_at: now,
+            resubmit_after,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Veto(proposal_id), &record);
+        env.storage().persistent().set(
+            &DataKey::VetoedActions(Self::actions_hash(&env, &proposal.actions)),
+            &resubmit_after,
+        );
+
+        events::ProposalVetoedEvent {
+            proposal_id,
+            council,
+            resubmit_after,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the council veto recorded against a proposal, if any
+    pub fn get_veto(env: Env, proposal_id: u64) -> Option<VetoRecord> {
+        env.storage().persistent().get(&DataKey::Veto(proposal_id))
+    }
+
+    /// Current lifecycle state of a proposal
+    pub fn state(env: Env, proposal_id: u64) -> Result<ProposalState, GovernanceError> {
+        let proposal = Self::load_proposal(&env, proposal_id)?;
+        let config = Self::get_config(env.clone())?;
+
+        if proposal.canceled {
+            return Ok(ProposalState::Canceled);
+        }
+        if env.storage().persistent().has(&DataKey::Veto(proposal_id)) {
+            return Ok(ProposalState::Vetoed);
+        }
+        if proposal.executed {
+            return Ok(ProposalState::Executed);
+        }
+        if proposal.eta.is_some() {
+            return Ok(ProposalState::Queued);
+        }
+        if env.ledger().timestamp() < proposal.end_time {
+            return Ok(ProposalState::Active);
+        }
+        if Self::passed(&proposal, &config) {
+            Ok(ProposalState::Succeeded)
+        } else {
+            Ok(ProposalState::Defeated)
+        }
+    }
+
+    /// Set the soulbound badge contract that gates quadratic voting
+    pub fn set_badge(
+        env: Env,
+        admin: Address,
+        badge: Option<Address>,
+    ) -> Result<(), GovernanceError> {
+        Self::require_admin(&env, &admin)?;
+
+        match &badge {
+            Some(badge) => env.storage().instance().set(&DataKey::Badge, badge),
+            None => env.storage().instance().remove(&DataKey::Badge),
+        }
+
+        events::BadgeSetEvent { badge }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the badge contract gating quadratic voting, if any
+    pub fn get_badge(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Badge)
+    }
+
+    /// Set the security council allowed to cancel and veto queued
+    /// proposals
+    pub fn set_security_council(
+        env: Env,
+        admin: Address,
+        council: Option<Address>,
+    ) -> Result<(), GovernanceError> {
+        Self::require_admin(&env, &admin)?;
+
+        match &council {
+            Some(council) => env
+                .storage()
+                .instance()
+                .set(&DataKey::SecurityCouncil, council),
+            None => env.storage().instance().remove(&DataKey::SecurityCouncil),
+        }
+
+        events::SecurityCouncilSetEvent { council }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the security council, if any
+    pub fn get_security_council(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::SecurityCouncil)
+    }
+
+    /// Override the timelock for actions calling `function`, e.g. a longer
+    /// wait for `upgrade` or a shorter one for `set_fee`. `None` falls back
+    /// to the configured execution delay. An override may not be shorter
+    /// than the veto window, so the council always has time to veto.
+    pub fn set_action_delay(
+        env: Env,
+        admin: Address,
+        function: Symbol,
+        delay: Option<u64>,
+    ) -> Result<(), GovernanceError> {
+        Self::require_admin(&env, &admin)?;
+        let config = Self::get_config(env.clone())?;
+        if delay.is_some_and(|delay| delay < config.veto_window) {
+            return Err(GovernanceError::InvalidConfig);
+        }
+
+        let key = DataKey::ActionDelay(function.clone());
+        match delay {
+            Some(delay) => env.storage().instance().set(&key, &delay),
+            None => env.storage().instance().remove(&key),
+        }
+
+        events::ActionDelaySetEvent { function, delay }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Timelock applied to actions calling `function`
+    pub fn get_action_delay(env: Env, function: Symbol) -> Result<u64, GovernanceError> {
+        let config = Self::get_config(env.clone())?;
+        Ok(Self::action_delay(&env, &function, &config))
+    }
+
+    /// Get proposal data
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Result<Proposal, GovernanceError> {
+        Self::load_proposal(&env, proposal_id)
+    }
+
+    /// Get how `voter` voted on a proposal, if at all
+    pub fn get_vote(env: Env, proposal_id: u64, voter: Address) -> Option<VoteType> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Vote(proposal_id, voter))
+    }
+
+    /// Get governance parameters
+    pub fn get_config(env: Env) -> Result<GovernanceConfig, GovernanceError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(GovernanceError::NotInitialized)
+    }
+
+    /// Get the governance token
+    pub fn get_token(env: Env) -> Result<Address, GovernanceError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(GovernanceError::NotInitialized)
+    }
+
+    /// Get admin address
+    pub fn get_admin(env: Env) -> Result<Address, GovernanceError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(GovernanceError::NotInitialized)
+    }
+}
+
+impl GovernanceContract {
+    fn require_council(env: &Env, council: &Address) -> Result<(), GovernanceError> {
+        let stored_council =
+            Self::get_security_council(env.clone()).ok_or(GovernanceError::CouncilNotConfigured)?;
+        if *council != stored_council {
+            return Err(GovernanceError::Unauthorized);
+        }
+        council.require_auth();
+        Ok(())
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), GovernanceError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if stored_admin != *admin {
+            return Err(GovernanceError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    /// Tally `voter`'s vote once its authorization has been checked
+    fn record_vote(
+        env: &Env,
+        voter: Address,
+        proposal_id: u64,
+        support: VoteType,
+    ) -> Result<i128, GovernanceError> {
+        let mut proposal = Self::load_proposal(env, proposal_id)?;
+
+        if Self::state(env.clone(), proposal_id)? != ProposalState::Active {
+            return Err(GovernanceError::VotingClosed);
+        }
+
+        let vote_key = DataKey::Vote(proposal_id, voter.clone());
+        if env.storage().persistent().has(&vote_key) {
+            return Err(GovernanceError::AlreadyVoted);
+        }
+
+        let power = Self::voting_power(env, &voter, proposal.snapshot_ledger);
+        if power <= 0 {
+            return Err(GovernanceError::NoVotingPower);
+        }
+
+        let weight = match proposal.mode {
+            VotingMode::Standard => power,
+            VotingMode::Quadratic => {
+                let badge =
+                    Self::get_badge(env.clone()).ok_or(GovernanceError::BadgeNotConfigured)?;
+                if !token::has_badge(env, &badge, &voter) {
+                    return Err(GovernanceError::MissingBadge);
+                }
+                math::sqrt(power)
+            }
+        };
+
+        if support != VoteType::Against {
+            proposal.participation += power;
+        }
+        match support {
+            VoteType::For => proposal.for_votes += weight,
+            VoteType::Against => proposal.against_votes += weight,
+            VoteType::Abstain => proposal.abstain_votes += weight,
+        }
+        env.storage().persistent().set(&vote_key, &support);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        events::VoteCastEvent {
+            voter,
+            proposal_id,
+            support,
+            weight,
+        }
+        .publish(env);
+
+        Ok(weight)
+    }
+
+    fn validate_config(config: &GovernanceConfig) -> Result<(), GovernanceError> {
+        if config.voting_period == 0
+            || config.quorum < 0
+            || config.proposal_threshold < 0
+            || config.pass_threshold_bps == 0
+            || config.pass_threshold_bps as i128 > BPS_DENOMINATOR
+            || config.veto_window > config.execution_delay
+        {
+            return Err(GovernanceError::InvalidConfig);
+        }
+        Ok(())
+    }
+
+    fn action_delay(env: &Env, function: &Symbol, config: &GovernanceConfig) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ActionDelay(function.clone()))
+            .unwrap_or(config.execution_delay)
+    }
+
+    /// Identifies a set of actions across proposals, so a vetoed change
+    /// cannot come straight back under a new proposal id
+    fn actions_hash(env: &Env, actions: &Vec<ProposalAction>) -> BytesN<32> {
+        env.crypto().sha256(&actions.clone().to_xdr(env)).into()
+    }
+
+    fn load_proposal(env: &Env, proposal_id: u64) -> Result<Proposal, GovernanceError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(GovernanceError::ProposalNotFound)
+    }
+
+    /// Voting power is the governance token balance delegated to `account`
+    /// at the end of `ledger`, which includes its own balance unless it had
+    /// delegated elsewhere
+    fn voting_power(env: &Env, account: &Address, ledger: u32) -> i128 {
+        let token: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        token::votes_at(env, &token, account, ledger)
+    }
+
+    /// Quorum counts the raw power behind for and abstain votes; the
+    /// threshold compares tallied for weight against for + against
+    fn passed(proposal: &Proposal, config: &GovernanceConfig) -> bool {
+        if proposal.participation < config.quorum {
+            return false;
+        }
+
+        let decisive = proposal.for_votes + proposal.against_votes;
+        decisive > 0
+            && proposal.for_votes * BPS_DENOMINATOR > decisive * config.pass_threshold_bps as i128
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,2 @@
+/// Vote-weighting math
+pub use lumenpulse_fixed::{sqrt, BPS_DENOMINATOR};
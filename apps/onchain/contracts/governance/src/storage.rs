@@ -0,0 +1,23 @@
+use soroban_sdk::{contracttype, Address, BytesN, Symbol};
+
+pub use lumenpulse_governance_interface::{
+    GovernanceConfig, Proposal, ProposalAction, ProposalState, SignedVote, VetoRecord, VoteMessage,
+    VoteType, VotingMode,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                     // -> Address
+    Token,                     // -> Address
+    Config,                    // -> GovernanceConfig
+    NextProposalId,            // -> u64
+    Proposal(u64),             // proposal_id -> Proposal
+    Vote(u64, Address),        // (proposal_id, voter) -> VoteType
+    Badge,                     // -> Address
+    SecurityCouncil,           // -> Address
+    ActionDelay(Symbol),       // function -> u64
+    VoteKey(Address),          // voter -> BytesN<32>
+    Veto(u64),                 // proposal_id -> VetoRecord
+    VetoedActions(BytesN<32>), // actions hash -> u64 (cooldown end)
+}
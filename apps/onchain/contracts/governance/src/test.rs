@@ -0,0 +1,819 @@
+extern crate std;
+
+use crate::errors::GovernanceError;
+use crate::storage::{
+    GovernanceConfig, ProposalAction, ProposalState, SignedVote, VoteMessage, VoteType, VotingMode,
+};
+use crate::{GovernanceContract, GovernanceContractClient};
+use ed25519_dalek::{Signer, SigningKey};
+use lumen_token::{LumenToken, LumenTokenClient};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short,
+    testutils::{Address as _, Ledger},
+    vec,
+    xdr::ToXdr,
+    Address, BytesN, Env, IntoVal, String, Vec,
+};
+
+const VOTING_PERIOD: u64 = 3_600;
+const EXECUTION_DELAY: u64 = 86_400;
+const VETO_WINDOW: u64 = 43_200;
+const RESUBMIT_COOLDOWN: u64 = 604_800;
+
+#[contracttype]
+enum BadgeKey {
+    Holder(Address),
+}
+
+/// Badge registry that tests award badges through directly
+#[contract]
+struct MockBadge;
+
+#[contractimpl]
+impl MockBadge {
+    pub fn award(env: Env, account: Address) {
+        env.storage()
+            .instance()
+            .set(&BadgeKey::Holder(account), &true);
+    }
+
+    pub fn has_badge(env: Env, account: Address) -> bool {
+        env.storage().instance().has(&BadgeKey::Holder(account))
+    }
+}
+
+#[contracttype]
+enum TargetKey {
+    Admin,
+    Fee,
+}
+
+/// Stand-in for a governed contract with an admin-only setter
+#[contract]
+struct MockTarget;
+
+#[contractimpl]
+impl MockTarget {
+    pub fn init(env: Env, admin: Address) {
+        env.storage().instance().set(&TargetKey::Admin, &admin);
+    }
+
+    pub fn set_fee(env: Env, fee: u32) {
+        let admin: Address = env.storage().instance().get(&TargetKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage().instance().set(&TargetKey::Fee, &fee);
+    }
+
+    pub fn fee(env: Env) -> u32 {
+        env.storage().instance().get(&TargetKey::Fee).unwrap_or(0)
+    }
+}
+
+struct Setup<'a> {
+    env: &'a Env,
+    client: GovernanceContractClient<'a>,
+    token: LumenTokenClient<'a>,
+    admin: Address,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let token_id = env.register(LumenToken, ());
+    let token = LumenTokenClient::new(env, &token_id);
+    token.initialize(
+        &admin,
+        &7,
+        &String::from_str(env, "LumenPulse"),
+        &String::from_str(env, "LMN"),
+    );
+
+    let contract_id = env.register(GovernanceContract, ());
+    let client = GovernanceContractClient::new(env, &contract_id);
+    client.initialize(&admin, &token_id, &default_config());
+
+    Setup {
+        env,
+        client,
+        token,
+        admin,
+    }
+}
+
+fn default_config() -> GovernanceConfig {
+    GovernanceConfig {
+        voting_period: VOTING_PERIOD,
+        quorum: 1_000,
+        pass_threshold_bps: 5_000,
+        proposal_threshold: 100,
+        execution_delay: EXECUTION_DELAY,
+        veto_window: VETO_WINDOW,
+        resubmit_cooldown: RESUBMIT_COOLDOWN,
+    }
+}
+
+fn holder(setup: &Setup, amount: i128) -> Address {
+    let account = Address::generate(setup.env);
+    setup.token.mint(&setup.admin, &account, &amount);
+    account
+}
+
+fn sample_actions(env: &Env) -> Vec<ProposalAction> {
+    vec![
+        env,
+        ProposalAction {
+            target: Address::generate(env),
+            function: symbol_short!("set_fee"),
+            args: vec![env, 25u32.into_val(env)],
+        },
+    ]
+}
+
+fn propose(setup: &Setup, proposer: &Address) -> u64 {
+    propose_actions(setup, proposer, &sample_actions(setup.env))
+}
+
+/// Close the ledger holding earlier mints and delegations, then propose
+fn propose_actions(setup: &Setup, proposer: &Address, actions: &Vec<ProposalAction>) -> u64 {
+    next_ledger(setup.env);
+    setup.client.propose(
+        proposer,
+        &String::from_str(setup.env, "Lower protocol fee"),
+        actions,
+        &VotingMode::Standard,
+    )
+}
+
+fn next_ledger(env: &Env) {
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+}
+
+fn end_voting(env: &Env) {
+    env.ledger().with_mut(|li| li.timestamp += VOTING_PERIOD);
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+
+    assert_eq!(setup.client.get_admin(), setup.admin);
+    assert_eq!(setup.client.get_config(), default_config());
+
+    let result =
+        setup
+            .client
+            .try_initialize(&setup.admin, &setup.client.get_token(), &default_config());
+    assert_eq!(result, Err(Ok(GovernanceError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_initialize_rejects_invalid_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(GovernanceContract, ());
+    let client = GovernanceContractClient::new(&env, &contract_id);
+
+    let mut config = default_config();
+    config.pass_threshold_bps = 10_001;
+    let result = client.try_initialize(&Address::generate(&env), &Address::generate(&env), &config);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidConfig)));
+
+    // A veto window cannot outlast the timelock it guards
+    let mut config = default_config();
+    config.veto_window = EXECUTION_DELAY + 1;
+    let result = client.try_initialize(&Address::generate(&env), &Address::generate(&env), &config);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidConfig)));
+}
+
+#[test]
+fn test_propose() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+
+    let id = propose(&setup, &proposer);
+    assert_eq!(id, 0);
+    assert_eq!(propose(&setup, &proposer), 1);
+
+    let proposal = setup.client.get_proposal(&id);
+    assert_eq!(proposal.proposer, proposer);
+    assert_eq!(proposal.actions.len(), 1);
+    assert_eq!(proposal.end_time, proposal.start_time + VOTING_PERIOD);
+    assert_eq!(setup.client.state(&id), ProposalState::Active);
+}
+
+#[test]
+fn test_propose_requires_threshold_and_actions() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let small = holder(&setup, 99);
+    next_ledger(&env);
+
+    let result = setup.client.try_propose(
+        &small,
+        &String::from_str(&env, "Too small"),
+        &sample_actions(&env),
+        &VotingMode::Standard,
+    );
+    assert_eq!(result, Err(Ok(GovernanceError::BelowProposalThreshold)));
+
+    let proposer = holder(&setup, 500);
+    let result = setup.client.try_propose(
+        &proposer,
+        &String::from_str(&env, "Empty"),
+        &Vec::new(&env),
+        &VotingMode::Standard,
+    );
+    assert_eq!(result, Err(Ok(GovernanceError::NoActions)));
+}
+
+#[test]
+fn test_vote_and_succeed() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+    let alice = holder(&setup, 800);
+    let bob = holder(&setup, 300);
+    let carol = holder(&setup, 200);
+
+    let id = propose(&setup, &proposer);
+    assert_eq!(setup.client.cast_vote(&alice, &id, &VoteType::For), 800);
+    setup.client.cast_vote(&bob, &id, &VoteType::Against);
+    setup.client.cast_vote(&carol, &id, &VoteType::Abstain);
+
+    assert_eq!(setup.client.get_vote(&id, &alice), Some(VoteType::For));
+    assert_eq!(setup.client.get_vote(&id, &proposer), None);
+
+    let proposal = setup.client.get_proposal(&id);
+    assert_eq!(proposal.for_votes, 800);
+    assert_eq!(proposal.against_votes, 300);
+    assert_eq!(proposal.abstain_votes, 200);
+
+    end_voting(&env);
+    assert_eq!(setup.client.state(&id), ProposalState::Succeeded);
+}
+
+#[test]
+fn test_defeated_by_threshold() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+    let alice = holder(&setup, 1_000);
+    let bob = holder(&setup, 1_000);
+
+    let id = propose(&setup, &proposer);
+    setup.client.cast_vote(&alice, &id, &VoteType::For);
+    setup.client.cast_vote(&bob, &id, &VoteType::Against);
+
+    // An exact tie does not clear a 50% threshold
+    end_voting(&env);
+    assert_eq!(setup.client.state(&id), ProposalState::Defeated);
+}
+
+#[test]
+fn test_defeated_by_quorum() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+
+    let id = propose(&setup, &proposer);
+    setup.client.cast_vote(&proposer, &id, &VoteType::For);
+
+    end_voting(&env);
+    assert_eq!(setup.client.state(&id), ProposalState::Defeated);
+}
+
+#[test]
+fn test_abstain_counts_toward_quorum() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+    let abstainer = holder(&setup, 600);
+
+    let id = propose(&setup, &proposer);
+    setup.client.cast_vote(&proposer, &id, &VoteType::For);
+    setup.client.cast_vote(&abstainer, &id, &VoteType::Abstain);
+
+    end_voting(&env);
+    assert_eq!(setup.client.state(&id), ProposalState::Succeeded);
+}
+
+#[test]
+fn test_vote_errors() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+    let nobody = Address::generate(&env);
+
+    let id = propose(&setup, &proposer);
+    setup.client.cast_vote(&proposer, &id, &VoteType::For);
+
+    let result = setup
+        .client
+        .try_cast_vote(&proposer, &id, &VoteType::Against);
+    assert_eq!(result, Err(Ok(GovernanceError::AlreadyVoted)));
+
+    let result = setup.client.try_cast_vote(&nobody, &id, &VoteType::For);
+    assert_eq!(result, Err(Ok(GovernanceError::NoVotingPower)));
+
+    let result = setup.client.try_cast_vote(&proposer, &99, &VoteType::For);
+    assert_eq!(result, Err(Ok(GovernanceError::ProposalNotFound)));
+
+    end_voting(&env);
+    let late = holder(&setup, 500);
+    let result = setup.client.try_cast_vote(&late, &id, &VoteType::For);
+    assert_eq!(result, Err(Ok(GovernanceError::VotingClosed)));
+}
+
+#[test]
+fn test_cancel() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+    let voter = holder(&setup, 2_000);
+
+    let id = propose(&setup, &proposer);
+    setup.client.cancel(&id);
+    assert_eq!(setup.client.state(&id), ProposalState::Canceled);
+
+    let result = setup.client.try_cast_vote(&voter, &id, &VoteType::For);
+    assert_eq!(result, Err(Ok(GovernanceError::VotingClosed)));
+
+    let result = setup.client.try_cancel(&id);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidState)));
+}
+
+#[test]
+fn test_delegated_votes() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+    let delegator = holder(&setup, 700);
+    let delegatee = holder(&setup, 100);
+
+    setup.token.delegate(&delegator, &delegatee);
+
+    let id = propose(&setup, &proposer);
+    assert_eq!(setup.client.cast_vote(&delegatee, &id, &VoteType::For), 800);
+
+    // Having delegated, the holder has nothing left to vote with
+    let result = setup
+        .client
+        .try_cast_vote(&delegator, &id, &VoteType::Against);
+    assert_eq!(result, Err(Ok(GovernanceError::NoVotingPower)));
+
+    end_voting(&env);
+    assert_eq!(setup.client.state(&id), ProposalState::Defeated);
+}
+
+#[test]
+fn test_voting_power_fixed_at_snapshot() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+    let seller = holder(&setup, 900);
+
+    let id = propose(&setup, &proposer);
+    assert_eq!(
+        setup.client.get_proposal(&id).snapshot_ledger,
+        env.ledger().sequence() - 1
+    );
+
+    // Tokens bought after the snapshot carry no weight
+    let buyer = Address::generate(&env);
+    setup.token.transfer(&seller, &buyer, &900);
+    next_ledger(&env);
+
+    let result = setup.client.try_cast_vote(&buyer, &id, &VoteType::For);
+    assert_eq!(result, Err(Ok(GovernanceError::NoVotingPower)));
+
+    // The seller still votes with what it held at the snapshot
+    assert_eq!(setup.client.cast_vote(&seller, &id, &VoteType::For), 900);
+
+    // A holder that had not closed a ledger yet cannot propose
+    let fresh = holder(&setup, 5_000);
+    let result = setup.client.try_propose(
+        &fresh,
+        &String::from_str(&env, "Too early"),
+        &sample_actions(&env),
+        &VotingMode::Standard,
+    );
+    assert_eq!(result, Err(Ok(GovernanceError::BelowProposalThreshold)));
+}
+
+#[test]
+fn test_queue_and_execute() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 2_000);
+
+    let target_id = env.register(MockTarget, ());
+    let target = MockTargetClient::new(&env, &target_id);
+    target.init(&setup.client.address);
+
+    let actions = vec![
+        &env,
+        ProposalAction {
+            target: target_id.clone(),
+            function: symbol_short!("set_fee"),
+            args: vec![&env, 25u32.into_val(&env)],
+        },
+    ];
+    let id = propose_actions(&setup, &proposer, &actions);
+    setup.client.cast_vote(&proposer, &id, &VoteType::For);
+
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidState)));
+
+    end_voting(&env);
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidState)));
+
+    let eta = setup.client.queue(&id);
+    assert_eq!(eta, env.ledger().timestamp() + EXECUTION_DELAY);
+    assert_eq!(setup.client.state(&id), ProposalState::Queued);
+
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(GovernanceError::TimelockNotElapsed)));
+
+    env.ledger().with_mut(|li| li.timestamp = eta);
+    setup.client.execute(&id);
+    assert_eq!(target.fee(), 25);
+    assert_eq!(setup.client.state(&id), ProposalState::Executed);
+
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidState)));
+}
+
+#[test]
+fn test_execute_defeated_proposal() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+
+    let id = propose(&setup, &proposer);
+    setup.client.cast_vote(&proposer, &id, &VoteType::For);
+
+    end_voting(&env);
+    let result = setup.client.try_queue(&id);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidState)));
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidState)));
+}
+
+#[test]
+fn test_per_action_delay() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 2_000);
+    let upgrade_delay = 7 * EXECUTION_DELAY;
+
+    setup.client.set_action_delay(
+        &setup.admin,
+        &symbol_short!("upgrade"),
+        &Some(upgrade_delay),
+    );
+    setup
+        .client
+        .set_action_delay(&setup.admin, &symbol_short!("set_fee"), &Some(VETO_WINDOW));
+    assert_eq!(
+        setup.client.get_action_delay(&symbol_short!("upgrade")),
+        upgrade_delay
+    );
+    assert_eq!(
+        setup.client.get_action_delay(&symbol_short!("withdraw")),
+        EXECUTION_DELAY
+    );
+
+    // A fee tweak alone waits the short delay
+    let fee_only = propose(&setup, &proposer);
+    setup.client.cast_vote(&proposer, &fee_only, &VoteType::For);
+
+    // Bundling an upgrade makes the whole proposal wait the longest delay
+    let mut actions = sample_actions(&env);
+    actions.push_back(ProposalAction {
+        target: Address::generate(&env),
+        function: symbol_short!("upgrade"),
+        args: Vec::new(&env),
+    });
+    let with_upgrade = propose_actions(&setup, &proposer, &actions);
+    setup
+        .client
+        .cast_vote(&proposer, &with_upgrade, &VoteType::For);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += 2 * VOTING_PERIOD);
+    let now = env.ledger().timestamp();
+    assert_eq!(setup.client.queue(&fee_only), now + VETO_WINDOW);
+    assert_eq!(setup.client.queue(&with_upgrade), now + upgrade_delay);
+
+    let result = setup
+        .client
+        .try_set_action_delay(&proposer, &symbol_short!("upgrade"), &None);
+    assert_eq!(result, Err(Ok(GovernanceError::Unauthorized)));
+}
+
+#[test]
+fn test_action_delay_cannot_undercut_veto_window() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let function = symbol_short!("set_fee");
+
+    // A shorter wait would let the action run before the council could veto
+    for delay in [0, VETO_WINDOW - 1] {
+        let result = setup
+            .client
+            .try_set_action_delay(&setup.admin, &function, &Some(delay));
+        assert_eq!(result, Err(Ok(GovernanceError::InvalidConfig)));
+    }
+    assert_eq!(setup.client.get_action_delay(&function), EXECUTION_DELAY);
+
+    setup
+        .client
+        .set_action_delay(&setup.admin, &function, &Some(VETO_WINDOW));
+    assert_eq!(setup.client.get_action_delay(&function), VETO_WINDOW);
+}
+
+#[test]
+fn test_security_council_veto() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 2_000);
+    let council = Address::generate(&env);
+
+    let id = propose(&setup, &proposer);
+    setup.client.cast_vote(&proposer, &id, &VoteType::For);
+    end_voting(&env);
+    setup.client.queue(&id);
+
+    let result = setup.client.try_veto(&council, &id);
+    assert_eq!(result, Err(Ok(GovernanceError::CouncilNotConfigured)));
+
+    setup
+        .client
+        .set_security_council(&setup.admin, &Some(council.clone()));
+    let result = setup.client.try_veto(&proposer, &id);
+    assert_eq!(result, Err(Ok(GovernanceError::Unauthorized)));
+
+    setup.client.veto(&council, &id);
+    assert_eq!(setup.client.state(&id), ProposalState::Vetoed);
+
+    let record = setup.client.get_veto(&id).unwrap();
+    assert_eq!(record.council, council);
+    assert_eq!(record.vetoed_at, env.ledger().timestamp());
+    assert_eq!(record.resubmit_after, record.vetoed_at + RESUBMIT_COOLDOWN);
+
+    env.ledger().with_mut(|li| li.timestamp += EXECUTION_DELAY);
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidState)));
+}
+
+fn setup_badge<'a>(setup: &Setup<'a>) -> MockBadgeClient<'a> {
+    let badge_id = setup.env.register(MockBadge, ());
+    setup
+        .client
+        .set_badge(&setup.admin, &Some(badge_id.clone()));
+    MockBadgeClient::new(setup.env, &badge_id)
+}
+
+fn propose_quadratic(setup: &Setup, proposer: &Address) -> u64 {
+    next_ledger(setup.env);
+    setup.client.propose(
+        proposer,
+        &String::from_str(setup.env, "Fund community grants"),
+        &sample_actions(setup.env),
+        &VotingMode::Quadratic,
+    )
+}
+
+#[test]
+fn test_quadratic_voting() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let badge = setup_badge(&setup);
+    let proposer = holder(&setup, 500);
+    let whale = holder(&setup, 10_000);
+    let small1 = holder(&setup, 900);
+    let small2 = holder(&setup, 1_000);
+    for account in [&whale, &small1, &small2] {
+        badge.award(account);
+    }
+
+    let id = propose_quadratic(&setup, &proposer);
+    assert_eq!(setup.client.cast_vote(&whale, &id, &VoteType::Against), 100);
+    assert_eq!(setup.client.cast_vote(&small1, &id, &VoteType::For), 30);
+    assert_eq!(setup.client.cast_vote(&small2, &id, &VoteType::For), 31);
+
+    let proposal = setup.client.get_proposal(&id);
+    assert_eq!(proposal.for_votes, 61);
+    assert_eq!(proposal.against_votes, 100);
+    assert_eq!(proposal.participation, 1_900);
+
+    end_voting(&env);
+    assert_eq!(setup.client.state(&id), ProposalState::Defeated);
+}
+
+#[test]
+fn test_quadratic_voting_requires_badge() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+
+    next_ledger(&env);
+    let result = setup.client.try_propose(
+        &proposer,
+        &String::from_str(&env, "No badge registry"),
+        &sample_actions(&env),
+        &VotingMode::Quadratic,
+    );
+    assert_eq!(result, Err(Ok(GovernanceError::BadgeNotConfigured)));
+
+    let badge = setup_badge(&setup);
+    let id = propose_quadratic(&setup, &proposer);
+
+    let result = setup.client.try_cast_vote(&proposer, &id, &VoteType::For);
+    assert_eq!(result, Err(Ok(GovernanceError::MissingBadge)));
+
+    badge.award(&proposer);
+    assert_eq!(setup.client.cast_vote(&proposer, &id, &VoteType::For), 22);
+}
+
+#[test]
+fn test_set_badge_unauthorized() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+
+    let result = setup
+        .client
+        .try_set_badge(&Address::generate(&env), &Some(Address::generate(&env)));
+    assert_eq!(result, Err(Ok(GovernanceError::Unauthorized)));
+    assert_eq!(setup.client.get_badge(), None);
+}
+
+/// Holder with a registered off-chain vote key
+fn signing_holder(setup: &Setup, amount: i128, seed: u8) -> (Address, SigningKey) {
+    let account = holder(setup, amount);
+    let key = SigningKey::from_bytes(&[seed; 32]);
+    let public_key = BytesN::from_array(setup.env, &key.verifying_key().to_bytes());
+    setup.client.register_vote_key(&account, &public_key);
+    (account, key)
+}
+
+fn sign_vote(
+    setup: &Setup,
+    key: &SigningKey,
+    voter: &Address,
+    proposal_id: u64,
+    support: VoteType,
+) -> SignedVote {
+    let message = VoteMessage {
+        governance: setup.client.address.clone(),
+        proposal_id,
+        support,
+    };
+    let payload = message.to_xdr(setup.env);
+    let mut buf = std::vec![0u8; payload.len() as usize];
+    payload.copy_into_slice(&mut buf);
+
+    SignedVote {
+        voter: voter.clone(),
+        proposal_id,
+        support,
+        signature: BytesN::from_array(setup.env, &key.sign(&buf).to_bytes()),
+    }
+}
+
+#[test]
+fn test_cast_votes_by_sig() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+    let (alice, alice_key) = signing_holder(&setup, 700, 1);
+    let (bob, bob_key) = signing_holder(&setup, 400, 2);
+
+    let id = propose(&setup, &proposer);
+    let votes = vec![
+        &env,
+        sign_vote(&setup, &alice_key, &alice, id, VoteType::For),
+        sign_vote(&setup, &bob_key, &bob, id, VoteType::Against),
+    ];
+    assert_eq!(setup.client.cast_votes_by_sig(&votes), 2);
+
+    let proposal = setup.client.get_proposal(&id);
+    assert_eq!(proposal.for_votes, 700);
+    assert_eq!(proposal.against_votes, 400);
+    assert_eq!(setup.client.get_vote(&id, &alice), Some(VoteType::For));
+
+    // Replaying the batch cannot count the votes twice
+    let result = setup.client.try_cast_votes_by_sig(&votes);
+    assert_eq!(result, Err(Ok(GovernanceError::AlreadyVoted)));
+}
+
+#[test]
+fn test_cast_votes_by_sig_rejects_bad_signature() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+    let (alice, alice_key) = signing_holder(&setup, 700, 1);
+
+    let id = propose(&setup, &proposer);
+
+    // A relayer flipping the choice invalidates the signature
+    let mut vote = sign_vote(&setup, &alice_key, &alice, id, VoteType::For);
+    vote.support = VoteType::Against;
+    let result = setup.client.try_cast_votes_by_sig(&vec![&env, vote]);
+    assert!(result.is_err());
+
+    // So does signing with a key other than the registered one
+    let other_key = SigningKey::from_bytes(&[9; 32]);
+    let vote = sign_vote(&setup, &other_key, &alice, id, VoteType::For);
+    let result = setup.client.try_cast_votes_by_sig(&vec![&env, vote]);
+    assert!(result.is_err());
+
+    assert_eq!(setup.client.get_vote(&id, &alice), None);
+}
+
+#[test]
+fn test_cast_votes_by_sig_errors() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 500);
+    let unregistered = holder(&setup, 700);
+    let key = SigningKey::from_bytes(&[3; 32]);
+
+    let id = propose(&setup, &proposer);
+
+    let result = setup.client.try_cast_votes_by_sig(&Vec::new(&env));
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidBatch)));
+
+    let vote = sign_vote(&setup, &key, &unregistered, id, VoteType::For);
+    let result = setup.client.try_cast_votes_by_sig(&vec![&env, vote]);
+    assert_eq!(result, Err(Ok(GovernanceError::VoteKeyNotRegistered)));
+}
+
+#[test]
+fn test_veto_window_closes() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 2_000);
+    let council = Address::generate(&env);
+    setup
+        .client
+        .set_security_council(&setup.admin, &Some(council.clone()));
+
+    let id = propose(&setup, &proposer);
+    setup.client.cast_vote(&proposer, &id, &VoteType::For);
+    end_voting(&env);
+    setup.client.queue(&id);
+
+    env.ledger().with_mut(|li| li.timestamp += VETO_WINDOW);
+    let result = setup.client.try_veto(&council, &id);
+    assert_eq!(result, Err(Ok(GovernanceError::VetoWindowClosed)));
+    assert_eq!(setup.client.get_veto(&id), None);
+
+    // The council can still stop it for the rest of the timelock
+    let result = setup.client.try_cancel_queued(&proposer, &id);
+    assert_eq!(result, Err(Ok(GovernanceError::Unauthorized)));
+    setup.client.cancel_queued(&council, &id);
+    assert_eq!(setup.client.state(&id), ProposalState::Canceled);
+    env.ledger().with_mut(|li| li.timestamp += EXECUTION_DELAY);
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(GovernanceError::InvalidState)));
+}
+
+#[test]
+fn test_vetoed_actions_resubmission_cooldown() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = holder(&setup, 2_000);
+    let council = Address::generate(&env);
+    setup
+        .client
+        .set_security_council(&setup.admin, &Some(council.clone()));
+
+    let actions = sample_actions(&env);
+    let id = propose_actions(&setup, &proposer, &actions);
+    setup.client.cast_vote(&proposer, &id, &VoteType::For);
+    end_voting(&env);
+    setup.client.queue(&id);
+    setup.client.veto(&council, &id);
+
+    next_ledger(&env);
+    let result = setup.client.try_propose(
+        &proposer,
+        &String::from_str(&env, "Same change again"),
+        &actions,
+        &VotingMode::Standard,
+    );
+    assert_eq!(result, Err(Ok(GovernanceError::ResubmissionCooldown)));
+
+    // Different actions are unaffected
+    propose(&setup, &proposer);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += RESUBMIT_COOLDOWN);
+    let resubmitted = propose_actions(&setup, &proposer, &actions);
+    assert_eq!(setup.client.state(&resubmitted), ProposalState::Active);
+}
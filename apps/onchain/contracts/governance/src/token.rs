@@ -0,0 +1,21 @@
+use lumenpulse_token_interface::LumenTokenClient;
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Anti-sybil check: a non-transferable badge proving a unique participant
+#[allow(dead_code)]
+#[contractclient(name = "BadgeClient")]
+pub trait Badge {
+    fn has_badge(env: Env, account: Address) -> bool;
+}
+
+/// Get the voting power delegated to an address as of the end of `ledger`.
+/// Like the token's `balance_at`, this reads finalized checkpoints, so it
+/// cannot be moved by transfers made after that ledger.
+pub fn votes_at(env: &Env, token: &Address, account: &Address, ledger: u32) -> i128 {
+    LumenTokenClient::new(env, token).votes_at(account, &ledger)
+}
+
+/// Whether `account` holds a badge from the `badge` contract
+pub fn has_badge(env: &Env, badge: &Address, account: &Address) -> bool {
+    BadgeClient::new(env, badge).has_badge(account)
+}
@@ -0,0 +1,18 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum InvoiceError {
+    Unauthorized = 1,
+    InvoiceNotFound = 2,
+    InvalidAmount = 3,
+    InvalidDueLedger = 4,
+    InvalidParties = 5,
+    ExceedsOutstanding = 6,
+    NotOpen = 7,
+    NoRefundPolicy = 8,
+    NotRefundable = 9,
+    InsufficientReserve = 10,
+    InvalidRefundPolicy = 11,
+}
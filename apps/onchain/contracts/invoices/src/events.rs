@@ -0,0 +1,69 @@
+use lumenpulse_refunds::RefundPolicy;
+use soroban_sdk::{contractevent, Address, BytesN};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceIssuedEvent {
+    #[topic]
+    pub payee: Address,
+    #[topic]
+    pub payer: Address,
+    pub invoice_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub due_ledger: u32,
+    pub memo_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoicePaidEvent {
+    #[topic]
+    pub invoice_id: u64,
+    pub amount: i128,
+    pub outstanding: i128,
+    /// Whether the payment came after the due ledger
+    pub late: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceSettledEvent {
+    #[topic]
+    pub invoice_id: u64,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceCancelledEvent {
+    #[topic]
+    pub invoice_id: u64,
+    pub paid: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundPolicySetEvent {
+    #[topic]
+    pub payee: Address,
+    pub policy: Option<RefundPolicy>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundReserveUpdatedEvent {
+    #[topic]
+    pub payee: Address,
+    #[topic]
+    pub token: Address,
+    pub reserve: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceRefundedEvent {
+    #[topic]
+    pub invoice_id: u64,
+    pub amount: i128,
+}
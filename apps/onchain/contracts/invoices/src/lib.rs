@@ -0,0 +1,389 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::InvoiceError;
+use lumenpulse_refunds::{RefundError, RefundPolicy};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+use storage::{DataKey, Invoice, InvoiceStatus, MAX_PAGE};
+
+/// Invoices between a payee and a payer. The payee issues an invoice for
+/// an amount of a token due by a ledger, and the payer settles it in one
+/// payment or several, each paid straight to the payee. Every party's
+/// invoices are indexed so their open, overdue and settled invoices can
+/// be listed page by page.
+///
+/// A payee may set a refund policy and fund a reserve to pay refunds from.
+/// Within the policy's windows after its latest payment, the payer can
+/// have what it paid towards an invoice refunded, closing the invoice.
+///
+/// There is no circuit breaker, as the contract has no admin to hold one.
+/// Payments go straight to the payee, and the only funds held are refund
+/// reserves, which their payee can withdraw at any time.
+#[contract]
+pub struct InvoiceContract;
+
+#[contractimpl]
+impl InvoiceContract {
+    /// Bill `payer` for `amount` of `token`, due by `due_ledger`, with the
+    /// hash of a memo describing the work. Returns the invoice ID.
+    pub fn issue(
+        env: Env,
+        payee: Address,
+        payer: Address,
+        token: Address,
+        amount: i128,
+        due_ledger: u32,
+        memo_hash: BytesN<32>,
+    ) -> Result<u64, InvoiceError> {
+        payee.require_auth();
+
+        if payee == payer {
+            return Err(InvoiceError::InvalidParties);
+        }
+        if amount <= 0 {
+            return Err(InvoiceError::InvalidAmount);
+        }
+        if due_ledger <= env.ledger().sequence() {
+            return Err(InvoiceError::InvalidDueLedger);
+        }
+
+        let invoice_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextInvoiceId)
+            .unwrap_or(0);
+        let invoice = Invoice {
+            id: invoice_id,
+            payee: payee.clone(),
+            payer: payer.clone(),
+            token: token.clone(),
+            amount,
+            paid: 0,
+            paid_ledger: 0,
+            refunded: 0,
+            due_ledger,
+            memo_hash: memo_hash.clone(),
+            status: InvoiceStatus::Open,
+            settled_ledger: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Invoice(invoice_id), &invoice);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextInvoiceId, &(invoice_id + 1));
+        Self::index(
+            &env,
+            DataKey::PayerCount(payer.clone()),
+            |index| DataKey::PayerInvoice(payer.clone(), index),
+            invoice_id,
+        );
+        Self::index(
+            &env,
+            DataKey::PayeeCount(payee.clone()),
+            |index| DataKey::PayeeInvoice(payee.clone(), index),
+            invoice_id,
+        );
+
+        events::InvoiceIssuedEvent {
+            payee,
+            payer,
+            invoice_id,
+            token,
+            amount,
+            due_ledger,
+            memo_hash,
+        }
+        .publish(&env);
+
+        Ok(invoice_id)
+    }
+
+    /// Pay `amount` towards an invoice (payer only), before or after it is
+    /// due. Returns what is still outstanding.
+    pub fn pay(env: Env, invoice_id: u64, amount: i128) -> Result<i128, InvoiceError> {
+        let mut invoice = Self::load(&env, invoice_id)?;
+
+        // Require payer authorization
+        invoice.payer.require_auth();
+
+        if invoice.status != InvoiceStatus::Open {
+            return Err(InvoiceError::NotOpen);
+        }
+        if amount <= 0 {
+            return Err(InvoiceError::InvalidAmount);
+        }
+        let outstanding = invoice.amount - invoice.paid;
+        if amount > outstanding {
+            return Err(InvoiceError::ExceedsOutstanding);
+        }
+
+        let ledger = env.ledger().sequence();
+        invoice.paid += amount;
+        invoice.paid_ledger = ledger;
+        if invoice.paid == invoice.amount {
+            invoice.status = InvoiceStatus::Settled;
+            invoice.settled_ledger = Some(ledger);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Invoice(invoice_id), &invoice);
+
+        token::transfer(
+            &env,
+            &invoice.token,
+            &invoice.payer,
+            &invoice.payee,
+            &amount,
+        );
+
+        events::InvoicePaidEvent {
+            invoice_id,
+            amount,
+            outstanding: outstanding - amount,
+            late: ledger > invoice.due_ledger,
+        }
+        .publish(&env);
+        if invoice.status == InvoiceStatus::Settled {
+            events::InvoiceSettledEvent {
+                invoice_id,
+                amount: invoice.amount,
+            }
+            .publish(&env);
+        }
+
+        Ok(outstanding - amount)
+    }
+
+    /// Withdraw an invoice that has not been settled (payee only). The
+    /// payee keeps anything already paid towards it.
+    pub fn cancel(env: Env, invoice_id: u64) -> Result<(), InvoiceError> {
+        let mut invoice = Self::load(&env, invoice_id)?;
+
+        // Require payee authorization
+        invoice.payee.require_auth();
+
+        if invoice.status != InvoiceStatus::Open {
+            return Err(InvoiceError::NotOpen);
+        }
+        invoice.status = InvoiceStatus::Cancelled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Invoice(invoice_id), &invoice);
+
+        events::InvoiceCancelledEvent {
+            invoice_id,
+            paid: invoice.paid,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Set or clear the refund policy applied to payments on `payee`'s
+    /// invoices (payee only)
+    pub fn set_refund_policy(
+        env: Env,
+        payee: Address,
+        policy: Option<RefundPolicy>,
+    ) -> Result<(), InvoiceError> {
+        payee.require_auth();
+
+        lumenpulse_refunds::set_policy(&env, &payee, &policy).map_err(Self::refund_error)?;
+
+        events::RefundPolicySetEvent { payee, policy }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Add `amount` of `token` to the reserve `payee`'s refunds are paid
+    /// from. Returns the new reserve.
+    pub fn fund_refund_reserve(
+        env: Env,
+        payee: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, InvoiceError> {
+        payee.require_auth();
+
+        let reserve = lumenpulse_refunds::fund_reserve(&env, &payee, &token, amount)
+            .map_err(Self::refund_error)?;
+
+        events::RefundReserveUpdatedEvent {
+            payee,
+            token,
+            reserve,
+        }
+        .publish(&env);
+
+        Ok(reserve)
+    }
+
+    /// Take `amount` of `token` back out of `payee`'s refund reserve.
+    /// Returns the new reserve.
+    pub fn withdraw_refund_reserve(
+        env: Env,
+        payee: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, InvoiceError> {
+        payee.require_auth();
+
+        let reserve = lumenpulse_refunds::withdraw_reserve(&env, &payee, &token, amount)
+            .map_err(Self::refund_error)?;
+
+        events::RefundReserveUpdatedEvent {
+            payee,
+            token,
+            reserve,
+        }
+        .publish(&env);
+
+        Ok(reserve)
+    }
+
+    /// Refund what has been paid towards an invoice as far as the payee's
+    /// policy allows, timed from the latest payment, and close the invoice
+    /// (payer only). Returns the amount refunded.
+    pub fn request_refund(env: Env, invoice_id: u64) -> Result<i128, InvoiceError> {
+        let mut invoice = Self::load(&env, invoice_id)?;
+
+        // Require payer authorization
+        invoice.payer.require_auth();
+
+        if invoice.status == InvoiceStatus::Refunded || invoice.paid == 0 {
+            return Err(InvoiceError::NotRefundable);
+        }
+        let refund =
+            lumenpulse_refunds::evaluate(&env, &invoice.payee, invoice.paid, invoice.paid_ledger)
+                .map_err(Self::refund_error)?;
+
+        invoice.refunded = refund;
+        invoice.status = InvoiceStatus::Refunded;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Invoice(invoice_id), &invoice);
+
+        lumenpulse_refunds::execute(&env, &invoice.payee, &invoice.token, &invoice.payer, refund)
+            .map_err(Self::refund_error)?;
+
+        events::InvoiceRefundedEvent {
+            invoice_id,
+            amount: refund,
+        }
+        .publish(&env);
+
+        Ok(refund)
+    }
+
+    /// Get an invoice by ID, reading as overdue if it is open past its due
+    /// ledger
+    pub fn get_invoice(env: Env, invoice_id: u64) -> Result<Invoice, InvoiceError> {
+        let mut invoice = Self::load(&env, invoice_id)?;
+        if invoice.status == InvoiceStatus::Open && env.ledger().sequence() > invoice.due_ledger {
+            invoice.status = InvoiceStatus::Overdue;
+        }
+        Ok(invoice)
+    }
+
+    /// `payee`'s refund policy, if it has one
+    pub fn refund_policy(env: Env, payee: Address) -> Option<RefundPolicy> {
+        lumenpulse_refunds::policy(&env, &payee)
+    }
+
+    /// `payee`'s reserve of `token` for refunds
+    pub fn refund_reserve(env: Env, payee: Address, token: Address) -> i128 {
+        lumenpulse_refunds::reserve(&env, &payee, &token)
+    }
+
+    /// Up to `limit` of the invoices billed to `payer`, oldest first,
+    /// starting at position `cursor`
+    pub fn invoices_of_payer(env: Env, payer: Address, cursor: u32, limit: u32) -> Vec<Invoice> {
+        Self::page(
+            &env,
+            DataKey::PayerCount(payer.clone()),
+            |index| DataKey::PayerInvoice(payer.clone(), index),
+            cursor,
+            limit,
+        )
+    }
+
+    /// Up to `limit` of the invoices issued by `payee`, oldest first,
+    /// starting at position `cursor`
+    pub fn invoices_of_payee(env: Env, payee: Address, cursor: u32, limit: u32) -> Vec<Invoice> {
+        Self::page(
+            &env,
+            DataKey::PayeeCount(payee.clone()),
+            |index| DataKey::PayeeInvoice(payee.clone(), index),
+            cursor,
+            limit,
+        )
+    }
+
+    /// Number of invoices billed to `payer`
+    pub fn payer_invoice_count(env: Env, payer: Address) -> u32 {
+        Self::count(&env, &DataKey::PayerCount(payer))
+    }
+
+    /// Number of invoices issued by `payee`
+    pub fn payee_invoice_count(env: Env, payee: Address) -> u32 {
+        Self::count(&env, &DataKey::PayeeCount(payee))
+    }
+}
+
+impl InvoiceContract {
+    fn refund_error(error: RefundError) -> InvoiceError {
+        match error {
+            RefundError::NoPolicy => InvoiceError::NoRefundPolicy,
+            RefundError::NotRefundable => InvoiceError::NotRefundable,
+            RefundError::InsufficientReserve => InvoiceError::InsufficientReserve,
+            RefundError::InvalidPolicy => InvoiceError::InvalidRefundPolicy,
+            RefundError::InvalidAmount => InvoiceError::InvalidAmount,
+        }
+    }
+
+    fn load(env: &Env, invoice_id: u64) -> Result<Invoice, InvoiceError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Invoice(invoice_id))
+            .ok_or(InvoiceError::InvoiceNotFound)
+    }
+
+    fn count(env: &Env, count_key: &DataKey) -> u32 {
+        env.storage().persistent().get(count_key).unwrap_or(0)
+    }
+
+    /// Append `invoice_id` to a party's list of invoices
+    fn index(env: &Env, count_key: DataKey, entry: impl Fn(u32) -> DataKey, invoice_id: u64) {
+        let count = Self::count(env, &count_key);
+        env.storage().persistent().set(&entry(count), &invoice_id);
+        env.storage().persistent().set(&count_key, &(count + 1));
+    }
+
+    fn page(
+        env: &Env,
+        count_key: DataKey,
+        entry: impl Fn(u32) -> DataKey,
+        cursor: u32,
+        limit: u32,
+    ) -> Vec<Invoice> {
+        let end = Self::count(env, &count_key).min(cursor.saturating_add(limit.min(MAX_PAGE)));
+        let mut invoices = Vec::new(env);
+        for index in cursor..end {
+            let invoice_id: Option<u64> = env.storage().persistent().get(&entry(index));
+            if let Some(Ok(invoice)) =
+                invoice_id.map(|invoice_id| Self::get_invoice(env.clone(), invoice_id))
+            {
+                invoices.push_back(invoice);
+            }
+        }
+        invoices
+    }
+}
+
+#[cfg(test)]
+mod test;
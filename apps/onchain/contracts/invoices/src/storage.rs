@@ -0,0 +1,52 @@
+use soroban_sdk::{contracttype, Address, BytesN};
+
+/// Most invoices returned by one page of a listing
+pub const MAX_PAGE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextInvoiceId,              // -> u64
+    Invoice(u64),               // invoice_id -> Invoice
+    PayerCount(Address),        // payer -> u32
+    PayerInvoice(Address, u32), // (payer, index) -> invoice_id
+    PayeeCount(Address),        // payee -> u32
+    PayeeInvoice(Address, u32), // (payee, index) -> invoice_id
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvoiceStatus {
+    /// Awaiting payment, in full or in parts
+    Open,
+    /// Still open after its due ledger. Never stored: open invoices read
+    /// as overdue once the due ledger has passed.
+    Overdue,
+    Settled,
+    Cancelled,
+    /// Paid back to the payer under the payee's refund policy
+    Refunded,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invoice {
+    pub id: u64,
+    pub payee: Address,
+    pub payer: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Paid so far
+    pub paid: i128,
+    /// Ledger the latest payment was made in
+    pub paid_ledger: u32,
+    /// Paid back to the payer
+    pub refunded: i128,
+    /// Last ledger the invoice is due in
+    pub due_ledger: u32,
+    /// Hash of the off-chain memo describing what is billed
+    pub memo_hash: BytesN<32>,
+    pub status: InvoiceStatus,
+    /// Ledger the final payment was made in
+    pub settled_ledger: Option<u32>,
+}
@@ -0,0 +1,181 @@
+use crate::errors::InvoiceError;
+use crate::storage::{Invoice, InvoiceStatus};
+use crate::{InvoiceContract, InvoiceContractClient};
+use lumenpulse_refunds::RefundPolicy;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, Vec,
+};
+
+const DUE: u32 = 100;
+
+struct Setup<'a> {
+    client: InvoiceContractClient<'a>,
+    payee: Address,
+    payer: Address,
+    token: TokenClient<'a>,
+}
+
+impl Setup<'_> {
+    fn issue(&self, env: &Env, amount: i128) -> u64 {
+        self.client.issue(
+            &self.payee,
+            &self.payer,
+            &self.token.address,
+            &amount,
+            &DUE,
+            &BytesN::from_array(env, &[7; 32]),
+        )
+    }
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let payee = Address::generate(env);
+    let payer = Address::generate(env);
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &sac.address()).mint(&payer, &10_000);
+
+    Setup {
+        client: InvoiceContractClient::new(env, &env.register(InvoiceContract, ())),
+        payee,
+        payer,
+        token: TokenClient::new(env, &sac.address()),
+    }
+}
+
+#[test]
+fn test_partial_payments_settle() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let invoice_id = s.issue(&env, 1_000);
+
+    assert_eq!(s.client.pay(&invoice_id, &400), 600);
+    let result = s.client.try_pay(&invoice_id, &601);
+    assert_eq!(result, Err(Ok(InvoiceError::ExceedsOutstanding)));
+    assert_eq!(
+        s.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Open
+    );
+
+    env.ledger().set_sequence_number(50);
+    assert_eq!(s.client.pay(&invoice_id, &600), 0);
+    let invoice = s.client.get_invoice(&invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::Settled);
+    assert_eq!(invoice.settled_ledger, Some(50));
+    assert_eq!(s.token.balance(&s.payee), 1_000);
+
+    let result = s.client.try_pay(&invoice_id, &1);
+    assert_eq!(result, Err(Ok(InvoiceError::NotOpen)));
+}
+
+#[test]
+fn test_overdue_and_listings() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let settled = s.issue(&env, 100);
+    let late = s.issue(&env, 200);
+    let cancelled = s.issue(&env, 300);
+    s.client.pay(&settled, &100);
+    s.client.cancel(&cancelled);
+
+    env.ledger().set_sequence_number(DUE + 1);
+    assert_eq!(s.client.get_invoice(&late).status, InvoiceStatus::Overdue);
+
+    let statuses = |invoices: Vec<Invoice>| {
+        let mut statuses = Vec::new(&env);
+        for invoice in invoices.iter() {
+            statuses.push_back(invoice.status);
+        }
+        statuses
+    };
+    assert_eq!(
+        statuses(s.client.invoices_of_payer(&s.payer, &0, &10)),
+        vec![
+            &env,
+            InvoiceStatus::Settled,
+            InvoiceStatus::Overdue,
+            InvoiceStatus::Cancelled
+        ]
+    );
+    assert_eq!(
+        statuses(s.client.invoices_of_payee(&s.payee, &1, &1)),
+        vec![&env, InvoiceStatus::Overdue]
+    );
+    assert_eq!(s.client.payee_invoice_count(&s.payee), 3);
+
+    // An overdue invoice can still be paid off
+    s.client.pay(&late, &200);
+    assert_eq!(s.client.get_invoice(&late).status, InvoiceStatus::Settled);
+}
+
+#[test]
+fn test_invalid_invoices_rejected() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let memo = BytesN::from_array(&env, &[0; 32]);
+
+    let result = s
+        .client
+        .try_issue(&s.payee, &s.payee, &s.token.address, &100, &DUE, &memo);
+    assert_eq!(result, Err(Ok(InvoiceError::InvalidParties)));
+    let result = s
+        .client
+        .try_issue(&s.payee, &s.payer, &s.token.address, &0, &DUE, &memo);
+    assert_eq!(result, Err(Ok(InvoiceError::InvalidAmount)));
+    env.ledger().set_sequence_number(DUE);
+    let result = s
+        .client
+        .try_issue(&s.payee, &s.payer, &s.token.address, &100, &DUE, &memo);
+    assert_eq!(result, Err(Ok(InvoiceError::InvalidDueLedger)));
+    assert_eq!(
+        s.client.try_get_invoice(&0),
+        Err(Ok(InvoiceError::InvoiceNotFound))
+    );
+}
+
+#[test]
+fn test_refund_under_policy() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let settled = s.issue(&env, 1_000);
+    let open = s.issue(&env, 1_000);
+    s.client.pay(&settled, &1_000);
+    s.client.pay(&open, &300);
+
+    let policy = RefundPolicy {
+        full_window: 0,
+        prorated_window: 200,
+    };
+    s.client.set_refund_policy(&s.payee, &Some(policy));
+    StellarAssetClient::new(&env, &s.token.address).mint(&s.payee, &1_000);
+    s.client
+        .fund_refund_reserve(&s.payee, &s.token.address, &1_000);
+
+    // A quarter of the way through the window, three quarters come back
+    env.ledger().set_sequence_number(50);
+    assert_eq!(s.client.request_refund(&settled), 750);
+    let invoice = s.client.get_invoice(&settled);
+    assert_eq!(
+        (invoice.status, invoice.refunded),
+        (InvoiceStatus::Refunded, 750)
+    );
+    let result = s.client.try_request_refund(&settled);
+    assert_eq!(result, Err(Ok(InvoiceError::NotRefundable)));
+    let result = s.client.try_pay(&settled, &1);
+    assert_eq!(result, Err(Ok(InvoiceError::NotOpen)));
+
+    // The reserve only covers what is left in it
+    env.ledger().set_sequence_number(100);
+    s.client.pay(&open, &700);
+    let result = s.client.try_request_refund(&open);
+    assert_eq!(result, Err(Ok(InvoiceError::InsufficientReserve)));
+    env.ledger().set_sequence_number(200);
+    s.client
+        .fund_refund_reserve(&s.payee, &s.token.address, &250);
+    assert_eq!(s.client.request_refund(&open), 500);
+    assert_eq!(s.token.balance(&s.payer), 10_000 - 2_000 + 1_250);
+    assert_eq!(s.client.refund_reserve(&s.payee, &s.token.address), 0);
+}
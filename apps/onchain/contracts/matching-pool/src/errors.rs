@@ -0,0 +1,15 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MatchingError {
+    PoolNotFound = 1,
+    InvalidAmount = 2,
+    InvalidTerms = 3,
+    AlreadyRegistered = 4,
+    RecipientNotRegistered = 5,
+    PoolEnded = 6,
+    PoolNotEnded = 7,
+    AlreadySwept = 8,
+}
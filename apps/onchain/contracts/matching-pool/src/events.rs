@@ -0,0 +1,40 @@
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolCreatedEvent {
+    #[topic]
+    pub sponsor: Address,
+    pub pool_id: u64,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecipientRegisteredEvent {
+    #[topic]
+    pub pool_id: u64,
+    #[topic]
+    pub recipient: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonationMatchedEvent {
+    #[topic]
+    pub pool_id: u64,
+    #[topic]
+    pub recipient: Address,
+    pub donor: Address,
+    pub amount: i128,
+    pub matched: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolSweptEvent {
+    #[topic]
+    pub pool_id: u64,
+    pub amount: i128,
+}
@@ -0,0 +1,228 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::MatchingError;
+use lumenpulse_fixed::{bps, Rounding};
+use soroban_sdk::{contract, contractimpl, Address, Env};
+use storage::{DataKey, MatchTerms, Pool, RecipientStats};
+
+/// Donation matching. A sponsor funds a pool and registers recipients, and
+/// every donation to a registered recipient is topped up from the pool at
+/// the sponsor's ratio until the pool runs out or the recipient reaches
+/// their cap. Donations go straight to the recipient; only the match is
+/// held here, and whatever is left when the pool ends goes back to the
+/// sponsor.
+///
+/// There is no circuit breaker, as the contract has no admin to hold one.
+/// Each pool answers only to its sponsor, and a pause would keep the
+/// sponsor from sweeping what is left once the pool ends.
+#[contract]
+pub struct MatchingPoolContract;
+
+#[contractimpl]
+impl MatchingPoolContract {
+    /// Fund a matching pool with `amount` of `token`
+    pub fn create_pool(
+        env: Env,
+        sponsor: Address,
+        token: Address,
+        amount: i128,
+        terms: MatchTerms,
+    ) -> Result<u64, MatchingError> {
+        sponsor.require_auth();
+
+        if amount <= 0 {
+            return Err(MatchingError::InvalidAmount);
+        }
+        if terms.ratio_bps == 0
+            || terms.recipient_cap <= 0
+            || terms.end_time <= env.ledger().timestamp()
+        {
+            return Err(MatchingError::InvalidTerms);
+        }
+
+        token::transfer(
+            &env,
+            &token,
+            &sponsor,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let pool_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextPoolId)
+            .unwrap_or(0);
+        let pool = Pool {
+            id: pool_id,
+            sponsor: sponsor.clone(),
+            token: token.clone(),
+            terms,
+            remaining: amount,
+            donated: 0,
+            matched: 0,
+            swept: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Pool(pool_id), &pool);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextPoolId, &(pool_id + 1));
+
+        events::PoolCreatedEvent {
+            sponsor,
+            pool_id,
+            token,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(pool_id)
+    }
+
+    /// Make `recipient` eligible for matching (sponsor only)
+    pub fn register_recipient(
+        env: Env,
+        pool_id: u64,
+        recipient: Address,
+    ) -> Result<(), MatchingError> {
+        let pool = Self::get_pool(env.clone(), pool_id)?;
+        pool.sponsor.require_auth();
+
+        let key = DataKey::Recipient(pool_id, recipient.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(MatchingError::AlreadyRegistered);
+        }
+        env.storage()
+            .persistent()
+            .set(&key, &RecipientStats::default());
+
+        events::RecipientRegisteredEvent { pool_id, recipient }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Donate `amount` to `recipient`, who also receives the pool's match.
+    /// Returns the amount matched, which may be zero once the pool or the
+    /// recipient's cap is exhausted.
+    pub fn donate(
+        env: Env,
+        donor: Address,
+        pool_id: u64,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<i128, MatchingError> {
+        donor.require_auth();
+
+        let mut pool = Self::get_pool(env.clone(), pool_id)?;
+        if env.ledger().timestamp() >= pool.terms.end_time {
+            return Err(MatchingError::PoolEnded);
+        }
+        if amount <= 0 {
+            return Err(MatchingError::InvalidAmount);
+        }
+        let key = DataKey::Recipient(pool_id, recipient.clone());
+        let mut stats: RecipientStats = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(MatchingError::RecipientNotRegistered)?;
+
+        // Matches round down, so the pool never pays more than the ratio
+        let matched = bps(amount, pool.terms.ratio_bps, Rounding::Down)
+            .expect("arithmetic overflow")
+            .min(pool.remaining)
+            .min(pool.terms.recipient_cap - stats.matched);
+
+        pool.remaining -= matched;
+        pool.donated += amount;
+        pool.matched += matched;
+        stats.donated += amount;
+        stats.matched += matched;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Pool(pool_id), &pool);
+        env.storage().persistent().set(&key, &stats);
+
+        token::transfer(&env, &pool.token, &donor, &recipient, &amount);
+        token::transfer(
+            &env,
+            &pool.token,
+            &env.current_contract_address(),
+            &recipient,
+            &matched,
+        );
+
+        events::DonationMatchedEvent {
+            pool_id,
+            recipient,
+            donor,
+            amount,
+            matched,
+        }
+        .publish(&env);
+
+        Ok(matched)
+    }
+
+    /// Return what is left of an ended pool to its sponsor. Needs no
+    /// authorization, as the remainder can only go back to the sponsor.
+    /// Returns the amount swept.
+    pub fn sweep(env: Env, pool_id: u64) -> Result<i128, MatchingError> {
+        let mut pool = Self::get_pool(env.clone(), pool_id)?;
+        if pool.swept {
+            return Err(MatchingError::AlreadySwept);
+        }
+        if env.ledger().timestamp() < pool.terms.end_time {
+            return Err(MatchingError::PoolNotEnded);
+        }
+
+        let amount = pool.remaining;
+        pool.remaining = 0;
+        pool.swept = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Pool(pool_id), &pool);
+
+        token::transfer(
+            &env,
+            &pool.token,
+            &env.current_contract_address(),
+            &pool.sponsor,
+            &amount,
+        );
+
+        events::PoolSweptEvent { pool_id, amount }.publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Get a pool by ID
+    pub fn get_pool(env: Env, pool_id: u64) -> Result<Pool, MatchingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(MatchingError::PoolNotFound)
+    }
+
+    /// Donations and match a registered recipient has received from a pool
+    pub fn get_recipient(
+        env: Env,
+        pool_id: u64,
+        recipient: Address,
+    ) -> Result<RecipientStats, MatchingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Recipient(pool_id, recipient))
+            .ok_or(MatchingError::RecipientNotRegistered)
+    }
+}
+
+#[cfg(test)]
+mod test;
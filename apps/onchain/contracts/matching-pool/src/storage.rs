@@ -0,0 +1,42 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextPoolId,              // -> u64
+    Pool(u64),               // pool_id -> Pool
+    Recipient(u64, Address), // (pool_id, recipient) -> RecipientStats
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchTerms {
+    /// Match paid per donated token, in basis points (10_000 matches 1:1)
+    pub ratio_bps: u32,
+    /// Most one recipient can be matched in total
+    pub recipient_cap: i128,
+    /// Donations are matched until this time, after which the sponsor can
+    /// sweep what is left
+    pub end_time: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pool {
+    pub id: u64,
+    pub sponsor: Address,
+    pub token: Address,
+    pub terms: MatchTerms,
+    /// Still available for matching
+    pub remaining: i128,
+    pub donated: i128,
+    pub matched: i128,
+    pub swept: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RecipientStats {
+    pub donated: i128,
+    pub matched: i128,
+}
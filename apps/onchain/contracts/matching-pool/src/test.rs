@@ -0,0 +1,113 @@
+use crate::errors::MatchingError;
+use crate::storage::MatchTerms;
+use crate::{MatchingPoolContract, MatchingPoolContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const END: u64 = 1_000;
+
+struct Setup<'a> {
+    client: MatchingPoolContractClient<'a>,
+    sponsor: Address,
+    donor: Address,
+    token: TokenClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let sponsor = Address::generate(env);
+    let donor = Address::generate(env);
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    let token_admin = StellarAssetClient::new(env, &sac.address());
+    token_admin.mint(&sponsor, &10_000);
+    token_admin.mint(&donor, &10_000);
+
+    let client = MatchingPoolContractClient::new(env, &env.register(MatchingPoolContract, ()));
+
+    Setup {
+        client,
+        sponsor,
+        donor,
+        token: TokenClient::new(env, &sac.address()),
+    }
+}
+
+/// Pool of 1,000 matching 2:1, at most 600 per recipient
+fn create_pool(s: &Setup) -> u64 {
+    let terms = MatchTerms {
+        ratio_bps: 20_000,
+        recipient_cap: 600,
+        end_time: END,
+    };
+    s.client
+        .create_pool(&s.sponsor, &s.token.address, &1_000, &terms)
+}
+
+#[test]
+fn test_donations_matched_until_cap() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let pool_id = create_pool(&s);
+    let recipient = Address::generate(&env);
+
+    let result = s.client.try_donate(&s.donor, &pool_id, &recipient, &100);
+    assert_eq!(result, Err(Ok(MatchingError::RecipientNotRegistered)));
+
+    s.client.register_recipient(&pool_id, &recipient);
+    assert_eq!(s.client.donate(&s.donor, &pool_id, &recipient, &100), 200);
+    assert_eq!(s.token.balance(&recipient), 300);
+
+    // The recipient's cap limits the second match
+    assert_eq!(s.client.donate(&s.donor, &pool_id, &recipient, &300), 400);
+    assert_eq!(s.client.donate(&s.donor, &pool_id, &recipient, &100), 0);
+    let stats = s.client.get_recipient(&pool_id, &recipient);
+    assert_eq!((stats.donated, stats.matched), (500, 600));
+    assert_eq!(s.token.balance(&recipient), 1_100);
+}
+
+#[test]
+fn test_pool_exhausts_then_sweeps() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let pool_id = create_pool(&s);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    s.client.register_recipient(&pool_id, &alice);
+    s.client.register_recipient(&pool_id, &bob);
+
+    assert_eq!(s.client.donate(&s.donor, &pool_id, &alice, &300), 600);
+    // Only 400 is left in the pool
+    assert_eq!(s.client.donate(&s.donor, &pool_id, &bob, &300), 400);
+    assert_eq!(s.client.get_pool(&pool_id).remaining, 0);
+
+    let result = s.client.try_sweep(&pool_id);
+    assert_eq!(result, Err(Ok(MatchingError::PoolNotEnded)));
+
+    env.ledger().set_timestamp(END);
+    let result = s.client.try_donate(&s.donor, &pool_id, &bob, &100);
+    assert_eq!(result, Err(Ok(MatchingError::PoolEnded)));
+    assert_eq!(s.client.sweep(&pool_id), 0);
+}
+
+#[test]
+fn test_sweep_returns_unmatched_funds() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let pool_id = create_pool(&s);
+    let recipient = Address::generate(&env);
+    s.client.register_recipient(&pool_id, &recipient);
+    s.client.donate(&s.donor, &pool_id, &recipient, &50);
+
+    env.ledger().set_timestamp(END);
+    assert_eq!(s.client.sweep(&pool_id), 900);
+    assert_eq!(s.token.balance(&s.sponsor), 9_900);
+    let result = s.client.try_sweep(&pool_id);
+    assert_eq!(result, Err(Ok(MatchingError::AlreadySwept)));
+
+    let result = s.client.try_register_recipient(&pool_id, &recipient);
+    assert_eq!(result, Err(Ok(MatchingError::AlreadyRegistered)));
+}
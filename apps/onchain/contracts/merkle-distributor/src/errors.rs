@@ -0,0 +1,23 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum DistributorError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    InvalidDeadline = 4,
+    InvalidAmount = 5,
+    InvalidProof = 6,
+    AlreadyClaimed = 7,
+    ClaimEnded = 8,
+    ClaimNotEnded = 9,
+    RoundNotFound = 10,
+    ClaimNotStarted = 11,
+    TooManyClaims = 12,
+    AlreadySwept = 13,
+    InvalidVesting = 14,
+    StreamingNotSet = 15,
+    StreamFailed = 16,
+}
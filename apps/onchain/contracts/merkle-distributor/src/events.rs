@@ -0,0 +1,56 @@
+use soroban_sdk::{contractevent, Address, BytesN};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub admin: Address,
+    pub treasury: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryUpdatedEvent {
+    pub treasury: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamingUpdatedEvent {
+    pub streaming: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundCreatedEvent {
+    #[topic]
+    pub round_id: u32,
+    pub token: Address,
+    pub merkle_root: BytesN<32>,
+    pub amount: i128,
+    pub start_time: u64,
+    pub claim_deadline: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimedEvent {
+    #[topic]
+    pub round_id: u32,
+    #[topic]
+    pub account: Address,
+    pub index: u32,
+    pub amount: i128,
+    /// Stream the claim vests through, for vested rounds
+    pub stream_id: Option<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweptEvent {
+    #[topic]
+    pub round_id: u32,
+    pub treasury: Address,
+    pub funded: i128,
+    pub claimed: i128,
+    pub swept: i128,
+}
@@ -0,0 +1,408 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod merkle;
+mod storage;
+mod token;
+
+use errors::DistributorError;
+use lumenpulse_pausable::Pausable;
+use lumenpulse_streaming_interface::StreamingClient;
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+use storage::{
+    Claim, ClaimMode, DataKey, Round, RoundClaim, RoundConfig, MAX_CLAIMS_PER_CALL,
+    MAX_CLAIM_PERIOD, MAX_PROOF_LEN,
+};
+
+/// Airdrop distributor for thousands of allocations across any number of
+/// concurrent rounds. Each round stores only the root of a Merkle tree of
+/// `(index, account, amount)` claims, its token and its claim window; each
+/// account claims its own allocation with a proof, and a per-round bitmap
+/// keyed by index stops any allocation being claimed twice. Rounds are
+/// funded when created and only ever pay out of their own deposit, and
+/// every round has a bounded claim deadline after which anyone can sweep
+/// what is left to the treasury. A round can pay claims out instantly, or
+/// vest each one as a stream to the claimant.
+#[contract]
+pub struct MerkleDistributorContract;
+
+#[contractimpl]
+impl MerkleDistributorContract {
+    /// Initialize the distributor with an admin address and the treasury
+    /// unclaimed funds are swept to
+    pub fn initialize(env: Env, admin: Address, treasury: Address) -> Result<(), DistributorError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(DistributorError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        env.storage().instance().set(&DataKey::NextRoundId, &0u32);
+
+        events::InitializedEvent { admin, treasury }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Change where unclaimed funds are swept to (admin only)
+    pub fn set_treasury(
+        env: Env,
+        admin: Address,
+        treasury: Address,
+    ) -> Result<(), DistributorError> {
+        Self::require_admin(&env, &admin)?;
+
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+
+        events::TreasuryUpdatedEvent { treasury }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Set the streaming contract vested rounds pay claims through (admin
+    /// only)
+    pub fn set_streaming(
+        env: Env,
+        admin: Address,
+        streaming: Address,
+    ) -> Result<(), DistributorError> {
+        Self::require_admin(&env, &admin)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Streaming, &streaming);
+
+        events::StreamingUpdatedEvent { streaming }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Open a round for `config`'s tree, funded with `amount` of its token
+    /// from the admin, which should be the tree's total (admin only). Claims
+    /// may stay open for at most `MAX_CLAIM_PERIOD`. Returns the round ID.
+    pub fn create_round(
+        env: Env,
+        admin: Address,
+        config: RoundConfig,
+        amount: i128,
+    ) -> Result<u32, DistributorError> {
+        Self::require_admin(&env, &admin)?;
+
+        if amount <= 0 {
+            return Err(DistributorError::InvalidAmount);
+        }
+        let opens = config.start_time.max(env.ledger().timestamp());
+        if config.claim_deadline <= opens || config.claim_deadline - opens > MAX_CLAIM_PERIOD {
+            return Err(DistributorError::InvalidDeadline);
+        }
+        if let ClaimMode::Vested(duration) = config.mode {
+            if duration == 0 {
+                return Err(DistributorError::InvalidVesting);
+            }
+            Self::get_streaming(env.clone())?;
+        }
+
+        token::transfer(
+            &env,
+            &config.token,
+            &admin,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let round_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRoundId)
+            .unwrap_or(0);
+        let round = Round {
+            id: round_id,
+            config: config.clone(),
+            funded: amount,
+            claimed: 0,
+            swept: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Round(round_id), &round);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextRoundId, &(round_id + 1));
+
+        events::RoundCreatedEvent {
+            round_id,
+            token: config.token,
+            merkle_root: config.merkle_root,
+            amount,
+            start_time: config.start_time,
+            claim_deadline: config.claim_deadline,
+        }
+        .publish(&env);
+
+        Ok(round_id)
+    }
+
+    /// Claim `amount` for `account` at `index` in a round's tree, proven by
+    /// `proof`: the sibling hashes from the leaf up to the root. In a vested
+    /// round the claim starts a stream to `account` instead of paying out.
+    pub fn claim(
+        env: Env,
+        account: Address,
+        round_id: u32,
+        index: u32,
+        amount: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<(), DistributorError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let claim = RoundClaim {
+            round_id,
+            index,
+            amount,
+            proof,
+        };
+        Self::claim_one(&env, &account, &claim)
+    }
+
+    /// Claim `account`'s allocations in several rounds at once. Either
+    /// every claim succeeds or none do. Returns the total claimed.
+    pub fn claim_many(
+        env: Env,
+        account: Address,
+        claims: Vec<RoundClaim>,
+    ) -> Result<i128, DistributorError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        if claims.len() > MAX_CLAIMS_PER_CALL {
+            return Err(DistributorError::TooManyClaims);
+        }
+        let mut total = 0;
+        for claim in claims.iter() {
+            Self::claim_one(&env, &account, &claim)?;
+            total += claim.amount;
+        }
+        Ok(total)
+    }
+
+    /// Send what is unclaimed in a round to the treasury once its claim
+    /// deadline has passed. Needs no authorization, as the funds can only
+    /// go to the treasury. Returns the amount swept.
+    pub fn sweep(env: Env, round_id: u32) -> Result<i128, DistributorError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let treasury = Self::get_treasury(env.clone())?;
+        let mut round = Self::get_round(env.clone(), round_id)?;
+        if round.swept {
+            return Err(DistributorError::AlreadySwept);
+        }
+        if env.ledger().timestamp() < round.config.claim_deadline {
+            return Err(DistributorError::ClaimNotEnded);
+        }
+
+        let swept = round.funded - round.claimed;
+        round.swept = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Round(round_id), &round);
+
+        token::transfer(
+            &env,
+            &round.config.token,
+            &env.current_contract_address(),
+            &treasury,
+            &swept,
+        );
+
+        events::SweptEvent {
+            round_id,
+            treasury,
+            funded: round.funded,
+            claimed: round.claimed,
+            swept,
+        }
+        .publish(&env);
+
+        Ok(swept)
+    }
+
+    /// Whether the allocation at `index` in a round has been claimed
+    pub fn is_claimed(env: Env, round_id: u32, index: u32) -> bool {
+        let word: u128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ClaimedWord(round_id, index / 128))
+            .unwrap_or(0);
+        word & 1 << (index % 128) != 0
+    }
+
+    /// Get a round by ID
+    pub fn get_round(env: Env, round_id: u32) -> Result<Round, DistributorError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Round(round_id))
+            .ok_or(DistributorError::RoundNotFound)
+    }
+
+    /// Number of rounds created so far
+    pub fn round_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::NextRoundId)
+            .unwrap_or(0)
+    }
+
+    /// Get the streaming contract vested claims are paid through
+    pub fn get_streaming(env: Env) -> Result<Address, DistributorError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Streaming)
+            .ok_or(DistributorError::StreamingNotSet)
+    }
+
+    /// Get the treasury unclaimed funds are swept to
+    pub fn get_treasury(env: Env) -> Result<Address, DistributorError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Treasury)
+            .ok_or(DistributorError::NotInitialized)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, DistributorError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(DistributorError::NotInitialized)
+    }
+}
+
+impl MerkleDistributorContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), DistributorError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(DistributorError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    /// Verify and pay one claim
+    fn claim_one(env: &Env, account: &Address, claim: &RoundClaim) -> Result<(), DistributorError> {
+        let round_id = claim.round_id;
+        let mut round = Self::get_round(env.clone(), round_id)?;
+        let now = env.ledger().timestamp();
+        if now < round.config.start_time {
+            return Err(DistributorError::ClaimNotStarted);
+        }
+        if now >= round.config.claim_deadline {
+            return Err(DistributorError::ClaimEnded);
+        }
+        if claim.amount <= 0 {
+            return Err(DistributorError::InvalidAmount);
+        }
+        if Self::is_claimed(env.clone(), round_id, claim.index) {
+            return Err(DistributorError::AlreadyClaimed);
+        }
+
+        let leaf = merkle::leaf(
+            env,
+            &Claim {
+                index: claim.index,
+                account: account.clone(),
+                amount: claim.amount,
+            },
+        );
+        if claim.proof.len() > MAX_PROOF_LEN
+            || !merkle::verify(env, &claim.proof, &round.config.merkle_root, leaf)
+        {
+            return Err(DistributorError::InvalidProof);
+        }
+        // A tree that allocates more than the round was funded with cannot
+        // draw on another round's deposit
+        if round.claimed + claim.amount > round.funded {
+            return Err(DistributorError::InvalidAmount);
+        }
+
+        let key = DataKey::ClaimedWord(round_id, claim.index / 128);
+        let word: u128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key, &(word | 1 << (claim.index % 128)));
+        round.claimed += claim.amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Round(round_id), &round);
+
+        let stream_id = match round.config.mode {
+            ClaimMode::Instant => {
+                token::transfer(
+                    env,
+                    &round.config.token,
+                    &env.current_contract_address(),
+                    account,
+                    &claim.amount,
+                );
+                None
+            }
+            ClaimMode::Vested(duration) => Some(Self::stream_claim(
+                env,
+                &round.config.token,
+                account,
+                claim.amount,
+                duration,
+            )?),
+        };
+
+        events::ClaimedEvent {
+            round_id,
+            account: account.clone(),
+            index: claim.index,
+            amount: claim.amount,
+            stream_id,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Start a stream of `amount` to `account` vesting over `duration`
+    /// seconds from now, with the distributor as sender
+    fn stream_claim(
+        env: &Env,
+        token: &Address,
+        account: &Address,
+        amount: i128,
+        duration: u64,
+    ) -> Result<u64, DistributorError> {
+        let streaming = Self::get_streaming(env.clone())?;
+        let this = env.current_contract_address();
+        let now = env.ledger().timestamp();
+
+        // The streaming contract pulls the deposit from the distributor
+        lumenpulse_sac::authorize_transfer(env, token, &streaming, amount);
+        match StreamingClient::new(env, &streaming).try_create_stream(
+            &this,
+            account,
+            token,
+            &amount,
+            &now,
+            &(now + duration),
+        ) {
+            Ok(Ok(stream_id)) => Ok(stream_id),
+            _ => Err(DistributorError::StreamFailed),
+        }
+    }
+}
+
+impl Pausable for MerkleDistributorContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(MerkleDistributorContract);
+
+#[cfg(test)]
+mod test;
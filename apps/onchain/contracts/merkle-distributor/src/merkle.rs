@@ -0,0 +1,31 @@
+//! Merkle proof verification
+
+use crate::storage::Claim;
+use soroban_sdk::{xdr::ToXdr, Bytes, BytesN, Env, Vec};
+
+/// Leaf hash of `claim`
+pub fn leaf(env: &Env, claim: &Claim) -> BytesN<32> {
+    env.crypto().sha256(&claim.clone().to_xdr(env)).into()
+}
+
+/// Whether `proof` leads from `leaf` up to `root`. Pairs are hashed in
+/// ascending byte order, so the proof needs no left/right flags.
+pub fn verify(env: &Env, proof: &Vec<BytesN<32>>, root: &BytesN<32>, leaf: BytesN<32>) -> bool {
+    let mut node = leaf;
+    for sibling in proof.iter() {
+        node = hash_pair(env, &node, &sibling);
+    }
+    node == *root
+}
+
+/// Parent of two nodes
+pub fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a.to_array() <= b.to_array() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let mut preimage = Bytes::from(first.clone());
+    preimage.append(&Bytes::from(second.clone()));
+    env.crypto().sha256(&preimage).into()
+}
@@ -0,0 +1,78 @@
+use soroban_sdk::{contracttype, Address, BytesN, Vec};
+
+/// Longest proof accepted, enough for a tree of 2^32 leaves
+pub const MAX_PROOF_LEN: u32 = 32;
+
+/// Most claims settled by one `claim_many`
+pub const MAX_CLAIMS_PER_CALL: u32 = 10;
+
+/// Longest a round's claim period may run, so its funds always reach the
+/// treasury eventually
+pub const MAX_CLAIM_PERIOD: u64 = 365 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                 // -> Address
+    Treasury,              // -> Address
+    Streaming,             // -> Address
+    NextRoundId,           // -> u32
+    Round(u32),            // round_id -> Round
+    ClaimedWord(u32, u32), // (round_id, word index) -> u128 (one bit per claim index)
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundConfig {
+    pub token: Address,
+    /// Root of the round's tree of `Claim` leaves
+    pub merkle_root: BytesN<32>,
+    /// Claims open at this time
+    pub start_time: u64,
+    /// Claims close at this time and anyone may sweep what is left to the
+    /// treasury
+    pub claim_deadline: u64,
+    pub mode: ClaimMode,
+}
+
+/// How a successful claim pays out: straight to the claimant, or as a
+/// stream on the streaming contract vesting linearly over the given number
+/// of seconds from the claim
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClaimMode {
+    Instant,
+    Vested(u64),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Round {
+    pub id: u32,
+    pub config: RoundConfig,
+    /// Deposited when the round was created
+    pub funded: i128,
+    pub claimed: i128,
+    pub swept: bool,
+}
+
+/// One allocation in a round's tree. Each leaf is the SHA-256 of a claim's
+/// XDR encoding, and each parent the SHA-256 of its two children in
+/// ascending byte order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub index: u32,
+    pub account: Address,
+    pub amount: i128,
+}
+
+/// A claim in one round, as passed to `claim_many`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundClaim {
+    pub round_id: u32,
+    pub index: u32,
+    pub amount: i128,
+    pub proof: Vec<BytesN<32>>,
+}
@@ -0,0 +1,377 @@
+use crate::errors::DistributorError;
+use crate::merkle::{hash_pair, leaf, verify};
+use crate::storage::{Claim, ClaimMode, RoundClaim, RoundConfig, MAX_CLAIM_PERIOD};
+use crate::{MerkleDistributorContract, MerkleDistributorContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, Vec,
+};
+use streaming::{StreamingContract, StreamingContractClient};
+
+const DEADLINE: u64 = 1_000;
+
+/// Four-leaf tree of claims
+struct Tree {
+    claims: [Claim; 4],
+    leaves: [BytesN<32>; 4],
+    parents: [BytesN<32>; 2],
+    root: BytesN<32>,
+}
+
+impl Tree {
+    fn new(env: &Env, accounts: &[Address; 4], amounts: [i128; 4]) -> Self {
+        let claims: [Claim; 4] = core::array::from_fn(|i| Claim {
+            index: i as u32,
+            account: accounts[i].clone(),
+            amount: amounts[i],
+        });
+        let leaves: [BytesN<32>; 4] = core::array::from_fn(|i| leaf(env, &claims[i]));
+        let parents = [
+            hash_pair(env, &leaves[0], &leaves[1]),
+            hash_pair(env, &leaves[2], &leaves[3]),
+        ];
+        let root = hash_pair(env, &parents[0], &parents[1]);
+        Tree {
+            claims,
+            leaves,
+            parents,
+            root,
+        }
+    }
+
+    /// Proof for claim `i`
+    fn proof(&self, env: &Env, i: usize) -> Vec<BytesN<32>> {
+        vec![
+            env,
+            self.leaves[i ^ 1].clone(),
+            self.parents[(i / 2) ^ 1].clone(),
+        ]
+    }
+}
+
+struct Setup<'a> {
+    client: MerkleDistributorContractClient<'a>,
+    admin: Address,
+    treasury: Address,
+    accounts: [Address; 4],
+    token: TokenClient<'a>,
+}
+
+impl Setup<'_> {
+    /// Fund a round for `tree` with the tree's total
+    fn create_round(&self, tree: &Tree, mode: ClaimMode) -> u32 {
+        let config = RoundConfig {
+            token: self.token.address.clone(),
+            merkle_root: tree.root.clone(),
+            start_time: 0,
+            claim_deadline: DEADLINE,
+            mode,
+        };
+        let total = tree.claims.iter().map(|claim| claim.amount).sum();
+        self.client.create_round(&self.admin, &config, &total)
+    }
+
+    fn claim(&self, env: &Env, round_id: u32, tree: &Tree, i: usize) {
+        let claim = &tree.claims[i];
+        self.client.claim(
+            &claim.account,
+            &round_id,
+            &claim.index,
+            &claim.amount,
+            &tree.proof(env, i),
+        );
+    }
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let treasury = Address::generate(env);
+    let accounts = core::array::from_fn(|_| Address::generate(env));
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &sac.address()).mint(&admin, &10_000);
+
+    let client =
+        MerkleDistributorContractClient::new(env, &env.register(MerkleDistributorContract, ()));
+    client.initialize(&admin, &treasury);
+
+    Setup {
+        client,
+        admin,
+        treasury,
+        accounts,
+        token: TokenClient::new(env, &sac.address()),
+    }
+}
+
+#[test]
+fn test_claim_with_proof() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let tree = Tree::new(&env, &s.accounts, [100, 200, 300, 400]);
+    let round_id = s.create_round(&tree, ClaimMode::Instant);
+    assert_eq!(s.client.get_round(&round_id).funded, 1_000);
+
+    for i in 0..4 {
+        assert!(!s.client.is_claimed(&round_id, &(i as u32)));
+        s.claim(&env, round_id, &tree, i);
+        assert!(s.client.is_claimed(&round_id, &(i as u32)));
+        assert_eq!(s.token.balance(&s.accounts[i]), tree.claims[i].amount);
+    }
+    assert_eq!(s.client.get_round(&round_id).claimed, 1_000);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    let claim = &tree.claims[0];
+    let result = s.client.try_claim(
+        &claim.account,
+        &round_id,
+        &claim.index,
+        &claim.amount,
+        &tree.proof(&env, 0),
+    );
+    assert_eq!(result, Err(Ok(DistributorError::AlreadyClaimed)));
+}
+
+#[test]
+fn test_invalid_claims_rejected() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let tree = Tree::new(&env, &s.accounts, [100, 200, 300, 400]);
+    let round_id = s.create_round(&tree, ClaimMode::Instant);
+    let claim = &tree.claims[1];
+    let proof = tree.proof(&env, 1);
+
+    // A different amount, account or index does not match the leaf
+    let result = s.client.try_claim(
+        &claim.account,
+        &round_id,
+        &claim.index,
+        &(claim.amount + 1),
+        &proof,
+    );
+    assert_eq!(result, Err(Ok(DistributorError::InvalidProof)));
+    let result = s.client.try_claim(
+        &s.accounts[0],
+        &round_id,
+        &claim.index,
+        &claim.amount,
+        &proof,
+    );
+    assert_eq!(result, Err(Ok(DistributorError::InvalidProof)));
+    let result = s
+        .client
+        .try_claim(&claim.account, &round_id, &2, &claim.amount, &proof);
+    assert_eq!(result, Err(Ok(DistributorError::InvalidProof)));
+    assert!(!s.client.is_claimed(&round_id, &1));
+
+    let result = s
+        .client
+        .try_claim(&claim.account, &7, &claim.index, &claim.amount, &proof);
+    assert_eq!(result, Err(Ok(DistributorError::RoundNotFound)));
+}
+
+#[test]
+fn test_claim_many_across_rounds() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let first = Tree::new(&env, &s.accounts, [100, 200, 300, 400]);
+    let second = Tree::new(&env, &s.accounts, [10, 20, 30, 40]);
+    let first_id = s.create_round(&first, ClaimMode::Instant);
+    let second_id = s.create_round(&second, ClaimMode::Instant);
+    assert_eq!(s.client.round_count(), 2);
+
+    let round_claim = |round_id, tree: &Tree, i: usize| RoundClaim {
+        round_id,
+        index: tree.claims[i].index,
+        amount: tree.claims[i].amount,
+        proof: tree.proof(&env, i),
+    };
+    let claims = vec![
+        &env,
+        round_claim(first_id, &first, 2),
+        round_claim(second_id, &second, 2),
+    ];
+    assert_eq!(s.client.claim_many(&s.accounts[2], &claims), 330);
+    assert_eq!(s.token.balance(&s.accounts[2]), 330);
+
+    // One claim already made fails the whole batch
+    let claims = vec![
+        &env,
+        round_claim(first_id, &first, 3),
+        round_claim(second_id, &second, 2),
+    ];
+    let result = s.client.try_claim_many(&s.accounts[3], &claims);
+    assert_eq!(result, Err(Ok(DistributorError::AlreadyClaimed)));
+    assert!(!s.client.is_claimed(&first_id, &3));
+}
+
+#[test]
+fn test_sweep_after_deadline() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let tree = Tree::new(&env, &s.accounts, [100, 200, 300, 400]);
+    let round_id = s.create_round(&tree, ClaimMode::Instant);
+    s.claim(&env, round_id, &tree, 3);
+
+    let result = s.client.try_sweep(&round_id);
+    assert_eq!(result, Err(Ok(DistributorError::ClaimNotEnded)));
+
+    env.ledger().set_timestamp(DEADLINE);
+    let claim = &tree.claims[0];
+    let result = s.client.try_claim(
+        &claim.account,
+        &round_id,
+        &claim.index,
+        &claim.amount,
+        &tree.proof(&env, 0),
+    );
+    assert_eq!(result, Err(Ok(DistributorError::ClaimEnded)));
+    assert_eq!(s.client.sweep(&round_id), 600);
+    assert_eq!(s.token.balance(&s.treasury), 600);
+    let result = s.client.try_sweep(&round_id);
+    assert_eq!(result, Err(Ok(DistributorError::AlreadySwept)));
+}
+
+#[test]
+fn test_claim_period_is_bounded() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let tree = Tree::new(&env, &s.accounts, [100, 200, 300, 400]);
+    env.ledger().set_timestamp(10_000);
+
+    // The period is measured from the later of now and the start
+    let mut config = RoundConfig {
+        token: s.token.address.clone(),
+        merkle_root: tree.root.clone(),
+        start_time: 20_000,
+        claim_deadline: 20_000 + MAX_CLAIM_PERIOD + 1,
+        mode: ClaimMode::Instant,
+    };
+    let result = s.client.try_create_round(&s.admin, &config, &1_000);
+    assert_eq!(result, Err(Ok(DistributorError::InvalidDeadline)));
+    config.claim_deadline = 20_000;
+    let result = s.client.try_create_round(&s.admin, &config, &1_000);
+    assert_eq!(result, Err(Ok(DistributorError::InvalidDeadline)));
+
+    config.claim_deadline = 20_000 + MAX_CLAIM_PERIOD;
+    let round_id = s.client.create_round(&s.admin, &config, &1_000);
+    let claim = &tree.claims[0];
+    let result = s.client.try_claim(
+        &claim.account,
+        &round_id,
+        &claim.index,
+        &claim.amount,
+        &tree.proof(&env, 0),
+    );
+    assert_eq!(result, Err(Ok(DistributorError::ClaimNotStarted)));
+}
+
+#[test]
+fn test_vested_claims_stream() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let tree = Tree::new(&env, &s.accounts, [100, 200, 300, 400]);
+    let mode = ClaimMode::Vested(100);
+
+    let config = RoundConfig {
+        token: s.token.address.clone(),
+        merkle_root: tree.root.clone(),
+        start_time: 0,
+        claim_deadline: DEADLINE,
+        mode: mode.clone(),
+    };
+    let result = s.client.try_create_round(&s.admin, &config, &1_000);
+    assert_eq!(result, Err(Ok(DistributorError::StreamingNotSet)));
+
+    let streaming = StreamingContractClient::new(&env, &env.register(StreamingContract, ()));
+    streaming.initialize(&s.admin);
+    s.client.set_streaming(&s.admin, &streaming.address);
+    let result = s.client.try_create_round(
+        &s.admin,
+        &RoundConfig {
+            mode: ClaimMode::Vested(0),
+            ..config
+        },
+        &1_000,
+    );
+    assert_eq!(result, Err(Ok(DistributorError::InvalidVesting)));
+
+    let round_id = s.create_round(&tree, mode);
+    env.ledger().set_timestamp(10);
+    s.claim(&env, round_id, &tree, 1);
+    assert_eq!(s.token.balance(&s.accounts[1]), 0);
+    assert!(s.client.is_claimed(&round_id, &1));
+    assert_eq!(s.client.get_round(&round_id).claimed, 200);
+
+    let stream = streaming.get_stream(&0);
+    assert_eq!(stream.sender, s.client.address);
+    assert_eq!(stream.recipient, s.accounts[1]);
+    assert_eq!(
+        (stream.deposit, stream.start_time, stream.end_time),
+        (200, 10, 110)
+    );
+
+    env.ledger().set_timestamp(60);
+    streaming.withdraw(&0);
+    assert_eq!(s.token.balance(&s.accounts[1]), 100);
+}
+
+/// Bytes of a hex hash
+fn hash(env: &Env, hex: &str) -> BytesN<32> {
+    let bytes: [u8; 32] =
+        core::array::from_fn(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap());
+    BytesN::from_array(env, &bytes)
+}
+
+#[test]
+fn test_claims_from_the_offchain_generator() {
+    // What `lumenpulse-merkle` generates for its test allocations
+    let distribution: serde_json::Value = serde_json::from_str(include_str!(
+        "../../../../offchain/crates/merkle/goldens/distribution.json"
+    ))
+    .unwrap();
+    let env = Env::default();
+    let s = setup_test(&env);
+    let total: i128 = distribution["total"].as_str().unwrap().parse().unwrap();
+    StellarAssetClient::new(&env, &s.token.address).mint(&s.admin, &total);
+    let root = hash(&env, distribution["root"].as_str().unwrap());
+
+    // Vested, so claims pay the streaming contract rather than accounts
+    // the test ledger has no trustlines for
+    let streaming = StreamingContractClient::new(&env, &env.register(StreamingContract, ()));
+    streaming.initialize(&s.admin);
+    s.client.set_streaming(&s.admin, &streaming.address);
+    let config = RoundConfig {
+        token: s.token.address.clone(),
+        merkle_root: root.clone(),
+        start_time: 0,
+        claim_deadline: DEADLINE,
+        mode: ClaimMode::Vested(100),
+    };
+    let round_id = s.client.create_round(&s.admin, &config, &total);
+
+    for entry in distribution["claims"].as_array().unwrap() {
+        let claim = Claim {
+            index: entry["index"].as_u64().unwrap() as u32,
+            account: Address::from_str(&env, entry["account"].as_str().unwrap()),
+            amount: entry["amount"].as_str().unwrap().parse().unwrap(),
+        };
+        let mut proof = Vec::new(&env);
+        for sibling in entry["proof"].as_array().unwrap() {
+            proof.push_back(hash(&env, sibling.as_str().unwrap()));
+        }
+        assert!(verify(&env, &proof, &root, leaf(&env, &claim)));
+        s.client.claim(
+            &claim.account,
+            &round_id,
+            &claim.index,
+            &claim.amount,
+            &proof,
+        );
+        assert!(s.client.is_claimed(&round_id, &claim.index));
+    }
+    assert_eq!(s.client.get_round(&round_id).claimed, total);
+    assert_eq!(s.token.balance(&streaming.address), total);
+}
@@ -0,0 +1,119 @@
+#![no_std]
+
+mod storage;
+
+use lumenpulse_oracle::{Asset, PriceData};
+use soroban_sdk::{contract, contractimpl, Env, Vec};
+use storage::DataKey;
+
+/// SEP-40 price feed for tests. Prices are set directly by anyone through
+/// the `set_price` helpers, so tests can put a contract through any price
+/// path deterministically. Never deploy it to a real network.
+#[contract]
+pub struct MockOracleContract;
+
+#[contractimpl]
+impl MockOracleContract {
+    /// Set what the feed reports about itself
+    pub fn initialize(env: Env, base: Asset, decimals: u32, resolution: u32) {
+        env.storage().instance().set(&DataKey::Base, &base);
+        env.storage().instance().set(&DataKey::Decimals, &decimals);
+        env.storage()
+            .instance()
+            .set(&DataKey::Resolution, &resolution);
+    }
+
+    /// Record `price` for `asset` at the current ledger time
+    pub fn set_price(env: Env, asset: Asset, price: i128) {
+        let timestamp = env.ledger().timestamp();
+        Self::set_price_at(env, asset, price, timestamp);
+    }
+
+    /// Record `price` for `asset` at `timestamp`, replacing any record
+    /// already there
+    pub fn set_price_at(env: Env, asset: Asset, price: i128, timestamp: u64) {
+        let mut assets = Self::assets(env.clone());
+        if !assets.contains(&asset) {
+            assets.push_back(asset.clone());
+            env.storage().instance().set(&DataKey::Assets, &assets);
+        }
+
+        let mut history = Self::history(&env, &asset);
+        let mut index = history.len();
+        for (i, record) in history.iter().enumerate() {
+            if record.timestamp <= timestamp {
+                index = i as u32;
+                break;
+            }
+        }
+        let record = PriceData { price, timestamp };
+        match history.get(index) {
+            Some(existing) if existing.timestamp == timestamp => history.set(index, record),
+            _ => history.insert(index, record),
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::History(asset), &history);
+    }
+
+    /// Forget every price recorded for `asset`
+    pub fn clear_prices(env: Env, asset: Asset) {
+        env.storage().instance().remove(&DataKey::History(asset));
+    }
+
+    /// Asset every price is quoted in
+    pub fn base(env: Env) -> Asset {
+        env.storage().instance().get(&DataKey::Base).unwrap()
+    }
+
+    /// Assets that have had a price set
+    pub fn assets(env: Env) -> Vec<Asset> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Assets)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Number of decimals prices are scaled by
+    pub fn decimals(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Decimals).unwrap()
+    }
+
+    /// Seconds between price records
+    pub fn resolution(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Resolution).unwrap()
+    }
+
+    /// Price recorded for `asset` at exactly `timestamp`
+    pub fn price(env: Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
+        Self::history(&env, &asset)
+            .iter()
+            .find(|record| record.timestamp == timestamp)
+    }
+
+    /// Up to `records` most recent prices for `asset`, newest first
+    pub fn prices(env: Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
+        let history = Self::history(&env, &asset);
+        if history.is_empty() {
+            return None;
+        }
+        Some(history.slice(0..records.min(history.len())))
+    }
+
+    /// Most recent price for `asset`
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        Self::history(&env, &asset).first()
+    }
+}
+
+impl MockOracleContract {
+    fn history(env: &Env, asset: &Asset) -> Vec<PriceData> {
+        env.storage()
+            .instance()
+            .get(&DataKey::History(asset.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+}
+
+#[cfg(test)]
+mod test;
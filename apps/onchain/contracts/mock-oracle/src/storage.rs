@@ -0,0 +1,12 @@
+use lumenpulse_oracle::Asset;
+use soroban_sdk::contracttype;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Base,           // -> Asset
+    Decimals,       // -> u32
+    Resolution,     // -> u32
+    Assets,         // -> Vec<Asset>
+    History(Asset), // asset -> Vec<PriceData> (newest first)
+}
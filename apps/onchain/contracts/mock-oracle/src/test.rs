@@ -0,0 +1,41 @@
+use crate::{MockOracleContract, MockOracleContractClient};
+use lumenpulse_oracle::{Asset, PriceData};
+use soroban_sdk::{symbol_short, testutils::Ledger, Env};
+
+fn setup_test(env: &Env) -> MockOracleContractClient<'_> {
+    let contract_id = env.register(MockOracleContract, ());
+    let client = MockOracleContractClient::new(env, &contract_id);
+    client.initialize(&Asset::Other(symbol_short!("USD")), &7, &300);
+    client
+}
+
+#[test]
+fn test_set_prices() {
+    let env = Env::default();
+    let client = setup_test(&env);
+    let xlm = Asset::Other(symbol_short!("XLM"));
+
+    assert_eq!(client.lastprice(&xlm), None);
+    assert_eq!(client.prices(&xlm, &3), None);
+
+    env.ledger().set_timestamp(600);
+    client.set_price(&xlm, &1_100_000);
+    // Backfilled records slot in by time, and a repeated time replaces
+    client.set_price_at(&xlm, &1_000_000, &300);
+    client.set_price_at(&xlm, &1_050_000, &300);
+
+    assert_eq!(
+        client.lastprice(&xlm),
+        Some(PriceData {
+            price: 1_100_000,
+            timestamp: 600,
+        })
+    );
+    assert_eq!(client.price(&xlm, &300).unwrap().price, 1_050_000);
+    assert_eq!(client.prices(&xlm, &5).unwrap().len(), 2);
+    assert_eq!(client.assets().len(), 1);
+    assert_eq!((client.decimals(), client.resolution()), (7, 300));
+
+    client.clear_prices(&xlm);
+    assert_eq!(client.lastprice(&xlm), None);
+}
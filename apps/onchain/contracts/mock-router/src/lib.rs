@@ -0,0 +1,74 @@
+#![no_std]
+
+mod storage;
+
+use soroban_sdk::{contract, contractimpl, token, Address, Env, Vec};
+use storage::DataKey;
+
+/// Rates are scaled by 10^7, so `RATE_SCALE` swaps one for one
+pub const RATE_SCALE: i128 = 10_000_000;
+
+/// Soroswap-compatible router for tests. Anyone sets the rate for any
+/// pair, and the router itself acts as every pair, paying out of whatever
+/// it holds, so tests fund it with the output token first. Never deploy it
+/// to a real network.
+#[contract]
+pub struct MockRouterContract;
+
+#[contractimpl]
+impl MockRouterContract {
+    /// Pay `rate / RATE_SCALE` of `token_out` per unit of `token_in`
+    pub fn set_rate(env: Env, token_in: Address, token_out: Address, rate: i128) {
+        env.storage()
+            .instance()
+            .set(&DataKey::Rate(token_in, token_out), &rate);
+    }
+
+    pub fn router_pair_for(env: Env, _token_a: Address, _token_b: Address) -> Address {
+        env.current_contract_address()
+    }
+
+    pub fn router_get_amounts_out(env: Env, amount_in: i128, path: Vec<Address>) -> Vec<i128> {
+        let mut amounts = Vec::new(&env);
+        let mut amount = amount_in;
+        amounts.push_back(amount);
+        for i in 1..path.len() {
+            let rate: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::Rate(
+                    path.get_unchecked(i - 1),
+                    path.get_unchecked(i),
+                ))
+                .expect("no rate for pair");
+            amount = amount * rate / RATE_SCALE;
+            amounts.push_back(amount);
+        }
+        amounts
+    }
+
+    pub fn swap_exact_tokens_for_tokens(
+        env: Env,
+        amount_in: i128,
+        amount_out_min: i128,
+        path: Vec<Address>,
+        to: Address,
+        deadline: u64,
+    ) -> Vec<i128> {
+        to.require_auth();
+        if env.ledger().timestamp() > deadline {
+            panic!("deadline passed");
+        }
+
+        let amounts = Self::router_get_amounts_out(env.clone(), amount_in, path.clone());
+        let amount_out = amounts.last_unchecked();
+        if amount_out < amount_out_min {
+            panic!("insufficient output amount");
+        }
+
+        let this = env.current_contract_address();
+        token::Client::new(&env, &path.first_unchecked()).transfer(&to, &this, &amount_in);
+        token::Client::new(&env, &path.last_unchecked()).transfer(&this, &to, &amount_out);
+        amounts
+    }
+}
@@ -0,0 +1,7 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Rate(Address, Address), // (token_in, token_out) -> i128
+}
@@ -0,0 +1,11 @@
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchExecutedEvent {
+    #[topic]
+    pub caller: Address,
+    pub calls: u32,
+    /// Calls allowed to fail that did
+    pub failed: u32,
+}
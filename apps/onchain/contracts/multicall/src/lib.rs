@@ -0,0 +1,82 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+
+pub use errors::MulticallError;
+pub use storage::Call;
+
+use soroban_sdk::{contract, contractimpl, Address, Env, Error, Val, Vec};
+
+/// Upper bound on the calls in one batch
+const MAX_CALLS: u32 = 10;
+
+/// Batch invoker. A caller authorizes one `execute` and every call in the
+/// batch runs beneath it, so a front-end can have a user approve a token,
+/// open a stream and label it with a single signature: each target's
+/// `require_auth` on the caller is satisfied by that one authorization
+/// tree. The batch is all-or-nothing unless a call is marked
+/// `allow_failure`, and the result of every call is reported in order.
+///
+/// The contract holds no state and no funds. Targets see the multicall
+/// as their direct invoker, so calls that check `require_auth` on the
+/// multicall itself are not supported.
+#[contract]
+pub struct MulticallContract;
+
+#[contractimpl]
+impl MulticallContract {
+    /// Make `calls` in order on behalf of `caller` and return each call's
+    /// result. A call allowed to fail that does reports its `Error` in place
+    /// of a return value; a contract cannot return an `Error` on success, so
+    /// the two never mix. If any other call fails, its error aborts the
+    /// whole batch and none of the calls take effect.
+    pub fn execute(
+        env: Env,
+        caller: Address,
+        calls: Vec<Call>,
+    ) -> Result<Vec<Val>, MulticallError> {
+        if calls.is_empty() {
+            return Err(MulticallError::NoCalls);
+        }
+        if calls.len() > MAX_CALLS {
+            return Err(MulticallError::TooManyCalls);
+        }
+
+        // Authorizing the root invocation covers the calls made beneath it
+        caller.require_auth();
+
+        let mut results = Vec::new(&env);
+        let mut failed = 0;
+        for call in calls.iter() {
+            let result = if call.allow_failure {
+                match env.try_invoke_contract::<Val, Error>(&call.target, &call.function, call.args)
+                {
+                    Ok(Ok(value)) => value,
+                    Err(Ok(error)) => {
+                        failed += 1;
+                        error.to_val()
+                    }
+                    // Any return value is a `Val` and any failure an `Error`
+                    Ok(Err(_)) | Err(Err(_)) => unreachable!(),
+                }
+            } else {
+                env.invoke_contract::<Val>(&call.target, &call.function, call.args)
+            };
+            results.push_back(result);
+        }
+
+        events::BatchExecutedEvent {
+            caller,
+            calls: calls.len(),
+            failed,
+        }
+        .publish(&env);
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,13 @@
+use soroban_sdk::{contracttype, Address, Symbol, Val, Vec};
+
+/// A contract invocation made as part of a batch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Call {
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+    /// Whether the batch goes on if this call fails. The call's own effects
+    /// are rolled back either way.
+    pub allow_failure: bool,
+}
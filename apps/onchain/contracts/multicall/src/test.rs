@@ -0,0 +1,145 @@
+use crate::{Call, MulticallContract, MulticallContractClient, MulticallError};
+use lumenpulse_streaming_interface::StreamingClient;
+use lumenpulse_test_utils::{deploy_protocol, funded_accounts, setup_env};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::TokenClient,
+    vec, Address, Env, Error, IntoVal, InvokeError, Symbol, TryFromVal, Val, Vec,
+};
+
+struct Setup<'a> {
+    multicall: MulticallContractClient<'a>,
+    streaming: StreamingClient<'a>,
+    token: TokenClient<'a>,
+    sender: Address,
+    recipient: Address,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    let protocol = deploy_protocol(env);
+    let [sender] = funded_accounts(env, &protocol.token, 10_000);
+
+    Setup {
+        multicall: MulticallContractClient::new(env, &env.register(MulticallContract, ())),
+        streaming: protocol.streaming,
+        token: protocol.token.client,
+        sender,
+        recipient: Address::generate(env),
+    }
+}
+
+fn call(target: &Address, function: &str, args: Vec<Val>, allow_failure: bool) -> Call {
+    Call {
+        target: target.clone(),
+        function: Symbol::new(args.env(), function),
+        args,
+        allow_failure,
+    }
+}
+
+/// Approve the streaming contract and open a stream, as a front-end would
+fn approve_and_stream(env: &Env, setup: &Setup, amount: i128) -> Vec<Call> {
+    let start_time = env.ledger().timestamp() + 100;
+    vec![
+        env,
+        call(
+            &setup.token.address,
+            "approve",
+            (
+                &setup.sender,
+                &setup.streaming.address,
+                &500i128,
+                &(env.ledger().sequence() + 100),
+            )
+                .into_val(env),
+            false,
+        ),
+        call(
+            &setup.streaming.address,
+            "create_stream",
+            (
+                &setup.sender,
+                &setup.recipient,
+                &setup.token.address,
+                &amount,
+                &start_time,
+                &(start_time + 1_000),
+            )
+                .into_val(env),
+            false,
+        ),
+    ]
+}
+
+#[test]
+fn test_batch_runs_under_one_auth() {
+    let env = setup_env();
+    env.ledger().with_mut(|ledger| ledger.sequence_number = 10);
+    let setup = setup_test(&env);
+
+    let mut calls = approve_and_stream(&env, &setup, 1_000);
+    calls.push_back(call(
+        &setup.streaming.address,
+        "get_stream",
+        vec![&env, 99u64.into_val(&env)],
+        true,
+    ));
+    let results = setup.multicall.execute(&setup.sender, &calls);
+
+    // A single authorization from the sender covers every call in the batch
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, setup.sender);
+    assert_eq!(auths[0].1.sub_invocations.len(), 2);
+
+    assert_eq!(results.len(), 3);
+    assert!(results.get_unchecked(0).is_void());
+    assert_eq!(u64::try_from_val(&env, &results.get_unchecked(1)), Ok(0));
+    // The lookup was allowed to fail, and reports the streaming error
+    assert_eq!(
+        Error::try_from_val(&env, &results.get_unchecked(2)),
+        Ok(Error::from_contract_error(4))
+    );
+
+    assert_eq!(setup.streaming.get_stream(&0).sender, setup.sender);
+    assert_eq!(setup.token.balance(&setup.sender), 9_000);
+    assert_eq!(
+        setup
+            .token
+            .allowance(&setup.sender, &setup.streaming.address),
+        500
+    );
+}
+
+#[test]
+fn test_failed_call_reverts_batch() {
+    let env = setup_env();
+    let setup = setup_test(&env);
+
+    // The stream is refused for a zero amount, which undoes the approval
+    let calls = approve_and_stream(&env, &setup, 0);
+    let result = setup.multicall.try_execute(&setup.sender, &calls);
+    assert_eq!(result.err(), Some(Err(InvokeError::Contract(5))));
+    assert_eq!(
+        setup
+            .token
+            .allowance(&setup.sender, &setup.streaming.address),
+        0
+    );
+    assert_eq!(setup.token.balance(&setup.sender), 10_000);
+
+    let result = setup.multicall.try_execute(&setup.sender, &Vec::new(&env));
+    assert_eq!(result.err(), Some(Ok(MulticallError::NoCalls)));
+
+    let mut calls = Vec::new(&env);
+    for _ in 0..11 {
+        calls.push_back(call(
+            &setup.streaming.address,
+            "get_admin",
+            Vec::new(&env),
+            false,
+        ));
+    }
+    let result = setup.multicall.try_execute(&setup.sender, &calls);
+    assert_eq!(result.err(), Some(Ok(MulticallError::TooManyCalls)));
+}
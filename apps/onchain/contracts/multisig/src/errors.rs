@@ -0,0 +1,28 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MultisigError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    NotSigner = 3,
+    InvalidThreshold = 4,
+    DuplicateSigner = 5,
+    TransactionNotFound = 6,
+    AlreadyConfirmed = 7,
+    NotConfirmed = 8,
+    AlreadyExecuted = 9,
+    ThresholdNotMet = 10,
+    TooManySigners = 11,
+    InvalidExpiry = 12,
+    TransactionExpired = 13,
+    NotExpired = 14,
+    NoCalls = 15,
+    TooManyCalls = 16,
+    RotationNotFound = 17,
+    RotationDelayNotElapsed = 18,
+    InvalidPolicy = 19,
+    SignerKeyNotRegistered = 20,
+    InvalidApprovals = 21,
+}
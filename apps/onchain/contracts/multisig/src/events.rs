@@ -0,0 +1,110 @@
+use crate::storage::SignerChange;
+use soroban_sdk::{contractevent, Address, BytesN, Vec};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionProposedEvent {
+    #[topic]
+    pub proposer: Address,
+    pub transaction_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfirmedEvent {
+    #[topic]
+    pub signer: Address,
+    #[topic]
+    pub transaction_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevokedEvent {
+    #[topic]
+    pub signer: Address,
+    #[topic]
+    pub transaction_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionExecutedEvent {
+    #[topic]
+    pub transaction_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionPrunedEvent {
+    #[topic]
+    pub transaction_id: u64,
+}
+
+/// A signer rotation was approved and takes effect from `eta_ledger`
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RotationAnnouncedEvent {
+    #[topic]
+    pub transaction_id: u64,
+    pub change: SignerChange,
+    pub eta_ledger: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RotationCancelledEvent {
+    #[topic]
+    pub transaction_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerAddedEvent {
+    #[topic]
+    pub signer: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerRemovedEvent {
+    #[topic]
+    pub signer: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ThresholdChangedEvent {
+    pub threshold: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendingPolicySetEvent {
+    #[topic]
+    pub token: Address,
+    pub threshold: u32,
+    pub daily_limit: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendingPolicyClearedEvent {
+    #[topic]
+    pub token: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerKeyRegisteredEvent {
+    #[topic]
+    pub signer: Address,
+    pub public_key: BytesN<32>,
+}
@@ -0,0 +1,686 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+
+pub use errors::MultisigError;
+pub use storage::{
+    Action, Approval, ApprovalMessage, Call, PendingRotation, SignerChange, SpendingPolicy,
+    SpendingRecord, Transaction,
+};
+
+use soroban_sdk::{
+    contract, contractimpl, xdr::ToXdr, Address, BytesN, Env, Symbol, TryFromVal, Val, Vec,
+};
+use storage::DataKey;
+
+/// Upper bound on the signer set
+const MAX_SIGNERS: u32 = 20;
+
+/// Upper bound on the calls in one batch
+const MAX_CALLS: u32 = 10;
+
+/// Length of a spending-policy day, in seconds
+const DAY: u64 = 86_400;
+
+/// Ledgers an approved signer rotation waits before it takes effect
+/// (about a day)
+pub const ROTATION_DELAY: u32 = 17_280;
+
+/// Longest a transaction may stay open, in ledgers (about 30 days)
+const MAX_LIFETIME: u32 = 518_400;
+
+/// M-of-N wallet. Any signer may propose a contract invocation; once
+/// enough signers have confirmed it, anyone may execute it, and the call is
+/// made with the multisig as the invoker, so targets that require the
+/// multisig's auth accept it. The signer set and threshold change only
+/// through the same flow, and adding or removing a signer is announced and
+/// held for `ROTATION_DELAY` ledgers before it takes effect. A spending
+/// policy lets each signer make small transfers of a token with fewer
+/// confirmations, up to a daily limit. Signers may also approve off-chain
+/// with ed25519 signatures that a relayer submits in a single call.
+#[contract]
+pub struct MultisigContract;
+
+#[contractimpl]
+impl MultisigContract {
+    /// Initialize the wallet. Every initial signer must authorize.
+    pub fn initialize(
+        env: Env,
+        signers: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), MultisigError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Signers) {
+            return Err(MultisigError::AlreadyInitialized);
+        }
+        Self::check_signers(&signers, threshold)?;
+        for signer in signers.iter() {
+            signer.require_auth();
+        }
+
+        env.storage().instance().set(&DataKey::Signers, &signers);
+        env.storage()
+            .instance()
+            .set(&DataKey::Threshold, &threshold);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextTransactionId, &0u64);
+
+        events::InitializedEvent { signers, threshold }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Propose calling `function` on `target` with `args`. The proposer's
+    /// confirmation is recorded straight away. The transaction can no
+    /// longer be confirmed or executed after `expiry_ledger`.
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+        expiry_ledger: u32,
+    ) -> Result<u64, MultisigError> {
+        let call = Call {
+            target,
+            function,
+            args,
+        };
+        Self::create(&env, proposer, Action::Invoke(call), expiry_ledger)
+    }
+
+    /// Propose an ordered list of calls made together on execution. If any
+    /// call fails, none of them take effect.
+    pub fn propose_batch(
+        env: Env,
+        proposer: Address,
+        calls: Vec<Call>,
+        expiry_ledger: u32,
+    ) -> Result<u64, MultisigError> {
+        if calls.is_empty() {
+            return Err(MultisigError::NoCalls);
+        }
+        if calls.len() > MAX_CALLS {
+            return Err(MultisigError::TooManyCalls);
+        }
+        Self::create(&env, proposer, Action::Batch(calls), expiry_ledger)
+    }
+
+    /// Propose a change to the signer set or threshold. The change is
+    /// checked against the current configuration now and again when the
+    /// transaction is executed.
+    pub fn propose_change(
+        env: Env,
+        proposer: Address,
+        change: SignerChange,
+        expiry_ledger: u32,
+    ) -> Result<u64, MultisigError> {
+        Self::create(&env, proposer, Action::Change(change), expiry_ledger)
+    }
+
+    /// Confirm a pending transaction
+    pub fn confirm(env: Env, signer: Address, transaction_id: u64) -> Result<(), MultisigError> {
+        Self::require_signer(&env, &signer)?;
+        Self::load_pending(&env, transaction_id)?;
+        if Self::is_confirmed(env.clone(), transaction_id, signer.clone()) {
+            return Err(MultisigError::AlreadyConfirmed);
+        }
+
+        Self::set_confirmation(&env, transaction_id, &signer);
+
+        Ok(())
+    }
+
+    /// Withdraw a confirmation from a pending transaction
+    pub fn revoke(env: Env, signer: Address, transaction_id: u64) -> Result<(), MultisigError> {
+        Self::require_signer(&env, &signer)?;
+        Self::load_pending(&env, transaction_id)?;
+        if !Self::is_confirmed(env.clone(), transaction_id, signer.clone()) {
+            return Err(MultisigError::NotConfirmed);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Confirmation(transaction_id, signer.clone()));
+
+        events::RevokedEvent {
+            signer,
+            transaction_id,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Make a transaction's call once it has reached the threshold. Anyone
+    /// may trigger execution. A single `transfer` of a token with a
+    /// spending policy that keeps the proposer within its daily limit only
+    /// needs the policy's threshold.
+    pub fn execute(env: Env, transaction_id: u64) -> Result<(), MultisigError> {
+        let mut transaction = Self::load_pending(&env, transaction_id)?;
+        let threshold = Self::get_threshold(env.clone())?;
+        let spend = Self::policy_spend(&env, &transaction);
+        let required = match &spend {
+            Some((_, policy, _)) => policy.threshold.min(threshold),
+            None => threshold,
+        };
+        if Self::confirmations(env.clone(), transaction_id) < required {
+            return Err(MultisigError::ThresholdNotMet);
+        }
+        if let Some((token, _, amount)) = spend {
+            Self::record_spend(&env, &transaction.proposer, &token, amount);
+        }
+
+        // Mark executed before calling out so a target cannot re-enter
+        transaction.executed = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Transaction(transaction_id), &transaction);
+
+        match transaction.action {
+            Action::Invoke(call) => Self::invoke(&env, call),
+            Action::Batch(calls) => {
+                for call in calls.iter() {
+                    Self::invoke(&env, call);
+                }
+            }
+            Action::Change(change) => Self::execute_change(&env, transaction_id, change)?,
+        }
+
+        events::TransactionExecutedEvent { transaction_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Register the ed25519 key `signer` approves transactions off-chain with
+    pub fn register_signer_key(
+        env: Env,
+        signer: Address,
+        public_key: BytesN<32>,
+    ) -> Result<(), MultisigError> {
+        Self::require_signer(&env, &signer)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::SignerKey(signer.clone()), &public_key);
+
+        events::SignerKeyRegisteredEvent { signer, public_key }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the key `signer` approves transactions off-chain with, if
+    /// registered
+    pub fn get_signer_key(env: Env, signer: Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::SignerKey(signer))
+    }
+
+    /// Hash signers sign to approve `action` off-chain: the SHA-256 of the
+    /// XDR encoding of the matching `ApprovalMessage`
+    pub fn proposal_hash(env: Env, action: Action, expiry_ledger: u32) -> BytesN<32> {
+        let message = ApprovalMessage {
+            multisig: env.current_contract_address(),
+            action,
+            expiry_ledger,
+        };
+        env.crypto().sha256(&message.to_xdr(&env)).into()
+    }
+
+    /// Propose, confirm and execute `action` in one call from signatures
+    /// collected off-chain over its `proposal_hash`. Every signature must
+    /// verify against a current signer's registered key; the first signer
+    /// is recorded as the proposer. Each approved hash executes only once.
+    pub fn execute_with_signatures(
+        env: Env,
+        action: Action,
+        expiry_ledger: u32,
+        approvals: Vec<Approval>,
+    ) -> Result<u64, MultisigError> {
+        if approvals.is_empty() || approvals.len() > MAX_SIGNERS {
+            return Err(MultisigError::InvalidApprovals);
+        }
+        let hash = Self::proposal_hash(env.clone(), action.clone(), expiry_ledger);
+        let used = DataKey::UsedApproval(hash.clone());
+        if env.storage().persistent().has(&used) {
+            return Err(MultisigError::AlreadyExecuted);
+        }
+
+        let signers = Self::get_signers(env.clone());
+        let mut approvers = Vec::new(&env);
+        for approval in approvals.iter() {
+            if !signers.contains(&approval.signer) {
+                return Err(MultisigError::NotSigner);
+            }
+            if approvers.contains(&approval.signer) {
+                return Err(MultisigError::DuplicateSigner);
+            }
+            let public_key = Self::get_signer_key(env.clone(), approval.signer.clone())
+                .ok_or(MultisigError::SignerKeyNotRegistered)?;
+            env.crypto()
+                .ed25519_verify(&public_key, &hash.clone().into(), &approval.signature);
+            approvers.push_back(approval.signer);
+        }
+
+        let transaction_id = Self::insert(&env, approvers.get(0).unwrap(), action, expiry_ledger)?;
+        for signer in approvers.iter().skip(1) {
+            Self::set_confirmation(&env, transaction_id, &signer);
+        }
+        env.storage().persistent().set(&used, &transaction_id);
+
+        Self::execute(env, transaction_id)?;
+
+        Ok(transaction_id)
+    }
+
+    /// Apply an announced signer rotation once its delay has passed.
+    /// Anyone may finalize.
+    pub fn finalize_rotation(env: Env, transaction_id: u64) -> Result<(), MultisigError> {
+        let rotation = Self::get_pending_rotation(env.clone(), transaction_id)?;
+        if env.ledger().sequence() < rotation.eta_ledger {
+            return Err(MultisigError::RotationDelayNotElapsed);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingRotation(transaction_id));
+        Self::apply_change(&env, rotation.change)
+    }
+
+    /// Get a signer rotation awaiting its delay, by the ID of the
+    /// transaction that approved it
+    pub fn get_pending_rotation(
+        env: Env,
+        transaction_id: u64,
+    ) -> Result<PendingRotation, MultisigError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingRotation(transaction_id))
+            .ok_or(MultisigError::RotationNotFound)
+    }
+
+    /// Spending policy for transfers of `token`, if one is set
+    pub fn get_spending_policy(env: Env, token: Address) -> Option<SpendingPolicy> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SpendingPolicy(token))
+    }
+
+    /// Amount of `token` `signer` has moved under its policy today
+    pub fn spent_today(env: Env, signer: Address, token: Address) -> i128 {
+        let record: Option<SpendingRecord> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Spent(signer, token));
+        match record {
+            Some(record) if record.day == Self::today(&env) => record.spent,
+            _ => 0,
+        }
+    }
+
+    /// Delete an expired transaction and the confirmations it collected.
+    /// Anyone may prune.
+    pub fn prune(env: Env, transaction_id: u64) -> Result<(), MultisigError> {
+        let transaction = Self::get_transaction(env.clone(), transaction_id)?;
+        if transaction.executed {
+            return Err(MultisigError::AlreadyExecuted);
+        }
+        if !Self::is_expired(&env, &transaction) {
+            return Err(MultisigError::NotExpired);
+        }
+
+        for signer in Self::get_signers(env.clone()).iter() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Confirmation(transaction_id, signer));
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Transaction(transaction_id));
+
+        events::TransactionPrunedEvent { transaction_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Confirmations a transaction holds from current signers
+    pub fn confirmations(env: Env, transaction_id: u64) -> u32 {
+        let mut count = 0;
+        for signer in Self::get_signers(env.clone()).iter() {
+            if Self::is_confirmed(env.clone(), transaction_id, signer) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Whether `signer` has confirmed a transaction
+    pub fn is_confirmed(env: Env, transaction_id: u64, signer: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Confirmation(transaction_id, signer))
+    }
+
+    /// Get a transaction by ID
+    pub fn get_transaction(env: Env, transaction_id: u64) -> Result<Transaction, MultisigError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Transaction(transaction_id))
+            .ok_or(MultisigError::TransactionNotFound)
+    }
+
+    /// Current signer set
+    pub fn get_signers(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Confirmations needed to execute a transaction
+    pub fn get_threshold(env: Env) -> Result<u32, MultisigError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .ok_or(MultisigError::NotInitialized)
+    }
+}
+
+impl MultisigContract {
+    fn create(
+        env: &Env,
+        proposer: Address,
+        action: Action,
+        expiry_ledger: u32,
+    ) -> Result<u64, MultisigError> {
+        Self::require_signer(env, &proposer)?;
+        Self::insert(env, proposer, action, expiry_ledger)
+    }
+
+    /// Store a new transaction confirmed by its proposer
+    fn insert(
+        env: &Env,
+        proposer: Address,
+        action: Action,
+        expiry_ledger: u32,
+    ) -> Result<u64, MultisigError> {
+        let now = env.ledger().sequence();
+        if expiry_ledger <= now || expiry_ledger - now > MAX_LIFETIME {
+            return Err(MultisigError::InvalidExpiry);
+        }
+        if let Action::Change(change) = &action {
+            Self::check_change(env, change)?;
+        }
+
+        let transaction_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTransactionId)
+            .unwrap();
+        let transaction = Transaction {
+            id: transaction_id,
+            proposer: proposer.clone(),
+            action,
+            expiry_ledger,
+            executed: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Transaction(transaction_id), &transaction);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextTransactionId, &(transaction_id + 1));
+
+        events::TransactionProposedEvent {
+            proposer: proposer.clone(),
+            transaction_id,
+        }
+        .publish(env);
+
+        Self::set_confirmation(env, transaction_id, &proposer);
+
+        Ok(transaction_id)
+    }
+
+    fn invoke(env: &Env, call: Call) {
+        env.invoke_contract::<Val>(&call.target, &call.function, call.args);
+    }
+
+    fn check_signers(signers: &Vec<Address>, threshold: u32) -> Result<(), MultisigError> {
+        if signers.len() > MAX_SIGNERS {
+            return Err(MultisigError::TooManySigners);
+        }
+        if threshold == 0 || threshold > signers.len() {
+            return Err(MultisigError::InvalidThreshold);
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            if signers.first_index_of(&signer) != Some(i as u32) {
+                return Err(MultisigError::DuplicateSigner);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a change leaves a valid configuration behind
+    fn check_change(env: &Env, change: &SignerChange) -> Result<(), MultisigError> {
+        match change {
+            SignerChange::CancelRotation(rotation_id) => {
+                Self::get_pending_rotation(env.clone(), *rotation_id)?;
+                return Ok(());
+            }
+            SignerChange::SetSpendingPolicy(_, policy) => {
+                let threshold = Self::get_threshold(env.clone())?;
+                if policy.threshold == 0 || policy.threshold > threshold || policy.daily_limit <= 0
+                {
+                    return Err(MultisigError::InvalidPolicy);
+                }
+                return Ok(());
+            }
+            _ => {}
+        }
+        let (signers, threshold) = Self::changed(env, change)?;
+        Self::check_signers(&signers, threshold)
+    }
+
+    /// Carry out an approved change. Signer rotations are only announced
+    /// here and applied by `finalize_rotation` after the delay.
+    fn execute_change(
+        env: &Env,
+        transaction_id: u64,
+        change: SignerChange,
+    ) -> Result<(), MultisigError> {
+        Self::check_change(env, &change)?;
+        match change {
+            SignerChange::AddSigner(_) | SignerChange::RemoveSigner(_) => {
+                let eta_ledger = env.ledger().sequence() + ROTATION_DELAY;
+                let rotation = PendingRotation {
+                    change: change.clone(),
+                    eta_ledger,
+                };
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::PendingRotation(transaction_id), &rotation);
+
+                events::RotationAnnouncedEvent {
+                    transaction_id,
+                    change,
+                    eta_ledger,
+                }
+                .publish(env);
+                Ok(())
+            }
+            SignerChange::CancelRotation(rotation_id) => {
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::PendingRotation(rotation_id));
+
+                events::RotationCancelledEvent {
+                    transaction_id: rotation_id,
+                }
+                .publish(env);
+                Ok(())
+            }
+            SignerChange::SetSpendingPolicy(token, policy) => {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::SpendingPolicy(token.clone()), &policy);
+
+                events::SpendingPolicySetEvent {
+                    token,
+                    threshold: policy.threshold,
+                    daily_limit: policy.daily_limit,
+                }
+                .publish(env);
+                Ok(())
+            }
+            SignerChange::ClearSpendingPolicy(token) => {
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::SpendingPolicy(token.clone()));
+
+                events::SpendingPolicyClearedEvent { token }.publish(env);
+                Ok(())
+            }
+            SignerChange::SetThreshold(_) => Self::apply_change(env, change),
+        }
+    }
+
+    /// Token, policy and amount of a transaction that qualifies for its
+    /// token's spending policy: a single `transfer` out of the multisig
+    /// that keeps the proposer within the daily limit
+    fn policy_spend(
+        env: &Env,
+        transaction: &Transaction,
+    ) -> Option<(Address, SpendingPolicy, i128)> {
+        let Action::Invoke(call) = &transaction.action else {
+            return None;
+        };
+        if call.function != Symbol::new(env, "transfer") || call.args.len() != 3 {
+            return None;
+        }
+        let policy = Self::get_spending_policy(env.clone(), call.target.clone())?;
+        let from = Address::try_from_val(env, &call.args.get(0)?).ok()?;
+        let amount = i128::try_from_val(env, &call.args.get(2)?).ok()?;
+        if from != env.current_contract_address() || amount <= 0 {
+            return None;
+        }
+
+        let spent = Self::spent_today(
+            env.clone(),
+            transaction.proposer.clone(),
+            call.target.clone(),
+        );
+        if spent + amount > policy.daily_limit {
+            return None;
+        }
+        Some((call.target.clone(), policy, amount))
+    }
+
+    fn record_spend(env: &Env, signer: &Address, token: &Address, amount: i128) {
+        let record = SpendingRecord {
+            day: Self::today(env),
+            spent: Self::spent_today(env.clone(), signer.clone(), token.clone()) + amount,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Spent(signer.clone(), token.clone()), &record);
+    }
+
+    fn today(env: &Env) -> u64 {
+        env.ledger().timestamp() / DAY
+    }
+
+    /// Signer set and threshold as they would be after `change`
+    fn changed(env: &Env, change: &SignerChange) -> Result<(Vec<Address>, u32), MultisigError> {
+        let mut signers = Self::get_signers(env.clone());
+        let mut threshold = Self::get_threshold(env.clone())?;
+        match change {
+            SignerChange::AddSigner(signer) => {
+                if signers.contains(signer) {
+                    return Err(MultisigError::DuplicateSigner);
+                }
+                signers.push_back(signer.clone());
+            }
+            SignerChange::RemoveSigner(signer) => {
+                let index = signers
+                    .first_index_of(signer)
+                    .ok_or(MultisigError::NotSigner)?;
+                signers.remove(index);
+            }
+            SignerChange::SetThreshold(new_threshold) => threshold = *new_threshold,
+            SignerChange::CancelRotation(_)
+            | SignerChange::SetSpendingPolicy(..)
+            | SignerChange::ClearSpendingPolicy(_) => {}
+        }
+        Ok((signers, threshold))
+    }
+
+    fn apply_change(env: &Env, change: SignerChange) -> Result<(), MultisigError> {
+        let (signers, threshold) = Self::changed(env, &change)?;
+        Self::check_signers(&signers, threshold)?;
+        env.storage().instance().set(&DataKey::Signers, &signers);
+        env.storage()
+            .instance()
+            .set(&DataKey::Threshold, &threshold);
+
+        match change {
+            SignerChange::AddSigner(signer) => events::SignerAddedEvent { signer }.publish(env),
+            SignerChange::RemoveSigner(signer) => {
+                events::SignerRemovedEvent { signer }.publish(env)
+            }
+            SignerChange::SetThreshold(threshold) => {
+                events::ThresholdChangedEvent { threshold }.publish(env)
+            }
+            SignerChange::CancelRotation(_)
+            | SignerChange::SetSpendingPolicy(..)
+            | SignerChange::ClearSpendingPolicy(_) => {}
+        }
+
+        Ok(())
+    }
+
+    fn require_signer(env: &Env, signer: &Address) -> Result<(), MultisigError> {
+        if !env.storage().instance().has(&DataKey::Signers) {
+            return Err(MultisigError::NotInitialized);
+        }
+        if !Self::get_signers(env.clone()).contains(signer) {
+            return Err(MultisigError::NotSigner);
+        }
+        signer.require_auth();
+        Ok(())
+    }
+
+    /// Load a transaction that has neither been executed nor expired
+    fn load_pending(env: &Env, transaction_id: u64) -> Result<Transaction, MultisigError> {
+        let transaction = Self::get_transaction(env.clone(), transaction_id)?;
+        if transaction.executed {
+            return Err(MultisigError::AlreadyExecuted);
+        }
+        if Self::is_expired(env, &transaction) {
+            return Err(MultisigError::TransactionExpired);
+        }
+        Ok(transaction)
+    }
+
+    fn is_expired(env: &Env, transaction: &Transaction) -> bool {
+        env.ledger().sequence() > transaction.expiry_ledger
+    }
+
+    fn set_confirmation(env: &Env, transaction_id: u64, signer: &Address) {
+        env.storage().persistent().set(
+            &DataKey::Confirmation(transaction_id, signer.clone()),
+            &true,
+        );
+
+        events::ConfirmedEvent {
+            signer: signer.clone(),
+            transaction_id,
+        }
+        .publish(env);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,108 @@
+use soroban_sdk::{contracttype, Address, BytesN, Symbol, Val, Vec};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Signers,                    // -> Vec<Address>
+    Threshold,                  // -> u32
+    NextTransactionId,          // -> u64
+    Transaction(u64),           // transaction_id -> Transaction
+    Confirmation(u64, Address), // (transaction_id, signer) -> bool
+    PendingRotation(u64),       // transaction_id -> PendingRotation
+    SpendingPolicy(Address),    // token -> SpendingPolicy
+    Spent(Address, Address),    // (signer, token) -> SpendingRecord
+    SignerKey(Address),         // signer -> BytesN<32>
+    UsedApproval(BytesN<32>),   // proposal hash -> u64 (transaction_id)
+}
+
+/// A contract invocation the multisig makes under its own address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Call {
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// Change to the multisig's own configuration. These can only be made
+/// through a confirmed transaction.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SignerChange {
+    AddSigner(Address),
+    RemoveSigner(Address),
+    SetThreshold(u32),
+    /// Withdraw a rotation still waiting out its delay, by the ID of the
+    /// transaction that approved it
+    CancelRotation(u64),
+    /// Let small transfers of a token through with fewer confirmations
+    SetSpendingPolicy(Address, SpendingPolicy),
+    ClearSpendingPolicy(Address),
+}
+
+/// What a transaction does once executed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    Invoke(Call),
+    /// Calls made in order within one execution
+    Batch(Vec<Call>),
+    Change(SignerChange),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transaction {
+    pub id: u64,
+    pub proposer: Address,
+    pub action: Action,
+    /// Last ledger the transaction may be confirmed or executed in
+    pub expiry_ledger: u32,
+    pub executed: bool,
+}
+
+/// Approved signer rotation waiting out the rotation delay
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingRotation {
+    pub change: SignerChange,
+    /// First ledger the rotation can be finalized in
+    pub eta_ledger: u32,
+}
+
+/// Transfers of a token that each signer may make with fewer confirmations
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendingPolicy {
+    /// Confirmations a qualifying transfer needs
+    pub threshold: u32,
+    /// Most one signer may move under the policy per day
+    pub daily_limit: i128,
+}
+
+/// A signer's spending under a policy on the given day
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendingRecord {
+    /// Days since the Unix epoch
+    pub day: u64,
+    pub spent: i128,
+}
+
+/// Payload whose hash signers approve off-chain. Naming the multisig keeps
+/// an approval from being replayed against another wallet.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalMessage {
+    pub multisig: Address,
+    pub action: Action,
+    pub expiry_ledger: u32,
+}
+
+/// A signer's ed25519 signature over a proposal hash
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Approval {
+    pub signer: Address,
+    pub signature: BytesN<64>,
+}
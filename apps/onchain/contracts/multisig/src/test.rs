@@ -0,0 +1,663 @@
+extern crate std;
+
+use crate::errors::MultisigError;
+use crate::{
+    Action, Approval, Call, MultisigContract, MultisigContractClient, SignerChange, SpendingPolicy,
+    ROTATION_DELAY,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use lumen_token::{LumenToken, LumenTokenClient};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, IntoVal, String, Symbol, Vec,
+};
+
+#[contracttype]
+enum TargetKey {
+    Admin,
+    Fee,
+    Paused,
+}
+
+/// Stands in for a contract administered by the multisig
+#[contract]
+struct MockTarget;
+
+#[contractimpl]
+impl MockTarget {
+    pub fn init(env: Env, admin: Address) {
+        env.storage().instance().set(&TargetKey::Admin, &admin);
+    }
+
+    pub fn set_fee(env: Env, fee: u32) {
+        let admin: Address = env.storage().instance().get(&TargetKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage().instance().set(&TargetKey::Fee, &fee);
+    }
+
+    pub fn set_paused(env: Env, paused: bool) {
+        let admin: Address = env.storage().instance().get(&TargetKey::Admin).unwrap();
+        admin.require_auth();
+        env.storage().instance().set(&TargetKey::Paused, &paused);
+    }
+
+    pub fn paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&TargetKey::Paused)
+            .unwrap_or(false)
+    }
+
+    pub fn fee(env: Env) -> u32 {
+        env.storage().instance().get(&TargetKey::Fee).unwrap_or(0)
+    }
+}
+
+struct Setup<'a> {
+    env: &'a Env,
+    client: MultisigContractClient<'a>,
+    signers: Vec<Address>,
+    target: MockTargetClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let signers = vec![
+        env,
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    let contract_id = env.register(MultisigContract, ());
+    let client = MultisigContractClient::new(env, &contract_id);
+    client.initialize(&signers, &2);
+
+    let target_id = env.register(MockTarget, ());
+    let target = MockTargetClient::new(env, &target_id);
+    target.init(&contract_id);
+
+    Setup {
+        env,
+        client,
+        signers,
+        target,
+    }
+}
+
+const LIFETIME: u32 = 100;
+
+fn expiry(env: &Env) -> u32 {
+    env.ledger().sequence() + LIFETIME
+}
+
+fn propose_fee(setup: &Setup, fee: u32) -> u64 {
+    setup.client.propose(
+        &setup.signers.get(0).unwrap(),
+        &setup.target.address,
+        &symbol_short!("set_fee"),
+        &vec![setup.env, fee.into_val(setup.env)],
+        &expiry(setup.env),
+    )
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+
+    assert_eq!(setup.client.get_signers(), setup.signers);
+    assert_eq!(setup.client.get_threshold(), 2);
+
+    let result = setup.client.try_initialize(&setup.signers, &2);
+    assert_eq!(result, Err(Ok(MultisigError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_initialize_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = MultisigContractClient::new(&env, &env.register(MultisigContract, ()));
+    let signer = Address::generate(&env);
+
+    let result = client.try_initialize(&vec![&env, signer.clone()], &0);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidThreshold)));
+
+    let result = client.try_initialize(&vec![&env, signer.clone()], &2);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidThreshold)));
+
+    let result = client.try_initialize(&vec![&env, signer.clone(), signer], &1);
+    assert_eq!(result, Err(Ok(MultisigError::DuplicateSigner)));
+}
+
+#[test]
+fn test_execute_at_threshold() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+
+    let id = propose_fee(&setup, 25);
+    assert_eq!(setup.client.confirmations(&id), 1);
+
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdNotMet)));
+
+    setup.client.confirm(&setup.signers.get(1).unwrap(), &id);
+    assert_eq!(setup.client.confirmations(&id), 2);
+
+    // The target only accepts the multisig's own authorization
+    env.set_auths(&[]);
+    setup.client.execute(&id);
+    assert_eq!(setup.target.fee(), 25);
+    assert!(setup.client.get_transaction(&id).executed);
+
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(MultisigError::AlreadyExecuted)));
+}
+
+#[test]
+fn test_confirm_and_revoke() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let second = setup.signers.get(1).unwrap();
+
+    let id = propose_fee(&setup, 25);
+
+    let result = setup
+        .client
+        .try_confirm(&setup.signers.get(0).unwrap(), &id);
+    assert_eq!(result, Err(Ok(MultisigError::AlreadyConfirmed)));
+
+    let result = setup.client.try_revoke(&second, &id);
+    assert_eq!(result, Err(Ok(MultisigError::NotConfirmed)));
+
+    setup.client.confirm(&second, &id);
+    setup.client.revoke(&second, &id);
+    assert!(!setup.client.is_confirmed(&id, &second));
+    assert_eq!(setup.client.confirmations(&id), 1);
+
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdNotMet)));
+
+    let outsider = Address::generate(&env);
+    let result = setup.client.try_confirm(&outsider, &id);
+    assert_eq!(result, Err(Ok(MultisigError::NotSigner)));
+
+    let result = setup.client.try_confirm(&second, &7);
+    assert_eq!(result, Err(Ok(MultisigError::TransactionNotFound)));
+}
+
+fn pass_rotation_delay(env: &Env) {
+    env.ledger()
+        .with_mut(|li| li.sequence_number += ROTATION_DELAY);
+}
+
+fn execute_change(setup: &Setup, change: SignerChange) -> u64 {
+    let id =
+        setup
+            .client
+            .propose_change(&setup.signers.get(0).unwrap(), &change, &expiry(setup.env));
+    setup.client.confirm(&setup.signers.get(1).unwrap(), &id);
+    setup.client.execute(&id);
+    id
+}
+
+#[test]
+fn test_signer_changes_go_through_proposals() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let newcomer = Address::generate(&env);
+
+    let id = setup.client.propose_change(
+        &setup.signers.get(0).unwrap(),
+        &SignerChange::AddSigner(newcomer.clone()),
+        &expiry(setup.env),
+    );
+    // Nothing changes until the threshold confirms
+    assert_eq!(setup.client.get_signers().len(), 3);
+    setup.client.confirm(&setup.signers.get(1).unwrap(), &id);
+    setup.client.execute(&id);
+
+    // The rotation waits out its delay
+    assert_eq!(setup.client.get_signers().len(), 3);
+    pass_rotation_delay(&env);
+    setup.client.finalize_rotation(&id);
+    assert_eq!(setup.client.get_signers().len(), 4);
+    assert!(setup.client.get_signers().contains(&newcomer));
+
+    execute_change(&setup, SignerChange::SetThreshold(3));
+    assert_eq!(setup.client.get_threshold(), 3);
+
+    let third = setup.signers.get(2).unwrap();
+    let id = setup.client.propose_change(
+        &newcomer,
+        &SignerChange::RemoveSigner(third.clone()),
+        &expiry(setup.env),
+    );
+    setup.client.confirm(&setup.signers.get(0).unwrap(), &id);
+    setup.client.confirm(&setup.signers.get(1).unwrap(), &id);
+    setup.client.execute(&id);
+    pass_rotation_delay(&env);
+    setup.client.finalize_rotation(&id);
+    assert!(!setup.client.get_signers().contains(&third));
+    assert_eq!(setup.client.get_signers().len(), 3);
+}
+
+#[test]
+fn test_signer_change_sanity_checks() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = setup.signers.get(0).unwrap();
+
+    let result = setup.client.try_propose_change(
+        &proposer,
+        &SignerChange::SetThreshold(0),
+        &expiry(setup.env),
+    );
+    assert_eq!(result, Err(Ok(MultisigError::InvalidThreshold)));
+
+    let result = setup.client.try_propose_change(
+        &proposer,
+        &SignerChange::SetThreshold(4),
+        &expiry(setup.env),
+    );
+    assert_eq!(result, Err(Ok(MultisigError::InvalidThreshold)));
+
+    let result = setup.client.try_propose_change(
+        &proposer,
+        &SignerChange::AddSigner(proposer.clone()),
+        &expiry(setup.env),
+    );
+    assert_eq!(result, Err(Ok(MultisigError::DuplicateSigner)));
+
+    let outsider = Address::generate(&env);
+    let result = setup.client.try_propose_change(
+        &proposer,
+        &SignerChange::RemoveSigner(outsider),
+        &expiry(setup.env),
+    );
+    assert_eq!(result, Err(Ok(MultisigError::NotSigner)));
+
+    // Two removals that are each fine alone cannot both pass: the second
+    // would leave fewer signers than the threshold
+    let first = setup.client.propose_change(
+        &proposer,
+        &SignerChange::RemoveSigner(setup.signers.get(1).unwrap()),
+        &expiry(setup.env),
+    );
+    let second = setup.client.propose_change(
+        &proposer,
+        &SignerChange::RemoveSigner(setup.signers.get(2).unwrap()),
+        &expiry(setup.env),
+    );
+    setup.client.confirm(&setup.signers.get(2).unwrap(), &first);
+    setup
+        .client
+        .confirm(&setup.signers.get(2).unwrap(), &second);
+    setup.client.execute(&first);
+    setup.client.execute(&second);
+
+    pass_rotation_delay(&env);
+    setup.client.finalize_rotation(&first);
+    let result = setup.client.try_finalize_rotation(&second);
+    assert_eq!(result, Err(Ok(MultisigError::InvalidThreshold)));
+    assert_eq!(setup.client.get_signers().len(), 2);
+}
+
+#[test]
+fn test_rotation_delay_and_cancellation() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let newcomer = Address::generate(&env);
+
+    let result = setup.client.try_propose_change(
+        &setup.signers.get(0).unwrap(),
+        &SignerChange::CancelRotation(7),
+        &expiry(&env),
+    );
+    assert_eq!(result, Err(Ok(MultisigError::RotationNotFound)));
+
+    let start = env.ledger().sequence();
+    let rotation = execute_change(&setup, SignerChange::AddSigner(newcomer.clone()));
+    let pending = setup.client.get_pending_rotation(&rotation);
+    assert_eq!(pending.change, SignerChange::AddSigner(newcomer.clone()));
+    assert_eq!(pending.eta_ledger, start + ROTATION_DELAY);
+
+    let result = setup.client.try_finalize_rotation(&rotation);
+    assert_eq!(result, Err(Ok(MultisigError::RotationDelayNotElapsed)));
+
+    // The other signers spot the rotation and call it off
+    execute_change(&setup, SignerChange::CancelRotation(rotation));
+    let result = setup.client.try_get_pending_rotation(&rotation);
+    assert_eq!(result, Err(Ok(MultisigError::RotationNotFound)));
+
+    pass_rotation_delay(&env);
+    let result = setup.client.try_finalize_rotation(&rotation);
+    assert_eq!(result, Err(Ok(MultisigError::RotationNotFound)));
+    assert!(!setup.client.get_signers().contains(&newcomer));
+}
+
+#[test]
+fn test_transaction_expiry() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let second = setup.signers.get(1).unwrap();
+
+    let result = setup.client.try_propose(
+        &setup.signers.get(0).unwrap(),
+        &setup.target.address,
+        &symbol_short!("set_fee"),
+        &vec![&env, 25u32.into_val(&env)],
+        &env.ledger().sequence(),
+    );
+    assert_eq!(result, Err(Ok(MultisigError::InvalidExpiry)));
+
+    let id = propose_fee(&setup, 25);
+    let result = setup.client.try_prune(&id);
+    assert_eq!(result, Err(Ok(MultisigError::NotExpired)));
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += LIFETIME + 1);
+
+    let result = setup.client.try_confirm(&second, &id);
+    assert_eq!(result, Err(Ok(MultisigError::TransactionExpired)));
+
+    setup.client.prune(&id);
+    assert!(!setup
+        .client
+        .is_confirmed(&id, &setup.signers.get(0).unwrap()));
+    let result = setup.client.try_get_transaction(&id);
+    assert_eq!(result, Err(Ok(MultisigError::TransactionNotFound)));
+}
+
+#[test]
+fn test_expired_transaction_cannot_execute() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+
+    let id = propose_fee(&setup, 25);
+    setup.client.confirm(&setup.signers.get(1).unwrap(), &id);
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += LIFETIME + 1);
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(MultisigError::TransactionExpired)));
+    assert_eq!(setup.target.fee(), 0);
+}
+
+fn set_fee_call(setup: &Setup, fee: u32) -> Call {
+    Call {
+        target: setup.target.address.clone(),
+        function: symbol_short!("set_fee"),
+        args: vec![setup.env, fee.into_val(setup.env)],
+    }
+}
+
+fn set_paused_call(setup: &Setup, paused: bool) -> Call {
+    Call {
+        target: setup.target.address.clone(),
+        function: Symbol::new(setup.env, "set_paused"),
+        args: vec![setup.env, paused.into_val(setup.env)],
+    }
+}
+
+#[test]
+fn test_batch_executes_in_order() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+
+    let calls = vec![
+        &env,
+        set_fee_call(&setup, 25),
+        set_paused_call(&setup, true),
+    ];
+    let id = setup
+        .client
+        .propose_batch(&setup.signers.get(0).unwrap(), &calls, &expiry(&env));
+    setup.client.confirm(&setup.signers.get(1).unwrap(), &id);
+
+    env.set_auths(&[]);
+    setup.client.execute(&id);
+    assert_eq!(setup.target.fee(), 25);
+    assert!(setup.target.paused());
+}
+
+#[test]
+fn test_batch_is_atomic() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let proposer = setup.signers.get(0).unwrap();
+
+    // The second call does not exist, so the first must be rolled back
+    let missing = Call {
+        target: setup.target.address.clone(),
+        function: symbol_short!("missing"),
+        args: vec![&env],
+    };
+    let calls = vec![&env, set_fee_call(&setup, 25), missing];
+    let id = setup.client.propose_batch(&proposer, &calls, &expiry(&env));
+    setup.client.confirm(&setup.signers.get(1).unwrap(), &id);
+
+    assert!(setup.client.try_execute(&id).is_err());
+    assert_eq!(setup.target.fee(), 0);
+    assert!(!setup.client.get_transaction(&id).executed);
+
+    let result = setup
+        .client
+        .try_propose_batch(&proposer, &vec![&env], &expiry(&env));
+    assert_eq!(result, Err(Ok(MultisigError::NoCalls)));
+}
+
+fn funded_token<'a>(setup: &Setup<'a>, amount: i128) -> LumenTokenClient<'a> {
+    let admin = Address::generate(setup.env);
+    let token = LumenTokenClient::new(setup.env, &setup.env.register(LumenToken, ()));
+    token.initialize(
+        &admin,
+        &7,
+        &String::from_str(setup.env, "LumenPulse"),
+        &String::from_str(setup.env, "LMN"),
+    );
+    token.mint(&admin, &setup.client.address, &amount);
+    token
+}
+
+fn propose_transfer(setup: &Setup, proposer: &Address, token: &Address, amount: i128) -> u64 {
+    let to = Address::generate(setup.env);
+    setup.client.propose(
+        proposer,
+        token,
+        &symbol_short!("transfer"),
+        &vec![
+            setup.env,
+            setup.client.address.into_val(setup.env),
+            to.into_val(setup.env),
+            amount.into_val(setup.env),
+        ],
+        &expiry(setup.env),
+    )
+}
+
+#[test]
+fn test_spending_policy_allows_small_transfers() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let token = funded_token(&setup, 1_000);
+    let first = setup.signers.get(0).unwrap();
+    let second = setup.signers.get(1).unwrap();
+
+    let policy = SpendingPolicy {
+        threshold: 1,
+        daily_limit: 100,
+    };
+    execute_change(
+        &setup,
+        SignerChange::SetSpendingPolicy(token.address.clone(), policy.clone()),
+    );
+    assert_eq!(
+        setup.client.get_spending_policy(&token.address),
+        Some(policy)
+    );
+
+    // Within the limit, the proposer's own confirmation is enough
+    let id = propose_transfer(&setup, &first, &token.address, 60);
+    setup.client.execute(&id);
+    assert_eq!(token.balance(&setup.client.address), 940);
+    assert_eq!(setup.client.spent_today(&first, &token.address), 60);
+
+    // Past the limit the full threshold applies again
+    let id = propose_transfer(&setup, &first, &token.address, 50);
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdNotMet)));
+    setup.client.confirm(&second, &id);
+    setup.client.execute(&id);
+    assert_eq!(setup.client.spent_today(&first, &token.address), 60);
+
+    // Each signer has its own allowance, and it resets the next day
+    let id = propose_transfer(&setup, &second, &token.address, 100);
+    setup.client.execute(&id);
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    assert_eq!(setup.client.spent_today(&first, &token.address), 0);
+    let id = propose_transfer(&setup, &first, &token.address, 100);
+    setup.client.execute(&id);
+    assert_eq!(token.balance(&setup.client.address), 690);
+}
+
+#[test]
+fn test_spending_policy_validation() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let token = funded_token(&setup, 1_000);
+    let proposer = setup.signers.get(0).unwrap();
+
+    let result = setup.client.try_propose_change(
+        &proposer,
+        &SignerChange::SetSpendingPolicy(
+            token.address.clone(),
+            SpendingPolicy {
+                threshold: 3,
+                daily_limit: 100,
+            },
+        ),
+        &expiry(&env),
+    );
+    assert_eq!(result, Err(Ok(MultisigError::InvalidPolicy)));
+
+    execute_change(
+        &setup,
+        SignerChange::SetSpendingPolicy(
+            token.address.clone(),
+            SpendingPolicy {
+                threshold: 1,
+                daily_limit: 100,
+            },
+        ),
+    );
+    execute_change(
+        &setup,
+        SignerChange::ClearSpendingPolicy(token.address.clone()),
+    );
+    assert_eq!(setup.client.get_spending_policy(&token.address), None);
+
+    let id = propose_transfer(&setup, &proposer, &token.address, 10);
+    let result = setup.client.try_execute(&id);
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdNotMet)));
+}
+
+fn signing_keys(setup: &Setup) -> std::vec::Vec<SigningKey> {
+    let mut keys = std::vec::Vec::new();
+    for (i, signer) in setup.signers.iter().enumerate() {
+        let key = SigningKey::from_bytes(&[i as u8 + 1; 32]);
+        let public_key = BytesN::from_array(setup.env, &key.verifying_key().to_bytes());
+        setup.client.register_signer_key(&signer, &public_key);
+        keys.push(key);
+    }
+    keys
+}
+
+fn approve(setup: &Setup, key: &SigningKey, signer: Address, hash: &BytesN<32>) -> Approval {
+    Approval {
+        signer,
+        signature: BytesN::from_array(setup.env, &key.sign(&hash.to_array()).to_bytes()),
+    }
+}
+
+#[test]
+fn test_execute_with_signatures() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let keys = signing_keys(&setup);
+
+    let action = Action::Invoke(set_fee_call(&setup, 25));
+    let expiry_ledger = expiry(&env);
+    let hash = setup.client.proposal_hash(&action, &expiry_ledger);
+    let approvals = vec![
+        &env,
+        approve(&setup, &keys[0], setup.signers.get(0).unwrap(), &hash),
+        approve(&setup, &keys[2], setup.signers.get(2).unwrap(), &hash),
+    ];
+
+    // A relayer submits everything without any signer authorizing on-chain
+    env.set_auths(&[]);
+    let id = setup
+        .client
+        .execute_with_signatures(&action, &expiry_ledger, &approvals);
+    assert_eq!(setup.target.fee(), 25);
+
+    let transaction = setup.client.get_transaction(&id);
+    assert!(transaction.executed);
+    assert_eq!(transaction.proposer, setup.signers.get(0).unwrap());
+    assert_eq!(setup.client.confirmations(&id), 2);
+
+    let result = setup
+        .client
+        .try_execute_with_signatures(&action, &expiry_ledger, &approvals);
+    assert_eq!(result, Err(Ok(MultisigError::AlreadyExecuted)));
+}
+
+#[test]
+fn test_execute_with_signatures_rejects_bad_approvals() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let keys = signing_keys(&setup);
+    let first = setup.signers.get(0).unwrap();
+
+    let action = Action::Invoke(set_fee_call(&setup, 25));
+    let expiry_ledger = expiry(&env);
+    let hash = setup.client.proposal_hash(&action, &expiry_ledger);
+
+    // Too few approvals for the threshold
+    let approvals = vec![&env, approve(&setup, &keys[0], first.clone(), &hash)];
+    let result = setup
+        .client
+        .try_execute_with_signatures(&action, &expiry_ledger, &approvals);
+    assert_eq!(result, Err(Ok(MultisigError::ThresholdNotMet)));
+
+    let approvals = vec![
+        &env,
+        approve(&setup, &keys[0], first.clone(), &hash),
+        approve(&setup, &keys[0], first.clone(), &hash),
+    ];
+    let result = setup
+        .client
+        .try_execute_with_signatures(&action, &expiry_ledger, &approvals);
+    assert_eq!(result, Err(Ok(MultisigError::DuplicateSigner)));
+
+    // Signed with the wrong key
+    let approvals = vec![
+        &env,
+        approve(&setup, &keys[0], first.clone(), &hash),
+        approve(&setup, &keys[0], setup.signers.get(1).unwrap(), &hash),
+    ];
+    let result = setup
+        .client
+        .try_execute_with_signatures(&action, &expiry_ledger, &approvals);
+    assert!(result.is_err());
+
+    let outsider = Address::generate(&env);
+    let approvals = vec![&env, approve(&setup, &keys[0], outsider, &hash)];
+    let result = setup
+        .client
+        .try_execute_with_signatures(&action, &expiry_ledger, &approvals);
+    assert_eq!(result, Err(Ok(MultisigError::NotSigner)));
+    assert_eq!(setup.target.fee(), 0);
+}
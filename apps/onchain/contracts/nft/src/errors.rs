@@ -0,0 +1,15 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum NftError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    TokenNotFound = 4,
+    NotOwner = 5,
+    NotApproved = 6,
+    IndexOutOfBounds = 7,
+    TooManyAttributes = 8,
+}
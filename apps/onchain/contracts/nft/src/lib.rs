@@ -0,0 +1,355 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+
+pub use errors::NftError;
+pub use storage::{Attribute, CollectionMetadata, TokenMetadata};
+
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+use storage::{DataKey, MAX_ATTRIBUTES};
+
+/// Most tokens returned by one page of a listing
+const MAX_PAGE: u32 = 100;
+
+/// Non-fungible token collection. Token IDs are assigned in mint order
+/// starting from 0. Owners may transfer their tokens, approve one address
+/// per token, or approve an operator for all of them. Each token carries its
+/// metadata on-chain and every owner's tokens are indexed, so holdings can
+/// be listed page by page without an indexer.
+#[contract]
+pub struct NftContract;
+
+#[contractimpl]
+impl NftContract {
+    /// Initialize the collection with the admin that mints and its name
+    /// and symbol
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+    ) -> Result<(), NftError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(NftError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::Metadata, &CollectionMetadata { name, symbol });
+        env.storage().instance().set(&DataKey::NextTokenId, &0u32);
+
+        Ok(())
+    }
+
+    /// Mint the next token to `to` with `metadata` (admin only). Returns
+    /// its ID.
+    pub fn mint(
+        env: Env,
+        admin: Address,
+        to: Address,
+        metadata: TokenMetadata,
+    ) -> Result<u32, NftError> {
+        Self::require_admin(&env, &admin)?;
+        Self::check_metadata(&metadata)?;
+
+        let token_id = Self::total_supply(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::NextTokenId, &(token_id + 1));
+        env.storage()
+            .persistent()
+            .set(&DataKey::TokenMetadata(token_id), &metadata);
+        Self::add_to_owner(&env, &to, token_id);
+
+        events::MintEvent { to, token_id }.publish(&env);
+
+        Ok(token_id)
+    }
+
+    /// Replace a token's metadata (admin only)
+    pub fn set_token_metadata(
+        env: Env,
+        admin: Address,
+        token_id: u32,
+        metadata: TokenMetadata,
+    ) -> Result<(), NftError> {
+        Self::require_admin(&env, &admin)?;
+        Self::owner_of(env.clone(), token_id)?;
+        Self::check_metadata(&metadata)?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::TokenMetadata(token_id), &metadata);
+
+        events::MetadataUpdatedEvent { token_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Transfer `token_id` from its owner `from` to `to`
+    pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) -> Result<(), NftError> {
+        from.require_auth();
+        Self::move_token(&env, &from, &to, token_id)
+    }
+
+    /// Transfer `token_id` from `from` to `to` on the owner's behalf.
+    /// `spender` must be the token's approved address or an operator for
+    /// the owner.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        token_id: u32,
+    ) -> Result<(), NftError> {
+        spender.require_auth();
+
+        let owner = Self::owner_of(env.clone(), token_id)?;
+        if spender != owner
+            && Self::get_approved(env.clone(), token_id) != Some(spender.clone())
+            && !Self::is_approved_for_all(env.clone(), owner, spender)
+        {
+            return Err(NftError::NotApproved);
+        }
+        Self::move_token(&env, &from, &to, token_id)
+    }
+
+    /// Let `approved` transfer `token_id`, or clear its approval with
+    /// `None`. `approver` must be the owner or one of its operators. The
+    /// approval is cleared whenever the token changes hands.
+    pub fn approve(
+        env: Env,
+        approver: Address,
+        approved: Option<Address>,
+        token_id: u32,
+    ) -> Result<(), NftError> {
+        approver.require_auth();
+
+        let owner = Self::owner_of(env.clone(), token_id)?;
+        if approver != owner && !Self::is_approved_for_all(env.clone(), owner.clone(), approver) {
+            return Err(NftError::NotApproved);
+        }
+
+        let key = DataKey::Approved(token_id);
+        match &approved {
+            Some(address) => env.storage().persistent().set(&key, address),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        events::ApprovalEvent {
+            owner,
+            approved,
+            token_id,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Let `operator` transfer and approve any of `owner`'s tokens, or
+    /// revoke that
+    pub fn approve_for_all(env: Env, owner: Address, operator: Address, approved: bool) {
+        owner.require_auth();
+
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        if approved {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        events::ApprovalForAllEvent {
+            owner,
+            operator,
+            approved,
+        }
+        .publish(&env);
+    }
+
+    /// Owner of `token_id`
+    pub fn owner_of(env: Env, token_id: u32) -> Result<Address, NftError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Owner(token_id))
+            .ok_or(NftError::TokenNotFound)
+    }
+
+    /// Address approved to transfer `token_id`, if any
+    pub fn get_approved(env: Env, token_id: u32) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Approved(token_id))
+    }
+
+    /// Whether `operator` may manage all of `owner`'s tokens
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Operator(owner, operator))
+            .unwrap_or(false)
+    }
+
+    /// Number of tokens `owner` holds
+    pub fn balance(env: Env, owner: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(owner))
+            .unwrap_or(0)
+    }
+
+    /// Number of tokens minted
+    pub fn total_supply(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::NextTokenId)
+            .unwrap_or(0)
+    }
+
+    /// Token at `index` among all tokens, in mint order
+    pub fn token_by_index(env: Env, index: u32) -> Result<u32, NftError> {
+        if index >= Self::total_supply(env) {
+            return Err(NftError::IndexOutOfBounds);
+        }
+        Ok(index)
+    }
+
+    /// Token at `index` among `owner`'s tokens. The order changes as tokens
+    /// are transferred out.
+    pub fn token_of_owner_by_index(env: Env, owner: Address, index: u32) -> Result<u32, NftError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OwnedToken(owner, index))
+            .ok_or(NftError::IndexOutOfBounds)
+    }
+
+    /// Metadata of `token_id`
+    pub fn token_metadata(env: Env, token_id: u32) -> Result<TokenMetadata, NftError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TokenMetadata(token_id))
+            .ok_or(NftError::TokenNotFound)
+    }
+
+    /// Up to `limit` of `owner`'s tokens, starting at position `cursor` in
+    /// its token list
+    pub fn tokens_of(env: Env, owner: Address, cursor: u32, limit: u32) -> Vec<u32> {
+        let end = Self::balance(env.clone(), owner.clone()).min(cursor + limit.min(MAX_PAGE));
+        let mut tokens = Vec::new(&env);
+        for index in cursor..end {
+            if let Ok(token_id) = Self::token_of_owner_by_index(env.clone(), owner.clone(), index) {
+                tokens.push_back(token_id);
+            }
+        }
+        tokens
+    }
+
+    /// Up to `limit` tokens in mint order, starting from ID `cursor`
+    pub fn all_tokens(env: Env, cursor: u32, limit: u32) -> Vec<u32> {
+        let end = Self::total_supply(env.clone()).min(cursor + limit.min(MAX_PAGE));
+        let mut tokens = Vec::new(&env);
+        for token_id in cursor..end {
+            tokens.push_back(token_id);
+        }
+        tokens
+    }
+
+    /// Name of the collection
+    pub fn name(env: Env) -> Result<String, NftError> {
+        Ok(Self::metadata(&env)?.name)
+    }
+
+    /// Symbol of the collection
+    pub fn symbol(env: Env) -> Result<String, NftError> {
+        Ok(Self::metadata(&env)?.symbol)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, NftError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(NftError::NotInitialized)
+    }
+}
+
+impl NftContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), NftError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(NftError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn check_metadata(metadata: &TokenMetadata) -> Result<(), NftError> {
+        if metadata.attributes.len() > MAX_ATTRIBUTES {
+            return Err(NftError::TooManyAttributes);
+        }
+        Ok(())
+    }
+
+    fn metadata(env: &Env) -> Result<CollectionMetadata, NftError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Metadata)
+            .ok_or(NftError::NotInitialized)
+    }
+
+    fn move_token(env: &Env, from: &Address, to: &Address, token_id: u32) -> Result<(), NftError> {
+        if Self::owner_of(env.clone(), token_id)? != *from {
+            return Err(NftError::NotOwner);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Approved(token_id));
+        Self::remove_from_owner(env, from, token_id);
+        Self::add_to_owner(env, to, token_id);
+
+        events::TransferEvent {
+            from: from.clone(),
+            to: to.clone(),
+            token_id,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Record `token_id` as `owner`'s, appended to its token list
+    fn add_to_owner(env: &Env, owner: &Address, token_id: u32) {
+        let balance = Self::balance(env.clone(), owner.clone());
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::Owner(token_id), owner);
+        storage.set(&DataKey::OwnedToken(owner.clone(), balance), &token_id);
+        storage.set(&DataKey::OwnedIndex(token_id), &balance);
+        storage.set(&DataKey::Balance(owner.clone()), &(balance + 1));
+    }
+
+    /// Drop `token_id` from `owner`'s token list, moving the last token
+    /// into its slot
+    fn remove_from_owner(env: &Env, owner: &Address, token_id: u32) {
+        let last = Self::balance(env.clone(), owner.clone()) - 1;
+        let storage = env.storage().persistent();
+        let index: u32 = storage.get(&DataKey::OwnedIndex(token_id)).unwrap();
+        if index != last {
+            let moved: u32 = storage
+                .get(&DataKey::OwnedToken(owner.clone(), last))
+                .unwrap();
+            storage.set(&DataKey::OwnedToken(owner.clone(), index), &moved);
+            storage.set(&DataKey::OwnedIndex(moved), &index);
+        }
+        storage.remove(&DataKey::OwnedToken(owner.clone(), last));
+        storage.remove(&DataKey::OwnedIndex(token_id));
+        storage.set(&DataKey::Balance(owner.clone()), &last);
+    }
+}
+
+#[cfg(test)]
+mod test;
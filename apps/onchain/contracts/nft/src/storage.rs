@@ -0,0 +1,44 @@
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Vec};
+
+/// Most attributes one token may carry
+pub const MAX_ATTRIBUTES: u32 = 16;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                      // -> Address
+    Metadata,                   // -> CollectionMetadata
+    NextTokenId,                // -> u32
+    Owner(u32),                 // token_id -> Address
+    Approved(u32),              // token_id -> Address
+    Operator(Address, Address), // (owner, operator) -> bool
+    Balance(Address),           // owner -> u32
+    OwnedToken(Address, u32),   // (owner, index) -> token_id
+    OwnedIndex(u32),            // token_id -> index in its owner's tokens
+    TokenMetadata(u32),         // token_id -> TokenMetadata
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollectionMetadata {
+    pub name: String,
+    pub symbol: String,
+}
+
+/// What a token represents, stored on-chain so it can be listed without an
+/// indexer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    pub name: String,
+    /// SHA-256 of the off-chain document the token's URI points to
+    pub uri_hash: BytesN<32>,
+    pub attributes: Vec<Attribute>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attribute {
+    pub key: Symbol,
+    pub value: String,
+}
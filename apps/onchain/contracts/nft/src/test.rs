@@ -0,0 +1,161 @@
+use crate::{Attribute, NftContract, NftContractClient, NftError, TokenMetadata};
+use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, BytesN, Env, String, Vec};
+
+struct Setup<'a> {
+    client: NftContractClient<'a>,
+    admin: Address,
+    alice: Address,
+    bob: Address,
+}
+
+impl Setup<'_> {
+    /// Mint a token with placeholder metadata to `to`
+    fn mint(&self, env: &Env, to: &Address) -> u32 {
+        self.client
+            .mint(&self.admin, to, &metadata(env, 0, Vec::new(env)))
+    }
+}
+
+fn metadata(env: &Env, seed: u8, attributes: Vec<Attribute>) -> TokenMetadata {
+    TokenMetadata {
+        name: String::from_str(env, "Pulse"),
+        uri_hash: BytesN::from_array(env, &[seed; 32]),
+        attributes,
+    }
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let client = NftContractClient::new(env, &env.register(NftContract, ()));
+    client.initialize(
+        &admin,
+        &String::from_str(env, "Lumenpulse"),
+        &String::from_str(env, "LPN"),
+    );
+
+    Setup {
+        client,
+        admin,
+        alice: Address::generate(env),
+        bob: Address::generate(env),
+    }
+}
+
+#[test]
+fn test_mint_and_transfer() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    assert_eq!(s.mint(&env, &s.alice), 0);
+    assert_eq!(s.mint(&env, &s.alice), 1);
+    assert_eq!(s.client.owner_of(&0), s.alice);
+    assert_eq!(s.client.total_supply(), 2);
+    assert_eq!(s.client.symbol(), String::from_str(&env, "LPN"));
+
+    s.client.transfer(&s.alice, &s.bob, &0);
+    assert_eq!(s.client.owner_of(&0), s.bob);
+    assert_eq!(
+        (s.client.balance(&s.alice), s.client.balance(&s.bob)),
+        (1, 1)
+    );
+
+    let result = s.client.try_transfer(&s.alice, &s.bob, &0);
+    assert_eq!(result, Err(Ok(NftError::NotOwner)));
+    let result = s.client.try_owner_of(&2);
+    assert_eq!(result, Err(Ok(NftError::TokenNotFound)));
+    let result = s
+        .client
+        .try_mint(&s.alice, &s.alice, &metadata(&env, 0, Vec::new(&env)));
+    assert_eq!(result, Err(Ok(NftError::Unauthorized)));
+}
+
+#[test]
+fn test_approvals() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let operator = Address::generate(&env);
+    s.mint(&env, &s.alice);
+    s.mint(&env, &s.alice);
+
+    let result = s.client.try_transfer_from(&s.bob, &s.alice, &s.bob, &0);
+    assert_eq!(result, Err(Ok(NftError::NotApproved)));
+
+    // A token approval is used up by the transfer
+    s.client.approve(&s.alice, &Some(s.bob.clone()), &0);
+    assert_eq!(s.client.get_approved(&0), Some(s.bob.clone()));
+    s.client.transfer_from(&s.bob, &s.alice, &s.bob, &0);
+    assert_eq!(s.client.owner_of(&0), s.bob);
+    assert_eq!(s.client.get_approved(&0), None);
+
+    // An operator may approve and transfer any of the owner's tokens
+    s.client.approve_for_all(&s.alice, &operator, &true);
+    s.client.approve(&operator, &Some(s.bob.clone()), &1);
+    s.client.transfer_from(&operator, &s.alice, &operator, &1);
+    assert_eq!(s.client.owner_of(&1), operator);
+
+    s.client.approve_for_all(&s.alice, &operator, &false);
+    assert!(!s.client.is_approved_for_all(&s.alice, &operator));
+    let result = s.client.try_approve(&s.alice, &None, &0);
+    assert_eq!(result, Err(Ok(NftError::NotApproved)));
+}
+
+#[test]
+fn test_enumeration() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    for _ in 0..3 {
+        s.mint(&env, &s.alice);
+    }
+    assert_eq!(s.client.token_by_index(&2), 2);
+    assert_eq!(
+        s.client.try_token_by_index(&3),
+        Err(Ok(NftError::IndexOutOfBounds))
+    );
+
+    // Moving the first token out fills its slot with the last
+    s.client.transfer(&s.alice, &s.bob, &0);
+    assert_eq!(s.client.token_of_owner_by_index(&s.alice, &0), 2);
+    assert_eq!(s.client.token_of_owner_by_index(&s.alice, &1), 1);
+    assert_eq!(
+        s.client.try_token_of_owner_by_index(&s.alice, &2),
+        Err(Ok(NftError::IndexOutOfBounds))
+    );
+    assert_eq!(s.client.token_of_owner_by_index(&s.bob, &0), 0);
+
+    assert_eq!(s.client.tokens_of(&s.alice, &0, &10), vec![&env, 2, 1]);
+    assert_eq!(s.client.tokens_of(&s.alice, &1, &10), vec![&env, 1]);
+    assert_eq!(s.client.tokens_of(&s.bob, &1, &10), Vec::new(&env));
+    assert_eq!(s.client.all_tokens(&1, &1), vec![&env, 1]);
+    assert_eq!(s.client.all_tokens(&0, &10), vec![&env, 0, 1, 2]);
+}
+
+#[test]
+fn test_token_metadata() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let attribute = |value| Attribute {
+        key: symbol_short!("tier"),
+        value: String::from_str(&env, value),
+    };
+
+    let original = metadata(&env, 1, vec![&env, attribute("gold")]);
+    let token_id = s.client.mint(&s.admin, &s.alice, &original);
+    assert_eq!(s.client.token_metadata(&token_id), original);
+
+    let updated = metadata(&env, 2, vec![&env, attribute("platinum")]);
+    s.client.set_token_metadata(&s.admin, &token_id, &updated);
+    assert_eq!(s.client.token_metadata(&token_id), updated);
+
+    let mut attributes = Vec::new(&env);
+    for _ in 0..17 {
+        attributes.push_back(attribute("gold"));
+    }
+    let result = s
+        .client
+        .try_mint(&s.admin, &s.alice, &metadata(&env, 3, attributes));
+    assert_eq!(result, Err(Ok(NftError::TooManyAttributes)));
+    let result = s.client.try_set_token_metadata(&s.admin, &9, &updated);
+    assert_eq!(result, Err(Ok(NftError::TokenNotFound)));
+}
@@ -0,0 +1,245 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod math;
+mod storage;
+
+use errors::AggregatorError;
+use lumenpulse_oracle::{Asset, OracleError, PriceData, PriceFeedClient};
+use math::MAX_VALUES;
+use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+use storage::{AggregatorConfig, DataKey};
+
+/// Upper bound on the feeds one aggregator queries
+const MAX_FEEDS: u32 = MAX_VALUES as u32;
+
+/// Upper bound on the decimals aggregated prices are scaled to
+const MAX_DECIMALS: u32 = 18;
+
+/// Upper bound on the records one `prices` call returns
+const MAX_RECORDS: u32 = 20;
+
+/// SEP-40 price feed that answers with the median of several other feeds.
+/// Feeds more than the configured deviation away from the median are
+/// dropped before the final median is taken, and a price is only given
+/// while enough feeds agree, so no single feed can poison the result.
+/// Consumers use it like any other feed.
+#[contract]
+pub struct OracleAggregatorContract;
+
+#[contractimpl]
+impl OracleAggregatorContract {
+    /// Initialize the aggregator with its admin and feeds
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        config: AggregatorConfig,
+    ) -> Result<(), AggregatorError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(AggregatorError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        Self::check_config(&config)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        events::InitializedEvent { admin, config }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Replace the feeds and aggregation rules (admin only)
+    pub fn set_config(
+        env: Env,
+        admin: Address,
+        config: AggregatorConfig,
+    ) -> Result<(), AggregatorError> {
+        Self::require_admin(&env, &admin)?;
+        Self::check_config(&config)?;
+
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        events::ConfigUpdatedEvent { config }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Current feeds and aggregation rules
+    pub fn get_config(env: Env) -> Result<AggregatorConfig, AggregatorError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(AggregatorError::NotInitialized)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, AggregatorError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AggregatorError::NotInitialized)
+    }
+
+    /// Asset every price is quoted in
+    pub fn base(env: Env) -> Result<Asset, AggregatorError> {
+        Ok(Self::get_config(env)?.base)
+    }
+
+    /// Assets at least `min_sources` feeds quote
+    pub fn assets(env: Env) -> Result<Vec<Asset>, AggregatorError> {
+        let config = Self::get_config(env.clone())?;
+        let mut quoted: Vec<Asset> = Vec::new(&env);
+        let mut counts: Vec<u32> = Vec::new(&env);
+        for feed in config.feeds.iter() {
+            let Ok(Ok(assets)) = PriceFeedClient::new(&env, &feed).try_assets() else {
+                continue;
+            };
+            for asset in assets.iter() {
+                match quoted.first_index_of(&asset) {
+                    Some(i) => counts.set(i, counts.get_unchecked(i) + 1),
+                    None => {
+                        quoted.push_back(asset);
+                        counts.push_back(1);
+                    }
+                }
+            }
+        }
+
+        let mut assets = Vec::new(&env);
+        for (asset, count) in quoted.iter().zip(counts.iter()) {
+            if count >= config.min_sources {
+                assets.push_back(asset);
+            }
+        }
+        Ok(assets)
+    }
+
+    /// Number of decimals aggregated prices are scaled by
+    pub fn decimals(env: Env) -> Result<u32, AggregatorError> {
+        Ok(Self::get_config(env)?.decimals)
+    }
+
+    /// Seconds between the records `prices` returns
+    pub fn resolution(env: Env) -> Result<u32, AggregatorError> {
+        Ok(Self::get_config(env)?.resolution)
+    }
+
+    /// Aggregated price of `asset` at `timestamp`
+    pub fn price(env: Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
+        let config = Self::get_config(env.clone()).ok()?;
+        let price = Self::aggregate(&env, &config, |feed| {
+            lumenpulse_oracle::price(&env, feed, &asset, timestamp)
+        })?;
+        Some(PriceData { price, timestamp })
+    }
+
+    /// Aggregated prices of `asset` at up to `records` of the latest
+    /// resolution steps, newest first. Steps without an aggregated price are
+    /// left out.
+    pub fn prices(env: Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
+        let config = Self::get_config(env.clone()).ok()?;
+        let resolution = config.resolution as u64;
+        let now = env.ledger().timestamp();
+        let mut timestamp = now - now % resolution;
+
+        let mut history = Vec::new(&env);
+        for _ in 0..records.min(MAX_RECORDS) {
+            if let Some(price) = Self::price(env.clone(), asset.clone(), timestamp) {
+                history.push_back(price);
+            }
+            if timestamp < resolution {
+                break;
+            }
+            timestamp -= resolution;
+        }
+        if history.is_empty() {
+            return None;
+        }
+        Some(history)
+    }
+
+    /// Aggregated latest price of `asset` across the feeds with a price no
+    /// older than `max_age`. Its timestamp is that of the oldest of those
+    /// prices.
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        let config = Self::get_config(env.clone()).ok()?;
+        let now = env.ledger().timestamp();
+        let mut oldest = now;
+        let price = Self::aggregate(&env, &config, |feed| {
+            let price = lumenpulse_oracle::lastprice(&env, feed, &asset)?;
+            if now.saturating_sub(price.timestamp) > config.max_age {
+                return Err(OracleError::StalePrice);
+            }
+            oldest = oldest.min(price.timestamp);
+            Ok(price)
+        })?;
+        Some(PriceData {
+            price,
+            timestamp: oldest,
+        })
+    }
+}
+
+impl OracleAggregatorContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), AggregatorError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(AggregatorError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn check_config(config: &AggregatorConfig) -> Result<(), AggregatorError> {
+        let feeds = config.feeds.len();
+        if feeds == 0
+            || feeds > MAX_FEEDS
+            || config.min_sources == 0
+            || config.min_sources > feeds
+            || config.decimals > MAX_DECIMALS
+            || config.resolution == 0
+        {
+            return Err(AggregatorError::InvalidConfig);
+        }
+        Ok(())
+    }
+
+    /// Median of the prices `fetch` gets from each feed, rescaled to the
+    /// configured decimals. Feeds that fail are skipped.
+    fn aggregate(
+        env: &Env,
+        config: &AggregatorConfig,
+        mut fetch: impl FnMut(&Address) -> Result<PriceData, OracleError>,
+    ) -> Option<i128> {
+        let mut values = [0i128; MAX_VALUES];
+        let mut count = 0;
+        for feed in config.feeds.iter() {
+            let Ok(price) = fetch(&feed) else {
+                continue;
+            };
+            let Ok(decimals) = lumenpulse_oracle::decimals(env, &feed) else {
+                continue;
+            };
+            let Some(value) = math::rescale(price.price, decimals, config.decimals) else {
+                continue;
+            };
+            if value > 0 {
+                math::insert_sorted(&mut values, count, value);
+                count += 1;
+            }
+        }
+        math::aggregate(
+            &values[..count],
+            config.min_sources,
+            config.max_deviation_bps,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,62 @@
+//! Median aggregation with outlier rejection
+
+use lumenpulse_fixed::{bps, Rounding};
+
+/// Most values `aggregate` handles at once
+pub const MAX_VALUES: usize = 10;
+
+/// Median of `values`, which must be sorted; the lower-rounded mean of the
+/// middle two for an even count
+pub fn median(values: &[i128]) -> Option<i128> {
+    let n = values.len();
+    if n == 0 {
+        return None;
+    }
+    if n % 2 == 1 {
+        return Some(values[n / 2]);
+    }
+    let (a, b) = (values[n / 2 - 1], values[n / 2]);
+    // Average without overflowing
+    Some(a / 2 + b / 2 + (a % 2 + b % 2) / 2)
+}
+
+/// Median of the values within `max_deviation_bps` of the median of all of
+/// them, or none if fewer than `min_sources` survive. `values` must be
+/// sorted and positive.
+pub fn aggregate(values: &[i128], min_sources: u32, max_deviation_bps: u32) -> Option<i128> {
+    let center = median(values)?;
+    // Rounding the tolerance down never admits a value past the bound
+    let tolerance = bps(center, max_deviation_bps, Rounding::Down)?;
+
+    let mut kept = [0i128; MAX_VALUES];
+    let mut count = 0;
+    for value in values {
+        if (value - center).abs() <= tolerance {
+            kept[count] = *value;
+            count += 1;
+        }
+    }
+    if (count as u32) < min_sources {
+        return None;
+    }
+    median(&kept[..count])
+}
+
+/// Insert `value` into the sorted prefix `values[..len]`
+pub fn insert_sorted(values: &mut [i128; MAX_VALUES], len: usize, value: i128) {
+    let mut i = len;
+    while i > 0 && values[i - 1] > value {
+        values[i] = values[i - 1];
+        i -= 1;
+    }
+    values[i] = value;
+}
+
+/// Rescale `price` from `from` decimals to `to` decimals, rounding down
+pub fn rescale(price: i128, from: u32, to: u32) -> Option<i128> {
+    if from <= to {
+        price.checked_mul(10i128.checked_pow(to - from)?)
+    } else {
+        Some(price / 10i128.checked_pow(from - to)?)
+    }
+}
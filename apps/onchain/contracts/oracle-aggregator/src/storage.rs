@@ -0,0 +1,29 @@
+use lumenpulse_oracle::Asset;
+use soroban_sdk::{contracttype, Address, Vec};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,  // -> Address
+    Config, // -> AggregatorConfig
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AggregatorConfig {
+    /// SEP-40 feeds queried for every price
+    pub feeds: Vec<Address>,
+    /// Asset the feeds quote prices in
+    pub base: Asset,
+    /// Decimals aggregated prices are scaled to, whatever the feeds use
+    pub decimals: u32,
+    /// Seconds between the records `prices` returns
+    pub resolution: u32,
+    /// Oldest feed price, in seconds, `lastprice` uses
+    pub max_age: u64,
+    /// Fewest agreeing feeds an aggregated price needs
+    pub min_sources: u32,
+    /// Furthest a feed may sit from the median, in basis points, before it
+    /// is rejected as an outlier
+    pub max_deviation_bps: u32,
+}
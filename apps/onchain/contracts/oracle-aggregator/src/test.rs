@@ -0,0 +1,139 @@
+use crate::errors::AggregatorError;
+use crate::math::{aggregate, median};
+use crate::storage::AggregatorConfig;
+use crate::{OracleAggregatorContract, OracleAggregatorContractClient};
+use lumenpulse_oracle::{Asset, PriceData};
+use mock_oracle::{MockOracleContract, MockOracleContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+fn xlm() -> Asset {
+    Asset::Other(symbol_short!("XLM"))
+}
+
+struct Setup<'a> {
+    client: OracleAggregatorContractClient<'a>,
+    admin: Address,
+    feeds: [MockOracleContractClient<'a>; 3],
+}
+
+/// Three feeds, the last one quoting with 8 decimals instead of 7
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let feed = |decimals: u32| {
+        let feed = MockOracleContractClient::new(env, &env.register(MockOracleContract, ()));
+        feed.initialize(&Asset::Other(symbol_short!("USD")), &decimals, &300);
+        feed
+    };
+    let feeds = [feed(7), feed(7), feed(8)];
+
+    let admin = Address::generate(env);
+    let contract_id = env.register(OracleAggregatorContract, ());
+    let client = OracleAggregatorContractClient::new(env, &contract_id);
+    client.initialize(
+        &admin,
+        &AggregatorConfig {
+            feeds: vec![
+                env,
+                feeds[0].address.clone(),
+                feeds[1].address.clone(),
+                feeds[2].address.clone(),
+            ],
+            base: Asset::Other(symbol_short!("USD")),
+            decimals: 7,
+            resolution: 300,
+            max_age: 600,
+            min_sources: 2,
+            max_deviation_bps: 500,
+        },
+    );
+
+    Setup {
+        client,
+        admin,
+        feeds,
+    }
+}
+
+#[test]
+fn test_median_math() {
+    assert_eq!(median(&[]), None);
+    assert_eq!(median(&[1, 5, 9]), Some(5));
+    assert_eq!(median(&[1, 4, 5, 9]), Some(4));
+    assert_eq!(median(&[i128::MAX - 1, i128::MAX]), Some(i128::MAX - 1));
+
+    // The outlier is dropped before the final median is taken
+    assert_eq!(aggregate(&[100, 102, 104, 200], 2, 500), Some(102));
+    assert_eq!(aggregate(&[100, 200], 2, 500), None);
+}
+
+#[test]
+fn test_lastprice_rejects_outliers() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    s.feeds[0].set_price_at(&xlm(), &1_000_000, &9_900);
+    s.feeds[1].set_price_at(&xlm(), &1_020_000, &9_800);
+    // Same $0.102, at 8 decimals
+    s.feeds[2].set_price_at(&xlm(), &10_200_000, &9_950);
+    assert_eq!(
+        s.client.lastprice(&xlm()),
+        Some(PriceData {
+            price: 1_020_000,
+            timestamp: 9_800,
+        })
+    );
+
+    // One feed reporting a wild price cannot move the result
+    s.feeds[2].set_price_at(&xlm(), &90_000_000, &9_950);
+    assert_eq!(s.client.lastprice(&xlm()).unwrap().price, 1_010_000);
+
+    // With one fresh feed left there is no agreement to report
+    env.ledger().set_timestamp(10_450);
+    assert_eq!(s.client.lastprice(&xlm()), None);
+}
+
+#[test]
+fn test_historical_prices() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    for (timestamp, price) in [(9_300, 1_000_000), (9_600, 1_100_000), (9_900, 1_200_000)] {
+        s.feeds[0].set_price_at(&xlm(), &price, &timestamp);
+        s.feeds[1].set_price_at(&xlm(), &price, &timestamp);
+    }
+    assert_eq!(s.client.price(&xlm(), &9_600).unwrap().price, 1_100_000);
+    assert_eq!(s.client.price(&xlm(), &9_500), None);
+
+    // Only the steps with an aggregated price are returned
+    let history = s.client.prices(&xlm(), &5).unwrap();
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap().timestamp, 9_900);
+    assert_eq!(history.get(2).unwrap().price, 1_000_000);
+
+    assert_eq!(s.client.assets(), vec![&env, xlm()]);
+}
+
+#[test]
+fn test_config_validation() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let mut config = s.client.get_config();
+    config.min_sources = 4;
+    let result = s.client.try_set_config(&s.admin, &config);
+    assert_eq!(result, Err(Ok(AggregatorError::InvalidConfig)));
+
+    config.min_sources = 1;
+    let result = s.client.try_set_config(&Address::generate(&env), &config);
+    assert_eq!(result, Err(Ok(AggregatorError::Unauthorized)));
+
+    s.client.set_config(&s.admin, &config);
+    assert_eq!(s.client.decimals(), 7);
+    assert_eq!(s.client.get_config().min_sources, 1);
+}
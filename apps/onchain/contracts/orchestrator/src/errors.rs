@@ -0,0 +1,27 @@
+use lumenpulse_access::AccessError;
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum OrchestratorError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    InvalidRelease = 4,
+    ReleasePending = 5,
+    NoPendingRelease = 6,
+    TimelockNotElapsed = 7,
+    /// A contract did not end up on the release its step names
+    ReleaseMismatch = 8,
+}
+
+impl From<AccessError> for OrchestratorError {
+    fn from(error: AccessError) -> Self {
+        match error {
+            AccessError::NotInitialized => OrchestratorError::NotInitialized,
+            AccessError::AlreadyInitialized => OrchestratorError::AlreadyInitialized,
+            _ => OrchestratorError::Unauthorized,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use soroban_sdk::{contractevent, Address, String};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseProposedEvent {
+    #[topic]
+    pub name: String,
+    pub contracts: u32,
+    pub eta: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseCanceledEvent {
+    #[topic]
+    pub name: String,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseExecutedEvent {
+    #[topic]
+    pub name: String,
+    pub contracts: u32,
+}
@@ -0,0 +1,192 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+
+pub use errors::OrchestratorError;
+pub use storage::{Migration, Release, ReleaseStep};
+
+use lumenpulse_storage::InstanceItem;
+use lumenpulse_upgradeable::UpgradeableClient;
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Val, Vec};
+use storage::DataKey;
+
+const PENDING_RELEASE: InstanceItem<DataKey, Release> = InstanceItem::new(DataKey::PendingRelease);
+const LAST_RELEASE: InstanceItem<DataKey, Release> = InstanceItem::new(DataKey::LastRelease);
+
+/// Upper bound on the contracts upgraded in one release
+const MAX_STEPS: u32 = 10;
+
+/// Upper bound on the migration calls made after one contract's upgrade
+const MAX_MIGRATIONS: u32 = 5;
+
+/// Coordinates a release across several contracts, such as streaming,
+/// token and governance moving to new WASM together. Every contract in a
+/// release names the orchestrator as its upgrade admin and goes through
+/// its own timelocked `propose_upgrade` / `execute_upgrade` path.
+///
+/// A release is proposed to all of its contracts at once. Once every
+/// upgrade delay has passed, anyone may execute it: each contract is
+/// upgraded and then migrated, in the order the release lists them, within
+/// a single invocation. If any upgrade or migration fails, the whole
+/// release is undone and stays pending, so the contracts are never left
+/// on a mix of old and new releases. Migrations cannot be reversed, so
+/// there is no coordinated rollback; a fix ships as a new release.
+#[contract]
+pub struct OrchestratorContract;
+
+#[contractimpl]
+impl OrchestratorContract {
+    /// Initialize the orchestrator with the admin (protocol governance)
+    /// that proposes releases
+    pub fn initialize(env: Env, admin: Address) -> Result<(), OrchestratorError> {
+        // Require admin authorization
+        admin.require_auth();
+
+        lumenpulse_access::init_admin(&env, &admin)?;
+
+        events::InitializedEvent { admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Propose the upgrade in each of `steps` to its contract (admin only).
+    /// Only one release may be pending at a time, and a contract that
+    /// refuses its proposal aborts the whole release. Returns when the
+    /// release can be executed.
+    pub fn propose_release(
+        env: Env,
+        admin: Address,
+        name: String,
+        steps: Vec<ReleaseStep>,
+    ) -> Result<u64, OrchestratorError> {
+        lumenpulse_access::require_admin(&env, &admin)?;
+        Self::check_steps(&name, &steps)?;
+
+        if PENDING_RELEASE.has(&env) {
+            return Err(OrchestratorError::ReleasePending);
+        }
+
+        let this = env.current_contract_address();
+        let mut eta = env.ledger().timestamp();
+        for step in steps.iter() {
+            let child = UpgradeableClient::new(&env, &step.contract);
+            eta = eta.max(child.propose_upgrade(&this, &step.wasm_hash, &step.version));
+        }
+
+        let release = Release {
+            name: name.clone(),
+            steps: steps.clone(),
+            eta,
+        };
+        PENDING_RELEASE.set(&env, &release);
+
+        events::ReleaseProposedEvent {
+            name,
+            contracts: steps.len(),
+            eta,
+        }
+        .publish(&env);
+
+        Ok(eta)
+    }
+
+    /// Withdraw the pending release from every contract in it (admin only)
+    pub fn cancel_release(env: Env, admin: Address) -> Result<(), OrchestratorError> {
+        lumenpulse_access::require_admin(&env, &admin)?;
+        let release = Self::load_pending(&env)?;
+
+        let this = env.current_contract_address();
+        for step in release.steps.iter() {
+            UpgradeableClient::new(&env, &step.contract).cancel_upgrade(&this);
+        }
+        PENDING_RELEASE.remove(&env);
+
+        events::ReleaseCanceledEvent { name: release.name }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Upgrade and migrate every contract in the pending release, in order,
+    /// once all of their delays have passed. The release was approved when
+    /// it was proposed, so this needs no further authorization.
+    pub fn execute_release(env: Env) -> Result<(), OrchestratorError> {
+        let release = Self::load_pending(&env)?;
+        if env.ledger().timestamp() < release.eta {
+            return Err(OrchestratorError::TimelockNotElapsed);
+        }
+
+        let this = env.current_contract_address();
+        for step in release.steps.iter() {
+            let child = UpgradeableClient::new(&env, &step.contract);
+            child.execute_upgrade(&this);
+            let expected = lumenpulse_upgradeable::Release {
+                wasm_hash: step.wasm_hash.clone(),
+                version: step.version,
+            };
+            if child.current_release() != Some(expected) {
+                return Err(OrchestratorError::ReleaseMismatch);
+            }
+
+            for migration in step.migrations.iter() {
+                env.invoke_contract::<Val>(&step.contract, &migration.function, migration.args);
+            }
+        }
+
+        PENDING_RELEASE.remove(&env);
+        LAST_RELEASE.set(&env, &release);
+
+        events::ReleaseExecutedEvent {
+            name: release.name,
+            contracts: release.steps.len(),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Release waiting out its contracts' delays, if any
+    pub fn pending_release(env: Env) -> Option<Release> {
+        PENDING_RELEASE.get(&env)
+    }
+
+    /// Release most recently executed, if any
+    pub fn last_release(env: Env) -> Option<Release> {
+        LAST_RELEASE.get(&env)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, OrchestratorError> {
+        Ok(lumenpulse_access::admin(&env)?)
+    }
+}
+
+impl OrchestratorContract {
+    fn check_steps(name: &String, steps: &Vec<ReleaseStep>) -> Result<(), OrchestratorError> {
+        if name.is_empty() || steps.is_empty() || steps.len() > MAX_STEPS {
+            return Err(OrchestratorError::InvalidRelease);
+        }
+        // A contract has a single pending upgrade, so it appears once
+        for (i, step) in steps.iter().enumerate() {
+            if step.migrations.len() > MAX_MIGRATIONS
+                || steps
+                    .iter()
+                    .skip(i + 1)
+                    .any(|other| other.contract == step.contract)
+            {
+                return Err(OrchestratorError::InvalidRelease);
+            }
+        }
+        Ok(())
+    }
+
+    fn load_pending(env: &Env) -> Result<Release, OrchestratorError> {
+        PENDING_RELEASE
+            .get(env)
+            .ok_or(OrchestratorError::NoPendingRelease)
+    }
+}
+
+#[cfg(test)]
+mod test;
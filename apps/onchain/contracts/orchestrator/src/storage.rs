@@ -0,0 +1,39 @@
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Val, Vec};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    PendingRelease, // -> Release
+    LastRelease,    // -> Release
+}
+
+/// A call made on a step's contract once its new WASM is installed, such
+/// as a storage migration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Migration {
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// One contract moving to new WASM as part of a release
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseStep {
+    pub contract: Address,
+    pub wasm_hash: BytesN<32>,
+    /// Release version the contract records, newer than its installed one
+    pub version: u32,
+    /// Calls made in order right after the upgrade
+    pub migrations: Vec<Migration>,
+}
+
+/// Upgrades rolled out together across several contracts
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Release {
+    pub name: String,
+    pub steps: Vec<ReleaseStep>,
+    /// Earliest time every contract's upgrade delay has passed
+    pub eta: u64,
+}
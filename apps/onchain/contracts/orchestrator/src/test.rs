@@ -0,0 +1,151 @@
+use crate::{
+    Migration, OrchestratorContract, OrchestratorContractClient, OrchestratorError, ReleaseStep,
+};
+use lumenpulse_streaming_interface::StreamError;
+use lumenpulse_upgradeable::DEFAULT_UPGRADE_DELAY;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, IntoVal, InvokeError, String, Symbol, Vec,
+};
+use streaming::{StreamingContract, StreamingContractClient};
+
+/// The factory's fixture is the streaming contract built to WASM
+const STREAMING_WASM: &[u8] = include_bytes!("../../factory/testdata/streaming.wasm");
+
+fn setup_test<'a>(
+    env: &Env,
+) -> (
+    OrchestratorContractClient<'a>,
+    Address,
+    StreamingContractClient<'a>,
+    StreamingContractClient<'a>,
+    BytesN<32>,
+) {
+    let admin = Address::generate(env);
+    let client = OrchestratorContractClient::new(env, &env.register(OrchestratorContract, ()));
+    client.initialize(&admin);
+
+    // The orchestrator administers both contracts, so it is their upgrade
+    // admin
+    let first = StreamingContractClient::new(env, &env.register(StreamingContract, ()));
+    first.initialize(&client.address);
+    let second = StreamingContractClient::new(env, &env.register(StreamingContract, ()));
+    second.initialize(&client.address);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(STREAMING_WASM);
+    (client, admin, first, second, wasm_hash)
+}
+
+fn step(contract: &Address, wasm_hash: &BytesN<32>, migrations: Vec<Migration>) -> ReleaseStep {
+    ReleaseStep {
+        contract: contract.clone(),
+        wasm_hash: wasm_hash.clone(),
+        version: 1,
+        migrations,
+    }
+}
+
+#[test]
+fn test_release_upgrades_and_migrates_in_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, first, second, wasm_hash) = setup_test(&env);
+    let token_in = Address::generate(&env);
+    let token_out = Address::generate(&env);
+    let name = String::from_str(&env, "v1.0.0");
+
+    // Enabling a pair stands in for a storage migration on the new WASM
+    let migration = Migration {
+        function: Symbol::new(&env, "set_pair_enabled"),
+        args: (&token_in, &token_out, true).into_val(&env),
+    };
+    let steps = vec![
+        &env,
+        step(&first.address, &wasm_hash, vec![&env, migration]),
+        step(&second.address, &wasm_hash, Vec::new(&env)),
+    ];
+    let result = client.try_propose_release(&Address::generate(&env), &name, &steps);
+    assert_eq!(result, Err(Ok(OrchestratorError::Unauthorized)));
+    assert_eq!(
+        client.propose_release(&admin, &name, &steps),
+        DEFAULT_UPGRADE_DELAY
+    );
+    assert!(first.pending_upgrade().is_some());
+    assert!(second.pending_upgrade().is_some());
+    let result = client.try_propose_release(&admin, &name, &steps);
+    assert_eq!(result, Err(Ok(OrchestratorError::ReleasePending)));
+
+    let result = client.try_execute_release();
+    assert_eq!(result, Err(Ok(OrchestratorError::TimelockNotElapsed)));
+
+    env.ledger().set_timestamp(DEFAULT_UPGRADE_DELAY);
+    client.execute_release();
+    assert_eq!(first.current_version(), 1);
+    assert_eq!(second.current_version(), 1);
+    assert!(first.is_pair_enabled(&token_in, &token_out));
+    assert_eq!(client.pending_release(), None);
+    assert_eq!(client.last_release().unwrap().name, name);
+    let result = client.try_execute_release();
+    assert_eq!(result, Err(Ok(OrchestratorError::NoPendingRelease)));
+}
+
+#[test]
+fn test_failed_step_aborts_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, first, second, wasm_hash) = setup_test(&env);
+    let name = String::from_str(&env, "v1.0.0");
+
+    let result = client.try_propose_release(
+        &admin,
+        &name,
+        &vec![
+            &env,
+            step(&first.address, &wasm_hash, Vec::new(&env)),
+            step(&first.address, &wasm_hash, Vec::new(&env)),
+        ],
+    );
+    assert_eq!(result, Err(Ok(OrchestratorError::InvalidRelease)));
+
+    // An oracle with no feeds is refused, failing the second contract's
+    // migration after the first contract was already upgraded
+    let broken = Migration {
+        function: Symbol::new(&env, "set_oracle"),
+        args: vec![
+            &env,
+            lumenpulse_oracle::OracleConfig {
+                feeds: Vec::new(&env),
+                max_age: 300,
+            }
+            .into_val(&env),
+        ],
+    };
+    let steps = vec![
+        &env,
+        step(&first.address, &wasm_hash, Vec::new(&env)),
+        step(&second.address, &wasm_hash, vec![&env, broken]),
+    ];
+    client.propose_release(&admin, &name, &steps);
+    env.ledger().set_timestamp(DEFAULT_UPGRADE_DELAY);
+    let result = client.try_execute_release();
+    assert_eq!(
+        result,
+        Err(Err(InvokeError::Contract(
+            StreamError::InvalidOracleConfig as u32
+        )))
+    );
+
+    // Neither contract moved, and the release can still be withdrawn
+    assert_eq!(first.current_version(), 0);
+    assert_eq!(second.current_version(), 0);
+    assert!(first.pending_upgrade().is_some());
+    assert_eq!(client.pending_release().unwrap().steps, steps);
+
+    client.cancel_release(&admin);
+    assert_eq!(first.pending_upgrade(), None);
+    assert_eq!(second.pending_upgrade(), None);
+    assert_eq!(client.pending_release(), None);
+    assert_eq!(client.last_release(), None);
+}
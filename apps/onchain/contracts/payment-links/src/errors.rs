@@ -0,0 +1,14 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PaymentLinkError {
+    LinkNotFound = 1,
+    InvalidAmount = 2,
+    InvalidExpiration = 3,
+    NotOpen = 4,
+    Expired = 5,
+    NotExpired = 6,
+    WrongLock = 7,
+}
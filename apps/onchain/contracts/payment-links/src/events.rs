@@ -0,0 +1,33 @@
+use crate::storage::ClaimLock;
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinkCreatedEvent {
+    #[topic]
+    pub link_id: u64,
+    #[topic]
+    pub sender: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub lock: ClaimLock,
+    pub expiration_ledger: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinkClaimedEvent {
+    #[topic]
+    pub link_id: u64,
+    #[topic]
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinkReclaimedEvent {
+    #[topic]
+    pub link_id: u64,
+    pub amount: i128,
+}
@@ -0,0 +1,224 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+pub use errors::PaymentLinkError;
+pub use storage::{ClaimLock, ClaimMessage, LinkStatus, PaymentLink};
+
+use soroban_sdk::{contract, contractimpl, xdr::ToXdr, Address, BytesN, Env};
+use storage::DataKey;
+
+/// One-time payment links. A sender locks funds behind either an ed25519
+/// public key, whose secret key is shared with the recipient as a link, or
+/// a known claimant address. The recipient claims before the link expires,
+/// by signing the claim for their own address with the link's key or
+/// authorizing as the claimant; after that the sender can take the funds
+/// back. The secret key never goes on-chain, and a signature only pays the
+/// address it names, so a claim seen in the mempool cannot be redirected.
+///
+/// There is no circuit breaker, as the contract has no admin to hold one.
+/// A link's funds can only go to its recipient or back to its sender, and
+/// a pause would let them expire unclaimed.
+#[contract]
+pub struct PaymentLinksContract;
+
+#[contractimpl]
+impl PaymentLinksContract {
+    /// Lock `amount` of `token` behind `lock` until `expiration_ledger`.
+    /// For key links `lock` carries the public half of a keypair generated
+    /// for this link alone. Returns the link ID.
+    pub fn create_link(
+        env: Env,
+        sender: Address,
+        token: Address,
+        amount: i128,
+        lock: ClaimLock,
+        expiration_ledger: u32,
+    ) -> Result<u64, PaymentLinkError> {
+        sender.require_auth();
+
+        if amount <= 0 {
+            return Err(PaymentLinkError::InvalidAmount);
+        }
+        if expiration_ledger <= env.ledger().sequence() {
+            return Err(PaymentLinkError::InvalidExpiration);
+        }
+
+        let link_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextLinkId)
+            .unwrap_or(0);
+        let link = PaymentLink {
+            id: link_id,
+            sender: sender.clone(),
+            token: token.clone(),
+            amount,
+            lock: lock.clone(),
+            expiration_ledger,
+            status: LinkStatus::Open,
+            recipient: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Link(link_id), &link);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextLinkId, &(link_id + 1));
+
+        token::transfer(
+            &env,
+            &token,
+            &sender,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        events::LinkCreatedEvent {
+            link_id,
+            sender,
+            token,
+            amount,
+            lock,
+            expiration_ledger,
+        }
+        .publish(&env);
+
+        Ok(link_id)
+    }
+
+    /// Claim a key link, paying `recipient`. `signature` is the link key's
+    /// signature over the XDR encoding of the matching `ClaimMessage`. It
+    /// fixes where the funds go, so a relayer may submit it for a recipient
+    /// with nothing to pay fees with; a bad signature fails the call.
+    pub fn claim_with_signature(
+        env: Env,
+        link_id: u64,
+        recipient: Address,
+        signature: BytesN<64>,
+    ) -> Result<(), PaymentLinkError> {
+        let link = Self::claimable(&env, link_id)?;
+        let ClaimLock::Key(public_key) = &link.lock else {
+            return Err(PaymentLinkError::WrongLock);
+        };
+        let message = ClaimMessage {
+            contract: env.current_contract_address(),
+            link_id,
+            recipient: recipient.clone(),
+        };
+        env.crypto()
+            .ed25519_verify(public_key, &message.to_xdr(&env), &signature);
+
+        Self::pay_out(&env, link, recipient);
+        Ok(())
+    }
+
+    /// Claim a link locked to the caller as claimant
+    pub fn claim(env: Env, link_id: u64) -> Result<(), PaymentLinkError> {
+        let link = Self::claimable(&env, link_id)?;
+        let ClaimLock::Claimant(claimant) = link.lock.clone() else {
+            return Err(PaymentLinkError::WrongLock);
+        };
+
+        // Require claimant authorization
+        claimant.require_auth();
+
+        Self::pay_out(&env, link, claimant);
+        Ok(())
+    }
+
+    /// Return an expired, unclaimed link's funds to its sender (sender only)
+    pub fn reclaim(env: Env, link_id: u64) -> Result<(), PaymentLinkError> {
+        let mut link = Self::get_link(env.clone(), link_id)?;
+
+        // Require sender authorization
+        link.sender.require_auth();
+
+        if link.status != LinkStatus::Open {
+            return Err(PaymentLinkError::NotOpen);
+        }
+        if env.ledger().sequence() <= link.expiration_ledger {
+            return Err(PaymentLinkError::NotExpired);
+        }
+
+        link.status = LinkStatus::Reclaimed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Link(link_id), &link);
+
+        token::transfer(
+            &env,
+            &link.token,
+            &env.current_contract_address(),
+            &link.sender,
+            &link.amount,
+        );
+
+        events::LinkReclaimedEvent {
+            link_id,
+            amount: link.amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get a link by ID
+    pub fn get_link(env: Env, link_id: u64) -> Result<PaymentLink, PaymentLinkError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Link(link_id))
+            .ok_or(PaymentLinkError::LinkNotFound)
+    }
+
+    /// Number of links created so far
+    pub fn link_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::NextLinkId)
+            .unwrap_or(0)
+    }
+}
+
+impl PaymentLinksContract {
+    /// Load a link that is still open and unexpired
+    fn claimable(env: &Env, link_id: u64) -> Result<PaymentLink, PaymentLinkError> {
+        let link = Self::get_link(env.clone(), link_id)?;
+        if link.status != LinkStatus::Open {
+            return Err(PaymentLinkError::NotOpen);
+        }
+        if env.ledger().sequence() > link.expiration_ledger {
+            return Err(PaymentLinkError::Expired);
+        }
+        Ok(link)
+    }
+
+    fn pay_out(env: &Env, mut link: PaymentLink, recipient: Address) {
+        link.status = LinkStatus::Claimed;
+        link.recipient = Some(recipient.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Link(link.id), &link);
+
+        token::transfer(
+            env,
+            &link.token,
+            &env.current_contract_address(),
+            &recipient,
+            &link.amount,
+        );
+
+        events::LinkClaimedEvent {
+            link_id: link.id,
+            recipient,
+            amount: link.amount,
+        }
+        .publish(env);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,53 @@
+use soroban_sdk::{contracttype, Address, BytesN};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextLinkId, // -> u64
+    Link(u64),  // link_id -> PaymentLink
+}
+
+/// What a recipient must present to claim a link
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClaimLock {
+    /// Whoever holds the secret key of this ed25519 public key
+    Key(BytesN<32>),
+    /// Only this address, by authorizing the claim
+    Claimant(Address),
+}
+
+/// Payload a link's key signs to claim it. Naming the recipient keeps the
+/// signature from paying anyone else, and naming the contract keeps it
+/// from being replayed against another deployment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimMessage {
+    pub contract: Address,
+    pub link_id: u64,
+    pub recipient: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LinkStatus {
+    Open,
+    Claimed,
+    /// Returned to the sender after expiring unclaimed
+    Reclaimed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentLink {
+    pub id: u64,
+    pub sender: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub lock: ClaimLock,
+    /// Last ledger the link can be claimed in
+    pub expiration_ledger: u32,
+    pub status: LinkStatus,
+    /// Address the funds were paid to, once claimed
+    pub recipient: Option<Address>,
+}
@@ -0,0 +1,143 @@
+extern crate std;
+
+use crate::errors::PaymentLinkError;
+use crate::storage::{ClaimLock, ClaimMessage, LinkStatus};
+use crate::{PaymentLinksContract, PaymentLinksContractClient};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    xdr::ToXdr,
+    Address, BytesN, Env,
+};
+
+struct Setup<'a> {
+    env: &'a Env,
+    client: PaymentLinksContractClient<'a>,
+    sender: Address,
+    token: TokenClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+
+    let sender = Address::generate(env);
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &sac.address()).mint(&sender, &1_000);
+    let client = PaymentLinksContractClient::new(env, &env.register(PaymentLinksContract, ()));
+
+    Setup {
+        env,
+        client,
+        sender,
+        token: TokenClient::new(env, &sac.address()),
+    }
+}
+
+/// Sign the claim of `link_id` for `recipient` with the link's key
+fn sign_claim(s: &Setup, key: &SigningKey, link_id: u64, recipient: &Address) -> BytesN<64> {
+    let message = ClaimMessage {
+        contract: s.client.address.clone(),
+        link_id,
+        recipient: recipient.clone(),
+    };
+    let payload = message.to_xdr(s.env);
+    let mut buf = std::vec![0u8; payload.len() as usize];
+    payload.copy_into_slice(&mut buf);
+    BytesN::from_array(s.env, &key.sign(&buf).to_bytes())
+}
+
+#[test]
+fn test_claim_with_signature() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let recipient = Address::generate(&env);
+    let key = SigningKey::from_bytes(&[7; 32]);
+    let public_key = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+
+    let id = s.client.create_link(
+        &s.sender,
+        &s.token.address,
+        &400,
+        &ClaimLock::Key(public_key),
+        &200,
+    );
+    assert_eq!(s.token.balance(&s.client.address), 400);
+
+    // A key that is not the link's cannot claim it
+    let wrong = SigningKey::from_bytes(&[8; 32]);
+    let forged = sign_claim(&s, &wrong, id, &recipient);
+    assert!(s
+        .client
+        .try_claim_with_signature(&id, &recipient, &forged)
+        .is_err());
+    let result = s.client.try_claim(&id);
+    assert_eq!(result, Err(Ok(PaymentLinkError::WrongLock)));
+
+    // Someone who copies the recipient's signature cannot redirect it
+    let signature = sign_claim(&s, &key, id, &recipient);
+    let thief = Address::generate(&env);
+    assert!(s
+        .client
+        .try_claim_with_signature(&id, &thief, &signature)
+        .is_err());
+
+    s.client.claim_with_signature(&id, &recipient, &signature);
+    assert_eq!(s.token.balance(&recipient), 400);
+    assert_eq!(s.token.balance(&thief), 0);
+    let link = s.client.get_link(&id);
+    assert_eq!(link.status, LinkStatus::Claimed);
+    assert_eq!(link.recipient, Some(recipient.clone()));
+
+    // The same signature cannot claim twice
+    let result = s
+        .client
+        .try_claim_with_signature(&id, &recipient, &signature);
+    assert_eq!(result, Err(Ok(PaymentLinkError::NotOpen)));
+}
+
+#[test]
+fn test_claimant_link_expires_and_reclaims() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let claimant = Address::generate(&env);
+
+    let claimed = s.client.create_link(
+        &s.sender,
+        &s.token.address,
+        &300,
+        &ClaimLock::Claimant(claimant.clone()),
+        &200,
+    );
+    let expiring = s.client.create_link(
+        &s.sender,
+        &s.token.address,
+        &500,
+        &ClaimLock::Claimant(claimant.clone()),
+        &200,
+    );
+    s.client.claim(&claimed);
+    assert_eq!(s.token.balance(&claimant), 300);
+
+    let result = s.client.try_reclaim(&expiring);
+    assert_eq!(result, Err(Ok(PaymentLinkError::NotExpired)));
+
+    env.ledger().set_sequence_number(201);
+    let result = s.client.try_claim(&expiring);
+    assert_eq!(result, Err(Ok(PaymentLinkError::Expired)));
+    s.client.reclaim(&expiring);
+    assert_eq!(s.token.balance(&s.sender), 700);
+    assert_eq!(s.client.get_link(&expiring).status, LinkStatus::Reclaimed);
+    let result = s.client.try_reclaim(&claimed);
+    assert_eq!(result, Err(Ok(PaymentLinkError::NotOpen)));
+
+    let result = s.client.try_create_link(
+        &s.sender,
+        &s.token.address,
+        &100,
+        &ClaimLock::Claimant(claimant),
+        &201,
+    );
+    assert_eq!(result, Err(Ok(PaymentLinkError::InvalidExpiration)));
+}
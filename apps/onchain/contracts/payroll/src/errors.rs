@@ -0,0 +1,20 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PayrollError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    InvalidAmount = 4,
+    InvalidPeriod = 5,
+    EmployeeExists = 6,
+    EmployeeNotFound = 7,
+    RosterFull = 8,
+    RunInProgress = 9,
+    RunNotDue = 10,
+    InsufficientFunds = 11,
+    StreamFailed = 12,
+    TransferFailed = 13,
+}
@@ -0,0 +1,53 @@
+use crate::storage::PayMode;
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmployeeSetEvent {
+    #[topic]
+    pub employee: Address,
+    pub salary: i128,
+    pub mode: PayMode,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmployeeRemovedEvent {
+    #[topic]
+    pub employee: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SalaryPaidEvent {
+    #[topic]
+    pub run_id: u32,
+    #[topic]
+    pub employee: Address,
+    pub amount: i128,
+    pub stream_id: Option<u64>,
+}
+
+/// An employee the run skipped because paying them failed
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SalaryFailedEvent {
+    #[topic]
+    pub run_id: u32,
+    #[topic]
+    pub employee: Address,
+    pub amount: i128,
+    /// `PayrollError` code of the failure
+    pub error: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RunCompletedEvent {
+    #[topic]
+    pub run_id: u32,
+    pub employees: u32,
+    pub total_paid: i128,
+    /// Employees skipped because paying them failed
+    pub failed: u32,
+}
@@ -0,0 +1,351 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::PayrollError;
+use lumenpulse_pausable::Pausable;
+use lumenpulse_streaming_interface::{StreamRate, StreamingClient};
+use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+use storage::{
+    DataKey, Employee, PayMode, PayrollConfig, PayrollRun, MAX_EMPLOYEES, MAX_PAYROLL_BATCH,
+};
+
+/// Pays an organisation's roster every pay period out of the contract's
+/// balance. Each employee is paid by transfer or through a salary stream
+/// that every run funds for another period. A run pays the roster in
+/// bounded batches, so `run_payroll` is called until the run completes,
+/// and each run keeps a report of what it paid. An employee who cannot be
+/// paid, because the balance has run short or the payment itself fails, is
+/// skipped and listed in the report, so one bad payee never holds up the
+/// rest of the roster. The roster cannot change while a run is in
+/// progress.
+#[contract]
+pub struct PayrollContract;
+
+#[contractimpl]
+impl PayrollContract {
+    /// Initialize payroll with the admin that manages the roster, the
+    /// token and streaming contract salaries are paid with, and the pay
+    /// period
+    pub fn initialize(env: Env, admin: Address, config: PayrollConfig) -> Result<(), PayrollError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(PayrollError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        if config.pay_period == 0 {
+            return Err(PayrollError::InvalidPeriod);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().set(&DataKey::NextRunId, &0u32);
+
+        Ok(())
+    }
+
+    /// Add `employee` to the roster, or change their salary or pay mode
+    /// (admin only). A streamed employee whose terms change is paid on a
+    /// new stream from the next run; the old one runs out what it holds.
+    pub fn set_employee(
+        env: Env,
+        admin: Address,
+        employee: Address,
+        salary: i128,
+        mode: PayMode,
+    ) -> Result<(), PayrollError> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_no_run(&env)?;
+
+        if salary <= 0 {
+            return Err(PayrollError::InvalidAmount);
+        }
+
+        let key = DataKey::Employee(employee.clone());
+        let stream_id = match env.storage().persistent().get::<_, Employee>(&key) {
+            Some(current) if current.salary == salary && current.mode == mode => current.stream_id,
+            Some(_) => None,
+            None => {
+                let mut roster = Self::roster(env.clone());
+                if roster.len() >= MAX_EMPLOYEES {
+                    return Err(PayrollError::RosterFull);
+                }
+                roster.push_back(employee.clone());
+                env.storage().persistent().set(&DataKey::Roster, &roster);
+                None
+            }
+        };
+        env.storage().persistent().set(
+            &key,
+            &Employee {
+                address: employee.clone(),
+                salary,
+                mode,
+                stream_id,
+            },
+        );
+
+        events::EmployeeSetEvent {
+            employee,
+            salary,
+            mode,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Take `employee` off the roster (admin only). A salary stream keeps
+    /// paying out what it was funded with.
+    pub fn remove_employee(
+        env: Env,
+        admin: Address,
+        employee: Address,
+    ) -> Result<(), PayrollError> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_no_run(&env)?;
+
+        let mut roster = Self::roster(env.clone());
+        let index = roster
+            .first_index_of(&employee)
+            .ok_or(PayrollError::EmployeeNotFound)?;
+        roster.remove(index);
+        env.storage().persistent().set(&DataKey::Roster, &roster);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Employee(employee.clone()));
+
+        events::EmployeeRemovedEvent { employee }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Pay the next batch of the current run, starting a new run if the
+    /// last one has completed and a pay period has passed since it began.
+    /// Employees who cannot be paid are skipped and added to the report's
+    /// `failed` list.
+    /// Anyone may call this, as only the configured salaries are ever paid.
+    /// Returns the run's report so far.
+    pub fn run_payroll(env: Env) -> Result<PayrollRun, PayrollError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let config = Self::get_config(env.clone())?;
+        let now = env.ledger().timestamp();
+
+        let mut run = match Self::latest_run(env.clone()) {
+            Some(run) if run.completed_at.is_none() => run,
+            latest => {
+                if latest.is_some_and(|run| now < run.started_at + config.pay_period) {
+                    return Err(PayrollError::RunNotDue);
+                }
+                Self::start_run(&env, now)
+            }
+        };
+
+        let roster = Self::roster(env.clone());
+        let end = run.employees.min(run.processed + MAX_PAYROLL_BATCH);
+        for index in run.processed..end {
+            let address = roster.get(index).ok_or(PayrollError::EmployeeNotFound)?;
+            let mut employee = Self::get_employee(env.clone(), address)?;
+            if let Err(error) = Self::pay(&env, &config, &mut employee, &mut run) {
+                run.failed.push_back(employee.address.clone());
+                events::SalaryFailedEvent {
+                    run_id: run.id,
+                    employee: employee.address,
+                    amount: employee.salary,
+                    error: error as u32,
+                }
+                .publish(&env);
+            }
+            run.processed += 1;
+        }
+
+        if run.processed == run.employees {
+            run.completed_at = Some(now);
+            events::RunCompletedEvent {
+                run_id: run.id,
+                employees: run.employees,
+                total_paid: run.total_paid,
+                failed: run.failed.len(),
+            }
+            .publish(&env);
+        }
+        env.storage().persistent().set(&DataKey::Run(run.id), &run);
+
+        Ok(run)
+    }
+
+    /// Get an employee's pay terms
+    pub fn get_employee(env: Env, employee: Address) -> Result<Employee, PayrollError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Employee(employee))
+            .ok_or(PayrollError::EmployeeNotFound)
+    }
+
+    /// Employees in the order runs pay them
+    pub fn roster(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Roster)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get a run's report by ID
+    pub fn get_run(env: Env, run_id: u32) -> Option<PayrollRun> {
+        env.storage().persistent().get(&DataKey::Run(run_id))
+    }
+
+    /// Report of the most recent run, if any
+    pub fn latest_run(env: Env) -> Option<PayrollRun> {
+        let next_run_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRunId)
+            .unwrap_or(0);
+        Self::get_run(env, next_run_id.checked_sub(1)?)
+    }
+
+    /// Get the payroll configuration
+    pub fn get_config(env: Env) -> Result<PayrollConfig, PayrollError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(PayrollError::NotInitialized)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, PayrollError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(PayrollError::NotInitialized)
+    }
+}
+
+impl PayrollContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), PayrollError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(PayrollError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn require_no_run(env: &Env) -> Result<(), PayrollError> {
+        if Self::latest_run(env.clone()).is_some_and(|run| run.completed_at.is_none()) {
+            return Err(PayrollError::RunInProgress);
+        }
+        Ok(())
+    }
+
+    fn start_run(env: &Env, now: u64) -> PayrollRun {
+        let run_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRunId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextRunId, &(run_id + 1));
+        PayrollRun {
+            id: run_id,
+            started_at: now,
+            employees: Self::roster(env.clone()).len(),
+            processed: 0,
+            paid: 0,
+            total_paid: 0,
+            transfers: 0,
+            streams_created: 0,
+            streams_extended: 0,
+            failed: Vec::new(env),
+            completed_at: None,
+        }
+    }
+
+    /// Pay one employee their salary for the run. Nothing is paid or
+    /// counted if it fails.
+    fn pay(
+        env: &Env,
+        config: &PayrollConfig,
+        employee: &mut Employee,
+        run: &mut PayrollRun,
+    ) -> Result<(), PayrollError> {
+        let this = env.current_contract_address();
+        let salary = employee.salary;
+        if token::balance(env, &config.token, &this) < salary {
+            return Err(PayrollError::InsufficientFunds);
+        }
+
+        match employee.mode {
+            PayMode::Transfer => {
+                token::transfer(env, &config.token, &this, &employee.address, &salary)
+                    .map_err(|_| PayrollError::TransferFailed)?;
+                run.transfers += 1;
+            }
+            PayMode::Stream => {
+                // The streaming contract pulls the salary from us in a call
+                // below ours, which needs our explicit authorization
+                lumenpulse_sac::authorize_transfer(env, &config.token, &config.streaming, salary);
+                let client = StreamingClient::new(env, &config.streaming);
+                match employee.stream_id {
+                    Some(stream_id) => match client.try_top_up(&stream_id, &salary) {
+                        Ok(Ok(())) => run.streams_extended += 1,
+                        _ => return Err(PayrollError::StreamFailed),
+                    },
+                    None => {
+                        let rate = StreamRate {
+                            amount: salary,
+                            period: config.pay_period,
+                        };
+                        let stream_id = match client.try_create_rate_stream(
+                            &this,
+                            &employee.address,
+                            &config.token,
+                            &rate,
+                            &env.ledger().timestamp(),
+                            &salary,
+                            &None,
+                        ) {
+                            Ok(Ok(stream_id)) => stream_id,
+                            _ => return Err(PayrollError::StreamFailed),
+                        };
+                        employee.stream_id = Some(stream_id);
+                        env.storage()
+                            .persistent()
+                            .set(&DataKey::Employee(employee.address.clone()), employee);
+                        run.streams_created += 1;
+                    }
+                }
+            }
+        }
+        run.paid += 1;
+        run.total_paid += salary;
+
+        events::SalaryPaidEvent {
+            run_id: run.id,
+            employee: employee.address.clone(),
+            amount: salary,
+            stream_id: employee.stream_id,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+}
+
+impl Pausable for PayrollContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(PayrollContract);
+
+#[cfg(test)]
+mod test;
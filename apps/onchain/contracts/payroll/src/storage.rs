@@ -0,0 +1,71 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+/// Most employees on one roster
+pub const MAX_EMPLOYEES: u32 = 200;
+
+/// Most employees paid by one `run_payroll` call
+pub const MAX_PAYROLL_BATCH: u32 = 10;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,             // -> Address
+    Config,            // -> PayrollConfig
+    Roster,            // -> Vec<Address>
+    Employee(Address), // -> Employee
+    NextRunId,         // -> u32
+    Run(u32),          // run_id -> PayrollRun
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayrollConfig {
+    /// Token salaries are paid in, out of the contract's balance
+    pub token: Address,
+    /// Streaming contract stream-paid salaries run on
+    pub streaming: Address,
+    /// Seconds between runs; salaries are per pay period
+    pub pay_period: u64,
+}
+
+/// How an employee receives their salary
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PayMode {
+    /// Paid in full by transfer on each run
+    Transfer,
+    /// Streamed over the pay period; each run funds the stream for another
+    /// period
+    Stream,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Employee {
+    pub address: Address,
+    /// Paid each pay period
+    pub salary: i128,
+    pub mode: PayMode,
+    /// Stream the salary runs on, once the first streamed run has paid
+    pub stream_id: Option<u64>,
+}
+
+/// Report of one payroll run, filled in batch by batch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayrollRun {
+    pub id: u32,
+    pub started_at: u64,
+    /// Employees on the roster when the run started
+    pub employees: u32,
+    /// Employees handled so far, paid or not, in roster order
+    pub processed: u32,
+    pub paid: u32,
+    pub total_paid: i128,
+    pub transfers: u32,
+    pub streams_created: u32,
+    pub streams_extended: u32,
+    /// Employees skipped because paying them failed, in roster order
+    pub failed: Vec<Address>,
+    pub completed_at: Option<u64>,
+}
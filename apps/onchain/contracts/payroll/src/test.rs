@@ -0,0 +1,142 @@
+use crate::errors::PayrollError;
+use crate::storage::{PayMode, PayrollConfig};
+use crate::{PayrollContract, PayrollContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, IssuerFlags, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+use streaming::{StreamingContract, StreamingContractClient};
+
+const PERIOD: u64 = 30 * 24 * 60 * 60;
+
+struct Setup<'a> {
+    client: PayrollContractClient<'a>,
+    streaming: StreamingContractClient<'a>,
+    admin: Address,
+    token: TokenClient<'a>,
+    token_admin: StellarAssetClient<'a>,
+}
+
+impl Setup<'_> {
+    fn hire(&self, env: &Env, salary: i128, mode: PayMode) -> Address {
+        let employee = Address::generate(env);
+        self.client
+            .set_employee(&self.admin, &employee, &salary, &mode);
+        employee
+    }
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let streaming = StreamingContractClient::new(env, &env.register(StreamingContract, ()));
+    streaming.initialize(&admin);
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    // Revocable, so a test can freeze a payee
+    sac.issuer().set_flag(IssuerFlags::RevocableFlag);
+
+    let client = PayrollContractClient::new(env, &env.register(PayrollContract, ()));
+    let config = PayrollConfig {
+        token: sac.address(),
+        streaming: streaming.address.clone(),
+        pay_period: PERIOD,
+    };
+    client.initialize(&admin, &config);
+    StellarAssetClient::new(env, &sac.address()).mint(&client.address, &100_000);
+
+    Setup {
+        client,
+        streaming,
+        admin,
+        token: TokenClient::new(env, &sac.address()),
+        token_admin: StellarAssetClient::new(env, &sac.address()),
+    }
+}
+
+#[test]
+fn test_run_pays_transfers_and_streams() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let contractor = s.hire(&env, 1_000, PayMode::Transfer);
+    let engineer = s.hire(&env, 3_000, PayMode::Stream);
+
+    let run = s.client.run_payroll();
+    assert_eq!(run.completed_at, Some(0));
+    assert_eq!((run.paid, run.total_paid), (2, 4_000));
+    assert_eq!((run.transfers, run.streams_created), (1, 1));
+    assert_eq!(s.token.balance(&contractor), 1_000);
+
+    let stream_id = s.client.get_employee(&engineer).stream_id.unwrap();
+    env.ledger().set_timestamp(PERIOD / 3);
+    s.streaming.withdraw(&stream_id);
+    assert_eq!(s.token.balance(&engineer), 1_000);
+
+    let result = s.client.try_run_payroll();
+    assert_eq!(result, Err(Ok(PayrollError::RunNotDue)));
+
+    // The next run funds the same stream for another period
+    env.ledger().set_timestamp(PERIOD);
+    let run = s.client.run_payroll();
+    assert_eq!((run.id, run.streams_extended), (1, 1));
+    assert_eq!(s.streaming.get_stream(&stream_id).deposit, 6_000);
+    assert_eq!(s.client.get_run(&0).unwrap().total_paid, 4_000);
+    assert_eq!(s.token.balance(&s.client.address), 100_000 - 8_000);
+}
+
+#[test]
+fn test_run_pays_in_batches() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let employees: [Address; 12] = core::array::from_fn(|_| s.hire(&env, 100, PayMode::Transfer));
+
+    let run = s.client.run_payroll();
+    assert_eq!((run.employees, run.paid, run.completed_at), (12, 10, None));
+
+    // The roster is frozen until the run completes
+    let result = s.client.try_remove_employee(&s.admin, &employees[0]);
+    assert_eq!(result, Err(Ok(PayrollError::RunInProgress)));
+
+    let run = s.client.run_payroll();
+    assert_eq!((run.id, run.paid), (0, 12));
+    assert!(run.completed_at.is_some());
+    for employee in employees.iter() {
+        assert_eq!(s.token.balance(employee), 100);
+    }
+
+    s.client.remove_employee(&s.admin, &employees[0]);
+    assert_eq!(s.client.roster().len(), 11);
+}
+
+#[test]
+fn test_run_skips_payees_it_cannot_pay() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let first = s.hire(&env, 60_000, PayMode::Transfer);
+    let second = s.hire(&env, 60_000, PayMode::Stream);
+    let blocked = s.hire(&env, 1_000, PayMode::Transfer);
+    let last = s.hire(&env, 2_000, PayMode::Transfer);
+    s.token_admin.set_authorized(&blocked, &false);
+
+    // The balance runs short for the second and the token refuses the
+    // third, but everyone else is still paid and the run completes
+    let run = s.client.run_payroll();
+    assert_eq!((run.processed, run.paid), (4, 2));
+    assert_eq!(run.total_paid, 62_000);
+    assert_eq!(run.failed, vec![&env, second.clone(), blocked.clone()]);
+    assert!(run.completed_at.is_some());
+    assert_eq!(s.token.balance(&first), 60_000);
+    assert_eq!(s.token.balance(&last), 2_000);
+    assert_eq!(s.client.get_employee(&second).stream_id, None);
+
+    // The roster is free to change again
+    s.client
+        .set_employee(&s.admin, &second, &30_000, &PayMode::Stream);
+    s.client.remove_employee(&s.admin, &blocked);
+
+    let result = s
+        .client
+        .try_set_employee(&s.admin, &second, &0, &PayMode::Stream);
+    assert_eq!(result, Err(Ok(PayrollError::InvalidAmount)));
+}
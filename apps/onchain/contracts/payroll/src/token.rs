@@ -0,0 +1,20 @@
+use lumenpulse_sac::TransferError;
+use soroban_sdk::{Address, Env};
+
+/// Transfer tokens from one address to another. Works the same for native
+/// XLM and classic-asset SACs, and returns an error unless exactly `amount`
+/// moved, so one payee the token refuses does not fail the whole run.
+pub fn transfer(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+    amount: &i128,
+) -> Result<(), TransferError> {
+    lumenpulse_sac::transfer_exact(env, token, from, to, *amount)
+}
+
+/// Get the balance of an address for a given token
+pub fn balance(env: &Env, token: &Address, address: &Address) -> i128 {
+    lumenpulse_sac::balance(env, token, address)
+}
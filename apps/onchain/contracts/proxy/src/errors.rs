@@ -0,0 +1,15 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ProxyError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    RouteNotFound = 4,
+    TooManyRoutes = 5,
+    NoPendingChange = 6,
+    ChangePending = 7,
+    TimelockNotElapsed = 8,
+}
@@ -0,0 +1,41 @@
+use soroban_sdk::{contractevent, Address, Symbol, Vec};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub admin: Address,
+    pub implementation: Address,
+    pub delay: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeQueuedEvent {
+    #[topic]
+    pub implementation: Address,
+    pub routes: Vec<Symbol>,
+    pub eta: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeCanceledEvent {
+    #[topic]
+    pub implementation: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImplementationChangedEvent {
+    #[topic]
+    pub implementation: Address,
+    pub previous: Address,
+    pub routes: Vec<Symbol>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminChangedEvent {
+    pub admin: Address,
+    pub new_admin: Address,
+}
@@ -0,0 +1,226 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+
+use errors::ProxyError;
+use soroban_sdk::{contract, contractimpl, Address, Env, Symbol, Val, Vec};
+use storage::{DataKey, PendingChange};
+
+/// Upper bound on the entrypoints one proxy forwards
+const MAX_ROUTES: u32 = 50;
+
+/// Stable public address for a product. Calls to a fixed set of
+/// entrypoints are forwarded to the current implementation contract, so the
+/// product can move to a fully redeployed contract without integrators
+/// changing the address they call.
+///
+/// Switching implementation or routes goes through a timelock, giving
+/// users time to react to an upcoming change. The implementation sees the
+/// proxy as its direct caller; users authorize the forwarded call as a
+/// sub-invocation of `forward`.
+#[contract]
+pub struct ProxyContract;
+
+#[contractimpl]
+impl ProxyContract {
+    /// Initialize the proxy. `delay` is the number of seconds every later
+    /// change of implementation waits before it can be applied, and cannot
+    /// be changed afterwards.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        implementation: Address,
+        routes: Vec<Symbol>,
+        delay: u64,
+    ) -> Result<(), ProxyError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ProxyError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        Self::check_routes(&routes)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Delay, &delay);
+        env.storage()
+            .instance()
+            .set(&DataKey::Implementation, &implementation);
+        env.storage().instance().set(&DataKey::Routes, &routes);
+
+        events::InitializedEvent {
+            admin,
+            implementation,
+            delay,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Call `function` on the current implementation with `args` and
+    /// return its result. Only routed entrypoints are forwarded.
+    pub fn forward(env: Env, function: Symbol, args: Vec<Val>) -> Result<Val, ProxyError> {
+        if !Self::get_routes(env.clone())?.contains(&function) {
+            return Err(ProxyError::RouteNotFound);
+        }
+        let implementation = Self::get_implementation(env.clone())?;
+        Ok(env.invoke_contract::<Val>(&implementation, &function, args))
+    }
+
+    /// Queue a switch to `implementation` forwarding `routes` (admin only).
+    /// Only one change may be pending at a time. Returns when it can be
+    /// applied.
+    pub fn queue_change(
+        env: Env,
+        admin: Address,
+        implementation: Address,
+        routes: Vec<Symbol>,
+    ) -> Result<u64, ProxyError> {
+        Self::require_admin(&env, &admin)?;
+        Self::check_routes(&routes)?;
+
+        if env.storage().instance().has(&DataKey::PendingChange) {
+            return Err(ProxyError::ChangePending);
+        }
+
+        let eta = env
+            .ledger()
+            .timestamp()
+            .saturating_add(Self::delay(env.clone()));
+        let change = PendingChange {
+            implementation: implementation.clone(),
+            routes: routes.clone(),
+            eta,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingChange, &change);
+
+        events::ChangeQueuedEvent {
+            implementation,
+            routes,
+            eta,
+        }
+        .publish(&env);
+
+        Ok(eta)
+    }
+
+    /// Drop the pending change (admin only)
+    pub fn cancel_change(env: Env, admin: Address) -> Result<(), ProxyError> {
+        Self::require_admin(&env, &admin)?;
+        let change = Self::load_pending(&env)?;
+
+        env.storage().instance().remove(&DataKey::PendingChange);
+
+        events::ChangeCanceledEvent {
+            implementation: change.implementation,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Apply the pending change once its timelock has elapsed. The admin
+    /// approved it when queuing, so this needs no further authorization.
+    pub fn apply_change(env: Env) -> Result<(), ProxyError> {
+        let change = Self::load_pending(&env)?;
+        if env.ledger().timestamp() < change.eta {
+            return Err(ProxyError::TimelockNotElapsed);
+        }
+
+        let previous = Self::get_implementation(env.clone())?;
+        env.storage()
+            .instance()
+            .set(&DataKey::Implementation, &change.implementation);
+        env.storage()
+            .instance()
+            .set(&DataKey::Routes, &change.routes);
+        env.storage().instance().remove(&DataKey::PendingChange);
+
+        events::ImplementationChangedEvent {
+            implementation: change.implementation,
+            previous,
+            routes: change.routes,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Hand the proxy to a new admin (admin only)
+    pub fn set_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), ProxyError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+
+        events::AdminChangedEvent { admin, new_admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Implementation calls are currently forwarded to
+    pub fn get_implementation(env: Env) -> Result<Address, ProxyError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Implementation)
+            .ok_or(ProxyError::NotInitialized)
+    }
+
+    /// Entrypoints currently forwarded
+    pub fn get_routes(env: Env) -> Result<Vec<Symbol>, ProxyError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Routes)
+            .ok_or(ProxyError::NotInitialized)
+    }
+
+    /// Change waiting out its timelock, if any
+    pub fn get_pending_change(env: Env) -> Option<PendingChange> {
+        env.storage().instance().get(&DataKey::PendingChange)
+    }
+
+    /// Seconds a queued change waits before it can be applied
+    pub fn delay(env: Env) -> u64 {
+        env.storage().instance().get(&DataKey::Delay).unwrap_or(0)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, ProxyError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ProxyError::NotInitialized)
+    }
+}
+
+impl ProxyContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), ProxyError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(ProxyError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn check_routes(routes: &Vec<Symbol>) -> Result<(), ProxyError> {
+        if routes.len() > MAX_ROUTES {
+            return Err(ProxyError::TooManyRoutes);
+        }
+        Ok(())
+    }
+
+    fn load_pending(env: &Env) -> Result<PendingChange, ProxyError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::PendingChange)
+            .ok_or(ProxyError::NoPendingChange)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,21 @@
+use soroban_sdk::{contracttype, Address, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,          // -> Address
+    Delay,          // -> u64
+    Implementation, // -> Address
+    Routes,         // -> Vec<Symbol>
+    PendingChange,  // -> PendingChange
+}
+
+/// A queued switch to a new implementation and set of routes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingChange {
+    pub implementation: Address,
+    pub routes: Vec<Symbol>,
+    /// Earliest time the change can be applied
+    pub eta: u64,
+}
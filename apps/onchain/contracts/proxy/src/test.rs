@@ -0,0 +1,167 @@
+use crate::errors::ProxyError;
+use crate::{ProxyContract, ProxyContractClient};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+};
+
+const DELAY: u64 = 2 * 86_400;
+
+/// First deployment of the product behind the proxy
+#[contract]
+struct ImplV1;
+
+#[contractimpl]
+impl ImplV1 {
+    pub fn version() -> u32 {
+        1
+    }
+
+    pub fn set_value(env: Env, user: Address, value: u32) {
+        user.require_auth();
+        env.storage().instance().set(&user, &value);
+    }
+
+    pub fn value(env: Env, user: Address) -> u32 {
+        env.storage().instance().get(&user).unwrap_or(0)
+    }
+}
+
+/// Full redeployment of the product, with fresh storage
+#[contract]
+struct ImplV2;
+
+#[contractimpl]
+impl ImplV2 {
+    pub fn version() -> u32 {
+        2
+    }
+}
+
+struct Setup<'a> {
+    client: ProxyContractClient<'a>,
+    admin: Address,
+    v1: Address,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let v1 = env.register(ImplV1, ());
+    let contract_id = env.register(ProxyContract, ());
+    let client = ProxyContractClient::new(env, &contract_id);
+    client.initialize(
+        &admin,
+        &v1,
+        &vec![
+            env,
+            symbol_short!("version"),
+            symbol_short!("set_value"),
+            symbol_short!("value"),
+        ],
+        &DELAY,
+    );
+
+    Setup { client, admin, v1 }
+}
+
+fn call_u32(env: &Env, client: &ProxyContractClient, function: Symbol, args: Vec<Val>) -> u32 {
+    u32::try_from_val(env, &client.forward(&function, &args)).unwrap()
+}
+
+#[test]
+fn test_forwards_routed_entrypoints() {
+    let env = Env::default();
+    let Setup { client, v1, .. } = setup_test(&env);
+    let user = Address::generate(&env);
+    // The user's auth is a sub-invocation below the proxy's `forward`
+    env.mock_all_auths_allowing_non_root_auth();
+
+    assert_eq!(
+        call_u32(&env, &client, symbol_short!("version"), vec![&env]),
+        1
+    );
+    client.forward(
+        &symbol_short!("set_value"),
+        &vec![&env, user.into_val(&env), 7u32.into_val(&env)],
+    );
+    assert_eq!(ImplV1Client::new(&env, &v1).value(&user), 7);
+    assert_eq!(
+        call_u32(
+            &env,
+            &client,
+            symbol_short!("value"),
+            vec![&env, user.into_val(&env)]
+        ),
+        7
+    );
+
+    let result = client.try_forward(&symbol_short!("init"), &vec![&env]);
+    assert_eq!(result.err(), Some(Ok(ProxyError::RouteNotFound)));
+}
+
+#[test]
+fn test_timelocked_implementation_change() {
+    let env = Env::default();
+    let Setup { client, admin, v1 } = setup_test(&env);
+    let v2 = env.register(ImplV2, ());
+    let routes = vec![&env, symbol_short!("version")];
+
+    let result = client.try_queue_change(&v1, &v2, &routes);
+    assert_eq!(result, Err(Ok(ProxyError::Unauthorized)));
+
+    let eta = client.queue_change(&admin, &v2, &routes);
+    assert_eq!(eta, DELAY);
+    assert_eq!(client.get_pending_change().unwrap().implementation, v2);
+    let result = client.try_queue_change(&admin, &v2, &routes);
+    assert_eq!(result, Err(Ok(ProxyError::ChangePending)));
+
+    // Nothing changes until the timelock has elapsed
+    let result = client.try_apply_change();
+    assert_eq!(result, Err(Ok(ProxyError::TimelockNotElapsed)));
+    assert_eq!(
+        call_u32(&env, &client, symbol_short!("version"), vec![&env]),
+        1
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = eta);
+    client.apply_change();
+    assert_eq!(client.get_implementation(), v2);
+    assert_eq!(client.get_routes(), routes);
+    assert_eq!(client.get_pending_change(), None);
+    assert_eq!(
+        call_u32(&env, &client, symbol_short!("version"), vec![&env]),
+        2
+    );
+
+    // Entrypoints dropped from the routes are no longer forwarded
+    let result = client.try_forward(&symbol_short!("value"), &vec![&env]);
+    assert_eq!(result.err(), Some(Ok(ProxyError::RouteNotFound)));
+}
+
+#[test]
+fn test_cancel_change() {
+    let env = Env::default();
+    let Setup { client, admin, v1 } = setup_test(&env);
+    let v2 = env.register(ImplV2, ());
+
+    let result = client.try_cancel_change(&admin);
+    assert_eq!(result, Err(Ok(ProxyError::NoPendingChange)));
+
+    client.queue_change(&admin, &v2, &vec![&env, symbol_short!("version")]);
+    client.cancel_change(&admin);
+
+    env.ledger().with_mut(|li| li.timestamp = DELAY);
+    let result = client.try_apply_change();
+    assert_eq!(result, Err(Ok(ProxyError::NoPendingChange)));
+    assert_eq!(client.get_implementation(), v1);
+
+    // A new admin takes over the timelock
+    let new_admin = Address::generate(&env);
+    client.set_admin(&admin, &new_admin);
+    let result = client.try_cancel_change(&admin);
+    assert_eq!(result, Err(Ok(ProxyError::Unauthorized)));
+    assert_eq!(client.get_admin(), new_admin);
+}
@@ -0,0 +1,20 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RaffleError {
+    RaffleNotFound = 1,
+    InvalidAmount = 2,
+    InvalidDeadline = 3,
+    CommitClosed = 4,
+    RaffleFull = 5,
+    AlreadyEntered = 6,
+    DuplicateCommitment = 7,
+    NotRevealPhase = 8,
+    NotEntered = 9,
+    AlreadyRevealed = 10,
+    InvalidReveal = 11,
+    RevealNotClosed = 12,
+    AlreadyDrawn = 13,
+}
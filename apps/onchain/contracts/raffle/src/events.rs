@@ -0,0 +1,49 @@
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RaffleCreatedEvent {
+    #[topic]
+    pub creator: Address,
+    pub raffle_id: u64,
+    pub token: Address,
+    pub ticket_price: i128,
+    pub commit_deadline: u64,
+    pub reveal_deadline: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnteredEvent {
+    #[topic]
+    pub raffle_id: u64,
+    #[topic]
+    pub entrant: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevealedEvent {
+    #[topic]
+    pub raffle_id: u64,
+    #[topic]
+    pub entrant: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WinnerDrawnEvent {
+    #[topic]
+    pub raffle_id: u64,
+    #[topic]
+    pub winner: Address,
+    pub prize: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RaffleVoidedEvent {
+    #[topic]
+    pub raffle_id: u64,
+    pub refunded: u32,
+}
@@ -0,0 +1,293 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::RaffleError;
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Vec};
+use storage::{DataKey, Entry, Raffle, RaffleStatus, MAX_ENTRANTS};
+
+/// Raffle drawn from commit-reveal randomness, since Soroban has none of
+/// its own. Entrants buy a ticket with the hash of a secret, reveal the
+/// secret once entries close, and the winner is drawn from every revealed
+/// secret plus the ledger the last of them was revealed in. Both are
+/// settled when reveals close, so choosing when to call the draw changes
+/// nothing. No single entrant can steer the result without knowing
+/// everyone else's secret, and an entrant who never reveals forfeits their
+/// ticket to the pot.
+///
+/// There is no circuit breaker, as the contract has no admin to hold one.
+/// A pause over the reveal phase would also decide who gets to reveal, and
+/// so who can win.
+#[contract]
+pub struct RaffleContract;
+
+#[contractimpl]
+impl RaffleContract {
+    /// Open a raffle selling tickets for `ticket_price` of `token` until
+    /// `commit_deadline`, with reveals accepted until `reveal_deadline`
+    pub fn create_raffle(
+        env: Env,
+        creator: Address,
+        token: Address,
+        ticket_price: i128,
+        commit_deadline: u64,
+        reveal_deadline: u64,
+    ) -> Result<u64, RaffleError> {
+        creator.require_auth();
+
+        if ticket_price <= 0 {
+            return Err(RaffleError::InvalidAmount);
+        }
+        if commit_deadline <= env.ledger().timestamp() || reveal_deadline <= commit_deadline {
+            return Err(RaffleError::InvalidDeadline);
+        }
+
+        let raffle_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRaffleId)
+            .unwrap_or(0);
+        let raffle = Raffle {
+            id: raffle_id,
+            creator: creator.clone(),
+            token: token.clone(),
+            ticket_price,
+            commit_deadline,
+            reveal_deadline,
+            pot: 0,
+            seed: BytesN::from_array(&env, &[0; 32]),
+            last_reveal_ledger: 0,
+            revealed: Vec::new(&env),
+            winner: None,
+            status: RaffleStatus::Open,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Raffle(raffle_id), &raffle);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextRaffleId, &(raffle_id + 1));
+
+        events::RaffleCreatedEvent {
+            creator,
+            raffle_id,
+            token,
+            ticket_price,
+            commit_deadline,
+            reveal_deadline,
+        }
+        .publish(&env);
+
+        Ok(raffle_id)
+    }
+
+    /// Buy one ticket, committing to the SHA-256 of a 32-byte secret kept
+    /// until the reveal phase. Each commitment may only be used once per
+    /// raffle, so nobody can copy another entrant's.
+    pub fn enter(
+        env: Env,
+        entrant: Address,
+        raffle_id: u64,
+        commitment: BytesN<32>,
+    ) -> Result<(), RaffleError> {
+        entrant.require_auth();
+
+        let mut raffle = Self::get_raffle(env.clone(), raffle_id)?;
+        if env.ledger().timestamp() >= raffle.commit_deadline {
+            return Err(RaffleError::CommitClosed);
+        }
+        let entry_key = DataKey::Entry(raffle_id, entrant.clone());
+        if env.storage().persistent().has(&entry_key) {
+            return Err(RaffleError::AlreadyEntered);
+        }
+        let commitment_key = DataKey::Commitment(raffle_id, commitment.clone());
+        if env.storage().persistent().has(&commitment_key) {
+            return Err(RaffleError::DuplicateCommitment);
+        }
+        let entrants_key = DataKey::Entrants(raffle_id);
+        let mut entrants: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&entrants_key)
+            .unwrap_or(Vec::new(&env));
+        if entrants.len() >= MAX_ENTRANTS {
+            return Err(RaffleError::RaffleFull);
+        }
+
+        token::transfer(
+            &env,
+            &raffle.token,
+            &entrant,
+            &env.current_contract_address(),
+            &raffle.ticket_price,
+        );
+
+        raffle.pot += raffle.ticket_price;
+        entrants.push_back(entrant.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Raffle(raffle_id), &raffle);
+        env.storage().persistent().set(&entrants_key, &entrants);
+        env.storage().persistent().set(
+            &entry_key,
+            &Entry {
+                commitment,
+                revealed: false,
+            },
+        );
+        env.storage().persistent().set(&commitment_key, &true);
+
+        events::EnteredEvent { raffle_id, entrant }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Reveal the secret behind `entrant`'s commitment, which makes the
+    /// entrant eligible to win and mixes the secret into the seed
+    pub fn reveal(
+        env: Env,
+        entrant: Address,
+        raffle_id: u64,
+        secret: BytesN<32>,
+    ) -> Result<(), RaffleError> {
+        entrant.require_auth();
+
+        let mut raffle = Self::get_raffle(env.clone(), raffle_id)?;
+        let now = env.ledger().timestamp();
+        if now < raffle.commit_deadline || now >= raffle.reveal_deadline {
+            return Err(RaffleError::NotRevealPhase);
+        }
+        let entry_key = DataKey::Entry(raffle_id, entrant.clone());
+        let mut entry: Entry = env
+            .storage()
+            .persistent()
+            .get(&entry_key)
+            .ok_or(RaffleError::NotEntered)?;
+        if entry.revealed {
+            return Err(RaffleError::AlreadyRevealed);
+        }
+        if Self::commitment(env.clone(), secret.clone()) != entry.commitment {
+            return Err(RaffleError::InvalidReveal);
+        }
+
+        let mut preimage = Bytes::from(raffle.seed.clone());
+        preimage.append(&Bytes::from(secret));
+        raffle.seed = env.crypto().sha256(&preimage).into();
+        raffle.last_reveal_ledger = env.ledger().sequence();
+        raffle.revealed.push_back(entrant.clone());
+        entry.revealed = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Raffle(raffle_id), &raffle);
+        env.storage().persistent().set(&entry_key, &entry);
+
+        events::RevealedEvent { raffle_id, entrant }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Draw the winner once reveals have closed and pay them the pot.
+    /// If nobody revealed, every ticket is refunded instead. The outcome is
+    /// fixed by the reveals, so any account may trigger the draw. Returns
+    /// the winner, if any.
+    pub fn draw(env: Env, raffle_id: u64) -> Result<Option<Address>, RaffleError> {
+        let mut raffle = Self::get_raffle(env.clone(), raffle_id)?;
+        if raffle.status != RaffleStatus::Open {
+            return Err(RaffleError::AlreadyDrawn);
+        }
+        if env.ledger().timestamp() < raffle.reveal_deadline {
+            return Err(RaffleError::RevealNotClosed);
+        }
+
+        let contract_address = env.current_contract_address();
+        if raffle.revealed.is_empty() {
+            raffle.status = RaffleStatus::Void;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Raffle(raffle_id), &raffle);
+
+            let entrants: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Entrants(raffle_id))
+                .unwrap_or(Vec::new(&env));
+            for entrant in entrants.iter() {
+                token::transfer(
+                    &env,
+                    &raffle.token,
+                    &contract_address,
+                    &entrant,
+                    &raffle.ticket_price,
+                );
+            }
+
+            events::RaffleVoidedEvent {
+                raffle_id,
+                refunded: entrants.len(),
+            }
+            .publish(&env);
+
+            return Ok(None);
+        }
+
+        let winner = Self::pick_winner(&env, &raffle);
+        raffle.winner = Some(winner.clone());
+        raffle.status = RaffleStatus::Drawn;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Raffle(raffle_id), &raffle);
+
+        token::transfer(&env, &raffle.token, &contract_address, &winner, &raffle.pot);
+
+        events::WinnerDrawnEvent {
+            raffle_id,
+            winner: winner.clone(),
+            prize: raffle.pot,
+        }
+        .publish(&env);
+
+        Ok(Some(winner))
+    }
+
+    /// Commitment to enter with for `secret`: its SHA-256
+    pub fn commitment(env: Env, secret: BytesN<32>) -> BytesN<32> {
+        env.crypto().sha256(&Bytes::from(secret)).into()
+    }
+
+    /// Get a raffle by ID
+    pub fn get_raffle(env: Env, raffle_id: u64) -> Result<Raffle, RaffleError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Raffle(raffle_id))
+            .ok_or(RaffleError::RaffleNotFound)
+    }
+
+    /// Get everyone who bought a ticket for a raffle
+    pub fn get_entrants(env: Env, raffle_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Entrants(raffle_id))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+impl RaffleContract {
+    /// Pick a revealed entrant from the seed salted with the ledger of the
+    /// last reveal. Nothing about the ledger the draw runs in is used.
+    fn pick_winner(env: &Env, raffle: &Raffle) -> Address {
+        let mut preimage = Bytes::from(raffle.seed.clone());
+        preimage.extend_from_array(&raffle.last_reveal_ledger.to_be_bytes());
+        let digest: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&digest.to_array()[..8]);
+        let index = u64::from_be_bytes(head) % raffle.revealed.len() as u64;
+        raffle.revealed.get_unchecked(index as u32)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,56 @@
+use soroban_sdk::{contracttype, Address, BytesN, Vec};
+
+/// Most tickets one raffle sells, so a void raffle can refund everyone in
+/// one call
+pub const MAX_ENTRANTS: u32 = 100;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextRaffleId,                // -> u64
+    Raffle(u64),                 // raffle_id -> Raffle
+    Entrants(u64),               // raffle_id -> Vec<Address>
+    Entry(u64, Address),         // (raffle_id, entrant) -> Entry
+    Commitment(u64, BytesN<32>), // (raffle_id, commitment) -> bool
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RaffleStatus {
+    Open,
+    Drawn,
+    /// Nobody revealed, so every ticket was refunded
+    Void,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Raffle {
+    pub id: u64,
+    pub creator: Address,
+    /// Token tickets are paid in
+    pub token: Address,
+    pub ticket_price: i128,
+    /// Entries close at this time and reveals open
+    pub commit_deadline: u64,
+    /// Reveals close at this time and the draw opens
+    pub reveal_deadline: u64,
+    /// Sum of ticket payments, all paid to the winner
+    pub pot: i128,
+    /// Every reveal so far hashed together
+    pub seed: BytesN<32>,
+    /// Ledger sequence of the latest reveal
+    pub last_reveal_ledger: u32,
+    /// Entrants who revealed, in reveal order; only they can win
+    pub revealed: Vec<Address>,
+    pub winner: Option<Address>,
+    pub status: RaffleStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    /// SHA-256 of the entrant's secret
+    pub commitment: BytesN<32>,
+    pub revealed: bool,
+}
@@ -0,0 +1,196 @@
+use crate::errors::RaffleError;
+use crate::storage::RaffleStatus;
+use crate::{RaffleContract, RaffleContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env,
+};
+
+const COMMIT_DEADLINE: u64 = 1_000;
+const REVEAL_DEADLINE: u64 = 2_000;
+const TICKET: i128 = 100;
+
+struct Setup<'a> {
+    client: RaffleContractClient<'a>,
+    creator: Address,
+    token: TokenClient<'a>,
+    token_admin: StellarAssetClient<'a>,
+}
+
+impl Setup<'_> {
+    fn entrant(&self, env: &Env) -> Address {
+        let entrant = Address::generate(env);
+        self.token_admin.mint(&entrant, &1_000);
+        entrant
+    }
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let creator = Address::generate(env);
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+
+    let contract_id = env.register(RaffleContract, ());
+    let client = RaffleContractClient::new(env, &contract_id);
+
+    Setup {
+        client,
+        creator,
+        token: TokenClient::new(env, &sac.address()),
+        token_admin: StellarAssetClient::new(env, &sac.address()),
+    }
+}
+
+fn create_raffle(s: &Setup) -> u64 {
+    s.client.create_raffle(
+        &s.creator,
+        &s.token.address,
+        &TICKET,
+        &COMMIT_DEADLINE,
+        &REVEAL_DEADLINE,
+    )
+}
+
+fn secret(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+#[test]
+fn test_winner_drawn_from_reveals() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let raffle_id = create_raffle(&s);
+    let alice = s.entrant(&env);
+    let bob = s.entrant(&env);
+    let carol = s.entrant(&env);
+
+    for (entrant, byte) in [(&alice, 1), (&bob, 2), (&carol, 3)] {
+        let commitment = s.client.commitment(&secret(&env, byte));
+        s.client.enter(entrant, &raffle_id, &commitment);
+    }
+    assert_eq!(s.token.balance(&s.client.address), 3 * TICKET);
+
+    assert_eq!(
+        s.client.try_reveal(&alice, &raffle_id, &secret(&env, 1)),
+        Err(Ok(RaffleError::NotRevealPhase))
+    );
+    env.ledger().set_timestamp(COMMIT_DEADLINE);
+    assert_eq!(
+        s.client.try_reveal(&alice, &raffle_id, &secret(&env, 2)),
+        Err(Ok(RaffleError::InvalidReveal))
+    );
+    s.client.reveal(&alice, &raffle_id, &secret(&env, 1));
+    s.client.reveal(&bob, &raffle_id, &secret(&env, 2));
+    assert_eq!(
+        s.client.try_reveal(&bob, &raffle_id, &secret(&env, 2)),
+        Err(Ok(RaffleError::AlreadyRevealed))
+    );
+
+    assert_eq!(
+        s.client.try_draw(&raffle_id),
+        Err(Ok(RaffleError::RevealNotClosed))
+    );
+    env.ledger().set_timestamp(REVEAL_DEADLINE);
+    let winner = s.client.draw(&raffle_id).unwrap();
+
+    // Carol never revealed, so only Alice or Bob can win, and Carol's
+    // ticket stays in the pot
+    assert!(winner == alice || winner == bob);
+    assert_eq!(s.token.balance(&winner), 1_000 - TICKET + 3 * TICKET);
+    let raffle = s.client.get_raffle(&raffle_id);
+    assert_eq!(raffle.status, RaffleStatus::Drawn);
+    assert_eq!(raffle.winner, Some(winner));
+    assert_eq!(
+        s.client.try_draw(&raffle_id),
+        Err(Ok(RaffleError::AlreadyDrawn))
+    );
+}
+
+#[test]
+fn test_draw_ledger_does_not_change_the_winner() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let entrants: [Address; 5] = core::array::from_fn(|_| s.entrant(&env));
+
+    // Identical raffles, revealed in the same ledger
+    let raffles: [u64; 6] = core::array::from_fn(|_| create_raffle(&s));
+    for raffle_id in raffles {
+        for (byte, entrant) in entrants.iter().enumerate() {
+            let commitment = s.client.commitment(&secret(&env, byte as u8));
+            s.client.enter(entrant, &raffle_id, &commitment);
+        }
+    }
+    env.ledger().set_timestamp(COMMIT_DEADLINE);
+    for raffle_id in raffles {
+        for (byte, entrant) in entrants.iter().enumerate() {
+            s.client
+                .reveal(entrant, &raffle_id, &secret(&env, byte as u8));
+        }
+    }
+
+    // Each is drawn in a different ledger, at a different time
+    let mut winners = [None, None, None, None, None, None];
+    for (i, raffle_id) in raffles.into_iter().enumerate() {
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 100 + 7 * i as u32;
+            li.timestamp = REVEAL_DEADLINE + 13 * i as u64;
+        });
+        winners[i] = s.client.draw(&raffle_id);
+    }
+    assert!(winners[0].is_some());
+    assert!(winners.iter().all(|winner| *winner == winners[0]));
+}
+
+#[test]
+fn test_entry_rules() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let raffle_id = create_raffle(&s);
+    let alice = s.entrant(&env);
+    let bob = s.entrant(&env);
+
+    let commitment = s.client.commitment(&secret(&env, 1));
+    s.client.enter(&alice, &raffle_id, &commitment);
+    assert_eq!(
+        s.client.try_enter(&alice, &raffle_id, &commitment),
+        Err(Ok(RaffleError::AlreadyEntered))
+    );
+    // Copying someone else's commitment is rejected
+    assert_eq!(
+        s.client.try_enter(&bob, &raffle_id, &commitment),
+        Err(Ok(RaffleError::DuplicateCommitment))
+    );
+
+    env.ledger().set_timestamp(COMMIT_DEADLINE);
+    let commitment = s.client.commitment(&secret(&env, 2));
+    assert_eq!(
+        s.client.try_enter(&bob, &raffle_id, &commitment),
+        Err(Ok(RaffleError::CommitClosed))
+    );
+    assert_eq!(
+        s.client.try_reveal(&bob, &raffle_id, &secret(&env, 2)),
+        Err(Ok(RaffleError::NotEntered))
+    );
+}
+
+#[test]
+fn test_void_raffle_refunds_tickets() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let raffle_id = create_raffle(&s);
+    let alice = s.entrant(&env);
+    let bob = s.entrant(&env);
+
+    s.client
+        .enter(&alice, &raffle_id, &s.client.commitment(&secret(&env, 1)));
+    s.client
+        .enter(&bob, &raffle_id, &s.client.commitment(&secret(&env, 2)));
+
+    env.ledger().set_timestamp(REVEAL_DEADLINE);
+    assert_eq!(s.client.draw(&raffle_id), None);
+    assert_eq!(s.token.balance(&alice), 1_000);
+    assert_eq!(s.token.balance(&bob), 1_000);
+    assert_eq!(s.client.get_raffle(&raffle_id).status, RaffleStatus::Void);
+}
@@ -0,0 +1,18 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RegistryError {
+    NameTaken = 1,
+    NameNotFound = 2,
+    TooManyNames = 3,
+    SameOwner = 4,
+    NotNameTarget = 5,
+    NotInitialized = 6,
+    AlreadyInitialized = 7,
+    Unauthorized = 8,
+    InvalidConfig = 9,
+    NameExpired = 10,
+    InvalidTerms = 11,
+}
@@ -0,0 +1,53 @@
+use crate::storage::RegistryConfig;
+use soroban_sdk::{contractevent, Address, Symbol};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NameRegisteredEvent {
+    #[topic]
+    pub name: Symbol,
+    pub owner: Address,
+    pub target: Address,
+    pub expires_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NameRenewedEvent {
+    #[topic]
+    pub name: Symbol,
+    pub payer: Address,
+    pub expires_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TargetUpdatedEvent {
+    #[topic]
+    pub name: Symbol,
+    pub target: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NameTransferredEvent {
+    #[topic]
+    pub name: Symbol,
+    pub from: Address,
+    pub to: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrimaryNameSetEvent {
+    #[topic]
+    pub address: Address,
+    /// New primary name, or none when it was cleared
+    pub name: Option<Symbol>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigUpdatedEvent {
+    pub config: RegistryConfig,
+}
@@ -0,0 +1,348 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::RegistryError;
+use lumenpulse_pausable::Pausable;
+use soroban_sdk::{contract, contractimpl, Address, Env, Symbol, Vec};
+use storage::{DataKey, NameRecord, RegistryConfig};
+
+/// Upper bound on the names one owner may hold
+const MAX_NAMES_PER_OWNER: u32 = 50;
+
+/// Upper bound on the terms one renewal may pay for
+const MAX_RENEWAL_TERMS: u32 = 10;
+
+/// On-chain name registry. Each human-readable name resolves to an
+/// address and belongs to an owner, who can repoint or transfer it, so
+/// integrations can look addresses up here instead of in config files.
+/// Any address may also pick one of the names resolving to it as its
+/// primary name, for display in place of the raw address.
+///
+/// Names are held for paid terms. Once a name expires it stops resolving,
+/// and there is a grace period to renew it for its owner before anyone
+/// else may register it, so names cannot be squatted for free forever.
+#[contract]
+pub struct RegistryContract;
+
+#[contractimpl]
+impl RegistryContract {
+    /// Initialize the registry with its admin and fee terms
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        config: RegistryConfig,
+    ) -> Result<(), RegistryError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(RegistryError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        Self::check_config(&config)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        Ok(())
+    }
+
+    /// Replace the fee terms (admin only). Names keep the expiry they have
+    /// already paid for; the new terms apply from their next renewal.
+    pub fn set_config(
+        env: Env,
+        admin: Address,
+        config: RegistryConfig,
+    ) -> Result<(), RegistryError> {
+        Self::require_admin(&env, &admin)?;
+        Self::check_config(&config)?;
+
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        events::ConfigUpdatedEvent { config }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Claim `name` for `owner` for one term, resolving to `target`. A name
+    /// whose grace period has run out is reclaimed from its previous owner.
+    pub fn register(
+        env: Env,
+        owner: Address,
+        name: Symbol,
+        target: Address,
+    ) -> Result<(), RegistryError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        owner.require_auth();
+
+        let config = Self::get_config(env.clone())?;
+        let now = env.ledger().timestamp();
+        let key = DataKey::Name(name.clone());
+        if let Some(previous) = env.storage().persistent().get::<_, NameRecord>(&key) {
+            if now < previous.expires_at.saturating_add(config.grace_period) {
+                return Err(RegistryError::NameTaken);
+            }
+            Self::remove_owned(&env, &previous.owner, &name);
+        }
+        Self::add_owned(&env, &owner, &name)?;
+
+        token::transfer(&env, &config.token, &owner, &config.treasury, &config.fee);
+
+        let expires_at = now.saturating_add(config.term);
+        let record = NameRecord {
+            owner: owner.clone(),
+            target: target.clone(),
+            expires_at,
+        };
+        env.storage().persistent().set(&key, &record);
+
+        events::NameRegisteredEvent {
+            name,
+            owner,
+            target,
+            expires_at,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Pay for `terms` more terms on `name`, extending it from its current
+    /// expiry. Anyone may pay, but only until the grace period runs out.
+    /// Returns the new expiry.
+    pub fn renew(env: Env, payer: Address, name: Symbol, terms: u32) -> Result<u64, RegistryError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        payer.require_auth();
+
+        if terms == 0 || terms > MAX_RENEWAL_TERMS {
+            return Err(RegistryError::InvalidTerms);
+        }
+        let config = Self::get_config(env.clone())?;
+        let mut record = Self::get_record(env.clone(), name.clone())?;
+        let now = env.ledger().timestamp();
+        if now >= record.expires_at.saturating_add(config.grace_period) {
+            return Err(RegistryError::NameExpired);
+        }
+
+        let fee = config
+            .fee
+            .checked_mul(terms as i128)
+            .ok_or(RegistryError::InvalidConfig)?;
+        token::transfer(&env, &config.token, &payer, &config.treasury, &fee);
+
+        record.expires_at = record
+            .expires_at
+            .saturating_add(config.term.saturating_mul(terms as u64));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Name(name.clone()), &record);
+
+        events::NameRenewedEvent {
+            name,
+            payer,
+            expires_at: record.expires_at,
+        }
+        .publish(&env);
+
+        Ok(record.expires_at)
+    }
+
+    /// Point a name at a new address (owner only)
+    pub fn set_target(env: Env, name: Symbol, target: Address) -> Result<(), RegistryError> {
+        let mut record = Self::active_record(&env, &name)?;
+
+        // Require owner authorization
+        record.owner.require_auth();
+
+        record.target = target.clone();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Name(name.clone()), &record);
+
+        events::TargetUpdatedEvent { name, target }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Hand a name to a new owner (owner only). Where it resolves to is
+    /// unchanged.
+    pub fn transfer(env: Env, name: Symbol, new_owner: Address) -> Result<(), RegistryError> {
+        let mut record = Self::active_record(&env, &name)?;
+
+        // Require owner authorization
+        record.owner.require_auth();
+
+        if new_owner == record.owner {
+            return Err(RegistryError::SameOwner);
+        }
+        Self::add_owned(&env, &new_owner, &name)?;
+        Self::remove_owned(&env, &record.owner, &name);
+
+        let from = record.owner;
+        record.owner = new_owner.clone();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Name(name.clone()), &record);
+
+        events::NameTransferredEvent {
+            name,
+            from,
+            to: new_owner,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Make `name` the primary name `address` reverse-resolves to. Only the
+    /// address itself may set it, and the name must resolve to it.
+    pub fn set_primary_name(env: Env, address: Address, name: Symbol) -> Result<(), RegistryError> {
+        address.require_auth();
+
+        if Self::resolve(env.clone(), name.clone())? != address {
+            return Err(RegistryError::NotNameTarget);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::PrimaryName(address.clone()), &name);
+
+        events::PrimaryNameSetEvent {
+            address,
+            name: Some(name),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Remove `address`'s primary name
+    pub fn clear_primary_name(env: Env, address: Address) {
+        address.require_auth();
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PrimaryName(address.clone()));
+
+        events::PrimaryNameSetEvent {
+            address,
+            name: None,
+        }
+        .publish(&env);
+    }
+
+    /// Primary name of `address`, if it has one that still resolves back
+    /// to it
+    pub fn reverse(env: Env, address: Address) -> Option<Symbol> {
+        let name: Symbol = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PrimaryName(address.clone()))?;
+        match Self::resolve(env, name.clone()) {
+            Ok(target) if target == address => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Address a name resolves to, while it has not expired
+    pub fn resolve(env: Env, name: Symbol) -> Result<Address, RegistryError> {
+        Ok(Self::active_record(&env, &name)?.target)
+    }
+
+    /// Get a name's record, including expired ones not yet reclaimed
+    pub fn get_record(env: Env, name: Symbol) -> Result<NameRecord, RegistryError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Name(name))
+            .ok_or(RegistryError::NameNotFound)
+    }
+
+    /// Names `owner` holds, in the order it acquired them
+    pub fn names_of(env: Env, owner: Address) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OwnerNames(owner))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Current fee terms
+    pub fn get_config(env: Env) -> Result<RegistryConfig, RegistryError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(RegistryError::NotInitialized)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, RegistryError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(RegistryError::NotInitialized)
+    }
+}
+
+impl RegistryContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), RegistryError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(RegistryError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn check_config(config: &RegistryConfig) -> Result<(), RegistryError> {
+        if config.fee < 0 || config.term == 0 {
+            return Err(RegistryError::InvalidConfig);
+        }
+        Ok(())
+    }
+
+    fn active_record(env: &Env, name: &Symbol) -> Result<NameRecord, RegistryError> {
+        let record = Self::get_record(env.clone(), name.clone())?;
+        if env.ledger().timestamp() >= record.expires_at {
+            return Err(RegistryError::NameExpired);
+        }
+        Ok(record)
+    }
+
+    fn add_owned(env: &Env, owner: &Address, name: &Symbol) -> Result<(), RegistryError> {
+        let mut names = Self::names_of(env.clone(), owner.clone());
+        if names.len() >= MAX_NAMES_PER_OWNER {
+            return Err(RegistryError::TooManyNames);
+        }
+        names.push_back(name.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::OwnerNames(owner.clone()), &names);
+        Ok(())
+    }
+
+    fn remove_owned(env: &Env, owner: &Address, name: &Symbol) {
+        let mut names = Self::names_of(env.clone(), owner.clone());
+        if let Some(index) = names.first_index_of(name) {
+            names.remove(index);
+        }
+        let key = DataKey::OwnerNames(owner.clone());
+        if names.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &names);
+        }
+    }
+}
+
+impl Pausable for RegistryContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(RegistryContract);
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,38 @@
+use soroban_sdk::{contracttype, Address, Symbol};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                // -> Address
+    Config,               // -> RegistryConfig
+    Name(Symbol),         // name -> NameRecord
+    OwnerNames(Address),  // owner -> Vec<Symbol>
+    PrimaryName(Address), // address -> Symbol
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistryConfig {
+    /// Token registration and renewal fees are paid in
+    pub token: Address,
+    /// Fee for one registration term
+    pub fee: i128,
+    /// Seconds one term lasts
+    pub term: u64,
+    /// Seconds after expiry during which the name can still be renewed,
+    /// keeping its owner, before anyone can register it again. Anyone may
+    /// pay for the renewal, as it only ever extends the owner's hold.
+    pub grace_period: u64,
+    /// Receives every fee
+    pub treasury: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NameRecord {
+    pub owner: Address,
+    /// Address the name resolves to
+    pub target: Address,
+    /// When the paid-up terms run out
+    pub expires_at: u64,
+}
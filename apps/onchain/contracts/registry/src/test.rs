@@ -0,0 +1,249 @@
+use crate::errors::RegistryError;
+use crate::storage::RegistryConfig;
+use crate::{RegistryContract, RegistryContractClient};
+use lumenpulse_pausable::PauseError;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, InvokeError,
+};
+
+const FEE: i128 = 100;
+const TERM: u64 = 365 * 86_400;
+const GRACE_PERIOD: u64 = 30 * 86_400;
+
+struct Setup<'a> {
+    client: RegistryContractClient<'a>,
+    admin: Address,
+    treasury: Address,
+    token: TokenClient<'a>,
+    token_admin: StellarAssetClient<'a>,
+}
+
+impl Setup<'_> {
+    /// A fresh address holding enough to pay fees
+    fn user(&self, env: &Env) -> Address {
+        let user = Address::generate(env);
+        self.token_admin.mint(&user, &10_000);
+        user
+    }
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let treasury = Address::generate(env);
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(env, &sac.address());
+    let token_admin = StellarAssetClient::new(env, &sac.address());
+
+    let contract_id = env.register(RegistryContract, ());
+    let client = RegistryContractClient::new(env, &contract_id);
+    client.initialize(
+        &admin,
+        &RegistryConfig {
+            token: token.address.clone(),
+            fee: FEE,
+            term: TERM,
+            grace_period: GRACE_PERIOD,
+            treasury: treasury.clone(),
+        },
+    );
+
+    Setup {
+        client,
+        admin,
+        treasury,
+        token,
+        token_admin,
+    }
+}
+
+#[test]
+fn test_register_and_resolve() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let client = &setup.client;
+    let owner = setup.user(&env);
+    let treasury = Address::generate(&env);
+
+    client.register(&owner, &symbol_short!("treasury"), &treasury);
+    assert_eq!(client.resolve(&symbol_short!("treasury")), treasury);
+
+    let record = client.get_record(&symbol_short!("treasury"));
+    assert_eq!(record.owner, owner);
+    assert_eq!(
+        client.names_of(&owner),
+        vec![&env, symbol_short!("treasury")]
+    );
+
+    let other = setup.user(&env);
+    let result = client.try_register(&other, &symbol_short!("treasury"), &other);
+    assert_eq!(result, Err(Ok(RegistryError::NameTaken)));
+
+    let result = client.try_resolve(&symbol_short!("vault"));
+    assert_eq!(result, Err(Ok(RegistryError::NameNotFound)));
+}
+
+#[test]
+fn test_update_and_transfer() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let client = &setup.client;
+    let owner = setup.user(&env);
+    let buyer = Address::generate(&env);
+    let name = symbol_short!("payroll");
+
+    client.register(&owner, &name, &Address::generate(&env));
+    let new_target = Address::generate(&env);
+    client.set_target(&name, &new_target);
+    assert_eq!(client.resolve(&name), new_target);
+
+    let result = client.try_transfer(&name, &owner);
+    assert_eq!(result, Err(Ok(RegistryError::SameOwner)));
+
+    client.transfer(&name, &buyer);
+    assert_eq!(client.get_record(&name).owner, buyer);
+    assert_eq!(client.resolve(&name), new_target);
+    assert_eq!(client.names_of(&owner).len(), 0);
+    assert_eq!(client.names_of(&buyer), vec![&env, name]);
+}
+
+#[test]
+fn test_reverse_lookup() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let client = &setup.client;
+    let owner = setup.user(&env);
+    let wallet = Address::generate(&env);
+    let name = symbol_short!("alice");
+
+    assert_eq!(client.reverse(&wallet), None);
+
+    // Only a name that resolves to the address can be its primary name
+    client.register(&owner, &symbol_short!("other"), &owner);
+    let result = client.try_set_primary_name(&wallet, &symbol_short!("other"));
+    assert_eq!(result, Err(Ok(RegistryError::NotNameTarget)));
+
+    client.register(&owner, &name, &wallet);
+    client.set_primary_name(&wallet, &name);
+    assert_eq!(client.reverse(&wallet), Some(name.clone()));
+
+    // Repointing the name drops the stale reverse record
+    client.set_target(&name, &owner);
+    assert_eq!(client.reverse(&wallet), None);
+
+    client.set_target(&name, &wallet);
+    assert_eq!(client.reverse(&wallet), Some(name));
+    client.clear_primary_name(&wallet);
+    assert_eq!(client.reverse(&wallet), None);
+}
+
+#[test]
+fn test_registration_fees_and_renewal() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let client = &setup.client;
+    let owner = setup.user(&env);
+    let name = symbol_short!("payroll");
+
+    client.register(&owner, &name, &owner);
+    assert_eq!(setup.token.balance(&setup.treasury), FEE);
+    assert_eq!(client.get_record(&name).expires_at, TERM);
+
+    // Anyone may pay to extend a name, from its current expiry
+    let sponsor = setup.user(&env);
+    assert_eq!(client.renew(&sponsor, &name, &2), 3 * TERM);
+    assert_eq!(setup.token.balance(&sponsor), 10_000 - 2 * FEE);
+    assert_eq!(setup.token.balance(&setup.treasury), 3 * FEE);
+
+    let result = client.try_renew(&sponsor, &name, &0);
+    assert_eq!(result, Err(Ok(RegistryError::InvalidTerms)));
+    let result = client.try_renew(&sponsor, &name, &11);
+    assert_eq!(result, Err(Ok(RegistryError::InvalidTerms)));
+
+    // New fee terms apply to later payments only
+    let mut config = client.get_config();
+    config.fee = 250;
+    client.set_config(&setup.admin, &config);
+    client.renew(&owner, &name, &1);
+    assert_eq!(setup.token.balance(&setup.treasury), 3 * FEE + 250);
+    assert_eq!(client.get_record(&name).expires_at, 4 * TERM);
+
+    config.term = 0;
+    let result = client.try_set_config(&setup.admin, &config);
+    assert_eq!(result, Err(Ok(RegistryError::InvalidConfig)));
+    let result = client.try_set_config(&owner, &client.get_config());
+    assert_eq!(result, Err(Ok(RegistryError::Unauthorized)));
+}
+
+#[test]
+fn test_pause_stops_paid_registration() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let client = &setup.client;
+    let owner = setup.user(&env);
+    let name = symbol_short!("payroll");
+    client.register(&owner, &name, &owner);
+    client.pause(&setup.admin);
+
+    let paused = InvokeError::Contract(PauseError::Paused as u32);
+    let other = symbol_short!("treasury");
+    let result = client.try_register(&owner, &other, &owner);
+    assert_eq!(result, Err(Err(paused)));
+    let result = client.try_renew(&owner, &name, &1);
+    assert_eq!(result, Err(Err(paused)));
+    // Names already held keep resolving
+    assert_eq!(client.resolve(&name), owner);
+
+    client.unpause(&setup.admin);
+    client.renew(&owner, &name, &1);
+    assert_eq!(setup.token.balance(&setup.treasury), 2 * FEE);
+}
+
+#[test]
+fn test_expiry_grace_and_reclaim() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let client = &setup.client;
+    let owner = setup.user(&env);
+    let squatter = setup.user(&env);
+    let name = symbol_short!("alice");
+
+    client.register(&owner, &name, &owner);
+    client.set_primary_name(&owner, &name);
+
+    // An expired name stops resolving, but only its owner can act on it
+    // during the grace period
+    env.ledger().with_mut(|li| li.timestamp = TERM);
+    assert_eq!(
+        client.try_resolve(&name),
+        Err(Ok(RegistryError::NameExpired))
+    );
+    assert_eq!(client.reverse(&owner), None);
+    let result = client.try_set_target(&name, &squatter);
+    assert_eq!(result, Err(Ok(RegistryError::NameExpired)));
+    let result = client.try_register(&squatter, &name, &squatter);
+    assert_eq!(result, Err(Ok(RegistryError::NameTaken)));
+
+    // Renewing within the grace period restores it, with no free time
+    assert_eq!(client.renew(&owner, &name, &1), 2 * TERM);
+    assert_eq!(client.resolve(&name), owner);
+    assert_eq!(client.reverse(&owner), Some(name.clone()));
+
+    // Once the grace period runs out the name is up for grabs
+    env.ledger()
+        .with_mut(|li| li.timestamp = 2 * TERM + GRACE_PERIOD);
+    let result = client.try_renew(&owner, &name, &1);
+    assert_eq!(result, Err(Ok(RegistryError::NameExpired)));
+
+    client.register(&squatter, &name, &squatter);
+    let record = client.get_record(&name);
+    assert_eq!(record.owner, squatter);
+    assert_eq!(record.expires_at, 3 * TERM + GRACE_PERIOD);
+    assert_eq!(client.names_of(&owner).len(), 0);
+    assert_eq!(client.names_of(&squatter), vec![&env, name.clone()]);
+    assert_eq!(client.reverse(&owner), None);
+}
@@ -0,0 +1,26 @@
+use crate::storage::Payee;
+use soroban_sdk::{contractevent, Address, Vec};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayeesSetEvent {
+    pub payees: Vec<Payee>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleasedEvent {
+    #[topic]
+    pub token: Address,
+    #[topic]
+    pub payee: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributedEvent {
+    #[topic]
+    pub token: Address,
+    pub amount: i128,
+}
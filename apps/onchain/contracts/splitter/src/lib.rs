@@ -0,0 +1,241 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::SplitterError;
+use lumenpulse_fixed::{mul_div, Rounding};
+use lumenpulse_pausable::Pausable;
+use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+use storage::{DataKey, Payee, MAX_PAYEES};
+
+/// Splits any token sent to it between weighted payees, so the protocol
+/// fee collector can fan out to the team, treasury and grants without
+/// manual transfers. Income is credited to payees the first time the
+/// contract sees it, so changing the weights never reassigns income that
+/// has already been accounted for.
+#[contract]
+pub struct SplitterContract;
+
+#[contractimpl]
+impl SplitterContract {
+    /// Initialize the splitter with an admin and its payees
+    pub fn initialize(env: Env, admin: Address, payees: Vec<Payee>) -> Result<(), SplitterError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(SplitterError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        Self::store_payees(&env, payees)?;
+        env.storage().instance().set(&DataKey::Admin, &admin);
+
+        Ok(())
+    }
+
+    /// Replace the payees. Income the contract has not accounted for yet is
+    /// split by the new weights, so distribute every token first to settle
+    /// it by the old ones. Payees that are removed keep what they are owed.
+    pub fn set_payees(env: Env, admin: Address, payees: Vec<Payee>) -> Result<(), SplitterError> {
+        Self::require_admin(&env, &admin)?;
+        Self::store_payees(&env, payees)
+    }
+
+    /// Pay every payee what they are owed in `token`, including their share
+    /// of any income since the last payout. Needs no authorization, as only
+    /// the admin's configured shares are paid. Returns the total paid.
+    pub fn distribute(env: Env, token: Address) -> Result<i128, SplitterError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let payees = Self::get_payees(env.clone())?;
+        Self::account(&env, &token, &payees);
+
+        let mut total = 0;
+        for payee in payees.iter() {
+            total += Self::pay_out(&env, &token, &payee.address);
+        }
+
+        events::DistributedEvent {
+            token,
+            amount: total,
+        }
+        .publish(&env);
+
+        Ok(total)
+    }
+
+    /// Pay one payee what they are owed in `token`. Anyone may call this,
+    /// as the funds can only go to the payee. Returns the amount paid.
+    pub fn release(env: Env, token: Address, payee: Address) -> Result<i128, SplitterError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let payees = Self::get_payees(env.clone())?;
+        Self::account(&env, &token, &payees);
+
+        let amount = Self::pay_out(&env, &token, &payee);
+        if amount == 0 {
+            return Err(SplitterError::NothingToRelease);
+        }
+        Ok(amount)
+    }
+
+    /// What `release` would pay `payee` in `token` right now
+    pub fn releasable(env: Env, token: Address, payee: Address) -> Result<i128, SplitterError> {
+        let payees = Self::get_payees(env.clone())?;
+        let mut owed = Self::owed(&env, &token, &payee);
+        let income = Self::unaccounted(&env, &token);
+        if income > 0 {
+            let total_weight = Self::total_weight(&payees);
+            for entry in payees.iter() {
+                if entry.address == payee {
+                    owed += Self::share(income, entry.weight, total_weight);
+                }
+            }
+        }
+        Ok(owed)
+    }
+
+    /// Get the payees and their weights
+    pub fn get_payees(env: Env) -> Result<Vec<Payee>, SplitterError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Payees)
+            .ok_or(SplitterError::NotInitialized)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, SplitterError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(SplitterError::NotInitialized)
+    }
+}
+
+impl SplitterContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), SplitterError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(SplitterError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn store_payees(env: &Env, payees: Vec<Payee>) -> Result<(), SplitterError> {
+        if payees.is_empty() || payees.len() > MAX_PAYEES {
+            return Err(SplitterError::InvalidPayees);
+        }
+        for (i, payee) in payees.iter().enumerate() {
+            if payee.weight == 0 {
+                return Err(SplitterError::InvalidPayees);
+            }
+            let duplicate = payees
+                .iter()
+                .skip(i + 1)
+                .any(|other| other.address == payee.address);
+            if duplicate {
+                return Err(SplitterError::InvalidPayees);
+            }
+        }
+
+        env.storage().instance().set(&DataKey::Payees, &payees);
+        events::PayeesSetEvent { payees }.publish(env);
+        Ok(())
+    }
+
+    /// Credit income the contract has not accounted for yet to `payees` by
+    /// weight. Rounding dust stays unaccounted and joins the next income.
+    fn account(env: &Env, token: &Address, payees: &Vec<Payee>) {
+        let income = Self::unaccounted(env, token);
+        if income <= 0 {
+            return;
+        }
+
+        let total_weight = Self::total_weight(payees);
+        let mut credited = 0;
+        for payee in payees.iter() {
+            let share = Self::share(income, payee.weight, total_weight);
+            if share > 0 {
+                let owed = Self::owed(env, token, &payee.address);
+                env.storage().persistent().set(
+                    &DataKey::Owed(token.clone(), payee.address.clone()),
+                    &(owed + share),
+                );
+                credited += share;
+            }
+        }
+
+        let key = DataKey::Accounted(token.clone());
+        let accounted: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key, &(accounted + credited));
+    }
+
+    /// Transfer what `payee` is owed in `token` and return the amount
+    fn pay_out(env: &Env, token: &Address, payee: &Address) -> i128 {
+        let amount = Self::owed(env, token, payee);
+        if amount == 0 {
+            return 0;
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Owed(token.clone(), payee.clone()));
+        let key = DataKey::Accounted(token.clone());
+        let accounted: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(accounted - amount));
+
+        token::transfer(env, token, &env.current_contract_address(), payee, &amount);
+
+        events::ReleasedEvent {
+            token: token.clone(),
+            payee: payee.clone(),
+            amount,
+        }
+        .publish(env);
+
+        amount
+    }
+
+    /// Balance not yet credited to any payee
+    fn unaccounted(env: &Env, token: &Address) -> i128 {
+        let accounted: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Accounted(token.clone()))
+            .unwrap_or(0);
+        token::balance(env, token, &env.current_contract_address()) - accounted
+    }
+
+    fn owed(env: &Env, token: &Address, payee: &Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Owed(token.clone(), payee.clone()))
+            .unwrap_or(0)
+    }
+
+    fn total_weight(payees: &Vec<Payee>) -> i128 {
+        payees.iter().map(|payee| payee.weight as i128).sum()
+    }
+
+    /// The part of `income` for `weight` out of `total_weight`, rounded down
+    /// so the shares never add up to more than the income
+    fn share(income: i128, weight: u32, total_weight: i128) -> i128 {
+        mul_div(income, weight as i128, total_weight, Rounding::Down).expect("arithmetic overflow")
+    }
+}
+
+impl Pausable for SplitterContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(SplitterContract);
+
+#[cfg(test)]
+mod test;
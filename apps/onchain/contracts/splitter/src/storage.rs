@@ -0,0 +1,21 @@
+use soroban_sdk::{contracttype, Address};
+
+/// Most payees one splitter pays out to
+pub const MAX_PAYEES: u32 = 20;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                  // -> Address
+    Payees,                 // -> Vec<Payee>
+    Accounted(Address),     // token -> i128
+    Owed(Address, Address), // (token, payee) -> i128
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payee {
+    pub address: Address,
+    /// Share of income relative to the other payees' weights
+    pub weight: u32,
+}
@@ -0,0 +1,141 @@
+use crate::errors::SplitterError;
+use crate::storage::Payee;
+use crate::{SplitterContract, SplitterContractClient};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+
+struct Setup<'a> {
+    client: SplitterContractClient<'a>,
+    admin: Address,
+    team: Address,
+    treasury: Address,
+    grants: Address,
+    token: TokenClient<'a>,
+    token_admin: StellarAssetClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let team = Address::generate(env);
+    let treasury = Address::generate(env);
+    let grants = Address::generate(env);
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+
+    let contract_id = env.register(SplitterContract, ());
+    let client = SplitterContractClient::new(env, &contract_id);
+    // 20% team, 50% treasury, 30% grants
+    client.initialize(
+        &admin,
+        &vec![
+            env,
+            Payee {
+                address: team.clone(),
+                weight: 2,
+            },
+            Payee {
+                address: treasury.clone(),
+                weight: 5,
+            },
+            Payee {
+                address: grants.clone(),
+                weight: 3,
+            },
+        ],
+    );
+
+    Setup {
+        client,
+        admin,
+        team,
+        treasury,
+        grants,
+        token: TokenClient::new(env, &sac.address()),
+        token_admin: StellarAssetClient::new(env, &sac.address()),
+    }
+}
+
+#[test]
+fn test_distribute_pushes_shares() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    s.token_admin.mint(&s.client.address, &1_000);
+    assert_eq!(s.client.distribute(&s.token.address), 1_000);
+    assert_eq!(s.token.balance(&s.team), 200);
+    assert_eq!(s.token.balance(&s.treasury), 500);
+    assert_eq!(s.token.balance(&s.grants), 300);
+
+    // Dust that does not split evenly waits for the next income
+    s.token_admin.mint(&s.client.address, &9);
+    assert_eq!(s.client.distribute(&s.token.address), 7);
+    assert_eq!(s.token.balance(&s.client.address), 2);
+    s.token_admin.mint(&s.client.address, &8);
+    assert_eq!(s.client.distribute(&s.token.address), 10);
+    assert_eq!(s.token.balance(&s.team), 200 + 1 + 2);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_release_pulls_one_share() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    s.token_admin.mint(&s.client.address, &1_000);
+    assert_eq!(s.client.releasable(&s.token.address, &s.treasury), 500);
+    assert_eq!(s.client.release(&s.token.address, &s.treasury), 500);
+    assert_eq!(s.token.balance(&s.treasury), 500);
+    assert_eq!(
+        s.client.try_release(&s.token.address, &s.treasury),
+        Err(Ok(SplitterError::NothingToRelease))
+    );
+
+    // The other shares stay reserved while new income arrives
+    s.token_admin.mint(&s.client.address, &1_000);
+    assert_eq!(s.client.releasable(&s.token.address, &s.team), 400);
+    assert_eq!(s.client.release(&s.token.address, &s.team), 400);
+    assert_eq!(s.client.distribute(&s.token.address), 1_100);
+    assert_eq!(s.token.balance(&s.treasury), 1_000);
+    assert_eq!(s.token.balance(&s.grants), 600);
+}
+
+#[test]
+fn test_changing_payees_keeps_credited_income() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    s.token_admin.mint(&s.client.address, &1_000);
+    s.client.release(&s.token.address, &s.team);
+
+    // Grants leaves; its credited 300 stays claimable
+    let payees = vec![
+        &env,
+        Payee {
+            address: s.team.clone(),
+            weight: 1,
+        },
+        Payee {
+            address: s.treasury.clone(),
+            weight: 1,
+        },
+    ];
+    s.client.set_payees(&s.admin, &payees);
+    s.token_admin.mint(&s.client.address, &1_000);
+    assert_eq!(s.client.distribute(&s.token.address), 1_500);
+    assert_eq!(s.token.balance(&s.team), 200 + 500);
+    assert_eq!(s.client.release(&s.token.address, &s.grants), 300);
+
+    let duplicate = vec![&env, payees.get(0).unwrap(), payees.get(0).unwrap()];
+    assert_eq!(
+        s.client.try_set_payees(&s.admin, &duplicate),
+        Err(Ok(SplitterError::InvalidPayees))
+    );
+    assert_eq!(
+        s.client.try_set_payees(&s.team, &payees),
+        Err(Ok(SplitterError::Unauthorized))
+    );
+}
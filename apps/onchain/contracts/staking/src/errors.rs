@@ -0,0 +1,33 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StakingError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    InvalidAmount = 4,
+    InvalidDuration = 5,
+    InsufficientStake = 6,
+    RewardRateTooLow = 7,
+    InvalidLockConfig = 8,
+    TierNotFound = 9,
+    LockDowngrade = 10,
+    SlashNotFound = 11,
+    InvalidFraction = 12,
+    InvalidSlashState = 13,
+    AppealWindowOpen = 14,
+    AppealWindowClosed = 15,
+    InvalidBounty = 16,
+    AutoCompoundDisabled = 17,
+    UnbondingRequired = 18,
+    TooManyUnbonds = 19,
+    UnbondNotFound = 20,
+    NothingToWithdraw = 21,
+    OperatorExists = 22,
+    OperatorNotFound = 23,
+    CommissionTooHigh = 24,
+    AlreadyDelegated = 25,
+    NotDelegated = 26,
+}
@@ -0,0 +1,168 @@
+use crate::storage::SlashDestination;
+use soroban_sdk::{contractevent, Address, String, Symbol};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub admin: Address,
+    pub token: Address,
+    pub reward_duration: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakedEvent {
+    #[topic]
+    pub staker: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnstakedEvent {
+    #[topic]
+    pub staker: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardClaimedEvent {
+    #[topic]
+    pub staker: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardsScheduledEvent {
+    pub amount: i128,
+    pub rate: i128,
+    pub period_finish: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LockConfigSetEvent {
+    pub tiers: u32,
+    pub early_exit_penalty_bps: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LockedEvent {
+    #[topic]
+    pub staker: Address,
+    pub multiplier_bps: u32,
+    pub unlock_time: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EarlyExitPenaltyEvent {
+    #[topic]
+    pub staker: Address,
+    pub penalty: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlashConfigSetEvent {
+    pub appeal_window: u64,
+    pub destination: SlashDestination,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlashProposedEvent {
+    #[topic]
+    pub staker: Address,
+    pub slash_id: u64,
+    pub amount: i128,
+    pub reason: String,
+    pub appeal_deadline: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlashAppealedEvent {
+    #[topic]
+    pub slash_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlashExecutedEvent {
+    #[topic]
+    pub staker: Address,
+    pub slash_id: u64,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlashOverturnedEvent {
+    #[topic]
+    pub slash_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompoundedEvent {
+    #[topic]
+    pub staker: Address,
+    #[topic]
+    pub keeper: Address,
+    pub restaked: i128,
+    pub bounty: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardStreamSetEvent {
+    pub streaming: Address,
+    pub stream_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnbondedEvent {
+    #[topic]
+    pub staker: Address,
+    pub amount: i128,
+    pub release_ledger: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorRegisteredEvent {
+    #[topic]
+    pub operator: Address,
+    pub name: Symbol,
+    pub commission_bps: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommissionChangedEvent {
+    #[topic]
+    pub operator: Address,
+    pub commission_bps: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegatedEvent {
+    #[topic]
+    pub staker: Address,
+    /// New operator, or none when the position was undelegated
+    pub operator: Option<Address>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommissionClaimedEvent {
+    #[topic]
+    pub operator: Address,
+    pub amount: i128,
+}
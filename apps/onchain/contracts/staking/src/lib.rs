@@ -0,0 +1,1370 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod math;
+mod storage;
+mod token;
+
+use errors::StakingError;
+use lumenpulse_pausable::Pausable;
+use lumenpulse_streaming_interface::StreamingClient;
+use math::BPS_DENOMINATOR;
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol, Vec};
+use storage::{
+    DataKey, LockConfig, Operator, RewardState, RewardStream, Slash, SlashConfig, SlashDestination,
+    SlashStatus, StakeInfo, Unbond,
+};
+
+/// Upper bound on the keeper's cut of compounded rewards
+const MAX_COMPOUND_BOUNTY_BPS: u32 = 1_000;
+
+/// Upper bound on entries in one staker's unbonding queue
+const MAX_UNBONDS: u32 = 20;
+
+/// Commission cap operators are held to until the admin sets one
+const DEFAULT_MAX_COMMISSION_BPS: u32 = 2_000;
+
+/// Staking pool for the Lumenpulse token. Rewards in the same token are
+/// emitted at a constant rate over a reward period and shared pro rata
+/// through a reward-per-token accumulator, so they accrue every second
+/// without any per-staker bookkeeping. Stakers may lock for a tier's
+/// duration to earn on a boosted weight instead of their plain amount.
+#[contract]
+pub struct StakingContract;
+
+#[contractimpl]
+impl StakingContract {
+    /// Initialize the pool. Each funding spreads its rewards over
+    /// `reward_duration` seconds on top of whatever is still scheduled.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        reward_duration: u64,
+    ) -> Result<(), StakingError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(StakingError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        if reward_duration == 0 {
+            return Err(StakingError::InvalidDuration);
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardDuration, &reward_duration);
+        env.storage().instance().set(&DataKey::TotalStaked, &0i128);
+        env.storage().instance().set(&DataKey::TotalWeight, &0i128);
+        env.storage()
+            .instance()
+            .set(&DataKey::Rewards, &RewardState::default());
+
+        events::InitializedEvent {
+            admin,
+            token,
+            reward_duration,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Stake `amount` tokens. Staking into a locked position renews its
+    /// lock at the same tier.
+    pub fn stake(env: Env, staker: Address, amount: i128) -> Result<(), StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        staker.require_auth();
+        if amount <= 0 {
+            return Err(StakingError::InvalidAmount);
+        }
+
+        let state = Self::refresh(&env)?;
+        let mut info = Self::settle(&env, &staker, &state);
+        let previous_weight = info.weight;
+
+        // An expired lock no longer earns its boost on new stake
+        let now = env.ledger().timestamp();
+        if info.unlock_time <= now {
+            Self::unlock(&mut info);
+        }
+
+        Self::deposit(&env, &staker, &mut info, amount);
+        if info.unlock_time > now {
+            info.unlock_time = now + info.lock_duration;
+        }
+        Self::save_position(&env, &staker, &info, previous_weight);
+
+        events::StakedEvent { staker, amount }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Stake `amount` (which may be zero to lock an existing position) and
+    /// commit the whole position to lock tier `tier` for a reward boost.
+    /// A lock can be extended or upgraded but never shortened.
+    pub fn stake_locked(
+        env: Env,
+        staker: Address,
+        amount: i128,
+        tier: u32,
+    ) -> Result<(), StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        staker.require_auth();
+        if amount < 0 {
+            return Err(StakingError::InvalidAmount);
+        }
+
+        let lock_tier = Self::get_lock_config(env.clone())
+            .tiers
+            .get(tier)
+            .ok_or(StakingError::TierNotFound)?;
+
+        let state = Self::refresh(&env)?;
+        let mut info = Self::settle(&env, &staker, &state);
+        let previous_weight = info.weight;
+        if info.amount + amount <= 0 {
+            return Err(StakingError::InvalidAmount);
+        }
+
+        let now = env.ledger().timestamp();
+        if info.unlock_time > now && lock_tier.multiplier_bps < info.multiplier_bps {
+            return Err(StakingError::LockDowngrade);
+        }
+
+        if amount > 0 {
+            Self::deposit(&env, &staker, &mut info, amount);
+        }
+        info.multiplier_bps = lock_tier.multiplier_bps;
+        info.lock_duration = lock_tier.duration;
+        info.unlock_time = info.unlock_time.max(now + lock_tier.duration);
+        info.weight = math::weight(info.amount, info.multiplier_bps);
+        Self::save_position(&env, &staker, &info, previous_weight);
+
+        if amount > 0 {
+            events::StakedEvent {
+                staker: staker.clone(),
+                amount,
+            }
+            .publish(&env);
+        }
+        events::LockedEvent {
+            staker,
+            multiplier_bps: info.multiplier_bps,
+            unlock_time: info.unlock_time,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Withdraw `amount` staked tokens. Earned rewards stay claimable.
+    /// Withdrawing from a position that is still locked forfeits the
+    /// early-exit penalty, which is shared among the remaining stakers.
+    /// Returns the amount paid out. Once an unbonding period is set, stake
+    /// leaves through `unbond` instead.
+    pub fn unstake(env: Env, staker: Address, amount: i128) -> Result<i128, StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        staker.require_auth();
+        if Self::unbonding_period(env.clone()) > 0 {
+            return Err(StakingError::UnbondingRequired);
+        }
+
+        let payout = Self::remove_stake(&env, &staker, amount)?;
+
+        let token = Self::token(&env);
+        token::transfer(
+            &env,
+            &token,
+            &env.current_contract_address(),
+            &staker,
+            &payout,
+        );
+
+        events::UnstakedEvent { staker, amount }.publish(&env);
+
+        Ok(payout)
+    }
+
+    /// Start withdrawing `amount`: it stops earning at once and joins the
+    /// back of the staker's unbonding queue, becoming withdrawable after
+    /// the unbonding period. Any early-exit penalty is taken now. Returns
+    /// the ledger the entry matures at.
+    pub fn unbond(env: Env, staker: Address, amount: i128) -> Result<u32, StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        staker.require_auth();
+
+        let mut queue = Self::pending_unbonds(env.clone(), staker.clone());
+        if queue.len() >= MAX_UNBONDS {
+            return Err(StakingError::TooManyUnbonds);
+        }
+
+        let net = Self::remove_stake(&env, &staker, amount)?;
+        let release_ledger = env.ledger().sequence() + Self::unbonding_period(env.clone());
+        queue.push_back(Unbond {
+            amount: net,
+            release_ledger,
+        });
+        Self::save_unbonds(&env, &staker, &queue);
+        Self::set_total_unbonding(&env, Self::total_unbonding(env.clone()) + net);
+
+        events::UnbondedEvent {
+            staker,
+            amount: net,
+            release_ledger,
+        }
+        .publish(&env);
+
+        Ok(release_ledger)
+    }
+
+    /// Pay out every matured entry at the front of the staker's unbonding
+    /// queue. Entries leave in the order they were queued.
+    pub fn withdraw_unbonded(env: Env, staker: Address) -> Result<i128, StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        staker.require_auth();
+
+        let mut queue = Self::pending_unbonds(env.clone(), staker.clone());
+        let now = env.ledger().sequence();
+        let mut amount = 0;
+        while let Some(entry) = queue.first() {
+            if entry.release_ledger > now {
+                break;
+            }
+            amount += entry.amount;
+            queue.pop_front();
+        }
+        if amount == 0 {
+            return Err(StakingError::NothingToWithdraw);
+        }
+
+        Self::save_unbonds(&env, &staker, &queue);
+        Self::set_total_unbonding(&env, Self::total_unbonding(env.clone()) - amount);
+
+        let token = Self::token(&env);
+        token::transfer(
+            &env,
+            &token,
+            &env.current_contract_address(),
+            &staker,
+            &amount,
+        );
+
+        events::UnstakedEvent { staker, amount }.publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Return queue entry `index` to the staker's position. A penalty paid
+    /// when it was unbonded is not refunded, and the lock is not renewed.
+    pub fn cancel_unbond(env: Env, staker: Address, index: u32) -> Result<i128, StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        staker.require_auth();
+
+        let mut queue = Self::pending_unbonds(env.clone(), staker.clone());
+        let entry = queue.get(index).ok_or(StakingError::UnbondNotFound)?;
+        queue.remove(index);
+        Self::save_unbonds(&env, &staker, &queue);
+        Self::set_total_unbonding(&env, Self::total_unbonding(env.clone()) - entry.amount);
+
+        let state = Self::refresh(&env)?;
+        let mut info = Self::settle(&env, &staker, &state);
+        let previous_weight = info.weight;
+        if info.unlock_time <= env.ledger().timestamp() {
+            Self::unlock(&mut info);
+        }
+        info.amount += entry.amount;
+        info.weight = math::weight(info.amount, info.multiplier_bps);
+        Self::save_position(&env, &staker, &info, previous_weight);
+        Self::set_total_staked(&env, Self::total_staked(env.clone()) + entry.amount);
+
+        events::StakedEvent {
+            staker,
+            amount: entry.amount,
+        }
+        .publish(&env);
+
+        Ok(entry.amount)
+    }
+
+    /// Entries waiting in the staker's unbonding queue, oldest first
+    pub fn pending_unbonds(env: Env, staker: Address) -> Vec<Unbond> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Unbonds(staker))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Set how many ledgers unbonding stake waits before it can be
+    /// withdrawn. Zero allows immediate `unstake`.
+    pub fn set_unbonding_period(
+        env: Env,
+        admin: Address,
+        ledgers: u32,
+    ) -> Result<(), StakingError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::UnbondingPeriod, &ledgers);
+        Ok(())
+    }
+
+    /// Ledgers unbonding stake waits before it can be withdrawn
+    pub fn unbonding_period(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::UnbondingPeriod)
+            .unwrap_or(0)
+    }
+
+    /// Tokens waiting in unbonding queues
+    pub fn total_unbonding(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalUnbonding)
+            .unwrap_or(0)
+    }
+
+    /// Drop the boost from a position whose lock has expired. Open to every
+    /// caller, so a staker cannot keep an expired boost by never touching
+    /// their position.
+    pub fn kick(env: Env, staker: Address) -> Result<(), StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        let state = Self::refresh(&env)?;
+        let mut info = Self::settle(&env, &staker, &state);
+        if info.unlock_time > env.ledger().timestamp() {
+            return Ok(());
+        }
+
+        let previous_weight = info.weight;
+        Self::unlock(&mut info);
+        Self::save_position(&env, &staker, &info, previous_weight);
+
+        Ok(())
+    }
+
+    /// Configure the lock tiers on offer and the early-exit penalty.
+    /// Existing positions keep the terms they locked in.
+    pub fn set_lock_config(
+        env: Env,
+        admin: Address,
+        config: LockConfig,
+    ) -> Result<(), StakingError> {
+        Self::require_admin(&env, &admin)?;
+
+        if config.early_exit_penalty_bps as i128 > BPS_DENOMINATOR {
+            return Err(StakingError::InvalidLockConfig);
+        }
+        for tier in config.tiers.iter() {
+            if tier.duration == 0 || (tier.multiplier_bps as i128) < BPS_DENOMINATOR {
+                return Err(StakingError::InvalidLockConfig);
+            }
+        }
+
+        env.storage().instance().set(&DataKey::LockConfig, &config);
+
+        events::LockConfigSetEvent {
+            tiers: config.tiers.len(),
+            early_exit_penalty_bps: config.early_exit_penalty_bps,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Configure the appeal window and where slashed stake goes
+    pub fn set_slash_config(
+        env: Env,
+        admin: Address,
+        config: SlashConfig,
+    ) -> Result<(), StakingError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage().instance().set(&DataKey::SlashConfig, &config);
+
+        events::SlashConfigSetEvent {
+            appeal_window: config.appeal_window,
+            destination: config.destination,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the slashing configuration. Untouched, slashes burn with no
+    /// appeal window.
+    pub fn get_slash_config(env: Env) -> SlashConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::SlashConfig)
+            .unwrap_or(SlashConfig {
+                appeal_window: 0,
+                destination: SlashDestination::Burn,
+            })
+    }
+
+    /// Propose slashing `fraction_bps` of `staker`'s position, e.g. for a
+    /// keeper or arbiter that misbehaved (admin only, which may be the
+    /// governance contract). The amount is held back from withdrawal and
+    /// taken once the appeal window closes.
+    pub fn slash(
+        env: Env,
+        admin: Address,
+        staker: Address,
+        fraction_bps: u32,
+        reason: String,
+    ) -> Result<u64, StakingError> {
+        Self::require_admin(&env, &admin)?;
+
+        if fraction_bps == 0 || fraction_bps as i128 > BPS_DENOMINATOR {
+            return Err(StakingError::InvalidFraction);
+        }
+
+        let mut info = Self::get_stake(env.clone(), staker.clone());
+        let slashable = info.amount - info.pending_slash;
+        let amount = math::share(slashable, fraction_bps);
+        if amount <= 0 {
+            return Err(StakingError::InsufficientStake);
+        }
+
+        info.pending_slash += amount;
+        Self::save_stake(&env, &staker, &info);
+
+        let slash_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextSlashId)
+            .unwrap_or(0);
+        let appeal_deadline =
+            env.ledger().timestamp() + Self::get_slash_config(env.clone()).appeal_window;
+        let slash = Slash {
+            id: slash_id,
+            staker: staker.clone(),
+            amount,
+            reason: reason.clone(),
+            appeal_deadline,
+            status: SlashStatus::Pending,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Slash(slash_id), &slash);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextSlashId, &(slash_id + 1));
+
+        events::SlashProposedEvent {
+            staker,
+            slash_id,
+            amount,
+            reason,
+            appeal_deadline,
+        }
+        .publish(&env);
+
+        Ok(slash_id)
+    }
+
+    /// Contest a pending slash before its appeal deadline (slashed staker
+    /// only). The admin then upholds or overturns it.
+    pub fn appeal(env: Env, slash_id: u64) -> Result<(), StakingError> {
+        let mut slash = Self::load_slash(&env, slash_id)?;
+        slash.staker.require_auth();
+
+        if slash.status != SlashStatus::Pending {
+            return Err(StakingError::InvalidSlashState);
+        }
+        if env.ledger().timestamp() >= slash.appeal_deadline {
+            return Err(StakingError::AppealWindowClosed);
+        }
+
+        slash.status = SlashStatus::Appealed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Slash(slash_id), &slash);
+
+        events::SlashAppealedEvent { slash_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Decide an appealed slash (admin only). Upholding executes it at once.
+    pub fn resolve_appeal(
+        env: Env,
+        admin: Address,
+        slash_id: u64,
+        uphold: bool,
+    ) -> Result<(), StakingError> {
+        Self::require_admin(&env, &admin)?;
+
+        let mut slash = Self::load_slash(&env, slash_id)?;
+        if slash.status != SlashStatus::Appealed {
+            return Err(StakingError::InvalidSlashState);
+        }
+
+        if uphold {
+            return Self::apply_slash(&env, slash);
+        }
+
+        let mut info = Self::get_stake(env.clone(), slash.staker.clone());
+        info.pending_slash -= slash.amount;
+        Self::save_stake(&env, &slash.staker, &info);
+
+        slash.status = SlashStatus::Overturned;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Slash(slash_id), &slash);
+
+        events::SlashOverturnedEvent { slash_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Carry out an unappealed slash once its appeal window has closed.
+    /// The slasher's proposal stands once the window passes, so no further
+    /// authorization is needed.
+    pub fn execute_slash(env: Env, slash_id: u64) -> Result<(), StakingError> {
+        let slash = Self::load_slash(&env, slash_id)?;
+        if slash.status != SlashStatus::Pending {
+            return Err(StakingError::InvalidSlashState);
+        }
+        if env.ledger().timestamp() < slash.appeal_deadline {
+            return Err(StakingError::AppealWindowOpen);
+        }
+
+        Self::apply_slash(&env, slash)
+    }
+
+    /// Get a slash record
+    pub fn get_slash(env: Env, slash_id: u64) -> Result<Slash, StakingError> {
+        Self::load_slash(&env, slash_id)
+    }
+
+    /// Get the lock tiers and early-exit penalty
+    pub fn get_lock_config(env: Env) -> LockConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::LockConfig)
+            .unwrap_or(LockConfig {
+                tiers: Vec::new(&env),
+                early_exit_penalty_bps: 0,
+            })
+    }
+
+    /// Pay out everything `staker` has earned so far
+    pub fn claim(env: Env, staker: Address) -> Result<i128, StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        staker.require_auth();
+
+        let mut state = Self::refresh(&env)?;
+        let mut info = Self::settle(&env, &staker, &state);
+
+        let amount = info.rewards;
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        info.rewards = 0;
+        Self::save_stake(&env, &staker, &info);
+        state.reserve -= amount;
+        env.storage().instance().set(&DataKey::Rewards, &state);
+
+        let token = Self::token(&env);
+        token::transfer(
+            &env,
+            &token,
+            &env.current_contract_address(),
+            &staker,
+            &amount,
+        );
+
+        events::RewardClaimedEvent { staker, amount }.publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Claim `staker`'s rewards and stake them back into its position in
+    /// one step. A keeper compounding someone else's position keeps the
+    /// configured bounty; stakers must opt in before keepers may do so.
+    /// Compounding never renews a lock. Returns the amount restaked.
+    pub fn compound(env: Env, keeper: Address, staker: Address) -> Result<i128, StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        keeper.require_auth();
+        if keeper != staker && !Self::auto_compound(env.clone(), staker.clone()) {
+            return Err(StakingError::AutoCompoundDisabled);
+        }
+
+        let mut state = Self::refresh(&env)?;
+        let mut info = Self::settle(&env, &staker, &state);
+        let previous_weight = info.weight;
+
+        let rewards = info.rewards;
+        if rewards == 0 {
+            return Ok(0);
+        }
+        let bounty = if keeper == staker {
+            0
+        } else {
+            math::share(rewards, Self::compound_bounty(env.clone()))
+        };
+        let restaked = rewards - bounty;
+
+        if info.unlock_time <= env.ledger().timestamp() {
+            Self::unlock(&mut info);
+        }
+
+        // The rewards already sit in the pool, so restaking only moves them
+        // from the reserve into stake
+        info.rewards = 0;
+        info.amount += restaked;
+        info.weight = math::weight(info.amount, info.multiplier_bps);
+        Self::save_position(&env, &staker, &info, previous_weight);
+        Self::set_total_staked(&env, Self::total_staked(env.clone()) + restaked);
+        state.reserve -= rewards;
+        env.storage().instance().set(&DataKey::Rewards, &state);
+
+        if bounty > 0 {
+            let token = Self::token(&env);
+            token::transfer(
+                &env,
+                &token,
+                &env.current_contract_address(),
+                &keeper,
+                &bounty,
+            );
+        }
+
+        events::CompoundedEvent {
+            staker,
+            keeper,
+            restaked,
+            bounty,
+        }
+        .publish(&env);
+
+        Ok(restaked)
+    }
+
+    /// Allow or stop keepers compounding `staker`'s position
+    pub fn set_auto_compound(env: Env, staker: Address, enabled: bool) {
+        staker.require_auth();
+        let key = DataKey::AutoCompound(staker);
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    /// Whether keepers may compound `staker`'s position
+    pub fn auto_compound(env: Env, staker: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::AutoCompound(staker))
+    }
+
+    /// Set the keeper's cut of rewards it compounds, in basis points
+    pub fn set_compound_bounty(env: Env, admin: Address, bps: u32) -> Result<(), StakingError> {
+        Self::require_admin(&env, &admin)?;
+        if bps > MAX_COMPOUND_BOUNTY_BPS {
+            return Err(StakingError::InvalidBounty);
+        }
+        env.storage().instance().set(&DataKey::CompoundBounty, &bps);
+        Ok(())
+    }
+
+    /// Keeper's cut of compounded rewards, in basis points
+    pub fn compound_bounty(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CompoundBounty)
+            .unwrap_or(0)
+    }
+
+    /// Add `amount` tokens to the reward schedule
+    pub fn fund(env: Env, funder: Address, amount: i128) -> Result<(), StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        funder.require_auth();
+        if amount <= 0 {
+            return Err(StakingError::InvalidAmount);
+        }
+
+        let mut state = Self::refresh(&env)?;
+
+        let token = Self::token(&env);
+        token::transfer(
+            &env,
+            &token,
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        Self::schedule(&env, &mut state, amount)?;
+        env.storage().instance().set(&DataKey::Rewards, &state);
+
+        Ok(())
+    }
+
+    /// Schedule reward tokens that reached the pool without `fund`, such as
+    /// a stream from the streaming contract with this pool as recipient,
+    /// pulling the registered reward stream first. Only tokens already held
+    /// by the pool are scheduled, so anyone may sync. Returns the amount
+    /// newly scheduled.
+    pub fn sync(env: Env) -> Result<i128, StakingError> {
+        let state = Self::update_rewards(&env)?;
+        Self::pull_stream(&env);
+        let (_, scheduled) = Self::collect(&env, state)?;
+        Ok(scheduled)
+    }
+
+    /// Fund rewards from stream `stream_id` on `streaming`, which must pay
+    /// this pool. Whatever has streamed is pulled in and scheduled on every
+    /// interaction, so the pool needs no manual top-ups.
+    pub fn set_reward_stream(
+        env: Env,
+        admin: Address,
+        streaming: Address,
+        stream_id: u64,
+    ) -> Result<(), StakingError> {
+        Self::require_admin(&env, &admin)?;
+
+        let stream = RewardStream {
+            streaming,
+            stream_id,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardStream, &stream);
+
+        events::RewardStreamSetEvent {
+            streaming: stream.streaming,
+            stream_id,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Stop pulling rewards from the registered stream
+    pub fn clear_reward_stream(env: Env, admin: Address) -> Result<(), StakingError> {
+        Self::require_admin(&env, &admin)?;
+        env.storage().instance().remove(&DataKey::RewardStream);
+        Ok(())
+    }
+
+    /// Get the stream funding rewards, if any
+    pub fn get_reward_stream(env: Env) -> Option<RewardStream> {
+        env.storage().instance().get(&DataKey::RewardStream)
+    }
+
+    /// Register `operator` under `name` to receive delegations, keeping
+    /// `commission_bps` of its delegators' rewards
+    pub fn register_operator(
+        env: Env,
+        operator: Address,
+        name: Symbol,
+        commission_bps: u32,
+    ) -> Result<(), StakingError> {
+        operator.require_auth();
+        let key = DataKey::Operator(operator.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(StakingError::OperatorExists);
+        }
+        if commission_bps > Self::max_commission(env.clone()) {
+            return Err(StakingError::CommissionTooHigh);
+        }
+
+        let record = Operator {
+            name: name.clone(),
+            commission_bps,
+            delegated: 0,
+            delegators: 0,
+            commission: 0,
+        };
+        env.storage().persistent().set(&key, &record);
+
+        events::OperatorRegisteredEvent {
+            operator,
+            name,
+            commission_bps,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Change the operator's commission. The new rate applies to every
+    /// reward its delegators have not yet settled.
+    pub fn set_commission(
+        env: Env,
+        operator: Address,
+        commission_bps: u32,
+    ) -> Result<(), StakingError> {
+        operator.require_auth();
+        let mut record = Self::get_operator(env.clone(), operator.clone())?;
+        if commission_bps > Self::max_commission(env.clone()) {
+            return Err(StakingError::CommissionTooHigh);
+        }
+
+        record.commission_bps = commission_bps;
+        Self::save_operator(&env, &operator, &record);
+
+        events::CommissionChangedEvent {
+            operator,
+            commission_bps,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Set the highest commission operators may charge. Operators already
+    /// above it keep their rate until they next change it.
+    pub fn set_max_commission(env: Env, admin: Address, bps: u32) -> Result<(), StakingError> {
+        Self::require_admin(&env, &admin)?;
+        if bps > BPS_DENOMINATOR as u32 {
+            return Err(StakingError::InvalidFraction);
+        }
+        env.storage().instance().set(&DataKey::MaxCommission, &bps);
+        Ok(())
+    }
+
+    /// Highest commission operators may charge, in basis points
+    pub fn max_commission(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxCommission)
+            .unwrap_or(DEFAULT_MAX_COMMISSION_BPS)
+    }
+
+    /// Delegate `staker`'s position to `operator`, or redelegate it from
+    /// its current operator. Rewards earned so far are settled at the old
+    /// operator's commission first.
+    pub fn delegate(env: Env, staker: Address, operator: Address) -> Result<(), StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        staker.require_auth();
+        let mut record = Self::get_operator(env.clone(), operator.clone())?;
+        let current = Self::delegation(env.clone(), staker.clone());
+        if current.as_ref() == Some(&operator) {
+            return Err(StakingError::AlreadyDelegated);
+        }
+
+        let state = Self::refresh(&env)?;
+        let info = Self::settle(&env, &staker, &state);
+        Self::save_stake(&env, &staker, &info);
+        if let Some(previous) = current {
+            Self::release_delegation(&env, &previous, info.amount);
+        }
+
+        record.delegated += info.amount;
+        record.delegators += 1;
+        Self::save_operator(&env, &operator, &record);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Delegation(staker.clone()), &operator);
+
+        events::DelegatedEvent {
+            staker,
+            operator: Some(operator),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Take `staker`'s position back from its operator
+    pub fn undelegate(env: Env, staker: Address) -> Result<(), StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        staker.require_auth();
+        let operator =
+            Self::delegation(env.clone(), staker.clone()).ok_or(StakingError::NotDelegated)?;
+
+        let state = Self::refresh(&env)?;
+        let info = Self::settle(&env, &staker, &state);
+        Self::save_stake(&env, &staker, &info);
+        Self::release_delegation(&env, &operator, info.amount);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Delegation(staker.clone()));
+
+        events::DelegatedEvent {
+            staker,
+            operator: None,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Pay out the commission `operator` has collected
+    pub fn claim_commission(env: Env, operator: Address) -> Result<i128, StakingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        operator.require_auth();
+        let mut record = Self::get_operator(env.clone(), operator.clone())?;
+
+        let amount = record.commission;
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        let mut state = Self::refresh(&env)?;
+        record.commission = 0;
+        Self::save_operator(&env, &operator, &record);
+        state.reserve -= amount;
+        env.storage().instance().set(&DataKey::Rewards, &state);
+
+        let token = Self::token(&env);
+        token::transfer(
+            &env,
+            &token,
+            &env.current_contract_address(),
+            &operator,
+            &amount,
+        );
+
+        events::CommissionClaimedEvent { operator, amount }.publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Get an operator's record
+    pub fn get_operator(env: Env, operator: Address) -> Result<Operator, StakingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Operator(operator))
+            .ok_or(StakingError::OperatorNotFound)
+    }
+
+    /// Operator `staker` has delegated to, if any
+    pub fn delegation(env: Env, staker: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Delegation(staker))
+    }
+
+    /// Rewards `staker` could claim right now, net of its operator's
+    /// commission
+    pub fn earned(env: Env, staker: Address) -> Result<i128, StakingError> {
+        let reward_per_token = Self::reward_per_token(env.clone())?;
+        let info = Self::get_stake(env.clone(), staker.clone());
+        let accrued = math::earned(info.weight, reward_per_token, info.reward_per_token_paid);
+        let commission = match Self::operator_of(&env, &staker) {
+            Some((_, record)) => math::share(accrued, record.commission_bps),
+            None => 0,
+        };
+        Ok(info.rewards + accrued - commission)
+    }
+
+    /// Current value of the reward-per-token accumulator
+    pub fn reward_per_token(env: Env) -> Result<i128, StakingError> {
+        let state = Self::get_reward_state(env.clone())?;
+        let now = env.ledger().timestamp().min(state.period_finish);
+        let elapsed = now.saturating_sub(state.last_update);
+        Ok(math::accrue(
+            state.reward_per_token,
+            state.rate,
+            elapsed,
+            Self::total_weight(env),
+        ))
+    }
+
+    /// Get a staker's position
+    pub fn get_stake(env: Env, staker: Address) -> StakeInfo {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Stake(staker))
+            .unwrap_or(StakeInfo {
+                amount: 0,
+                weight: 0,
+                multiplier_bps: BPS_DENOMINATOR as u32,
+                lock_duration: 0,
+                unlock_time: 0,
+                pending_slash: 0,
+                reward_per_token_paid: 0,
+                rewards: 0,
+            })
+    }
+
+    /// Total tokens staked in the pool
+    pub fn total_staked(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalStaked)
+            .unwrap_or(0)
+    }
+
+    /// Total boosted weight rewards are shared across
+    pub fn total_weight(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalWeight)
+            .unwrap_or(0)
+    }
+
+    /// Get the reward schedule as of the last update
+    pub fn get_reward_state(env: Env) -> Result<RewardState, StakingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Rewards)
+            .ok_or(StakingError::NotInitialized)
+    }
+
+    /// Get the staked and reward token
+    pub fn get_token(env: Env) -> Result<Address, StakingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(StakingError::NotInitialized)
+    }
+
+    /// Get admin address
+    pub fn get_admin(env: Env) -> Result<Address, StakingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(StakingError::NotInitialized)
+    }
+}
+
+impl StakingContract {
+    fn token(env: &Env) -> Address {
+        env.storage().instance().get(&DataKey::Token).unwrap()
+    }
+
+    fn set_total_staked(env: &Env, total: i128) {
+        env.storage().instance().set(&DataKey::TotalStaked, &total);
+    }
+
+    /// Store a position, keeping its operator's delegated total in step
+    /// with the staked amount
+    fn save_stake(env: &Env, staker: &Address, info: &StakeInfo) {
+        if let Some((operator, mut record)) = Self::operator_of(env, staker) {
+            let previous = Self::get_stake(env.clone(), staker.clone()).amount;
+            if info.amount != previous {
+                record.delegated += info.amount - previous;
+                Self::save_operator(env, &operator, &record);
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stake(staker.clone()), info);
+    }
+
+    fn save_operator(env: &Env, operator: &Address, record: &Operator) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Operator(operator.clone()), record);
+    }
+
+    fn operator_of(env: &Env, staker: &Address) -> Option<(Address, Operator)> {
+        let operator = Self::delegation(env.clone(), staker.clone())?;
+        let record = Self::get_operator(env.clone(), operator.clone()).ok()?;
+        Some((operator, record))
+    }
+
+    /// Remove a delegator holding `amount` from the operator's totals
+    fn release_delegation(env: &Env, operator: &Address, amount: i128) {
+        if let Ok(mut record) = Self::get_operator(env.clone(), operator.clone()) {
+            record.delegated -= amount;
+            record.delegators -= 1;
+            Self::save_operator(env, operator, &record);
+        }
+    }
+
+    /// Store a position whose weight may have changed from
+    /// `previous_weight`, keeping the pool's total weight in step
+    fn save_position(env: &Env, staker: &Address, info: &StakeInfo, previous_weight: i128) {
+        Self::save_stake(env, staker, info);
+        let total_weight = Self::total_weight(env.clone()) + info.weight - previous_weight;
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalWeight, &total_weight);
+    }
+
+    /// Pull `amount` from the staker into its position at the position's
+    /// current multiplier
+    fn deposit(env: &Env, staker: &Address, info: &mut StakeInfo, amount: i128) {
+        let token = Self::token(env);
+        token::transfer(
+            env,
+            &token,
+            staker,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        info.amount += amount;
+        info.weight = math::weight(info.amount, info.multiplier_bps);
+        Self::set_total_staked(env, Self::total_staked(env.clone()) + amount);
+    }
+
+    fn load_slash(env: &Env, slash_id: u64) -> Result<Slash, StakingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Slash(slash_id))
+            .ok_or(StakingError::SlashNotFound)
+    }
+
+    /// Take a slash out of the staker's position and burn or redirect it.
+    /// Rewards already earned are left untouched.
+    fn apply_slash(env: &Env, mut slash: Slash) -> Result<(), StakingError> {
+        let state = Self::refresh(env)?;
+        let mut info = Self::settle(env, &slash.staker, &state);
+        let previous_weight = info.weight;
+
+        info.pending_slash -= slash.amount;
+        info.amount -= slash.amount;
+        info.weight = math::weight(info.amount, info.multiplier_bps);
+        Self::save_position(env, &slash.staker, &info, previous_weight);
+        Self::set_total_staked(env, Self::total_staked(env.clone()) - slash.amount);
+
+        slash.status = SlashStatus::Executed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Slash(slash.id), &slash);
+
+        let token = Self::token(env);
+        match Self::get_slash_config(env.clone()).destination {
+            SlashDestination::Burn => token::burn(env, &token, &slash.amount),
+            SlashDestination::Redirect(recipient) => token::transfer(
+                env,
+                &token,
+                &env.current_contract_address(),
+                &recipient,
+                &slash.amount,
+            ),
+        }
+
+        events::SlashExecutedEvent {
+            staker: slash.staker,
+            slash_id: slash.id,
+            amount: slash.amount,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Return a position to the flexible 1x tier
+    fn unlock(info: &mut StakeInfo) {
+        info.multiplier_bps = BPS_DENOMINATOR as u32;
+        info.lock_duration = 0;
+        info.weight = info.amount;
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), StakingError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(StakingError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    /// Advance the accumulator, then pull anything the reward stream has
+    /// accrued and schedule it along with any other unaccounted inflow
+    fn refresh(env: &Env) -> Result<RewardState, StakingError> {
+        let state = Self::update_rewards(env)?;
+        Self::pull_stream(env);
+        let (state, _) = Self::collect(env, state)?;
+        Ok(state)
+    }
+
+    /// Take `amount` out of the staker's position, charging the early-exit
+    /// penalty if it is still locked. Returns what the staker keeps.
+    fn remove_stake(env: &Env, staker: &Address, amount: i128) -> Result<i128, StakingError> {
+        if amount <= 0 {
+            return Err(StakingError::InvalidAmount);
+        }
+
+        let mut state = Self::refresh(env)?;
+        let mut info = Self::settle(env, staker, &state);
+        if amount > info.amount - info.pending_slash {
+            return Err(StakingError::InsufficientStake);
+        }
+        let previous_weight = info.weight;
+
+        let penalty = if info.unlock_time > env.ledger().timestamp() {
+            let bps = Self::get_lock_config(env.clone()).early_exit_penalty_bps;
+            math::share(amount, bps)
+        } else {
+            0
+        };
+
+        info.amount -= amount;
+        info.weight = math::weight(info.amount, info.multiplier_bps);
+        Self::save_position(env, staker, &info, previous_weight);
+        Self::set_total_staked(env, Self::total_staked(env.clone()) - amount);
+
+        if penalty > 0 {
+            // Credit the penalty straight to the accumulator; with nobody
+            // left to share it, it stays unaccounted for `sync` to schedule
+            let total_weight = Self::total_weight(env.clone());
+            if total_weight > 0 {
+                state.reward_per_token += math::per_token(penalty, total_weight);
+                state.reserve += penalty;
+                env.storage().instance().set(&DataKey::Rewards, &state);
+            }
+
+            events::EarlyExitPenaltyEvent {
+                staker: staker.clone(),
+                penalty,
+            }
+            .publish(env);
+        }
+
+        Ok(amount - penalty)
+    }
+
+    fn save_unbonds(env: &Env, staker: &Address, queue: &Vec<Unbond>) {
+        let key = DataKey::Unbonds(staker.clone());
+        if queue.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, queue);
+        }
+    }
+
+    fn set_total_unbonding(env: &Env, total: i128) {
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalUnbonding, &total);
+    }
+
+    /// Withdraw whatever the reward stream has accrued into the pool
+    fn pull_stream(env: &Env) {
+        let Some(stream) = Self::get_reward_stream(env.clone()) else {
+            return;
+        };
+
+        // A finished or cancelled stream must not block staking, so
+        // failures here only skip the pull
+        let client = StreamingClient::new(env, &stream.streaming);
+        if let Ok(Ok(available)) = client.try_withdrawable_amount(&stream.stream_id) {
+            if available > 0 {
+                let _ = client.try_withdraw(&stream.stream_id);
+            }
+        }
+    }
+
+    /// Schedule reward tokens held beyond stake and reserve. `state` must
+    /// already be updated to now. Returns the amount newly scheduled.
+    fn collect(env: &Env, mut state: RewardState) -> Result<(RewardState, i128), StakingError> {
+        let token = Self::token(env);
+        let balance = token::balance(env, &token, &env.current_contract_address());
+        let inflow = balance
+            - Self::total_staked(env.clone())
+            - Self::total_unbonding(env.clone())
+            - state.reserve;
+        if inflow <= 0 {
+            return Ok((state, 0));
+        }
+
+        // Too little to emit yet; leave it for a later collection
+        match Self::schedule(env, &mut state, inflow) {
+            Ok(()) => {}
+            Err(StakingError::RewardRateTooLow) => return Ok((state, 0)),
+            Err(err) => return Err(err),
+        }
+        env.storage().instance().set(&DataKey::Rewards, &state);
+
+        Ok((state, inflow))
+    }
+
+    /// Advance the accumulator to now. While nothing is staked the schedule
+    /// is paused rather than emitting into the void.
+    fn update_rewards(env: &Env) -> Result<RewardState, StakingError> {
+        let mut state = Self::get_reward_state(env.clone())?;
+        let now = env.ledger().timestamp();
+
+        if state.last_update < state.period_finish && now > state.last_update {
+            let total_weight = Self::total_weight(env.clone());
+            if total_weight == 0 {
+                state.period_finish = now + (state.period_finish - state.last_update);
+                state.last_update = now;
+            } else {
+                let applicable = now.min(state.period_finish);
+                state.reward_per_token = math::accrue(
+                    state.reward_per_token,
+                    state.rate,
+                    applicable - state.last_update,
+                    total_weight,
+                );
+                state.last_update = applicable;
+            }
+            env.storage().instance().set(&DataKey::Rewards, &state);
+        }
+
+        Ok(state)
+    }
+
+    /// Credit a position with what it earned up to the current accumulator
+    fn settle(env: &Env, staker: &Address, state: &RewardState) -> StakeInfo {
+        let mut info = Self::get_stake(env.clone(), staker.clone());
+        let mut accrued = math::earned(
+            info.weight,
+            state.reward_per_token,
+            info.reward_per_token_paid,
+        );
+        if let Some((operator, mut record)) = Self::operator_of(env, staker) {
+            let commission = math::share(accrued, record.commission_bps);
+            if commission > 0 {
+                record.commission += commission;
+                Self::save_operator(env, &operator, &record);
+                accrued -= commission;
+            }
+        }
+        info.rewards += accrued;
+        info.reward_per_token_paid = state.reward_per_token;
+        info
+    }
+
+    /// Spread `amount` plus anything still unemitted over a fresh reward
+    /// period starting now. `state` must already be updated to now.
+    fn schedule(env: &Env, state: &mut RewardState, amount: i128) -> Result<(), StakingError> {
+        let now = env.ledger().timestamp();
+        let duration: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RewardDuration)
+            .unwrap();
+
+        let leftover = if now < state.period_finish {
+            (state.period_finish - now) as i128 * state.rate
+        } else {
+            0
+        };
+        let rate = (amount + leftover) / duration as i128;
+        if rate == 0 {
+            return Err(StakingError::RewardRateTooLow);
+        }
+
+        state.rate = rate;
+        state.last_update = now;
+        state.period_finish = now + duration;
+        state.reserve += amount;
+
+        events::RewardsScheduledEvent {
+            amount,
+            rate,
+            period_finish: state.period_finish,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+}
+
+impl Pausable for StakingContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(StakingContract);
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,39 @@
+pub use lumenpulse_fixed::BPS_DENOMINATOR;
+/// Reward accumulator math
+use lumenpulse_fixed::{bps, mul_div, Rounding};
+
+/// Scale for the reward-per-token accumulator, so small per-second rates
+/// spread over a large stake do not round to zero
+pub const PRECISION: i128 = 1_000_000_000_000;
+
+/// `rate` basis points of `amount`, rounded down
+pub fn share(amount: i128, rate: u32) -> i128 {
+    bps(amount, rate, Rounding::Down).expect("arithmetic overflow")
+}
+
+/// Reward weight of `amount` staked at `multiplier_bps`
+pub fn weight(amount: i128, multiplier_bps: u32) -> i128 {
+    share(amount, multiplier_bps)
+}
+
+/// Accumulator increase from spreading `amount` over `total_staked`
+pub fn per_token(amount: i128, total_staked: i128) -> i128 {
+    mul_div(amount, PRECISION, total_staked, Rounding::Down).expect("arithmetic overflow")
+}
+
+/// Accumulator value after `elapsed` seconds at `rate` over `total_staked`
+pub fn accrue(reward_per_token: i128, rate: i128, elapsed: u64, total_staked: i128) -> i128 {
+    if total_staked == 0 || elapsed == 0 {
+        return reward_per_token;
+    }
+    let emitted = rate
+        .checked_mul(elapsed as i128)
+        .expect("arithmetic overflow");
+    reward_per_token + per_token(emitted, total_staked)
+}
+
+/// Rewards a position of `amount` has earned between two accumulator values
+pub fn earned(amount: i128, reward_per_token: i128, paid: i128) -> i128 {
+    mul_div(amount, reward_per_token - paid, PRECISION, Rounding::Down)
+        .expect("arithmetic overflow")
+}
@@ -0,0 +1,142 @@
+use soroban_sdk::{contracttype, Address, String, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                 // -> Address
+    Token,                 // -> Address
+    RewardDuration,        // -> u64
+    TotalStaked,           // -> i128
+    TotalWeight,           // -> i128
+    Rewards,               // -> RewardState
+    LockConfig,            // -> LockConfig
+    SlashConfig,           // -> SlashConfig
+    NextSlashId,           // -> u64
+    Slash(u64),            // slash_id -> Slash
+    CompoundBounty,        // -> u32 (bps)
+    AutoCompound(Address), // staker -> bool
+    RewardStream,          // -> RewardStream
+    UnbondingPeriod,       // -> u32 (ledgers)
+    TotalUnbonding,        // -> i128
+    Unbonds(Address),      // staker -> Vec<Unbond>
+    MaxCommission,         // -> u32 (bps)
+    Operator(Address),     // operator -> Operator
+    Delegation(Address),   // staker -> operator Address
+    Stake(Address),        // staker -> StakeInfo
+}
+
+/// Global reward schedule and accumulator
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RewardState {
+    /// Tokens emitted per second until `period_finish`
+    pub rate: i128,
+    pub period_finish: u64,
+    pub last_update: u64,
+    /// Rewards earned per staked token since launch, scaled by `PRECISION`
+    pub reward_per_token: i128,
+    /// Reward tokens held for the schedule or owed to stakers
+    pub reserve: i128,
+}
+
+/// Stake on its way out, withdrawable from `release_ledger`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Unbond {
+    pub amount: i128,
+    pub release_ledger: u32,
+}
+
+/// Stream on the streaming contract whose recipient is the pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardStream {
+    pub streaming: Address,
+    pub stream_id: u64,
+}
+
+/// A lock duration stakers can commit to in exchange for a reward boost
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LockTier {
+    pub duration: u64,
+    /// Reward weight per staked token, in basis points (10_000 = 1x)
+    pub multiplier_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LockConfig {
+    pub tiers: Vec<LockTier>,
+    /// Share of the withdrawn amount forfeited by unstaking before unlock
+    pub early_exit_penalty_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeInfo {
+    pub amount: i128,
+    /// `amount` scaled by the position's multiplier; rewards follow weight
+    pub weight: i128,
+    pub multiplier_bps: u32,
+    /// Lock length the position renews to when more is staked into it
+    pub lock_duration: u64,
+    /// Withdrawing before this time incurs the early-exit penalty
+    pub unlock_time: u64,
+    /// Stake held back from withdrawal for slashes awaiting execution
+    pub pending_slash: i128,
+    /// Accumulator value this position was last settled at
+    pub reward_per_token_paid: i128,
+    /// Settled rewards not yet claimed
+    pub rewards: i128,
+}
+
+/// Operator stakers may delegate their positions to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Operator {
+    pub name: Symbol,
+    /// Share of delegators' rewards the operator keeps
+    pub commission_bps: u32,
+    /// Stake currently delegated to the operator
+    pub delegated: i128,
+    pub delegators: u32,
+    /// Collected commission not yet claimed
+    pub commission: i128,
+}
+
+/// Where slashed stake goes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SlashDestination {
+    Burn,
+    Redirect(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlashConfig {
+    /// Seconds a staker has to appeal before a slash can be executed
+    pub appeal_window: u64,
+    pub destination: SlashDestination,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SlashStatus {
+    Pending,
+    Appealed,
+    Executed,
+    Overturned,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Slash {
+    pub id: u64,
+    pub staker: Address,
+    pub amount: i128,
+    pub reason: String,
+    pub appeal_deadline: u64,
+    pub status: SlashStatus,
+}
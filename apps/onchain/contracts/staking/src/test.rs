@@ -0,0 +1,814 @@
+use crate::errors::StakingError;
+use crate::storage::{LockConfig, LockTier, SlashConfig, SlashDestination, SlashStatus, Unbond};
+use crate::{StakingContract, StakingContractClient};
+use lumen_token::{LumenToken, LumenTokenClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String,
+};
+use streaming::{StreamingContract, StreamingContractClient};
+
+const REWARD_DURATION: u64 = 100;
+const MONTH: u64 = 2_592_000;
+
+struct Setup<'a> {
+    env: &'a Env,
+    client: StakingContractClient<'a>,
+    token: LumenTokenClient<'a>,
+    admin: Address,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let token_id = env.register(LumenToken, ());
+    let token = LumenTokenClient::new(env, &token_id);
+    token.initialize(
+        &admin,
+        &7,
+        &String::from_str(env, "LumenPulse"),
+        &String::from_str(env, "LMN"),
+    );
+
+    let contract_id = env.register(StakingContract, ());
+    let client = StakingContractClient::new(env, &contract_id);
+    client.initialize(&admin, &token_id, &REWARD_DURATION);
+
+    Setup {
+        env,
+        client,
+        token,
+        admin,
+    }
+}
+
+fn funded(setup: &Setup, amount: i128) -> Address {
+    let account = Address::generate(setup.env);
+    setup.token.mint(&setup.admin, &account, &amount);
+    account
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+
+    assert_eq!(setup.client.get_admin(), setup.admin);
+    assert_eq!(setup.client.get_token(), setup.token.address);
+    assert_eq!(setup.client.total_staked(), 0);
+
+    let result = setup
+        .client
+        .try_initialize(&setup.admin, &setup.token.address, &REWARD_DURATION);
+    assert_eq!(result, Err(Ok(StakingError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_stake_and_claim() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let staker = funded(&setup, 100);
+    let funder = funded(&setup, 1_000);
+
+    setup.client.stake(&staker, &100);
+    setup.client.fund(&funder, &1_000);
+    assert_eq!(setup.client.get_reward_state().rate, 10);
+
+    advance(&env, 50);
+    assert_eq!(setup.client.earned(&staker), 500);
+
+    assert_eq!(setup.client.claim(&staker), 500);
+    assert_eq!(setup.token.balance(&staker), 500);
+    assert_eq!(setup.client.earned(&staker), 0);
+
+    // Emission stops at the end of the period
+    advance(&env, 500);
+    assert_eq!(setup.client.earned(&staker), 500);
+}
+
+#[test]
+fn test_rewards_split_pro_rata() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let alice = funded(&setup, 100);
+    let bob = funded(&setup, 300);
+    let funder = funded(&setup, 1_000);
+
+    setup.client.stake(&alice, &100);
+    setup.client.fund(&funder, &1_000);
+
+    advance(&env, 50);
+    setup.client.stake(&bob, &300);
+
+    advance(&env, 50);
+    assert_eq!(setup.client.earned(&alice), 625);
+    assert_eq!(setup.client.earned(&bob), 375);
+}
+
+#[test]
+fn test_unstake() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let staker = funded(&setup, 100);
+    let funder = funded(&setup, 1_000);
+
+    setup.client.stake(&staker, &100);
+    setup.client.fund(&funder, &1_000);
+    advance(&env, 20);
+
+    let result = setup.client.try_unstake(&staker, &101);
+    assert_eq!(result, Err(Ok(StakingError::InsufficientStake)));
+
+    setup.client.unstake(&staker, &100);
+    assert_eq!(setup.token.balance(&staker), 100);
+    assert_eq!(setup.client.total_staked(), 0);
+
+    // Rewards earned while staked remain claimable
+    advance(&env, 20);
+    assert_eq!(setup.client.claim(&staker), 200);
+}
+
+#[test]
+fn test_schedule_pauses_without_stakers() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let staker = funded(&setup, 100);
+    let funder = funded(&setup, 1_000);
+
+    setup.client.fund(&funder, &1_000);
+    advance(&env, 50);
+
+    // Nothing has been emitted while the pool was empty
+    setup.client.stake(&staker, &100);
+    advance(&env, REWARD_DURATION);
+    assert_eq!(setup.client.earned(&staker), 1_000);
+}
+
+#[test]
+fn test_fund_extends_schedule() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let staker = funded(&setup, 100);
+    let funder = funded(&setup, 2_000);
+
+    setup.client.stake(&staker, &100);
+    setup.client.fund(&funder, &1_000);
+    advance(&env, 50);
+
+    // The unemitted 500 rolls into the new period with the fresh 1000
+    setup.client.fund(&funder, &1_000);
+    let state = setup.client.get_reward_state();
+    assert_eq!(state.rate, 15);
+    assert_eq!(
+        state.period_finish,
+        env.ledger().timestamp() + REWARD_DURATION
+    );
+
+    advance(&env, REWARD_DURATION);
+    assert_eq!(setup.client.earned(&staker), 2_000);
+}
+
+#[test]
+fn test_fund_errors() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let funder = funded(&setup, 1_000);
+
+    let result = setup.client.try_fund(&funder, &0);
+    assert_eq!(result, Err(Ok(StakingError::InvalidAmount)));
+
+    let result = setup.client.try_fund(&funder, &99);
+    assert_eq!(result, Err(Ok(StakingError::RewardRateTooLow)));
+}
+
+#[test]
+fn test_stream_funds_rewards() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let staker = funded(&setup, 100);
+    let sender = funded(&setup, 1_000);
+
+    let streaming_id = env.register(StreamingContract, ());
+    let streaming = StreamingContractClient::new(&env, &streaming_id);
+    streaming.initialize(&setup.admin);
+
+    setup.client.stake(&staker, &100);
+
+    // Route a stream into the pool
+    let start = env.ledger().timestamp();
+    let stream_id = streaming.create_stream(
+        &sender,
+        &setup.client.address,
+        &setup.token.address,
+        &1_000,
+        &start,
+        &(start + 200),
+    );
+
+    advance(&env, 100);
+    streaming.withdraw_many(&vec![&env, stream_id]);
+    assert_eq!(setup.client.sync(), 500);
+
+    // Staked principal is never mistaken for new rewards
+    assert_eq!(setup.client.sync(), 0);
+
+    advance(&env, REWARD_DURATION);
+    assert_eq!(setup.client.claim(&staker), 500);
+    assert_eq!(setup.token.balance(&setup.client.address), 100);
+}
+
+/// 1, 3 and 12 month tiers at 1.25x, 1.5x and 3x with a 10% early-exit
+/// penalty
+fn set_tiers(setup: &Setup) {
+    let tiers = vec![
+        setup.env,
+        LockTier {
+            duration: MONTH,
+            multiplier_bps: 12_500,
+        },
+        LockTier {
+            duration: 3 * MONTH,
+            multiplier_bps: 15_000,
+        },
+        LockTier {
+            duration: 12 * MONTH,
+            multiplier_bps: 30_000,
+        },
+    ];
+    setup.client.set_lock_config(
+        &setup.admin,
+        &LockConfig {
+            tiers,
+            early_exit_penalty_bps: 1_000,
+        },
+    );
+}
+
+#[test]
+fn test_lock_boosts_rewards() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    set_tiers(&setup);
+    let alice = funded(&setup, 100);
+    let bob = funded(&setup, 100);
+    let funder = funded(&setup, 1_000);
+
+    setup.client.stake(&alice, &100);
+    setup.client.stake_locked(&bob, &100, &2);
+
+    let position = setup.client.get_stake(&bob);
+    assert_eq!(position.weight, 300);
+    assert_eq!(position.unlock_time, env.ledger().timestamp() + 12 * MONTH);
+    assert_eq!(setup.client.total_staked(), 200);
+    assert_eq!(setup.client.total_weight(), 400);
+
+    setup.client.fund(&funder, &1_000);
+    advance(&env, REWARD_DURATION);
+    assert_eq!(setup.client.earned(&alice), 250);
+    assert_eq!(setup.client.earned(&bob), 750);
+}
+
+#[test]
+fn test_early_exit_penalty_goes_to_stakers() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    set_tiers(&setup);
+    let alice = funded(&setup, 100);
+    let bob = funded(&setup, 100);
+
+    setup.client.stake(&alice, &100);
+    setup.client.stake_locked(&bob, &100, &0);
+
+    assert_eq!(setup.client.unstake(&bob, &100), 90);
+    assert_eq!(setup.token.balance(&bob), 90);
+    assert_eq!(setup.client.earned(&alice), 10);
+    assert_eq!(setup.client.claim(&alice), 10);
+
+    // Once the lock has passed there is no penalty
+    setup.client.stake_locked(&alice, &0, &0);
+    advance(&env, MONTH);
+    assert_eq!(setup.client.unstake(&alice, &100), 100);
+}
+
+#[test]
+fn test_lock_rules() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    set_tiers(&setup);
+    let staker = funded(&setup, 200);
+
+    let result = setup.client.try_stake_locked(&staker, &100, &3);
+    assert_eq!(result, Err(Ok(StakingError::TierNotFound)));
+
+    let result = setup.client.try_stake_locked(&staker, &0, &0);
+    assert_eq!(result, Err(Ok(StakingError::InvalidAmount)));
+
+    setup.client.stake_locked(&staker, &100, &1);
+    let result = setup.client.try_stake_locked(&staker, &0, &0);
+    assert_eq!(result, Err(Ok(StakingError::LockDowngrade)));
+
+    // Adding stake renews the lock at the same tier
+    advance(&env, MONTH);
+    setup.client.stake(&staker, &100);
+    let position = setup.client.get_stake(&staker);
+    assert_eq!(position.weight, 300);
+    assert_eq!(position.unlock_time, env.ledger().timestamp() + 3 * MONTH);
+}
+
+#[test]
+fn test_kick_expired_lock() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    set_tiers(&setup);
+    let staker = funded(&setup, 100);
+
+    setup.client.stake_locked(&staker, &100, &2);
+
+    // Kicking a live lock leaves it alone
+    setup.client.kick(&staker);
+    assert_eq!(setup.client.total_weight(), 300);
+
+    advance(&env, 12 * MONTH);
+    setup.client.kick(&staker);
+    assert_eq!(setup.client.get_stake(&staker).weight, 100);
+    assert_eq!(setup.client.total_weight(), 100);
+}
+
+#[test]
+fn test_set_lock_config_validation() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let stranger = Address::generate(&env);
+
+    let config = LockConfig {
+        tiers: vec![
+            &env,
+            LockTier {
+                duration: MONTH,
+                multiplier_bps: 9_000,
+            },
+        ],
+        early_exit_penalty_bps: 1_000,
+    };
+    let result = setup.client.try_set_lock_config(&setup.admin, &config);
+    assert_eq!(result, Err(Ok(StakingError::InvalidLockConfig)));
+
+    let result = setup.client.try_set_lock_config(&stranger, &config);
+    assert_eq!(result, Err(Ok(StakingError::Unauthorized)));
+}
+
+const APPEAL_WINDOW: u64 = 86_400;
+
+fn set_slashing(setup: &Setup, destination: SlashDestination) {
+    setup.client.set_slash_config(
+        &setup.admin,
+        &SlashConfig {
+            appeal_window: APPEAL_WINDOW,
+            destination,
+        },
+    );
+}
+
+fn reason(env: &Env) -> String {
+    String::from_str(env, "missed keeper duty")
+}
+
+#[test]
+fn test_slash_burns_after_appeal_window() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    set_slashing(&setup, SlashDestination::Burn);
+    let operator = funded(&setup, 1_000);
+    setup.client.stake(&operator, &1_000);
+
+    let slash_id = setup
+        .client
+        .slash(&setup.admin, &operator, &2_500, &reason(&env));
+    let slash = setup.client.get_slash(&slash_id);
+    assert_eq!(slash.amount, 250);
+    assert_eq!(slash.status, SlashStatus::Pending);
+
+    // The slashed amount cannot be withdrawn while the slash is pending
+    let result = setup.client.try_unstake(&operator, &751);
+    assert_eq!(result, Err(Ok(StakingError::InsufficientStake)));
+
+    let result = setup.client.try_execute_slash(&slash_id);
+    assert_eq!(result, Err(Ok(StakingError::AppealWindowOpen)));
+
+    advance(&env, APPEAL_WINDOW);
+    setup.client.execute_slash(&slash_id);
+    assert_eq!(setup.client.get_stake(&operator).amount, 750);
+    assert_eq!(setup.client.total_staked(), 750);
+    assert_eq!(setup.token.total_supply(), 750);
+    assert_eq!(
+        setup.client.get_slash(&slash_id).status,
+        SlashStatus::Executed
+    );
+
+    let result = setup.client.try_execute_slash(&slash_id);
+    assert_eq!(result, Err(Ok(StakingError::InvalidSlashState)));
+}
+
+#[test]
+fn test_slash_redirect_on_upheld_appeal() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let treasury = Address::generate(&env);
+    set_slashing(&setup, SlashDestination::Redirect(treasury.clone()));
+    let operator = funded(&setup, 1_000);
+    setup.client.stake(&operator, &1_000);
+
+    let slash_id = setup
+        .client
+        .slash(&setup.admin, &operator, &1_000, &reason(&env));
+    setup.client.appeal(&slash_id);
+
+    // Appealed slashes wait for the admin's decision
+    advance(&env, APPEAL_WINDOW);
+    let result = setup.client.try_execute_slash(&slash_id);
+    assert_eq!(result, Err(Ok(StakingError::InvalidSlashState)));
+
+    setup.client.resolve_appeal(&setup.admin, &slash_id, &true);
+    assert_eq!(setup.token.balance(&treasury), 100);
+    assert_eq!(setup.client.get_stake(&operator).amount, 900);
+}
+
+#[test]
+fn test_slash_overturned_on_appeal() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    set_slashing(&setup, SlashDestination::Burn);
+    let operator = funded(&setup, 1_000);
+    setup.client.stake(&operator, &1_000);
+
+    let slash_id = setup
+        .client
+        .slash(&setup.admin, &operator, &5_000, &reason(&env));
+    setup.client.appeal(&slash_id);
+    setup.client.resolve_appeal(&setup.admin, &slash_id, &false);
+
+    assert_eq!(
+        setup.client.get_slash(&slash_id).status,
+        SlashStatus::Overturned
+    );
+    assert_eq!(setup.client.get_stake(&operator).pending_slash, 0);
+    setup.client.unstake(&operator, &1_000);
+}
+
+#[test]
+fn test_slash_errors() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    set_slashing(&setup, SlashDestination::Burn);
+    let operator = funded(&setup, 1_000);
+    let stranger = Address::generate(&env);
+    setup.client.stake(&operator, &1_000);
+
+    let result = setup
+        .client
+        .try_slash(&stranger, &operator, &1_000, &reason(&env));
+    assert_eq!(result, Err(Ok(StakingError::Unauthorized)));
+
+    let result = setup
+        .client
+        .try_slash(&setup.admin, &operator, &10_001, &reason(&env));
+    assert_eq!(result, Err(Ok(StakingError::InvalidFraction)));
+
+    let result = setup
+        .client
+        .try_slash(&setup.admin, &stranger, &1_000, &reason(&env));
+    assert_eq!(result, Err(Ok(StakingError::InsufficientStake)));
+
+    let slash_id = setup
+        .client
+        .slash(&setup.admin, &operator, &1_000, &reason(&env));
+    advance(&env, APPEAL_WINDOW);
+    let result = setup.client.try_appeal(&slash_id);
+    assert_eq!(result, Err(Ok(StakingError::AppealWindowClosed)));
+}
+
+#[test]
+fn test_compound_by_keeper() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let staker = funded(&setup, 100);
+    let funder = funded(&setup, 1_000);
+    let keeper = Address::generate(&env);
+
+    setup.client.set_compound_bounty(&setup.admin, &100);
+    setup.client.stake(&staker, &100);
+    setup.client.fund(&funder, &1_000);
+    advance(&env, 50);
+
+    let result = setup.client.try_compound(&keeper, &staker);
+    assert_eq!(result, Err(Ok(StakingError::AutoCompoundDisabled)));
+
+    setup.client.set_auto_compound(&staker, &true);
+    assert_eq!(setup.client.compound(&keeper, &staker), 495);
+    assert_eq!(setup.token.balance(&keeper), 5);
+
+    let position = setup.client.get_stake(&staker);
+    assert_eq!(position.amount, 595);
+    assert_eq!(position.rewards, 0);
+    assert_eq!(setup.client.total_staked(), 595);
+
+    // Nothing to compound right after compounding
+    assert_eq!(setup.client.compound(&keeper, &staker), 0);
+
+    // Compounded stake keeps earning (less accumulator rounding), and the
+    // pool still balances
+    advance(&env, 50);
+    assert_eq!(setup.client.earned(&staker), 499);
+    assert_eq!(setup.client.sync(), 0);
+}
+
+#[test]
+fn test_compound_own_position_without_bounty() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    set_tiers(&setup);
+    let staker = funded(&setup, 100);
+    let funder = funded(&setup, 1_000);
+
+    setup.client.set_compound_bounty(&setup.admin, &100);
+    setup.client.stake_locked(&staker, &100, &0);
+    let unlock_time = setup.client.get_stake(&staker).unlock_time;
+    setup.client.fund(&funder, &1_000);
+    advance(&env, REWARD_DURATION);
+
+    assert_eq!(setup.client.compound(&staker, &staker), 1_000);
+
+    // Restaked rewards earn the lock's boost without extending it
+    let position = setup.client.get_stake(&staker);
+    assert_eq!(position.amount, 1_100);
+    assert_eq!(position.weight, 1_375);
+    assert_eq!(position.unlock_time, unlock_time);
+
+    let result = setup.client.try_set_compound_bounty(&setup.admin, &1_001);
+    assert_eq!(result, Err(Ok(StakingError::InvalidBounty)));
+}
+
+#[test]
+fn test_reward_stream_pulled_on_interaction() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let alice = funded(&setup, 100);
+    let bob = funded(&setup, 100);
+    let sender = funded(&setup, 2_000);
+
+    let streaming_id = env.register(StreamingContract, ());
+    let streaming = StreamingContractClient::new(&env, &streaming_id);
+    streaming.initialize(&setup.admin);
+
+    let start = env.ledger().timestamp();
+    let stream_id = streaming.create_stream(
+        &sender,
+        &setup.client.address,
+        &setup.token.address,
+        &2_000,
+        &start,
+        &(start + 200),
+    );
+    setup
+        .client
+        .set_reward_stream(&setup.admin, &streaming_id, &stream_id);
+
+    setup.client.stake(&alice, &100);
+
+    // Bob's stake pulls the 500 streamed so far into the schedule
+    advance(&env, 50);
+    setup.client.stake(&bob, &100);
+    assert_eq!(streaming.get_stream(&stream_id).withdrawn, 500);
+    let state = setup.client.get_reward_state();
+    assert_eq!(state.reserve, 500);
+    assert_eq!(state.rate, 5);
+
+    // Claims pull again before paying out
+    advance(&env, 50);
+    setup.client.claim(&alice);
+    assert_eq!(streaming.get_stream(&stream_id).withdrawn, 1_000);
+    assert_eq!(setup.token.balance(&alice), 125);
+}
+
+#[test]
+fn test_cancelled_reward_stream_does_not_block() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let staker = funded(&setup, 100);
+    let sender = funded(&setup, 1_000);
+
+    let streaming_id = env.register(StreamingContract, ());
+    let streaming = StreamingContractClient::new(&env, &streaming_id);
+    streaming.initialize(&setup.admin);
+
+    let start = env.ledger().timestamp();
+    let stream_id = streaming.create_stream(
+        &sender,
+        &setup.client.address,
+        &setup.token.address,
+        &1_000,
+        &start,
+        &(start + 100),
+    );
+    setup
+        .client
+        .set_reward_stream(&setup.admin, &streaming_id, &stream_id);
+
+    advance(&env, 10);
+    streaming.cancel(&stream_id);
+    setup.client.stake(&staker, &100);
+    assert_eq!(setup.client.get_stake(&staker).amount, 100);
+
+    setup.client.clear_reward_stream(&setup.admin);
+    assert_eq!(setup.client.get_reward_stream(), None);
+}
+
+const UNBONDING_PERIOD: u32 = 10;
+
+fn next_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_unbonding_queue_withdraws_in_order() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let staker = funded(&setup, 100);
+    let funder = funded(&setup, 1_000);
+
+    setup
+        .client
+        .set_unbonding_period(&setup.admin, &UNBONDING_PERIOD);
+    setup.client.stake(&staker, &100);
+    setup.client.fund(&funder, &1_000);
+
+    let result = setup.client.try_unstake(&staker, &40);
+    assert_eq!(result, Err(Ok(StakingError::UnbondingRequired)));
+
+    let start = env.ledger().sequence();
+    assert_eq!(setup.client.unbond(&staker, &40), start + UNBONDING_PERIOD);
+    next_ledgers(&env, 5);
+    setup.client.unbond(&staker, &20);
+
+    // Unbonding stake stops counting at once but stays in the contract
+    assert_eq!(setup.client.get_stake(&staker).amount, 40);
+    assert_eq!(setup.client.total_staked(), 40);
+    assert_eq!(setup.client.total_unbonding(), 60);
+    assert_eq!(
+        setup.client.pending_unbonds(&staker),
+        vec![
+            &env,
+            Unbond {
+                amount: 40,
+                release_ledger: start + UNBONDING_PERIOD,
+            },
+            Unbond {
+                amount: 20,
+                release_ledger: start + 5 + UNBONDING_PERIOD,
+            },
+        ]
+    );
+
+    let result = setup.client.try_withdraw_unbonded(&staker);
+    assert_eq!(result, Err(Ok(StakingError::NothingToWithdraw)));
+
+    next_ledgers(&env, 5);
+    assert_eq!(setup.client.withdraw_unbonded(&staker), 40);
+    assert_eq!(setup.token.balance(&staker), 40);
+    assert_eq!(setup.client.pending_unbonds(&staker).len(), 1);
+
+    next_ledgers(&env, 5);
+    assert_eq!(setup.client.withdraw_unbonded(&staker), 20);
+    assert_eq!(setup.token.balance(&staker), 60);
+    assert_eq!(setup.client.pending_unbonds(&staker).len(), 0);
+    assert_eq!(setup.client.total_unbonding(), 0);
+
+    // Queued funds were never taken for rewards
+    advance(&env, REWARD_DURATION);
+    assert_eq!(setup.client.claim(&staker), 1_000);
+}
+
+#[test]
+fn test_cancel_unbond_restakes() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let staker = funded(&setup, 100);
+
+    setup
+        .client
+        .set_unbonding_period(&setup.admin, &UNBONDING_PERIOD);
+    setup.client.stake(&staker, &100);
+    setup.client.unbond(&staker, &30);
+    setup.client.unbond(&staker, &20);
+
+    let result = setup.client.try_cancel_unbond(&staker, &2);
+    assert_eq!(result, Err(Ok(StakingError::UnbondNotFound)));
+
+    assert_eq!(setup.client.cancel_unbond(&staker, &0), 30);
+    assert_eq!(setup.client.get_stake(&staker).amount, 80);
+    assert_eq!(setup.client.total_staked(), 80);
+    assert_eq!(setup.client.total_unbonding(), 20);
+    assert_eq!(setup.client.pending_unbonds(&staker).len(), 1);
+    assert_eq!(
+        setup.client.pending_unbonds(&staker).get(0).unwrap().amount,
+        20
+    );
+
+    let result = setup.client.try_unbond(&staker, &81);
+    assert_eq!(result, Err(Ok(StakingError::InsufficientStake)));
+}
+
+#[test]
+fn test_operator_earns_commission() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let staker = funded(&setup, 100);
+    let funder = funded(&setup, 1_000);
+    let operator = Address::generate(&env);
+
+    setup
+        .client
+        .register_operator(&operator, &symbol_short!("node1"), &1_000);
+    setup.client.delegate(&staker, &operator);
+    setup.client.stake(&staker, &100);
+    setup.client.fund(&funder, &1_000);
+
+    let record = setup.client.get_operator(&operator);
+    assert_eq!(record.delegated, 100);
+    assert_eq!(record.delegators, 1);
+
+    advance(&env, REWARD_DURATION);
+    assert_eq!(setup.client.earned(&staker), 900);
+    assert_eq!(setup.client.claim(&staker), 900);
+    assert_eq!(setup.client.get_operator(&operator).commission, 100);
+
+    assert_eq!(setup.client.claim_commission(&operator), 100);
+    assert_eq!(setup.token.balance(&operator), 100);
+    assert_eq!(setup.client.get_operator(&operator).commission, 0);
+
+    // Withdrawn stake leaves the operator's total
+    setup.client.unstake(&staker, &40);
+    assert_eq!(setup.client.get_operator(&operator).delegated, 60);
+}
+
+#[test]
+fn test_redelegate_and_commission_caps() {
+    let env = Env::default();
+    let setup = setup_test(&env);
+    let staker = funded(&setup, 100);
+    let funder = funded(&setup, 1_000);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    let result = setup
+        .client
+        .try_register_operator(&first, &symbol_short!("first"), &2_001);
+    assert_eq!(result, Err(Ok(StakingError::CommissionTooHigh)));
+
+    setup
+        .client
+        .register_operator(&first, &symbol_short!("first"), &2_000);
+    setup
+        .client
+        .register_operator(&second, &symbol_short!("second"), &0);
+    let result = setup
+        .client
+        .try_register_operator(&first, &symbol_short!("again"), &0);
+    assert_eq!(result, Err(Ok(StakingError::OperatorExists)));
+
+    setup.client.stake(&staker, &100);
+    setup.client.delegate(&staker, &first);
+    let result = setup.client.try_delegate(&staker, &first);
+    assert_eq!(result, Err(Ok(StakingError::AlreadyDelegated)));
+    setup.client.fund(&funder, &1_000);
+
+    // Half the period at 20%, then half at nothing
+    advance(&env, 50);
+    setup.client.delegate(&staker, &second);
+    assert_eq!(setup.client.delegation(&staker), Some(second.clone()));
+    assert_eq!(setup.client.get_operator(&first).delegated, 0);
+    assert_eq!(setup.client.get_operator(&first).delegators, 0);
+    assert_eq!(setup.client.get_operator(&second).delegated, 100);
+
+    advance(&env, 50);
+    assert_eq!(setup.client.earned(&staker), 900);
+    assert_eq!(setup.client.get_operator(&first).commission, 100);
+
+    setup.client.set_max_commission(&setup.admin, &500);
+    let result = setup.client.try_set_commission(&second, &600);
+    assert_eq!(result, Err(Ok(StakingError::CommissionTooHigh)));
+    setup.client.set_commission(&second, &500);
+    assert_eq!(setup.client.get_operator(&second).commission_bps, 500);
+
+    setup.client.undelegate(&staker);
+    assert_eq!(setup.client.delegation(&staker), None);
+    assert_eq!(setup.client.get_operator(&second).delegated, 0);
+    let result = setup.client.try_undelegate(&staker);
+    assert_eq!(result, Err(Ok(StakingError::NotDelegated)));
+}
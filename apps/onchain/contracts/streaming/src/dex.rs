@@ -0,0 +1,31 @@
+use crate::errors::StreamError;
+use crate::storage::PaymentSwap;
+use lumenpulse_dex::DexError;
+use soroban_sdk::{Address, Env};
+
+/// Swap the payment the current contract holds into `token_out` through
+/// `router` and return how much arrived
+pub fn swap(
+    env: &Env,
+    router: &Address,
+    payment: &PaymentSwap,
+    token_out: &Address,
+) -> Result<i128, StreamError> {
+    lumenpulse_dex::swap_exact_in(
+        env,
+        router,
+        &payment.token_in,
+        token_out,
+        payment.amount_in,
+        payment.min_amount_out,
+    )
+    .map_err(dex_error)
+}
+
+fn dex_error(error: DexError) -> StreamError {
+    match error {
+        DexError::InvalidAmount => StreamError::InvalidAmount,
+        DexError::SlippageExceeded => StreamError::SlippageExceeded,
+        _ => StreamError::SwapFailed,
+    }
+}
@@ -0,0 +1 @@
+pub use lumenpulse_streaming_interface::StreamError;
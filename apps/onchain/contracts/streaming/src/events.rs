@@ -0,0 +1,132 @@
+use lumenpulse_oracle::OracleConfig;
+use soroban_sdk::{contractevent, Address, BytesN};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamCreatedEvent {
+    #[topic]
+    pub sender: Address,
+    #[topic]
+    pub recipient: Address,
+    pub stream_id: u64,
+    pub token: Address,
+    pub deposit: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawnEvent {
+    #[topic]
+    pub recipient: Address,
+    #[topic]
+    pub stream_id: u64,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamCancelledEvent {
+    #[topic]
+    pub sender: Address,
+    #[topic]
+    pub stream_id: u64,
+    pub recipient_amount: i128,
+    pub sender_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceDepositedEvent {
+    #[topic]
+    pub sender: Address,
+    #[topic]
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceWithdrawnEvent {
+    #[topic]
+    pub sender: Address,
+    #[topic]
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorApprovedEvent {
+    #[topic]
+    pub sender: Address,
+    #[topic]
+    pub operator: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ToppedUpEvent {
+    #[topic]
+    pub sender: Address,
+    #[topic]
+    pub stream_id: u64,
+    pub amount: i128,
+    pub deposit: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgraderSetEvent {
+    pub upgrader: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradedEvent {
+    pub new_wasm_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleSetEvent {
+    pub config: OracleConfig,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DexRouterSetEvent {
+    pub router: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PairEnabledEvent {
+    #[topic]
+    pub token_in: Address,
+    #[topic]
+    pub token_out: Address,
+    pub enabled: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentSwappedEvent {
+    #[topic]
+    pub sender: Address,
+    #[topic]
+    pub stream_id: u64,
+    pub token_in: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+}
@@ -0,0 +1,1016 @@
+#![no_std]
+
+mod dex;
+mod errors;
+mod events;
+mod oracle;
+mod storage;
+mod token;
+
+use errors::StreamError;
+use lumenpulse_oracle::OracleConfig;
+use lumenpulse_pausable::Pausable;
+use lumenpulse_upgradeable::Upgradeable;
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+use storage::{
+    DataKey, OperatorAllowance, OperatorKey, PaymentSwap, StreamData, StreamKind, StreamRate,
+    UsdStream, UsdTerms, WithdrawResult, WithdrawStatus,
+};
+
+/// Upper bound on the number of streams settled by a single `withdraw_many`
+const MAX_BATCH_SIZE: u32 = 50;
+
+#[contract]
+pub struct StreamingContract;
+
+#[contractimpl]
+impl StreamingContract {
+    /// Initialize the contract with an admin address
+    pub fn initialize(env: Env, admin: Address) -> Result<(), StreamError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(StreamError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        // Store admin address and initialize stream ID counter
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::NextStreamId, &0u64);
+
+        events::InitializedEvent { admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Create a stream funded directly from the sender's token balance
+    pub fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<u64, StreamError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_initialized(&env)?;
+
+        // Require sender authorization
+        sender.require_auth();
+
+        Self::validate_schedule(&env, amount, start_time, end_time)?;
+
+        // Transfer tokens from sender to contract
+        token::transfer(
+            &env,
+            &token,
+            &sender,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        Ok(Self::store_stream(
+            &env,
+            StreamData {
+                id: 0,
+                sender,
+                recipient,
+                token,
+                deposit: amount,
+                start_time,
+                end_time,
+                withdrawn: 0,
+                cancelled: false,
+                kind: StreamKind::Linear,
+                max_total: None,
+            },
+        ))
+    }
+
+    /// Pre-deposit tokens that operators may later use to create streams on
+    /// the sender's behalf
+    pub fn deposit(
+        env: Env,
+        sender: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), StreamError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_initialized(&env)?;
+
+        // Require sender authorization
+        sender.require_auth();
+
+        if amount <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+
+        token::transfer(
+            &env,
+            &token,
+            &sender,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        // Credit the sender's pre-deposited balance
+        let key = DataKey::SenderBalance(sender.clone(), token.clone());
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(balance + amount));
+
+        events::BalanceDepositedEvent {
+            sender,
+            token,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Withdraw unused pre-deposited tokens back to the sender
+    pub fn withdraw_deposit(
+        env: Env,
+        sender: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), StreamError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_initialized(&env)?;
+
+        // Require sender authorization
+        sender.require_auth();
+
+        if amount <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+
+        Self::debit_sender_balance(&env, &sender, &token, amount)?;
+
+        token::transfer(
+            &env,
+            &token,
+            &env.current_contract_address(),
+            &sender,
+            &amount,
+        );
+
+        events::BalanceWithdrawnEvent {
+            sender,
+            token,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Allow an operator to create streams from the sender's pre-deposited
+    /// balance, up to `amount` in total and until `expiration_ledger`.
+    /// Approving again replaces the previous allowance.
+    pub fn approve_operator(
+        env: Env,
+        sender: Address,
+        operator: Address,
+        token: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<(), StreamError> {
+        Self::require_initialized(&env)?;
+
+        // Require sender authorization
+        sender.require_auth();
+
+        if amount < 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+
+        // A non-zero allowance must not already be expired
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            return Err(StreamError::InvalidExpiration);
+        }
+
+        let key = DataKey::Operator(OperatorKey {
+            sender: sender.clone(),
+            operator: operator.clone(),
+            token: token.clone(),
+        });
+        env.storage().persistent().set(
+            &key,
+            &OperatorAllowance {
+                amount,
+                expiration_ledger,
+            },
+        );
+
+        events::OperatorApprovedEvent {
+            sender,
+            operator,
+            token,
+            amount,
+            expiration_ledger,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Create a stream on behalf of `sender`, funded from their pre-deposited
+    /// balance and charged against the operator's allowance
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream_from(
+        env: Env,
+        operator: Address,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<u64, StreamError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_initialized(&env)?;
+
+        // Require operator authorization
+        operator.require_auth();
+
+        Self::validate_schedule(&env, amount, start_time, end_time)?;
+
+        // Spend the operator allowance
+        let key = DataKey::Operator(OperatorKey {
+            sender: sender.clone(),
+            operator,
+            token: token.clone(),
+        });
+        let mut allowance: OperatorAllowance = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(StreamError::InsufficientAllowance)?;
+
+        if allowance.expiration_ledger < env.ledger().sequence() {
+            return Err(StreamError::AllowanceExpired);
+        }
+        if allowance.amount < amount {
+            return Err(StreamError::InsufficientAllowance);
+        }
+
+        Self::debit_sender_balance(&env, &sender, &token, amount)?;
+
+        allowance.amount -= amount;
+        env.storage().persistent().set(&key, &allowance);
+
+        Ok(Self::store_stream(
+            &env,
+            StreamData {
+                id: 0,
+                sender,
+                recipient,
+                token,
+                deposit: amount,
+                start_time,
+                end_time,
+                withdrawn: 0,
+                cancelled: false,
+                kind: StreamKind::Linear,
+                max_total: None,
+            },
+        ))
+    }
+
+    /// Create a stream that accrues at a fixed rate of `rate.amount` tokens
+    /// every `rate.period` seconds, funded with `deposit` up front.
+    ///
+    /// With `max_total` set, the stream ends once that total has accrued and
+    /// may be topped up to at most `max_total`. Without it the stream is
+    /// open-ended: accrual is capped by the funded amount, and top-ups cover
+    /// any accrued shortfall first.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_rate_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        rate: StreamRate,
+        start_time: u64,
+        deposit: i128,
+        max_total: Option<i128>,
+    ) -> Result<u64, StreamError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_initialized(&env)?;
+
+        // Require sender authorization
+        sender.require_auth();
+
+        if rate.amount <= 0 || rate.period == 0 {
+            return Err(StreamError::InvalidRate);
+        }
+        if deposit <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+        if start_time < env.ledger().timestamp() {
+            return Err(StreamError::InvalidTimeRange);
+        }
+
+        // The end is only known when the total is capped; round up so the
+        // final partial period still accrues in full
+        let end_time = match max_total {
+            Some(max_total) => {
+                if deposit > max_total {
+                    return Err(StreamError::ExceedsMaxTotal);
+                }
+                let periods = (max_total as u128).div_ceil(rate.amount as u128);
+                let duration = periods
+                    .checked_mul(rate.period as u128)
+                    .ok_or(StreamError::InvalidRate)?;
+                start_time
+                    .checked_add(u64::try_from(duration).map_err(|_| StreamError::InvalidRate)?)
+                    .ok_or(StreamError::InvalidRate)?
+            }
+            None => u64::MAX,
+        };
+
+        token::transfer(
+            &env,
+            &token,
+            &sender,
+            &env.current_contract_address(),
+            &deposit,
+        );
+
+        Ok(Self::store_stream(
+            &env,
+            StreamData {
+                id: 0,
+                sender,
+                recipient,
+                token,
+                deposit,
+                start_time,
+                end_time,
+                withdrawn: 0,
+                cancelled: false,
+                kind: StreamKind::Rate(rate),
+                max_total,
+            },
+        ))
+    }
+
+    /// Create a stream paying `terms.value` USD linearly between
+    /// `start_time` and `end_time`, in `token` converted at the oracle price
+    /// when each withdrawal is made. Withdrawals fail while the price is
+    /// outside the terms' bounds, protecting the sender from paying out at a
+    /// manipulated or crashed rate. The stream is funded with `deposit`
+    /// tokens and may be topped up if the token's price falls.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_usd_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        terms: UsdTerms,
+        start_time: u64,
+        end_time: u64,
+        deposit: i128,
+    ) -> Result<u64, StreamError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_initialized(&env)?;
+
+        // Require sender authorization
+        sender.require_auth();
+
+        Self::get_oracle(env.clone()).ok_or(StreamError::OracleNotSet)?;
+        Self::validate_schedule(&env, terms.value, start_time, end_time)?;
+        if terms.min_price <= 0 || terms.min_price > terms.max_price {
+            return Err(StreamError::InvalidPriceBounds);
+        }
+        if deposit <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+
+        token::transfer(
+            &env,
+            &token,
+            &sender,
+            &env.current_contract_address(),
+            &deposit,
+        );
+
+        Ok(Self::store_stream(
+            &env,
+            StreamData {
+                id: 0,
+                sender,
+                recipient,
+                token,
+                deposit,
+                start_time,
+                end_time,
+                withdrawn: 0,
+                cancelled: false,
+                kind: StreamKind::Usd(UsdStream { terms, settled: 0 }),
+                max_total: None,
+            },
+        ))
+    }
+
+    /// Create a linear stream in `token` funded by a payment in another
+    /// token, converted through the DEX router at creation. The sender pays
+    /// `payment.amount_in` and the stream's deposit is whatever the swap
+    /// returns, which must be at least `payment.min_amount_out`. The pair
+    /// must be enabled by the admin.
+    pub fn create_swapped_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        payment: PaymentSwap,
+        token: Address,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<u64, StreamError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_initialized(&env)?;
+
+        // Require sender authorization
+        sender.require_auth();
+
+        Self::validate_schedule(&env, payment.amount_in, start_time, end_time)?;
+        let router = Self::get_dex_router(env.clone()).ok_or(StreamError::DexNotSet)?;
+        if !Self::is_pair_enabled(env.clone(), payment.token_in.clone(), token.clone()) {
+            return Err(StreamError::PairNotEnabled);
+        }
+
+        token::transfer(
+            &env,
+            &payment.token_in,
+            &sender,
+            &env.current_contract_address(),
+            &payment.amount_in,
+        );
+        let deposit = dex::swap(&env, &router, &payment, &token)?;
+        if deposit <= 0 {
+            return Err(StreamError::SlippageExceeded);
+        }
+
+        let stream_id = Self::store_stream(
+            &env,
+            StreamData {
+                id: 0,
+                sender: sender.clone(),
+                recipient,
+                token,
+                deposit,
+                start_time,
+                end_time,
+                withdrawn: 0,
+                cancelled: false,
+                kind: StreamKind::Linear,
+                max_total: None,
+            },
+        );
+
+        events::PaymentSwappedEvent {
+            sender,
+            stream_id,
+            token_in: payment.token_in,
+            amount_in: payment.amount_in,
+            amount_out: deposit,
+        }
+        .publish(&env);
+
+        Ok(stream_id)
+    }
+
+    /// Add funds to a rate-denominated or USD stream (sender only)
+    pub fn top_up(env: Env, stream_id: u64, amount: i128) -> Result<(), StreamError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_initialized(&env)?;
+
+        let mut stream: StreamData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Stream(stream_id))
+            .ok_or(StreamError::StreamNotFound)?;
+
+        // Require sender authorization
+        stream.sender.require_auth();
+
+        if stream.cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+        if stream.kind == StreamKind::Linear {
+            return Err(StreamError::NotRateStream);
+        }
+        if amount <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+
+        let deposit = stream.deposit + amount;
+        if stream
+            .max_total
+            .is_some_and(|max_total| deposit > max_total)
+        {
+            return Err(StreamError::ExceedsMaxTotal);
+        }
+
+        token::transfer(
+            &env,
+            &stream.token,
+            &stream.sender,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        stream.deposit = deposit;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stream(stream_id), &stream);
+
+        events::ToppedUpEvent {
+            sender: stream.sender,
+            stream_id,
+            amount,
+            deposit,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Withdraw everything that has streamed to the recipient so far
+    pub fn withdraw(env: Env, stream_id: u64) -> Result<i128, StreamError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_initialized(&env)?;
+        Self::withdraw_available(&env, stream_id, true)
+    }
+
+    /// Settle several streams in one invocation. Funds always go to each
+    /// stream's recipient, so anyone (e.g. a keeper) may call this. A stream
+    /// that cannot be withdrawn from is reported in its result instead of
+    /// failing the whole batch.
+    pub fn withdraw_many(
+        env: Env,
+        stream_ids: Vec<u64>,
+    ) -> Result<Vec<WithdrawResult>, StreamError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_initialized(&env)?;
+
+        if stream_ids.len() > MAX_BATCH_SIZE {
+            return Err(StreamError::BatchTooLarge);
+        }
+
+        let mut results = Vec::new(&env);
+        for stream_id in stream_ids.iter() {
+            let (amount, status) = match Self::withdraw_available(&env, stream_id, false) {
+                Ok(amount) => (amount, WithdrawStatus::Withdrawn),
+                Err(StreamError::StreamNotFound) => (0, WithdrawStatus::StreamNotFound),
+                Err(StreamError::StreamCancelled) => (0, WithdrawStatus::StreamCancelled),
+                Err(StreamError::NothingToWithdraw) => (0, WithdrawStatus::NothingToWithdraw),
+                Err(
+                    StreamError::OracleNotSet
+                    | StreamError::PriceUnavailable
+                    | StreamError::StalePrice
+                    | StreamError::PriceOutOfBounds,
+                ) => (0, WithdrawStatus::PriceUnavailable),
+                Err(error) => return Err(error),
+            };
+            results.push_back(WithdrawResult {
+                stream_id,
+                amount,
+                status,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Cancel a stream (sender only). The streamed portion is paid out to the
+    /// recipient and the remainder is refunded to the sender. A USD stream
+    /// can only be cancelled while the oracle gives an in-bounds price, so
+    /// the sender cannot cancel to avoid paying what has accrued.
+    pub fn cancel(env: Env, stream_id: u64) -> Result<(), StreamError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_initialized(&env)?;
+
+        let mut stream: StreamData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Stream(stream_id))
+            .ok_or(StreamError::StreamNotFound)?;
+
+        // Require sender authorization
+        stream.sender.require_auth();
+
+        if stream.cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+
+        let now = env.ledger().timestamp();
+        let (recipient_amount, value) = Self::owed(&env, &stream, now)?;
+        let sender_amount = stream.deposit - stream.withdrawn - recipient_amount;
+
+        Self::settle(&mut stream, recipient_amount, value);
+        stream.cancelled = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stream(stream_id), &stream);
+
+        let contract_address = env.current_contract_address();
+        if recipient_amount > 0 {
+            token::transfer(
+                &env,
+                &stream.token,
+                &contract_address,
+                &stream.recipient,
+                &recipient_amount,
+            );
+        }
+        if sender_amount > 0 {
+            token::transfer(
+                &env,
+                &stream.token,
+                &contract_address,
+                &stream.sender,
+                &sender_amount,
+            );
+        }
+
+        events::StreamCancelledEvent {
+            sender: stream.sender,
+            stream_id,
+            recipient_amount,
+            sender_amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get stream data
+    pub fn get_stream(env: Env, stream_id: u64) -> Result<StreamData, StreamError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Stream(stream_id))
+            .ok_or(StreamError::StreamNotFound)
+    }
+
+    /// Get the amount the recipient could withdraw right now
+    pub fn withdrawable_amount(env: Env, stream_id: u64) -> Result<i128, StreamError> {
+        let stream: StreamData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Stream(stream_id))
+            .ok_or(StreamError::StreamNotFound)?;
+
+        if stream.cancelled {
+            return Ok(0);
+        }
+
+        let now = env.ledger().timestamp();
+        Ok(Self::owed(&env, &stream, now)?.0)
+    }
+
+    /// Get the sender's unused pre-deposited balance for a token
+    pub fn get_deposit_balance(env: Env, sender: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SenderBalance(sender, token))
+            .unwrap_or(0)
+    }
+
+    /// Get the remaining operator allowance; expired allowances read as zero
+    pub fn get_operator_allowance(
+        env: Env,
+        sender: Address,
+        operator: Address,
+        token: Address,
+    ) -> i128 {
+        let key = DataKey::Operator(OperatorKey {
+            sender,
+            operator,
+            token,
+        });
+        match env.storage().persistent().get::<_, OperatorAllowance>(&key) {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => {
+                allowance.amount
+            }
+            _ => 0,
+        }
+    }
+
+    /// Let `upgrader` replace the contract's WASM without the upgrade
+    /// delay (admin only). Factory-deployed instances name the factory here.
+    pub fn set_upgrader(env: Env, upgrader: Address) -> Result<(), StreamError> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Upgrader, &upgrader);
+
+        events::UpgraderSetEvent { upgrader }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Address allowed to upgrade the contract without the delay, if any
+    pub fn get_upgrader(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Upgrader)
+    }
+
+    /// Set the SEP-40 price feeds used for price conversions on this
+    /// network and the oldest price they may give (admin only)
+    pub fn set_oracle(env: Env, config: OracleConfig) -> Result<(), StreamError> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        if !config.is_valid() {
+            return Err(StreamError::InvalidOracleConfig);
+        }
+        env.storage().instance().set(&DataKey::Oracle, &config);
+
+        events::OracleSetEvent { config }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Price feeds used for price conversions, if any
+    pub fn get_oracle(env: Env) -> Option<OracleConfig> {
+        env.storage().instance().get(&DataKey::Oracle)
+    }
+
+    /// Value of `amount` of `token` in the feeds' base asset, at the latest
+    /// fresh price. Falls back through the configured feeds.
+    pub fn quote_value(env: Env, token: Address, amount: i128) -> Result<i128, StreamError> {
+        let config = Self::get_oracle(env.clone()).ok_or(StreamError::OracleNotSet)?;
+        oracle::value_of(&env, &config, &token, amount)
+    }
+
+    /// Set the DEX router payments are converted through (admin only)
+    pub fn set_dex_router(env: Env, router: Address) -> Result<(), StreamError> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::DexRouter, &router);
+
+        events::DexRouterSetEvent { router }.publish(&env);
+
+        Ok(())
+    }
+
+    /// DEX router payments are converted through, if any
+    pub fn get_dex_router(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::DexRouter)
+    }
+
+    /// Allow or stop paying in `token_in` to fund streams in `token_out`
+    /// (admin only)
+    pub fn set_pair_enabled(
+        env: Env,
+        token_in: Address,
+        token_out: Address,
+        enabled: bool,
+    ) -> Result<(), StreamError> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        let key = DataKey::DexPair(token_in.clone(), token_out.clone());
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        events::PairEnabledEvent {
+            token_in,
+            token_out,
+            enabled,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Whether payments in `token_in` may fund streams in `token_out`
+    pub fn is_pair_enabled(env: Env, token_in: Address, token_out: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::DexPair(token_in, token_out))
+    }
+
+    /// Replace the contract's WASM at once (upgrader only), for the
+    /// factory's rollouts. Storage is kept, and the new WASM is recorded as
+    /// the next release so the admin can roll it back. The admin upgrades
+    /// through `propose_upgrade` and `execute_upgrade`, after the delay.
+    pub fn upgrade(
+        env: Env,
+        caller: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), StreamError> {
+        Self::require_initialized(&env)?;
+        if Self::get_upgrader(env.clone()) != Some(caller.clone()) {
+            return Err(StreamError::Unauthorized);
+        }
+        caller.require_auth();
+
+        lumenpulse_upgradeable::install_next(&env, &new_wasm_hash)
+            .map_err(|_| StreamError::UpgradePending)?;
+
+        events::UpgradedEvent { new_wasm_hash }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get admin address
+    pub fn get_admin(env: Env) -> Result<Address, StreamError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(StreamError::NotInitialized)
+    }
+}
+
+impl StreamingContract {
+    fn require_initialized(env: &Env) -> Result<(), StreamError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(StreamError::NotInitialized);
+        }
+        Ok(())
+    }
+
+    fn validate_schedule(
+        env: &Env,
+        amount: i128,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<(), StreamError> {
+        if amount <= 0 {
+            return Err(StreamError::InvalidAmount);
+        }
+        if start_time < env.ledger().timestamp() || end_time <= start_time {
+            return Err(StreamError::InvalidTimeRange);
+        }
+        Ok(())
+    }
+
+    fn withdraw_available(
+        env: &Env,
+        stream_id: u64,
+        require_recipient_auth: bool,
+    ) -> Result<i128, StreamError> {
+        let mut stream: StreamData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Stream(stream_id))
+            .ok_or(StreamError::StreamNotFound)?;
+
+        if require_recipient_auth {
+            stream.recipient.require_auth();
+        }
+
+        if stream.cancelled {
+            return Err(StreamError::StreamCancelled);
+        }
+
+        let now = env.ledger().timestamp();
+        let (available, value) = Self::owed(env, &stream, now)?;
+        if available <= 0 {
+            return Err(StreamError::NothingToWithdraw);
+        }
+
+        Self::settle(&mut stream, available, value);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stream(stream_id), &stream);
+
+        token::transfer(
+            env,
+            &stream.token,
+            &env.current_contract_address(),
+            &stream.recipient,
+            &available,
+        );
+
+        events::WithdrawnEvent {
+            recipient: stream.recipient,
+            stream_id,
+            amount: available,
+        }
+        .publish(env);
+
+        Ok(available)
+    }
+
+    /// Tokens owed to the recipient at `now`, and for USD streams the USD
+    /// value they settle
+    fn owed(env: &Env, stream: &StreamData, now: u64) -> Result<(i128, i128), StreamError> {
+        let streamed = match &stream.kind {
+            StreamKind::Rate(rate) => lumenpulse_accrual::rate(
+                stream.deposit,
+                rate.amount,
+                rate.period,
+                stream.start_time,
+                now,
+            ),
+            StreamKind::Linear => {
+                lumenpulse_accrual::linear(stream.deposit, stream.start_time, stream.end_time, now)
+            }
+            StreamKind::Usd(usd) => return Self::owed_usd(env, stream, usd, now),
+        };
+        Ok((lumenpulse_accrual::claimable(streamed, stream.withdrawn), 0))
+    }
+
+    /// Convert the USD accrued but not yet settled into tokens at the
+    /// current price, limited to what the stream still holds. The value
+    /// credited is that of the tokens actually paid, so rounding dust stays
+    /// owed rather than being lost.
+    fn owed_usd(
+        env: &Env,
+        stream: &StreamData,
+        usd: &UsdStream,
+        now: u64,
+    ) -> Result<(i128, i128), StreamError> {
+        let due =
+            lumenpulse_accrual::linear(usd.terms.value, stream.start_time, stream.end_time, now)
+                - usd.settled;
+        if due <= 0 {
+            return Ok((0, 0));
+        }
+
+        let config = Self::get_oracle(env.clone()).ok_or(StreamError::OracleNotSet)?;
+        let price = oracle::bounded_price(env, &config, &stream.token, &usd.terms)?;
+        let tokens = oracle::to_tokens(due, &price)?.min(stream.deposit - stream.withdrawn);
+        Ok((tokens, oracle::to_value(tokens, &price)?))
+    }
+
+    fn settle(stream: &mut StreamData, amount: i128, value: i128) {
+        stream.withdrawn += amount;
+        if let StreamKind::Usd(usd) = &mut stream.kind {
+            usd.settled += value;
+        }
+    }
+
+    fn debit_sender_balance(
+        env: &Env,
+        sender: &Address,
+        token: &Address,
+        amount: i128,
+    ) -> Result<(), StreamError> {
+        let key = DataKey::SenderBalance(sender.clone(), token.clone());
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if balance < amount {
+            return Err(StreamError::InsufficientBalance);
+        }
+        env.storage().persistent().set(&key, &(balance - amount));
+        Ok(())
+    }
+
+    /// Persist a new stream under the next available id and return the id
+    fn store_stream(env: &Env, mut stream: StreamData) -> u64 {
+        let stream_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextStreamId)
+            .unwrap_or(0);
+
+        stream.id = stream_id;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stream(stream_id), &stream);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextStreamId, &(stream_id + 1));
+
+        events::StreamCreatedEvent {
+            sender: stream.sender,
+            recipient: stream.recipient,
+            stream_id,
+            token: stream.token,
+            deposit: stream.deposit,
+            start_time: stream.start_time,
+            end_time: stream.end_time,
+        }
+        .publish(env);
+
+        stream_id
+    }
+}
+
+impl Upgradeable for StreamingContract {
+    fn upgrade_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_upgradeable::upgradeable!(StreamingContract);
+
+impl Pausable for StreamingContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(StreamingContract);
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,53 @@
+use crate::errors::StreamError;
+use crate::storage::UsdTerms;
+use lumenpulse_oracle::{Asset, FeedPrice, OracleConfig, OracleError};
+use soroban_sdk::{Address, Env};
+
+/// Value of `amount` of `token` in the feeds' base asset, at the latest
+/// price no older than the config allows
+pub fn value_of(
+    env: &Env,
+    config: &OracleConfig,
+    token: &Address,
+    amount: i128,
+) -> Result<i128, StreamError> {
+    let price = price_of(env, config, token)?;
+    to_value(amount, &price)
+}
+
+/// Latest fresh price of `token`, which must lie within the bounds `terms`
+/// accept
+pub fn bounded_price(
+    env: &Env,
+    config: &OracleConfig,
+    token: &Address,
+    terms: &UsdTerms,
+) -> Result<FeedPrice, StreamError> {
+    let price = price_of(env, config, token)?;
+    if price.price < terms.min_price || price.price > terms.max_price {
+        return Err(StreamError::PriceOutOfBounds);
+    }
+    Ok(price)
+}
+
+/// Value of `amount` tokens at `price`, rounded down
+pub fn to_value(amount: i128, price: &FeedPrice) -> Result<i128, StreamError> {
+    lumenpulse_oracle::to_base(amount, price.price, price.decimals).map_err(price_error)
+}
+
+/// Tokens worth `value` at `price`, rounded down
+pub fn to_tokens(value: i128, price: &FeedPrice) -> Result<i128, StreamError> {
+    lumenpulse_oracle::from_base(value, price.price, price.decimals).map_err(price_error)
+}
+
+fn price_of(env: &Env, config: &OracleConfig, token: &Address) -> Result<FeedPrice, StreamError> {
+    let asset = Asset::Stellar(token.clone());
+    lumenpulse_oracle::fresh_price(env, config, &asset).map_err(price_error)
+}
+
+fn price_error(error: OracleError) -> StreamError {
+    match error {
+        OracleError::StalePrice => StreamError::StalePrice,
+        _ => StreamError::PriceUnavailable,
+    }
+}
@@ -0,0 +1,35 @@
+use soroban_sdk::{contracttype, Address};
+
+pub use lumenpulse_streaming_interface::{
+    PaymentSwap, StreamData, StreamKind, StreamRate, UsdStream, UsdTerms, WithdrawResult,
+    WithdrawStatus,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                           // -> Address
+    NextStreamId,                    // -> u64
+    Stream(u64),                     // stream_id -> StreamData
+    SenderBalance(Address, Address), // (sender, token) -> i128
+    Operator(OperatorKey),           // -> OperatorAllowance
+    Upgrader,                        // -> Address
+    Oracle,                          // -> OracleConfig
+    DexRouter,                       // -> Address
+    DexPair(Address, Address),       // (token_in, token_out) -> bool
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorKey {
+    pub sender: Address,
+    pub operator: Address,
+    pub token: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorAllowance {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
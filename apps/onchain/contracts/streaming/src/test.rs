@@ -0,0 +1,1188 @@
+extern crate std;
+
+use crate::errors::StreamError;
+use crate::storage::{
+    PaymentSwap, StreamKind, StreamRate, UsdTerms, WithdrawResult, WithdrawStatus,
+};
+use crate::{StreamingContract, StreamingContractClient};
+use lumenpulse_oracle::{Asset, OracleConfig};
+use lumenpulse_pausable::PauseError;
+use lumenpulse_streaming_interface::StreamingClient;
+use lumenpulse_test_utils::{
+    assert_golden,
+    auth::{assert_auth_matrix, Outcome},
+    create_token,
+    golden::Transcript,
+};
+use lumenpulse_upgradeable::{UpgradeError, DEFAULT_UPGRADE_DELAY};
+use mock_oracle::MockOracleContractClient;
+use mock_router::{MockRouterContractClient, RATE_SCALE};
+use proptest::prelude::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, EnvTestConfig, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, IntoVal, InvokeError, MuxedAddress, Vec,
+};
+
+/// Token that silently keeps a 1% cut of every transfer
+#[contract]
+struct SkimmingToken;
+
+#[contractimpl]
+impl SkimmingToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: MuxedAddress, amount: i128) {
+        Self::mint(env.clone(), from, -amount);
+        Self::mint(env, to.address(), amount - amount / 100);
+    }
+}
+
+fn setup_test<'a>(
+    env: &Env,
+) -> (
+    StreamingContractClient<'a>,
+    Address,
+    Address,
+    Address,
+    TokenClient<'a>,
+) {
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let recipient = Address::generate(env);
+
+    // Create token and fund the sender for streaming
+    let token = create_token(env, &admin);
+    token.mint(&sender, 10_000_000);
+
+    // Register and initialize contract
+    let contract_id = env.register(StreamingContract, ());
+    let client = StreamingContractClient::new(env, &contract_id);
+    client.initialize(&admin);
+
+    (client, admin, sender, recipient, token.client)
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _, _) = setup_test(&env);
+
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_double_initialization_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _, _) = setup_test(&env);
+
+    let result = client.try_initialize(&admin);
+    assert_eq!(result, Err(Ok(StreamError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_create_stream() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let end_time = start_time + 1_000;
+    let stream_id = client.create_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &1_000_000,
+        &start_time,
+        &end_time,
+    );
+    assert_eq!(stream_id, 0);
+
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.sender, sender);
+    assert_eq!(stream.recipient, recipient);
+    assert_eq!(stream.deposit, 1_000_000);
+    assert_eq!(stream.withdrawn, 0);
+    assert!(!stream.cancelled);
+
+    assert_eq!(token_client.balance(&client.address), 1_000_000);
+    assert_eq!(token_client.balance(&sender), 9_000_000);
+}
+
+#[test]
+fn test_create_stream_invalid_params() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let result = client.try_create_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &0,
+        &start_time,
+        &(start_time + 1_000),
+    );
+    assert_eq!(result, Err(Ok(StreamError::InvalidAmount)));
+
+    let result = client.try_create_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &1_000,
+        &start_time,
+        &start_time,
+    );
+    assert_eq!(result, Err(Ok(StreamError::InvalidTimeRange)));
+}
+
+#[test]
+fn test_create_stream_rejects_short_delivery() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, _) = setup_test(&env);
+    let token = env.register(SkimmingToken, ());
+    SkimmingTokenClient::new(&env, &token).mint(&sender, &10_000);
+
+    // The contract would receive 990 but record a 1_000 deposit, so the
+    // stream is refused rather than left promising funds it does not hold
+    let start_time = env.ledger().timestamp() + 100;
+    let result = client.try_create_stream(
+        &sender,
+        &recipient,
+        &token,
+        &1_000,
+        &start_time,
+        &(start_time + 1_000),
+    );
+    assert_eq!(
+        result,
+        Err(Err(InvokeError::Contract(
+            lumenpulse_sac::TransferError::TransferFailed as u32
+        )))
+    );
+    assert_eq!(
+        client.try_get_stream(&0),
+        Err(Ok(StreamError::StreamNotFound))
+    );
+    assert_eq!(
+        SkimmingTokenClient::new(&env, &token).balance(&sender),
+        10_000
+    );
+}
+
+#[test]
+fn test_withdraw_linear() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let end_time = start_time + 1_000;
+    let stream_id = client.create_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &1_000_000,
+        &start_time,
+        &end_time,
+    );
+
+    // Nothing streams before the start
+    assert_eq!(client.withdrawable_amount(&stream_id), 0);
+    let result = client.try_withdraw(&stream_id);
+    assert_eq!(result, Err(Ok(StreamError::NothingToWithdraw)));
+
+    // Halfway through
+    env.ledger().set_timestamp(start_time + 500);
+    assert_eq!(client.withdraw(&stream_id), 500_000);
+    assert_eq!(token_client.balance(&recipient), 500_000);
+
+    // Past the end everything is withdrawable
+    env.ledger().set_timestamp(end_time + 10);
+    assert_eq!(client.withdraw(&stream_id), 500_000);
+    assert_eq!(token_client.balance(&recipient), 1_000_000);
+    assert_eq!(client.get_stream(&stream_id).withdrawn, 1_000_000);
+}
+
+#[test]
+fn test_cancel_splits_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let end_time = start_time + 1_000;
+    let stream_id = client.create_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &1_000_000,
+        &start_time,
+        &end_time,
+    );
+
+    env.ledger().set_timestamp(start_time + 250);
+    client.cancel(&stream_id);
+
+    assert_eq!(token_client.balance(&recipient), 250_000);
+    assert_eq!(token_client.balance(&sender), 9_750_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert!(client.get_stream(&stream_id).cancelled);
+
+    let result = client.try_withdraw(&stream_id);
+    assert_eq!(result, Err(Ok(StreamError::StreamCancelled)));
+    let result = client.try_cancel(&stream_id);
+    assert_eq!(result, Err(Ok(StreamError::StreamCancelled)));
+}
+
+#[test]
+fn test_lifecycle_matches_golden() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+    let mut transcript = Transcript::new();
+
+    let stream_id = client.create_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &1_000_000,
+        &100,
+        &1_100,
+    );
+    transcript.record(&env, "create_stream", &stream_id);
+    env.ledger().set_timestamp(600);
+    let withdrawn = client.withdraw(&stream_id);
+    transcript.record(&env, "withdraw", &withdrawn);
+    env.ledger().set_timestamp(850);
+    client.cancel(&stream_id);
+    transcript.record(&env, "cancel", &());
+    let stream = client.get_stream(&stream_id);
+    transcript.record(&env, "get_stream", &stream);
+
+    assert_golden!("stream_lifecycle", transcript);
+}
+
+#[test]
+fn test_deposit_and_withdraw_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, _, token_client) = setup_test(&env);
+
+    client.deposit(&sender, &token_client.address, &3_000_000);
+    assert_eq!(
+        client.get_deposit_balance(&sender, &token_client.address),
+        3_000_000
+    );
+    assert_eq!(token_client.balance(&client.address), 3_000_000);
+
+    client.withdraw_deposit(&sender, &token_client.address, &1_000_000);
+    assert_eq!(
+        client.get_deposit_balance(&sender, &token_client.address),
+        2_000_000
+    );
+    assert_eq!(token_client.balance(&sender), 8_000_000);
+
+    let result = client.try_withdraw_deposit(&sender, &token_client.address, &5_000_000);
+    assert_eq!(result, Err(Ok(StreamError::InsufficientBalance)));
+}
+
+#[test]
+fn test_operator_creates_stream_from_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+    let operator = Address::generate(&env);
+
+    client.deposit(&sender, &token_client.address, &3_000_000);
+    let expiration_ledger = env.ledger().sequence() + 1_000;
+    client.approve_operator(
+        &sender,
+        &operator,
+        &token_client.address,
+        &2_000_000,
+        &expiration_ledger,
+    );
+
+    let start_time = env.ledger().timestamp() + 100;
+    let end_time = start_time + 1_000;
+    let stream_id = client.create_stream_from(
+        &operator,
+        &sender,
+        &recipient,
+        &token_client.address,
+        &1_500_000,
+        &start_time,
+        &end_time,
+    );
+
+    // The stream belongs to the sender, not the operator
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.sender, sender);
+    assert_eq!(stream.deposit, 1_500_000);
+
+    assert_eq!(
+        client.get_deposit_balance(&sender, &token_client.address),
+        1_500_000
+    );
+    assert_eq!(
+        client.get_operator_allowance(&sender, &operator, &token_client.address),
+        500_000
+    );
+
+    // Spending beyond the remaining allowance fails even with enough balance
+    let result = client.try_create_stream_from(
+        &operator,
+        &sender,
+        &recipient,
+        &token_client.address,
+        &600_000,
+        &start_time,
+        &end_time,
+    );
+    assert_eq!(result, Err(Ok(StreamError::InsufficientAllowance)));
+}
+
+#[test]
+fn test_operator_allowance_capped_by_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+    let operator = Address::generate(&env);
+
+    client.deposit(&sender, &token_client.address, &100_000);
+    client.approve_operator(
+        &sender,
+        &operator,
+        &token_client.address,
+        &1_000_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    let start_time = env.ledger().timestamp() + 100;
+    let result = client.try_create_stream_from(
+        &operator,
+        &sender,
+        &recipient,
+        &token_client.address,
+        &200_000,
+        &start_time,
+        &(start_time + 1_000),
+    );
+    assert_eq!(result, Err(Ok(StreamError::InsufficientBalance)));
+}
+
+#[test]
+fn test_operator_allowance_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+    let operator = Address::generate(&env);
+
+    client.deposit(&sender, &token_client.address, &1_000_000);
+    let expiration_ledger = env.ledger().sequence() + 10;
+    client.approve_operator(
+        &sender,
+        &operator,
+        &token_client.address,
+        &1_000_000,
+        &expiration_ledger,
+    );
+
+    env.ledger().set_sequence_number(expiration_ledger + 1);
+    assert_eq!(
+        client.get_operator_allowance(&sender, &operator, &token_client.address),
+        0
+    );
+
+    let start_time = env.ledger().timestamp() + 100;
+    let result = client.try_create_stream_from(
+        &operator,
+        &sender,
+        &recipient,
+        &token_client.address,
+        &100_000,
+        &start_time,
+        &(start_time + 1_000),
+    );
+    assert_eq!(result, Err(Ok(StreamError::AllowanceExpired)));
+}
+
+#[test]
+fn test_unapproved_operator_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+    let operator = Address::generate(&env);
+
+    client.deposit(&sender, &token_client.address, &1_000_000);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let result = client.try_create_stream_from(
+        &operator,
+        &sender,
+        &recipient,
+        &token_client.address,
+        &100_000,
+        &start_time,
+        &(start_time + 1_000),
+    );
+    assert_eq!(result, Err(Ok(StreamError::InsufficientAllowance)));
+}
+
+#[test]
+fn test_approve_operator_rejects_past_expiration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, _, token_client) = setup_test(&env);
+    let operator = Address::generate(&env);
+
+    env.ledger().set_sequence_number(100);
+    let result =
+        client.try_approve_operator(&sender, &operator, &token_client.address, &1_000, &99);
+    assert_eq!(result, Err(Ok(StreamError::InvalidExpiration)));
+
+    // Revoking with a zero amount is always allowed
+    client.approve_operator(&sender, &operator, &token_client.address, &0, &0);
+    assert_eq!(
+        client.get_operator_allowance(&sender, &operator, &token_client.address),
+        0
+    );
+}
+
+#[test]
+fn test_withdraw_many_reports_per_stream_results() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+    let other_recipient = Address::generate(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let end_time = start_time + 1_000;
+    let first = client.create_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &1_000,
+        &start_time,
+        &end_time,
+    );
+    let second = client.create_stream(
+        &sender,
+        &other_recipient,
+        &token_client.address,
+        &2_000,
+        &start_time,
+        &end_time,
+    );
+    let cancelled = client.create_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &3_000,
+        &start_time,
+        &end_time,
+    );
+    client.cancel(&cancelled);
+
+    env.ledger().set_timestamp(start_time + 500);
+    let results = client.withdraw_many(&vec![&env, first, second, cancelled, 99]);
+
+    assert_eq!(
+        results,
+        vec![
+            &env,
+            WithdrawResult {
+                stream_id: first,
+                amount: 500,
+                status: WithdrawStatus::Withdrawn,
+            },
+            WithdrawResult {
+                stream_id: second,
+                amount: 1_000,
+                status: WithdrawStatus::Withdrawn,
+            },
+            WithdrawResult {
+                stream_id: cancelled,
+                amount: 0,
+                status: WithdrawStatus::StreamCancelled,
+            },
+            WithdrawResult {
+                stream_id: 99,
+                amount: 0,
+                status: WithdrawStatus::StreamNotFound,
+            },
+        ]
+    );
+    assert_eq!(token_client.balance(&recipient), 500);
+    assert_eq!(token_client.balance(&other_recipient), 1_000);
+
+    // A second pass in the same ledger has nothing left to settle
+    let results = client.withdraw_many(&vec![&env, first]);
+    assert_eq!(
+        results.get(0).unwrap().status,
+        WithdrawStatus::NothingToWithdraw
+    );
+}
+
+#[test]
+fn test_withdraw_many_batch_too_large() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, _) = setup_test(&env);
+
+    let mut stream_ids = Vec::new(&env);
+    for stream_id in 0..51u64 {
+        stream_ids.push_back(stream_id);
+    }
+    let result = client.try_withdraw_many(&stream_ids);
+    assert_eq!(result, Err(Ok(StreamError::BatchTooLarge)));
+}
+
+#[test]
+fn test_rate_stream_with_max_total() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+
+    // 100 tokens per day, capped at 250 tokens
+    let rate = StreamRate {
+        amount: 100,
+        period: 86_400,
+    };
+    let start_time = env.ledger().timestamp() + 10;
+    let stream_id = client.create_rate_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &rate,
+        &start_time,
+        &250,
+        &Some(250),
+    );
+
+    // The end rounds up to the third (partial) day
+    let stream = client.get_stream(&stream_id);
+    assert_eq!(stream.end_time, start_time + 3 * 86_400);
+    assert_eq!(stream.kind, StreamKind::Rate(rate));
+    assert_eq!(stream.max_total, Some(250));
+
+    env.ledger().set_timestamp(start_time + 43_200);
+    assert_eq!(client.withdrawable_amount(&stream_id), 50);
+
+    env.ledger().set_timestamp(start_time + 2 * 86_400);
+    assert_eq!(client.withdraw(&stream_id), 200);
+
+    env.ledger().set_timestamp(start_time + 10 * 86_400);
+    assert_eq!(client.withdraw(&stream_id), 50);
+    assert_eq!(token_client.balance(&recipient), 250);
+
+    // Capped streams cannot be funded above their maximum
+    let result = client.try_top_up(&stream_id, &1);
+    assert_eq!(result, Err(Ok(StreamError::ExceedsMaxTotal)));
+}
+
+#[test]
+fn test_open_ended_rate_stream_top_up() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+
+    // 1 token per second with only 100 funded
+    let rate = StreamRate {
+        amount: 1,
+        period: 1,
+    };
+    let start_time = env.ledger().timestamp();
+    let stream_id = client.create_rate_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &rate,
+        &start_time,
+        &100,
+        &None,
+    );
+    assert_eq!(client.get_stream(&stream_id).end_time, u64::MAX);
+
+    // Accrual is capped by funding
+    env.ledger().set_timestamp(start_time + 150);
+    assert_eq!(client.withdrawable_amount(&stream_id), 100);
+    assert_eq!(client.withdraw(&stream_id), 100);
+
+    // A top-up first covers the accrued shortfall
+    client.top_up(&stream_id, &1_000);
+    assert_eq!(client.withdrawable_amount(&stream_id), 50);
+    assert_eq!(client.get_stream(&stream_id).deposit, 1_100);
+
+    // Cancelling pays out accrual and refunds the unstreamed remainder
+    env.ledger().set_timestamp(start_time + 200);
+    client.cancel(&stream_id);
+    assert_eq!(token_client.balance(&recipient), 200);
+    assert_eq!(token_client.balance(&sender), 10_000_000 - 200);
+}
+
+#[test]
+fn test_rate_stream_invalid_params() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp();
+    let result = client.try_create_rate_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &StreamRate {
+            amount: 100,
+            period: 0,
+        },
+        &start_time,
+        &100,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(StreamError::InvalidRate)));
+
+    let result = client.try_create_rate_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &StreamRate {
+            amount: 100,
+            period: 60,
+        },
+        &start_time,
+        &500,
+        &Some(400),
+    );
+    assert_eq!(result, Err(Ok(StreamError::ExceedsMaxTotal)));
+}
+
+#[test]
+fn test_top_up_rejects_linear_stream() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let stream_id = client.create_stream(
+        &sender,
+        &recipient,
+        &token_client.address,
+        &1_000,
+        &start_time,
+        &(start_time + 1_000),
+    );
+
+    let result = client.try_top_up(&stream_id, &100);
+    assert_eq!(result, Err(Ok(StreamError::NotRateStream)));
+}
+
+#[test]
+fn test_stream_lumen_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, sender, recipient, _) = setup_test(&env);
+
+    // Streams hold the native Lumenpulse token like any other SEP-41 asset
+    let lumen_id = env.register(lumen_token::LumenToken, ());
+    let lumen = lumen_token::LumenTokenClient::new(&env, &lumen_id);
+    lumen.initialize(
+        &admin,
+        &7,
+        &soroban_sdk::String::from_str(&env, "LumenPulse"),
+        &soroban_sdk::String::from_str(&env, "LMN"),
+    );
+    lumen.mint(&admin, &sender, &1_000);
+
+    let start_time = env.ledger().timestamp();
+    let stream_id = client.create_stream(
+        &sender,
+        &recipient,
+        &lumen_id,
+        &1_000,
+        &start_time,
+        &(start_time + 100),
+    );
+    assert_eq!(lumen.balance(&client.address), 1_000);
+
+    env.ledger().set_timestamp(start_time + 40);
+    client.withdraw(&stream_id);
+    assert_eq!(lumen.balance(&recipient), 400);
+}
+
+#[test]
+fn test_upgrade_is_for_the_upgrader_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let wasm = include_bytes!("../../factory/testdata/streaming.wasm");
+    let wasm_hash = env.deployer().upload_contract_wasm(wasm.as_slice());
+    let (client, admin, sender, _, _) = setup_test(&env);
+    let upgrader = Address::generate(&env);
+
+    assert_eq!(client.get_upgrader(), None);
+    client.set_upgrader(&upgrader);
+    assert_eq!(client.get_upgrader(), Some(upgrader.clone()));
+
+    // The admin goes through the timelock like any other upgrade
+    let result = client.try_upgrade(&sender, &wasm_hash);
+    assert_eq!(result, Err(Ok(StreamError::Unauthorized)));
+    let result = client.try_upgrade(&admin, &wasm_hash);
+    assert_eq!(result, Err(Ok(StreamError::Unauthorized)));
+
+    // A rollout does not override an upgrade the admin has queued
+    client.propose_upgrade(&admin, &wasm_hash, &1);
+    let result = client.try_upgrade(&upgrader, &wasm_hash);
+    assert_eq!(result, Err(Ok(StreamError::UpgradePending)));
+    client.cancel_upgrade(&admin);
+
+    // Each rollout is recorded as a release the admin can roll back
+    client.upgrade(&upgrader, &wasm_hash);
+    client.upgrade(&upgrader, &wasm_hash);
+    assert_eq!(client.current_version(), 2);
+    client.rollback_upgrade(&admin);
+    assert_eq!(client.current_version(), 1);
+    assert_eq!(client.get_upgrader(), Some(upgrader));
+}
+
+#[test]
+fn test_privileged_entrypoints_turn_away_outsiders() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, sender, _, token) = setup_test(&env);
+    let outsider = Address::generate(&env);
+    let wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
+    let config = OracleConfig {
+        feeds: vec![&env, Address::generate(&env)],
+        max_age: 300,
+    };
+
+    assert_auth_matrix(
+        &env,
+        &client.address,
+        &[
+            (
+                &outsider,
+                "set_upgrader",
+                (outsider.clone(),).into_val(&env),
+                Outcome::Denied,
+            ),
+            (
+                &outsider,
+                "set_oracle",
+                (config,).into_val(&env),
+                Outcome::Denied,
+            ),
+            (
+                &outsider,
+                "set_dex_router",
+                (outsider.clone(),).into_val(&env),
+                Outcome::Denied,
+            ),
+            (
+                &outsider,
+                "set_pair_enabled",
+                (token.address.clone(), sender.clone(), true).into_val(&env),
+                Outcome::Denied,
+            ),
+            (
+                &outsider,
+                "upgrade",
+                (outsider.clone(), wasm_hash.clone()).into_val(&env),
+                Outcome::Fails(StreamError::Unauthorized.into()),
+            ),
+            // The admin cannot skip the timelock either
+            (
+                &admin,
+                "upgrade",
+                (admin.clone(), wasm_hash.clone()).into_val(&env),
+                Outcome::Fails(StreamError::Unauthorized.into()),
+            ),
+            (
+                &outsider,
+                "propose_upgrade",
+                (outsider.clone(), wasm_hash, 2u32).into_val(&env),
+                Outcome::Fails(UpgradeError::Unauthorized.into()),
+            ),
+            (
+                &outsider,
+                "pause",
+                (outsider.clone(),).into_val(&env),
+                Outcome::Fails(PauseError::Unauthorized.into()),
+            ),
+            (
+                &outsider,
+                "grant_pauser",
+                (outsider.clone(), outsider.clone()).into_val(&env),
+                Outcome::Fails(PauseError::Unauthorized.into()),
+            ),
+            (
+                &admin,
+                "set_dex_router",
+                (outsider.clone(),).into_val(&env),
+                Outcome::Allowed,
+            ),
+        ],
+    );
+    assert_eq!(client.get_upgrader(), None);
+    assert_eq!(client.get_dex_router(), Some(outsider));
+    assert!(!client.paused());
+}
+
+#[test]
+fn test_timelocked_upgrade_and_rollback() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // The factory's fixture is this contract built to WASM
+    let wasm = include_bytes!("../../factory/testdata/streaming.wasm");
+    let wasm_hash = env.deployer().upload_contract_wasm(wasm.as_slice());
+    let (client, admin, sender, _, _) = setup_test(&env);
+
+    let result = client.try_propose_upgrade(&sender, &wasm_hash, &1);
+    assert_eq!(result, Err(Ok(UpgradeError::Unauthorized)));
+    client.propose_upgrade(&admin, &wasm_hash, &1);
+    let result = client.try_execute_upgrade(&admin);
+    assert_eq!(result, Err(Ok(UpgradeError::TooEarly)));
+
+    env.ledger().set_timestamp(DEFAULT_UPGRADE_DELAY);
+    client.execute_upgrade(&admin);
+    assert_eq!(client.current_version(), 1);
+
+    // The installed WASM carries the same upgrade surface
+    client.propose_upgrade(&admin, &wasm_hash, &2);
+    env.ledger().set_timestamp(2 * DEFAULT_UPGRADE_DELAY);
+    client.execute_upgrade(&admin);
+    assert_eq!(client.current_version(), 2);
+    client.rollback_upgrade(&admin);
+    assert_eq!(client.current_version(), 1);
+    assert_eq!(client.previous_release(), None);
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_oracle_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, token) = setup_test(&env);
+    let result = client.try_quote_value(&token.address, &1_000);
+    assert_eq!(result, Err(Ok(StreamError::OracleNotSet)));
+
+    let mut config = OracleConfig {
+        feeds: Vec::new(&env),
+        max_age: 300,
+    };
+    let result = client.try_set_oracle(&config);
+    assert_eq!(result, Err(Ok(StreamError::InvalidOracleConfig)));
+
+    // An address that is not a price feed never has a fresh price
+    config.feeds.push_back(Address::generate(&env));
+    client.set_oracle(&config);
+    assert_eq!(client.get_oracle(), Some(config));
+    let result = client.try_quote_value(&token.address, &1_000);
+    assert_eq!(result, Err(Ok(StreamError::StalePrice)));
+}
+
+#[test]
+fn test_usd_stream() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token) = setup_test(&env);
+    let feed =
+        MockOracleContractClient::new(&env, &env.register(mock_oracle::MockOracleContract, ()));
+    feed.initialize(&Asset::Other(symbol_short!("USD")), &7, &300);
+    let asset = Asset::Stellar(token.address.clone());
+    let terms = UsdTerms {
+        value: 100_000,
+        min_price: 500_000,
+        max_price: 5_000_000,
+    };
+    env.ledger().set_timestamp(1_000);
+
+    let result = client.try_create_usd_stream(
+        &sender,
+        &recipient,
+        &token.address,
+        &terms,
+        &1_000,
+        &1_100,
+        &700_000,
+    );
+    assert_eq!(result, Err(Ok(StreamError::OracleNotSet)));
+
+    client.set_oracle(&OracleConfig {
+        feeds: vec![&env, feed.address.clone()],
+        max_age: 600,
+    });
+    let inverted = UsdTerms {
+        min_price: 5_000_000,
+        max_price: 500_000,
+        ..terms.clone()
+    };
+    let result = client.try_create_usd_stream(
+        &sender,
+        &recipient,
+        &token.address,
+        &inverted,
+        &1_000,
+        &1_100,
+        &700_000,
+    );
+    assert_eq!(result, Err(Ok(StreamError::InvalidPriceBounds)));
+
+    // $100,000 units over 100 seconds, with the token at $0.10
+    let stream_id = client.create_usd_stream(
+        &sender,
+        &recipient,
+        &token.address,
+        &terms,
+        &1_000,
+        &1_100,
+        &700_000,
+    );
+    feed.set_price(&asset, &1_000_000);
+
+    env.ledger().set_timestamp(1_050);
+    assert_eq!(client.withdrawable_amount(&stream_id), 500_000);
+    assert_eq!(client.withdraw(&stream_id), 500_000);
+
+    // At $0.20 the next quarter of the schedule costs half as many tokens
+    feed.set_price(&asset, &2_000_000);
+    env.ledger().set_timestamp(1_075);
+    assert_eq!(client.withdraw(&stream_id), 125_000);
+
+    // Prices outside the sender's bounds are refused,
+    env.ledger().set_timestamp(1_080);
+    feed.set_price(&asset, &6_000_000);
+    let result = client.try_withdraw(&stream_id);
+    assert_eq!(result, Err(Ok(StreamError::PriceOutOfBounds)));
+    // and a keeper's batch reports them apart from an empty stream
+    let results = client.withdraw_many(&vec![&env, stream_id]);
+    assert_eq!(
+        results.get(0).unwrap().status,
+        WithdrawStatus::PriceUnavailable
+    );
+
+    // So are prices older than the oracle config allows
+    feed.set_price(&asset, &1_000_000);
+    env.ledger().set_timestamp(1_700);
+    let result = client.try_withdraw(&stream_id);
+    assert_eq!(result, Err(Ok(StreamError::StalePrice)));
+
+    // The stream only pays what it still holds; a top-up covers the rest
+    feed.set_price(&asset, &1_000_000);
+    assert_eq!(client.withdraw(&stream_id), 75_000);
+    let result = client.try_withdraw(&stream_id);
+    assert_eq!(result, Err(Ok(StreamError::NothingToWithdraw)));
+    client.top_up(&stream_id, &200_000);
+    assert_eq!(client.withdraw(&stream_id), 175_000);
+    assert_eq!(token.balance(&recipient), 875_000);
+
+    match client.get_stream(&stream_id).kind {
+        StreamKind::Usd(usd) => assert_eq!(usd.settled, 100_000),
+        kind => panic!("unexpected kind {:?}", kind),
+    }
+
+    // Cancelling refunds what was funded beyond the schedule
+    client.cancel(&stream_id);
+    assert_eq!(token.balance(&sender), 10_000_000 - 875_000);
+}
+
+#[test]
+fn test_swapped_stream() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, sender, recipient, token) = setup_test(&env);
+    let usdc = create_token(&env, &admin);
+    usdc.mint(&sender, 1_000);
+    let router =
+        MockRouterContractClient::new(&env, &env.register(mock_router::MockRouterContract, ()));
+    StellarAssetClient::new(&env, &token.address).mint(&router.address, &100_000);
+    // 1 USDC = 8 stream tokens
+    router.set_rate(&usdc.address, &token.address, &(8 * RATE_SCALE));
+
+    let payment = PaymentSwap {
+        token_in: usdc.address.clone(),
+        amount_in: 1_000,
+        min_amount_out: 8_000,
+    };
+    let result =
+        client.try_create_swapped_stream(&sender, &recipient, &payment, &token.address, &0, &100);
+    assert_eq!(result, Err(Ok(StreamError::DexNotSet)));
+
+    client.set_dex_router(&router.address);
+    let result =
+        client.try_create_swapped_stream(&sender, &recipient, &payment, &token.address, &0, &100);
+    assert_eq!(result, Err(Ok(StreamError::PairNotEnabled)));
+
+    client.set_pair_enabled(&usdc.address, &token.address, &true);
+    assert!(!client.is_pair_enabled(&token.address, &usdc.address));
+
+    // Demanding more than the router pays fails before anything moves
+    let greedy = PaymentSwap {
+        min_amount_out: 8_001,
+        ..payment.clone()
+    };
+    let result =
+        client.try_create_swapped_stream(&sender, &recipient, &greedy, &token.address, &0, &100);
+    assert_eq!(result, Err(Ok(StreamError::SwapFailed)));
+    assert_eq!(usdc.balance(&sender), 1_000);
+
+    // The sender pays in USDC and the recipient withdraws the stream token
+    let stream_id =
+        client.create_swapped_stream(&sender, &recipient, &payment, &token.address, &0, &100);
+    assert_eq!(usdc.balance(&sender), 0);
+    assert_eq!(client.get_stream(&stream_id).deposit, 8_000);
+
+    env.ledger().set_timestamp(50);
+    assert_eq!(client.withdraw(&stream_id), 4_000);
+    assert_eq!(token.balance(&recipient), 4_000);
+
+    client.set_pair_enabled(&usdc.address, &token.address, &false);
+    assert!(!client.is_pair_enabled(&usdc.address, &token.address));
+}
+
+#[test]
+fn test_interface_client_drives_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, sender, recipient, token) = setup_test(&env);
+    let streaming = StreamingClient::new(&env, &client.address);
+
+    let stream_id = streaming.create_stream(&sender, &recipient, &token.address, &1_000, &0, &100);
+    env.ledger().set_timestamp(50);
+    assert_eq!(streaming.withdrawable_amount(&stream_id), 500);
+    assert_eq!(streaming.get_stream(&stream_id).kind, StreamKind::Linear);
+    let result = streaming.try_get_stream(&(stream_id + 1));
+    assert_eq!(result, Err(Ok(StreamError::StreamNotFound)));
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+    Create {
+        amount: i128,
+        start_in: u64,
+        duration: u64,
+    },
+    Withdraw(usize),
+    Cancel(usize),
+    Advance(u64),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (1..1_000_000i128, 0..500u64, 1..2_000u64).prop_map(|(amount, start_in, duration)| {
+            Op::Create {
+                amount,
+                start_in,
+                duration,
+            }
+        }),
+        any::<usize>().prop_map(Op::Withdraw),
+        any::<usize>().prop_map(Op::Cancel),
+        (0..1_000u64).prop_map(Op::Advance),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    /// Streams created, withdrawn from and cancelled at random times never
+    /// create or lose funds, and what a stream has accrued never goes down
+    #[test]
+    fn prop_streams_conserve_funds(ops in prop::collection::vec(op(), 1..30)) {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+        let (client, _, sender, recipient, token) = setup_test(&env);
+        let minted = token.balance(&sender);
+
+        let mut ids: std::vec::Vec<u64> = std::vec::Vec::new();
+        let mut accrued: std::vec::Vec<i128> = std::vec::Vec::new();
+        for op in ops {
+            match op {
+                Op::Create { amount, start_in, duration } => {
+                    let start_time = env.ledger().timestamp() + start_in;
+                    ids.push(client.create_stream(
+                        &sender,
+                        &recipient,
+                        &token.address,
+                        &amount,
+                        &start_time,
+                        &(start_time + duration),
+                    ));
+                    accrued.push(0);
+                }
+                Op::Withdraw(i) if !ids.is_empty() => {
+                    let _ = client.try_withdraw(&ids[i % ids.len()]);
+                }
+                Op::Cancel(i) if !ids.is_empty() => {
+                    let _ = client.try_cancel(&ids[i % ids.len()]);
+                }
+                Op::Advance(seconds) => {
+                    env.ledger().with_mut(|ledger| ledger.timestamp += seconds);
+                }
+                _ => {}
+            }
+
+            let mut held = 0;
+            for (id, accrued) in ids.iter().zip(accrued.iter_mut()) {
+                let stream = client.get_stream(id);
+                prop_assert!(0 <= stream.withdrawn && stream.withdrawn <= stream.deposit);
+                if stream.cancelled {
+                    continue;
+                }
+                let withdrawable = client.withdrawable_amount(id);
+                let now = stream.withdrawn + withdrawable;
+                prop_assert!(now >= *accrued && now <= stream.deposit);
+                *accrued = now;
+                held += stream.deposit - stream.withdrawn;
+            }
+            prop_assert_eq!(token.balance(&client.address), held);
+            prop_assert_eq!(
+                token.balance(&sender) + token.balance(&recipient) + held,
+                minted
+            );
+        }
+    }
+}
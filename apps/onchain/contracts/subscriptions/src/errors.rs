@@ -0,0 +1,25 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SubscriptionError {
+    Unauthorized = 1,
+    PlanNotFound = 2,
+    SubscriptionNotFound = 3,
+    InvalidAmount = 4,
+    InvalidPeriod = 5,
+    PlanInactive = 6,
+    AlreadySubscribed = 7,
+    NotActive = 8,
+    ChargeFailed = 9,
+    InvalidDunning = 10,
+    InvalidCurve = 11,
+    NotMetered = 12,
+    InvalidUsagePeriod = 13,
+    IncompatiblePlans = 14,
+    NoRefundPolicy = 15,
+    NotRefundable = 16,
+    InsufficientReserve = 17,
+    InvalidRefundPolicy = 18,
+}
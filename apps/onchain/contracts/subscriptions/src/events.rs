@@ -0,0 +1,137 @@
+use crate::storage::{Billing, Dunning};
+use lumenpulse_refunds::RefundPolicy;
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanCreatedEvent {
+    #[topic]
+    pub plan_id: u64,
+    #[topic]
+    pub merchant: Address,
+    pub token: Address,
+    pub price: i128,
+    pub period: u64,
+    pub dunning: Dunning,
+    pub billing: Billing,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanUpdatedEvent {
+    #[topic]
+    pub plan_id: u64,
+    pub active: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscribedEvent {
+    #[topic]
+    pub subscription_id: u64,
+    #[topic]
+    pub subscriber: Address,
+    pub plan_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChargedEvent {
+    #[topic]
+    pub subscription_id: u64,
+    pub amount: i128,
+    pub next_charge: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChargeFailedEvent {
+    #[topic]
+    pub subscription_id: u64,
+    pub amount: i128,
+    /// Failed attempts in a row, including this one
+    pub failures: u32,
+    pub retry_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveredEvent {
+    #[topic]
+    pub subscription_id: u64,
+    /// Failed attempts before the charge went through
+    pub failures: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LapsedEvent {
+    #[topic]
+    pub subscription_id: u64,
+    pub failures: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancelledEvent {
+    #[topic]
+    pub subscription_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UsageReportedEvent {
+    #[topic]
+    pub subscription_id: u64,
+    pub period: u32,
+    pub units: u64,
+    /// Units reported for the period so far
+    pub total: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanChangedEvent {
+    #[topic]
+    pub subscription_id: u64,
+    pub from_plan: u64,
+    pub to_plan: u64,
+    /// Prorated difference charged for the rest of the period
+    pub charged: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanChangeScheduledEvent {
+    #[topic]
+    pub subscription_id: u64,
+    /// Plan the subscription moves to, or `None` if the change was withdrawn
+    pub plan_id: Option<u64>,
+    pub effective_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundPolicySetEvent {
+    #[topic]
+    pub merchant: Address,
+    pub policy: Option<RefundPolicy>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundReserveUpdatedEvent {
+    #[topic]
+    pub merchant: Address,
+    #[topic]
+    pub token: Address,
+    pub reserve: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundedEvent {
+    #[topic]
+    pub subscription_id: u64,
+    pub amount: i128,
+}
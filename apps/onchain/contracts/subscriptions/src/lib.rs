@@ -0,0 +1,717 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod math;
+mod storage;
+mod token;
+
+use errors::SubscriptionError;
+use lumenpulse_refunds::{RefundError, RefundPolicy};
+use lumenpulse_time::Schedule;
+use math::Rounding;
+use soroban_sdk::{contract, contractimpl, Address, Env};
+use storage::{
+    Billing, Charge, DataKey, Dunning, Metering, Plan, PlanChange, ProcessReport, Subscription,
+    SubscriptionStatus, MAX_PROCESS_BATCH,
+};
+
+/// Recurring pull payments. Merchants register plans that charge a price
+/// every period, and subscribers approve this contract to spend the plan's
+/// token on their behalf. The first period is charged on subscribing;
+/// after that anyone can call `process_due` to charge every subscription
+/// whose period has come round, so merchants need not run their own
+/// billing job. A charge that fails puts the subscription past due and is
+/// retried on later passes, under the plan's dunning policy, until it goes
+/// through or the subscription lapses.
+///
+/// Metered plans bill in arrears instead: the plan's meter reports each
+/// subscription's usage as the period runs, and the period's price plus
+/// its usage, priced along the plan's curve, is charged when it ends.
+///
+/// Subscribers on flat plans can move between plans of the same merchant
+/// and token. Upgrades apply at once for the prorated difference, and
+/// downgrades wait for the end of the paid period.
+///
+/// Merchants may also set a refund policy and fund a reserve to pay
+/// refunds from. A subscriber refunded for its latest charge under the
+/// policy is unsubscribed.
+///
+/// There is no circuit breaker, as the contract has no admin to hold one.
+/// Charges move straight from subscriber to merchant under an allowance
+/// the subscriber can revoke at the token, and the only funds held are
+/// refund reserves, which their merchant can withdraw at any time.
+#[contract]
+pub struct SubscriptionsContract;
+
+#[contractimpl]
+impl SubscriptionsContract {
+    /// Register a plan charging `price` of `token` every `period` seconds,
+    /// paid to `merchant`, retrying failed charges under `dunning`. Returns
+    /// the plan ID.
+    pub fn create_plan(
+        env: Env,
+        merchant: Address,
+        token: Address,
+        price: i128,
+        period: u64,
+        dunning: Dunning,
+    ) -> Result<u64, SubscriptionError> {
+        merchant.require_auth();
+
+        if price <= 0 {
+            return Err(SubscriptionError::InvalidAmount);
+        }
+        Self::store_plan(&env, merchant, token, price, period, dunning, Billing::Flat)
+    }
+
+    /// Register a metered plan. At the end of every `period` seconds it
+    /// charges `price`, which may be zero, plus the usage `metering.meter`
+    /// reported for the period, priced along `metering.curve`. Returns the
+    /// plan ID.
+    pub fn create_metered_plan(
+        env: Env,
+        merchant: Address,
+        token: Address,
+        price: i128,
+        period: u64,
+        dunning: Dunning,
+        metering: Metering,
+    ) -> Result<u64, SubscriptionError> {
+        merchant.require_auth();
+
+        if price < 0 {
+            return Err(SubscriptionError::InvalidAmount);
+        }
+        if !metering.curve.is_valid() {
+            return Err(SubscriptionError::InvalidCurve);
+        }
+        Self::store_plan(
+            &env,
+            merchant,
+            token,
+            price,
+            period,
+            dunning,
+            Billing::Metered(metering),
+        )
+    }
+
+    /// Open or close a plan to new subscribers (merchant only). Existing
+    /// subscriptions keep being charged either way.
+    pub fn set_plan_active(env: Env, plan_id: u64, active: bool) -> Result<(), SubscriptionError> {
+        let mut plan = Self::get_plan(env.clone(), plan_id)?;
+
+        // Require merchant authorization
+        plan.merchant.require_auth();
+
+        plan.active = active;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Plan(plan_id), &plan);
+
+        events::PlanUpdatedEvent { plan_id, active }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Subscribe to a plan, charging its first period now unless the plan
+    /// is metered. The subscriber must have approved this contract to spend
+    /// the plan's token, for at least as many periods as it wants to stay
+    /// subscribed. Returns the subscription ID.
+    pub fn subscribe(
+        env: Env,
+        subscriber: Address,
+        plan_id: u64,
+    ) -> Result<u64, SubscriptionError> {
+        subscriber.require_auth();
+
+        let plan = Self::get_plan(env.clone(), plan_id)?;
+        if !plan.active {
+            return Err(SubscriptionError::PlanInactive);
+        }
+        let key = DataKey::SubscriberPlan(subscriber.clone(), plan_id);
+        if Self::live_subscription(&env, &subscriber, plan_id).is_some() {
+            return Err(SubscriptionError::AlreadySubscribed);
+        }
+
+        // Flat plans are paid in advance, metered plans once the period ends
+        let prepaid = plan.billing == Billing::Flat;
+        if prepaid && !token::pull(&env, &plan.token, &subscriber, &plan.merchant, plan.price) {
+            return Err(SubscriptionError::ChargeFailed);
+        }
+
+        let now = env.ledger().timestamp();
+        let subscription_id = Self::subscription_count(env.clone());
+        let subscription = Subscription {
+            id: subscription_id,
+            plan_id,
+            subscriber: subscriber.clone(),
+            started_at: now,
+            next_charge: now + plan.period,
+            charges: prepaid as u32,
+            failures: 0,
+            retry_at: 0,
+            status: SubscriptionStatus::Active,
+            pending_plan: None,
+            last_charge: Charge {
+                amount: if prepaid { plan.price } else { 0 },
+                ledger: env.ledger().sequence(),
+            },
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Subscription(subscription_id), &subscription);
+        env.storage().persistent().set(&key, &subscription_id);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextSubscriptionId, &(subscription_id + 1));
+
+        events::SubscribedEvent {
+            subscription_id,
+            subscriber,
+            plan_id,
+        }
+        .publish(&env);
+        if prepaid {
+            events::ChargedEvent {
+                subscription_id,
+                amount: plan.price,
+                next_charge: subscription.next_charge,
+            }
+            .publish(&env);
+        }
+
+        Ok(subscription_id)
+    }
+
+    /// Move a subscription to another flat plan of the same merchant and
+    /// token (subscriber only). If the new plan costs more for the rest of
+    /// the paid period than the unused time on the current one is worth,
+    /// the subscription switches now and the difference is charged;
+    /// otherwise the switch is scheduled for the next charge. Changing to
+    /// the current plan withdraws a scheduled switch.
+    pub fn change_plan(
+        env: Env,
+        subscription_id: u64,
+        plan_id: u64,
+    ) -> Result<PlanChange, SubscriptionError> {
+        let mut subscription = Self::get_subscription(env.clone(), subscription_id)?;
+
+        // Require subscriber authorization
+        subscription.subscriber.require_auth();
+
+        if subscription.status != SubscriptionStatus::Active {
+            return Err(SubscriptionError::NotActive);
+        }
+        let key = DataKey::Subscription(subscription_id);
+        if plan_id == subscription.plan_id {
+            if subscription.pending_plan.take().is_none() {
+                return Err(SubscriptionError::IncompatiblePlans);
+            }
+            env.storage().persistent().set(&key, &subscription);
+            events::PlanChangeScheduledEvent {
+                subscription_id,
+                plan_id: None,
+                effective_at: subscription.next_charge,
+            }
+            .publish(&env);
+            return Ok(PlanChange::Withdrawn);
+        }
+
+        let current = Self::get_plan(env.clone(), subscription.plan_id)?;
+        let plan = Self::get_plan(env.clone(), plan_id)?;
+        if !plan.active {
+            return Err(SubscriptionError::PlanInactive);
+        }
+        if current.billing != Billing::Flat
+            || plan.billing != Billing::Flat
+            || current.merchant != plan.merchant
+            || current.token != plan.token
+        {
+            return Err(SubscriptionError::IncompatiblePlans);
+        }
+        if Self::live_subscription(&env, &subscription.subscriber, plan_id).is_some() {
+            return Err(SubscriptionError::AlreadySubscribed);
+        }
+
+        // Credit the unused time on the current plan against the same time
+        // on the new one
+        let remaining = lumenpulse_time::remaining(&env, subscription.next_charge);
+        let credit = math::prorate(current.price, remaining, current.period, Rounding::Down);
+        let cost = math::prorate(plan.price, remaining, plan.period, Rounding::Up);
+
+        if cost < credit {
+            subscription.pending_plan = Some(plan_id);
+            env.storage().persistent().set(&key, &subscription);
+            events::PlanChangeScheduledEvent {
+                subscription_id,
+                plan_id: Some(plan_id),
+                effective_at: subscription.next_charge,
+            }
+            .publish(&env);
+            return Ok(PlanChange::Scheduled(subscription.next_charge));
+        }
+
+        let charged = cost - credit;
+        if charged > 0
+            && !token::pull(
+                &env,
+                &plan.token,
+                &subscription.subscriber,
+                &plan.merchant,
+                charged,
+            )
+        {
+            return Err(SubscriptionError::ChargeFailed);
+        }
+        subscription.pending_plan = None;
+        subscription.last_charge.amount += charged;
+        Self::move_to_plan(&env, &mut subscription, plan_id, charged);
+
+        Ok(PlanChange::Upgraded(charged))
+    }
+
+    /// Record `units` of usage by a subscription in its `period`th period
+    /// (the plan's meter only). Reports for a period add up, so the meter
+    /// may report as usage happens. Periods already billed are closed.
+    /// Returns the units reported for the period so far.
+    pub fn report_usage(
+        env: Env,
+        subscription_id: u64,
+        period: u32,
+        units: u64,
+    ) -> Result<u64, SubscriptionError> {
+        let subscription = Self::get_subscription(env.clone(), subscription_id)?;
+        let plan = Self::get_plan(env.clone(), subscription.plan_id)?;
+        let Billing::Metered(metering) = plan.billing else {
+            return Err(SubscriptionError::NotMetered);
+        };
+
+        // Require meter authorization
+        metering.meter.require_auth();
+
+        if !subscription.is_live() {
+            return Err(SubscriptionError::NotActive);
+        }
+        if units == 0 {
+            return Err(SubscriptionError::InvalidAmount);
+        }
+        let period_start = Schedule::every(subscription.started_at, plan.period).occurrence(period);
+        if period < subscription.charges
+            || period_start.is_none_or(|start| !lumenpulse_time::reached(&env, start))
+        {
+            return Err(SubscriptionError::InvalidUsagePeriod);
+        }
+
+        let total = Self::usage(env.clone(), subscription_id, period) + units;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Usage(subscription_id, period), &total);
+
+        events::UsageReportedEvent {
+            subscription_id,
+            period,
+            units,
+            total,
+        }
+        .publish(&env);
+
+        Ok(total)
+    }
+
+    /// Stop a subscription (subscriber only). The current period is not
+    /// refunded, and a metered plan's open period is not billed.
+    pub fn cancel(env: Env, subscription_id: u64) -> Result<(), SubscriptionError> {
+        let mut subscription = Self::get_subscription(env.clone(), subscription_id)?;
+
+        // Require subscriber authorization
+        subscription.subscriber.require_auth();
+
+        if !subscription.is_live() {
+            return Err(SubscriptionError::NotActive);
+        }
+        subscription.status = SubscriptionStatus::Cancelled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Subscription(subscription_id), &subscription);
+
+        events::CancelledEvent { subscription_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Charge the due period of every running subscription among up to
+    /// `limit` subscriptions starting from ID `cursor`, retrying past-due
+    /// charges whose retry time has come. Anyone may call this, as
+    /// subscribers authorized the charges when subscribing. A
+    /// subscription that has fallen several periods behind is charged one
+    /// period per call.
+    pub fn process_due(env: Env, cursor: u64, limit: u32) -> ProcessReport {
+        let end =
+            Self::subscription_count(env.clone()).min(cursor + limit.min(MAX_PROCESS_BATCH) as u64);
+        let mut report = ProcessReport::default();
+        for subscription_id in cursor..end {
+            if let Ok(subscription) = Self::get_subscription(env.clone(), subscription_id) {
+                Self::process(&env, subscription, &mut report);
+            }
+        }
+        report
+    }
+
+    /// Set or clear the refund policy applied to `merchant`'s charges
+    /// (merchant only)
+    pub fn set_refund_policy(
+        env: Env,
+        merchant: Address,
+        policy: Option<RefundPolicy>,
+    ) -> Result<(), SubscriptionError> {
+        merchant.require_auth();
+
+        lumenpulse_refunds::set_policy(&env, &merchant, &policy).map_err(Self::refund_error)?;
+
+        events::RefundPolicySetEvent { merchant, policy }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Add `amount` of `token` to the reserve `merchant`'s refunds are paid
+    /// from. Returns the new reserve.
+    pub fn fund_refund_reserve(
+        env: Env,
+        merchant: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, SubscriptionError> {
+        merchant.require_auth();
+
+        let reserve = lumenpulse_refunds::fund_reserve(&env, &merchant, &token, amount)
+            .map_err(Self::refund_error)?;
+
+        events::RefundReserveUpdatedEvent {
+            merchant,
+            token,
+            reserve,
+        }
+        .publish(&env);
+
+        Ok(reserve)
+    }
+
+    /// Take `amount` of `token` back out of `merchant`'s refund reserve.
+    /// Returns the new reserve.
+    pub fn withdraw_refund_reserve(
+        env: Env,
+        merchant: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, SubscriptionError> {
+        merchant.require_auth();
+
+        let reserve = lumenpulse_refunds::withdraw_reserve(&env, &merchant, &token, amount)
+            .map_err(Self::refund_error)?;
+
+        events::RefundReserveUpdatedEvent {
+            merchant,
+            token,
+            reserve,
+        }
+        .publish(&env);
+
+        Ok(reserve)
+    }
+
+    /// Refund a subscription's latest charge as far as the merchant's
+    /// policy allows, and cancel it (subscriber only). Returns the amount
+    /// refunded.
+    pub fn request_refund(env: Env, subscription_id: u64) -> Result<i128, SubscriptionError> {
+        let mut subscription = Self::get_subscription(env.clone(), subscription_id)?;
+
+        // Require subscriber authorization
+        subscription.subscriber.require_auth();
+
+        if !subscription.is_live() {
+            return Err(SubscriptionError::NotActive);
+        }
+        let charge = core::mem::take(&mut subscription.last_charge);
+        if charge.amount == 0 {
+            return Err(SubscriptionError::NotRefundable);
+        }
+        let plan = Self::get_plan(env.clone(), subscription.plan_id)?;
+        let refund =
+            lumenpulse_refunds::evaluate(&env, &plan.merchant, charge.amount, charge.ledger)
+                .map_err(Self::refund_error)?;
+
+        subscription.status = SubscriptionStatus::Cancelled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Subscription(subscription_id), &subscription);
+
+        lumenpulse_refunds::execute(
+            &env,
+            &plan.merchant,
+            &plan.token,
+            &subscription.subscriber,
+            refund,
+        )
+        .map_err(Self::refund_error)?;
+
+        events::RefundedEvent {
+            subscription_id,
+            amount: refund,
+        }
+        .publish(&env);
+        events::CancelledEvent { subscription_id }.publish(&env);
+
+        Ok(refund)
+    }
+
+    /// Get a plan by ID
+    pub fn get_plan(env: Env, plan_id: u64) -> Result<Plan, SubscriptionError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Plan(plan_id))
+            .ok_or(SubscriptionError::PlanNotFound)
+    }
+
+    /// Get a subscription by ID
+    pub fn get_subscription(
+        env: Env,
+        subscription_id: u64,
+    ) -> Result<Subscription, SubscriptionError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Subscription(subscription_id))
+            .ok_or(SubscriptionError::SubscriptionNotFound)
+    }
+
+    /// `subscriber`'s latest subscription to a plan, if any
+    pub fn subscription_of(env: Env, subscriber: Address, plan_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SubscriberPlan(subscriber, plan_id))
+    }
+
+    /// Units reported for a subscription's `period`th period
+    pub fn usage(env: Env, subscription_id: u64, period: u32) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Usage(subscription_id, period))
+            .unwrap_or(0)
+    }
+
+    /// `merchant`'s refund policy, if it has one
+    pub fn refund_policy(env: Env, merchant: Address) -> Option<RefundPolicy> {
+        lumenpulse_refunds::policy(&env, &merchant)
+    }
+
+    /// `merchant`'s reserve of `token` for refunds
+    pub fn refund_reserve(env: Env, merchant: Address, token: Address) -> i128 {
+        lumenpulse_refunds::reserve(&env, &merchant, &token)
+    }
+
+    /// Number of subscriptions opened so far
+    pub fn subscription_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::NextSubscriptionId)
+            .unwrap_or(0)
+    }
+}
+
+impl SubscriptionsContract {
+    fn refund_error(error: RefundError) -> SubscriptionError {
+        match error {
+            RefundError::NoPolicy => SubscriptionError::NoRefundPolicy,
+            RefundError::NotRefundable => SubscriptionError::NotRefundable,
+            RefundError::InsufficientReserve => SubscriptionError::InsufficientReserve,
+            RefundError::InvalidPolicy => SubscriptionError::InvalidRefundPolicy,
+            RefundError::InvalidAmount => SubscriptionError::InvalidAmount,
+        }
+    }
+
+    /// `subscriber`'s running subscription to a plan, if any
+    fn live_subscription(env: &Env, subscriber: &Address, plan_id: u64) -> Option<Subscription> {
+        let subscription_id = Self::subscription_of(env.clone(), subscriber.clone(), plan_id)?;
+        Self::get_subscription(env.clone(), subscription_id)
+            .ok()
+            .filter(Subscription::is_live)
+    }
+
+    /// Switch a subscription's plan, keeping its billing date. The caller
+    /// stores the subscription.
+    fn move_to_plan(env: &Env, subscription: &mut Subscription, plan_id: u64, charged: i128) {
+        let from_plan = subscription.plan_id;
+        env.storage().persistent().remove(&DataKey::SubscriberPlan(
+            subscription.subscriber.clone(),
+            from_plan,
+        ));
+        env.storage().persistent().set(
+            &DataKey::SubscriberPlan(subscription.subscriber.clone(), plan_id),
+            &subscription.id,
+        );
+        subscription.plan_id = plan_id;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Subscription(subscription.id), subscription);
+
+        events::PlanChangedEvent {
+            subscription_id: subscription.id,
+            from_plan,
+            to_plan: plan_id,
+            charged,
+        }
+        .publish(env);
+    }
+
+    fn store_plan(
+        env: &Env,
+        merchant: Address,
+        token: Address,
+        price: i128,
+        period: u64,
+        dunning: Dunning,
+        billing: Billing,
+    ) -> Result<u64, SubscriptionError> {
+        if period == 0 {
+            return Err(SubscriptionError::InvalidPeriod);
+        }
+        if dunning.max_failures == 0 || dunning.retry_interval == 0 {
+            return Err(SubscriptionError::InvalidDunning);
+        }
+
+        let plan_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextPlanId)
+            .unwrap_or(0);
+        let plan = Plan {
+            id: plan_id,
+            merchant: merchant.clone(),
+            token: token.clone(),
+            price,
+            period,
+            dunning: dunning.clone(),
+            billing: billing.clone(),
+            active: true,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Plan(plan_id), &plan);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextPlanId, &(plan_id + 1));
+
+        events::PlanCreatedEvent {
+            plan_id,
+            merchant,
+            token,
+            price,
+            period,
+            dunning,
+            billing,
+        }
+        .publish(env);
+
+        Ok(plan_id)
+    }
+
+    /// Amount due for a subscription's next charge: the plan's price, plus
+    /// the billed period's usage on metered plans
+    fn amount_due(env: &Env, plan: &Plan, subscription: &Subscription) -> i128 {
+        match &plan.billing {
+            Billing::Flat => plan.price,
+            Billing::Metered(metering) => {
+                let units = Self::usage(env.clone(), subscription.id, subscription.charges);
+                plan.price + metering.curve.charge(units)
+            }
+        }
+    }
+
+    /// Attempt a subscription's charge if it is due, and record the result
+    fn process(env: &Env, mut subscription: Subscription, report: &mut ProcessReport) {
+        let now = env.ledger().timestamp();
+        let due = match subscription.status {
+            SubscriptionStatus::Active => subscription.next_charge <= now,
+            SubscriptionStatus::PastDue => subscription.retry_at <= now,
+            _ => false,
+        };
+        if !due {
+            return;
+        }
+
+        // A scheduled downgrade takes effect with the first charge of the
+        // new period, unless the subscriber has since joined the plan
+        if let Some(plan_id) = subscription.pending_plan.take() {
+            if Self::live_subscription(env, &subscription.subscriber, plan_id).is_none() {
+                Self::move_to_plan(env, &mut subscription, plan_id, 0);
+            }
+        }
+        let Ok(plan) = Self::get_plan(env.clone(), subscription.plan_id) else {
+            return;
+        };
+        let subscription_id = subscription.id;
+        let amount = Self::amount_due(env, &plan, &subscription);
+
+        if amount == 0
+            || token::pull(
+                env,
+                &plan.token,
+                &subscription.subscriber,
+                &plan.merchant,
+                amount,
+            )
+        {
+            if subscription.status == SubscriptionStatus::PastDue {
+                events::RecoveredEvent {
+                    subscription_id,
+                    failures: subscription.failures,
+                }
+                .publish(env);
+            }
+            subscription.next_charge += plan.period;
+            subscription.charges += 1;
+            subscription.last_charge = Charge {
+                amount,
+                ledger: env.ledger().sequence(),
+            };
+            subscription.failures = 0;
+            subscription.status = SubscriptionStatus::Active;
+            report.charged += 1;
+            events::ChargedEvent {
+                subscription_id,
+                amount,
+                next_charge: subscription.next_charge,
+            }
+            .publish(env);
+        } else {
+            subscription.failures += 1;
+            if subscription.failures >= plan.dunning.max_failures {
+                subscription.status = SubscriptionStatus::Lapsed;
+                report.lapsed += 1;
+                events::LapsedEvent {
+                    subscription_id,
+                    failures: subscription.failures,
+                }
+                .publish(env);
+            } else {
+                subscription.status = SubscriptionStatus::PastDue;
+                subscription.retry_at = now + plan.dunning.retry_interval;
+                report.failed += 1;
+                events::ChargeFailedEvent {
+                    subscription_id,
+                    amount,
+                    failures: subscription.failures,
+                    retry_at: subscription.retry_at,
+                }
+                .publish(env);
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Subscription(subscription_id), &subscription);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,12 @@
+/// Proration math for plan changes
+///
+/// A plan change mid-period credits the unused time left on the old plan
+/// and costs the same time on the new one, each priced pro rata to its
+/// period. Credits round down and costs round up, so a change never pays
+/// out more than the subscriber paid in.
+pub use lumenpulse_fixed::Rounding;
+
+/// Share of `price` per `period` covering `remaining` seconds
+pub fn prorate(price: i128, remaining: u64, period: u64, rounding: Rounding) -> i128 {
+    lumenpulse_fixed::prorate(price, remaining, period, rounding).expect("arithmetic overflow")
+}
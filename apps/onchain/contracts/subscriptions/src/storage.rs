@@ -0,0 +1,197 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+/// Most subscriptions one `process_due` call walks
+pub const MAX_PROCESS_BATCH: u32 = 50;
+
+/// Most tiers a metered plan's price curve may have
+pub const MAX_TIERS: u32 = 10;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextPlanId,                   // -> u64
+    Plan(u64),                    // plan_id -> Plan
+    NextSubscriptionId,           // -> u64
+    Subscription(u64),            // subscription_id -> Subscription
+    SubscriberPlan(Address, u64), // (subscriber, plan_id) -> subscription_id
+    Usage(u64, u32),              // (subscription_id, period) -> u64
+}
+
+/// What a merchant charges: `price` of `token` every `period` seconds,
+/// plus any metered usage
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Plan {
+    pub id: u64,
+    pub merchant: Address,
+    pub token: Address,
+    pub price: i128,
+    pub period: u64,
+    pub dunning: Dunning,
+    pub billing: Billing,
+    /// Whether new subscribers are accepted
+    pub active: bool,
+}
+
+/// When and how a plan's periods are charged
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Billing {
+    /// The price is charged at the start of each period
+    Flat,
+    /// The price plus the period's metered usage is charged at the end of
+    /// each period
+    Metered(Metering),
+}
+
+/// Who reports a metered plan's usage and what it costs
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Metering {
+    /// Operator or oracle allowed to report usage
+    pub meter: Address,
+    pub curve: PriceCurve,
+}
+
+/// Graduated price of a period's usage. The first `included` units are
+/// free, and each tier prices the units above the previous tier up to its
+/// own `up_to`. Units beyond the last tier cost the last tier's price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceCurve {
+    pub included: u64,
+    pub tiers: Vec<PriceTier>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceTier {
+    pub up_to: u64,
+    pub unit_price: i128,
+}
+
+impl PriceCurve {
+    /// Whether the tiers are non-empty, ascending and above the included
+    /// units, with no negative prices
+    pub fn is_valid(&self) -> bool {
+        if self.tiers.is_empty() || self.tiers.len() > MAX_TIERS {
+            return false;
+        }
+        let mut floor = self.included;
+        for tier in self.tiers.iter() {
+            if tier.up_to <= floor || tier.unit_price < 0 {
+                return false;
+            }
+            floor = tier.up_to;
+        }
+        true
+    }
+
+    /// Price of `units` of usage in one period
+    pub fn charge(&self, units: u64) -> i128 {
+        let mut charge = 0;
+        let mut floor = self.included;
+        let mut unit_price = 0;
+        for tier in self.tiers.iter() {
+            if units <= floor {
+                break;
+            }
+            unit_price = tier.unit_price;
+            charge += (units.min(tier.up_to) - floor) as i128 * unit_price;
+            floor = tier.up_to;
+        }
+        if units > floor {
+            charge += (units - floor) as i128 * unit_price;
+        }
+        charge
+    }
+}
+
+/// How a plan handles failed charges: the charge is retried every
+/// `retry_interval` seconds, and the subscription lapses once
+/// `max_failures` attempts in a row have failed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dunning {
+    pub max_failures: u32,
+    pub retry_interval: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubscriptionStatus {
+    Active,
+    /// The due charge failed and is being retried
+    PastDue,
+    /// Too many charges in a row failed, so the subscription stopped
+    Lapsed,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub id: u64,
+    pub plan_id: u64,
+    pub subscriber: Address,
+    /// Time the first period started at
+    pub started_at: u64,
+    /// Time the next period is charged at
+    pub next_charge: u64,
+    /// Periods charged so far. For metered plans this is also the index of
+    /// the period billed next.
+    pub charges: u32,
+    /// Failed attempts at the due charge
+    pub failures: u32,
+    /// While past due, time the charge is next retried at
+    pub retry_at: u64,
+    pub status: SubscriptionStatus,
+    /// Plan a scheduled downgrade moves the subscription to at its next
+    /// charge
+    pub pending_plan: Option<u64>,
+    /// Latest successful charge, zeroed once refunded
+    pub last_charge: Charge,
+}
+
+/// A charge a subscriber paid, which the merchant's refund policy applies to
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Charge {
+    /// Paid for the period, including any upgrade during it
+    pub amount: i128,
+    pub ledger: u32,
+}
+
+impl Subscription {
+    /// Whether the subscription is still running, charges failing or not
+    pub fn is_live(&self) -> bool {
+        matches!(
+            self.status,
+            SubscriptionStatus::Active | SubscriptionStatus::PastDue
+        )
+    }
+}
+
+/// Outcome of a `change_plan` call
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlanChange {
+    /// The subscription moved to the new plan now, paying the prorated
+    /// difference
+    Upgraded(i128),
+    /// The subscription moves to the new plan at its next charge
+    Scheduled(u64),
+    /// A scheduled change was called off
+    Withdrawn,
+}
+
+/// Outcome of one `process_due` call
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProcessReport {
+    pub charged: u32,
+    /// Charges that failed and will be retried
+    pub failed: u32,
+    /// Subscriptions that lapsed after their last allowed failure
+    pub lapsed: u32,
+}
@@ -0,0 +1,343 @@
+use crate::errors::SubscriptionError;
+use crate::storage::{
+    Dunning, Metering, PlanChange, PriceCurve, PriceTier, ProcessReport, SubscriptionStatus,
+};
+use crate::{SubscriptionsContract, SubscriptionsContractClient};
+use lumenpulse_refunds::RefundPolicy;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+const MONTH: u64 = 30 * DAY;
+
+struct Setup<'a> {
+    client: SubscriptionsContractClient<'a>,
+    merchant: Address,
+    token: TokenClient<'a>,
+    plan_id: u64,
+}
+
+impl Setup<'_> {
+    /// A subscriber holding `balance` who approved `allowance`
+    fn subscriber(&self, env: &Env, balance: i128, allowance: i128) -> Address {
+        let subscriber = Address::generate(env);
+        StellarAssetClient::new(env, &self.token.address).mint(&subscriber, &balance);
+        self.token
+            .approve(&subscriber, &self.client.address, &allowance, &1_000);
+        subscriber
+    }
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let merchant = Address::generate(env);
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    let client = SubscriptionsContractClient::new(env, &env.register(SubscriptionsContract, ()));
+    let dunning = Dunning {
+        max_failures: 3,
+        retry_interval: DAY,
+    };
+    let plan_id = client.create_plan(&merchant, &sac.address(), &100, &MONTH, &dunning);
+
+    Setup {
+        client,
+        merchant,
+        token: TokenClient::new(env, &sac.address()),
+        plan_id,
+    }
+}
+
+#[test]
+fn test_subscribe_and_process_due() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let alice = s.subscriber(&env, 1_000, 1_000);
+    let bob = s.subscriber(&env, 1_000, 1_000);
+
+    let first = s.client.subscribe(&alice, &s.plan_id);
+    env.ledger().set_timestamp(MONTH / 2);
+    let second = s.client.subscribe(&bob, &s.plan_id);
+    assert_eq!(s.token.balance(&s.merchant), 200);
+
+    let result = s.client.try_subscribe(&alice, &s.plan_id);
+    assert_eq!(result, Err(Ok(SubscriptionError::AlreadySubscribed)));
+
+    // Only the first subscription is due
+    env.ledger().set_timestamp(MONTH);
+    let report = s.client.process_due(&0, &10);
+    assert_eq!(
+        report,
+        ProcessReport {
+            charged: 1,
+            failed: 0,
+            lapsed: 0
+        }
+    );
+    assert_eq!(s.client.get_subscription(&first).next_charge, 2 * MONTH);
+    assert_eq!(s.client.get_subscription(&second).charges, 1);
+
+    // Nothing is charged twice for the same period
+    assert_eq!(s.client.process_due(&0, &10).charged, 0);
+    env.ledger().set_timestamp(2 * MONTH);
+    assert_eq!(s.client.process_due(&1, &1).charged, 1);
+    assert_eq!(s.client.get_subscription(&second).charges, 2);
+    assert_eq!(s.token.balance(&s.merchant), 400);
+}
+
+#[test]
+fn test_failed_charges_retried_then_lapse() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let alice = s.subscriber(&env, 1_000, 100);
+    let bob = s.subscriber(&env, 150, 1_000);
+    let unapproved = s.subscriber(&env, 1_000, 0);
+
+    let result = s.client.try_subscribe(&unapproved, &s.plan_id);
+    assert_eq!(result, Err(Ok(SubscriptionError::ChargeFailed)));
+
+    // Alice's allowance and Bob's balance only covered the first period
+    let first = s.client.subscribe(&alice, &s.plan_id);
+    let second = s.client.subscribe(&bob, &s.plan_id);
+    env.ledger().set_timestamp(MONTH);
+    let report = s.client.process_due(&0, &10);
+    assert_eq!(report.failed, 2);
+    let subscription = s.client.get_subscription(&first);
+    assert_eq!(subscription.status, SubscriptionStatus::PastDue);
+    assert_eq!(subscription.retry_at, MONTH + DAY);
+
+    // Retries wait for the retry interval
+    assert_eq!(s.client.process_due(&0, &10), ProcessReport::default());
+
+    // Alice tops up her allowance and recovers on the next retry, keeping
+    // her billing date
+    s.token.approve(&alice, &s.client.address, &500, &1_000);
+    env.ledger().set_timestamp(MONTH + DAY);
+    let report = s.client.process_due(&0, &10);
+    assert_eq!((report.charged, report.failed), (1, 1));
+    let subscription = s.client.get_subscription(&first);
+    assert_eq!(subscription.status, SubscriptionStatus::Active);
+    assert_eq!(
+        (subscription.next_charge, subscription.failures),
+        (2 * MONTH, 0)
+    );
+
+    // Bob's third failure in a row lapses his subscription
+    env.ledger().set_timestamp(MONTH + 2 * DAY);
+    assert_eq!(s.client.process_due(&0, &10).lapsed, 1);
+    let subscription = s.client.get_subscription(&second);
+    assert_eq!(subscription.status, SubscriptionStatus::Lapsed);
+    assert_eq!(subscription.failures, 3);
+    env.ledger().set_timestamp(2 * MONTH);
+    assert_eq!(s.client.process_due(&1, &1), ProcessReport::default());
+
+    // A lapsed subscriber may subscribe again once it has topped up
+    StellarAssetClient::new(&env, &s.token.address).mint(&bob, &100);
+    let renewed = s.client.subscribe(&bob, &s.plan_id);
+    assert_eq!(s.client.subscription_of(&bob, &s.plan_id), Some(renewed));
+}
+
+#[test]
+fn test_cancel_and_retire_plan() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let alice = s.subscriber(&env, 1_000, 1_000);
+    let bob = s.subscriber(&env, 1_000, 1_000);
+
+    let id = s.client.subscribe(&alice, &s.plan_id);
+    s.client.cancel(&id);
+    let result = s.client.try_cancel(&id);
+    assert_eq!(result, Err(Ok(SubscriptionError::NotActive)));
+    env.ledger().set_timestamp(MONTH);
+    assert_eq!(s.client.process_due(&0, &10).charged, 0);
+    assert_eq!(s.token.balance(&alice), 900);
+
+    s.client.set_plan_active(&s.plan_id, &false);
+    let result = s.client.try_subscribe(&bob, &s.plan_id);
+    assert_eq!(result, Err(Ok(SubscriptionError::PlanInactive)));
+    let mut dunning = s.client.get_plan(&s.plan_id).dunning;
+    let result = s
+        .client
+        .try_create_plan(&s.merchant, &s.token.address, &100, &0, &dunning);
+    assert_eq!(result, Err(Ok(SubscriptionError::InvalidPeriod)));
+    dunning.max_failures = 0;
+    let result = s
+        .client
+        .try_create_plan(&s.merchant, &s.token.address, &100, &MONTH, &dunning);
+    assert_eq!(result, Err(Ok(SubscriptionError::InvalidDunning)));
+}
+
+#[test]
+fn test_metered_billing() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let meter = Address::generate(&env);
+    let alice = s.subscriber(&env, 10_000, 10_000);
+    let metering = Metering {
+        meter: meter.clone(),
+        curve: PriceCurve {
+            included: 100,
+            tiers: vec![
+                &env,
+                PriceTier {
+                    up_to: 1_000,
+                    unit_price: 2,
+                },
+                PriceTier {
+                    up_to: 5_000,
+                    unit_price: 1,
+                },
+            ],
+        },
+    };
+    let dunning = s.client.get_plan(&s.plan_id).dunning;
+    let plan_id = s.client.create_metered_plan(
+        &s.merchant,
+        &s.token.address,
+        &50,
+        &MONTH,
+        &dunning,
+        &metering,
+    );
+
+    // Nothing is charged up front
+    let id = s.client.subscribe(&alice, &plan_id);
+    assert_eq!(s.token.balance(&alice), 10_000);
+
+    // Usage adds up over the period, and later periods only open once started
+    s.client.report_usage(&id, &0, &700);
+    assert_eq!(s.client.report_usage(&id, &0, &500), 1_200);
+    let result = s.client.try_report_usage(&id, &1, &10);
+    assert_eq!(result, Err(Ok(SubscriptionError::InvalidUsagePeriod)));
+
+    // 900 units in the first tier, 200 in the second, plus the base price
+    env.ledger().set_timestamp(MONTH);
+    assert_eq!(s.client.process_due(&0, &10).charged, 1);
+    assert_eq!(s.token.balance(&alice), 10_000 - 2_050);
+    let result = s.client.try_report_usage(&id, &0, &10);
+    assert_eq!(result, Err(Ok(SubscriptionError::InvalidUsagePeriod)));
+
+    // Units past the last tier cost its price; an idle period costs the base
+    s.client.report_usage(&id, &1, &6_000);
+    env.ledger().set_timestamp(2 * MONTH);
+    s.client.process_due(&0, &10);
+    assert_eq!(s.token.balance(&s.merchant), 2_050 + 6_850);
+    env.ledger().set_timestamp(3 * MONTH);
+    s.client.process_due(&0, &10);
+    assert_eq!(s.token.balance(&alice), 10_000 - 2_050 - 6_850 - 50);
+
+    let flat = s.client.subscribe(&alice, &s.plan_id);
+    let result = s.client.try_report_usage(&flat, &0, &10);
+    assert_eq!(result, Err(Ok(SubscriptionError::NotMetered)));
+    let mut invalid = metering.clone();
+    invalid.curve.included = 1_000;
+    let result = s.client.try_create_metered_plan(
+        &s.merchant,
+        &s.token.address,
+        &50,
+        &MONTH,
+        &dunning,
+        &invalid,
+    );
+    assert_eq!(result, Err(Ok(SubscriptionError::InvalidCurve)));
+}
+
+#[test]
+fn test_change_plan_with_proration() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let alice = s.subscriber(&env, 1_000, 1_000);
+    let dunning = s.client.get_plan(&s.plan_id).dunning;
+    let pro = s
+        .client
+        .create_plan(&s.merchant, &s.token.address, &300, &MONTH, &dunning);
+    let lite = s
+        .client
+        .create_plan(&s.merchant, &s.token.address, &40, &MONTH, &dunning);
+    let id = s.client.subscribe(&alice, &s.plan_id);
+
+    // Half a period left: 150 on the new plan less 50 credited on the old
+    env.ledger().set_timestamp(MONTH / 2);
+    assert_eq!(s.client.change_plan(&id, &pro), PlanChange::Upgraded(100));
+    assert_eq!(s.token.balance(&alice), 800);
+    assert_eq!(s.client.subscription_of(&alice, &pro), Some(id));
+    assert_eq!(s.client.subscription_of(&alice, &s.plan_id), None);
+
+    // Downgrades wait for the paid period to run out, and can be withdrawn
+    assert_eq!(
+        s.client.change_plan(&id, &s.plan_id),
+        PlanChange::Scheduled(MONTH)
+    );
+    assert_eq!(s.client.change_plan(&id, &pro), PlanChange::Withdrawn);
+    assert_eq!(
+        s.client.change_plan(&id, &lite),
+        PlanChange::Scheduled(MONTH)
+    );
+    assert_eq!(s.client.get_subscription(&id).plan_id, pro);
+
+    env.ledger().set_timestamp(MONTH);
+    assert_eq!(s.client.process_due(&0, &10).charged, 1);
+    let subscription = s.client.get_subscription(&id);
+    assert_eq!(
+        (subscription.plan_id, subscription.pending_plan),
+        (lite, None)
+    );
+    assert_eq!(s.token.balance(&s.merchant), 240);
+
+    // Plans of another merchant cannot be switched to
+    let other = s
+        .client
+        .create_plan(&alice, &s.token.address, &10, &MONTH, &dunning);
+    let result = s.client.try_change_plan(&id, &other);
+    assert_eq!(result, Err(Ok(SubscriptionError::IncompatiblePlans)));
+}
+
+#[test]
+fn test_refund_from_reserve() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let alice = s.subscriber(&env, 1_000, 1_000);
+    let bob = s.subscriber(&env, 1_000, 1_000);
+    let carol = s.subscriber(&env, 1_000, 1_000);
+    let first = s.client.subscribe(&alice, &s.plan_id);
+    let second = s.client.subscribe(&bob, &s.plan_id);
+    let third = s.client.subscribe(&carol, &s.plan_id);
+
+    let result = s.client.try_request_refund(&first);
+    assert_eq!(result, Err(Ok(SubscriptionError::NoRefundPolicy)));
+
+    let policy = RefundPolicy {
+        full_window: 100,
+        prorated_window: 100,
+    };
+    s.client.set_refund_policy(&s.merchant, &Some(policy));
+    let reserve = s
+        .client
+        .fund_refund_reserve(&s.merchant, &s.token.address, &150);
+    assert_eq!(reserve, 150);
+
+    // In full within the window, then prorated
+    env.ledger().set_sequence_number(50);
+    assert_eq!(s.client.request_refund(&first), 100);
+    assert_eq!(s.token.balance(&alice), 1_000);
+    let subscription = s.client.get_subscription(&first);
+    assert_eq!(subscription.status, SubscriptionStatus::Cancelled);
+    env.ledger().set_sequence_number(150);
+    assert_eq!(s.client.request_refund(&second), 50);
+    assert_eq!(s.client.refund_reserve(&s.merchant, &s.token.address), 0);
+
+    // Refunds stop once the reserve runs dry or the charge has settled
+    let result = s.client.try_request_refund(&third);
+    assert_eq!(result, Err(Ok(SubscriptionError::InsufficientReserve)));
+    s.client
+        .fund_refund_reserve(&s.merchant, &s.token.address, &100);
+    env.ledger().set_sequence_number(200);
+    let result = s.client.try_request_refund(&third);
+    assert_eq!(result, Err(Ok(SubscriptionError::NotRefundable)));
+    s.client
+        .withdraw_refund_reserve(&s.merchant, &s.token.address, &100);
+    assert_eq!(s.token.balance(&s.merchant), 300 - 150);
+}
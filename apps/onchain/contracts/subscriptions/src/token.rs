@@ -0,0 +1,10 @@
+use soroban_sdk::{Address, Env};
+
+/// Pull `amount` from `from` to `to` against the allowance `from` gave the
+/// current contract. Returns whether exactly `amount` moved; a missing
+/// allowance or balance, or a token delivering short, is reported rather
+/// than aborting the caller.
+pub fn pull(env: &Env, token: &Address, from: &Address, to: &Address, amount: i128) -> bool {
+    let this = env.current_contract_address();
+    lumenpulse_sac::transfer_from_exact(env, token, &this, from, to, amount).is_ok()
+}
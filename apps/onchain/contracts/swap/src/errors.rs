@@ -0,0 +1,16 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SwapError {
+    SwapNotFound = 1,
+    InvalidAmount = 2,
+    InvalidDeadline = 3,
+    SameParty = 4,
+    NotParty = 5,
+    AlreadyFunded = 6,
+    DeadlinePassed = 7,
+    DeadlineNotPassed = 8,
+    SwapClosed = 9,
+}
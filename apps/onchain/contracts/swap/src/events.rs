@@ -0,0 +1,38 @@
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapCreatedEvent {
+    #[topic]
+    pub maker: Address,
+    #[topic]
+    pub taker: Address,
+    pub swap_id: u64,
+    pub deadline: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegFundedEvent {
+    #[topic]
+    pub swap_id: u64,
+    pub party: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapExecutedEvent {
+    #[topic]
+    pub swap_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapRefundedEvent {
+    #[topic]
+    pub swap_id: u64,
+    pub maker_refund: i128,
+    pub taker_refund: i128,
+}
@@ -0,0 +1,220 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::SwapError;
+use soroban_sdk::{contract, contractimpl, Address, Env};
+use storage::{DataKey, Leg, Swap, SwapStatus};
+
+/// Atomic two-party token swap. Each party deposits its leg, and the
+/// moment both legs are in the contract swaps them. If the deadline passes
+/// first, whatever was deposited goes back. Either party may be a contract,
+/// so escrow or stream settlements can convert assets without a DEX.
+///
+/// There is no circuit breaker, as the contract has no admin to hold one.
+/// Deposited legs only ever go to the counterparty or back to the party
+/// that deposited them, and a pause would hold both past the deadline.
+#[contract]
+pub struct SwapContract;
+
+#[contractimpl]
+impl SwapContract {
+    /// Propose swapping `maker_amount` of `maker_token` for `taker_amount`
+    /// of `taker_token` with `taker`, funded by both before `deadline`.
+    /// Nothing moves until a party funds its leg.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        env: Env,
+        maker: Address,
+        maker_token: Address,
+        maker_amount: i128,
+        taker: Address,
+        taker_token: Address,
+        taker_amount: i128,
+        deadline: u64,
+    ) -> Result<u64, SwapError> {
+        maker.require_auth();
+
+        if maker_amount <= 0 || taker_amount <= 0 {
+            return Err(SwapError::InvalidAmount);
+        }
+        if maker == taker {
+            return Err(SwapError::SameParty);
+        }
+        if deadline <= env.ledger().timestamp() {
+            return Err(SwapError::InvalidDeadline);
+        }
+
+        let swap_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextSwapId)
+            .unwrap_or(0);
+        let swap = Swap {
+            id: swap_id,
+            maker: Leg {
+                party: maker.clone(),
+                token: maker_token,
+                amount: maker_amount,
+                funded: false,
+            },
+            taker: Leg {
+                party: taker.clone(),
+                token: taker_token,
+                amount: taker_amount,
+                funded: false,
+            },
+            deadline,
+            status: SwapStatus::Open,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Swap(swap_id), &swap);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextSwapId, &(swap_id + 1));
+
+        events::SwapCreatedEvent {
+            maker,
+            taker,
+            swap_id,
+            deadline,
+        }
+        .publish(&env);
+
+        Ok(swap_id)
+    }
+
+    /// Deposit `party`'s leg. Funding the second leg executes the swap.
+    /// Returns the swap's status afterwards.
+    pub fn fund(env: Env, swap_id: u64, party: Address) -> Result<SwapStatus, SwapError> {
+        party.require_auth();
+
+        let mut swap = Self::get_swap(env.clone(), swap_id)?;
+        if swap.status != SwapStatus::Open {
+            return Err(SwapError::SwapClosed);
+        }
+        if env.ledger().timestamp() >= swap.deadline {
+            return Err(SwapError::DeadlinePassed);
+        }
+
+        let leg = if party == swap.maker.party {
+            &mut swap.maker
+        } else if party == swap.taker.party {
+            &mut swap.taker
+        } else {
+            return Err(SwapError::NotParty);
+        };
+        if leg.funded {
+            return Err(SwapError::AlreadyFunded);
+        }
+        token::transfer(
+            &env,
+            &leg.token,
+            &party,
+            &env.current_contract_address(),
+            &leg.amount,
+        );
+        leg.funded = true;
+
+        events::LegFundedEvent {
+            swap_id,
+            party,
+            token: leg.token.clone(),
+            amount: leg.amount,
+        }
+        .publish(&env);
+
+        if swap.maker.funded && swap.taker.funded {
+            swap.status = SwapStatus::Executed;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Swap(swap_id), &swap);
+
+            let contract_address = env.current_contract_address();
+            token::transfer(
+                &env,
+                &swap.maker.token,
+                &contract_address,
+                &swap.taker.party,
+                &swap.maker.amount,
+            );
+            token::transfer(
+                &env,
+                &swap.taker.token,
+                &contract_address,
+                &swap.maker.party,
+                &swap.taker.amount,
+            );
+
+            events::SwapExecutedEvent { swap_id }.publish(&env);
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Swap(swap_id), &swap);
+        }
+
+        Ok(swap.status)
+    }
+
+    /// Return deposited legs once the deadline has passed without the swap
+    /// executing. Funds only go back to the party that deposited them, so
+    /// anyone may call this.
+    pub fn refund(env: Env, swap_id: u64) -> Result<(), SwapError> {
+        let mut swap = Self::get_swap(env.clone(), swap_id)?;
+        if swap.status != SwapStatus::Open {
+            return Err(SwapError::SwapClosed);
+        }
+        if env.ledger().timestamp() < swap.deadline {
+            return Err(SwapError::DeadlineNotPassed);
+        }
+
+        swap.status = SwapStatus::Refunded;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Swap(swap_id), &swap);
+
+        let maker_refund = Self::return_leg(&env, &swap.maker);
+        let taker_refund = Self::return_leg(&env, &swap.taker);
+
+        events::SwapRefundedEvent {
+            swap_id,
+            maker_refund,
+            taker_refund,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get a swap by ID
+    pub fn get_swap(env: Env, swap_id: u64) -> Result<Swap, SwapError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Swap(swap_id))
+            .ok_or(SwapError::SwapNotFound)
+    }
+}
+
+impl SwapContract {
+    /// Send a funded leg back to its party and return the amount sent
+    fn return_leg(env: &Env, leg: &Leg) -> i128 {
+        if !leg.funded {
+            return 0;
+        }
+        token::transfer(
+            env,
+            &leg.token,
+            &env.current_contract_address(),
+            &leg.party,
+            &leg.amount,
+        );
+        leg.amount
+    }
+}
+
+#[cfg(test)]
+mod test;
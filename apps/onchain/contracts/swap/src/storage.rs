@@ -0,0 +1,42 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    NextSwapId, // -> u64
+    Swap(u64),  // swap_id -> Swap
+}
+
+/// What one party puts into a swap
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Leg {
+    pub party: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub funded: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SwapStatus {
+    /// Waiting for one or both legs
+    Open,
+    /// Both legs were exchanged
+    Executed,
+    /// The deadline passed first and funded legs went back
+    Refunded,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Swap {
+    pub id: u64,
+    /// Leg of the party that proposed the swap
+    pub maker: Leg,
+    /// Leg of the counterparty
+    pub taker: Leg,
+    /// Both legs must be funded before this time
+    pub deadline: u64,
+    pub status: SwapStatus,
+}
@@ -0,0 +1,140 @@
+use crate::errors::SwapError;
+use crate::storage::SwapStatus;
+use crate::{SwapContract, SwapContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const DEADLINE: u64 = 1_000;
+
+struct Setup<'a> {
+    client: SwapContractClient<'a>,
+    maker: Address,
+    taker: Address,
+    usdc: TokenClient<'a>,
+    xlm: TokenClient<'a>,
+}
+
+fn create_token<'a>(env: &Env, holder: &Address) -> TokenClient<'a> {
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &sac.address()).mint(holder, &10_000);
+    TokenClient::new(env, &sac.address())
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let maker = Address::generate(env);
+    let taker = Address::generate(env);
+    let usdc = create_token(env, &maker);
+    let xlm = create_token(env, &taker);
+
+    let contract_id = env.register(SwapContract, ());
+    let client = SwapContractClient::new(env, &contract_id);
+
+    Setup {
+        client,
+        maker,
+        taker,
+        usdc,
+        xlm,
+    }
+}
+
+fn create_swap(s: &Setup) -> u64 {
+    s.client.create(
+        &s.maker,
+        &s.usdc.address,
+        &1_000,
+        &s.taker,
+        &s.xlm.address,
+        &8_000,
+        &DEADLINE,
+    )
+}
+
+#[test]
+fn test_swap_executes_when_both_legs_funded() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let swap_id = create_swap(&s);
+
+    assert_eq!(s.client.fund(&swap_id, &s.taker), SwapStatus::Open);
+    assert_eq!(s.xlm.balance(&s.client.address), 8_000);
+    let result = s.client.try_fund(&swap_id, &s.taker);
+    assert_eq!(result, Err(Ok(SwapError::AlreadyFunded)));
+
+    assert_eq!(s.client.fund(&swap_id, &s.maker), SwapStatus::Executed);
+    assert_eq!(s.usdc.balance(&s.taker), 1_000);
+    assert_eq!(s.xlm.balance(&s.maker), 8_000);
+    assert_eq!(s.usdc.balance(&s.client.address), 0);
+    assert_eq!(s.xlm.balance(&s.client.address), 0);
+
+    env.ledger().set_timestamp(DEADLINE);
+    let result = s.client.try_refund(&swap_id);
+    assert_eq!(result, Err(Ok(SwapError::SwapClosed)));
+}
+
+#[test]
+fn test_refund_after_deadline() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let swap_id = create_swap(&s);
+
+    s.client.fund(&swap_id, &s.maker);
+    let result = s.client.try_refund(&swap_id);
+    assert_eq!(result, Err(Ok(SwapError::DeadlineNotPassed)));
+
+    env.ledger().set_timestamp(DEADLINE);
+    let result = s.client.try_fund(&swap_id, &s.taker);
+    assert_eq!(result, Err(Ok(SwapError::DeadlinePassed)));
+
+    s.client.refund(&swap_id);
+    assert_eq!(s.usdc.balance(&s.maker), 10_000);
+    assert_eq!(s.client.get_swap(&swap_id).status, SwapStatus::Refunded);
+}
+
+#[test]
+fn test_create_and_fund_errors() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    let result = s.client.try_create(
+        &s.maker,
+        &s.usdc.address,
+        &0,
+        &s.taker,
+        &s.xlm.address,
+        &8_000,
+        &DEADLINE,
+    );
+    assert_eq!(result, Err(Ok(SwapError::InvalidAmount)));
+    let result = s.client.try_create(
+        &s.maker,
+        &s.usdc.address,
+        &1_000,
+        &s.maker,
+        &s.xlm.address,
+        &8_000,
+        &DEADLINE,
+    );
+    assert_eq!(result, Err(Ok(SwapError::SameParty)));
+    let result = s.client.try_create(
+        &s.maker,
+        &s.usdc.address,
+        &1_000,
+        &s.taker,
+        &s.xlm.address,
+        &8_000,
+        &0,
+    );
+    assert_eq!(result, Err(Ok(SwapError::InvalidDeadline)));
+
+    let swap_id = create_swap(&s);
+    let result = s.client.try_fund(&swap_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(SwapError::NotParty)));
+    let result = s.client.try_fund(&7, &s.maker);
+    assert_eq!(result, Err(Ok(SwapError::SwapNotFound)));
+}
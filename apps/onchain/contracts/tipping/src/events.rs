@@ -0,0 +1,35 @@
+use soroban_sdk::{contractevent, Address, String};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSetEvent {
+    pub bps: u32,
+    pub recipient: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigSetEvent {
+    pub config: Option<Address>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatorRegisteredEvent {
+    #[topic]
+    pub creator: Address,
+    pub name: String,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TippedEvent {
+    #[topic]
+    pub creator: Address,
+    #[topic]
+    pub tipper: Address,
+    pub receipt_id: u64,
+    pub token: Address,
+    pub amount: i128,
+    pub fee: i128,
+}
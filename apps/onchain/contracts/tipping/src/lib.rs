@@ -0,0 +1,291 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod registry;
+mod storage;
+mod token;
+
+use errors::TippingError;
+use lumenpulse_fixed::{bps, Rounding};
+use lumenpulse_pausable::Pausable;
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+use storage::{
+    Creator, CreatorStats, DataKey, ProtocolFee, Receipt, MAX_FEE_BPS, MAX_NAME_LEN, MAX_PAGE,
+};
+
+/// Tips and donations to registered creators. A tip is paid straight to
+/// the creator, less the protocol fee if one is set, and leaves a receipt
+/// the tipper can list later. Each creator's tips are totalled per token
+/// for leaderboards.
+#[contract]
+pub struct TippingContract;
+
+#[contractimpl]
+impl TippingContract {
+    /// Initialize the contract with an admin address. Tips carry no fee
+    /// until one is set.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), TippingError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(TippingError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+
+        Ok(())
+    }
+
+    /// Take `bps` of every tip for `recipient` (admin only). Zero turns the
+    /// fee off.
+    pub fn set_fee(
+        env: Env,
+        admin: Address,
+        bps: u32,
+        recipient: Address,
+    ) -> Result<(), TippingError> {
+        Self::require_admin(&env, &admin)?;
+
+        if bps > MAX_FEE_BPS {
+            return Err(TippingError::InvalidFee);
+        }
+        env.storage().instance().set(
+            &DataKey::Fee,
+            &ProtocolFee {
+                bps,
+                recipient: recipient.clone(),
+            },
+        );
+
+        events::FeeSetEvent { bps, recipient }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Take the fee from the parameter registry at `config` instead of
+    /// `set_fee` (admin only), so governance sets it for every contract in
+    /// one place. The registry's `tip_fee` and `fee_to` apply from the next
+    /// tip, and no fee is taken while either is unset. `None` goes back to
+    /// the fee set here.
+    pub fn set_config(
+        env: Env,
+        admin: Address,
+        config: Option<Address>,
+    ) -> Result<(), TippingError> {
+        Self::require_admin(&env, &admin)?;
+
+        match &config {
+            Some(config) => env.storage().instance().set(&DataKey::Config, config),
+            None => env.storage().instance().remove(&DataKey::Config),
+        }
+
+        events::ConfigSetEvent { config }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Register the caller as a creator that can receive tips
+    pub fn register_creator(env: Env, creator: Address, name: String) -> Result<(), TippingError> {
+        creator.require_auth();
+
+        if name.is_empty() || name.len() > MAX_NAME_LEN {
+            return Err(TippingError::InvalidName);
+        }
+        let key = DataKey::Creator(creator.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(TippingError::CreatorExists);
+        }
+        env.storage().persistent().set(
+            &key,
+            &Creator {
+                address: creator.clone(),
+                name: name.clone(),
+                registered_at: env.ledger().timestamp(),
+            },
+        );
+
+        events::CreatorRegisteredEvent { creator, name }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Tip `creator` `amount` of `token`, fee included. Returns the ID of
+    /// the tipper's receipt.
+    pub fn tip(
+        env: Env,
+        tipper: Address,
+        creator: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<u64, TippingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        tipper.require_auth();
+
+        if amount <= 0 {
+            return Err(TippingError::InvalidAmount);
+        }
+        Self::get_creator(env.clone(), creator.clone())?;
+
+        let fee = Self::get_fee(env.clone());
+        let fee_amount = fee.as_ref().map_or(0, |fee| {
+            bps(amount, fee.bps, Rounding::Down).expect("arithmetic overflow")
+        });
+
+        let receipt_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextReceiptId)
+            .unwrap_or(0);
+        let receipt = Receipt {
+            id: receipt_id,
+            tipper: tipper.clone(),
+            creator: creator.clone(),
+            token: token.clone(),
+            amount,
+            fee: fee_amount,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Receipt(receipt_id), &receipt);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextReceiptId, &(receipt_id + 1));
+        let count_key = DataKey::TipperCount(tipper.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::TipperReceipt(tipper.clone(), count), &receipt_id);
+        env.storage().persistent().set(&count_key, &(count + 1));
+
+        let stats_key = DataKey::CreatorStats(creator.clone(), token.clone());
+        let mut stats = Self::creator_stats(env.clone(), creator.clone(), token.clone());
+        stats.tips += 1;
+        stats.received += amount - fee_amount;
+        stats.largest = stats.largest.max(amount);
+        env.storage().persistent().set(&stats_key, &stats);
+
+        token::transfer(&env, &token, &tipper, &creator, &(amount - fee_amount));
+        if let Some(fee) = fee {
+            token::transfer(&env, &token, &tipper, &fee.recipient, &fee_amount);
+        }
+
+        events::TippedEvent {
+            creator,
+            tipper,
+            receipt_id,
+            token,
+            amount,
+            fee: fee_amount,
+        }
+        .publish(&env);
+
+        Ok(receipt_id)
+    }
+
+    /// Get a registered creator
+    pub fn get_creator(env: Env, creator: Address) -> Result<Creator, TippingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Creator(creator))
+            .ok_or(TippingError::CreatorNotFound)
+    }
+
+    /// Totals of the tips `creator` has received in `token`
+    pub fn creator_stats(env: Env, creator: Address, token: Address) -> CreatorStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CreatorStats(creator, token))
+            .unwrap_or_default()
+    }
+
+    /// Each creator's totals in `token`, in the order given, for ranking a
+    /// leaderboard off-chain
+    pub fn leaderboard(env: Env, creators: Vec<Address>, token: Address) -> Vec<CreatorStats> {
+        let mut stats = Vec::new(&env);
+        for creator in creators.iter().take(MAX_PAGE as usize) {
+            stats.push_back(Self::creator_stats(env.clone(), creator, token.clone()));
+        }
+        stats
+    }
+
+    /// Get a receipt by ID
+    pub fn get_receipt(env: Env, receipt_id: u64) -> Result<Receipt, TippingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Receipt(receipt_id))
+            .ok_or(TippingError::ReceiptNotFound)
+    }
+
+    /// Up to `limit` of `tipper`'s receipts, oldest first, starting at
+    /// position `cursor`
+    pub fn receipts_of(env: Env, tipper: Address, cursor: u32, limit: u32) -> Vec<Receipt> {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TipperCount(tipper.clone()))
+            .unwrap_or(0);
+        let end = count.min(cursor.saturating_add(limit.min(MAX_PAGE)));
+        let mut receipts = Vec::new(&env);
+        for index in cursor..end {
+            let receipt_id: Option<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::TipperReceipt(tipper.clone(), index));
+            if let Some(Ok(receipt)) =
+                receipt_id.map(|receipt_id| Self::get_receipt(env.clone(), receipt_id))
+            {
+                receipts.push_back(receipt);
+            }
+        }
+        receipts
+    }
+
+    /// Protocol fee taken from tips, if any, read from the registry when
+    /// one is set
+    pub fn get_fee(env: Env) -> Option<ProtocolFee> {
+        let fee = match Self::get_config(env.clone()) {
+            Some(config) => registry::protocol_fee(&env, &config),
+            None => env.storage().instance().get(&DataKey::Fee),
+        };
+        fee.filter(|fee| fee.bps > 0)
+    }
+
+    /// Parameter registry the fee is read from, if any
+    pub fn get_config(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Config)
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Result<Address, TippingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(TippingError::NotInitialized)
+    }
+}
+
+impl TippingContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), TippingError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(TippingError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+}
+
+impl Pausable for TippingContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(TippingContract);
+
+#[cfg(test)]
+mod test;
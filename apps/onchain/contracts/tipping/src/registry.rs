@@ -0,0 +1,30 @@
+use crate::storage::{ProtocolFee, MAX_FEE_BPS};
+use soroban_sdk::{contractclient, symbol_short, Address, Env, Symbol};
+
+/// Registry parameter holding the tip fee, in basis points
+pub const FEE_BPS_PARAM: Symbol = symbol_short!("tip_fee");
+/// Registry parameter holding the address tip fees are paid to
+pub const FEE_RECIPIENT_PARAM: Symbol = symbol_short!("fee_to");
+
+/// The parameter registry's typed getters
+#[allow(dead_code)]
+#[contractclient(name = "ConfigClient")]
+pub trait Config {
+    fn get_u32(env: Env, name: Symbol) -> u32;
+    fn get_address(env: Env, name: Symbol) -> Address;
+}
+
+/// The tip fee governance has set in the registry at `config`, or `None`
+/// while either parameter is missing or of the wrong type. A fee above
+/// `MAX_FEE_BPS` is capped rather than trusted.
+pub fn protocol_fee(env: &Env, config: &Address) -> Option<ProtocolFee> {
+    let client = ConfigClient::new(env, config);
+    let bps = match client.try_get_u32(&FEE_BPS_PARAM) {
+        Ok(Ok(bps)) => bps.min(MAX_FEE_BPS),
+        _ => return None,
+    };
+    match client.try_get_address(&FEE_RECIPIENT_PARAM) {
+        Ok(Ok(recipient)) => Some(ProtocolFee { bps, recipient }),
+        _ => None,
+    }
+}
@@ -0,0 +1,65 @@
+use soroban_sdk::{contracttype, Address, String};
+
+/// Highest protocol fee, in basis points (10%)
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+/// Longest creator name accepted
+pub const MAX_NAME_LEN: u32 = 64;
+
+/// Most receipts returned by one page of a listing
+pub const MAX_PAGE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                          // -> Address
+    Fee,                            // -> ProtocolFee
+    Config,                         // -> Address
+    Creator(Address),               // creator -> Creator
+    CreatorStats(Address, Address), // (creator, token) -> CreatorStats
+    NextReceiptId,                  // -> u64
+    Receipt(u64),                   // receipt_id -> Receipt
+    TipperCount(Address),           // tipper -> u32
+    TipperReceipt(Address, u32),    // (tipper, index) -> receipt_id
+}
+
+/// Share of every tip paid to the protocol
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolFee {
+    pub bps: u32,
+    pub recipient: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Creator {
+    pub address: Address,
+    pub name: String,
+    pub registered_at: u64,
+}
+
+/// Running totals of the tips a creator has received in one token
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CreatorStats {
+    pub tips: u32,
+    /// Received after fees
+    pub received: i128,
+    /// Largest single tip, before fees
+    pub largest: i128,
+}
+
+/// Record of one tip, kept as the tipper's receipt
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    pub id: u64,
+    pub tipper: Address,
+    pub creator: Address,
+    pub token: Address,
+    /// Paid by the tipper, fee included
+    pub amount: i128,
+    pub fee: i128,
+    pub timestamp: u64,
+}
@@ -0,0 +1,162 @@
+use crate::errors::TippingError;
+use crate::{TippingContract, TippingContractClient};
+use config::{ConfigContract, ConfigContractClient, ConfigValue};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, String,
+};
+
+struct Setup<'a> {
+    client: TippingContractClient<'a>,
+    admin: Address,
+    creator: Address,
+    tipper: Address,
+    token: TokenClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let tipper = Address::generate(env);
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &sac.address()).mint(&tipper, &10_000);
+
+    let client = TippingContractClient::new(env, &env.register(TippingContract, ()));
+    client.initialize(&admin);
+    client.register_creator(&creator, &String::from_str(env, "pulse"));
+
+    Setup {
+        client,
+        admin,
+        creator,
+        tipper,
+        token: TokenClient::new(env, &sac.address()),
+    }
+}
+
+#[test]
+fn test_tip_with_fee_and_receipt() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let treasury = Address::generate(&env);
+    env.ledger().set_timestamp(100);
+
+    let first = s
+        .client
+        .tip(&s.tipper, &s.creator, &s.token.address, &1_000);
+    assert_eq!(s.token.balance(&s.creator), 1_000);
+
+    s.client.set_fee(&s.admin, &250, &treasury);
+    let second = s
+        .client
+        .tip(&s.tipper, &s.creator, &s.token.address, &2_000);
+    assert_eq!(s.token.balance(&s.creator), 2_950);
+    assert_eq!(s.token.balance(&treasury), 50);
+
+    let receipt = s.client.get_receipt(&second);
+    assert_eq!(
+        (receipt.amount, receipt.fee, receipt.timestamp),
+        (2_000, 50, 100)
+    );
+    let receipts = s.client.receipts_of(&s.tipper, &0, &10);
+    assert_eq!(receipts.len(), 2);
+    assert_eq!(receipts.get(0).unwrap().id, first);
+
+    let stats = s.client.creator_stats(&s.creator, &s.token.address);
+    assert_eq!(
+        (stats.tips, stats.received, stats.largest),
+        (2, 2_950, 2_000)
+    );
+}
+
+#[test]
+fn test_leaderboard() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let rival = Address::generate(&env);
+    let unknown = Address::generate(&env);
+    s.client
+        .register_creator(&rival, &String::from_str(&env, "rival"));
+
+    s.client.tip(&s.tipper, &s.creator, &s.token.address, &300);
+    s.client.tip(&s.tipper, &rival, &s.token.address, &500);
+    s.client.tip(&s.tipper, &rival, &s.token.address, &100);
+
+    let board = s.client.leaderboard(
+        &vec![&env, s.creator.clone(), rival.clone(), unknown],
+        &s.token.address,
+    );
+    let received: [i128; 3] = core::array::from_fn(|i| board.get(i as u32).unwrap().received);
+    assert_eq!(received, [300, 600, 0]);
+    assert_eq!(board.get(1).unwrap().tips, 2);
+}
+
+#[test]
+fn test_invalid_tips_rejected() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let stranger = Address::generate(&env);
+
+    let result = s
+        .client
+        .try_tip(&s.tipper, &stranger, &s.token.address, &100);
+    assert_eq!(result, Err(Ok(TippingError::CreatorNotFound)));
+    let result = s
+        .client
+        .try_tip(&s.tipper, &s.creator, &s.token.address, &0);
+    assert_eq!(result, Err(Ok(TippingError::InvalidAmount)));
+    let result = s
+        .client
+        .try_register_creator(&s.creator, &String::from_str(&env, "again"));
+    assert_eq!(result, Err(Ok(TippingError::CreatorExists)));
+    let result = s.client.try_set_fee(&s.admin, &1_001, &s.admin);
+    assert_eq!(result, Err(Ok(TippingError::InvalidFee)));
+    let result = s.client.try_set_fee(&stranger, &100, &stranger);
+    assert_eq!(result, Err(Ok(TippingError::Unauthorized)));
+    let result = s.client.try_set_config(&stranger, &Some(stranger.clone()));
+    assert_eq!(result, Err(Ok(TippingError::Unauthorized)));
+}
+
+#[test]
+fn test_fee_from_parameter_registry() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let governance = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let registry = ConfigContractClient::new(&env, &env.register(ConfigContract, ()));
+    registry.initialize(&governance);
+
+    // The registry replaces the fee set on the contract, and takes none
+    // until governance has set both parameters
+    s.client.set_fee(&s.admin, &100, &s.admin);
+    s.client
+        .set_config(&s.admin, &Some(registry.address.clone()));
+    registry.set(&symbol_short!("tip_fee"), &ConfigValue::U32(250));
+    assert_eq!(s.client.get_fee(), None);
+    registry.set(
+        &symbol_short!("fee_to"),
+        &ConfigValue::Address(treasury.clone()),
+    );
+
+    s.client
+        .tip(&s.tipper, &s.creator, &s.token.address, &2_000);
+    assert_eq!(s.token.balance(&treasury), 50);
+    assert_eq!(s.token.balance(&s.creator), 1_950);
+
+    // A governance change applies to the next tip without touching tipping
+    registry.set(&symbol_short!("tip_fee"), &ConfigValue::U32(500));
+    s.client
+        .tip(&s.tipper, &s.creator, &s.token.address, &2_000);
+    assert_eq!(s.token.balance(&treasury), 150);
+
+    // A fee above the contract's own limit is capped
+    registry.set(&symbol_short!("tip_fee"), &ConfigValue::U32(5_000));
+    assert_eq!(s.client.get_fee().unwrap().bps, 1_000);
+
+    s.client.set_config(&s.admin, &None);
+    assert_eq!(s.client.get_fee().unwrap().recipient, s.admin);
+}
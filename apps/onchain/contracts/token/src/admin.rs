@@ -1,18 +1,33 @@
 use soroban_sdk::{Address, Env};
 
 pub fn has_administrator(e: &Env) -> bool {
-    let key = DataKey::Admin;
-    e.storage().instance().has(&key)
+    e.storage().instance().has(&DataKey::Admin) || lumenpulse_access::admin(e).is_ok()
 }
 
 pub fn read_administrator(e: &Env) -> Address {
-    let key = DataKey::Admin;
-    e.storage().instance().get(&key).unwrap()
+    adopt_legacy_admin(e);
+    lumenpulse_access::admin(e).expect("not initialized")
+}
+
+pub fn init_administrator(e: &Env, id: &Address) {
+    lumenpulse_access::init_admin(e, id).expect("already initialized");
+}
+
+/// Hand the token to `new_admin` (admin only)
+pub fn write_administrator(e: &Env, admin: &Address, new_admin: &Address) {
+    lumenpulse_access::set_admin(e, admin, new_admin).expect("not admin");
 }
 
-pub fn write_administrator(e: &Env, id: &Address) {
+/// Move an admin stored by a release before `lumenpulse-access` to where
+/// the access checks look for it, so an upgraded token keeps its admin
+fn adopt_legacy_admin(e: &Env) {
     let key = DataKey::Admin;
-    e.storage().instance().set(&key, id);
+    if let Some(admin) = e.storage().instance().get::<_, Address>(&key) {
+        if lumenpulse_access::admin(e).is_err() {
+            lumenpulse_access::init_admin(e, &admin).expect("no admin yet");
+        }
+        e.storage().instance().remove(&key);
+    }
 }
 
 #[derive(Clone)]
@@ -4,6 +4,7 @@ use soroban_sdk::{Address, Env};
 #[soroban_sdk::contracttype]
 pub enum DataKey {
     Allowance(AllowanceDataKey),
+    PermitNonce(Address),
 }
 
 #[derive(Clone)]
@@ -20,14 +21,24 @@ pub struct AllowanceValue {
     pub expiration_ledger: u32,
 }
 
+/// Read the allowance, treating an expired approval as zero
 pub fn read_allowance(e: &Env, from: Address, spender: Address) -> AllowanceValue {
     let key = DataKey::Allowance(AllowanceDataKey { from, spender });
-    e.storage().temporary().get(&key).unwrap_or(AllowanceValue {
-        amount: 0,
-        expiration_ledger: 0,
-    })
+    match e.storage().temporary().get::<_, AllowanceValue>(&key) {
+        Some(allowance) if allowance.expiration_ledger >= e.ledger().sequence() => allowance,
+        Some(allowance) => AllowanceValue {
+            amount: 0,
+            expiration_ledger: allowance.expiration_ledger,
+        },
+        None => AllowanceValue {
+            amount: 0,
+            expiration_ledger: 0,
+        },
+    }
 }
 
+/// Overwrite the allowance. A non-zero allowance must not already be
+/// expired, and its entry lives until `expiration_ledger`.
 pub fn write_allowance(
     e: &Env,
     from: Address,
@@ -35,14 +46,24 @@ pub fn write_allowance(
     amount: i128,
     expiration_ledger: u32,
 ) {
+    let allowance = AllowanceValue {
+        amount,
+        expiration_ledger,
+    };
+
+    if amount > 0 && expiration_ledger < e.ledger().sequence() {
+        panic!("expiration_ledger is less than ledger seq when amount > 0")
+    }
+
     let key = DataKey::Allowance(AllowanceDataKey { from, spender });
-    e.storage().temporary().set(
-        &key,
-        &AllowanceValue {
-            amount,
-            expiration_ledger,
-        },
-    );
+    e.storage().temporary().set(&key, &allowance);
+
+    if amount > 0 {
+        let live_for = expiration_ledger
+            .checked_sub(e.ledger().sequence())
+            .unwrap();
+        e.storage().temporary().extend_ttl(&key, live_for, live_for)
+    }
 }
 
 pub fn spend_allowance(e: &Env, from: Address, spender: Address, amount: i128) {
@@ -50,17 +71,28 @@ pub fn spend_allowance(e: &Env, from: Address, spender: Address, amount: i128) {
     if allowance.amount < amount {
         panic!("insufficient allowance");
     }
-    // If expiration_ledger is 0, it means no expiration? Or should we handle that?
-    // Usually 0 means expired or not set.
-    // Let's assume strict expiration.
-    if allowance.expiration_ledger < e.ledger().sequence() {
-        panic!("allowance expired");
+    if amount > 0 {
+        write_allowance(
+            e,
+            from,
+            spender,
+            allowance.amount - amount,
+            allowance.expiration_ledger,
+        );
+    }
+}
+
+pub fn read_permit_nonce(e: &Env, owner: Address) -> u64 {
+    let key = DataKey::PermitNonce(owner);
+    e.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Consume the owner's next permit nonce; permits must be used in order
+pub fn use_permit_nonce(e: &Env, owner: Address, nonce: u64) {
+    let expected = read_permit_nonce(e, owner.clone());
+    if nonce != expected {
+        panic!("invalid permit nonce");
     }
-    write_allowance(
-        e,
-        from,
-        spender,
-        allowance.amount - amount,
-        allowance.expiration_ledger,
-    );
+    let key = DataKey::PermitNonce(owner);
+    e.storage().persistent().set(&key, &(expected + 1));
 }
@@ -1,3 +1,4 @@
+use crate::{checkpoints, votes};
 use soroban_sdk::{Address, Env};
 
 #[derive(Clone)]
@@ -13,8 +14,11 @@ pub fn read_balance(e: &Env, addr: Address) -> i128 {
 }
 
 pub fn write_balance(e: &Env, addr: Address, amount: i128) {
-    let key = DataKey::Balance(addr);
+    let previous = read_balance(e, addr.clone());
+    let key = DataKey::Balance(addr.clone());
     e.storage().persistent().set(&key, &amount);
+    checkpoints::record_balance(e, addr.clone(), amount);
+    votes::move_votes(e, &votes::read_delegate(e, &addr), amount - previous);
 }
 
 pub fn read_state(e: &Env, addr: Address) -> bool {
@@ -47,3 +51,12 @@ pub fn spend_balance(e: &Env, addr: Address, amount: i128) {
     }
     write_balance(e, addr, balance - amount);
 }
+
+/// Remove funds regardless of freeze state, as an issuer clawback does
+pub fn claw_balance(e: &Env, addr: Address, amount: i128) {
+    let balance = read_balance(e, addr.clone());
+    if balance < amount {
+        panic!("insufficient balance");
+    }
+    write_balance(e, addr, balance - amount);
+}
@@ -0,0 +1,133 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+/// Value recorded as of the end of `ledger`
+#[derive(Clone)]
+#[contracttype]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub value: i128,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    BalanceCheckpoint(Address, u32),
+    BalanceCheckpointCount(Address),
+    SupplyCheckpoint(u32),
+    SupplyCheckpointCount,
+    VotesCheckpoint(Address, u32),
+    VotesCheckpointCount(Address),
+}
+
+/// Which series of checkpoints to read or extend
+enum Series {
+    Balance(Address),
+    Supply,
+    Votes(Address),
+}
+
+impl Series {
+    fn count_key(&self) -> DataKey {
+        match self {
+            Series::Balance(addr) => DataKey::BalanceCheckpointCount(addr.clone()),
+            Series::Supply => DataKey::SupplyCheckpointCount,
+            Series::Votes(addr) => DataKey::VotesCheckpointCount(addr.clone()),
+        }
+    }
+
+    fn entry_key(&self, index: u32) -> DataKey {
+        match self {
+            Series::Balance(addr) => DataKey::BalanceCheckpoint(addr.clone(), index),
+            Series::Supply => DataKey::SupplyCheckpoint(index),
+            Series::Votes(addr) => DataKey::VotesCheckpoint(addr.clone(), index),
+        }
+    }
+}
+
+pub fn record_balance(e: &Env, addr: Address, value: i128) {
+    record(e, Series::Balance(addr), value);
+}
+
+pub fn record_supply(e: &Env, value: i128) {
+    record(e, Series::Supply, value);
+}
+
+pub fn record_votes(e: &Env, addr: Address, value: i128) {
+    record(e, Series::Votes(addr), value);
+}
+
+pub fn balance_at(e: &Env, addr: Address, ledger: u32) -> i128 {
+    value_at(e, Series::Balance(addr), ledger)
+}
+
+pub fn supply_at(e: &Env, ledger: u32) -> i128 {
+    value_at(e, Series::Supply, ledger)
+}
+
+pub fn votes_at(e: &Env, addr: Address, ledger: u32) -> i128 {
+    value_at(e, Series::Votes(addr), ledger)
+}
+
+pub fn latest_votes(e: &Env, addr: Address) -> i128 {
+    latest(e, Series::Votes(addr))
+}
+
+/// Record the latest value for the current ledger. Several writes in the
+/// same ledger collapse into one checkpoint holding the last value.
+fn record(e: &Env, series: Series, value: i128) {
+    let storage = e.storage().persistent();
+    let ledger = e.ledger().sequence();
+    let count: u32 = storage.get(&series.count_key()).unwrap_or(0);
+
+    if count > 0 {
+        let last_key = series.entry_key(count - 1);
+        let last: Checkpoint = storage.get(&last_key).unwrap();
+        if last.ledger == ledger {
+            storage.set(&last_key, &Checkpoint { ledger, value });
+            return;
+        }
+    }
+
+    storage.set(&series.entry_key(count), &Checkpoint { ledger, value });
+    storage.set(&series.count_key(), &(count + 1));
+}
+
+/// Most recently recorded value, including writes in the current ledger
+fn latest(e: &Env, series: Series) -> i128 {
+    let storage = e.storage().persistent();
+    let count: u32 = storage.get(&series.count_key()).unwrap_or(0);
+    if count == 0 {
+        return 0;
+    }
+    let checkpoint: Checkpoint = storage.get(&series.entry_key(count - 1)).unwrap();
+    checkpoint.value
+}
+
+/// Value as of the end of a past ledger. The current ledger is still open,
+/// so querying it would let same-ledger transfers sway the result.
+fn value_at(e: &Env, series: Series, ledger: u32) -> i128 {
+    if ledger >= e.ledger().sequence() {
+        panic!("ledger is not yet finalized");
+    }
+
+    let storage = e.storage().persistent();
+    let count: u32 = storage.get(&series.count_key()).unwrap_or(0);
+
+    // Binary search for the last checkpoint at or before `ledger`
+    let (mut low, mut high) = (0u32, count);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let checkpoint: Checkpoint = storage.get(&series.entry_key(mid)).unwrap();
+        if checkpoint.ledger <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    if low == 0 {
+        return 0;
+    }
+    let checkpoint: Checkpoint = storage.get(&series.entry_key(low - 1)).unwrap();
+    checkpoint.value
+}
@@ -0,0 +1,61 @@
+use crate::{balance, events};
+use lumenpulse_fixed::{bps, Rounding};
+use soroban_sdk::{contracttype, Address, Env};
+
+pub use lumenpulse_token_interface::{TransferFee, TransferQuote};
+
+/// Highest transfer fee the admin may configure (10%)
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    TransferFee,
+}
+
+pub fn read_transfer_fee(e: &Env) -> Option<TransferFee> {
+    let key = DataKey::TransferFee;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_transfer_fee(e: &Env, fee: Option<TransferFee>) {
+    let key = DataKey::TransferFee;
+    match fee {
+        Some(fee) => {
+            if fee.bps > MAX_FEE_BPS {
+                panic!("transfer fee exceeds maximum");
+            }
+            e.storage().instance().set(&key, &fee);
+        }
+        None => e.storage().instance().remove(&key),
+    }
+}
+
+pub fn quote(e: &Env, amount: i128) -> TransferQuote {
+    let fee = match read_transfer_fee(e) {
+        Some(config) => bps(amount, config.bps, Rounding::Down).expect("arithmetic overflow"),
+        None => 0,
+    };
+    TransferQuote {
+        amount,
+        fee,
+        received: amount - fee,
+    }
+}
+
+/// Pay the fee on a transfer of `amount` out of `from`'s already-debited
+/// funds and return what is left for the recipient
+pub fn charge(e: &Env, from: &Address, amount: i128) -> i128 {
+    let quote = quote(e, amount);
+    if quote.fee > 0 {
+        let treasury = read_transfer_fee(e).unwrap().treasury;
+        balance::receive_balance(e, treasury.clone(), quote.fee);
+        events::Transfer {
+            from: from.clone(),
+            to: treasury,
+            amount: quote.fee,
+        }
+        .publish(e);
+    }
+    quote.received
+}
@@ -0,0 +1,57 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+pub use lumenpulse_token_interface::{HookKind, TransferHook, TransferHookClient};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Hook(HookKind),
+    HookLock,
+}
+
+pub fn read_hook(e: &Env, kind: HookKind) -> Option<Address> {
+    let key = DataKey::Hook(kind);
+    e.storage().instance().get(&key)
+}
+
+pub fn write_hook(e: &Env, kind: HookKind, hook: Option<Address>) {
+    let key = DataKey::Hook(kind);
+    match hook {
+        Some(hook) => e.storage().instance().set(&key, &hook),
+        None => e.storage().instance().remove(&key),
+    }
+}
+
+/// Reject any state change made while a hook is running
+pub fn check_not_locked(e: &Env) {
+    if e.storage().instance().has(&DataKey::HookLock) {
+        panic!("reentrant call during transfer hook");
+    }
+}
+
+/// Ask the registered hooks whether the transfer may proceed. A hook that
+/// returns anything but `true`, or fails, counts as a veto. Hooks run on
+/// the transaction's own budget, which cannot be capped per call, so one
+/// that exhausts it fails the whole transaction rather than vetoing.
+pub fn check_transfer(e: &Env, from: &Address, to: &Address, amount: i128) {
+    let hooks = [
+        read_hook(e, HookKind::Sender),
+        read_hook(e, HookKind::Recipient),
+    ];
+    if hooks.iter().all(Option::is_none) {
+        return;
+    }
+
+    check_not_locked(e);
+    e.storage().instance().set(&DataKey::HookLock, &true);
+
+    let token = e.current_contract_address();
+    for hook in hooks.iter().flatten() {
+        let approved = TransferHookClient::new(e, hook).try_on_transfer(&token, from, to, &amount);
+        if approved != Ok(Ok(true)) {
+            panic!("transfer rejected by hook");
+        }
+    }
+
+    e.storage().instance().remove(&DataKey::HookLock);
+}
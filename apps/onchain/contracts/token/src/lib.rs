@@ -3,10 +3,34 @@
 mod admin;
 mod allowance;
 mod balance;
+mod checkpoints;
+mod events;
+mod fees;
+mod hooks;
 mod metadata;
+mod roles;
+mod supply;
 mod test;
+mod votes;
 
-use soroban_sdk::{contract, contractimpl, Address, Env, String};
+pub use fees::{TransferFee, TransferQuote};
+pub use hooks::{HookKind, TransferHook, TransferHookClient};
+pub use roles::Role;
+
+use lumenpulse_upgradeable::Upgradeable;
+
+use soroban_sdk::{
+    contract, contractimpl, token::TokenInterface, Address, Env, IntoVal, MuxedAddress, String,
+};
+
+/// Decimals above this cannot be represented by SAC-compatible tooling
+const MAX_DECIMALS: u32 = 18;
+
+fn check_nonnegative_amount(amount: i128) {
+    if amount < 0 {
+        panic!("negative amount is not allowed: {}", amount)
+    }
+}
 
 #[contract]
 pub struct LumenToken;
@@ -17,20 +41,194 @@ impl LumenToken {
         if admin::has_administrator(&e) {
             panic!("already initialized");
         }
-        admin::write_administrator(&e, &admin);
+        if decimal > MAX_DECIMALS {
+            panic!("decimal must not be greater than {}", MAX_DECIMALS);
+        }
+        admin::init_administrator(&e, &admin);
         metadata::write_metadata(&e, decimal, name, symbol);
     }
 
-    pub fn mint(e: Env, to: Address, amount: i128) {
+    /// Mint new tokens. `minter` must hold the minter role, and the total
+    /// supply may not exceed the cap.
+    pub fn mint(e: Env, minter: Address, to: Address, amount: i128) {
+        check_nonnegative_amount(amount);
+        roles::require_role(&e, Role::Minter, &minter);
+        hooks::check_not_locked(&e);
+        supply::increase_supply(&e, amount);
+        balance::receive_balance(&e, to.clone(), amount);
+        events::Mint { to, amount }.publish(&e);
+    }
+
+    pub fn grant_role(e: Env, role: Role, account: Address) {
+        roles::grant_role(&e, role, &account);
+    }
+
+    pub fn revoke_role(e: Env, role: Role, account: Address) {
+        roles::revoke_role(&e, role, &account);
+    }
+
+    /// Take tokens back from `from` and destroy them (admin only). Like a
+    /// Stellar asset clawback this works on frozen accounts and needs no
+    /// consent from the holder.
+    pub fn clawback(e: Env, from: Address, amount: i128) {
+        check_nonnegative_amount(amount);
         let admin = admin::read_administrator(&e);
         admin.require_auth();
-        balance::receive_balance(&e, to, amount);
+        hooks::check_not_locked(&e);
+
+        balance::claw_balance(&e, from.clone(), amount);
+        supply::decrease_supply(&e, amount);
+        events::Clawback { from, amount }.publish(&e);
     }
 
-    pub fn set_admin(e: Env, new_admin: Address) {
+    /// Approve `spender` on the owner's behalf using an authorization the
+    /// owner signed off-chain over `(spender, amount, nonce, expiration_ledger)`.
+    /// Anyone may submit it, so a relayer can bundle the approval with the
+    /// call that spends it. Each nonce is usable once, in order, and the
+    /// permit is void after `expiration_ledger`.
+    pub fn permit(
+        e: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        nonce: u64,
+        expiration_ledger: u32,
+    ) {
+        owner.require_auth_for_args(
+            (spender.clone(), amount, nonce, expiration_ledger).into_val(&e),
+        );
+        check_nonnegative_amount(amount);
+        hooks::check_not_locked(&e);
+        balance::check_not_frozen(&e, &owner);
+        if e.ledger().sequence() > expiration_ledger {
+            panic!("permit expired");
+        }
+
+        allowance::use_permit_nonce(&e, owner.clone(), nonce);
+        allowance::write_allowance(
+            &e,
+            owner.clone(),
+            spender.clone(),
+            amount,
+            expiration_ledger,
+        );
+        events::Approve {
+            from: owner,
+            spender,
+            amount,
+            expiration_ledger,
+        }
+        .publish(&e);
+    }
+
+    /// Next nonce the owner must sign into a permit
+    pub fn permit_nonce(e: Env, owner: Address) -> u64 {
+        allowance::read_permit_nonce(&e, owner)
+    }
+
+    /// Register or clear the hook consulted on every transfer
+    pub fn set_transfer_hook(e: Env, kind: HookKind, hook: Option<Address>) {
+        let admin = admin::read_administrator(&e);
+        admin.require_auth();
+        hooks::write_hook(&e, kind, hook.clone());
+        events::TransferHookSet { kind, hook }.publish(&e);
+    }
+
+    pub fn transfer_hook(e: Env, kind: HookKind) -> Option<Address> {
+        hooks::read_hook(&e, kind)
+    }
+
+    pub fn has_role(e: Env, role: Role, account: Address) -> bool {
+        roles::has_role(&e, role, &account)
+    }
+
+    /// Cap the total supply. The cap cannot be set below the current supply.
+    pub fn set_supply_cap(e: Env, cap: i128) {
+        check_nonnegative_amount(cap);
+        let admin = admin::read_administrator(&e);
+        admin.require_auth();
+        supply::write_supply_cap(&e, cap);
+        events::SupplyCapSet { cap }.publish(&e);
+    }
+
+    pub fn supply_cap(e: Env) -> Option<i128> {
+        supply::read_supply_cap(&e)
+    }
+
+    pub fn total_supply(e: Env) -> i128 {
+        supply::read_total_supply(&e)
+    }
+
+    /// Take a fee on every transfer, paid to the fee's treasury out of the
+    /// transferred amount (admin only). `None` turns the fee off.
+    pub fn set_transfer_fee(e: Env, fee: Option<TransferFee>) {
         let admin = admin::read_administrator(&e);
         admin.require_auth();
-        admin::write_administrator(&e, &new_admin);
+        fees::write_transfer_fee(&e, fee.clone());
+        events::TransferFeeSet { fee }.publish(&e);
+    }
+
+    pub fn transfer_fee(e: Env) -> Option<TransferFee> {
+        fees::read_transfer_fee(&e)
+    }
+
+    /// Split of a transfer of `amount` between fee and recipient. The sender
+    /// is always debited the full `amount`; the recipient receives
+    /// `received`, so integrators that need an exact delivered amount must
+    /// gross it up before transferring.
+    pub fn quote_transfer(e: Env, amount: i128) -> TransferQuote {
+        check_nonnegative_amount(amount);
+        fees::quote(&e, amount)
+    }
+
+    /// Balance of `id` as of the end of a past `ledger`, for vote weighting
+    pub fn balance_at(e: Env, id: Address, ledger: u32) -> i128 {
+        checkpoints::balance_at(&e, id, ledger)
+    }
+
+    /// Total supply as of the end of a past `ledger`
+    pub fn total_supply_at(e: Env, ledger: u32) -> i128 {
+        checkpoints::supply_at(&e, ledger)
+    }
+
+    /// Hand `delegator`'s voting power to `delegatee`. Delegating to
+    /// oneself takes it back.
+    pub fn delegate(e: Env, delegator: Address, delegatee: Address) {
+        delegator.require_auth();
+        hooks::check_not_locked(&e);
+        let from_delegate = votes::read_delegate(&e, &delegator);
+        votes::write_delegate(&e, &delegator, &delegatee);
+        events::DelegateChanged {
+            delegator,
+            from_delegate,
+            to_delegate: delegatee,
+        }
+        .publish(&e);
+    }
+
+    /// Address currently voting with `account`'s balance
+    pub fn delegates(e: Env, account: Address) -> Address {
+        votes::read_delegate(&e, &account)
+    }
+
+    /// Voting power currently delegated to `account`
+    pub fn votes(e: Env, account: Address) -> i128 {
+        checkpoints::latest_votes(&e, account)
+    }
+
+    /// Voting power delegated to `account` as of the end of a past `ledger`
+    pub fn votes_at(e: Env, account: Address, ledger: u32) -> i128 {
+        checkpoints::votes_at(&e, account, ledger)
+    }
+
+    pub fn set_admin(e: Env, new_admin: Address) {
+        let admin = admin::read_administrator(&e);
+        admin::write_administrator(&e, &admin, &new_admin);
+        events::SetAdmin { admin, new_admin }.publish(&e);
+    }
+
+    pub fn admin(e: Env) -> Address {
+        admin::read_administrator(&e)
     }
 
     pub fn freeze(e: Env, id: Address) {
@@ -44,60 +242,121 @@ impl LumenToken {
         admin.require_auth();
         balance::write_state(&e, id, false);
     }
+}
 
-    pub fn allowance(e: Env, from: Address, spender: Address) -> i128 {
+/// SEP-41 interface. Allowances follow Stellar Asset Contract semantics:
+/// `approve` overwrites any previous allowance, an allowance stops counting
+/// once `expiration_ledger` has passed, and approving zero revokes it.
+#[contractimpl]
+impl TokenInterface for LumenToken {
+    fn allowance(e: Env, from: Address, spender: Address) -> i128 {
         allowance::read_allowance(&e, from, spender).amount
     }
 
-    pub fn approve(e: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+    fn approve(e: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
         from.require_auth();
+        check_nonnegative_amount(amount);
+        hooks::check_not_locked(&e);
         balance::check_not_frozen(&e, &from);
-        allowance::write_allowance(&e, from, spender, amount, expiration_ledger);
+        allowance::write_allowance(&e, from.clone(), spender.clone(), amount, expiration_ledger);
+        events::Approve {
+            from,
+            spender,
+            amount,
+            expiration_ledger,
+        }
+        .publish(&e);
     }
 
-    pub fn balance(e: Env, id: Address) -> i128 {
+    fn balance(e: Env, id: Address) -> i128 {
         balance::read_balance(&e, id)
     }
 
-    pub fn transfer(e: Env, from: Address, to: Address, amount: i128) {
+    fn transfer(e: Env, from: Address, to: MuxedAddress, amount: i128) {
         from.require_auth();
+        check_nonnegative_amount(amount);
+        hooks::check_transfer(&e, &from, &to.address(), amount);
         // balance::spend_balance checks from is not frozen
         // balance::receive_balance checks to is not frozen
         balance::spend_balance(&e, from.clone(), amount);
-        balance::receive_balance(&e, to, amount);
+        let received = fees::charge(&e, &from, amount);
+        balance::receive_balance(&e, to.address(), received);
+
+        match to.id() {
+            Some(to_muxed_id) => events::TransferMuxed {
+                from,
+                to: to.address(),
+                to_muxed_id,
+                amount: received,
+            }
+            .publish(&e),
+            None => events::Transfer {
+                from,
+                to: to.address(),
+                amount: received,
+            }
+            .publish(&e),
+        }
     }
 
-    pub fn transfer_from(e: Env, spender: Address, from: Address, to: Address, amount: i128) {
+    fn transfer_from(e: Env, spender: Address, from: Address, to: Address, amount: i128) {
         spender.require_auth();
+        check_nonnegative_amount(amount);
         balance::check_not_frozen(&e, &spender);
+        hooks::check_transfer(&e, &from, &to, amount);
 
         allowance::spend_allowance(&e, from.clone(), spender, amount);
         balance::spend_balance(&e, from.clone(), amount);
-        balance::receive_balance(&e, to, amount);
+        let received = fees::charge(&e, &from, amount);
+        balance::receive_balance(&e, to.clone(), received);
+        events::Transfer {
+            from,
+            to,
+            amount: received,
+        }
+        .publish(&e);
     }
 
-    pub fn burn(e: Env, from: Address, amount: i128) {
+    fn burn(e: Env, from: Address, amount: i128) {
         from.require_auth();
-        balance::spend_balance(&e, from, amount);
+        check_nonnegative_amount(amount);
+        hooks::check_not_locked(&e);
+        balance::spend_balance(&e, from.clone(), amount);
+        supply::decrease_supply(&e, amount);
+        events::Burn { from, amount }.publish(&e);
     }
 
-    pub fn burn_from(e: Env, spender: Address, from: Address, amount: i128) {
-        spender.require_auth();
+    /// Burn on the owner's behalf. The spender needs both the burner role and
+    /// an allowance from `from`; there is no way to burn without consent.
+    fn burn_from(e: Env, spender: Address, from: Address, amount: i128) {
+        check_nonnegative_amount(amount);
+        roles::require_role(&e, Role::Burner, &spender);
         balance::check_not_frozen(&e, &spender);
+        hooks::check_not_locked(&e);
 
         allowance::spend_allowance(&e, from.clone(), spender, amount);
-        balance::spend_balance(&e, from, amount);
+        balance::spend_balance(&e, from.clone(), amount);
+        supply::decrease_supply(&e, amount);
+        events::Burn { from, amount }.publish(&e);
     }
 
-    pub fn decimals(e: Env) -> u32 {
+    fn decimals(e: Env) -> u32 {
         metadata::read_decimal(&e)
     }
 
-    pub fn name(e: Env) -> String {
+    fn name(e: Env) -> String {
         metadata::read_name(&e)
     }
 
-    pub fn symbol(e: Env) -> String {
+    fn symbol(e: Env) -> String {
         metadata::read_symbol(&e)
     }
 }
+
+impl Upgradeable for LumenToken {
+    fn upgrade_admin(e: &Env) -> Option<Address> {
+        admin::has_administrator(e).then(|| admin::read_administrator(e))
+    }
+}
+
+lumenpulse_upgradeable::upgradeable!(LumenToken);
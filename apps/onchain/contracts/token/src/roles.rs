@@ -0,0 +1,38 @@
+use crate::admin;
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+pub use lumenpulse_token_interface::Role;
+
+/// Name `lumenpulse-access` keeps the role under
+fn role_name(role: Role) -> Symbol {
+    match role {
+        Role::Minter => symbol_short!("minter"),
+        Role::Burner => symbol_short!("burner"),
+    }
+}
+
+/// Whether `account` holds `role`; the admin holds every role
+pub fn has_role(e: &Env, role: Role, account: &Address) -> bool {
+    lumenpulse_access::has_role(e, &role_name(role), account)
+        || (admin::has_administrator(e) && admin::read_administrator(e) == *account)
+}
+
+/// Grant `role` to `account` (admin only)
+pub fn grant_role(e: &Env, role: Role, account: &Address) {
+    let admin = admin::read_administrator(e);
+    lumenpulse_access::grant_role(e, &admin, &role_name(role), account).expect("not admin");
+}
+
+/// Take `role` away from `account` (admin only)
+pub fn revoke_role(e: &Env, role: Role, account: &Address) {
+    let admin = admin::read_administrator(e);
+    lumenpulse_access::revoke_role(e, &admin, &role_name(role), account).expect("not admin");
+}
+
+/// Check that `account` holds `role`, or is the admin, and has authorized
+/// the call
+pub fn require_role(e: &Env, role: Role, account: &Address) {
+    if lumenpulse_access::require_role(e, &role_name(role), account).is_err() {
+        panic!("account is missing role");
+    }
+}
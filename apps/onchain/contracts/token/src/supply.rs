@@ -0,0 +1,49 @@
+use crate::checkpoints;
+use soroban_sdk::{contracttype, Env};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    TotalSupply,
+    SupplyCap,
+}
+
+pub fn read_total_supply(e: &Env) -> i128 {
+    let key = DataKey::TotalSupply;
+    e.storage().instance().get(&key).unwrap_or(0)
+}
+
+pub fn read_supply_cap(e: &Env) -> Option<i128> {
+    let key = DataKey::SupplyCap;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_supply_cap(e: &Env, cap: i128) {
+    if cap < read_total_supply(e) {
+        panic!("supply cap is below total supply");
+    }
+    e.storage().instance().set(&DataKey::SupplyCap, &cap);
+}
+
+/// Record newly minted tokens, enforcing the supply cap if one is set
+pub fn increase_supply(e: &Env, amount: i128) {
+    let supply = read_total_supply(e)
+        .checked_add(amount)
+        .expect("total supply overflow");
+    if let Some(cap) = read_supply_cap(e) {
+        if supply > cap {
+            panic!("supply cap exceeded");
+        }
+    }
+    write_total_supply(e, supply);
+}
+
+pub fn decrease_supply(e: &Env, amount: i128) {
+    let supply = read_total_supply(e) - amount;
+    write_total_supply(e, supply);
+}
+
+fn write_total_supply(e: &Env, supply: i128) {
+    e.storage().instance().set(&DataKey::TotalSupply, &supply);
+    checkpoints::record_supply(e, supply);
+}
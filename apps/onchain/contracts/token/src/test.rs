@@ -1,8 +1,28 @@
 #![cfg(test)]
 extern crate std;
 
-use crate::{LumenToken, LumenTokenClient};
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use crate::{HookKind, LumenToken, LumenTokenClient, Role, TransferFee};
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    testutils::{
+        Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger, MuxedAddress as _,
+    },
+    token::TokenClient,
+    vec, Address, Env, IntoVal, MuxedAddress, String, Symbol,
+};
+
+fn setup_token<'a>(env: &Env) -> (LumenTokenClient<'a>, Address) {
+    let admin = Address::generate(env);
+    let contract_id = env.register(LumenToken, ());
+    let client = LumenTokenClient::new(env, &contract_id);
+    client.initialize(
+        &admin,
+        &7,
+        &String::from_str(env, "LumenPulse"),
+        &String::from_str(env, "LMN"),
+    );
+    (client, admin)
+}
 
 #[test]
 fn test_token() {
@@ -27,7 +47,7 @@ fn test_token() {
     assert_eq!(client.name(), String::from_str(&env, "LumenPulse"));
     assert_eq!(client.symbol(), String::from_str(&env, "LMN"));
 
-    client.mint(&user1, &1000);
+    client.mint(&admin, &user1, &1000);
     assert_eq!(client.balance(&user1), 1000);
 
     client.transfer(&user1, &user2, &500);
@@ -58,8 +78,724 @@ fn test_freeze() {
         &String::from_str(&env, "LMN"),
     );
 
-    client.mint(&user1, &1000);
+    client.mint(&admin, &user1, &1000);
     client.freeze(&user1);
 
     client.transfer(&user1, &user2, &100);
 }
+
+#[test]
+fn test_sep41_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    client.mint(&admin, &user1, &1000);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "mint"), user1.clone()).into_val(&env),
+                1000i128.into_val(&env),
+            ),
+        ]
+    );
+
+    client.transfer(&user1, &user2, &400);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "transfer"), user1.clone(), user2.clone()).into_val(&env),
+                400i128.into_val(&env),
+            ),
+        ]
+    );
+
+    client.burn(&user2, &100);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "burn"), user2.clone()).into_val(&env),
+                100i128.into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_transfer_to_muxed_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user1 = Address::generate(&env);
+    // Only account addresses can be multiplexed
+    let user2 = Address::from_str(
+        &env,
+        "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+    );
+    client.mint(&admin, &user1, &1000);
+
+    let muxed = MuxedAddress::new(user2.clone(), 42);
+    client.transfer(&user1, &muxed, &250);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (Symbol::new(&env, "transfer"), user1, user2.clone()).into_val(&env)
+    );
+    assert_eq!(client.balance(&user2), 250);
+}
+
+#[test]
+fn test_standard_token_client() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint(&admin, &user1, &1000);
+
+    // Any SEP-41 consumer can drive the contract through the standard client
+    let token = TokenClient::new(&env, &client.address);
+    token.transfer(&user1, &user2, &300);
+    assert_eq!(token.balance(&user2), 300);
+    assert_eq!(token.decimals(), 7);
+    assert_eq!(token.symbol(), String::from_str(&env, "LMN"));
+}
+
+#[test]
+#[should_panic(expected = "negative amount is not allowed")]
+fn test_transfer_negative_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint(&admin, &user1, &1000);
+
+    client.transfer(&user1, &user2, &-1);
+}
+
+#[test]
+#[should_panic(expected = "decimal must not be greater than 18")]
+fn test_initialize_rejects_large_decimals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(LumenToken, ());
+    let client = LumenTokenClient::new(&env, &contract_id);
+    client.initialize(
+        &admin,
+        &19,
+        &String::from_str(&env, "LumenPulse"),
+        &String::from_str(&env, "LMN"),
+    );
+}
+
+#[test]
+fn test_approve_and_transfer_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let to = Address::generate(&env);
+    client.mint(&admin, &owner, &1000);
+
+    let expiration_ledger = env.ledger().sequence() + 100;
+    client.approve(&owner, &spender, &600, &expiration_ledger);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "approve"), owner.clone(), spender.clone()).into_val(&env),
+                (600i128, expiration_ledger).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.allowance(&owner, &spender), 600);
+
+    client.transfer_from(&spender, &owner, &to, &250);
+    assert_eq!(client.allowance(&owner, &spender), 350);
+    assert_eq!(client.balance(&to), 250);
+
+    client.grant_role(&Role::Burner, &spender);
+    client.burn_from(&spender, &owner, &50);
+    assert_eq!(client.allowance(&owner, &spender), 300);
+    assert_eq!(client.balance(&owner), 700);
+
+    // Approving again overwrites rather than adds
+    client.approve(&owner, &spender, &100, &expiration_ledger);
+    assert_eq!(client.allowance(&owner, &spender), 100);
+}
+
+#[test]
+fn test_allowance_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.mint(&admin, &owner, &1000);
+
+    let expiration_ledger = env.ledger().sequence() + 10;
+    client.approve(&owner, &spender, &500, &expiration_ledger);
+
+    env.ledger().set_sequence_number(expiration_ledger);
+    assert_eq!(client.allowance(&owner, &spender), 500);
+
+    env.ledger().set_sequence_number(expiration_ledger + 1);
+    assert_eq!(client.allowance(&owner, &spender), 0);
+}
+
+#[test]
+#[should_panic(expected = "insufficient allowance")]
+fn test_transfer_from_expired_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.mint(&admin, &owner, &1000);
+
+    let expiration_ledger = env.ledger().sequence() + 10;
+    client.approve(&owner, &spender, &500, &expiration_ledger);
+    env.ledger().set_sequence_number(expiration_ledger + 1);
+
+    client.transfer_from(&spender, &owner, &spender, &1);
+}
+
+#[test]
+#[should_panic(expected = "expiration_ledger is less than ledger seq when amount > 0")]
+fn test_approve_past_expiration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_token(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    env.ledger().set_sequence_number(100);
+
+    // Revoking with a past ledger is allowed
+    client.approve(&owner, &spender, &0, &50);
+    client.approve(&owner, &spender, &1, &50);
+}
+
+#[test]
+fn test_minter_role() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let minter = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    assert!(client.has_role(&Role::Minter, &admin));
+    assert!(!client.has_role(&Role::Minter, &minter));
+
+    client.grant_role(&Role::Minter, &minter);
+    client.mint(&minter, &user, &500);
+    assert_eq!(client.balance(&user), 500);
+    assert_eq!(client.total_supply(), 500);
+
+    client.revoke_role(&Role::Minter, &minter);
+    assert!(client.try_mint(&minter, &user, &1).is_err());
+}
+
+#[test]
+#[should_panic(expected = "account is missing role")]
+fn test_mint_requires_minter_role() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_token(&env);
+    let user = Address::generate(&env);
+
+    client.mint(&user, &user, &500);
+}
+
+#[test]
+#[should_panic(expected = "account is missing role")]
+fn test_burn_from_requires_burner_role() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.mint(&admin, &owner, &1000);
+    client.approve(&owner, &spender, &500, &(env.ledger().sequence() + 100));
+
+    client.burn_from(&spender, &owner, &100);
+}
+
+#[test]
+fn test_supply_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user = Address::generate(&env);
+
+    client.set_supply_cap(&1_000);
+    assert_eq!(client.supply_cap(), Some(1_000));
+
+    client.mint(&admin, &user, &800);
+    assert!(client.try_mint(&admin, &user, &201).is_err());
+
+    // Burning frees up room under the cap
+    client.burn(&user, &300);
+    assert_eq!(client.total_supply(), 500);
+    client.mint(&admin, &user, &500);
+    assert_eq!(client.total_supply(), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "supply cap is below total supply")]
+fn test_supply_cap_below_supply() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user = Address::generate(&env);
+    client.mint(&admin, &user, &800);
+
+    client.set_supply_cap(&799);
+}
+
+#[contracttype]
+enum HookDataKey {
+    Approved(Address),
+    Minter,
+}
+
+/// Recipient hook that only lets allowlisted addresses receive
+#[contract]
+struct AllowlistHook;
+
+#[contractimpl]
+impl AllowlistHook {
+    pub fn allow(env: Env, account: Address) {
+        env.storage()
+            .instance()
+            .set(&HookDataKey::Approved(account), &true);
+    }
+
+    pub fn on_transfer(
+        env: Env,
+        _token: Address,
+        _from: Address,
+        to: Address,
+        _amount: i128,
+    ) -> bool {
+        env.storage().instance().has(&HookDataKey::Approved(to))
+    }
+}
+
+/// Hook that tries to move funds back through the token while running
+#[contract]
+struct ReentrantHook;
+
+#[contractimpl]
+impl ReentrantHook {
+    pub fn on_transfer(env: Env, token: Address, from: Address, to: Address, amount: i128) -> bool {
+        TokenClient::new(&env, &token).transfer(&to, &from, &amount);
+        true
+    }
+}
+
+/// Hook that tries to mint to the recipient while running
+#[contract]
+struct MintingHook;
+
+#[contractimpl]
+impl MintingHook {
+    pub fn on_transfer(
+        env: Env,
+        token: Address,
+        _from: Address,
+        to: Address,
+        amount: i128,
+    ) -> bool {
+        let minter: Address = env.storage().instance().get(&HookDataKey::Minter).unwrap();
+        LumenTokenClient::new(&env, &token).mint(&minter, &to, &amount);
+        true
+    }
+}
+
+#[test]
+fn test_recipient_hook_vetoes_transfers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    client.mint(&admin, &user1, &1000);
+
+    let hook_id = env.register(AllowlistHook, ());
+    let hook = AllowlistHookClient::new(&env, &hook_id);
+    hook.allow(&user2);
+    client.set_transfer_hook(&HookKind::Recipient, &Some(hook_id.clone()));
+    assert_eq!(client.transfer_hook(&HookKind::Recipient), Some(hook_id));
+
+    client.transfer(&user1, &user2, &100);
+    assert_eq!(client.balance(&user2), 100);
+
+    assert!(client.try_transfer(&user1, &outsider, &100).is_err());
+    assert_eq!(client.balance(&outsider), 0);
+
+    // Allowance-based transfers are screened too
+    client.approve(&user1, &user2, &500, &(env.ledger().sequence() + 100));
+    assert!(client
+        .try_transfer_from(&user2, &user1, &outsider, &100)
+        .is_err());
+
+    client.set_transfer_hook(&HookKind::Recipient, &None);
+    client.transfer(&user1, &outsider, &100);
+    assert_eq!(client.balance(&outsider), 100);
+}
+
+#[test]
+#[should_panic(expected = "transfer rejected by hook")]
+fn test_reentrant_hook_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint(&admin, &user1, &1000);
+
+    let hook_id = env.register(ReentrantHook, ());
+    client.set_transfer_hook(&HookKind::Sender, &Some(hook_id));
+
+    client.transfer(&user1, &user2, &100);
+}
+
+#[test]
+fn test_clawback() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user = Address::generate(&env);
+    client.mint(&admin, &user, &1000);
+
+    // Frozen balances can still be clawed back
+    client.freeze(&user);
+    client.clawback(&user, &400);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "clawback"), user.clone()).into_val(&env),
+                400i128.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.balance(&user), 600);
+    assert_eq!(client.total_supply(), 600);
+}
+
+#[test]
+#[should_panic(expected = "insufficient balance")]
+fn test_clawback_exceeds_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user = Address::generate(&env);
+    client.mint(&admin, &user, &100);
+
+    client.clawback(&user, &101);
+}
+
+#[test]
+fn test_clawback_requires_admin() {
+    let env = Env::default();
+
+    let (client, _) = setup_token(&env);
+    let user = Address::generate(&env);
+
+    assert!(client.try_clawback(&user, &0).is_err());
+}
+
+#[test]
+fn test_permit_sets_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.mint(&admin, &owner, &1000);
+
+    let expiration_ledger = env.ledger().sequence() + 100;
+    assert_eq!(client.permit_nonce(&owner), 0);
+    client.permit(&owner, &spender, &300, &0, &expiration_ledger);
+
+    // The owner authorized exactly the permit arguments
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            owner.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "permit"),
+                    (spender.clone(), 300i128, 0u64, expiration_ledger).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(client.allowance(&owner, &spender), 300);
+    assert_eq!(client.permit_nonce(&owner), 1);
+
+    client.transfer_from(&spender, &owner, &recipient, &300);
+    assert_eq!(client.balance(&recipient), 300);
+}
+
+#[test]
+#[should_panic(expected = "invalid permit nonce")]
+fn test_permit_replay_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_token(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    let expiration_ledger = env.ledger().sequence() + 100;
+    client.permit(&owner, &spender, &300, &0, &expiration_ledger);
+    client.permit(&owner, &spender, &300, &0, &expiration_ledger);
+}
+
+#[test]
+#[should_panic(expected = "permit expired")]
+fn test_permit_expired() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_token(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    let expiration_ledger = env.ledger().sequence() + 10;
+    env.ledger().set_sequence_number(expiration_ledger + 1);
+    client.permit(&owner, &spender, &300, &0, &expiration_ledger);
+}
+
+#[test]
+fn test_balance_checkpoints() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+
+    env.ledger().set_sequence_number(10);
+    client.mint(&admin, &user1, &1000);
+
+    env.ledger().set_sequence_number(20);
+    client.transfer(&user1, &user2, &400);
+    // Several moves within one ledger only keep the final balance
+    client.transfer(&user2, &user1, &100);
+
+    env.ledger().set_sequence_number(30);
+    client.burn(&user1, &200);
+
+    env.ledger().set_sequence_number(31);
+    assert_eq!(client.balance_at(&user1, &9), 0);
+    assert_eq!(client.balance_at(&user1, &10), 1000);
+    assert_eq!(client.balance_at(&user1, &19), 1000);
+    assert_eq!(client.balance_at(&user1, &20), 700);
+    assert_eq!(client.balance_at(&user2, &25), 300);
+    assert_eq!(client.balance_at(&user1, &30), 500);
+
+    assert_eq!(client.total_supply_at(&15), 1000);
+    assert_eq!(client.total_supply_at(&30), 800);
+}
+
+#[test]
+#[should_panic(expected = "ledger is not yet finalized")]
+fn test_balance_at_current_ledger() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user = Address::generate(&env);
+    client.mint(&admin, &user, &1000);
+
+    client.balance_at(&user, &env.ledger().sequence());
+}
+
+#[test]
+fn test_transfer_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let treasury = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.mint(&admin, &user1, &10_000);
+
+    client.set_transfer_fee(&Some(TransferFee {
+        bps: 250,
+        treasury: treasury.clone(),
+    }));
+
+    let quote = client.quote_transfer(&1_000);
+    assert_eq!(quote.fee, 25);
+    assert_eq!(quote.received, 975);
+
+    client.transfer(&user1, &user2, &1_000);
+    assert_eq!(client.balance(&user1), 9_000);
+    assert_eq!(client.balance(&user2), quote.received);
+    assert_eq!(client.balance(&treasury), quote.fee);
+
+    client.approve(&user1, &spender, &2_000, &(env.ledger().sequence() + 100));
+    client.transfer_from(&spender, &user1, &user2, &2_000);
+    assert_eq!(client.balance(&user2), 975 + 1_950);
+    assert_eq!(client.balance(&treasury), 25 + 50);
+    assert_eq!(client.total_supply(), 10_000);
+
+    client.set_transfer_fee(&None);
+    assert_eq!(client.quote_transfer(&1_000).received, 1_000);
+}
+
+#[test]
+#[should_panic(expected = "transfer fee exceeds maximum")]
+fn test_transfer_fee_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_token(&env);
+    client.set_transfer_fee(&Some(TransferFee {
+        bps: 1_001,
+        treasury: Address::generate(&env),
+    }));
+}
+
+#[test]
+fn test_delegate_votes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let delegatee = Address::generate(&env);
+
+    env.ledger().set_sequence_number(10);
+    client.mint(&admin, &user1, &1000);
+    client.mint(&admin, &user2, &400);
+    assert_eq!(client.delegates(&user1), user1);
+    assert_eq!(client.votes(&user1), 1000);
+
+    env.ledger().set_sequence_number(20);
+    client.delegate(&user1, &delegatee);
+    client.delegate(&user2, &delegatee);
+    assert_eq!(client.delegates(&user1), delegatee);
+    assert_eq!(client.votes(&user1), 0);
+    assert_eq!(client.votes(&delegatee), 1400);
+
+    // Balance changes follow the delegation
+    env.ledger().set_sequence_number(30);
+    client.transfer(&user1, &user2, &300);
+    assert_eq!(client.votes(&delegatee), 1400);
+    client.burn(&user2, &100);
+    assert_eq!(client.votes(&delegatee), 1300);
+
+    // Taking votes back moves only this holder's balance
+    env.ledger().set_sequence_number(40);
+    client.delegate(&user2, &user2);
+    assert_eq!(client.votes(&user2), 600);
+    assert_eq!(client.votes(&delegatee), 700);
+
+    env.ledger().set_sequence_number(41);
+    assert_eq!(client.votes_at(&user1, &10), 1000);
+    assert_eq!(client.votes_at(&user1, &20), 0);
+    assert_eq!(client.votes_at(&delegatee, &19), 0);
+    assert_eq!(client.votes_at(&delegatee, &20), 1400);
+    assert_eq!(client.votes_at(&delegatee, &30), 1300);
+    assert_eq!(client.votes_at(&delegatee, &40), 700);
+    assert_eq!(client.balance(&delegatee), 0);
+}
+
+#[test]
+fn test_hook_cannot_mint() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin) = setup_token(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint(&admin, &user1, &1000);
+
+    // The hook mints as the admin, who holds the minter role
+    let hook_id = env.register(MintingHook, ());
+    env.as_contract(&hook_id, || {
+        env.storage().instance().set(&HookDataKey::Minter, &admin)
+    });
+    client.set_transfer_hook(&HookKind::Recipient, &Some(hook_id));
+
+    assert!(client.try_transfer(&user1, &user2, &100).is_err());
+    assert_eq!(client.balance(&user2), 0);
+    assert_eq!(client.total_supply(), 1000);
+}
+
+#[test]
+fn test_admin_stored_before_access_is_adopted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // A token deployed before `lumenpulse-access` keeps its admin here
+    let admin = Address::generate(&env);
+    let contract_id = env.register(LumenToken, ());
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .instance()
+            .set(&crate::admin::DataKey::Admin, &admin);
+    });
+    let client = LumenTokenClient::new(&env, &contract_id);
+
+    assert_eq!(client.admin(), admin);
+    assert!(client.has_role(&Role::Minter, &admin));
+    let user = Address::generate(&env);
+    client.mint(&admin, &user, &100);
+    assert_eq!(client.balance(&user), 100);
+
+    let successor = Address::generate(&env);
+    client.set_admin(&successor);
+    assert_eq!(client.admin(), successor);
+    assert!(!client.has_role(&Role::Minter, &admin));
+}
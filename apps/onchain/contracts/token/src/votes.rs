@@ -0,0 +1,46 @@
+//! Vote delegation. Every holder's balance counts as votes for exactly one
+//! delegate, which is the holder itself until it delegates elsewhere. Vote
+//! totals are checkpointed per ledger so past tallies stay fixed.
+
+use crate::{balance, checkpoints};
+use soroban_sdk::{contracttype, Address, Env};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Delegate(Address),
+}
+
+pub fn read_delegate(e: &Env, account: &Address) -> Address {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Delegate(account.clone()))
+        .unwrap_or(account.clone())
+}
+
+/// Point `account`'s votes at `delegatee`, carrying its whole balance over
+pub fn write_delegate(e: &Env, account: &Address, delegatee: &Address) {
+    let previous = read_delegate(e, account);
+    if previous == *delegatee {
+        return;
+    }
+
+    let key = DataKey::Delegate(account.clone());
+    if delegatee == account {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage().persistent().set(&key, delegatee);
+    }
+
+    let weight = balance::read_balance(e, account.clone());
+    move_votes(e, &previous, -weight);
+    move_votes(e, delegatee, weight);
+}
+
+pub fn move_votes(e: &Env, delegate: &Address, delta: i128) {
+    if delta == 0 {
+        return;
+    }
+    let votes = checkpoints::latest_votes(e, delegate.clone());
+    checkpoints::record_votes(e, delegate.clone(), votes + delta);
+}
@@ -0,0 +1,17 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TreasuryError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    InvalidAmount = 3,
+    InvalidBudget = 4,
+    BudgetExceeded = 5,
+    TooManyCategories = 6,
+    TooManyReleases = 7,
+    ReleaseNotFound = 8,
+    ReleaseCancelled = 9,
+    StreamFailed = 10,
+}
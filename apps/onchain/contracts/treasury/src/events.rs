@@ -0,0 +1,40 @@
+use soroban_sdk::{contractevent, Address, Symbol};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub governance: Address,
+    pub streaming: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BudgetSetEvent {
+    #[topic]
+    pub category: Symbol,
+    #[topic]
+    pub token: Address,
+    pub allocated: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseScheduledEvent {
+    #[topic]
+    pub category: Symbol,
+    #[topic]
+    pub recipient: Address,
+    pub release_id: u64,
+    pub stream_id: u64,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseCancelledEvent {
+    #[topic]
+    pub category: Symbol,
+    pub release_id: u64,
+    pub refunded: i128,
+}
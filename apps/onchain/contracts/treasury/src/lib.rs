@@ -0,0 +1,302 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::TreasuryError;
+use lumenpulse_streaming_interface::StreamingClient;
+use soroban_sdk::{contract, contractimpl, Address, Env, Symbol, Vec};
+use storage::{Budget, DataKey, Release, SpendReport, MAX_CATEGORIES, MAX_RELEASES_PER_CATEGORY};
+
+/// Holds protocol funds and pays them out only on schedules governance
+/// approves. Every release is a stream the treasury creates on the
+/// streaming contract as sender, so funds leave gradually and a cancelled
+/// release returns what has not streamed yet. Releases are charged to a
+/// category's budget for their token, which the spend reports break down.
+#[contract]
+pub struct TreasuryContract;
+
+#[contractimpl]
+impl TreasuryContract {
+    /// Initialize the treasury with the governance address that approves
+    /// every budget and release, and the streaming contract releases use
+    pub fn initialize(
+        env: Env,
+        governance: Address,
+        streaming: Address,
+    ) -> Result<(), TreasuryError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Governance) {
+            return Err(TreasuryError::AlreadyInitialized);
+        }
+
+        // Require governance authorization
+        governance.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Governance, &governance);
+        env.storage()
+            .instance()
+            .set(&DataKey::Streaming, &streaming);
+
+        events::InitializedEvent {
+            governance,
+            streaming,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Set how much of `token` `category` may spend in total (governance
+    /// only). The allocation can not drop below what is already committed.
+    pub fn set_budget(
+        env: Env,
+        category: Symbol,
+        token: Address,
+        allocated: i128,
+    ) -> Result<(), TreasuryError> {
+        Self::require_governance(&env)?;
+
+        let budget = Self::get_budget(env.clone(), category.clone(), token.clone());
+        if allocated < budget.committed {
+            return Err(TreasuryError::InvalidBudget);
+        }
+
+        let mut categories = Self::categories(env.clone());
+        if !categories.contains(&category) {
+            if categories.len() >= MAX_CATEGORIES {
+                return Err(TreasuryError::TooManyCategories);
+            }
+            categories.push_back(category.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Categories, &categories);
+        }
+        env.storage().persistent().set(
+            &DataKey::Budget(category.clone(), token.clone()),
+            &Budget {
+                allocated,
+                ..budget
+            },
+        );
+
+        events::BudgetSetEvent {
+            category,
+            token,
+            allocated,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Stream `amount` of `token` to `recipient` between `start_time` and
+    /// `end_time`, charged to `category`'s budget (governance only).
+    /// Returns the release ID.
+    pub fn schedule_release(
+        env: Env,
+        category: Symbol,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<u64, TreasuryError> {
+        Self::require_governance(&env)?;
+
+        if amount <= 0 {
+            return Err(TreasuryError::InvalidAmount);
+        }
+        let budget_key = DataKey::Budget(category.clone(), token.clone());
+        let mut budget = Self::get_budget(env.clone(), category.clone(), token.clone());
+        if budget.committed + amount > budget.allocated {
+            return Err(TreasuryError::BudgetExceeded);
+        }
+        let releases_key = DataKey::CategoryReleases(category.clone());
+        let mut releases = Self::category_releases(env.clone(), category.clone());
+        if releases.len() >= MAX_RELEASES_PER_CATEGORY {
+            return Err(TreasuryError::TooManyReleases);
+        }
+
+        let streaming = Self::streaming(&env)?;
+        let this = env.current_contract_address();
+        // The streaming contract pulls the funds from us in a call below
+        // `create_stream`, which needs our explicit authorization
+        lumenpulse_sac::authorize_transfer(&env, &token, &streaming, amount);
+        let stream_id = match StreamingClient::new(&env, &streaming).try_create_stream(
+            &this,
+            &recipient,
+            &token,
+            &amount,
+            &start_time,
+            &end_time,
+        ) {
+            Ok(Ok(stream_id)) => stream_id,
+            _ => return Err(TreasuryError::StreamFailed),
+        };
+
+        let release_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextReleaseId)
+            .unwrap_or(0);
+        let release = Release {
+            id: release_id,
+            category: category.clone(),
+            recipient: recipient.clone(),
+            token: token.clone(),
+            amount,
+            stream_id,
+            refunded: 0,
+            cancelled: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Release(release_id), &release);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextReleaseId, &(release_id + 1));
+        releases.push_back(release_id);
+        env.storage().persistent().set(&releases_key, &releases);
+        budget.committed += amount;
+        env.storage().persistent().set(&budget_key, &budget);
+
+        events::ReleaseScheduledEvent {
+            category,
+            recipient,
+            release_id,
+            stream_id,
+            token,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(release_id)
+    }
+
+    /// Stop a release (governance only). The recipient keeps what has
+    /// streamed so far and the rest returns to the treasury and the
+    /// category's budget. Returns the amount returned.
+    pub fn cancel_release(env: Env, release_id: u64) -> Result<i128, TreasuryError> {
+        Self::require_governance(&env)?;
+
+        let mut release = Self::get_release(env.clone(), release_id)?;
+        if release.cancelled {
+            return Err(TreasuryError::ReleaseCancelled);
+        }
+
+        let streaming = Self::streaming(&env)?;
+        let this = env.current_contract_address();
+        let before = token::balance(&env, &release.token, &this);
+        match StreamingClient::new(&env, &streaming).try_cancel(&release.stream_id) {
+            Ok(Ok(())) => {}
+            _ => return Err(TreasuryError::StreamFailed),
+        }
+        let refunded = token::balance(&env, &release.token, &this) - before;
+
+        release.refunded = refunded;
+        release.cancelled = true;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Release(release_id), &release);
+        let budget_key = DataKey::Budget(release.category.clone(), release.token.clone());
+        let mut budget =
+            Self::get_budget(env.clone(), release.category.clone(), release.token.clone());
+        budget.committed -= refunded;
+        env.storage().persistent().set(&budget_key, &budget);
+
+        events::ReleaseCancelledEvent {
+            category: release.category,
+            release_id,
+            refunded,
+        }
+        .publish(&env);
+
+        Ok(refunded)
+    }
+
+    /// Spending in `category` for `token` across all its releases
+    pub fn spend_report(env: Env, category: Symbol, token: Address) -> SpendReport {
+        let budget = Self::get_budget(env.clone(), category.clone(), token.clone());
+        let mut refunded = 0;
+        let mut releases = 0;
+        for release_id in Self::category_releases(env.clone(), category.clone()).iter() {
+            if let Ok(release) = Self::get_release(env.clone(), release_id) {
+                if release.token == token {
+                    refunded += release.refunded;
+                    releases += 1;
+                }
+            }
+        }
+        SpendReport {
+            category,
+            token,
+            allocated: budget.allocated,
+            committed: budget.committed,
+            refunded,
+            available: budget.allocated - budget.committed,
+            releases,
+        }
+    }
+
+    /// Budget `category` has for `token`, zero if never set
+    pub fn get_budget(env: Env, category: Symbol, token: Address) -> Budget {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Budget(category, token))
+            .unwrap_or_default()
+    }
+
+    /// Every category a budget was ever set for
+    pub fn categories(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Categories)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// IDs of every release charged to `category`, oldest first
+    pub fn category_releases(env: Env, category: Symbol) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CategoryReleases(category))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get a release by ID
+    pub fn get_release(env: Env, release_id: u64) -> Result<Release, TreasuryError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Release(release_id))
+            .ok_or(TreasuryError::ReleaseNotFound)
+    }
+
+    /// Get the governance address
+    pub fn get_governance(env: Env) -> Result<Address, TreasuryError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Governance)
+            .ok_or(TreasuryError::NotInitialized)
+    }
+}
+
+impl TreasuryContract {
+    fn require_governance(env: &Env) -> Result<(), TreasuryError> {
+        Self::get_governance(env.clone())?.require_auth();
+        Ok(())
+    }
+
+    fn streaming(env: &Env) -> Result<Address, TreasuryError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Streaming)
+            .ok_or(TreasuryError::NotInitialized)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,58 @@
+use soroban_sdk::{contracttype, Address, Symbol};
+
+/// Most budget categories the treasury tracks
+pub const MAX_CATEGORIES: u32 = 20;
+
+/// Most releases one category may schedule
+pub const MAX_RELEASES_PER_CATEGORY: u32 = 100;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Governance,               // -> Address
+    Streaming,                // -> Address
+    NextReleaseId,            // -> u64
+    Categories,               // -> Vec<Symbol>
+    Budget(Symbol, Address),  // (category, token) -> Budget
+    CategoryReleases(Symbol), // category -> Vec<u64>
+    Release(u64),             // release_id -> Release
+}
+
+/// How much of one token a category may spend
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Budget {
+    pub allocated: i128,
+    /// Scheduled in releases, less what cancelled releases returned
+    pub committed: i128,
+}
+
+/// A scheduled payout, streamed to the recipient by the streaming contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Release {
+    pub id: u64,
+    pub category: Symbol,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub stream_id: u64,
+    /// Unstreamed amount returned when the release was cancelled
+    pub refunded: i128,
+    pub cancelled: bool,
+}
+
+/// Spending in one category and token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendReport {
+    pub category: Symbol,
+    pub token: Address,
+    pub allocated: i128,
+    pub committed: i128,
+    /// Returned by cancelled releases
+    pub refunded: i128,
+    /// Still free to schedule
+    pub available: i128,
+    pub releases: u32,
+}
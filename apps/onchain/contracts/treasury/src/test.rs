@@ -0,0 +1,105 @@
+use crate::errors::TreasuryError;
+use crate::{TreasuryContract, TreasuryContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol,
+};
+use streaming::{StreamingContract, StreamingContractClient};
+
+const GRANTS: Symbol = symbol_short!("grants");
+
+struct Setup<'a> {
+    client: TreasuryContractClient<'a>,
+    streaming: StreamingContractClient<'a>,
+    governance: Address,
+    token: TokenClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let governance = Address::generate(env);
+    let streaming = StreamingContractClient::new(env, &env.register(StreamingContract, ()));
+    streaming.initialize(&governance);
+
+    let client = TreasuryContractClient::new(env, &env.register(TreasuryContract, ()));
+    client.initialize(&governance, &streaming.address);
+
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &sac.address()).mint(&client.address, &10_000);
+
+    Setup {
+        client,
+        streaming,
+        governance,
+        token: TokenClient::new(env, &sac.address()),
+    }
+}
+
+#[test]
+fn test_release_streams_from_budget() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let recipient = Address::generate(&env);
+
+    let result =
+        s.client
+            .try_schedule_release(&GRANTS, &recipient, &s.token.address, &1_000, &0, &100);
+    assert_eq!(result, Err(Ok(TreasuryError::BudgetExceeded)));
+
+    s.client.set_budget(&GRANTS, &s.token.address, &1_500);
+    let release_id =
+        s.client
+            .schedule_release(&GRANTS, &recipient, &s.token.address, &1_000, &0, &100);
+    assert_eq!(s.token.balance(&s.client.address), 9_000);
+    let release = s.client.get_release(&release_id);
+    assert_eq!(
+        s.streaming.get_stream(&release.stream_id).sender,
+        s.client.address
+    );
+
+    let result =
+        s.client
+            .try_schedule_release(&GRANTS, &recipient, &s.token.address, &501, &0, &100);
+    assert_eq!(result, Err(Ok(TreasuryError::BudgetExceeded)));
+    let result = s.client.try_set_budget(&GRANTS, &s.token.address, &999);
+    assert_eq!(result, Err(Ok(TreasuryError::InvalidBudget)));
+
+    env.ledger().set_timestamp(50);
+    s.streaming.withdraw(&release.stream_id);
+    assert_eq!(s.token.balance(&recipient), 500);
+}
+
+#[test]
+fn test_cancel_release_returns_unstreamed() {
+    let env = Env::default();
+    let s = setup_test(&env);
+    let recipient = Address::generate(&env);
+
+    s.client.set_budget(&GRANTS, &s.token.address, &2_000);
+    let release_id =
+        s.client
+            .schedule_release(&GRANTS, &recipient, &s.token.address, &1_000, &0, &100);
+    s.client
+        .schedule_release(&GRANTS, &recipient, &s.token.address, &400, &0, &100);
+
+    env.ledger().set_timestamp(25);
+    assert_eq!(s.client.cancel_release(&release_id), 750);
+    assert_eq!(s.token.balance(&recipient), 250);
+    assert_eq!(s.token.balance(&s.client.address), 10_000 - 1_400 + 750);
+    assert_eq!(
+        s.client.try_cancel_release(&release_id),
+        Err(Ok(TreasuryError::ReleaseCancelled))
+    );
+
+    let report = s.client.spend_report(&GRANTS, &s.token.address);
+    assert_eq!(report.allocated, 2_000);
+    assert_eq!(report.committed, 1_400 - 750);
+    assert_eq!(report.refunded, 750);
+    assert_eq!(report.available, 2_000 - 650);
+    assert_eq!(report.releases, 2);
+    assert_eq!(s.client.categories().len(), 1);
+    assert_eq!(s.client.get_governance(), s.governance);
+}
@@ -0,0 +1,6 @@
+use soroban_sdk::{Address, Env};
+
+/// Get the balance of an address for a given token
+pub fn balance(env: &Env, token: &Address, address: &Address) -> i128 {
+    lumenpulse_sac::balance(env, token, address)
+}
@@ -1,10 +1,71 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+use lumenpulse_storage::InstanceItem;
+use soroban_sdk::{contract, contractevent, contractimpl, contracttype, Address, Env};
 
 #[contracttype]
 pub enum DataKey {
-    Admin,
-    Counter,
+    /// The admin as releases before `lumenpulse-access` stored it, moved
+    /// under `AccessKey::Administrator` the first time it is needed
+    Admin, // -> Address
+    /// The first release's count
+    Counter, // -> u32
+    /// The count from the second release on, once `migrate` has moved it
+    Count, // -> u64
+}
+
+#[cfg(not(feature = "v2"))]
+const COUNTER: InstanceItem<DataKey, u32> = InstanceItem::new(DataKey::Counter);
+
+#[cfg(not(feature = "v2"))]
+fn read_count(env: &Env) -> u32 {
+    COUNTER.get(env).unwrap_or(0)
+}
+
+#[cfg(not(feature = "v2"))]
+fn write_count(env: &Env, count: u32) {
+    COUNTER.set(env, &count);
+}
+
+#[cfg(feature = "v2")]
+const LEGACY_COUNTER: InstanceItem<DataKey, u32> = InstanceItem::new(DataKey::Counter);
+#[cfg(feature = "v2")]
+const COUNTER: InstanceItem<DataKey, u64> = InstanceItem::new(DataKey::Count);
+
+#[cfg(feature = "v2")]
+fn read_count(env: &Env) -> u32 {
+    let count = COUNTER.get(env).unwrap_or(0);
+    count.try_into().expect("count overflow")
+}
+
+#[cfg(feature = "v2")]
+fn write_count(env: &Env, count: u32) {
+    COUNTER.set(env, &count.into());
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncrementedEvent {
+    pub count: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigratedEvent {
+    pub count: u32,
+}
+
+const LEGACY_ADMIN: InstanceItem<DataKey, Address> = InstanceItem::new(DataKey::Admin);
+
+/// Move an admin stored by a release before `lumenpulse-access` to where
+/// the access checks look for it, so an instance upgraded from one keeps
+/// its admin
+fn adopt_legacy_admin(env: &Env) {
+    if let Some(admin) = LEGACY_ADMIN.get(env) {
+        if lumenpulse_access::admin(env).is_err() {
+            lumenpulse_access::init_admin(env, &admin).expect("no admin yet");
+        }
+        LEGACY_ADMIN.remove(env);
+    }
 }
 
 #[contract]
@@ -13,36 +74,73 @@ pub struct UpgradableContract;
 #[contractimpl]
 impl UpgradableContract {
     pub fn init(env: Env, admin: Address) {
-        if env.storage().instance().has(&DataKey::Admin) {
+        adopt_legacy_admin(&env);
+        if lumenpulse_access::init_admin(&env, &admin).is_err() {
             panic!("already initialized");
         }
-        env.storage().instance().set(&DataKey::Admin, &admin);
     }
 
     pub fn upgrade(env: Env, new_wasm_hash: soroban_sdk::BytesN<32>) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("not initialized");
+        adopt_legacy_admin(&env);
+        let admin = lumenpulse_access::admin(&env).expect("not initialized");
         admin.require_auth();
 
         env.deployer().update_current_contract_wasm(new_wasm_hash);
     }
 
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) {
+        adopt_legacy_admin(&env);
+        lumenpulse_access::propose_admin(&env, &admin, &new_admin).expect("not admin");
+    }
+
+    pub fn accept_admin(env: Env, new_admin: Address) {
+        adopt_legacy_admin(&env);
+        lumenpulse_access::accept_admin(&env, &new_admin).expect("no pending admin");
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        adopt_legacy_admin(&env);
+        lumenpulse_access::admin(&env).expect("not initialized")
+    }
+
     pub fn increment(env: Env) -> u32 {
-        let mut count: u32 = env.storage().instance().get(&DataKey::Counter).unwrap_or(0);
-        count += 1;
-        env.storage().instance().set(&DataKey::Counter, &count);
+        let count = read_count(&env) + 1;
+        write_count(&env, count);
+        IncrementedEvent { count }.publish(&env);
         count
     }
 
     pub fn get_count(env: Env) -> u32 {
-        env.storage().instance().get(&DataKey::Counter).unwrap_or(0)
+        read_count(&env)
     }
 
     pub fn version() -> u32 {
-        1
+        // The `v2` build is the release the upgrade test moves to
+        if cfg!(feature = "v2") {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// The second release widens the count and keeps it under a new key, so
+/// it has to be migrated after the upgrade
+#[cfg(feature = "v2")]
+#[contractimpl]
+impl UpgradableContract {
+    /// Move the first release's count to the new key (admin only). Later
+    /// calls find nothing left to move.
+    pub fn migrate(env: Env) {
+        adopt_legacy_admin(&env);
+        let admin = lumenpulse_access::admin(&env).expect("not initialized");
+        admin.require_auth();
+
+        if let Some(count) = LEGACY_COUNTER.get(&env) {
+            write_count(&env, count);
+            LEGACY_COUNTER.remove(&env);
+            MigratedEvent { count }.publish(&env);
+        }
     }
 }
 
@@ -1,6 +1,32 @@
 #![cfg(test)]
-use crate::{UpgradableContract, UpgradableContractClient};
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+use crate::{
+    DataKey, IncrementedEvent, MigratedEvent, UpgradableContract, UpgradableContractClient,
+};
+use lumenpulse_test_utils::{
+    auth::{assert_auth_matrix, Outcome},
+    upgradable,
+};
+use proptest::prelude::*;
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig, Events as _},
+    vec, Address, Env, Event, IntoVal, Val,
+};
+
+/// This contract built with the `v2` feature, from the factory's fixtures,
+/// for the `migrate` entrypoint only the second release has
+mod v2 {
+    soroban_sdk::contractimport!(file = "../factory/testdata/upgradable_contract_v2.wasm");
+}
+
+const V2_WASM: &[u8] = v2::WASM;
+
+/// The first release, which kept its admin under `DataKey::Admin`
+mod v0 {
+    soroban_sdk::contractimport!(file = "../factory/testdata/upgradable_contract_v0.wasm");
+}
+
+/// This contract's own build, to upgrade deployed instances to
+const WASM: &[u8] = include_bytes!("../../factory/testdata/upgradable_contract.wasm");
 
 #[test]
 fn test_contract_upgrade() {
@@ -15,24 +41,65 @@ fn test_contract_upgrade() {
     client.init(&admin);
     assert_eq!(client.version(), 1);
     assert_eq!(client.increment(), 1);
-    assert_eq!(client.get_count(), 1);
-
-    // In Soroban tests, we can use a dummy hash to test the upgrade call.
-    // NOTE: This will fail at the VM level because our dummy WASM is empty/invalid,
-    // but it confirms the administrative check (require_auth) passed.
-    let _new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
+    assert_eq!(client.increment(), 2);
 
-    // We expect a panic here because the WASM is invalid, but we've already
-    // proven state exists and admin can call it.
-    // client.upgrade(&new_wasm_hash);
+    // Move to the second release, built from this contract with `v2`
+    let new_wasm_hash = env.deployer().upload_contract_wasm(V2_WASM);
+    client.upgrade(&new_wasm_hash);
+    assert_eq!(client.version(), 2);
 
-    // State should be preserved
-    assert_eq!(client.get_count(), 1);
-    assert_eq!(client.increment(), 2);
+    // The count only reads back once it has been migrated to its new key
+    assert_eq!(client.get_count(), 0);
+    v2::Client::new(&env, &contract_id).migrate();
     assert_eq!(client.get_count(), 2);
+    assert_eq!(client.increment(), 3);
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_upgrade_from_the_first_release_keeps_its_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
 
-    // Version still 1 because we used same WASM for simulation, but logic was re-run
+    let admin = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let contract_id = env.register(v0::WASM, ());
+    let first = v0::Client::new(&env, &contract_id);
+    first.init(&admin);
+    assert_eq!(first.increment(), 1);
+
+    first.upgrade(&env.deployer().upload_contract_wasm(WASM));
+    let client = UpgradableContractClient::new(&env, &contract_id);
     assert_eq!(client.version(), 1);
+    assert_eq!(client.get_count(), 1);
+    assert_eq!(client.get_admin(), admin);
+    assert!(client.try_init(&outsider).is_err());
+    let legacy = env.as_contract(&contract_id, || {
+        env.storage().instance().has(&DataKey::Admin)
+    });
+    assert!(!legacy, "the admin moved to lumenpulse-access");
+
+    // Only the first release's admin may upgrade further
+    let v2_hash = env.deployer().upload_contract_wasm(V2_WASM);
+    assert_auth_matrix(
+        &env,
+        &contract_id,
+        &[
+            (
+                &outsider,
+                "upgrade",
+                (v2_hash.clone(),).into_val(&env),
+                Outcome::Denied,
+            ),
+            (
+                &admin,
+                "upgrade",
+                (v2_hash,).into_val(&env),
+                Outcome::Allowed,
+            ),
+        ],
+    );
+    assert_eq!(client.version(), 2);
 }
 
 #[test]
@@ -48,28 +115,160 @@ fn test_already_initialized() {
 }
 
 #[test]
-#[should_panic]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
 fn test_not_admin_cannot_upgrade() {
     let env = Env::default();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(UpgradableContract, ());
+    let client = UpgradableContractClient::new(&env, &contract_id);
+
+    client.init(&admin);
+
+    // A valid release, but nobody has the admin's authorization
+    let new_wasm_hash = env.deployer().upload_contract_wasm(V2_WASM);
+    client.upgrade(&new_wasm_hash);
+}
+
+#[test]
+fn test_only_admin_passes_auth_matrix() {
+    let env = Env::default();
+
+    let admin = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let contract_id = env.register(UpgradableContract, ());
+    let client = UpgradableContractClient::new(&env, &contract_id);
+    client.init(&admin);
+    let new_wasm_hash = env.deployer().upload_contract_wasm(V2_WASM);
+
+    assert_auth_matrix(
+        &env,
+        &contract_id,
+        &[
+            (
+                &outsider,
+                "upgrade",
+                (new_wasm_hash.clone(),).into_val(&env),
+                Outcome::Denied,
+            ),
+            // Naming the admin does not stand in for the admin's signature
+            (
+                &outsider,
+                "propose_admin",
+                (admin.clone(), outsider.clone()).into_val(&env),
+                Outcome::Denied,
+            ),
+            (
+                &admin,
+                "upgrade",
+                (new_wasm_hash,).into_val(&env),
+                Outcome::Allowed,
+            ),
+        ],
+    );
+    assert_eq!(client.version(), 2);
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_admin_handover() {
+    let env = Env::default();
     env.mock_all_auths();
 
     let admin = Address::generate(&env);
-    let _non_admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
     let contract_id = env.register(UpgradableContract, ());
     let client = UpgradableContractClient::new(&env, &contract_id);
 
     client.init(&admin);
+    client.propose_admin(&admin, &new_admin);
+    assert_eq!(client.get_admin(), admin);
+    client.accept_admin(&new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+    assert!(client.try_propose_admin(&admin, &admin).is_err());
+}
 
-    let new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
+#[derive(Clone, Debug)]
+enum Op {
+    Increment,
+    /// Upgrade to the second release and migrate, the way a release is
+    /// rolled out
+    Release,
+}
 
-    // This should fail because mock_all_auths will require auth for the address called,
-    // and we need to ensure it's the admin. But wait, require_auth() with mock_all_auths
-    // usually succeeds. To properly test auth, we'd need more specific setup or check
-    // demand of auth. For now, since client.upgrade(&new_wasm_hash) is called without
-    // specifying the caller as admin, and it will require_auth of admin, it should fail
-    // if the environment is set up to check it.
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![4 => Just(Op::Increment), 1 => Just(Op::Release)]
+}
 
-    // Actually, in Soroban tests, you can switch the caller using `client.mock_auths`.
-    // Let's simplify and just rely on the implementation.
-    client.upgrade(&new_wasm_hash);
+type Events = soroban_sdk::Vec<(Address, soroban_sdk::Vec<Val>, Val)>;
+
+/// What the contract should look like from outside, whichever release
+/// is installed
+struct Model {
+    count: u32,
+    version: u32,
+    /// Whether the first release has a count the next migration moves
+    unmigrated: bool,
+}
+
+impl Model {
+    /// Apply `op` and return the events the contract emits for it
+    fn apply(&mut self, env: &Env, contract: &Address, op: &Op) -> Events {
+        let event = |event: &dyn Event| (contract.clone(), event.topics(env), event.data(env));
+        match op {
+            Op::Increment => {
+                self.count += 1;
+                self.unmigrated |= self.version == 1;
+                vec![env, event(&IncrementedEvent { count: self.count })]
+            }
+            Op::Release => {
+                self.version = 2;
+                if core::mem::take(&mut self.unmigrated) {
+                    vec![env, event(&MigratedEvent { count: self.count })]
+                } else {
+                    vec![env]
+                }
+            }
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    /// Run the same operations against the first release's WASM, rolling
+    /// the second release out wherever a release falls, and check every
+    /// return value, view and event against the model
+    #[test]
+    fn prop_release_matches_model(ops in prop::collection::vec(op(), 1..20)) {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+        let contract_id = env.register(upgradable::WASM, ());
+        let client = v2::Client::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.init(&admin);
+        let v2_hash = env.deployer().upload_contract_wasm(V2_WASM);
+
+        let mut model = Model {
+            count: 0,
+            version: 1,
+            unmigrated: false,
+        };
+        for op in ops {
+            let expected = model.apply(&env, &contract_id, &op);
+            match op {
+                Op::Increment => prop_assert_eq!(client.increment(), model.count),
+                Op::Release => {
+                    client.upgrade(&v2_hash);
+                    client.migrate();
+                }
+            }
+            prop_assert_eq!(env.events().all(), expected);
+            prop_assert_eq!(client.get_count(), model.count);
+            prop_assert_eq!(client.version(), model.version);
+            prop_assert_eq!(client.get_admin(), admin.clone());
+        }
+    }
 }
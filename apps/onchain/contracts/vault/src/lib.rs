@@ -0,0 +1,432 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod math;
+mod storage;
+mod token;
+
+use errors::VaultError;
+use lumenpulse_pausable::Pausable;
+use math::Rounding;
+use soroban_sdk::{contract, contractclient, contractimpl, Address, Env};
+use storage::DataKey;
+
+/// Yield source the vault can place idle assets into
+#[contractclient(name = "StrategyClient")]
+pub trait Strategy {
+    /// Assets the strategy currently holds for the vault, including yield
+    fn total_assets(env: Env) -> i128;
+
+    /// Put to work `amount` the vault has just transferred to the strategy
+    fn deposit(env: Env, amount: i128);
+
+    /// Send `amount` of assets back to the vault
+    fn withdraw(env: Env, amount: i128);
+}
+
+/// Tokenized vault with ERC-4626 style share accounting. Depositors receive
+/// shares for the asset; anything that raises the vault's holdings without
+/// minting shares, such as streamed rewards or strategy yield, raises the
+/// value of every share.
+#[contract]
+pub struct VaultContract;
+
+#[contractimpl]
+impl VaultContract {
+    /// Initialize the vault for a single underlying asset
+    pub fn initialize(env: Env, admin: Address, asset: Address) -> Result<(), VaultError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(VaultError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Asset, &asset);
+        env.storage().instance().set(&DataKey::TotalShares, &0i128);
+
+        events::InitializedEvent { admin, asset }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Deposit `assets` and receive shares at the current exchange rate,
+    /// rounded in the vault's favour
+    pub fn deposit(env: Env, from: Address, assets: i128) -> Result<i128, VaultError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        from.require_auth();
+        if assets <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let shares = Self::preview_deposit(env.clone(), assets)?;
+        if shares == 0 {
+            return Err(VaultError::ZeroShares);
+        }
+
+        Self::enter(&env, &from, assets, shares)?;
+        Ok(shares)
+    }
+
+    /// Mint exactly `shares`, paying whatever assets they cost
+    pub fn mint(env: Env, from: Address, shares: i128) -> Result<i128, VaultError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        from.require_auth();
+        if shares <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let assets = Self::preview_mint(env.clone(), shares)?;
+        Self::enter(&env, &from, assets, shares)?;
+        Ok(assets)
+    }
+
+    /// Withdraw exactly `assets` to `receiver`, burning the owner's shares
+    pub fn withdraw(
+        env: Env,
+        owner: Address,
+        receiver: Address,
+        assets: i128,
+    ) -> Result<i128, VaultError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        owner.require_auth();
+        if assets <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let shares = Self::preview_withdraw(env.clone(), assets)?;
+        Self::exit(&env, &owner, &receiver, assets, shares)?;
+        Ok(shares)
+    }
+
+    /// Burn `shares` and send the assets they are worth to `receiver`
+    pub fn redeem(
+        env: Env,
+        owner: Address,
+        receiver: Address,
+        shares: i128,
+    ) -> Result<i128, VaultError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        owner.require_auth();
+        if shares <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let assets = Self::preview_redeem(env.clone(), shares)?;
+        Self::exit(&env, &owner, &receiver, assets, shares)?;
+        Ok(assets)
+    }
+
+    /// Move shares between holders
+    pub fn transfer_shares(
+        env: Env,
+        from: Address,
+        to: Address,
+        shares: i128,
+    ) -> Result<(), VaultError> {
+        from.require_auth();
+        if shares <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let balance = Self::balance_of(env.clone(), from.clone());
+        if balance < shares {
+            return Err(VaultError::InsufficientShares);
+        }
+        Self::write_shares(&env, &from, balance - shares);
+        let to_balance = Self::balance_of(env.clone(), to.clone());
+        Self::write_shares(&env, &to, to_balance + shares);
+
+        events::SharesTransferredEvent { from, to, shares }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Plug in a strategy, or remove it with `None` (admin only). Everything
+    /// held by the previous strategy is pulled back into the vault first.
+    pub fn set_strategy(
+        env: Env,
+        admin: Address,
+        strategy: Option<Address>,
+    ) -> Result<(), VaultError> {
+        Self::require_admin(&env, &admin)?;
+
+        if let Some(current) = Self::get_strategy(env.clone()) {
+            let client = StrategyClient::new(&env, &current);
+            let invested = client.total_assets();
+            if invested > 0 {
+                client.withdraw(&invested);
+            }
+        }
+
+        match &strategy {
+            Some(strategy) => env.storage().instance().set(&DataKey::Strategy, strategy),
+            None => env.storage().instance().remove(&DataKey::Strategy),
+        }
+
+        events::StrategyUpdatedEvent { strategy }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Move idle assets into the strategy (admin only)
+    pub fn invest(env: Env, admin: Address, amount: i128) -> Result<(), VaultError> {
+        Self::require_admin(&env, &admin)?;
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let strategy = Self::get_strategy(env.clone()).ok_or(VaultError::NoStrategy)?;
+        if Self::idle_assets(&env)? < amount {
+            return Err(VaultError::InsufficientLiquidity);
+        }
+
+        token::transfer(
+            &env,
+            &Self::get_asset(env.clone())?,
+            &env.current_contract_address(),
+            &strategy,
+            &amount,
+        );
+        StrategyClient::new(&env, &strategy).deposit(&amount);
+
+        events::InvestedEvent { strategy, amount }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Pull assets back from the strategy into the vault (admin only)
+    pub fn divest(env: Env, admin: Address, amount: i128) -> Result<(), VaultError> {
+        Self::require_admin(&env, &admin)?;
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let strategy = Self::get_strategy(env.clone()).ok_or(VaultError::NoStrategy)?;
+        StrategyClient::new(&env, &strategy).withdraw(&amount);
+
+        events::DivestedEvent { strategy, amount }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Shares a deposit of `assets` would mint
+    pub fn preview_deposit(env: Env, assets: i128) -> Result<i128, VaultError> {
+        let (total_shares, total_assets) = Self::totals(&env)?;
+        Ok(math::to_shares(
+            assets,
+            total_shares,
+            total_assets,
+            Rounding::Down,
+        ))
+    }
+
+    /// Assets needed to mint `shares`
+    pub fn preview_mint(env: Env, shares: i128) -> Result<i128, VaultError> {
+        let (total_shares, total_assets) = Self::totals(&env)?;
+        Ok(math::to_assets(
+            shares,
+            total_shares,
+            total_assets,
+            Rounding::Up,
+        ))
+    }
+
+    /// Shares burned to withdraw `assets`
+    pub fn preview_withdraw(env: Env, assets: i128) -> Result<i128, VaultError> {
+        let (total_shares, total_assets) = Self::totals(&env)?;
+        Ok(math::to_shares(
+            assets,
+            total_shares,
+            total_assets,
+            Rounding::Up,
+        ))
+    }
+
+    /// Assets paid out for redeeming `shares`
+    pub fn preview_redeem(env: Env, shares: i128) -> Result<i128, VaultError> {
+        let (total_shares, total_assets) = Self::totals(&env)?;
+        Ok(math::to_assets(
+            shares,
+            total_shares,
+            total_assets,
+            Rounding::Down,
+        ))
+    }
+
+    /// Value of `shares` in assets at the current exchange rate
+    pub fn convert_to_assets(env: Env, shares: i128) -> Result<i128, VaultError> {
+        Self::preview_redeem(env, shares)
+    }
+
+    /// Shares `assets` are worth at the current exchange rate
+    pub fn convert_to_shares(env: Env, assets: i128) -> Result<i128, VaultError> {
+        Self::preview_deposit(env, assets)
+    }
+
+    /// Assets under management, idle plus invested
+    pub fn total_assets(env: Env) -> Result<i128, VaultError> {
+        let idle = Self::idle_assets(&env)?;
+        let invested = match Self::get_strategy(env.clone()) {
+            Some(strategy) => StrategyClient::new(&env, &strategy).total_assets(),
+            None => 0,
+        };
+        Ok(idle + invested)
+    }
+
+    /// Get total shares outstanding
+    pub fn total_shares(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalShares)
+            .unwrap_or(0)
+    }
+
+    /// Get shares held by `owner`
+    pub fn balance_of(env: Env, owner: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Shares(owner))
+            .unwrap_or(0)
+    }
+
+    /// Get the underlying asset
+    pub fn get_asset(env: Env) -> Result<Address, VaultError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Asset)
+            .ok_or(VaultError::NotInitialized)
+    }
+
+    /// Get the current strategy, if any
+    pub fn get_strategy(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Strategy)
+    }
+
+    /// Get admin address
+    pub fn get_admin(env: Env) -> Result<Address, VaultError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VaultError::NotInitialized)
+    }
+}
+
+impl VaultContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), VaultError> {
+        let stored_admin = Self::get_admin(env.clone())?;
+        if *admin != stored_admin {
+            return Err(VaultError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn totals(env: &Env) -> Result<(i128, i128), VaultError> {
+        Ok((
+            Self::total_shares(env.clone()),
+            Self::total_assets(env.clone())?,
+        ))
+    }
+
+    fn idle_assets(env: &Env) -> Result<i128, VaultError> {
+        let asset = Self::get_asset(env.clone())?;
+        Ok(token::balance(env, &asset, &env.current_contract_address()))
+    }
+
+    fn write_shares(env: &Env, owner: &Address, shares: i128) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Shares(owner.clone()), &shares);
+    }
+
+    /// Take `assets` from `from` and credit `shares`
+    fn enter(env: &Env, from: &Address, assets: i128, shares: i128) -> Result<(), VaultError> {
+        token::transfer(
+            env,
+            &Self::get_asset(env.clone())?,
+            from,
+            &env.current_contract_address(),
+            &assets,
+        );
+
+        let balance = Self::balance_of(env.clone(), from.clone());
+        Self::write_shares(env, from, balance + shares);
+        let total_shares = Self::total_shares(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalShares, &(total_shares + shares));
+
+        events::DepositEvent {
+            owner: from.clone(),
+            assets,
+            shares,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Burn the owner's `shares` and pay `assets` to `receiver`, recalling
+    /// funds from the strategy if the vault is short on idle assets
+    fn exit(
+        env: &Env,
+        owner: &Address,
+        receiver: &Address,
+        assets: i128,
+        shares: i128,
+    ) -> Result<(), VaultError> {
+        let balance = Self::balance_of(env.clone(), owner.clone());
+        if balance < shares {
+            return Err(VaultError::InsufficientShares);
+        }
+
+        let idle = Self::idle_assets(env)?;
+        if idle < assets {
+            let strategy =
+                Self::get_strategy(env.clone()).ok_or(VaultError::InsufficientLiquidity)?;
+            StrategyClient::new(env, &strategy).withdraw(&(assets - idle));
+            if Self::idle_assets(env)? < assets {
+                return Err(VaultError::InsufficientLiquidity);
+            }
+        }
+
+        Self::write_shares(env, owner, balance - shares);
+        let total_shares = Self::total_shares(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalShares, &(total_shares - shares));
+
+        token::transfer(
+            env,
+            &Self::get_asset(env.clone())?,
+            &env.current_contract_address(),
+            receiver,
+            &assets,
+        );
+
+        events::WithdrawEvent {
+            owner: owner.clone(),
+            receiver: receiver.clone(),
+            assets,
+            shares,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+}
+
+impl Pausable for VaultContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(VaultContract);
+
+#[cfg(test)]
+mod test;
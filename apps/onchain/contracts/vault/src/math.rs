@@ -0,0 +1,31 @@
+/// Share/asset conversion math
+///
+/// A virtual share and asset are added to each side of the exchange rate so
+/// that the first depositor cannot inflate the share price by donating
+/// assets to an empty vault.
+use lumenpulse_fixed::mul_div;
+pub use lumenpulse_fixed::Rounding;
+
+const VIRTUAL_OFFSET: i128 = 1;
+
+/// Shares worth `assets` at the current exchange rate
+pub fn to_shares(assets: i128, total_shares: i128, total_assets: i128, rounding: Rounding) -> i128 {
+    mul_div(
+        assets,
+        total_shares + VIRTUAL_OFFSET,
+        total_assets + VIRTUAL_OFFSET,
+        rounding,
+    )
+    .expect("arithmetic overflow")
+}
+
+/// Assets backing `shares` at the current exchange rate
+pub fn to_assets(shares: i128, total_shares: i128, total_assets: i128, rounding: Rounding) -> i128 {
+    mul_div(
+        shares,
+        total_assets + VIRTUAL_OFFSET,
+        total_shares + VIRTUAL_OFFSET,
+        rounding,
+    )
+    .expect("arithmetic overflow")
+}
@@ -0,0 +1,11 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,           // -> Address
+    Asset,           // -> Address
+    TotalShares,     // -> i128
+    Shares(Address), // owner -> i128
+    Strategy,        // -> Address
+}
@@ -0,0 +1,267 @@
+use crate::errors::VaultError;
+use crate::{VaultContract, VaultContractClient};
+use lumenpulse_pausable::PauseError;
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, InvokeError,
+};
+
+#[contracttype]
+enum StrategyKey {
+    Vault,
+    Asset,
+}
+
+/// Strategy that simply holds what it is given; tests mint to it to
+/// simulate yield
+#[contract]
+struct MockStrategy;
+
+#[contractimpl]
+impl MockStrategy {
+    pub fn init(env: Env, vault: Address, asset: Address) {
+        env.storage().instance().set(&StrategyKey::Vault, &vault);
+        env.storage().instance().set(&StrategyKey::Asset, &asset);
+    }
+
+    pub fn total_assets(env: Env) -> i128 {
+        let asset: Address = env.storage().instance().get(&StrategyKey::Asset).unwrap();
+        TokenClient::new(&env, &asset).balance(&env.current_contract_address())
+    }
+
+    pub fn deposit(_env: Env, _amount: i128) {}
+
+    pub fn withdraw(env: Env, amount: i128) {
+        let vault: Address = env.storage().instance().get(&StrategyKey::Vault).unwrap();
+        let asset: Address = env.storage().instance().get(&StrategyKey::Asset).unwrap();
+        TokenClient::new(&env, &asset).transfer(&env.current_contract_address(), &vault, &amount);
+    }
+}
+
+struct Setup<'a> {
+    client: VaultContractClient<'a>,
+    admin: Address,
+    alice: Address,
+    bob: Address,
+    token: TokenClient<'a>,
+    token_admin: StellarAssetClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    let admin = Address::generate(env);
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(env, &sac.address());
+    let token_admin = StellarAssetClient::new(env, &sac.address());
+    token_admin.mint(&alice, &1_000_000);
+    token_admin.mint(&bob, &1_000_000);
+
+    let contract_id = env.register(VaultContract, ());
+    let client = VaultContractClient::new(env, &contract_id);
+    client.initialize(&admin, &token.address);
+
+    Setup {
+        client,
+        admin,
+        alice,
+        bob,
+        token,
+        token_admin,
+    }
+}
+
+fn setup_strategy(env: &Env, s: &Setup) -> Address {
+    let strategy = env.register(MockStrategy, ());
+    MockStrategyClient::new(env, &strategy).init(&s.client.address, &s.token.address);
+    s.client.set_strategy(&s.admin, &Some(strategy.clone()));
+    strategy
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+
+    assert_eq!(s.client.get_admin(), s.admin);
+    assert_eq!(s.client.get_asset(), s.token.address);
+    let result = s.client.try_initialize(&s.admin, &s.token.address);
+    assert_eq!(result, Err(Ok(VaultError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_deposit_and_redeem() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+
+    let shares = s.client.deposit(&s.alice, &1_000);
+    assert_eq!(shares, 1_000);
+    assert_eq!(s.client.balance_of(&s.alice), 1_000);
+    assert_eq!(s.client.total_assets(), 1_000);
+
+    let assets = s.client.redeem(&s.alice, &s.alice, &400);
+    assert_eq!(assets, 400);
+    assert_eq!(s.client.balance_of(&s.alice), 600);
+    assert_eq!(s.token.balance(&s.alice), 1_000_000 - 600);
+}
+
+#[test]
+fn test_rewards_raise_share_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    s.client.deposit(&s.alice, &1_000);
+
+    // Rewards streamed straight to the vault compound into every share
+    s.token_admin.mint(&s.client.address, &1_000);
+    assert_eq!(s.client.convert_to_assets(&1_000), 1_999);
+
+    // Later depositors get fewer shares for the same assets
+    let shares = s.client.deposit(&s.bob, &1_000);
+    assert_eq!(shares, 500);
+
+    let assets = s.client.redeem(&s.alice, &s.alice, &1_000);
+    assert!(assets > 1_990 && assets <= 2_000);
+}
+
+#[test]
+fn test_mint_and_withdraw_round_against_caller() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    s.client.deposit(&s.alice, &1_000);
+    s.token_admin.mint(&s.client.address, &500);
+
+    // Minting rounds the asset cost up, withdrawing rounds burned shares up
+    assert_eq!(s.client.preview_mint(&3), 5);
+    assert_eq!(s.client.preview_deposit(&5), 3);
+    assert_eq!(s.client.preview_withdraw(&5), 4);
+    assert_eq!(s.client.preview_redeem(&4), 5);
+
+    let assets = s.client.mint(&s.bob, &3);
+    assert_eq!(assets, 5);
+    assert_eq!(s.client.balance_of(&s.bob), 3);
+
+    let burned = s.client.withdraw(&s.alice, &s.bob, &150);
+    assert_eq!(burned, 100);
+    assert_eq!(s.client.balance_of(&s.alice), 900);
+}
+
+#[test]
+fn test_first_deposit_inflation_is_unprofitable() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+
+    // Attacker deposits 1 unit and donates a large amount to skew the rate
+    s.client.deposit(&s.alice, &1);
+    s.token_admin.mint(&s.client.address, &100_000);
+
+    let result = s.client.try_deposit(&s.bob, &1_000);
+    assert_eq!(result, Err(Ok(VaultError::ZeroShares)));
+
+    // The donation is split with the virtual share, so the attacker cannot
+    // recover all of it
+    let assets = s.client.redeem(&s.alice, &s.alice, &1);
+    assert!(assets < 100_001);
+}
+
+#[test]
+fn test_withdraw_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    s.client.deposit(&s.alice, &1_000);
+
+    let result = s.client.try_redeem(&s.bob, &s.bob, &1);
+    assert_eq!(result, Err(Ok(VaultError::InsufficientShares)));
+    let result = s.client.try_deposit(&s.alice, &0);
+    assert_eq!(result, Err(Ok(VaultError::InvalidAmount)));
+
+    s.client.transfer_shares(&s.alice, &s.bob, &250);
+    assert_eq!(s.client.balance_of(&s.bob), 250);
+    let result = s.client.try_transfer_shares(&s.bob, &s.alice, &251);
+    assert_eq!(result, Err(Ok(VaultError::InsufficientShares)));
+}
+
+#[test]
+fn test_strategy_yield_and_recall() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    let strategy = setup_strategy(&env, &s);
+    s.client.deposit(&s.alice, &1_000);
+
+    s.client.invest(&s.admin, &800);
+    assert_eq!(s.token.balance(&strategy), 800);
+    assert_eq!(s.client.total_assets(), 1_000);
+
+    // Strategy earns yield
+    s.token_admin.mint(&strategy, &200);
+    assert_eq!(s.client.total_assets(), 1_200);
+
+    // Redeeming more than is idle pulls the shortfall from the strategy
+    let assets = s.client.redeem(&s.alice, &s.alice, &1_000);
+    assert_eq!(assets, 1_199);
+    assert_eq!(s.client.total_shares(), 0);
+}
+
+#[test]
+fn test_strategy_admin_controls() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    s.client.deposit(&s.alice, &1_000);
+
+    let result = s.client.try_invest(&s.admin, &100);
+    assert_eq!(result, Err(Ok(VaultError::NoStrategy)));
+
+    let strategy = setup_strategy(&env, &s);
+    let result = s.client.try_invest(&s.bob, &100);
+    assert_eq!(result, Err(Ok(VaultError::Unauthorized)));
+    let result = s.client.try_invest(&s.admin, &1_001);
+    assert_eq!(result, Err(Ok(VaultError::InsufficientLiquidity)));
+
+    s.client.invest(&s.admin, &600);
+    s.client.divest(&s.admin, &100);
+    assert_eq!(s.token.balance(&strategy), 500);
+
+    // Removing the strategy brings everything home
+    s.client.set_strategy(&s.admin, &None);
+    assert_eq!(s.token.balance(&strategy), 0);
+    assert_eq!(s.token.balance(&s.client.address), 1_000);
+    assert_eq!(s.client.get_strategy(), None);
+}
+
+#[test]
+fn test_pause_stops_deposits_and_withdrawals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let s = setup_test(&env);
+    s.client.deposit(&s.alice, &1_000);
+    s.client.grant_pauser(&s.admin, &s.bob);
+    s.client.pause(&s.bob);
+
+    let paused = Err(Err(InvokeError::Contract(PauseError::Paused as u32)));
+    assert_eq!(s.client.try_deposit(&s.alice, &100), paused);
+    assert_eq!(s.client.try_redeem(&s.alice, &s.alice, &100), paused);
+    let result = s.client.try_unpause(&s.bob);
+    assert_eq!(result, Err(Ok(PauseError::Unauthorized)));
+
+    s.client.unpause(&s.admin);
+    assert_eq!(s.client.redeem(&s.alice, &s.alice, &100), 100);
+}
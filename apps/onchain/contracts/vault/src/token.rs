@@ -0,0 +1,13 @@
+use soroban_sdk::{Address, Env};
+
+/// Transfer tokens from one address to another. Works the same for native
+/// XLM and classic-asset SACs, and fails with `TransferError::TransferFailed`
+/// unless exactly `amount` moves.
+pub fn transfer(env: &Env, token: &Address, from: &Address, to: &Address, amount: &i128) {
+    lumenpulse_sac::transfer(env, token, from, to, *amount);
+}
+
+/// Get the balance of an address for a given token
+pub fn balance(env: &Env, token: &Address, address: &Address) -> i128 {
+    lumenpulse_sac::balance(env, token, address)
+}
@@ -5,6 +5,7 @@ mod storage;
 mod token;
 
 use errors::VestingError;
+use lumenpulse_pausable::Pausable;
 use soroban_sdk::{contract, contractimpl, Address, Env};
 use storage::{DataKey, VestingData};
 use token::transfer;
@@ -115,6 +116,7 @@ impl VestingWalletContract {
 
     /// Claim available tokens based on linear vesting schedule
     pub fn claim(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
+        lumenpulse_pausable::when_not_paused(&env);
         // Check if contract is initialized
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(VestingError::NotInitialized);
@@ -239,5 +241,13 @@ impl VestingWalletContract {
     }
 }
 
+impl Pausable for VestingWalletContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(VestingWalletContract);
+
 #[cfg(test)]
 mod test;
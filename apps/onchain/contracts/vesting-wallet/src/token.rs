@@ -1,14 +1,14 @@
 use soroban_sdk::{Address, Env};
 
-/// Transfer tokens from one address to another
+/// Transfer tokens from one address to another. Works the same for native
+/// XLM and classic-asset SACs, and fails with `TransferError::TransferFailed`
+/// unless exactly `amount` moves.
 pub fn transfer(env: &Env, token: &Address, from: &Address, to: &Address, amount: &i128) {
-    let token_client = soroban_sdk::token::Client::new(env, token);
-    token_client.transfer(from, to, amount);
+    lumenpulse_sac::transfer(env, token, from, to, *amount);
 }
 
 /// Get the balance of an address for a given token
 #[allow(dead_code)]
 pub fn balance(env: &Env, token: &Address, address: &Address) -> i128 {
-    let token_client = soroban_sdk::token::Client::new(env, token);
-    token_client.balance(address)
+    lumenpulse_sac::balance(env, token, address)
 }
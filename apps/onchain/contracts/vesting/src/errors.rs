@@ -0,0 +1,24 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VestingError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    ScheduleNotFound = 4,
+    InvalidAmount = 5,
+    InvalidDuration = 6,
+    InvalidStartTime = 7,
+    InvalidCliff = 8,
+    NothingToClaim = 9,
+    NotRevocable = 10,
+    AlreadyRevoked = 11,
+    EmptyBatch = 12,
+    BatchTooLarge = 13,
+    SameBeneficiary = 14,
+    InvalidUnlockCondition = 15,
+    MilestoneNotReached = 16,
+    AlreadyUnlocked = 17,
+}
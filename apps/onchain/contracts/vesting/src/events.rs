@@ -0,0 +1,72 @@
+use soroban_sdk::{contractevent, Address};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitializedEvent {
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleCreatedEvent {
+    #[topic]
+    pub beneficiary: Address,
+    #[topic]
+    pub token: Address,
+    pub schedule_id: u64,
+    pub total_amount: i128,
+    pub start_time: u64,
+    pub cliff: u64,
+    pub duration: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimedEvent {
+    #[topic]
+    pub beneficiary: Address,
+    #[topic]
+    pub schedule_id: u64,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryUpdatedEvent {
+    #[topic]
+    pub admin: Address,
+    pub treasury: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleRevokedEvent {
+    #[topic]
+    pub beneficiary: Address,
+    #[topic]
+    pub schedule_id: u64,
+    pub treasury: Address,
+    pub unvested_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeneficiaryReassignedEvent {
+    #[topic]
+    pub old_beneficiary: Address,
+    #[topic]
+    pub new_beneficiary: Address,
+    pub schedule_id: u64,
+    /// True when the admin reassigned the schedule without the beneficiary
+    pub disputed: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneUnlockedEvent {
+    #[topic]
+    pub beneficiary: Address,
+    #[topic]
+    pub schedule_id: u64,
+    pub unlocked_at: u64,
+}
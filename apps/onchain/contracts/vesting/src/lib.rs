@@ -0,0 +1,716 @@
+#![no_std]
+
+mod errors;
+mod events;
+mod storage;
+mod token;
+
+use errors::VestingError;
+use lumenpulse_pausable::Pausable;
+use soroban_sdk::{contract, contractclient, contractimpl, Address, Env, Map, Vec};
+use storage::{
+    DataKey, Schedule, ScheduleParams, TokenPosition, UnlockCondition, VestingPortfolio,
+};
+
+/// Upper bound on the number of schedules created by one `create_schedules`
+/// call, keeping each batch within a single transaction's write budget
+const MAX_BATCH_SIZE: u32 = 25;
+
+/// Interface oracle contracts implement to gate milestone schedules
+#[contractclient(name = "MilestoneOracleClient")]
+pub trait MilestoneOracle {
+    fn is_condition_met(env: Env, condition_id: u64) -> bool;
+}
+
+#[contract]
+pub struct VestingContract;
+
+#[contractimpl]
+impl VestingContract {
+    /// Initialize the contract with an admin address
+    pub fn initialize(env: Env, admin: Address) -> Result<(), VestingError> {
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(VestingError::AlreadyInitialized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+
+        // Store admin address and initialize schedule ID counter
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextScheduleId, &0u64);
+
+        events::InitializedEvent { admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Create a vesting schedule for a beneficiary, funded by the admin.
+    /// Schedules cannot be cancelled unless created with `revocable` set.
+    pub fn create_schedule(
+        env: Env,
+        admin: Address,
+        params: ScheduleParams,
+    ) -> Result<u64, VestingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_admin(&env, &admin)?;
+        Self::validate_params(&env, &params)?;
+
+        // Transfer tokens from admin to contract
+        token::transfer(
+            &env,
+            &params.token,
+            &admin,
+            &env.current_contract_address(),
+            &params.total_amount,
+        );
+
+        Ok(Self::store_schedule(&env, params, UnlockCondition::Time))
+    }
+
+    /// Create a schedule that only starts releasing once an external
+    /// milestone is reached, either attested by the admin or reported by an
+    /// oracle contract. The time-based release still applies on top, so a
+    /// schedule whose window has fully elapsed unlocks entirely at once.
+    pub fn create_milestone_schedule(
+        env: Env,
+        admin: Address,
+        params: ScheduleParams,
+        unlock: UnlockCondition,
+    ) -> Result<u64, VestingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_admin(&env, &admin)?;
+        Self::validate_params(&env, &params)?;
+
+        if unlock == UnlockCondition::Time {
+            return Err(VestingError::InvalidUnlockCondition);
+        }
+
+        token::transfer(
+            &env,
+            &params.token,
+            &admin,
+            &env.current_contract_address(),
+            &params.total_amount,
+        );
+
+        Ok(Self::store_schedule(&env, params, unlock))
+    }
+
+    /// Attest that the milestone of an attestation-gated schedule has been
+    /// reached (admin only)
+    pub fn attest_milestone(
+        env: Env,
+        admin: Address,
+        schedule_id: u64,
+    ) -> Result<(), VestingError> {
+        Self::require_admin(&env, &admin)?;
+
+        let schedule: Schedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .ok_or(VestingError::ScheduleNotFound)?;
+
+        if schedule.unlock != UnlockCondition::Attestation {
+            return Err(VestingError::InvalidUnlockCondition);
+        }
+
+        Self::mark_unlocked(&env, schedule)
+    }
+
+    /// Unlock an oracle-gated schedule once its oracle reports the condition
+    /// as met. Anyone may call this, as the oracle alone decides whether
+    /// the schedule unlocks.
+    pub fn unlock_milestone(env: Env, schedule_id: u64) -> Result<(), VestingError> {
+        let schedule: Schedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .ok_or(VestingError::ScheduleNotFound)?;
+
+        let condition = match &schedule.unlock {
+            UnlockCondition::Oracle(condition) => condition.clone(),
+            _ => return Err(VestingError::InvalidUnlockCondition),
+        };
+
+        let oracle = MilestoneOracleClient::new(&env, &condition.oracle);
+        if !oracle.is_condition_met(&condition.condition_id) {
+            return Err(VestingError::MilestoneNotReached);
+        }
+
+        Self::mark_unlocked(&env, schedule)
+    }
+
+    /// Create several schedules at once. All parameters are validated before
+    /// any funds move, and each token is pulled from the admin in a single
+    /// aggregate transfer. Returns the new schedule ids in input order.
+    pub fn create_schedules(
+        env: Env,
+        admin: Address,
+        schedules: Vec<ScheduleParams>,
+    ) -> Result<Vec<u64>, VestingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        Self::require_admin(&env, &admin)?;
+
+        if schedules.is_empty() {
+            return Err(VestingError::EmptyBatch);
+        }
+        if schedules.len() > MAX_BATCH_SIZE {
+            return Err(VestingError::BatchTooLarge);
+        }
+
+        // Validate everything and total the funding required per token
+        let mut totals: Map<Address, i128> = Map::new(&env);
+        for params in schedules.iter() {
+            Self::validate_params(&env, &params)?;
+            let total = totals.get(params.token.clone()).unwrap_or(0);
+            totals.set(params.token.clone(), total + params.total_amount);
+        }
+
+        let contract_address = env.current_contract_address();
+        for (token, amount) in totals.iter() {
+            token::transfer(&env, &token, &admin, &contract_address, &amount);
+        }
+
+        let mut schedule_ids = Vec::new(&env);
+        for params in schedules.iter() {
+            schedule_ids.push_back(Self::store_schedule(&env, params, UnlockCondition::Time));
+        }
+
+        Ok(schedule_ids)
+    }
+
+    /// Claim everything vested so far on a schedule (beneficiary only)
+    pub fn claim(env: Env, schedule_id: u64) -> Result<i128, VestingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        // Check if contract is initialized
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(VestingError::NotInitialized);
+        }
+
+        let mut schedule: Schedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .ok_or(VestingError::ScheduleNotFound)?;
+
+        // Require beneficiary authorization
+        schedule.beneficiary.require_auth();
+
+        let available = Self::vested(&schedule, env.ledger().timestamp()) - schedule.claimed_amount;
+        if available <= 0 {
+            return Err(VestingError::NothingToClaim);
+        }
+
+        // Update claimed amount before moving funds
+        schedule.claimed_amount += available;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schedule(schedule_id), &schedule);
+
+        token::transfer(
+            &env,
+            &schedule.token,
+            &env.current_contract_address(),
+            &schedule.beneficiary,
+            &available,
+        );
+
+        events::ClaimedEvent {
+            beneficiary: schedule.beneficiary,
+            schedule_id,
+            amount: available,
+        }
+        .publish(&env);
+
+        Ok(available)
+    }
+
+    /// Claim everything vested across all of the beneficiary's schedules.
+    /// Each token is paid out in a single transfer; returns the amount
+    /// claimed per token.
+    pub fn claim_all(env: Env, beneficiary: Address) -> Result<Map<Address, i128>, VestingError> {
+        lumenpulse_pausable::when_not_paused(&env);
+        // Check if contract is initialized
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(VestingError::NotInitialized);
+        }
+
+        // Require beneficiary authorization
+        beneficiary.require_auth();
+
+        let now = env.ledger().timestamp();
+        let mut claimed: Map<Address, i128> = Map::new(&env);
+        for schedule_id in Self::schedule_ids(&env, &beneficiary).iter() {
+            let mut schedule: Schedule = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::Schedule(schedule_id))
+            {
+                Some(schedule) => schedule,
+                None => continue,
+            };
+
+            let available = Self::vested(&schedule, now) - schedule.claimed_amount;
+            if available <= 0 {
+                continue;
+            }
+
+            schedule.claimed_amount += available;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Schedule(schedule_id), &schedule);
+
+            let total = claimed.get(schedule.token.clone()).unwrap_or(0);
+            claimed.set(schedule.token, total + available);
+
+            events::ClaimedEvent {
+                beneficiary: beneficiary.clone(),
+                schedule_id,
+                amount: available,
+            }
+            .publish(&env);
+        }
+
+        if claimed.is_empty() {
+            return Err(VestingError::NothingToClaim);
+        }
+
+        let contract_address = env.current_contract_address();
+        for (token, amount) in claimed.iter() {
+            token::transfer(&env, &token, &contract_address, &beneficiary, &amount);
+        }
+
+        Ok(claimed)
+    }
+
+    /// Set the address that receives unvested tokens from revoked schedules
+    /// (admin only). Defaults to the admin when unset.
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) -> Result<(), VestingError> {
+        Self::require_admin(&env, &admin)?;
+
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+
+        events::TreasuryUpdatedEvent { admin, treasury }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Revoke a revocable schedule (admin only). Tokens vested so far stay
+    /// claimable by the beneficiary; the unvested remainder is sent to the
+    /// treasury. Returns the amount returned to the treasury.
+    pub fn revoke(env: Env, admin: Address, schedule_id: u64) -> Result<i128, VestingError> {
+        Self::require_admin(&env, &admin)?;
+
+        let mut schedule: Schedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .ok_or(VestingError::ScheduleNotFound)?;
+
+        if !schedule.revocable {
+            return Err(VestingError::NotRevocable);
+        }
+        if schedule.revoked_at.is_some() {
+            return Err(VestingError::AlreadyRevoked);
+        }
+
+        let now = env.ledger().timestamp();
+        let unvested = schedule.total_amount - Self::vested(&schedule, now);
+
+        // Freeze vesting at the revocation time
+        schedule.revoked_at = Some(now);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schedule(schedule_id), &schedule);
+
+        let treasury = Self::get_treasury(env.clone())?;
+        if unvested > 0 {
+            token::transfer(
+                &env,
+                &schedule.token,
+                &env.current_contract_address(),
+                &treasury,
+                &unvested,
+            );
+        }
+
+        events::ScheduleRevokedEvent {
+            beneficiary: schedule.beneficiary,
+            schedule_id,
+            treasury,
+            unvested_amount: unvested,
+        }
+        .publish(&env);
+
+        Ok(unvested)
+    }
+
+    /// Move a schedule to a new beneficiary address (current beneficiary
+    /// only), e.g. when rotating keys. Claimed and unclaimed amounts carry
+    /// over unchanged.
+    pub fn transfer_beneficiary(
+        env: Env,
+        schedule_id: u64,
+        new_beneficiary: Address,
+    ) -> Result<(), VestingError> {
+        // Check if contract is initialized
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(VestingError::NotInitialized);
+        }
+
+        let schedule: Schedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .ok_or(VestingError::ScheduleNotFound)?;
+
+        // Require current beneficiary authorization
+        schedule.beneficiary.require_auth();
+
+        Self::reassign(&env, schedule, new_beneficiary, false)
+    }
+
+    /// Forcibly reassign a schedule to a new beneficiary (admin only), for
+    /// cases such as a compromised or lost beneficiary key. The reassignment
+    /// is flagged as disputed in the emitted event.
+    pub fn reassign_beneficiary(
+        env: Env,
+        admin: Address,
+        schedule_id: u64,
+        new_beneficiary: Address,
+    ) -> Result<(), VestingError> {
+        Self::require_admin(&env, &admin)?;
+
+        let schedule: Schedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .ok_or(VestingError::ScheduleNotFound)?;
+
+        Self::reassign(&env, schedule, new_beneficiary, true)
+    }
+
+    /// Get the amount claimable right now across all of a beneficiary's
+    /// schedules, per token
+    pub fn claimable(env: Env, beneficiary: Address) -> Map<Address, i128> {
+        let now = env.ledger().timestamp();
+        let mut claimable: Map<Address, i128> = Map::new(&env);
+        for schedule_id in Self::schedule_ids(&env, &beneficiary).iter() {
+            if let Some(schedule) = env
+                .storage()
+                .persistent()
+                .get::<_, Schedule>(&DataKey::Schedule(schedule_id))
+            {
+                let available = Self::vested(&schedule, now) - schedule.claimed_amount;
+                if available > 0 {
+                    let total = claimable.get(schedule.token.clone()).unwrap_or(0);
+                    claimable.set(schedule.token, total + available);
+                }
+            }
+        }
+        claimable
+    }
+
+    /// Summarize granted, vested and claimed amounts per token and the next
+    /// unlock time across all of a beneficiary's schedules
+    pub fn portfolio(env: Env, beneficiary: Address) -> VestingPortfolio {
+        let now = env.ledger().timestamp();
+        let schedule_ids = Self::schedule_ids(&env, &beneficiary);
+
+        let mut positions: Map<Address, TokenPosition> = Map::new(&env);
+        let mut next_unlock_time: Option<u64> = None;
+        for schedule_id in schedule_ids.iter() {
+            let schedule: Schedule = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::Schedule(schedule_id))
+            {
+                Some(schedule) => schedule,
+                None => continue,
+            };
+
+            let vested = Self::vested(&schedule, now);
+            // A revoked schedule is only ever worth what vested before revocation
+            let granted = match schedule.revoked_at {
+                Some(revoked_at) => Self::vested(&schedule, revoked_at),
+                None => schedule.total_amount,
+            };
+
+            let mut position = positions
+                .get(schedule.token.clone())
+                .unwrap_or(TokenPosition {
+                    token: schedule.token.clone(),
+                    granted: 0,
+                    vested: 0,
+                    claimed: 0,
+                });
+            position.granted += granted;
+            position.vested += vested;
+            position.claimed += schedule.claimed_amount;
+            positions.set(schedule.token.clone(), position);
+
+            if let Some(unlock_time) = Self::next_unlock(&schedule, vested, granted, now) {
+                next_unlock_time = Some(match next_unlock_time {
+                    Some(current) if current <= unlock_time => current,
+                    _ => unlock_time,
+                });
+            }
+        }
+
+        VestingPortfolio {
+            schedule_count: schedule_ids.len(),
+            positions: positions.values(),
+            next_unlock_time,
+        }
+    }
+
+    /// Get the ids of all schedules held by a beneficiary
+    pub fn get_schedule_ids(env: Env, beneficiary: Address) -> Vec<u64> {
+        Self::schedule_ids(&env, &beneficiary)
+    }
+
+    /// Get the total amount vested on a schedule at timestamp `at`
+    pub fn vested_amount(env: Env, schedule_id: u64, at: u64) -> Result<i128, VestingError> {
+        let schedule: Schedule = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .ok_or(VestingError::ScheduleNotFound)?;
+
+        Ok(Self::vested(&schedule, at))
+    }
+
+    /// Get schedule data
+    pub fn get_schedule(env: Env, schedule_id: u64) -> Result<Schedule, VestingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .ok_or(VestingError::ScheduleNotFound)
+    }
+
+    /// Get the treasury that receives revoked funds
+    pub fn get_treasury(env: Env) -> Result<Address, VestingError> {
+        if let Some(treasury) = env.storage().instance().get(&DataKey::Treasury) {
+            return Ok(treasury);
+        }
+        Self::get_admin(env)
+    }
+
+    /// Get admin address
+    pub fn get_admin(env: Env) -> Result<Address, VestingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VestingError::NotInitialized)
+    }
+}
+
+impl VestingContract {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), VestingError> {
+        // Check if contract is initialized
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VestingError::NotInitialized)?;
+
+        // Verify admin identity
+        if *admin != stored_admin {
+            return Err(VestingError::Unauthorized);
+        }
+
+        // Require admin authorization
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn validate_params(env: &Env, params: &ScheduleParams) -> Result<(), VestingError> {
+        if params.total_amount <= 0 {
+            return Err(VestingError::InvalidAmount);
+        }
+        if params.duration == 0 {
+            return Err(VestingError::InvalidDuration);
+        }
+        if params.cliff > params.duration {
+            return Err(VestingError::InvalidCliff);
+        }
+        if params.start_time < env.ledger().timestamp() {
+            return Err(VestingError::InvalidStartTime);
+        }
+        Ok(())
+    }
+
+    /// Persist a funded schedule under the next available id
+    fn store_schedule(env: &Env, params: ScheduleParams, unlock: UnlockCondition) -> u64 {
+        let schedule_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextScheduleId)
+            .unwrap_or(0);
+
+        let schedule = Schedule {
+            id: schedule_id,
+            beneficiary: params.beneficiary.clone(),
+            token: params.token.clone(),
+            total_amount: params.total_amount,
+            start_time: params.start_time,
+            cliff: params.cliff,
+            duration: params.duration,
+            claimed_amount: 0,
+            revocable: params.revocable,
+            revoked_at: None,
+            unlock,
+            unlocked_at: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schedule(schedule_id), &schedule);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextScheduleId, &(schedule_id + 1));
+        Self::add_schedule_id(env, &params.beneficiary, schedule_id);
+
+        events::ScheduleCreatedEvent {
+            beneficiary: params.beneficiary,
+            token: params.token,
+            schedule_id,
+            total_amount: params.total_amount,
+            start_time: params.start_time,
+            cliff: params.cliff,
+            duration: params.duration,
+        }
+        .publish(env);
+
+        schedule_id
+    }
+
+    fn reassign(
+        env: &Env,
+        mut schedule: Schedule,
+        new_beneficiary: Address,
+        disputed: bool,
+    ) -> Result<(), VestingError> {
+        if schedule.beneficiary == new_beneficiary {
+            return Err(VestingError::SameBeneficiary);
+        }
+
+        let old_beneficiary = schedule.beneficiary.clone();
+        schedule.beneficiary = new_beneficiary.clone();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schedule(schedule.id), &schedule);
+
+        // Move the schedule between the beneficiaries' indexes
+        let mut old_ids = Self::schedule_ids(env, &old_beneficiary);
+        if let Some(index) = old_ids.first_index_of(schedule.id) {
+            old_ids.remove(index);
+        }
+        env.storage().persistent().set(
+            &DataKey::BeneficiarySchedules(old_beneficiary.clone()),
+            &old_ids,
+        );
+        Self::add_schedule_id(env, &new_beneficiary, schedule.id);
+
+        events::BeneficiaryReassignedEvent {
+            old_beneficiary,
+            new_beneficiary,
+            schedule_id: schedule.id,
+            disputed,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    fn mark_unlocked(env: &Env, mut schedule: Schedule) -> Result<(), VestingError> {
+        if schedule.unlocked_at.is_some() {
+            return Err(VestingError::AlreadyUnlocked);
+        }
+        if schedule.revoked_at.is_some() {
+            return Err(VestingError::AlreadyRevoked);
+        }
+
+        let now = env.ledger().timestamp();
+        schedule.unlocked_at = Some(now);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schedule(schedule.id), &schedule);
+
+        events::MilestoneUnlockedEvent {
+            beneficiary: schedule.beneficiary,
+            schedule_id: schedule.id,
+            unlocked_at: now,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Earliest time at or after `now` at which a schedule is vesting, or
+    /// `None` if it is fully vested, revoked or waiting on a milestone
+    fn next_unlock(schedule: &Schedule, vested: i128, granted: i128, now: u64) -> Option<u64> {
+        if vested >= granted || schedule.revoked_at.is_some() {
+            return None;
+        }
+
+        let mut unlock_time = schedule.start_time.saturating_add(schedule.cliff);
+        if schedule.unlock != UnlockCondition::Time {
+            unlock_time = unlock_time.max(schedule.unlocked_at?);
+        }
+        Some(unlock_time.max(now))
+    }
+
+    fn schedule_ids(env: &Env, beneficiary: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BeneficiarySchedules(beneficiary.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn add_schedule_id(env: &Env, beneficiary: &Address, schedule_id: u64) {
+        let mut ids = Self::schedule_ids(env, beneficiary);
+        ids.push_back(schedule_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BeneficiarySchedules(beneficiary.clone()), &ids);
+    }
+
+    fn vested(schedule: &Schedule, at: u64) -> i128 {
+        // Milestone schedules vest nothing before their condition is met
+        if schedule.unlock != UnlockCondition::Time {
+            match schedule.unlocked_at {
+                Some(unlocked_at) if unlocked_at <= at => {}
+                _ => return 0,
+            }
+        }
+
+        // Revoked schedules stop vesting at the revocation time
+        let at = match schedule.revoked_at {
+            Some(revoked_at) if revoked_at < at => revoked_at,
+            _ => at,
+        };
+        lumenpulse_accrual::cliff_linear(
+            schedule.total_amount,
+            schedule.start_time,
+            schedule.cliff,
+            schedule.duration,
+            at,
+        )
+    }
+}
+
+impl Pausable for VestingContract {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        Self::get_admin(env.clone()).ok()
+    }
+}
+
+lumenpulse_pausable::pausable!(VestingContract);
+
+#[cfg(test)]
+mod test;
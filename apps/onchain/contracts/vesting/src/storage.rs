@@ -0,0 +1,86 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,                         // -> Address
+    NextScheduleId,                // -> u64
+    Schedule(u64),                 // schedule_id -> Schedule
+    Treasury,                      // -> Address
+    BeneficiarySchedules(Address), // beneficiary -> Vec<u64>
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Schedule {
+    pub id: u64,
+    pub beneficiary: Address,
+    pub token: Address,
+    pub total_amount: i128,
+    pub start_time: u64,
+    /// Seconds after `start_time` before anything vests
+    pub cliff: u64,
+    pub duration: u64,
+    pub claimed_amount: i128,
+    pub revocable: bool,
+    /// Set once a revocable schedule has been revoked; vesting stops here
+    pub revoked_at: Option<u64>,
+    pub unlock: UnlockCondition,
+    /// Set once a milestone condition has been met; nothing vests before it
+    pub unlocked_at: Option<u64>,
+}
+
+/// External trigger gating a schedule in addition to its time-based release
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UnlockCondition {
+    /// Purely time-based, no milestone
+    Time,
+    /// Unlocked when the admin attests the milestone was reached
+    Attestation,
+    /// Unlocked when the oracle reports the condition as met
+    Oracle(OracleCondition),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleCondition {
+    pub oracle: Address,
+    pub condition_id: u64,
+}
+
+/// Parameters for a new vesting schedule
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleParams {
+    pub beneficiary: Address,
+    pub token: Address,
+    pub total_amount: i128,
+    pub start_time: u64,
+    /// Seconds after `start_time` before anything vests
+    pub cliff: u64,
+    pub duration: u64,
+    pub revocable: bool,
+}
+
+/// Per-token totals across a beneficiary's schedules
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenPosition {
+    pub token: Address,
+    pub granted: i128,
+    pub vested: i128,
+    pub claimed: i128,
+}
+
+/// Summary of everything a beneficiary holds in the contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingPortfolio {
+    pub schedule_count: u32,
+    pub positions: Vec<TokenPosition>,
+    /// Earliest time at or after now at which tokens are vesting, if any
+    /// schedule is still time-locked or accruing. Milestone schedules awaiting
+    /// their trigger have no known unlock time and are not counted.
+    pub next_unlock_time: Option<u64>,
+}
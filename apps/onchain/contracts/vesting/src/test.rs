@@ -0,0 +1,850 @@
+use crate::errors::VestingError;
+use crate::storage::{
+    OracleCondition, ScheduleParams, TokenPosition, UnlockCondition, VestingPortfolio,
+};
+use crate::{VestingContract, VestingContractClient};
+use lumenpulse_pausable::PauseError;
+use soroban_sdk::{
+    contract, contractimpl, map,
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, IntoVal, InvokeError, Map, Symbol, Val, Vec,
+};
+
+#[contract]
+struct MockMilestoneOracle;
+
+#[contractimpl]
+impl MockMilestoneOracle {
+    pub fn set_met(env: Env, condition_id: u64) {
+        env.storage().instance().set(&condition_id, &true);
+    }
+
+    pub fn is_condition_met(env: Env, condition_id: u64) -> bool {
+        env.storage().instance().get(&condition_id).unwrap_or(false)
+    }
+}
+
+fn create_token_contract<'a>(
+    env: &Env,
+    admin: &Address,
+) -> (TokenClient<'a>, StellarAssetClient<'a>) {
+    let contract_address = env.register_stellar_asset_contract_v2(admin.clone());
+    (
+        TokenClient::new(env, &contract_address.address()),
+        StellarAssetClient::new(env, &contract_address.address()),
+    )
+}
+
+fn setup_test<'a>(env: &Env) -> (VestingContractClient<'a>, Address, Address, TokenClient<'a>) {
+    let admin = Address::generate(env);
+    let beneficiary = Address::generate(env);
+
+    // Create token and fund the admin for grants
+    let (token_client, token_admin_client) = create_token_contract(env, &admin);
+    token_admin_client.mint(&admin, &10_000_000);
+
+    // Register and initialize contract
+    let contract_id = env.register(VestingContract, ());
+    let client = VestingContractClient::new(env, &contract_id);
+    client.initialize(&admin);
+
+    (client, admin, beneficiary, token_client)
+}
+
+#[test]
+fn test_initialize() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _) = setup_test(&env);
+
+    assert_eq!(client.get_admin(), admin);
+    let result = client.try_initialize(&admin);
+    assert_eq!(result, Err(Ok(VestingError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_create_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let schedule_id = client.create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000_000,
+            start_time,
+            cliff: 1_000,
+            duration: 10_000,
+            revocable: false,
+        },
+    );
+
+    let schedule = client.get_schedule(&schedule_id);
+    assert_eq!(schedule.beneficiary, beneficiary);
+    assert_eq!(schedule.token, token_client.address);
+    assert_eq!(schedule.total_amount, 1_000_000);
+    assert_eq!(schedule.cliff, 1_000);
+    assert_eq!(schedule.claimed_amount, 0);
+    assert_eq!(token_client.balance(&client.address), 1_000_000);
+}
+
+#[test]
+fn test_pause_stops_new_schedules() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+    let params = ScheduleParams {
+        beneficiary,
+        token: token_client.address.clone(),
+        total_amount: 1_000,
+        start_time: env.ledger().timestamp(),
+        cliff: 0,
+        duration: 1_000,
+        revocable: false,
+    };
+    client.pause(&admin);
+
+    let paused = InvokeError::Contract(PauseError::Paused as u32);
+    let result = client.try_create_schedule(&admin, &params);
+    assert_eq!(result, Err(Err(paused)));
+    let batch = vec![&env, params.clone()];
+    let result = client.try_create_schedules(&admin, &batch);
+    assert_eq!(result, Err(Err(paused)));
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    client.unpause(&admin);
+    client.create_schedule(&admin, &params);
+    assert_eq!(token_client.balance(&client.address), 1_000);
+}
+
+#[test]
+fn test_create_schedule_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+    let start_time = env.ledger().timestamp() + 100;
+
+    let result = client.try_create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 0,
+            start_time,
+            cliff: 0,
+            duration: 10_000,
+            revocable: false,
+        },
+    );
+    assert_eq!(result, Err(Ok(VestingError::InvalidAmount)));
+
+    let result = client.try_create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000,
+            start_time,
+            cliff: 0,
+            duration: 0,
+            revocable: false,
+        },
+    );
+    assert_eq!(result, Err(Ok(VestingError::InvalidDuration)));
+
+    let result = client.try_create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000,
+            start_time,
+            cliff: 20_000,
+            duration: 10_000,
+            revocable: false,
+        },
+    );
+    assert_eq!(result, Err(Ok(VestingError::InvalidCliff)));
+}
+
+#[test]
+fn test_create_schedule_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, beneficiary, token_client) = setup_test(&env);
+    let non_admin = Address::generate(&env);
+
+    let result = client.try_create_schedule(
+        &non_admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000,
+            start_time: env.ledger().timestamp(),
+            cliff: 0,
+            duration: 10_000,
+            revocable: false,
+        },
+    );
+    assert_eq!(result, Err(Ok(VestingError::Unauthorized)));
+}
+
+#[test]
+fn test_cliff_blocks_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let schedule_id = client.create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000_000,
+            start_time,
+            cliff: 2_500,
+            duration: 10_000,
+            revocable: false,
+        },
+    );
+
+    // Just before the cliff nothing is vested
+    env.ledger().set_timestamp(start_time + 2_499);
+    assert_eq!(client.vested_amount(&schedule_id, &(start_time + 2_499)), 0);
+    let result = client.try_claim(&schedule_id);
+    assert_eq!(result, Err(Ok(VestingError::NothingToClaim)));
+
+    // At the cliff the accrued portion unlocks at once
+    env.ledger().set_timestamp(start_time + 2_500);
+    assert_eq!(client.claim(&schedule_id), 250_000);
+    assert_eq!(token_client.balance(&beneficiary), 250_000);
+}
+
+#[test]
+fn test_claim_linear_then_full() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let schedule_id = client.create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000_000,
+            start_time,
+            cliff: 0,
+            duration: 10_000,
+            revocable: false,
+        },
+    );
+
+    env.ledger().set_timestamp(start_time + 5_000);
+    assert_eq!(client.claim(&schedule_id), 500_000);
+
+    env.ledger().set_timestamp(start_time + 20_000);
+    assert_eq!(client.claim(&schedule_id), 500_000);
+    assert_eq!(token_client.balance(&beneficiary), 1_000_000);
+    assert_eq!(client.get_schedule(&schedule_id).claimed_amount, 1_000_000);
+
+    let result = client.try_claim(&schedule_id);
+    assert_eq!(result, Err(Ok(VestingError::NothingToClaim)));
+}
+
+#[test]
+fn test_vested_amount_view() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let schedule_id = client.create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000_000,
+            start_time,
+            cliff: 1_000,
+            duration: 10_000,
+            revocable: false,
+        },
+    );
+
+    assert_eq!(client.vested_amount(&schedule_id, &0), 0);
+    assert_eq!(client.vested_amount(&schedule_id, &(start_time + 999)), 0);
+    assert_eq!(
+        client.vested_amount(&schedule_id, &(start_time + 7_500)),
+        750_000
+    );
+    assert_eq!(
+        client.vested_amount(&schedule_id, &(start_time + 50_000)),
+        1_000_000
+    );
+
+    let result = client.try_vested_amount(&42, &0);
+    assert_eq!(result, Err(Ok(VestingError::ScheduleNotFound)));
+}
+
+#[test]
+fn test_revoke_returns_unvested_to_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+    let treasury = Address::generate(&env);
+    client.set_treasury(&admin, &treasury);
+    assert_eq!(client.get_treasury(), treasury);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let schedule_id = client.create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000_000,
+            start_time,
+            cliff: 0,
+            duration: 10_000,
+            revocable: true,
+        },
+    );
+
+    env.ledger().set_timestamp(start_time + 4_000);
+    assert_eq!(client.revoke(&admin, &schedule_id), 600_000);
+    assert_eq!(token_client.balance(&treasury), 600_000);
+
+    // Vested tokens remain claimable and vesting no longer grows
+    env.ledger().set_timestamp(start_time + 9_000);
+    assert_eq!(
+        client.vested_amount(&schedule_id, &(start_time + 9_000)),
+        400_000
+    );
+    assert_eq!(client.claim(&schedule_id), 400_000);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    let result = client.try_revoke(&admin, &schedule_id);
+    assert_eq!(result, Err(Ok(VestingError::AlreadyRevoked)));
+}
+
+#[test]
+fn test_revoke_before_cliff_returns_everything() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let schedule_id = client.create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000_000,
+            start_time,
+            cliff: 5_000,
+            duration: 10_000,
+            revocable: true,
+        },
+    );
+
+    // Treasury defaults to the admin
+    env.ledger().set_timestamp(start_time + 4_000);
+    assert_eq!(client.revoke(&admin, &schedule_id), 1_000_000);
+    assert_eq!(token_client.balance(&admin), 10_000_000);
+
+    env.ledger().set_timestamp(start_time + 20_000);
+    let result = client.try_claim(&schedule_id);
+    assert_eq!(result, Err(Ok(VestingError::NothingToClaim)));
+}
+
+#[test]
+fn test_non_revocable_schedule_cannot_be_revoked() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+
+    let schedule_id = client.create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000_000,
+            start_time: env.ledger().timestamp(),
+            cliff: 0,
+            duration: 10_000,
+            revocable: false,
+        },
+    );
+
+    let result = client.try_revoke(&admin, &schedule_id);
+    assert_eq!(result, Err(Ok(VestingError::NotRevocable)));
+
+    let non_admin = Address::generate(&env);
+    let result = client.try_revoke(&non_admin, &schedule_id);
+    assert_eq!(result, Err(Ok(VestingError::Unauthorized)));
+}
+
+#[test]
+fn test_create_schedules_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+    let second_beneficiary = Address::generate(&env);
+
+    // A second token exercises per-token aggregation
+    let (other_token, other_token_admin) = create_token_contract(&env, &admin);
+    other_token_admin.mint(&admin, &5_000);
+
+    let start_time = env.ledger().timestamp() + 100;
+    let params = |beneficiary: &Address, token: &Address, total_amount: i128| ScheduleParams {
+        beneficiary: beneficiary.clone(),
+        token: token.clone(),
+        total_amount,
+        start_time,
+        cliff: 0,
+        duration: 10_000,
+        revocable: false,
+    };
+
+    let schedule_ids = client.create_schedules(
+        &admin,
+        &vec![
+            &env,
+            params(&beneficiary, &token_client.address, 1_000),
+            params(&second_beneficiary, &token_client.address, 2_000),
+            params(&beneficiary, &other_token.address, 3_000),
+        ],
+    );
+    assert_eq!(schedule_ids, vec![&env, 0, 1, 2]);
+
+    assert_eq!(token_client.balance(&client.address), 3_000);
+    assert_eq!(other_token.balance(&client.address), 3_000);
+    assert_eq!(client.get_schedule(&1).beneficiary, second_beneficiary);
+    assert_eq!(client.get_schedule(&2).token, other_token.address);
+}
+
+#[test]
+fn test_create_schedules_rejects_invalid_entry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+
+    let valid = ScheduleParams {
+        beneficiary: beneficiary.clone(),
+        token: token_client.address.clone(),
+        total_amount: 1_000,
+        start_time: env.ledger().timestamp(),
+        cliff: 0,
+        duration: 10_000,
+        revocable: false,
+    };
+    let mut invalid = valid.clone();
+    invalid.duration = 0;
+
+    // Nothing is created or transferred when any entry is invalid
+    let result = client.try_create_schedules(&admin, &vec![&env, valid.clone(), invalid]);
+    assert_eq!(result, Err(Ok(VestingError::InvalidDuration)));
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    let result = client.try_create_schedules(&admin, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(VestingError::EmptyBatch)));
+
+    let mut too_many = Vec::new(&env);
+    for _ in 0..26 {
+        too_many.push_back(valid.clone());
+    }
+    let result = client.try_create_schedules(&admin, &too_many);
+    assert_eq!(result, Err(Ok(VestingError::BatchTooLarge)));
+}
+
+#[test]
+fn test_beneficiary_transfers_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+    let new_beneficiary = Address::generate(&env);
+
+    let start_time = env.ledger().timestamp();
+    let schedule_id = client.create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000_000,
+            start_time,
+            cliff: 0,
+            duration: 10_000,
+            revocable: false,
+        },
+    );
+
+    env.ledger().set_timestamp(start_time + 2_000);
+    assert_eq!(client.claim(&schedule_id), 200_000);
+
+    client.transfer_beneficiary(&schedule_id, &new_beneficiary);
+    let schedule = client.get_schedule(&schedule_id);
+    assert_eq!(schedule.beneficiary, new_beneficiary);
+    assert_eq!(schedule.claimed_amount, 200_000);
+
+    // Future claims go to the new address
+    env.ledger().set_timestamp(start_time + 5_000);
+    assert_eq!(client.claim(&schedule_id), 300_000);
+    assert_eq!(token_client.balance(&new_beneficiary), 300_000);
+    assert_eq!(token_client.balance(&beneficiary), 200_000);
+
+    let result = client.try_transfer_beneficiary(&schedule_id, &new_beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::SameBeneficiary)));
+}
+
+#[test]
+fn test_admin_reassigns_disputed_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+    let recovery = Address::generate(&env);
+
+    let schedule_id = client.create_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000_000,
+            start_time: env.ledger().timestamp(),
+            cliff: 0,
+            duration: 10_000,
+            revocable: false,
+        },
+    );
+
+    client.reassign_beneficiary(&admin, &schedule_id, &recovery);
+    let data: Map<Symbol, Val> = map![
+        &env,
+        (Symbol::new(&env, "disputed"), true.into_val(&env)),
+        (Symbol::new(&env, "schedule_id"), schedule_id.into_val(&env)),
+    ];
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (
+                    Symbol::new(&env, "beneficiary_reassigned_event"),
+                    beneficiary.clone(),
+                    recovery.clone(),
+                )
+                    .into_val(&env),
+                data.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_schedule(&schedule_id).beneficiary, recovery);
+
+    let non_admin = Address::generate(&env);
+    let result = client.try_reassign_beneficiary(&non_admin, &schedule_id, &beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::Unauthorized)));
+}
+
+#[test]
+fn test_multiple_tranches_claim_all() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+    let (other_token, other_token_admin) = create_token_contract(&env, &admin);
+    other_token_admin.mint(&admin, &1_000_000);
+
+    // Initial grant plus a refresher with a later start, and one in another token
+    let start_time = env.ledger().timestamp();
+    let params = |token: &Address, total_amount: i128, start_time: u64| ScheduleParams {
+        beneficiary: beneficiary.clone(),
+        token: token.clone(),
+        total_amount,
+        start_time,
+        cliff: 0,
+        duration: 10_000,
+        revocable: false,
+    };
+    client.create_schedules(
+        &admin,
+        &vec![
+            &env,
+            params(&token_client.address, 1_000_000, start_time),
+            params(&token_client.address, 500_000, start_time + 5_000),
+            params(&other_token.address, 100_000, start_time),
+        ],
+    );
+    assert_eq!(client.get_schedule_ids(&beneficiary), vec![&env, 0, 1, 2]);
+
+    env.ledger().set_timestamp(start_time + 6_000);
+    let expected = map![
+        &env,
+        (token_client.address.clone(), 600_000 + 50_000),
+        (other_token.address.clone(), 60_000),
+    ];
+    assert_eq!(client.claimable(&beneficiary), expected);
+
+    assert_eq!(client.claim_all(&beneficiary), expected);
+    assert_eq!(token_client.balance(&beneficiary), 650_000);
+    assert_eq!(other_token.balance(&beneficiary), 60_000);
+    assert!(client.claimable(&beneficiary).is_empty());
+
+    let result = client.try_claim_all(&beneficiary);
+    assert_eq!(result, Err(Ok(VestingError::NothingToClaim)));
+}
+
+#[test]
+fn test_reassignment_moves_schedule_index() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+    let new_beneficiary = Address::generate(&env);
+
+    let params = ScheduleParams {
+        beneficiary: beneficiary.clone(),
+        token: token_client.address.clone(),
+        total_amount: 1_000,
+        start_time: env.ledger().timestamp(),
+        cliff: 0,
+        duration: 10_000,
+        revocable: false,
+    };
+    client.create_schedules(&admin, &vec![&env, params.clone(), params]);
+
+    client.transfer_beneficiary(&0, &new_beneficiary);
+    assert_eq!(client.get_schedule_ids(&beneficiary), vec![&env, 1]);
+    assert_eq!(client.get_schedule_ids(&new_beneficiary), vec![&env, 0]);
+}
+
+#[test]
+fn test_attestation_milestone_unlocks_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp();
+    let schedule_id = client.create_milestone_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000_000,
+            start_time,
+            cliff: 0,
+            duration: 10_000,
+            revocable: false,
+        },
+        &UnlockCondition::Attestation,
+    );
+
+    // Time alone does not vest a milestone tranche
+    env.ledger().set_timestamp(start_time + 4_000);
+    assert_eq!(client.vested_amount(&schedule_id, &(start_time + 4_000)), 0);
+    let result = client.try_claim(&schedule_id);
+    assert_eq!(result, Err(Ok(VestingError::NothingToClaim)));
+
+    // Once attested, the time-based accrual applies
+    client.attest_milestone(&admin, &schedule_id);
+    assert_eq!(
+        client.get_schedule(&schedule_id).unlocked_at,
+        Some(start_time + 4_000)
+    );
+    assert_eq!(client.claim(&schedule_id), 400_000);
+
+    let result = client.try_attest_milestone(&admin, &schedule_id);
+    assert_eq!(result, Err(Ok(VestingError::AlreadyUnlocked)));
+}
+
+#[test]
+fn test_oracle_milestone_unlocks_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+    let oracle_id = env.register(MockMilestoneOracle, ());
+    let oracle = MockMilestoneOracleClient::new(&env, &oracle_id);
+
+    let start_time = env.ledger().timestamp();
+    let schedule_id = client.create_milestone_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token_client.address.clone(),
+            total_amount: 1_000_000,
+            start_time,
+            cliff: 0,
+            duration: 10_000,
+            revocable: false,
+        },
+        &UnlockCondition::Oracle(OracleCondition {
+            oracle: oracle_id.clone(),
+            condition_id: 7,
+        }),
+    );
+
+    let result = client.try_unlock_milestone(&schedule_id);
+    assert_eq!(result, Err(Ok(VestingError::MilestoneNotReached)));
+
+    // Attestation does not apply to oracle-gated schedules
+    let result = client.try_attest_milestone(&admin, &schedule_id);
+    assert_eq!(result, Err(Ok(VestingError::InvalidUnlockCondition)));
+
+    oracle.set_met(&7);
+    env.ledger().set_timestamp(start_time + 20_000);
+    client.unlock_milestone(&schedule_id);
+    assert_eq!(client.claim(&schedule_id), 1_000_000);
+}
+
+#[test]
+fn test_milestone_schedule_requires_condition() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+
+    let result = client.try_create_milestone_schedule(
+        &admin,
+        &ScheduleParams {
+            beneficiary,
+            token: token_client.address.clone(),
+            total_amount: 1_000,
+            start_time: env.ledger().timestamp(),
+            cliff: 0,
+            duration: 10_000,
+            revocable: false,
+        },
+        &UnlockCondition::Time,
+    );
+    assert_eq!(result, Err(Ok(VestingError::InvalidUnlockCondition)));
+}
+
+#[test]
+fn test_portfolio_summarizes_schedules() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+    let (other_token, other_token_admin) = create_token_contract(&env, &admin);
+    other_token_admin.mint(&admin, &1_000_000);
+
+    let start_time = env.ledger().timestamp();
+    let params =
+        |token: &Address, total_amount: i128, cliff: u64, revocable: bool| ScheduleParams {
+            beneficiary: beneficiary.clone(),
+            token: token.clone(),
+            total_amount,
+            start_time,
+            cliff,
+            duration: 10_000,
+            revocable,
+        };
+    client.create_schedules(
+        &admin,
+        &vec![
+            &env,
+            params(&token_client.address, 1_000_000, 0, false),
+            params(&token_client.address, 200_000, 0, true),
+            params(&other_token.address, 100_000, 8_000, false),
+        ],
+    );
+    // A pending milestone tranche counts as granted but never as unlocking
+    client.create_milestone_schedule(
+        &admin,
+        &params(&token_client.address, 50_000, 0, false),
+        &UnlockCondition::Attestation,
+    );
+
+    env.ledger().set_timestamp(start_time + 2_000);
+    client.claim(&0);
+    client.revoke(&admin, &1);
+
+    env.ledger().set_timestamp(start_time + 5_000);
+    let primary = TokenPosition {
+        token: token_client.address.clone(),
+        granted: 1_000_000 + 40_000 + 50_000,
+        vested: 500_000 + 40_000,
+        claimed: 200_000,
+    };
+    let secondary = TokenPosition {
+        token: other_token.address.clone(),
+        granted: 100_000,
+        vested: 0,
+        claimed: 0,
+    };
+    // Positions come back ordered by token address
+    let positions = if primary.token < secondary.token {
+        vec![&env, primary, secondary]
+    } else {
+        vec![&env, secondary, primary]
+    };
+    assert_eq!(
+        client.portfolio(&beneficiary),
+        VestingPortfolio {
+            schedule_count: 4,
+            positions,
+            // The first schedule is still accruing right now
+            next_unlock_time: Some(start_time + 5_000),
+        }
+    );
+}
+
+#[test]
+fn test_portfolio_next_unlock_after_full_vesting() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, beneficiary, token_client) = setup_test(&env);
+
+    let start_time = env.ledger().timestamp();
+    let params = |cliff: u64| ScheduleParams {
+        beneficiary: beneficiary.clone(),
+        token: token_client.address.clone(),
+        total_amount: 1_000,
+        start_time,
+        cliff,
+        duration: 10_000,
+        revocable: false,
+    };
+    client.create_schedules(&admin, &vec![&env, params(6_000), params(9_000)]);
+
+    // Both are before their cliff, so the earliest cliff is next
+    assert_eq!(
+        client.portfolio(&beneficiary).next_unlock_time,
+        Some(start_time + 6_000)
+    );
+
+    // Once everything has vested there is nothing left to unlock
+    env.ledger().set_timestamp(start_time + 10_000);
+    let portfolio = client.portfolio(&beneficiary);
+    assert_eq!(portfolio.next_unlock_time, None);
+    assert_eq!(portfolio.positions.get(0).unwrap().vested, 2_000);
+
+    // An address without schedules has an empty portfolio
+    let stranger = Address::generate(&env);
+    let empty = client.portfolio(&stranger);
+    assert_eq!(empty.schedule_count, 0);
+    assert!(empty.positions.is_empty());
+}
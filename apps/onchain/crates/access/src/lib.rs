@@ -0,0 +1,233 @@
+#![no_std]
+//! Admin and role checks for Lumenpulse contracts.
+//!
+//! A contract stores its admin with `init_admin` when it is initialized
+//! and guards admin-only entrypoints with `require_admin`. The admin can
+//! be handed over in two steps, `propose_admin` then `accept_admin`, so a
+//! mistyped address cannot lock the contract; `set_admin` hands it over
+//! at once for interfaces that need that. Roles are named by a
+//! `Symbol` and granted to accounts by the admin, for work that should not
+//! need the admin key, and checked with `require_role`.
+//!
+//! The admin lives in instance storage and roles in persistent storage,
+//! under variant names no Lumenpulse contract uses for its own keys.
+
+use soroban_sdk::{contractevent, contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum AccessKey {
+    Administrator,               // -> Address
+    PendingAdministrator,        // -> Address
+    RoleMember(Symbol, Address), // (role, account) -> bool
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AccessError {
+    NotInitialized,
+    AlreadyInitialized,
+    /// The caller is not the admin
+    Unauthorized,
+    /// No admin handover is waiting to be accepted by the caller
+    NoPendingAdmin,
+    /// The caller does not hold the role
+    MissingRole,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposedEvent {
+    #[topic]
+    pub admin: Address,
+    pub pending_admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferredEvent {
+    #[topic]
+    pub previous_admin: Address,
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGrantedEvent {
+    #[topic]
+    pub role: Symbol,
+    #[topic]
+    pub account: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleRevokedEvent {
+    #[topic]
+    pub role: Symbol,
+    #[topic]
+    pub account: Address,
+}
+
+/// Store the contract's first admin
+pub fn init_admin(env: &Env, admin: &Address) -> Result<(), AccessError> {
+    if env.storage().instance().has(&AccessKey::Administrator) {
+        return Err(AccessError::AlreadyInitialized);
+    }
+    env.storage()
+        .instance()
+        .set(&AccessKey::Administrator, admin);
+    Ok(())
+}
+
+/// The contract's admin
+pub fn admin(env: &Env) -> Result<Address, AccessError> {
+    env.storage()
+        .instance()
+        .get(&AccessKey::Administrator)
+        .ok_or(AccessError::NotInitialized)
+}
+
+/// Check that `caller` is the admin and has authorized the call
+pub fn require_admin(env: &Env, caller: &Address) -> Result<(), AccessError> {
+    if *caller != admin(env)? {
+        return Err(AccessError::Unauthorized);
+    }
+    caller.require_auth();
+    Ok(())
+}
+
+/// Offer the admin role to `pending_admin` (admin only). It takes over
+/// once it accepts; a later proposal replaces this one.
+pub fn propose_admin(
+    env: &Env,
+    admin: &Address,
+    pending_admin: &Address,
+) -> Result<(), AccessError> {
+    require_admin(env, admin)?;
+    env.storage()
+        .instance()
+        .set(&AccessKey::PendingAdministrator, pending_admin);
+
+    AdminProposedEvent {
+        admin: admin.clone(),
+        pending_admin: pending_admin.clone(),
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Take over as admin, as proposed by the current admin
+pub fn accept_admin(env: &Env, pending_admin: &Address) -> Result<(), AccessError> {
+    if pending(env).as_ref() != Some(pending_admin) {
+        return Err(AccessError::NoPendingAdmin);
+    }
+    pending_admin.require_auth();
+
+    let previous_admin = admin(env)?;
+    env.storage()
+        .instance()
+        .set(&AccessKey::Administrator, pending_admin);
+    env.storage()
+        .instance()
+        .remove(&AccessKey::PendingAdministrator);
+
+    AdminTransferredEvent {
+        previous_admin,
+        admin: pending_admin.clone(),
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Hand the admin role straight to `new_admin` (admin only), for
+/// contracts whose interface fixes a one-step handover, such as a Stellar
+/// asset's `set_admin`. Any pending handover is dropped. Prefer
+/// `propose_admin` elsewhere.
+pub fn set_admin(env: &Env, admin: &Address, new_admin: &Address) -> Result<(), AccessError> {
+    require_admin(env, admin)?;
+    env.storage()
+        .instance()
+        .set(&AccessKey::Administrator, new_admin);
+    env.storage()
+        .instance()
+        .remove(&AccessKey::PendingAdministrator);
+
+    AdminTransferredEvent {
+        previous_admin: admin.clone(),
+        admin: new_admin.clone(),
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Admin handover waiting to be accepted, if any
+pub fn pending(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&AccessKey::PendingAdministrator)
+}
+
+/// Whether `account` holds `role`
+pub fn has_role(env: &Env, role: &Symbol, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&AccessKey::RoleMember(role.clone(), account.clone()))
+}
+
+/// Grant `role` to `account` (admin only)
+pub fn grant_role(
+    env: &Env,
+    admin: &Address,
+    role: &Symbol,
+    account: &Address,
+) -> Result<(), AccessError> {
+    require_admin(env, admin)?;
+    env.storage()
+        .persistent()
+        .set(&AccessKey::RoleMember(role.clone(), account.clone()), &true);
+
+    RoleGrantedEvent {
+        role: role.clone(),
+        account: account.clone(),
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Take `role` away from `account` (admin only)
+pub fn revoke_role(
+    env: &Env,
+    admin: &Address,
+    role: &Symbol,
+    account: &Address,
+) -> Result<(), AccessError> {
+    require_admin(env, admin)?;
+    env.storage()
+        .persistent()
+        .remove(&AccessKey::RoleMember(role.clone(), account.clone()));
+
+    RoleRevokedEvent {
+        role: role.clone(),
+        account: account.clone(),
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Check that `account` holds `role` and has authorized the call. The
+/// admin passes every role check.
+pub fn require_role(env: &Env, role: &Symbol, account: &Address) -> Result<(), AccessError> {
+    if !has_role(env, role, account) && admin(env).ok().as_ref() != Some(account) {
+        return Err(AccessError::MissingRole);
+    }
+    account.require_auth();
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,136 @@
+use crate::AccessError;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, testutils::Address as _, Address, Env, Symbol,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+enum GuardError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    NoPendingAdmin = 4,
+    MissingRole = 5,
+}
+
+impl From<AccessError> for GuardError {
+    fn from(error: AccessError) -> Self {
+        match error {
+            AccessError::NotInitialized => GuardError::NotInitialized,
+            AccessError::AlreadyInitialized => GuardError::AlreadyInitialized,
+            AccessError::Unauthorized => GuardError::Unauthorized,
+            AccessError::NoPendingAdmin => GuardError::NoPendingAdmin,
+            AccessError::MissingRole => GuardError::MissingRole,
+        }
+    }
+}
+
+/// Contract exposing the access checks as entrypoints
+#[contract]
+struct Guarded;
+
+#[contractimpl]
+impl Guarded {
+    pub fn init(env: Env, admin: Address) -> Result<(), GuardError> {
+        Ok(crate::init_admin(&env, &admin)?)
+    }
+
+    pub fn admin(env: Env) -> Result<Address, GuardError> {
+        Ok(crate::admin(&env)?)
+    }
+
+    pub fn propose(env: Env, admin: Address, pending: Address) -> Result<(), GuardError> {
+        Ok(crate::propose_admin(&env, &admin, &pending)?)
+    }
+
+    pub fn accept(env: Env, pending: Address) -> Result<(), GuardError> {
+        Ok(crate::accept_admin(&env, &pending)?)
+    }
+
+    pub fn set(env: Env, admin: Address, new_admin: Address) -> Result<(), GuardError> {
+        Ok(crate::set_admin(&env, &admin, &new_admin)?)
+    }
+
+    pub fn grant(
+        env: Env,
+        admin: Address,
+        role: Symbol,
+        account: Address,
+    ) -> Result<(), GuardError> {
+        Ok(crate::grant_role(&env, &admin, &role, &account)?)
+    }
+
+    pub fn revoke(
+        env: Env,
+        admin: Address,
+        role: Symbol,
+        account: Address,
+    ) -> Result<(), GuardError> {
+        Ok(crate::revoke_role(&env, &admin, &role, &account)?)
+    }
+
+    pub fn guarded(env: Env, role: Symbol, account: Address) -> Result<(), GuardError> {
+        Ok(crate::require_role(&env, &role, &account)?)
+    }
+}
+
+#[test]
+fn test_admin_handover_and_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = GuardedClient::new(&env, &env.register(Guarded, ()));
+    let owner = Address::generate(&env);
+    let successor = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let role = Symbol::new(&env, "keeper");
+
+    assert_eq!(client.try_admin(), Err(Ok(GuardError::NotInitialized)));
+    client.init(&owner);
+    let result = client.try_init(&successor);
+    assert_eq!(result, Err(Ok(GuardError::AlreadyInitialized)));
+
+    // Roles are the admin's to hand out, and the admin holds them all
+    let result = client.try_grant(&keeper, &role, &keeper);
+    assert_eq!(result, Err(Ok(GuardError::Unauthorized)));
+    let result = client.try_guarded(&role, &keeper);
+    assert_eq!(result, Err(Ok(GuardError::MissingRole)));
+    client.grant(&owner, &role, &keeper);
+    client.guarded(&role, &keeper);
+    client.guarded(&role, &owner);
+    client.revoke(&owner, &role, &keeper);
+    let result = client.try_guarded(&role, &keeper);
+    assert_eq!(result, Err(Ok(GuardError::MissingRole)));
+
+    // Only the proposed admin can take over, and only once
+    client.propose(&owner, &successor);
+    let result = client.try_accept(&keeper);
+    assert_eq!(result, Err(Ok(GuardError::NoPendingAdmin)));
+    client.accept(&successor);
+    assert_eq!(client.admin(), successor);
+    let result = client.try_accept(&successor);
+    assert_eq!(result, Err(Ok(GuardError::NoPendingAdmin)));
+    let result = client.try_grant(&owner, &role, &keeper);
+    assert_eq!(result, Err(Ok(GuardError::Unauthorized)));
+}
+
+#[test]
+fn test_set_admin_hands_over_at_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = GuardedClient::new(&env, &env.register(Guarded, ()));
+    let owner = Address::generate(&env);
+    let successor = Address::generate(&env);
+    let proposed = Address::generate(&env);
+    client.init(&owner);
+    client.propose(&owner, &proposed);
+
+    let result = client.try_set(&successor, &successor);
+    assert_eq!(result, Err(Ok(GuardError::Unauthorized)));
+    client.set(&owner, &successor);
+    assert_eq!(client.admin(), successor);
+
+    // The handover proposed by the previous admin no longer stands
+    let result = client.try_accept(&proposed);
+    assert_eq!(result, Err(Ok(GuardError::NoPendingAdmin)));
+}
@@ -0,0 +1,113 @@
+#![no_std]
+//! How much of a stream or vesting schedule has accrued at a given time.
+//!
+//! The contracts pay out what these functions return, and the off-chain
+//! services compute what to show from the same functions, so an amount a
+//! service reports as claimable is the amount the contract pays at that
+//! timestamp. The crate has no Soroban dependency, so it builds for the
+//! host as well as for `wasm32-unknown-unknown`.
+//!
+//! Every function rounds down, so a payout never runs ahead of the
+//! curve, and none of them panic: times before the start accrue nothing,
+//! fixed-total curves are exact for any `i128` total and pay it all from
+//! their end, and a rate whose product overflows accrues the cap.
+
+use lumenpulse_fixed::{prorate, Rounding};
+
+/// Shape of a release over time
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Curve {
+    /// Evenly from `start` to `end`
+    Linear { start: u64, end: u64 },
+    /// Evenly over `duration` seconds from `start`, with nothing released
+    /// before `start + cliff`
+    Cliff {
+        start: u64,
+        cliff: u64,
+        duration: u64,
+    },
+    /// `amount` every `period` seconds from `start`, with no end
+    Rate {
+        start: u64,
+        amount: i128,
+        period: u64,
+    },
+}
+
+impl Curve {
+    /// Amount of `total` released by `now`. For `Rate`, `total` is the
+    /// funding that caps the accrual.
+    pub fn accrued(&self, total: i128, now: u64) -> i128 {
+        match *self {
+            Curve::Linear { start, end } => linear(total, start, end, now),
+            Curve::Cliff {
+                start,
+                cliff,
+                duration,
+            } => cliff_linear(total, start, cliff, duration, now),
+            Curve::Rate {
+                start,
+                amount,
+                period,
+            } => rate(total, amount, period, start, now),
+        }
+    }
+}
+
+/// Amount of `total` released by `now` for a linear release between
+/// `start` and `end`
+pub fn linear(total: i128, start: u64, end: u64, now: u64) -> i128 {
+    let elapsed = now.min(end).saturating_sub(start);
+    let duration = end.saturating_sub(start);
+    if elapsed >= duration {
+        return total;
+    }
+
+    share(total, elapsed, duration)
+}
+
+/// Amount of `total` released by `now` for a linear release over
+/// `duration` seconds from `start`, with nothing released before
+/// `start + cliff`. At the cliff the linearly accrued amount unlocks at
+/// once.
+pub fn cliff_linear(total: i128, start: u64, cliff: u64, duration: u64, now: u64) -> i128 {
+    if now < start.saturating_add(cliff) {
+        return 0;
+    }
+    let elapsed = now.saturating_sub(start);
+    if elapsed >= duration {
+        return total;
+    }
+
+    share(total, elapsed, duration)
+}
+
+/// Amount accrued by `now` paying `amount` every `period` seconds from
+/// `start`, capped at the funded `cap`. Accrual is computed from the rate
+/// directly so no per-second rounding accumulates over long-running
+/// streams.
+pub fn rate(cap: i128, amount: i128, period: u64, start: u64, now: u64) -> i128 {
+    prorate(amount, now.saturating_sub(start), period, Rounding::Down)
+        .map_or(cap, |accrued| accrued.min(cap))
+}
+
+/// `total * elapsed / duration` rounded down, for `elapsed < duration`.
+/// Splitting `total` at a multiple of `duration` leaves a remainder below
+/// `duration`, whose product with `elapsed` always fits in a `u128`, so
+/// no total is too large to accrue exactly.
+fn share(total: i128, elapsed: u64, duration: u64) -> i128 {
+    let whole = total.div_euclid(duration as i128);
+    let remainder = total.rem_euclid(duration as i128) as u128;
+    let part = remainder * elapsed as u128 / duration as u128;
+    whole
+        .saturating_mul(elapsed as i128)
+        .saturating_add(part as i128)
+}
+
+/// What is left to claim of `accrued` once `withdrawn` has been paid
+pub fn claimable(accrued: i128, withdrawn: i128) -> i128 {
+    accrued.saturating_sub(withdrawn).max(0)
+}
+
+#[cfg(test)]
+mod test;
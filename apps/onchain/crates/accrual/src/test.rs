@@ -0,0 +1,98 @@
+use crate::{claimable, cliff_linear, linear, rate, Curve};
+
+#[test]
+fn test_linear() {
+    assert_eq!(linear(1_000, 100, 200, 50), 0);
+    assert_eq!(linear(1_000, 100, 200, 100), 0);
+    assert_eq!(linear(1_000, 100, 200, 133), 330);
+    assert_eq!(linear(1_000, 100, 200, 200), 1_000);
+    assert_eq!(linear(1_000, 100, 200, u64::MAX), 1_000);
+
+    // A stream that starts and ends together pays out at once, and one
+    // that rounds down never overpays
+    assert_eq!(linear(1_000, 100, 100, 100), 1_000);
+    assert_eq!(linear(10, 0, 3, 1), 3);
+}
+
+#[test]
+fn test_cliff_linear() {
+    assert_eq!(cliff_linear(10_000, 1_000, 2_500, 10_000, 3_499), 0);
+    // What accrued before the cliff unlocks at it
+    assert_eq!(cliff_linear(10_000, 1_000, 2_500, 10_000, 3_500), 2_500);
+    assert_eq!(cliff_linear(10_000, 1_000, 2_500, 10_000, 8_500), 7_500);
+    assert_eq!(cliff_linear(10_000, 1_000, 2_500, 10_000, 11_000), 10_000);
+    assert_eq!(cliff_linear(10_000, 1_000, 0, 10_000, 999), 0);
+}
+
+#[test]
+fn test_huge_totals_accrue_exactly() {
+    // total * elapsed overflows even a u128 here
+    let half = u64::MAX / 2;
+    assert_eq!(
+        linear(i128::MAX, 0, u64::MAX, half),
+        85_070_591_730_234_615_861_231_965_839_514_664_959
+    );
+    assert_eq!(
+        linear(i128::MAX, 0, u64::MAX, u64::MAX - 1),
+        170_141_183_460_469_231_722_463_931_679_029_329_918
+    );
+    assert_eq!(linear(i128::MAX, 0, u64::MAX, u64::MAX), i128::MAX);
+    assert_eq!(
+        cliff_linear(i128::MAX, 0, half, u64::MAX, half),
+        linear(i128::MAX, 0, u64::MAX, half)
+    );
+    assert_eq!(
+        cliff_linear(i128::MAX, 0, half, u64::MAX, u64::MAX),
+        i128::MAX
+    );
+}
+
+#[test]
+fn test_rate() {
+    assert_eq!(rate(1_000, 10, 60, 100, 100), 0);
+    assert_eq!(rate(1_000, 10, 60, 100, 130), 5);
+    assert_eq!(rate(1_000, 10, 60, 100, 100 + 60 * 50), 500);
+    assert_eq!(rate(1_000, 10, 60, 100, u64::MAX), 1_000);
+    // An overflowing product is past any cap
+    assert_eq!(rate(1_000, i128::MAX, 1, 0, u64::MAX), 1_000);
+}
+
+#[test]
+fn test_curve_matches_functions() {
+    for now in [0, 150, 1_000, 5_000, 20_000] {
+        assert_eq!(
+            Curve::Linear {
+                start: 100,
+                end: 10_100
+            }
+            .accrued(7_777, now),
+            linear(7_777, 100, 10_100, now)
+        );
+        assert_eq!(
+            Curve::Cliff {
+                start: 100,
+                cliff: 900,
+                duration: 10_000
+            }
+            .accrued(7_777, now),
+            cliff_linear(7_777, 100, 900, 10_000, now)
+        );
+        assert_eq!(
+            Curve::Rate {
+                start: 100,
+                amount: 3,
+                period: 7
+            }
+            .accrued(7_777, now),
+            rate(7_777, 3, 7, 100, now)
+        );
+    }
+}
+
+#[test]
+fn test_claimable() {
+    assert_eq!(claimable(500, 200), 300);
+    assert_eq!(claimable(500, 500), 0);
+    // Withdrawals never make the claimable amount negative
+    assert_eq!(claimable(200, 500), 0);
+}
@@ -0,0 +1,46 @@
+#![no_std]
+//! Badge-gated access for Lumenpulse contracts.
+//!
+//! A contract that restricts an entrypoint to holders of a badge stores a
+//! `BadgeGate` naming the badge contract and badge, and calls
+//! `requires_badge` before doing anything else. An unreachable badge
+//! contract fails the check rather than letting the caller through.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+/// The part of the badge contract gates read
+#[contractclient(name = "BadgeClient")]
+pub trait Badges {
+    /// Whether `holder` currently holds badge `badge_id`
+    fn has_badge(env: Env, holder: Address, badge_id: u32) -> bool;
+}
+
+/// Badge an entrypoint requires its caller to hold
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeGate {
+    /// Badge contract the badge is issued on
+    pub contract: Address,
+    pub badge_id: u32,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GateError {
+    /// The holder does not hold the badge
+    MissingBadge,
+    /// The badge contract could not be called or returned something
+    /// malformed
+    Unavailable,
+}
+
+/// Check that `holder` holds the badge `gate` requires
+pub fn requires_badge(env: &Env, gate: &BadgeGate, holder: &Address) -> Result<(), GateError> {
+    match BadgeClient::new(env, &gate.contract).try_has_badge(holder, &gate.badge_id) {
+        Ok(Ok(true)) => Ok(()),
+        Ok(Ok(false)) => Err(GateError::MissingBadge),
+        _ => Err(GateError::Unavailable),
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,41 @@
+use crate::{requires_badge, BadgeGate, GateError};
+use badge::{BadgeContract, BadgeContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+#[test]
+fn test_requires_badge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let badges = BadgeContractClient::new(&env, &env.register(BadgeContract, ()));
+    badges.initialize(&admin);
+    let badge_id = badges.create_badge(&admin, &String::from_str(&env, "arbiter"), &admin);
+    let gate = BadgeGate {
+        contract: badges.address.clone(),
+        badge_id,
+    };
+
+    assert_eq!(
+        requires_badge(&env, &gate, &holder),
+        Err(GateError::MissingBadge)
+    );
+    badges.issue(&badge_id, &holder);
+    assert_eq!(requires_badge(&env, &gate, &holder), Ok(()));
+    badges.revoke(&badge_id, &holder);
+    assert_eq!(
+        requires_badge(&env, &gate, &holder),
+        Err(GateError::MissingBadge)
+    );
+
+    // Anything that is not a badge contract fails closed
+    let not_badges = BadgeGate {
+        contract: Address::generate(&env),
+        badge_id,
+    };
+    assert_eq!(
+        requires_badge(&env, &not_badges, &holder),
+        Err(GateError::Unavailable)
+    );
+}
@@ -0,0 +1,172 @@
+//! Resource benchmarks for Lumenpulse entrypoints.
+//!
+//! Every call a user makes is paid for in instructions, memory and ledger
+//! entries, so a hot path that quietly grows is a fee increase. The suite
+//! in this crate runs entrypoints of the WASM builds from the factory's
+//! `testdata` once each, reads what the call cost from the `Env`'s budget
+//! and invocation metering, and compares it with the baseline recorded in
+//! `BASELINES`:
+//!
+//! - instructions and memory may grow by up to `TOLERANCE_PERCENT`
+//! - read and write entry counts may not grow at all, since each entry is
+//!   charged separately
+//!
+//! A change that makes an entrypoint cheaper or knowingly dearer updates
+//! its baseline in the same commit. The failure report prints the new
+//! figures to paste in; `cargo test -p lumenpulse-benchmarks -- --nocapture`
+//! prints them for every entrypoint.
+
+use core::fmt;
+use soroban_sdk::Env;
+
+/// How far instructions and memory may rise above the baseline before the
+/// suite fails
+pub const TOLERANCE_PERCENT: i64 = 10;
+
+/// What one top-level invocation cost
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cost {
+    pub instructions: i64,
+    pub mem_bytes: i64,
+    pub read_entries: u32,
+    pub write_entries: u32,
+}
+
+impl Cost {
+    pub const fn new(
+        instructions: i64,
+        mem_bytes: i64,
+        read_entries: u32,
+        write_entries: u32,
+    ) -> Self {
+        Cost {
+            instructions,
+            mem_bytes,
+            read_entries,
+            write_entries,
+        }
+    }
+
+    /// Cost of the last top-level invocation made in `env`
+    pub fn of_last_call(env: &Env) -> Self {
+        let estimate = env.cost_estimate();
+        let budget = estimate.budget();
+        let resources = estimate.resources();
+        Cost {
+            instructions: budget.cpu_instruction_cost() as i64,
+            mem_bytes: budget.memory_bytes_cost() as i64,
+            read_entries: resources.disk_read_entries + resources.memory_read_entries,
+            write_entries: resources.write_entries,
+        }
+    }
+
+    /// Ways `self` exceeds what `baseline` allows, if any
+    pub fn regressions(&self, baseline: &Cost) -> Vec<String> {
+        let mut found = Vec::new();
+        if exceeds(self.instructions, baseline.instructions) {
+            found.push(format!(
+                "instructions {} > {} + {TOLERANCE_PERCENT}%",
+                self.instructions, baseline.instructions
+            ));
+        }
+        if exceeds(self.mem_bytes, baseline.mem_bytes) {
+            found.push(format!(
+                "mem_bytes {} > {} + {TOLERANCE_PERCENT}%",
+                self.mem_bytes, baseline.mem_bytes
+            ));
+        }
+        if self.read_entries > baseline.read_entries {
+            found.push(format!(
+                "read_entries {} > {}",
+                self.read_entries, baseline.read_entries
+            ));
+        }
+        if self.write_entries > baseline.write_entries {
+            found.push(format!(
+                "write_entries {} > {}",
+                self.write_entries, baseline.write_entries
+            ));
+        }
+        found
+    }
+}
+
+impl fmt::Display for Cost {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Cost {{ instructions: {}, mem_bytes: {}, read_entries: {}, write_entries: {} }}",
+            self.instructions, self.mem_bytes, self.read_entries, self.write_entries
+        )
+    }
+}
+
+fn exceeds(measured: i64, baseline: i64) -> bool {
+    measured * 100 > baseline * (100 + TOLERANCE_PERCENT)
+}
+
+/// Recorded cost of each benchmarked entrypoint, by `contract::entrypoint`,
+/// as instructions, memory bytes, read entries and write entries
+pub const BASELINES: &[(&str, Cost)] = &[
+    (
+        "streaming::create_stream",
+        Cost::new(1_230_307, 1_364_298, 9, 5),
+    ),
+    ("streaming::withdraw", Cost::new(1_277_018, 1_362_672, 8, 4)),
+    ("streaming::cancel", Cost::new(1_263_505, 1_362_516, 8, 4)),
+    ("streaming::deposit", Cost::new(1_146_637, 1_360_517, 8, 4)),
+    (
+        "streaming::withdraw_deposit",
+        Cost::new(1_167_839, 1_360_169, 8, 4),
+    ),
+    (
+        "escrow::create_escrow",
+        Cost::new(1_307_616, 1_378_633, 10, 6),
+    ),
+    ("escrow::contribute", Cost::new(1_400_263, 1_381_236, 10, 5)),
+    (
+        "escrow::release_partial",
+        Cost::new(1_314_199, 1_371_884, 9, 4),
+    ),
+    ("escrow::release", Cost::new(1_287_458, 1_371_656, 8, 4)),
+    ("escrow::refund", Cost::new(1_321_940, 1_379_002, 9, 4)),
+    ("upgradable::increment", Cost::new(406_219, 1_221_850, 2, 1)),
+];
+
+/// Results of a benchmark run, checked against `BASELINES` at the end
+#[derive(Default)]
+pub struct Report {
+    measured: Vec<(&'static str, Cost)>,
+}
+
+impl Report {
+    /// Record the cost of the call just made in `env` under `name`
+    pub fn record(&mut self, env: &Env, name: &'static str) {
+        self.measured.push((name, Cost::of_last_call(env)));
+    }
+
+    /// Print every measurement and panic listing the entrypoints that
+    /// regressed or have no baseline
+    pub fn check(&self) {
+        let mut failures = Vec::new();
+        for (name, cost) in &self.measured {
+            println!("{name}: {cost}");
+            match BASELINES.iter().find(|(baseline, _)| baseline == name) {
+                Some((_, baseline)) => {
+                    for regression in cost.regressions(baseline) {
+                        failures.push(format!("{name}: {regression}"));
+                    }
+                }
+                None => failures.push(format!("{name}: no baseline, measured {cost}")),
+            }
+        }
+        assert!(
+            failures.is_empty(),
+            "entrypoints over budget:\n{}",
+            failures.join("\n")
+        );
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,88 @@
+use crate::{Cost, Report};
+use lumenpulse_test_utils::{advance_time, deploy_protocol, funded_accounts, Protocol};
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig},
+    Address, Env,
+};
+
+fn setup<'a>() -> (Env, Protocol<'a>) {
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+    let protocol = deploy_protocol(&env);
+    (env, protocol)
+}
+
+#[test]
+fn bench_streaming() {
+    let (env, protocol) = setup();
+    let [sender, recipient] = funded_accounts(&env, &protocol.token, 1_000_000);
+    let token = &protocol.token.address;
+    let streaming = &protocol.streaming;
+    let start = env.ledger().timestamp();
+    let mut report = Report::default();
+
+    let stream_id =
+        streaming.create_stream(&sender, &recipient, token, &1_000, &start, &(start + 100));
+    report.record(&env, "streaming::create_stream");
+    advance_time(&env, 25);
+    streaming.withdraw(&stream_id);
+    report.record(&env, "streaming::withdraw");
+    streaming.cancel(&stream_id);
+    report.record(&env, "streaming::cancel");
+
+    streaming.deposit(&sender, token, &1_000);
+    report.record(&env, "streaming::deposit");
+    streaming.withdraw_deposit(&sender, token, &1_000);
+    report.record(&env, "streaming::withdraw_deposit");
+    report.check();
+}
+
+#[test]
+fn bench_escrow() {
+    let (env, protocol) = setup();
+    let [depositor, payee, contributor] = funded_accounts(&env, &protocol.token, 1_000_000);
+    let arbiter = Address::generate(&env);
+    let token = &protocol.token.address;
+    let escrow = &protocol.escrow;
+    let mut report = Report::default();
+
+    let escrow_id = escrow.create_escrow(&depositor, &payee, &arbiter, token, &1_000);
+    report.record(&env, "escrow::create_escrow");
+    escrow.contribute(&contributor, &escrow_id, &500);
+    report.record(&env, "escrow::contribute");
+    escrow.release_partial(&escrow_id, &500);
+    report.record(&env, "escrow::release_partial");
+    escrow.release(&escrow_id);
+    report.record(&env, "escrow::release");
+
+    let escrow_id = escrow.create_escrow(&depositor, &payee, &arbiter, token, &1_000);
+    escrow.refund(&escrow_id);
+    report.record(&env, "escrow::refund");
+    report.check();
+}
+
+#[test]
+fn bench_upgradable() {
+    let (env, protocol) = setup();
+    let mut report = Report::default();
+
+    protocol.upgradable.increment();
+    report.record(&env, "upgradable::increment");
+    report.check();
+}
+
+#[test]
+fn test_regressions_respect_tolerance() {
+    let baseline = Cost::new(1_000, 1_000, 4, 2);
+    assert!(Cost::new(1_100, 1_100, 4, 2)
+        .regressions(&baseline)
+        .is_empty());
+    assert!(Cost::new(500, 500, 3, 1).regressions(&baseline).is_empty());
+    // Instructions and memory have headroom; entry counts have none
+    assert_eq!(
+        Cost::new(1_101, 1_000, 5, 2).regressions(&baseline),
+        ["instructions 1101 > 1000 + 10%", "read_entries 5 > 4"]
+    );
+}
@@ -0,0 +1,104 @@
+#![no_std]
+//! Consumer side of a Soroswap-compatible DEX router.
+//!
+//! Contracts that convert payments between tokens store the router they
+//! trust and swap through `swap_exact_in`, which authorizes exactly the
+//! input transfer the router needs and checks what actually arrived
+//! against the caller's minimum, so a misbehaving router cannot take more
+//! or pay less than agreed.
+
+use soroban_sdk::{contractclient, vec, Address, Env, Vec};
+
+/// The subset of the Soroswap router interface used for swaps
+#[contractclient(name = "RouterClient")]
+pub trait Router {
+    /// Pair contract holding the liquidity for `token_a` and `token_b`
+    fn router_pair_for(env: Env, token_a: Address, token_b: Address) -> Address;
+    /// Amounts along `path` when swapping in `amount_in` of its first token
+    fn router_get_amounts_out(env: Env, amount_in: i128, path: Vec<Address>) -> Vec<i128>;
+    /// Swap `amount_in` of the first token in `path` from `to` for at least
+    /// `amount_out_min` of the last, paid to `to`
+    fn swap_exact_tokens_for_tokens(
+        env: Env,
+        amount_in: i128,
+        amount_out_min: i128,
+        path: Vec<Address>,
+        to: Address,
+        deadline: u64,
+    ) -> Vec<i128>;
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DexError {
+    /// Amount was zero or negative
+    InvalidAmount,
+    /// The router could not be called or returned something malformed
+    Unavailable,
+    /// The router rejected the swap
+    SwapFailed,
+    /// Less than the minimum arrived
+    SlippageExceeded,
+}
+
+/// How much of `token_out` the router would pay for `amount_in` of
+/// `token_in` right now
+pub fn quote(
+    env: &Env,
+    router: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount_in: i128,
+) -> Result<i128, DexError> {
+    if amount_in <= 0 {
+        return Err(DexError::InvalidAmount);
+    }
+    let path = vec![env, token_in.clone(), token_out.clone()];
+    match RouterClient::new(env, router).try_router_get_amounts_out(&amount_in, &path) {
+        Ok(Ok(amounts)) => amounts.last().ok_or(DexError::Unavailable),
+        _ => Err(DexError::Unavailable),
+    }
+}
+
+/// Swap `amount_in` of `token_in` held by the current contract for at
+/// least `min_out` of `token_out`, through the direct pair. Returns the
+/// amount of `token_out` the contract received.
+pub fn swap_exact_in(
+    env: &Env,
+    router: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount_in: i128,
+    min_out: i128,
+) -> Result<i128, DexError> {
+    if amount_in <= 0 || min_out < 0 {
+        return Err(DexError::InvalidAmount);
+    }
+    let client = RouterClient::new(env, router);
+    let this = env.current_contract_address();
+    let pair = match client.try_router_pair_for(token_in, token_out) {
+        Ok(Ok(pair)) => pair,
+        _ => return Err(DexError::Unavailable),
+    };
+
+    let before = lumenpulse_sac::balance(env, token_out, &this);
+
+    // The router moves the input straight from us to the pair, which is a
+    // call below the router and needs our explicit authorization. It only
+    // covers the next call, so nothing may come between it and the swap.
+    lumenpulse_sac::authorize_transfer(env, token_in, &pair, amount_in);
+    let path = vec![env, token_in.clone(), token_out.clone()];
+    let deadline = env.ledger().timestamp();
+    match client.try_swap_exact_tokens_for_tokens(&amount_in, &min_out, &path, &this, &deadline) {
+        Ok(Ok(_)) => {}
+        _ => return Err(DexError::SwapFailed),
+    }
+
+    let received = lumenpulse_sac::balance(env, token_out, &this) - before;
+    if received < min_out {
+        return Err(DexError::SlippageExceeded);
+    }
+    Ok(received)
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,134 @@
+use crate::{quote, swap_exact_in, DexError};
+use mock_router::{MockRouterContract, MockRouterContractClient, RATE_SCALE};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+/// Contract swapping its own balance, for the helpers to run inside
+#[contract]
+struct Payer;
+
+#[contractimpl]
+impl Payer {
+    pub fn swap(
+        env: Env,
+        router: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_out: i128,
+    ) -> Option<i128> {
+        swap_exact_in(&env, &router, &token_in, &token_out, amount_in, min_out).ok()
+    }
+}
+
+struct Setup<'a> {
+    router: MockRouterContractClient<'a>,
+    payer: PayerClient<'a>,
+    usdc: TokenClient<'a>,
+    xlm: TokenClient<'a>,
+}
+
+fn setup_test(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+
+    let router = MockRouterContractClient::new(env, &env.register(MockRouterContract, ()));
+    let payer = PayerClient::new(env, &env.register(Payer, ()));
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(env));
+    let xlm = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &usdc.address()).mint(&payer.address, &1_000);
+    StellarAssetClient::new(env, &xlm.address()).mint(&router.address, &100_000);
+
+    // 1 USDC = 8 XLM
+    router.set_rate(&usdc.address(), &xlm.address(), &(8 * RATE_SCALE));
+
+    Setup {
+        router,
+        payer,
+        usdc: TokenClient::new(env, &usdc.address()),
+        xlm: TokenClient::new(env, &xlm.address()),
+    }
+}
+
+#[test]
+fn test_swap_exact_in() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    assert_eq!(
+        env.as_contract(&s.payer.address, || quote(
+            &env,
+            &s.router.address,
+            &s.usdc.address,
+            &s.xlm.address,
+            100
+        )),
+        Ok(800)
+    );
+
+    // Only the contract's own authorization is used: nothing is mocked
+    env.set_auths(&[]);
+    assert_eq!(
+        s.payer.swap(
+            &s.router.address,
+            &s.usdc.address,
+            &s.xlm.address,
+            &100,
+            &800
+        ),
+        Some(800)
+    );
+    assert_eq!(s.usdc.balance(&s.payer.address), 900);
+    assert_eq!(s.xlm.balance(&s.payer.address), 800);
+}
+
+#[test]
+fn test_swap_failures() {
+    let env = Env::default();
+    let s = setup_test(&env);
+
+    // Asking for more than the rate gives is rejected by the router
+    assert_eq!(
+        s.payer.swap(
+            &s.router.address,
+            &s.usdc.address,
+            &s.xlm.address,
+            &100,
+            &801
+        ),
+        None
+    );
+    assert_eq!(s.usdc.balance(&s.payer.address), 1_000);
+
+    env.as_contract(&s.payer.address, || {
+        let not_a_router = Address::generate(&env);
+        assert_eq!(
+            swap_exact_in(&env, &not_a_router, &s.usdc.address, &s.xlm.address, 100, 0),
+            Err(DexError::Unavailable)
+        );
+        assert_eq!(
+            swap_exact_in(
+                &env,
+                &s.router.address,
+                &s.usdc.address,
+                &s.xlm.address,
+                0,
+                0
+            ),
+            Err(DexError::InvalidAmount)
+        );
+        assert_eq!(
+            quote(
+                &env,
+                &s.router.address,
+                &s.xlm.address,
+                &s.usdc.address,
+                100
+            ),
+            Err(DexError::Unavailable)
+        );
+    });
+}
@@ -0,0 +1,320 @@
+#![no_std]
+//! Client, types and errors of the Lumenpulse escrow contract.
+//!
+//! Contracts that call the escrow contract, and off-chain Rust that talks
+//! to it, depend on this crate instead of the contract itself, so none of
+//! its implementation ends up in their WASM. The escrow contract uses these
+//! same types, so the two cannot drift apart.
+
+use lumenpulse_badges::BadgeGate;
+use lumenpulse_oracle::OracleConfig;
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Env, Vec};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowStatus {
+    Active,
+    Disputed,
+    Released,
+    Refunded,
+    /// Arbiter has ruled; the ruling may be appealed until its deadline
+    Ruled,
+    /// Ruling has been appealed to the admin
+    Appealed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowData {
+    pub id: u64,
+    pub depositor: Address,
+    pub payee: Address,
+    pub arbiter: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Portion of `amount` already paid out to the payee
+    pub released: i128,
+    pub status: EscrowStatus,
+    /// For timed escrows, when the payee may be paid without the depositor
+    pub release_after: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    pub amount: i128,
+    pub approved: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Contribution {
+    pub contributor: Address,
+    pub amount: i128,
+}
+
+/// Bonded arbitration settings. Once configured, arbiters must have
+/// `min_bond` of `bond_token` available to take a dispute, and their rulings
+/// only take effect after `appeal_window` seconds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbitrationConfig {
+    pub bond_token: Address,
+    pub min_bond: i128,
+    pub appeal_window: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ArbiterBond {
+    pub staked: i128,
+    /// Portion of `staked` backing open disputes
+    pub locked: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ruling {
+    pub release_to_payee: bool,
+    pub appeal_deadline: u64,
+    pub appellant: Option<Address>,
+}
+
+/// A basis-point fee with absolute floor and ceiling
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeRate {
+    pub bps: u32,
+    pub min: i128,
+    pub max: i128,
+}
+
+/// Fees withheld from every payout to the payee. The protocol fee goes to
+/// `recipient`, the arbiter fee to the escrow's arbiter.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    pub protocol: FeeRate,
+    pub arbiter: FeeRate,
+    pub recipient: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeQuote {
+    pub protocol_fee: i128,
+    pub arbiter_fee: i128,
+    /// What the payee receives after fees
+    pub net_amount: i128,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EscrowError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    EscrowNotFound = 4,
+    InvalidAmount = 5,
+    InvalidParties = 6,
+    NotActive = 7,
+    NotDisputed = 8,
+    InvalidDeadline = 9,
+    NotTimed = 10,
+    DeadlineNotReached = 11,
+    NotMilestoneEscrow = 12,
+    MilestoneNotFound = 13,
+    MilestoneAlreadyApproved = 14,
+    TooManyMilestones = 15,
+    ExceedsRemaining = 16,
+    HasMilestones = 17,
+    TooManyContributors = 18,
+    ArbitrationNotConfigured = 19,
+    InvalidConfig = 20,
+    InsufficientBond = 21,
+    NotRuled = 22,
+    AppealWindowClosed = 23,
+    AppealWindowOpen = 24,
+    NotAppealed = 25,
+    InvalidFeeConfig = 26,
+    OracleNotSet = 27,
+    PriceUnavailable = 28,
+    StalePrice = 29,
+    InvalidOracleConfig = 30,
+    MissingBadge = 31,
+    BadgeUnavailable = 32,
+    BondsOutstanding = 33,
+    DisputeWindowClosed = 34,
+}
+
+/// Entrypoints of the escrow contract, besides the standard upgrade and
+/// pause entrypoints
+#[contractclient(name = "EscrowClient")]
+pub trait Escrow {
+    /// Initialize the contract with an admin address
+    fn initialize(env: Env, admin: Address) -> Result<(), EscrowError>;
+
+    /// Fund a new escrow for `payee`. The arbiter may settle the escrow either
+    /// way if the parties raise a dispute.
+    fn create_escrow(
+        env: Env,
+        depositor: Address,
+        payee: Address,
+        arbiter: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<u64, EscrowError>;
+
+    /// Fund an escrow that becomes releasable to the payee by anyone via
+    /// `finalize` once `release_after` has passed, unless a dispute has been
+    /// raised before then
+    fn create_timed_escrow(
+        env: Env,
+        depositor: Address,
+        payee: Address,
+        arbiter: Address,
+        token: Address,
+        amount: i128,
+        release_after: u64,
+    ) -> Result<u64, EscrowError>;
+
+    /// Fund an escrow split into milestones. Each milestone is paid to the
+    /// payee as the depositor approves it; the escrow total is their sum.
+    fn create_milestone_escrow(
+        env: Env,
+        depositor: Address,
+        payee: Address,
+        arbiter: Address,
+        token: Address,
+        amounts: Vec<i128>,
+    ) -> Result<u64, EscrowError>;
+
+    /// Approve a milestone and pay its amount to the payee (depositor only).
+    /// The escrow is released once every milestone has been approved.
+    fn approve_milestone(env: Env, escrow_id: u64, milestone: u32) -> Result<(), EscrowError>;
+
+    /// Release the escrowed funds to the payee (depositor only)
+    fn release(env: Env, escrow_id: u64) -> Result<(), EscrowError>;
+
+    /// Add funds to an active escrow. Each contributor's share is tracked so
+    /// that a refund returns contributions proportionally. Milestone
+    /// escrows have a fixed total and cannot take contributions.
+    fn contribute(
+        env: Env,
+        contributor: Address,
+        escrow_id: u64,
+        amount: i128,
+    ) -> Result<(), EscrowError>;
+
+    /// Release part of the escrow to the payee, keeping the rest escrowed
+    /// (depositor only). Releasing the full remainder closes the escrow.
+    /// Milestone escrows are paid through `approve_milestone` instead.
+    fn release_partial(env: Env, escrow_id: u64, amount: i128) -> Result<(), EscrowError>;
+
+    /// Return the escrowed funds to the depositor (payee only)
+    fn refund(env: Env, escrow_id: u64) -> Result<(), EscrowError>;
+
+    /// Raise a dispute, freezing the escrow until the arbiter rules
+    /// (depositor or payee only). A timed escrow can only be disputed
+    /// before `release_after`.
+    fn dispute(env: Env, caller: Address, escrow_id: u64) -> Result<(), EscrowError>;
+
+    /// Settle a disputed escrow in favour of the payee or the depositor
+    /// (arbiter only). Bonded disputes record the ruling instead, which takes
+    /// effect through `execute_ruling` once the appeal window has passed.
+    /// While an arbiter gate is set, the arbiter must hold its badge.
+    fn resolve(env: Env, escrow_id: u64, release_to_payee: bool) -> Result<(), EscrowError>;
+
+    /// Configure bonded arbitration (admin only). Applies to disputes raised
+    /// from now on. The bond token cannot change while any bond is staked,
+    /// which is held in the old token.
+    fn configure_arbitration(
+        env: Env,
+        admin: Address,
+        bond_token: Address,
+        min_bond: i128,
+        appeal_window: u64,
+    ) -> Result<(), EscrowError>;
+
+    /// Stake bond so the arbiter can take disputes
+    fn stake_bond(env: Env, arbiter: Address, amount: i128) -> Result<(), EscrowError>;
+
+    /// Withdraw bond that is not backing an open dispute
+    fn unstake_bond(env: Env, arbiter: Address, amount: i128) -> Result<(), EscrowError>;
+
+    /// Appeal a bonded ruling to the admin before its deadline
+    /// (depositor or payee only)
+    fn appeal(env: Env, caller: Address, escrow_id: u64) -> Result<(), EscrowError>;
+
+    /// Carry out an unappealed ruling, releasing the arbiter's bond. Anyone
+    /// may call this once the appeal window has passed; funds only go to
+    /// the ruled party.
+    fn execute_ruling(env: Env, escrow_id: u64) -> Result<(), EscrowError>;
+
+    /// Decide an appealed ruling (admin only). Overturning the ruling slashes
+    /// the arbiter's locked bond to the appellant; upholding releases it.
+    fn decide_appeal(
+        env: Env,
+        admin: Address,
+        escrow_id: u64,
+        release_to_payee: bool,
+    ) -> Result<(), EscrowError>;
+
+    /// Set the protocol and arbiter fees withheld from payouts (admin only)
+    fn set_fee_config(env: Env, admin: Address, config: FeeConfig) -> Result<(), EscrowError>;
+
+    /// Get the fee configuration, if any
+    fn get_fee_config(env: Env) -> Option<FeeConfig>;
+
+    /// Fees that would be withheld from a payout of `amount` to the payee
+    fn quote_fees(env: Env, amount: i128) -> FeeQuote;
+
+    /// Only let arbiters holding `gate`'s badge rule on disputes, or drop
+    /// the requirement with `None` (admin only)
+    fn set_arbiter_gate(
+        env: Env,
+        admin: Address,
+        gate: Option<BadgeGate>,
+    ) -> Result<(), EscrowError>;
+
+    /// Badge arbiters must hold to rule, if any
+    fn get_arbiter_gate(env: Env) -> Option<BadgeGate>;
+
+    /// Set the SEP-40 price feeds used for price conversions on this
+    /// network and the oldest price they may give (admin only)
+    fn set_oracle(env: Env, admin: Address, config: OracleConfig) -> Result<(), EscrowError>;
+
+    /// Price feeds used for price conversions, if any
+    fn get_oracle(env: Env) -> Option<OracleConfig>;
+
+    /// Value of `amount` of `token` in the feeds' base asset, at the latest
+    /// fresh price. Falls back through the configured feeds.
+    fn quote_value(env: Env, token: Address, amount: i128) -> Result<i128, EscrowError>;
+
+    /// Get an arbiter's staked and locked bond
+    fn get_arbiter_bond(env: Env, arbiter: Address) -> ArbiterBond;
+
+    /// Get the pending ruling on a bonded dispute
+    fn get_ruling(env: Env, escrow_id: u64) -> Result<Ruling, EscrowError>;
+
+    /// Release a timed escrow to the payee once its deadline has passed.
+    /// Needs no authorization, as the deadline was agreed at creation;
+    /// disputed escrows are left to the arbiter.
+    fn finalize(env: Env, escrow_id: u64) -> Result<(), EscrowError>;
+
+    /// Get escrow data
+    fn get_escrow(env: Env, escrow_id: u64) -> Result<EscrowData, EscrowError>;
+
+    /// Get the amount still held in escrow
+    fn remaining_amount(env: Env, escrow_id: u64) -> Result<i128, EscrowError>;
+
+    /// Get each contributor's share of an escrow
+    fn get_contributions(env: Env, escrow_id: u64) -> Result<Vec<Contribution>, EscrowError>;
+
+    /// Get the milestones of a milestone escrow
+    fn get_milestones(env: Env, escrow_id: u64) -> Result<Vec<Milestone>, EscrowError>;
+
+    /// Get admin address
+    fn get_admin(env: Env) -> Result<Address, EscrowError>;
+}
@@ -0,0 +1,105 @@
+#![no_std]
+//! Fixed-point and integer math shared by Lumenpulse contracts.
+//!
+//! Every operation is checked: it returns `None` on overflow or division
+//! by zero rather than wrapping or panicking, and the caller decides what
+//! that means. Operations that divide take a `Rounding` so each call site
+//! states which side of the exact value it rounds to. Products are taken
+//! at 128-bit magnitude, so `a * b` only overflows when it exceeds
+//! `u128::MAX`, not `i128::MAX`.
+
+/// Basis points denominator (100% = 10_000)
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Percent denominator (100% = 100)
+pub const PERCENT_DENOMINATOR: i128 = 100;
+
+/// Which side of the exact quotient to keep
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Rounding {
+    /// Towards negative infinity
+    Down,
+    /// Towards positive infinity
+    Up,
+}
+
+/// `a * b / denominator`, rounded as asked
+pub fn mul_div(a: i128, b: i128, denominator: i128, rounding: Rounding) -> Option<i128> {
+    if denominator == 0 {
+        return None;
+    }
+    let negative = (a < 0) ^ (b < 0) ^ (denominator < 0);
+    let product = a.unsigned_abs().checked_mul(b.unsigned_abs())?;
+    let divisor = denominator.unsigned_abs();
+    let mut quotient = product / divisor;
+    let inexact = product % divisor != 0;
+
+    // The magnitude was truncated, which rounds towards zero: step away
+    // from zero when that is the direction asked for
+    if inexact && ((rounding == Rounding::Up) != negative) {
+        quotient += 1;
+    }
+    if negative {
+        0i128.checked_sub_unsigned(quotient)
+    } else {
+        i128::try_from(quotient).ok()
+    }
+}
+
+/// `bps` basis points of `amount`
+pub fn bps(amount: i128, bps: u32, rounding: Rounding) -> Option<i128> {
+    mul_div(amount, bps as i128, BPS_DENOMINATOR, rounding)
+}
+
+/// `percent` percent of `amount`
+pub fn percent(amount: i128, percent: u32, rounding: Rounding) -> Option<i128> {
+    mul_div(amount, percent as i128, PERCENT_DENOMINATOR, rounding)
+}
+
+/// Share of `amount` for `part` out of `whole`, e.g. seconds elapsed out
+/// of a period
+pub fn prorate(amount: i128, part: u64, whole: u64, rounding: Rounding) -> Option<i128> {
+    mul_div(amount, part as i128, whole as i128, rounding)
+}
+
+/// `base` raised to `exponent`, where `base` and the result are fixed-point
+/// values with `scale` as one. Every intermediate product is rounded as
+/// asked, so results can drift by a unit per multiplication.
+pub fn pow(base: i128, exponent: u32, scale: i128, rounding: Rounding) -> Option<i128> {
+    if scale <= 0 {
+        return None;
+    }
+    let mut result = scale;
+    let mut square = base;
+    let mut remaining = exponent;
+    while remaining > 0 {
+        if remaining & 1 == 1 {
+            result = mul_div(result, square, scale, rounding)?;
+        }
+        remaining >>= 1;
+        if remaining > 0 {
+            square = mul_div(square, square, scale, rounding)?;
+        }
+    }
+    Some(result)
+}
+
+/// Largest integer whose square does not exceed `value`, or 0 for values
+/// below 1
+pub fn sqrt(value: i128) -> i128 {
+    if value <= 0 {
+        return 0;
+    }
+
+    // Newton's method from an initial guess at or above the root
+    let mut x = value;
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,44 @@
+use crate::{bps, mul_div, percent, pow, prorate, sqrt, Rounding};
+
+#[test]
+fn test_mul_div_rounding() {
+    assert_eq!(mul_div(7, 3, 2, Rounding::Down), Some(10));
+    assert_eq!(mul_div(7, 3, 2, Rounding::Up), Some(11));
+    assert_eq!(mul_div(8, 3, 2, Rounding::Up), Some(12));
+
+    // Down and up are floor and ceiling on both sides of zero
+    assert_eq!(mul_div(-7, 3, 2, Rounding::Down), Some(-11));
+    assert_eq!(mul_div(-7, 3, 2, Rounding::Up), Some(-10));
+    assert_eq!(mul_div(7, 3, -2, Rounding::Down), Some(-11));
+
+    // Products past i128 still divide back into range
+    assert_eq!(
+        mul_div(i128::MAX, 2, 4, Rounding::Down),
+        Some(i128::MAX / 2)
+    );
+    assert_eq!(mul_div(i128::MAX, 4, 2, Rounding::Down), None);
+    assert_eq!(mul_div(1, 1, 0, Rounding::Down), None);
+    assert_eq!(mul_div(i128::MIN, 1, 1, Rounding::Down), Some(i128::MIN));
+}
+
+#[test]
+fn test_helpers() {
+    assert_eq!(bps(10_001, 250, Rounding::Down), Some(250));
+    assert_eq!(bps(10_001, 250, Rounding::Up), Some(251));
+    assert_eq!(percent(999, 10, Rounding::Down), Some(99));
+    assert_eq!(prorate(300, 1, 3, Rounding::Up), Some(100));
+    assert_eq!(prorate(100, 1, 3, Rounding::Up), Some(34));
+
+    // 1.5^3 = 3.375 at a scale of 1_000
+    assert_eq!(pow(1_500, 3, 1_000, Rounding::Down), Some(3_375));
+    assert_eq!(pow(1_500, 0, 1_000, Rounding::Down), Some(1_000));
+    assert_eq!(pow(500, 10, 1_000_000, Rounding::Down), Some(0));
+    assert_eq!(pow(i128::MAX, 2, 1, Rounding::Down), None);
+
+    assert_eq!(sqrt(0), 0);
+    assert_eq!(sqrt(1), 1);
+    assert_eq!(sqrt(2), 1);
+    assert_eq!(sqrt(99), 9);
+    assert_eq!(sqrt(100), 10);
+    assert_eq!(sqrt(i128::MAX), 13_043_817_825_332_782_212);
+}
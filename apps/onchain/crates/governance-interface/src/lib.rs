@@ -0,0 +1,274 @@
+#![no_std]
+//! Client, types and errors of the Lumenpulse governance contract.
+//!
+//! Contracts that call the governance contract, and off-chain Rust that
+//! talks to it, depend on this crate instead of the contract itself, so
+//! none of its implementation ends up in their WASM. The governance
+//! contract uses these same types, so the two cannot drift apart.
+
+use soroban_sdk::{
+    contractclient, contracterror, contracttype, Address, BytesN, Env, String, Symbol, Val, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceConfig {
+    /// Seconds a proposal stays open for voting
+    pub voting_period: u64,
+    /// Minimum for + abstain votes for a proposal to be valid
+    pub quorum: i128,
+    /// Share of for votes among for + against needed to pass, in basis points
+    pub pass_threshold_bps: u32,
+    /// Voting power needed to create a proposal
+    pub proposal_threshold: i128,
+    /// Seconds a queued proposal waits before execution, unless one of its
+    /// actions calls a function with its own delay
+    pub execution_delay: u64,
+    /// Seconds after queueing during which the security council may veto,
+    /// at most the execution delay
+    pub veto_window: u64,
+    /// Seconds a vetoed set of actions is barred from being proposed again
+    pub resubmit_cooldown: u64,
+}
+
+/// A cross-contract call the proposal asks to make
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalAction {
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// How a voter's power turns into tallied weight
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VotingMode {
+    /// One token, one vote
+    Standard,
+    /// Weight is the square root of voting power; badge holders only
+    Quadratic,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VoteType {
+    For,
+    Against,
+    Abstain,
+}
+
+/// Payload a voter signs off-chain. Naming the governance contract keeps a
+/// signature from being replayed against another deployment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteMessage {
+    pub governance: Address,
+    pub proposal_id: u64,
+    pub support: VoteType,
+}
+
+/// A vote relayed on the voter's behalf, signed with its registered key
+/// over the XDR encoding of the matching `VoteMessage`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedVote {
+    pub voter: Address,
+    pub proposal_id: u64,
+    pub support: VoteType,
+    pub signature: BytesN<64>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProposalState {
+    Active,
+    Defeated,
+    Succeeded,
+    Queued,
+    Vetoed,
+    Canceled,
+    Executed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub description: String,
+    pub actions: Vec<ProposalAction>,
+    pub mode: VotingMode,
+    /// Ledger whose closing voting power is used for every vote
+    pub snapshot_ledger: u32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub for_votes: i128,
+    pub against_votes: i128,
+    pub abstain_votes: i128,
+    /// Raw voting power behind for and abstain votes, measured against
+    /// quorum in every mode
+    pub participation: i128,
+    /// When the proposal entered the timelock
+    pub queued_at: Option<u64>,
+    /// Earliest execution time, set once the proposal is queued
+    pub eta: Option<u64>,
+    pub canceled: bool,
+    pub executed: bool,
+}
+
+/// On-chain record of a security council veto
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VetoRecord {
+    pub council: Address,
+    pub vetoed_at: u64,
+    /// The same actions may not be proposed again before this time
+    pub resubmit_after: u64,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernanceError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    ProposalNotFound = 4,
+    InvalidConfig = 5,
+    NoActions = 6,
+    TooManyActions = 7,
+    BelowProposalThreshold = 8,
+    VotingClosed = 9,
+    AlreadyVoted = 10,
+    NoVotingPower = 11,
+    InvalidState = 12,
+    TimelockNotElapsed = 13,
+    BadgeNotConfigured = 14,
+    MissingBadge = 15,
+    CouncilNotConfigured = 16,
+    VoteKeyNotRegistered = 17,
+    InvalidBatch = 18,
+    VetoWindowClosed = 19,
+    ResubmissionCooldown = 20,
+}
+
+/// Entrypoints of the governance contract
+#[contractclient(name = "GovernanceClient")]
+pub trait Governance {
+    /// Initialize governance over holders of `token`
+    fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        config: GovernanceConfig,
+    ) -> Result<(), GovernanceError>;
+
+    /// Create a proposal to make `actions`. Voting opens immediately and
+    /// runs for the configured voting period. Voting power is fixed at the
+    /// last closed ledger, so tokens acquired afterwards carry no weight.
+    /// Quadratic proposals need a badge contract to gate who may vote.
+    fn propose(
+        env: Env,
+        proposer: Address,
+        description: String,
+        actions: Vec<ProposalAction>,
+        mode: VotingMode,
+    ) -> Result<u64, GovernanceError>;
+
+    /// Vote on an active proposal with the voter's full voting power as of
+    /// the proposal's snapshot ledger. Returns the weight tallied, which on
+    /// quadratic proposals is the square root of that power.
+    fn cast_vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        support: VoteType,
+    ) -> Result<i128, GovernanceError>;
+
+    /// Register the ed25519 key `voter` signs off-chain votes with
+    fn register_vote_key(env: Env, voter: Address, public_key: BytesN<32>);
+
+    /// Get the key `voter` signs off-chain votes with, if registered
+    fn get_vote_key(env: Env, voter: Address) -> Option<BytesN<32>>;
+
+    /// Tally a batch of off-chain signed votes, so a relayer can pay for
+    /// many small holders at once. Every signature must verify against
+    /// the voter's registered key; one bad entry rejects the whole batch.
+    fn cast_votes_by_sig(env: Env, votes: Vec<SignedVote>) -> Result<u32, GovernanceError>;
+
+    /// Withdraw a proposal while it is still being voted on (proposer only)
+    fn cancel(env: Env, proposal_id: u64) -> Result<(), GovernanceError>;
+
+    /// Move a passed proposal into the timelock. It becomes executable
+    /// after the longest delay among its actions. Until it executes the
+    /// security council may cancel it, and within the veto window veto it.
+    fn queue(env: Env, proposal_id: u64) -> Result<u64, GovernanceError>;
+
+    /// Perform a queued proposal's calls once its timelock has elapsed.
+    /// Anyone may trigger this; the calls run with governance as the
+    /// invoker, so targets that name governance as admin accept them.
+    fn execute(env: Env, proposal_id: u64) -> Result<(), GovernanceError>;
+
+    /// Stop a queued proposal at any point before it executes (security
+    /// council only). Unlike a veto nothing is recorded against its
+    /// actions, which may be proposed again at once.
+    fn cancel_queued(env: Env, council: Address, proposal_id: u64) -> Result<(), GovernanceError>;
+
+    /// Veto a queued proposal within the veto window (security council
+    /// only). The veto is recorded, and the same actions cannot be proposed
+    /// again until the resubmission cooldown has passed.
+    fn veto(env: Env, council: Address, proposal_id: u64) -> Result<(), GovernanceError>;
+
+    /// Get the council veto recorded against a proposal, if any
+    fn get_veto(env: Env, proposal_id: u64) -> Option<VetoRecord>;
+
+    /// Current lifecycle state of a proposal
+    fn state(env: Env, proposal_id: u64) -> Result<ProposalState, GovernanceError>;
+
+    /// Set the soulbound badge contract that gates quadratic voting
+    fn set_badge(env: Env, admin: Address, badge: Option<Address>) -> Result<(), GovernanceError>;
+
+    /// Get the badge contract gating quadratic voting, if any
+    fn get_badge(env: Env) -> Option<Address>;
+
+    /// Set the security council allowed to cancel and veto queued
+    /// proposals
+    fn set_security_council(
+        env: Env,
+        admin: Address,
+        council: Option<Address>,
+    ) -> Result<(), GovernanceError>;
+
+    /// Get the security council, if any
+    fn get_security_council(env: Env) -> Option<Address>;
+
+    /// Override the timelock for actions calling `function`, e.g. a longer
+    /// wait for `upgrade` or a shorter one for `set_fee`. `None` falls back
+    /// to the configured execution delay. An override may not be shorter
+    /// than the veto window, so the council always has time to veto.
+    fn set_action_delay(
+        env: Env,
+        admin: Address,
+        function: Symbol,
+        delay: Option<u64>,
+    ) -> Result<(), GovernanceError>;
+
+    /// Timelock applied to actions calling `function`
+    fn get_action_delay(env: Env, function: Symbol) -> Result<u64, GovernanceError>;
+
+    /// Get proposal data
+    fn get_proposal(env: Env, proposal_id: u64) -> Result<Proposal, GovernanceError>;
+
+    /// Get how `voter` voted on a proposal, if at all
+    fn get_vote(env: Env, proposal_id: u64, voter: Address) -> Option<VoteType>;
+
+    /// Get governance parameters
+    fn get_config(env: Env) -> Result<GovernanceConfig, GovernanceError>;
+
+    /// Get the governance token
+    fn get_token(env: Env) -> Result<Address, GovernanceError>;
+
+    /// Get admin address
+    fn get_admin(env: Env) -> Result<Address, GovernanceError>;
+}
@@ -0,0 +1,247 @@
+#![no_std]
+//! Consumer side of the SEP-40 price feed interface.
+//!
+//! Each contract stores the oracle address it trusts for its network and
+//! reads prices through these helpers, which turn a missing or failing feed
+//! into a typed error instead of a trap. Decisions that a single trade
+//! could skew, such as fee conversion, should use `twap` rather than the
+//! spot price.
+
+use lumenpulse_fixed::{mul_div, Rounding};
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol, Vec};
+
+/// Upper bound on the price records one `twap` reads
+pub const MAX_TWAP_RECORDS: u32 = 100;
+
+/// Upper bound on the feeds in an `OracleConfig`
+pub const MAX_FEEDS: u32 = 5;
+
+/// Asset a SEP-40 feed quotes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    /// Stellar asset, by its contract address
+    Stellar(Address),
+    /// Off-chain asset, by ticker
+    Other(Symbol),
+}
+
+/// One price record, scaled by the feed's `decimals`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Which feeds a contract trusts and how old a price it accepts
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleConfig {
+    /// Primary feed first, then fallbacks in order of preference
+    pub feeds: Vec<Address>,
+    /// Oldest price, in seconds, that may be used
+    pub max_age: u64,
+}
+
+impl OracleConfig {
+    /// Whether the config names between one and `MAX_FEEDS` feeds
+    pub fn is_valid(&self) -> bool {
+        !self.feeds.is_empty() && self.feeds.len() <= MAX_FEEDS
+    }
+}
+
+/// A price together with the feed it came from and that feed's scale
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeedPrice {
+    pub oracle: Address,
+    pub price: i128,
+    pub timestamp: u64,
+    pub decimals: u32,
+}
+
+/// The SEP-40 price feed interface
+#[contractclient(name = "PriceFeedClient")]
+pub trait PriceFeed {
+    /// Asset every price is quoted in
+    fn base(env: Env) -> Asset;
+    /// Assets the feed quotes
+    fn assets(env: Env) -> Vec<Asset>;
+    /// Number of decimals prices are scaled by
+    fn decimals(env: Env) -> u32;
+    /// Seconds between price records
+    fn resolution(env: Env) -> u32;
+    /// Price recorded for `asset` at `timestamp`
+    fn price(env: Env, asset: Asset, timestamp: u64) -> Option<PriceData>;
+    /// Up to `records` most recent prices for `asset`, newest first
+    fn prices(env: Env, asset: Asset, records: u32) -> Option<Vec<PriceData>>;
+    /// Most recent price for `asset`
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OracleError {
+    /// The feed could not be called or returned something malformed
+    Unavailable,
+    /// The feed has no price for the asset
+    NoPrice,
+    /// Price was zero or negative
+    InvalidPrice,
+    /// Conversion overflowed
+    Overflow,
+    /// The feed's records do not cover the whole averaging window
+    InsufficientHistory,
+    /// No configured feed has a recent enough price
+    StalePrice,
+}
+
+/// Most recent price for `asset` from `oracle`
+pub fn lastprice(env: &Env, oracle: &Address, asset: &Asset) -> Result<PriceData, OracleError> {
+    match PriceFeedClient::new(env, oracle).try_lastprice(asset) {
+        Ok(Ok(price)) => checked(price),
+        _ => Err(OracleError::Unavailable),
+    }
+}
+
+/// Latest price for `asset` from the first feed in `config` that has one no
+/// older than `config.max_age`. Feeds that are stale, empty or failing are
+/// skipped in favour of the next.
+pub fn fresh_price(
+    env: &Env,
+    config: &OracleConfig,
+    asset: &Asset,
+) -> Result<FeedPrice, OracleError> {
+    let now = env.ledger().timestamp();
+    for oracle in config.feeds.iter() {
+        let Ok(price) = lastprice(env, &oracle, asset) else {
+            continue;
+        };
+        if now.saturating_sub(price.timestamp) > config.max_age {
+            continue;
+        }
+        let Ok(decimals) = decimals(env, &oracle) else {
+            continue;
+        };
+        return Ok(FeedPrice {
+            oracle,
+            price: price.price,
+            timestamp: price.timestamp,
+            decimals,
+        });
+    }
+    Err(OracleError::StalePrice)
+}
+
+/// Price `oracle` recorded for `asset` at `timestamp`
+pub fn price(
+    env: &Env,
+    oracle: &Address,
+    asset: &Asset,
+    timestamp: u64,
+) -> Result<PriceData, OracleError> {
+    match PriceFeedClient::new(env, oracle).try_price(asset, &timestamp) {
+        Ok(Ok(price)) => checked(price),
+        _ => Err(OracleError::Unavailable),
+    }
+}
+
+/// Number of decimals `oracle` scales its prices by
+pub fn decimals(env: &Env, oracle: &Address) -> Result<u32, OracleError> {
+    match PriceFeedClient::new(env, oracle).try_decimals() {
+        Ok(Ok(decimals)) => Ok(decimals),
+        _ => Err(OracleError::Unavailable),
+    }
+}
+
+/// Time-weighted average price of `asset` over the `window` seconds up to
+/// now, from the feed's recent records
+pub fn twap(env: &Env, oracle: &Address, asset: &Asset, window: u64) -> Result<i128, OracleError> {
+    let feed = PriceFeedClient::new(env, oracle);
+    let resolution = match feed.try_resolution() {
+        Ok(Ok(resolution)) if resolution > 0 => resolution as u64,
+        _ => return Err(OracleError::Unavailable),
+    };
+    // One record per resolution step, plus the one in force when the
+    // window opened
+    let records = (window / resolution + 1).min(MAX_TWAP_RECORDS as u64) as u32;
+    let history = match feed.try_prices(asset, &records) {
+        Ok(Ok(Some(history))) => history,
+        Ok(Ok(None)) => return Err(OracleError::NoPrice),
+        _ => return Err(OracleError::Unavailable),
+    };
+    time_weighted_average(&history, env.ledger().timestamp(), window)
+}
+
+/// Average of `records` (newest first) over the `window` seconds up to
+/// `now`, each price weighted by how long it was in force. Rounds down.
+pub fn time_weighted_average(
+    records: &Vec<PriceData>,
+    now: u64,
+    window: u64,
+) -> Result<i128, OracleError> {
+    if window == 0 {
+        return Err(OracleError::InsufficientHistory);
+    }
+    let start = now.saturating_sub(window);
+
+    let mut weighted: i128 = 0;
+    let mut covered: u64 = 0;
+    // Each record is in force until the next newer one, the newest until now
+    let mut until = now;
+    for record in records.iter() {
+        if record.timestamp > until {
+            continue;
+        }
+        if record.price <= 0 {
+            return Err(OracleError::InvalidPrice);
+        }
+        let from = record.timestamp.max(start);
+        let duration = until - from;
+        weighted = (duration as i128)
+            .checked_mul(record.price)
+            .and_then(|part| weighted.checked_add(part))
+            .ok_or(OracleError::Overflow)?;
+        covered += duration;
+        until = from;
+        if from == start {
+            break;
+        }
+    }
+
+    if covered < window {
+        return Err(OracleError::InsufficientHistory);
+    }
+    mul_div(weighted, 1, covered as i128, Rounding::Down).ok_or(OracleError::Overflow)
+}
+
+/// Value of `amount` units of an asset priced at `price`, in units of the
+/// feed's base asset. Rounds down.
+pub fn to_base(amount: i128, price: i128, decimals: u32) -> Result<i128, OracleError> {
+    let scale = scale(decimals)?;
+    mul_div(amount, price, scale, Rounding::Down).ok_or(OracleError::Overflow)
+}
+
+/// Units of an asset priced at `price` worth `value` in the feed's base
+/// asset. Rounds down.
+pub fn from_base(value: i128, price: i128, decimals: u32) -> Result<i128, OracleError> {
+    if price <= 0 {
+        return Err(OracleError::InvalidPrice);
+    }
+    let scale = scale(decimals)?;
+    mul_div(value, scale, price, Rounding::Down).ok_or(OracleError::Overflow)
+}
+
+fn scale(decimals: u32) -> Result<i128, OracleError> {
+    10i128.checked_pow(decimals).ok_or(OracleError::Overflow)
+}
+
+fn checked(price: Option<PriceData>) -> Result<PriceData, OracleError> {
+    let price = price.ok_or(OracleError::NoPrice)?;
+    if price.price <= 0 {
+        return Err(OracleError::InvalidPrice);
+    }
+    Ok(price)
+}
+
+#[cfg(test)]
+mod test;
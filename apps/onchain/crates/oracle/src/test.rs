@@ -0,0 +1,218 @@
+use crate::{
+    decimals, fresh_price, from_base, lastprice, price, time_weighted_average, to_base, twap,
+    Asset, OracleConfig, OracleError, PriceData,
+};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, Vec,
+};
+
+#[contracttype]
+enum DataKey {
+    Price(Asset, u64),
+    History(Asset),
+}
+
+/// Feed quoting prices in USD with 7 decimals
+#[contract]
+struct Feed;
+
+#[contractimpl]
+impl Feed {
+    pub fn set(env: Env, asset: Asset, price: i128, timestamp: u64) {
+        let data = PriceData { price, timestamp };
+        env.storage()
+            .instance()
+            .set(&DataKey::Price(asset.clone(), timestamp), &data);
+        let key = DataKey::History(asset);
+        let mut history: Vec<PriceData> =
+            env.storage().instance().get(&key).unwrap_or(Vec::new(&env));
+        history.push_front(data);
+        env.storage().instance().set(&key, &history);
+    }
+
+    pub fn base(_env: Env) -> Asset {
+        Asset::Other(symbol_short!("USD"))
+    }
+
+    pub fn assets(env: Env) -> Vec<Asset> {
+        Vec::new(&env)
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    pub fn resolution(_env: Env) -> u32 {
+        300
+    }
+
+    pub fn price(env: Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Price(asset, timestamp))
+    }
+
+    pub fn prices(env: Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
+        let history: Vec<PriceData> = env.storage().instance().get(&DataKey::History(asset))?;
+        Some(history.slice(0..records.min(history.len())))
+    }
+
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        Self::prices(env, asset, 1)?.first()
+    }
+}
+
+#[test]
+fn test_reads_prices() {
+    let env = Env::default();
+    let oracle = env.register(Feed, ());
+    let client = FeedClient::new(&env, &oracle);
+    let xlm = Asset::Other(symbol_short!("XLM"));
+
+    assert_eq!(lastprice(&env, &oracle, &xlm), Err(OracleError::NoPrice));
+
+    client.set(&xlm, &1_200_000, &300);
+    client.set(&xlm, &1_250_000, &600);
+    assert_eq!(
+        lastprice(&env, &oracle, &xlm),
+        Ok(PriceData {
+            price: 1_250_000,
+            timestamp: 600
+        })
+    );
+    assert_eq!(price(&env, &oracle, &xlm, 300).unwrap().price, 1_200_000);
+    assert_eq!(price(&env, &oracle, &xlm, 900), Err(OracleError::NoPrice));
+    assert_eq!(decimals(&env, &oracle), Ok(7));
+
+    client.set(&xlm, &0, &900);
+    assert_eq!(
+        lastprice(&env, &oracle, &xlm),
+        Err(OracleError::InvalidPrice)
+    );
+
+    // Anything that is not a feed is reported as unavailable
+    let not_a_feed = Address::generate(&env);
+    assert_eq!(
+        lastprice(&env, &not_a_feed, &xlm),
+        Err(OracleError::Unavailable)
+    );
+}
+
+#[test]
+fn test_conversions() {
+    // 1 XLM = 0.125 USD with 7 decimals
+    let price = 1_250_000;
+    assert_eq!(to_base(80_000_000, price, 7), Ok(10_000_000));
+    assert_eq!(from_base(10_000_000, price, 7), Ok(80_000_000));
+
+    // Both directions round down
+    assert_eq!(to_base(7, price, 7), Ok(0));
+    assert_eq!(from_base(1, 3, 0), Ok(0));
+
+    assert_eq!(from_base(1, 0, 7), Err(OracleError::InvalidPrice));
+    assert_eq!(to_base(i128::MAX, price, 7), Err(OracleError::Overflow));
+}
+
+#[test]
+fn test_time_weighted_average() {
+    let env = Env::default();
+    let record = |price, timestamp| PriceData { price, timestamp };
+
+    // 100 for 200s, then 400 for the last 100s
+    let records = vec![&env, record(400, 1_200), record(100, 1_000)];
+    assert_eq!(time_weighted_average(&records, 1_300, 300), Ok(200));
+
+    // Older records are cut at the start of the window
+    let records = vec![&env, record(400, 1_200), record(100, 0)];
+    assert_eq!(time_weighted_average(&records, 1_300, 300), Ok(200));
+
+    // Rounds down
+    let records = vec![&env, record(2, 1_200), record(1, 1_000)];
+    assert_eq!(time_weighted_average(&records, 1_300, 300), Ok(1));
+
+    // Records newer than now are ignored
+    let records = vec![&env, record(9_999, 1_400), record(100, 1_000)];
+    assert_eq!(time_weighted_average(&records, 1_300, 300), Ok(100));
+
+    let records = vec![&env, record(400, 1_200)];
+    assert_eq!(
+        time_weighted_average(&records, 1_300, 300),
+        Err(OracleError::InsufficientHistory)
+    );
+    assert_eq!(
+        time_weighted_average(&records, 1_300, 0),
+        Err(OracleError::InsufficientHistory)
+    );
+}
+
+#[test]
+fn test_twap_from_feed() {
+    let env = Env::default();
+    let oracle = env.register(Feed, ());
+    let client = FeedClient::new(&env, &oracle);
+    let xlm = Asset::Other(symbol_short!("XLM"));
+
+    assert_eq!(twap(&env, &oracle, &xlm, 600), Err(OracleError::NoPrice));
+
+    client.set(&xlm, &1_000, &0);
+    client.set(&xlm, &1_000, &300);
+    client.set(&xlm, &1_600, &600);
+    client.set(&xlm, &10_000, &900);
+    env.ledger().set_timestamp(900);
+
+    // A spike in the latest record has not been in force yet
+    assert_eq!(twap(&env, &oracle, &xlm, 600), Ok(1_300));
+    env.ledger().set_timestamp(1_050);
+    assert_eq!(twap(&env, &oracle, &xlm, 600), Ok(3_550));
+
+    assert_eq!(
+        twap(&env, &oracle, &xlm, 3_000),
+        Err(OracleError::InsufficientHistory)
+    );
+}
+
+#[test]
+fn test_fresh_price_falls_back() {
+    let env = Env::default();
+    let primary = env.register(Feed, ());
+    let fallback = env.register(Feed, ());
+    let xlm = Asset::Other(symbol_short!("XLM"));
+    let config = OracleConfig {
+        feeds: vec![
+            &env,
+            primary.clone(),
+            Address::generate(&env),
+            fallback.clone(),
+        ],
+        max_age: 600,
+    };
+
+    FeedClient::new(&env, &primary).set(&xlm, &1_000, &1_000);
+    FeedClient::new(&env, &fallback).set(&xlm, &1_100, &1_500);
+
+    env.ledger().set_timestamp(1_600);
+    let quote = fresh_price(&env, &config, &xlm).unwrap();
+    assert_eq!(quote.oracle, primary);
+    assert_eq!((quote.price, quote.decimals), (1_000, 7));
+
+    // The primary goes stale and the failing feed is skipped
+    env.ledger().set_timestamp(1_601);
+    let quote = fresh_price(&env, &config, &xlm).unwrap();
+    assert_eq!(quote.oracle, fallback);
+    assert_eq!(quote.price, 1_100);
+
+    env.ledger().set_timestamp(2_101);
+    assert_eq!(
+        fresh_price(&env, &config, &xlm),
+        Err(OracleError::StalePrice)
+    );
+
+    assert!(config.is_valid());
+    let empty = OracleConfig {
+        feeds: Vec::new(&env),
+        max_age: 600,
+    };
+    assert!(!empty.is_valid());
+}
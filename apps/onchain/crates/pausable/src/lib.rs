@@ -0,0 +1,228 @@
+#![no_std]
+//! Circuit breaker for Lumenpulse contracts that hold funds.
+//!
+//! A contract implements `Pausable` to name who manages its breaker, then
+//! invokes `pausable!(Contract)` once to gain the standard entrypoints:
+//!
+//! - `pause` stops every guarded entrypoint, and can be called by the
+//!   pause admin or any pauser
+//! - `unpause` resumes them, and is left to the pause admin alone
+//! - `grant_pauser` and `revoke_pauser` let the pause admin hand the
+//!   ability to pause to accounts that watch the contract, so an incident
+//!   does not have to wait for the admin key
+//! - `paused` and `is_pauser` report where the contract stands
+//!
+//! Entrypoints that move funds call `when_not_paused` first. It fails
+//! with the same `PauseError::Paused` code in every contract.
+
+use soroban_sdk::{contracterror, contractevent, contracttype, panic_with_error, Address, Env};
+
+/// How a contract's circuit breaker is governed
+pub trait Pausable {
+    /// Address allowed to pause, unpause and manage pausers, if the
+    /// contract has been set up
+    fn pause_admin(env: &Env) -> Option<Address>;
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum PauseKey {
+    PauseState,      // -> bool
+    Pauser(Address), // account -> bool
+}
+
+/// Codes start at 1100 so they never overlap a contract's own errors or
+/// the upgrade errors
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PauseError {
+    /// The caller may not pause or unpause
+    Unauthorized = 1100,
+    Paused = 1101,
+    NotPaused = 1102,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PausedEvent {
+    #[topic]
+    pub by: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnpausedEvent {
+    #[topic]
+    pub by: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauserGrantedEvent {
+    #[topic]
+    pub account: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauserRevokedEvent {
+    #[topic]
+    pub account: Address,
+}
+
+/// Stop the guarded entrypoints (pause admin or pauser)
+pub fn pause<T: Pausable>(env: &Env, caller: &Address) -> Result<(), PauseError> {
+    if !is_pauser(env, caller) && T::pause_admin(env).as_ref() != Some(caller) {
+        return Err(PauseError::Unauthorized);
+    }
+    caller.require_auth();
+
+    if is_paused(env) {
+        return Err(PauseError::Paused);
+    }
+    env.storage().instance().set(&PauseKey::PauseState, &true);
+
+    PausedEvent { by: caller.clone() }.publish(env);
+    Ok(())
+}
+
+/// Resume the guarded entrypoints (pause admin only)
+pub fn unpause<T: Pausable>(env: &Env, caller: &Address) -> Result<(), PauseError> {
+    require_pause_admin::<T>(env, caller)?;
+
+    if !is_paused(env) {
+        return Err(PauseError::NotPaused);
+    }
+    env.storage().instance().remove(&PauseKey::PauseState);
+
+    UnpausedEvent { by: caller.clone() }.publish(env);
+    Ok(())
+}
+
+/// Let `account` pause the contract (pause admin only)
+pub fn grant_pauser<T: Pausable>(
+    env: &Env,
+    caller: &Address,
+    account: &Address,
+) -> Result<(), PauseError> {
+    require_pause_admin::<T>(env, caller)?;
+
+    if !is_pauser(env, account) {
+        env.storage()
+            .persistent()
+            .set(&PauseKey::Pauser(account.clone()), &true);
+        PauserGrantedEvent {
+            account: account.clone(),
+        }
+        .publish(env);
+    }
+    Ok(())
+}
+
+/// Take the ability to pause away from `account` (pause admin only)
+pub fn revoke_pauser<T: Pausable>(
+    env: &Env,
+    caller: &Address,
+    account: &Address,
+) -> Result<(), PauseError> {
+    require_pause_admin::<T>(env, caller)?;
+
+    if is_pauser(env, account) {
+        env.storage()
+            .persistent()
+            .remove(&PauseKey::Pauser(account.clone()));
+        PauserRevokedEvent {
+            account: account.clone(),
+        }
+        .publish(env);
+    }
+    Ok(())
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&PauseKey::PauseState)
+        .unwrap_or(false)
+}
+
+pub fn is_pauser(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&PauseKey::Pauser(account.clone()))
+        .unwrap_or(false)
+}
+
+/// Fail with `PauseError::Paused` while the contract is paused
+pub fn when_not_paused(env: &Env) {
+    if is_paused(env) {
+        panic_with_error!(env, PauseError::Paused);
+    }
+}
+
+fn require_pause_admin<T: Pausable>(env: &Env, caller: &Address) -> Result<(), PauseError> {
+    if T::pause_admin(env).as_ref() != Some(caller) {
+        return Err(PauseError::Unauthorized);
+    }
+    caller.require_auth();
+    Ok(())
+}
+
+/// Add the standard circuit breaker entrypoints to a contract
+/// implementing `Pausable`
+#[macro_export]
+macro_rules! pausable {
+    ($contract:ident) => {
+        #[soroban_sdk::contractimpl]
+        impl $contract {
+            /// Stop the guarded entrypoints (pause admin or pauser)
+            pub fn pause(
+                env: soroban_sdk::Env,
+                caller: soroban_sdk::Address,
+            ) -> Result<(), $crate::PauseError> {
+                $crate::pause::<$contract>(&env, &caller)
+            }
+
+            /// Resume the guarded entrypoints (pause admin only)
+            pub fn unpause(
+                env: soroban_sdk::Env,
+                caller: soroban_sdk::Address,
+            ) -> Result<(), $crate::PauseError> {
+                $crate::unpause::<$contract>(&env, &caller)
+            }
+
+            /// Let `account` pause the contract (pause admin only)
+            pub fn grant_pauser(
+                env: soroban_sdk::Env,
+                caller: soroban_sdk::Address,
+                account: soroban_sdk::Address,
+            ) -> Result<(), $crate::PauseError> {
+                $crate::grant_pauser::<$contract>(&env, &caller, &account)
+            }
+
+            /// Take the ability to pause away from `account` (pause admin
+            /// only)
+            pub fn revoke_pauser(
+                env: soroban_sdk::Env,
+                caller: soroban_sdk::Address,
+                account: soroban_sdk::Address,
+            ) -> Result<(), $crate::PauseError> {
+                $crate::revoke_pauser::<$contract>(&env, &caller, &account)
+            }
+
+            /// Whether the guarded entrypoints are stopped
+            pub fn paused(env: soroban_sdk::Env) -> bool {
+                $crate::is_paused(&env)
+            }
+
+            /// Whether `account` may pause the contract
+            pub fn is_pauser(env: soroban_sdk::Env, account: soroban_sdk::Address) -> bool {
+                $crate::is_pauser(&env, &account)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test;
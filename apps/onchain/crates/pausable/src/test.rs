@@ -0,0 +1,72 @@
+use crate::{Pausable, PauseError};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, testutils::Address as _, Address, Env, Error,
+};
+
+#[contracttype]
+enum DataKey {
+    Admin,
+    Balance,
+}
+
+#[contract]
+struct Vault;
+
+#[contractimpl]
+impl Vault {
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
+    pub fn deposit(env: Env, amount: i128) -> i128 {
+        crate::when_not_paused(&env);
+        let balance: i128 = env.storage().instance().get(&DataKey::Balance).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::Balance, &(balance + amount));
+        balance + amount
+    }
+}
+
+impl Pausable for Vault {
+    fn pause_admin(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Admin)
+    }
+}
+
+pausable!(Vault);
+
+#[test]
+fn test_pause_stops_guarded_entrypoints() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let pauser = Address::generate(&env);
+    let client = VaultClient::new(&env, &env.register(Vault, (&admin,)));
+
+    let result = client.try_pause(&pauser);
+    assert_eq!(result, Err(Ok(PauseError::Unauthorized)));
+    let result = client.try_grant_pauser(&pauser, &pauser);
+    assert_eq!(result, Err(Ok(PauseError::Unauthorized)));
+    client.grant_pauser(&admin, &pauser);
+    assert!(client.is_pauser(&pauser));
+
+    // A pauser can stop the contract but only the admin restarts it
+    assert_eq!(client.deposit(&100), 100);
+    client.pause(&pauser);
+    assert!(client.paused());
+    let result = client.try_deposit(&100);
+    assert_eq!(result, Err(Ok(Error::from(PauseError::Paused))));
+    let result = client.try_pause(&admin);
+    assert_eq!(result, Err(Ok(PauseError::Paused)));
+    let result = client.try_unpause(&pauser);
+    assert_eq!(result, Err(Ok(PauseError::Unauthorized)));
+    client.unpause(&admin);
+    assert_eq!(client.deposit(&100), 200);
+    let result = client.try_unpause(&admin);
+    assert_eq!(result, Err(Ok(PauseError::NotPaused)));
+
+    client.revoke_pauser(&admin, &pauser);
+    let result = client.try_pause(&pauser);
+    assert_eq!(result, Err(Ok(PauseError::Unauthorized)));
+}
@@ -0,0 +1,204 @@
+#![no_std]
+//! Refund policies and merchant-funded refund reserves for Lumenpulse
+//! payment contracts.
+//!
+//! A merchant sets a `RefundPolicy` and tops up a reserve per token in the
+//! contract that took the payment. When a payer asks for a refund, the
+//! contract calls `evaluate` with what was paid and when, applies the
+//! refund to its own records, and pays it out of the merchant's reserve
+//! with `execute`. Authorization and events are left to the contract.
+//!
+//! Keys are stored in the calling contract's persistent storage, under
+//! variant names no Lumenpulse contract uses for its own keys.
+
+use lumenpulse_fixed::{prorate, Rounding};
+use soroban_sdk::{contracttype, Address, Env};
+
+/// When a payment can be refunded, in ledgers after it was made: in full
+/// for `full_window` ledgers, then for a share falling linearly to nothing
+/// over the next `prorated_window` ledgers. After that the payment is
+/// settled and nothing is refunded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundPolicy {
+    pub full_window: u32,
+    pub prorated_window: u32,
+}
+
+impl RefundPolicy {
+    /// Whether the policy refunds anything at all
+    pub fn is_valid(&self) -> bool {
+        self.full_window > 0 || self.prorated_window > 0
+    }
+
+    /// Part of `amount` refundable `elapsed` ledgers after it was paid,
+    /// rounded down
+    pub fn refundable(&self, amount: i128, elapsed: u32) -> i128 {
+        if elapsed <= self.full_window {
+            return amount;
+        }
+        let into_proration = elapsed - self.full_window;
+        if into_proration >= self.prorated_window {
+            return 0;
+        }
+        let left = self.prorated_window - into_proration;
+        prorate(
+            amount,
+            left.into(),
+            self.prorated_window.into(),
+            Rounding::Down,
+        )
+        .expect("arithmetic overflow")
+    }
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum RefundKey {
+    RefundPolicy(Address),           // merchant -> RefundPolicy
+    RefundReserve(Address, Address), // (merchant, token) -> i128
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RefundError {
+    /// The merchant has no refund policy
+    NoPolicy,
+    /// The policy refunds nothing for this payment
+    NotRefundable,
+    /// The merchant's reserve cannot cover the refund
+    InsufficientReserve,
+    InvalidPolicy,
+    InvalidAmount,
+}
+
+/// Set or clear `merchant`'s refund policy
+pub fn set_policy(
+    env: &Env,
+    merchant: &Address,
+    policy: &Option<RefundPolicy>,
+) -> Result<(), RefundError> {
+    let key = RefundKey::RefundPolicy(merchant.clone());
+    match policy {
+        Some(policy) if !policy.is_valid() => return Err(RefundError::InvalidPolicy),
+        Some(policy) => env.storage().persistent().set(&key, policy),
+        None => env.storage().persistent().remove(&key),
+    }
+    Ok(())
+}
+
+/// `merchant`'s refund policy, if it has one
+pub fn policy(env: &Env, merchant: &Address) -> Option<RefundPolicy> {
+    env.storage()
+        .persistent()
+        .get(&RefundKey::RefundPolicy(merchant.clone()))
+}
+
+/// `merchant`'s reserve of `token`
+pub fn reserve(env: &Env, merchant: &Address, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&RefundKey::RefundReserve(merchant.clone(), token.clone()))
+        .unwrap_or(0)
+}
+
+/// Move `amount` of `token` from `merchant` into its reserve. Returns the
+/// new reserve.
+pub fn fund_reserve(
+    env: &Env,
+    merchant: &Address,
+    token: &Address,
+    amount: i128,
+) -> Result<i128, RefundError> {
+    if amount <= 0 {
+        return Err(RefundError::InvalidAmount);
+    }
+    let reserve = reserve(env, merchant, token) + amount;
+    set_reserve(env, merchant, token, reserve);
+    transfer(
+        env,
+        token,
+        merchant,
+        &env.current_contract_address(),
+        amount,
+    );
+    Ok(reserve)
+}
+
+/// Pay `amount` of `merchant`'s reserve of `token` back to it. Returns the
+/// new reserve.
+pub fn withdraw_reserve(
+    env: &Env,
+    merchant: &Address,
+    token: &Address,
+    amount: i128,
+) -> Result<i128, RefundError> {
+    if amount <= 0 {
+        return Err(RefundError::InvalidAmount);
+    }
+    let reserve = debit(env, merchant, token, amount)?;
+    transfer(
+        env,
+        token,
+        &env.current_contract_address(),
+        merchant,
+        amount,
+    );
+    Ok(reserve)
+}
+
+/// Refund owed under `merchant`'s policy on `amount` paid in ledger
+/// `paid_ledger`
+pub fn evaluate(
+    env: &Env,
+    merchant: &Address,
+    amount: i128,
+    paid_ledger: u32,
+) -> Result<i128, RefundError> {
+    let policy = policy(env, merchant).ok_or(RefundError::NoPolicy)?;
+    let elapsed = env.ledger().sequence().saturating_sub(paid_ledger);
+    match policy.refundable(amount, elapsed) {
+        refund if refund > 0 => Ok(refund),
+        _ => Err(RefundError::NotRefundable),
+    }
+}
+
+/// Pay a refund of `amount` of `token` to `to` out of `merchant`'s reserve
+pub fn execute(
+    env: &Env,
+    merchant: &Address,
+    token: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), RefundError> {
+    debit(env, merchant, token, amount)?;
+    transfer(env, token, &env.current_contract_address(), to, amount);
+    Ok(())
+}
+
+fn set_reserve(env: &Env, merchant: &Address, token: &Address, reserve: i128) {
+    env.storage().persistent().set(
+        &RefundKey::RefundReserve(merchant.clone(), token.clone()),
+        &reserve,
+    );
+}
+
+fn debit(
+    env: &Env,
+    merchant: &Address,
+    token: &Address,
+    amount: i128,
+) -> Result<i128, RefundError> {
+    let reserve = reserve(env, merchant, token);
+    if amount > reserve {
+        return Err(RefundError::InsufficientReserve);
+    }
+    set_reserve(env, merchant, token, reserve - amount);
+    Ok(reserve - amount)
+}
+
+fn transfer(env: &Env, token: &Address, from: &Address, to: &Address, amount: i128) {
+    lumenpulse_sac::transfer(env, token, from, to, amount);
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,30 @@
+use crate::RefundPolicy;
+
+#[test]
+fn test_refundable() {
+    let policy = RefundPolicy {
+        full_window: 100,
+        prorated_window: 400,
+    };
+    assert!(policy.is_valid());
+
+    // In full, then falling linearly to nothing once settled
+    assert_eq!(policy.refundable(1_000, 0), 1_000);
+    assert_eq!(policy.refundable(1_000, 100), 1_000);
+    assert_eq!(policy.refundable(1_000, 200), 750);
+    assert_eq!(policy.refundable(1_000, 499), 2);
+    assert_eq!(policy.refundable(1_000, 500), 0);
+    assert_eq!(policy.refundable(1_000, u32::MAX), 0);
+
+    let full_only = RefundPolicy {
+        full_window: 10,
+        prorated_window: 0,
+    };
+    assert_eq!(full_only.refundable(1_000, 10), 1_000);
+    assert_eq!(full_only.refundable(1_000, 11), 0);
+    assert!(!RefundPolicy {
+        full_window: 0,
+        prorated_window: 0
+    }
+    .is_valid());
+}
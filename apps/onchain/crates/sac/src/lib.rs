@@ -0,0 +1,146 @@
+#![no_std]
+//! Helpers for moving funds through Stellar Asset Contracts.
+//!
+//! Native XLM and classic assets are both exposed on Soroban through a SAC,
+//! so contracts can treat them uniformly as long as they resolve the right
+//! contract address and check that transfers settle for the exact amount.
+//!
+//! Fund-moving contracts go through `transfer` rather than calling the token
+//! client directly. A token that quietly delivers less than it was asked to,
+//! such as one taking a fee on transfer, would otherwise leave a contract
+//! recording funds it never received.
+
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contracterror, panic_with_error, token, vec, Address, Bytes, Env, IntoVal, Symbol, Vec,
+};
+
+/// XDR encoding of `Asset::Native`
+const NATIVE_ASSET_XDR: [u8; 4] = [0, 0, 0, 0];
+
+/// Codes start at 1200 so they never overlap a contract's own errors or
+/// the upgrade and pause errors
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TransferError {
+    /// Amount was negative
+    InvalidAmount = 1200,
+    /// The token rejected the transfer, or balances did not move by exactly
+    /// the transferred amount
+    TransferFailed = 1201,
+}
+
+/// Address of the native XLM Stellar Asset Contract on the current network.
+///
+/// SAC addresses are derived from the network passphrase, so this resolves
+/// correctly on mainnet, testnet, futurenet and local networks alike.
+pub fn native_asset_address(env: &Env) -> Address {
+    env.deployer()
+        .with_stellar_asset(Bytes::from_array(env, &NATIVE_ASSET_XDR))
+        .deployed_address()
+}
+
+/// Whether `token` is the native XLM contract
+pub fn is_native(env: &Env, token: &Address) -> bool {
+    *token == native_asset_address(env)
+}
+
+/// Get the balance of an address for a given token
+pub fn balance(env: &Env, token: &Address, address: &Address) -> i128 {
+    token::Client::new(env, token).balance(address)
+}
+
+/// Transfer `amount` and verify that `from` lost and `to` gained exactly that
+/// much. Zero amounts are a no-op.
+pub fn transfer_exact(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), TransferError> {
+    settle_exact(env, token, from, to, amount, |client| {
+        client.try_transfer(from, to, &amount)
+    })
+}
+
+/// Like `transfer_exact`, but spending the allowance `from` gave `spender`
+pub fn transfer_from_exact(
+    env: &Env,
+    token: &Address,
+    spender: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), TransferError> {
+    settle_exact(env, token, from, to, amount, |client| {
+        client.try_transfer_from(spender, from, to, &amount)
+    })
+}
+
+/// Transfer exactly `amount`, failing the invocation with a
+/// `TransferError` code otherwise
+pub fn transfer(env: &Env, token: &Address, from: &Address, to: &Address, amount: i128) {
+    if let Err(error) = transfer_exact(env, token, from, to, amount) {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Authorize the current contract's transfer of `amount` of `token` to `to`
+/// when it happens in a call below the contract's next one, such as a
+/// streaming contract or a router pulling a deposit. Direct calls are
+/// authorized implicitly, but deeper ones are not. The authorization only
+/// covers the next call the contract makes.
+pub fn authorize_transfer(env: &Env, token: &Address, to: &Address, amount: i128) {
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (env.current_contract_address(), to.clone(), amount).into_val(env),
+            },
+            sub_invocations: Vec::new(env),
+        }),
+    ]);
+}
+
+fn settle_exact<T, E>(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    send: impl FnOnce(&token::Client) -> Result<Result<(), T>, E>,
+) -> Result<(), TransferError> {
+    if amount < 0 {
+        return Err(TransferError::InvalidAmount);
+    }
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let client = token::Client::new(env, token);
+    let from_before = client.balance(from);
+    let to_before = client.balance(to);
+
+    if !matches!(send(&client), Ok(Ok(()))) {
+        return Err(TransferError::TransferFailed);
+    }
+
+    // Sending to oneself leaves both balances where they were
+    if from == to {
+        return Ok(());
+    }
+    let sent = from_before - client.balance(from);
+    let received = client.balance(to) - to_before;
+    if sent != amount || received != amount {
+        return Err(TransferError::TransferFailed);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
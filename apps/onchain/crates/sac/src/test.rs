@@ -0,0 +1,118 @@
+use crate::{
+    balance, is_native, native_asset_address, transfer_exact, transfer_from_exact, TransferError,
+};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, testutils::Address as _, token::StellarAssetClient,
+    Address, Bytes, Env, MuxedAddress,
+};
+
+#[contracttype]
+enum DataKey {
+    Balance(Address),
+}
+
+/// Token that silently keeps a 1% cut of every transfer
+#[contract]
+struct SkimmingToken;
+
+#[contractimpl]
+impl SkimmingToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let key = DataKey::Balance(to);
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(id))
+            .unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: MuxedAddress, amount: i128) {
+        let to = to.address();
+        Self::mint(env.clone(), from, -amount);
+        Self::mint(env, to, amount - amount / 100);
+    }
+}
+
+#[test]
+fn test_native_asset_address_matches_deployed_sac() {
+    let env = Env::default();
+
+    let native = native_asset_address(&env);
+    let deployed = env
+        .deployer()
+        .with_stellar_asset(Bytes::from_array(&env, &[0, 0, 0, 0]))
+        .deploy();
+    assert_eq!(native, deployed);
+    assert!(is_native(&env, &native));
+
+    let other = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    assert!(!is_native(&env, &other.address()));
+}
+
+#[test]
+fn test_transfer_exact() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let token = sac.address();
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&from, &1_000);
+
+    assert_eq!(transfer_exact(&env, &token, &from, &to, 400), Ok(()));
+    assert_eq!(balance(&env, &token, &from), 600);
+    assert_eq!(balance(&env, &token, &to), 400);
+
+    assert_eq!(transfer_exact(&env, &token, &from, &to, 0), Ok(()));
+    assert_eq!(
+        transfer_exact(&env, &token, &from, &to, -1),
+        Err(TransferError::InvalidAmount)
+    );
+}
+
+#[test]
+fn test_transfer_exact_detects_short_delivery() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let token = env.register(SkimmingToken, ());
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    SkimmingTokenClient::new(&env, &token).mint(&from, &1_000);
+
+    assert_eq!(
+        transfer_exact(&env, &token, &from, &to, 500),
+        Err(TransferError::TransferFailed)
+    );
+}
+
+#[test]
+fn test_transfer_from_exact_reports_rejection() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let token = sac.address();
+    let spender = Address::generate(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&from, &1_000);
+
+    // Without an allowance the SAC refuses, which surfaces as an error
+    // rather than aborting the caller
+    assert_eq!(
+        transfer_from_exact(&env, &token, &spender, &from, &to, 100),
+        Err(TransferError::TransferFailed)
+    );
+    soroban_sdk::token::TokenClient::new(&env, &token).approve(&from, &spender, &100, &1_000);
+    assert_eq!(
+        transfer_from_exact(&env, &token, &spender, &from, &to, 100),
+        Ok(())
+    );
+    assert_eq!(balance(&env, &token, &to), 100);
+}
@@ -0,0 +1,269 @@
+#![no_std]
+//! Typed storage slots for Lumenpulse contracts.
+//!
+//! A contract declares each slot once, as a constant pairing its key with
+//! the type stored under it, and reads and writes through the slot rather
+//! than through `env.storage()` directly:
+//!
+//! ```ignore
+//! const COUNTER: InstanceItem<DataKey, u32> = InstanceItem::new(DataKey::Counter);
+//! const BALANCES: PersistentMap<Address, DataKey, i128> = PersistentMap::new(DataKey::Balance);
+//! ```
+//!
+//! Every slot carries a `Ttl` policy. Writes always extend the entry's
+//! TTL, and reads extend it when it has fallen below the policy's
+//! threshold, so entries a contract keeps using never get archived. A slot
+//! declared `without_ttl` leaves TTLs alone.
+
+use core::marker::PhantomData;
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val};
+
+/// Ledgers closed in a day, at five seconds a ledger
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// When and how far an entry's TTL is extended
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ttl {
+    /// Extend once fewer than this many ledgers are left
+    pub threshold: u32,
+    /// Ledgers the entry lives for after an extension
+    pub extend_to: u32,
+}
+
+impl Ttl {
+    /// Live for `days`, extending once a day has gone by
+    pub const fn days(days: u32) -> Self {
+        Ttl {
+            threshold: (days - 1) * DAY_IN_LEDGERS,
+            extend_to: days * DAY_IN_LEDGERS,
+        }
+    }
+}
+
+/// Default policy for instance storage, which lives and dies with the
+/// contract itself
+pub const INSTANCE_TTL: Ttl = Ttl::days(7);
+
+/// Default policy for persistent entries
+pub const PERSISTENT_TTL: Ttl = Ttl::days(30);
+
+/// A single value in instance storage
+pub struct InstanceItem<K, V> {
+    key: K,
+    ttl: Option<Ttl>,
+    value: PhantomData<V>,
+}
+
+impl<K, V> InstanceItem<K, V>
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    /// Slot under `key` with the default TTL policy
+    pub const fn new(key: K) -> Self {
+        Self::with_ttl(key, INSTANCE_TTL)
+    }
+
+    pub const fn with_ttl(key: K, ttl: Ttl) -> Self {
+        InstanceItem {
+            key,
+            ttl: Some(ttl),
+            value: PhantomData,
+        }
+    }
+
+    /// Slot under `key` that never extends TTLs
+    pub const fn without_ttl(key: K) -> Self {
+        InstanceItem {
+            key,
+            ttl: None,
+            value: PhantomData,
+        }
+    }
+
+    pub fn get(&self, env: &Env) -> Option<V> {
+        let value = env.storage().instance().get(&self.key);
+        if value.is_some() {
+            self.bump(env);
+        }
+        value
+    }
+
+    pub fn has(&self, env: &Env) -> bool {
+        env.storage().instance().has(&self.key)
+    }
+
+    pub fn set(&self, env: &Env, value: &V) {
+        env.storage().instance().set(&self.key, value);
+        self.bump(env);
+    }
+
+    pub fn remove(&self, env: &Env) {
+        env.storage().instance().remove(&self.key);
+    }
+
+    /// Store the value `f` computes from the current one and return it
+    pub fn update(&self, env: &Env, f: impl FnOnce(Option<V>) -> V) -> V {
+        let value = f(self.get(env));
+        self.set(env, &value);
+        value
+    }
+
+    fn bump(&self, env: &Env) {
+        if let Some(ttl) = self.ttl {
+            env.storage()
+                .instance()
+                .extend_ttl(ttl.threshold, ttl.extend_to);
+        }
+    }
+}
+
+/// A single value in persistent storage
+pub struct PersistentItem<K, V> {
+    key: K,
+    ttl: Option<Ttl>,
+    value: PhantomData<V>,
+}
+
+impl<K, V> PersistentItem<K, V>
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    /// Slot under `key` with the default TTL policy
+    pub const fn new(key: K) -> Self {
+        Self::with_ttl(key, PERSISTENT_TTL)
+    }
+
+    pub const fn with_ttl(key: K, ttl: Ttl) -> Self {
+        PersistentItem {
+            key,
+            ttl: Some(ttl),
+            value: PhantomData,
+        }
+    }
+
+    /// Slot under `key` that never extends TTLs
+    pub const fn without_ttl(key: K) -> Self {
+        PersistentItem {
+            key,
+            ttl: None,
+            value: PhantomData,
+        }
+    }
+
+    pub fn get(&self, env: &Env) -> Option<V> {
+        persistent_get(env, &self.key, self.ttl)
+    }
+
+    pub fn has(&self, env: &Env) -> bool {
+        env.storage().persistent().has(&self.key)
+    }
+
+    pub fn set(&self, env: &Env, value: &V) {
+        persistent_set(env, &self.key, value, self.ttl);
+    }
+
+    pub fn remove(&self, env: &Env) {
+        env.storage().persistent().remove(&self.key);
+    }
+
+    /// Store the value `f` computes from the current one and return it
+    pub fn update(&self, env: &Env, f: impl FnOnce(Option<V>) -> V) -> V {
+        let value = f(self.get(env));
+        self.set(env, &value);
+        value
+    }
+}
+
+/// Values in persistent storage, one entry per key. `key` builds the
+/// storage key for each map key, usually a tuple variant of the
+/// contract's `DataKey`.
+pub struct PersistentMap<K, S, V> {
+    key: fn(K) -> S,
+    ttl: Option<Ttl>,
+    value: PhantomData<V>,
+}
+
+impl<K, S, V> PersistentMap<K, S, V>
+where
+    S: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    /// Slot under `key` with the default TTL policy
+    pub const fn new(key: fn(K) -> S) -> Self {
+        Self::with_ttl(key, PERSISTENT_TTL)
+    }
+
+    pub const fn with_ttl(key: fn(K) -> S, ttl: Ttl) -> Self {
+        PersistentMap {
+            key,
+            ttl: Some(ttl),
+            value: PhantomData,
+        }
+    }
+
+    /// Slot under `key` that never extends TTLs
+    pub const fn without_ttl(key: fn(K) -> S) -> Self {
+        PersistentMap {
+            key,
+            ttl: None,
+            value: PhantomData,
+        }
+    }
+
+    pub fn get(&self, env: &Env, key: K) -> Option<V> {
+        persistent_get(env, &(self.key)(key), self.ttl)
+    }
+
+    pub fn has(&self, env: &Env, key: K) -> bool {
+        env.storage().persistent().has(&(self.key)(key))
+    }
+
+    pub fn set(&self, env: &Env, key: K, value: &V) {
+        persistent_set(env, &(self.key)(key), value, self.ttl);
+    }
+
+    pub fn remove(&self, env: &Env, key: K) {
+        env.storage().persistent().remove(&(self.key)(key));
+    }
+
+    /// Store the value `f` computes from the current one under `key` and
+    /// return it
+    pub fn update(&self, env: &Env, key: K, f: impl FnOnce(Option<V>) -> V) -> V {
+        let key = (self.key)(key);
+        let value = f(persistent_get(env, &key, self.ttl));
+        persistent_set(env, &key, &value, self.ttl);
+        value
+    }
+}
+
+fn persistent_get<S, V>(env: &Env, key: &S, ttl: Option<Ttl>) -> Option<V>
+where
+    S: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    let value = env.storage().persistent().get(key);
+    if let (Some(_), Some(ttl)) = (&value, ttl) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, ttl.threshold, ttl.extend_to);
+    }
+    value
+}
+
+fn persistent_set<S, V>(env: &Env, key: &S, value: &V, ttl: Option<Ttl>)
+where
+    S: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    env.storage().persistent().set(key, value);
+    if let Some(ttl) = ttl {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, ttl.threshold, ttl.extend_to);
+    }
+}
+
+#[cfg(test)]
+mod test;
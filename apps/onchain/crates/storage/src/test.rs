@@ -0,0 +1,71 @@
+use crate::{InstanceItem, PersistentItem, PersistentMap, Ttl, DAY_IN_LEDGERS, PERSISTENT_TTL};
+use soroban_sdk::{
+    contract, contracttype,
+    testutils::{
+        storage::{Instance as _, Persistent as _},
+        Address as _, Ledger,
+    },
+    Address, Env,
+};
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Counter,
+    Owner,
+    Balance(Address),
+}
+
+const COUNTER: InstanceItem<DataKey, u32> = InstanceItem::new(DataKey::Counter);
+const OWNER: PersistentItem<DataKey, Address> =
+    PersistentItem::with_ttl(DataKey::Owner, Ttl::days(90));
+const BALANCES: PersistentMap<Address, DataKey, i128> = PersistentMap::new(DataKey::Balance);
+
+#[contract]
+struct Store;
+
+#[test]
+fn test_slots_round_trip_and_extend_ttl() {
+    let env = Env::default();
+    let contract_id = env.register(Store, ());
+    let owner = Address::generate(&env);
+    let holder = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(COUNTER.get(&env), None);
+        assert_eq!(COUNTER.update(&env, |count| count.unwrap_or(0) + 1), 1);
+        assert_eq!(COUNTER.update(&env, |count| count.unwrap_or(0) + 1), 2);
+
+        OWNER.set(&env, &owner);
+        assert_eq!(OWNER.get(&env), Some(owner.clone()));
+        assert_eq!(
+            env.storage().persistent().get_ttl(&DataKey::Owner),
+            90 * DAY_IN_LEDGERS
+        );
+
+        BALANCES.set(&env, holder.clone(), &500);
+        assert!(BALANCES.has(&env, holder.clone()));
+        assert_eq!(BALANCES.get(&env, owner.clone()), None);
+        BALANCES.remove(&env, holder.clone());
+        assert_eq!(BALANCES.get(&env, holder.clone()), None);
+        BALANCES.set(&env, holder.clone(), &500);
+    });
+
+    // A read once the TTL drops below the threshold extends it again
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number += 2 * DAY_IN_LEDGERS);
+    env.as_contract(&contract_id, || {
+        let key = DataKey::Balance(holder.clone());
+        assert_eq!(
+            env.storage().persistent().get_ttl(&key),
+            PERSISTENT_TTL.extend_to - 2 * DAY_IN_LEDGERS
+        );
+        assert_eq!(BALANCES.get(&env, holder.clone()), Some(500));
+        assert_eq!(
+            env.storage().persistent().get_ttl(&key),
+            PERSISTENT_TTL.extend_to
+        );
+        assert_eq!(COUNTER.get(&env), Some(2));
+        assert_eq!(env.storage().instance().get_ttl(), 7 * DAY_IN_LEDGERS);
+    });
+}
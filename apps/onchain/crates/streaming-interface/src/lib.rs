@@ -0,0 +1,317 @@
+#![no_std]
+//! Client, types and errors of the Lumenpulse streaming contract.
+//!
+//! Contracts that call the streaming contract, and off-chain Rust that
+//! talks to it, depend on this crate instead of the contract itself, so
+//! none of its implementation ends up in their WASM. The streaming
+//! contract uses these same types, so the two cannot drift apart.
+
+use lumenpulse_oracle::OracleConfig;
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, BytesN, Env, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamData {
+    pub id: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub token: Address,
+    pub deposit: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub withdrawn: i128,
+    pub cancelled: bool,
+    pub kind: StreamKind,
+    pub max_total: Option<i128>,
+}
+
+/// How a stream accrues: linearly between start and end, at a fixed rate,
+/// or linearly in USD paid out at the oracle rate
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StreamKind {
+    Linear,
+    Rate(StreamRate),
+    Usd(UsdStream),
+}
+
+/// Accrual rate for rate-denominated streams: `amount` tokens per `period`
+/// seconds (e.g. `period = 86_400` for a daily rate)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamRate {
+    pub amount: i128,
+    pub period: u64,
+}
+
+/// Terms a sender sets for a USD-denominated stream. Values are in the
+/// oracle's base asset (USD), scaled to the payment token's decimals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UsdTerms {
+    /// USD value streamed linearly between start and end
+    pub value: i128,
+    /// Lowest oracle price, in the feed's decimals, withdrawals accept
+    pub min_price: i128,
+    /// Highest oracle price, in the feed's decimals, withdrawals accept
+    pub max_price: i128,
+}
+
+/// A USD-denominated stream's terms and progress
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UsdStream {
+    pub terms: UsdTerms,
+    /// USD value already settled to the recipient
+    pub settled: i128,
+}
+
+/// Payment a sender makes in one token to fund a stream in another,
+/// converted through the DEX router when the stream is created
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentSwap {
+    /// Token the sender pays in
+    pub token_in: Address,
+    pub amount_in: i128,
+    /// Fewest stream tokens the swap may return
+    pub min_amount_out: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WithdrawStatus {
+    Withdrawn,
+    NothingToWithdraw,
+    StreamNotFound,
+    StreamCancelled,
+    /// A USD stream's oracle gave no in-bounds price, so what is owed
+    /// cannot be worked out until it does
+    PriceUnavailable,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawResult {
+    pub stream_id: u64,
+    pub amount: i128,
+    pub status: WithdrawStatus,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StreamError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    StreamNotFound = 4,
+    InvalidAmount = 5,
+    InvalidTimeRange = 6,
+    NothingToWithdraw = 7,
+    StreamCancelled = 8,
+    InsufficientBalance = 9,
+    InsufficientAllowance = 10,
+    AllowanceExpired = 11,
+    InvalidExpiration = 12,
+    BatchTooLarge = 13,
+    InvalidRate = 14,
+    ExceedsMaxTotal = 15,
+    NotRateStream = 16,
+    OracleNotSet = 17,
+    PriceUnavailable = 18,
+    StalePrice = 19,
+    InvalidOracleConfig = 20,
+    PriceOutOfBounds = 21,
+    InvalidPriceBounds = 22,
+    DexNotSet = 23,
+    PairNotEnabled = 24,
+    SwapFailed = 25,
+    SlippageExceeded = 26,
+    UpgradePending = 27,
+}
+
+/// Entrypoints of the streaming contract, besides the standard upgrade and
+/// pause entrypoints
+#[contractclient(name = "StreamingClient")]
+pub trait Streaming {
+    /// Initialize the contract with an admin address
+    fn initialize(env: Env, admin: Address) -> Result<(), StreamError>;
+
+    /// Create a stream funded directly from the sender's token balance
+    fn create_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<u64, StreamError>;
+
+    /// Pre-deposit tokens that operators may later use to create streams on
+    /// the sender's behalf
+    fn deposit(env: Env, sender: Address, token: Address, amount: i128) -> Result<(), StreamError>;
+
+    /// Withdraw unused pre-deposited tokens back to the sender
+    fn withdraw_deposit(
+        env: Env,
+        sender: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), StreamError>;
+
+    /// Allow an operator to create streams from the sender's pre-deposited
+    /// balance, up to `amount` in total and until `expiration_ledger`.
+    /// Approving again replaces the previous allowance.
+    fn approve_operator(
+        env: Env,
+        sender: Address,
+        operator: Address,
+        token: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<(), StreamError>;
+
+    /// Create a stream on behalf of `sender`, funded from their pre-deposited
+    /// balance and charged against the operator's allowance
+    #[allow(clippy::too_many_arguments)]
+    fn create_stream_from(
+        env: Env,
+        operator: Address,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<u64, StreamError>;
+
+    /// Create a stream that accrues at a fixed rate of `rate.amount` tokens
+    /// every `rate.period` seconds, funded with `deposit` up front.
+    ///
+    /// With `max_total` set, the stream ends once that total has accrued and
+    /// may be topped up to at most `max_total`. Without it the stream is
+    /// open-ended: accrual is capped by the funded amount, and top-ups cover
+    /// any accrued shortfall first.
+    #[allow(clippy::too_many_arguments)]
+    fn create_rate_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        rate: StreamRate,
+        start_time: u64,
+        deposit: i128,
+        max_total: Option<i128>,
+    ) -> Result<u64, StreamError>;
+
+    /// Create a stream paying `terms.value` USD linearly between
+    /// `start_time` and `end_time`, in `token` converted at the oracle price
+    /// when each withdrawal is made. Withdrawals fail while the price is
+    /// outside the terms' bounds, protecting the sender from paying out at a
+    /// manipulated or crashed rate. The stream is funded with `deposit`
+    /// tokens and may be topped up if the token's price falls.
+    #[allow(clippy::too_many_arguments)]
+    fn create_usd_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        terms: UsdTerms,
+        start_time: u64,
+        end_time: u64,
+        deposit: i128,
+    ) -> Result<u64, StreamError>;
+
+    /// Create a linear stream in `token` funded by a payment in another
+    /// token, converted through the DEX router at creation. The sender pays
+    /// `payment.amount_in` and the stream's deposit is whatever the swap
+    /// returns, which must be at least `payment.min_amount_out`. The pair
+    /// must be enabled by the admin.
+    fn create_swapped_stream(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        payment: PaymentSwap,
+        token: Address,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<u64, StreamError>;
+
+    /// Add funds to a rate-denominated or USD stream (sender only)
+    fn top_up(env: Env, stream_id: u64, amount: i128) -> Result<(), StreamError>;
+
+    /// Withdraw everything that has streamed to the recipient so far
+    fn withdraw(env: Env, stream_id: u64) -> Result<i128, StreamError>;
+
+    /// Settle several streams in one invocation. Funds always go to each
+    /// stream's recipient, so anyone (e.g. a keeper) may call this. A stream
+    /// that cannot be withdrawn from is reported in its result instead of
+    /// failing the whole batch.
+    fn withdraw_many(env: Env, stream_ids: Vec<u64>) -> Result<Vec<WithdrawResult>, StreamError>;
+
+    /// Cancel a stream (sender only). The streamed portion is paid out to the
+    /// recipient and the remainder is refunded to the sender. A USD stream
+    /// can only be cancelled while the oracle gives an in-bounds price, so
+    /// the sender cannot cancel to avoid paying what has accrued.
+    fn cancel(env: Env, stream_id: u64) -> Result<(), StreamError>;
+
+    /// Get stream data
+    fn get_stream(env: Env, stream_id: u64) -> Result<StreamData, StreamError>;
+
+    /// Get the amount the recipient could withdraw right now
+    fn withdrawable_amount(env: Env, stream_id: u64) -> Result<i128, StreamError>;
+
+    /// Get the sender's unused pre-deposited balance for a token
+    fn get_deposit_balance(env: Env, sender: Address, token: Address) -> i128;
+
+    /// Get the remaining operator allowance; expired allowances read as zero
+    fn get_operator_allowance(env: Env, sender: Address, operator: Address, token: Address)
+        -> i128;
+
+    /// Let `upgrader` replace the contract's WASM without the upgrade
+    /// delay (admin only). Factory-deployed instances name the factory here.
+    fn set_upgrader(env: Env, upgrader: Address) -> Result<(), StreamError>;
+
+    /// Address allowed to upgrade the contract without the delay, if any
+    fn get_upgrader(env: Env) -> Option<Address>;
+
+    /// Set the SEP-40 price feeds used for price conversions on this
+    /// network and the oldest price they may give (admin only)
+    fn set_oracle(env: Env, config: OracleConfig) -> Result<(), StreamError>;
+
+    /// Price feeds used for price conversions, if any
+    fn get_oracle(env: Env) -> Option<OracleConfig>;
+
+    /// Value of `amount` of `token` in the feeds' base asset, at the latest
+    /// fresh price. Falls back through the configured feeds.
+    fn quote_value(env: Env, token: Address, amount: i128) -> Result<i128, StreamError>;
+
+    /// Set the DEX router payments are converted through (admin only)
+    fn set_dex_router(env: Env, router: Address) -> Result<(), StreamError>;
+
+    /// DEX router payments are converted through, if any
+    fn get_dex_router(env: Env) -> Option<Address>;
+
+    /// Allow or stop paying in `token_in` to fund streams in `token_out`
+    /// (admin only)
+    fn set_pair_enabled(
+        env: Env,
+        token_in: Address,
+        token_out: Address,
+        enabled: bool,
+    ) -> Result<(), StreamError>;
+
+    /// Whether payments in `token_in` may fund streams in `token_out`
+    fn is_pair_enabled(env: Env, token_in: Address, token_out: Address) -> bool;
+
+    /// Replace the contract's WASM at once (upgrader only), for the
+    /// factory's rollouts. Storage is kept, and the new WASM is recorded as
+    /// the next release so the admin can roll it back. The admin upgrades
+    /// through `propose_upgrade` and `execute_upgrade`, after the delay.
+    fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), StreamError>;
+
+    /// Get admin address
+    fn get_admin(env: Env) -> Result<Address, StreamError>;
+}
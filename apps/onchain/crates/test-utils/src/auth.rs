@@ -0,0 +1,82 @@
+//! Auth matrices for privileged entrypoints.
+//!
+//! A test that runs under `mock_all_auths` shows the admin can call an
+//! entrypoint, never that anyone else cannot, because every
+//! `require_auth` passes. An auth matrix instead calls each entrypoint
+//! with nothing authorized but the listed caller signing that exact call,
+//! and checks the outcome the table expects. Listing an outsider against
+//! every privileged entrypoint, including calls that name the admin as an
+//! argument, is what shows the checks are there.
+
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{MockAuth, MockAuthInvoke},
+    xdr::{ScErrorCode, ScErrorType},
+    Address, Env, Error, Symbol, Val, Vec,
+};
+use std::{format, string::String, vec::Vec as StdVec};
+
+/// What a call in an auth matrix should come to
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    /// The call succeeds
+    Allowed,
+    /// The host rejects the call because an address it needed did not
+    /// authorize it. A contract registered natively reports this as the
+    /// `Error(Context, InvalidAction)` any aborting host check gives it,
+    /// rather than the WASM build's `Error(Auth, InvalidAction)`.
+    Denied,
+    /// The call fails with this error, such as a contract's own
+    /// `Unauthorized`
+    Fails(Error),
+}
+
+/// One row of an auth matrix: who signs, which entrypoint, its arguments
+/// and what should happen
+pub type AuthCase<'a> = (&'a Address, &'a str, Vec<Val>, Outcome);
+
+/// Call `contract` once per case with only the caller's authorization of
+/// that call mocked, and fail listing every case whose outcome differs.
+/// Cases run in order against the same state, so an allowed call is seen
+/// by the ones after it. No auths are mocked in `env` afterwards.
+pub fn assert_auth_matrix(env: &Env, contract: &Address, cases: &[AuthCase]) {
+    let denied = [
+        Error::from_type_and_code(ScErrorType::Auth, ScErrorCode::InvalidAction),
+        Error::from_type_and_code(ScErrorType::Context, ScErrorCode::InvalidAction),
+    ];
+    let mut failures: StdVec<String> = StdVec::new();
+    for (caller, function, args, expected) in cases {
+        env.mock_auths(&[MockAuth {
+            address: caller,
+            invoke: &MockAuthInvoke {
+                contract,
+                fn_name: function,
+                args: args.clone(),
+                sub_invokes: &[],
+            },
+        }]);
+        let result = env.try_invoke_contract::<Val, Error>(
+            contract,
+            &Symbol::new(env, function),
+            args.clone(),
+        );
+        let outcome = match result {
+            Ok(_) => Outcome::Allowed,
+            Err(Ok(error)) if denied.contains(&error) => Outcome::Denied,
+            Err(Ok(error)) => Outcome::Fails(error),
+            Err(Err(_)) => unreachable!("every error converts to `Error`"),
+        };
+        if outcome != *expected {
+            failures.push(format!(
+                "{function} signed by {caller:?}: expected {expected:?}, got {outcome:?}"
+            ));
+        }
+    }
+    env.mock_auths(&[]);
+    assert!(
+        failures.is_empty(),
+        "auth matrix failed:\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,86 @@
+//! Golden files for events and return values.
+//!
+//! Indexers decode events and clients decode return structs by their XDR
+//! shape, so a renamed field or reordered topic breaks them even when
+//! every test still passes. A golden test renders what a canonical
+//! scenario emitted and returned as JSON and compares it with the file
+//! checked in under the crate's `goldens` directory. A deliberate schema
+//! change regenerates the files with `UPDATE_GOLDENS=1 cargo test` and
+//! commits them with the change, so the diff shows the new shape.
+
+extern crate std;
+
+use serde_json::{json, Value};
+use soroban_sdk::{testutils::Events as _, xdr::ScVal, Env, IntoVal, TryFromVal, Val};
+use std::{format, fs, path::Path, string::String, vec::Vec};
+
+/// What each call of a scenario emitted and returned, in order
+#[derive(Default)]
+pub struct Transcript {
+    calls: Vec<Value>,
+}
+
+impl Transcript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the events of the last top-level invocation in `env`, which
+    /// was `call` and returned `returned`
+    pub fn record<T: IntoVal<Env, Val>>(&mut self, env: &Env, call: &str, returned: &T) {
+        let events: Vec<Value> = env
+            .events()
+            .all()
+            .iter()
+            .map(|(contract, topics, data)| {
+                json!({
+                    "contract": to_json(env, contract.to_val()),
+                    "topics": topics.iter().map(|topic| to_json(env, topic)).collect::<Vec<_>>(),
+                    "data": to_json(env, data),
+                })
+            })
+            .collect();
+        self.calls.push(json!({
+            "call": call,
+            "events": events,
+            "returned": to_json(env, returned.into_val(env)),
+        }));
+    }
+}
+
+/// Fail unless `transcript` matches `goldens/<name>.json` under `dir`, the
+/// calling crate's manifest directory. With `UPDATE_GOLDENS` set the file
+/// is written instead.
+pub fn check(dir: &str, name: &str, transcript: &Transcript) {
+    let path = Path::new(dir).join("goldens").join(format!("{name}.json"));
+    let actual = serde_json::to_string_pretty(&transcript.calls).unwrap() + "\n";
+    if std::env::var_os("UPDATE_GOLDENS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected: String = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "no golden at {}; run with UPDATE_GOLDENS=1 to create it",
+            path.display()
+        )
+    });
+    assert!(
+        actual == expected,
+        "{} no longer matches; if the change is intended, run with \
+         UPDATE_GOLDENS=1 and commit the new file\n--- expected\n{expected}--- actual\n{actual}",
+        path.display()
+    );
+}
+
+fn to_json(env: &Env, val: Val) -> Value {
+    serde_json::to_value(ScVal::try_from_val(env, &val).unwrap()).unwrap()
+}
+
+/// Compare a `Transcript` with the calling crate's golden file `name`
+#[macro_export]
+macro_rules! assert_golden {
+    ($name:expr, $transcript:expr) => {
+        $crate::golden::check(env!("CARGO_MANIFEST_DIR"), $name, &$transcript)
+    };
+}
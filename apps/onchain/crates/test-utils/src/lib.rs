@@ -0,0 +1,212 @@
+#![no_std]
+//! Fixtures shared by Lumenpulse contract tests.
+//!
+//! Contract tests start from the same pieces: an `Env` with auth mocked,
+//! a Stellar Asset Contract to move funds in, accounts holding some of it,
+//! and a way to move the clock. The protocol fixture also deploys the
+//! streaming, escrow and upgradable contracts from the WASM in the
+//! factory's `testdata`, so contracts that integrate with them can be
+//! tested against the real builds. Rebuild those with `make fixtures` in
+//! `contracts/factory` after changing one of the contracts.
+//!
+//! A contract's own tests should keep registering it natively, so failures
+//! point into its source; the WASM builds are for its neighbours.
+//!
+//! Beyond moving the clock, tests can jump to an exact time or ledger,
+//! let a storage entry's TTL run out so it is archived, and reopen the
+//! `Env` from a snapshot of its ledger.
+//!
+//! `auth` checks privileged entrypoints against callers who should be
+//! turned away, which `mock_all_auths` cannot show.
+//!
+//! `golden` compares events and return values with checked-in JSON, for
+//! catching schema changes that indexers and clients would trip over.
+
+pub mod auth;
+pub mod golden;
+
+use lumenpulse_escrow_interface::EscrowClient;
+use lumenpulse_streaming_interface::StreamingClient;
+use lumenpulse_time::Network;
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    xdr::{ContractDataDurability, LedgerKey, LedgerKeyContractData, ScAddress, ScVal},
+    Address, Env, IntoVal, TryFromVal, Val,
+};
+
+/// The streaming contract built to WASM
+pub const STREAMING_WASM: &[u8] =
+    include_bytes!("../../../contracts/factory/testdata/streaming.wasm");
+
+/// The escrow contract built to WASM
+pub const ESCROW_WASM: &[u8] = include_bytes!("../../../contracts/factory/testdata/escrow.wasm");
+
+/// Client for the upgradable contract's WASM build, and the build itself
+/// as `upgradable::WASM`
+pub mod upgradable {
+    soroban_sdk::contractimport!(
+        file = "../../contracts/factory/testdata/upgradable_contract.wasm"
+    );
+}
+
+/// Network whose close time `advance_ledgers` moves the clock at
+const NETWORK: Network = Network::Testnet;
+
+/// Default environment with every `require_auth` passing
+pub fn setup_env() -> Env {
+    let env = Env::default();
+    env.mock_all_auths();
+    env
+}
+
+/// A Stellar Asset Contract registered for a test
+pub struct TestToken<'a> {
+    pub address: Address,
+    pub client: TokenClient<'a>,
+    /// Client for the admin interface, for minting
+    pub admin: StellarAssetClient<'a>,
+}
+
+impl TestToken<'_> {
+    /// Mint `amount` to `to` (requires auth to be mocked)
+    pub fn mint(&self, to: &Address, amount: i128) {
+        self.admin.mint(to, &amount);
+    }
+
+    pub fn balance(&self, id: &Address) -> i128 {
+        self.client.balance(id)
+    }
+}
+
+/// Register a Stellar Asset Contract administered by `admin`
+pub fn create_token<'a>(env: &Env, admin: &Address) -> TestToken<'a> {
+    let address = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    TestToken {
+        client: TokenClient::new(env, &address),
+        admin: StellarAssetClient::new(env, &address),
+        address,
+    }
+}
+
+/// `N` new accounts each holding `amount` of `token`
+pub fn funded_accounts<const N: usize>(env: &Env, token: &TestToken, amount: i128) -> [Address; N] {
+    core::array::from_fn(|_| {
+        let account = Address::generate(env);
+        token.mint(&account, amount);
+        account
+    })
+}
+
+/// Move the ledger timestamp forward by `seconds`. The sequence stays put,
+/// so long jumps do not expire storage entries under the test's feet.
+pub fn advance_time(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|ledger| ledger.timestamp += seconds);
+}
+
+/// Close `ledgers` ledgers, moving the timestamp along at the network's
+/// close time
+pub fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|ledger| {
+        ledger.sequence_number += ledgers;
+        ledger.timestamp += NETWORK.seconds_in(ledgers);
+    });
+}
+
+/// Set the ledger timestamp to `timestamp`, leaving the sequence put
+pub fn jump_to_time(env: &Env, timestamp: u64) {
+    env.ledger().set_timestamp(timestamp);
+}
+
+/// Close ledgers up to `sequence`, moving the timestamp along at the
+/// network's close time. Ledgers only move forward.
+pub fn jump_to_ledger(env: &Env, sequence: u32) {
+    let current = env.ledger().sequence();
+    assert!(sequence >= current, "ledger {sequence} is in the past");
+    advance_ledgers(env, sequence - current);
+}
+
+/// Ledgers after the current one that `contract`'s live persistent entry
+/// under `key` stays live for
+pub fn persistent_ttl<K: IntoVal<Env, Val>>(env: &Env, contract: &Address, key: &K) -> u32 {
+    env.as_contract(contract, || env.storage().persistent().get_ttl(key))
+}
+
+/// Close ledgers until `contract`'s persistent entry under `key` is
+/// archived. Every entry due to expire sooner, such as an instance that
+/// was not extended, is archived along with it, and temporary ones are
+/// gone for good.
+pub fn archive_persistent<K: IntoVal<Env, Val>>(env: &Env, contract: &Address, key: &K) {
+    advance_ledgers(env, persistent_ttl(env, contract, key) + 1);
+}
+
+/// The same ledger reopened in a new `Env`, as the next transaction sees
+/// it, with every `require_auth` passing. Contracts registered natively
+/// are not part of the ledger, so only WASM deployments can be called in
+/// the new `Env`. Values from the old one do not carry over; rebuild
+/// addresses with `address_in`.
+pub fn reopen(env: &Env) -> Env {
+    let env = Env::from_snapshot(env.to_snapshot());
+    env.mock_all_auths();
+    env
+}
+
+/// `address`, from another `Env`, in `env`
+pub fn address_in(env: &Env, address: &Address) -> Address {
+    Address::try_from_val(env, &ScAddress::from(address)).unwrap()
+}
+
+/// Whether `contract`'s persistent entry under `key` has outlived its
+/// TTL. Since protocol 23 the next invocation that touches an archived
+/// entry restores it, so this is how a test sees the archival happen.
+pub fn is_archived<K: IntoVal<Env, Val>>(env: &Env, contract: &Address, key: &K) -> bool {
+    let key = LedgerKey::ContractData(LedgerKeyContractData {
+        contract: contract.into(),
+        key: ScVal::try_from_val(env, &key.into_val(env)).unwrap(),
+        durability: ContractDataDurability::Persistent,
+    });
+    let sequence = env.ledger().sequence();
+    env.to_snapshot()
+        .ledger
+        .ledger_entries
+        .iter()
+        .find(|(entry_key, _)| **entry_key == key)
+        .is_some_and(|(_, (_, live_until))| live_until.is_some_and(|until| until < sequence))
+}
+
+/// The protocol's core contracts, deployed from their WASM builds and
+/// initialized with `admin`
+pub struct Protocol<'a> {
+    pub admin: Address,
+    pub token: TestToken<'a>,
+    pub streaming: StreamingClient<'a>,
+    pub escrow: EscrowClient<'a>,
+    pub upgradable: upgradable::Client<'a>,
+}
+
+/// Deploy and initialize the protocol's core contracts (requires auth to
+/// be mocked)
+pub fn deploy_protocol<'a>(env: &Env) -> Protocol<'a> {
+    let admin = Address::generate(env);
+    let token = create_token(env, &admin);
+
+    let streaming = StreamingClient::new(env, &env.register(STREAMING_WASM, ()));
+    streaming.initialize(&admin);
+    let escrow = EscrowClient::new(env, &env.register(ESCROW_WASM, ()));
+    escrow.initialize(&admin);
+    let upgradable = upgradable::Client::new(env, &env.register(upgradable::WASM, ()));
+    upgradable.init(&admin);
+
+    Protocol {
+        admin,
+        token,
+        streaming,
+        escrow,
+        upgradable,
+    }
+}
+
+#[cfg(test)]
+mod test;
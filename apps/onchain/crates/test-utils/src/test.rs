@@ -0,0 +1,123 @@
+use crate::auth::{assert_auth_matrix, Outcome};
+use crate::{
+    address_in, advance_ledgers, advance_time, archive_persistent, deploy_protocol,
+    funded_accounts, is_archived, jump_to_ledger, jump_to_time, persistent_ttl, reopen, setup_env,
+};
+use lumenpulse_streaming_interface::StreamingClient;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token::TokenClient,
+    Address, IntoVal,
+};
+
+#[test]
+fn test_protocol_fixture_streams_over_time() {
+    let env = setup_env();
+    let protocol = deploy_protocol(&env);
+    let [sender, recipient] = funded_accounts(&env, &protocol.token, 1_000);
+    assert_eq!(protocol.token.balance(&recipient), 1_000);
+
+    let stream_id = protocol.streaming.create_stream(
+        &sender,
+        &recipient,
+        &protocol.token.address,
+        &1_000,
+        &0,
+        &100,
+    );
+    advance_time(&env, 25);
+    assert_eq!(protocol.streaming.withdrawable_amount(&stream_id), 250);
+
+    advance_ledgers(&env, 3);
+    assert_eq!(env.ledger().timestamp(), 40);
+    assert_eq!(protocol.streaming.withdrawable_amount(&stream_id), 400);
+
+    assert_eq!(protocol.escrow.get_admin(), protocol.admin);
+    assert_eq!(protocol.upgradable.version(), 1);
+    assert_eq!(protocol.upgradable.increment(), 1);
+}
+
+#[test]
+fn test_stream_pays_out_exactly_at_end() {
+    let env = setup_env();
+    let protocol = deploy_protocol(&env);
+    let [sender, recipient] = funded_accounts(&env, &protocol.token, 1_000);
+    let stream_id = protocol.streaming.create_stream(
+        &sender,
+        &recipient,
+        &protocol.token.address,
+        &1_000,
+        &100,
+        &200,
+    );
+
+    jump_to_time(&env, 199);
+    assert_eq!(protocol.streaming.withdrawable_amount(&stream_id), 990);
+    jump_to_time(&env, 200);
+    assert_eq!(protocol.streaming.withdraw(&stream_id), 1_000);
+    jump_to_ledger(&env, env.ledger().sequence() + 10);
+    assert_eq!(protocol.streaming.withdrawable_amount(&stream_id), 0);
+}
+
+#[test]
+fn test_archived_stream_entry_is_restored_on_access() {
+    let env = setup_env();
+    let protocol = deploy_protocol(&env);
+    let [sender, recipient] = funded_accounts(&env, &protocol.token, 1_000);
+    let stream_id = protocol.streaming.create_stream(
+        &sender,
+        &recipient,
+        &protocol.token.address,
+        &1_000,
+        &0,
+        &100,
+    );
+
+    // The stream entry is keyed like the contract's `DataKey::Stream`
+    let key = (symbol_short!("Stream"), stream_id);
+    let streaming = protocol.streaming.address.clone();
+    archive_persistent(&env, &streaming, &key);
+    assert!(is_archived(&env, &streaming, &key));
+
+    // The next transaction restores everything it touches
+    let env = reopen(&env);
+    let streaming = address_in(&env, &streaming);
+    let recipient = address_in(&env, &recipient);
+    let key = (symbol_short!("Stream"), stream_id);
+    let client = StreamingClient::new(&env, &streaming);
+    assert_eq!(client.withdraw(&stream_id), 1_000);
+    assert!(!is_archived(&env, &streaming, &key));
+    assert_eq!(
+        persistent_ttl(&env, &streaming, &key),
+        env.ledger().get().min_persistent_entry_ttl - 1
+    );
+    let token = TokenClient::new(&env, &address_in(&env, &protocol.token.address));
+    assert_eq!(token.balance(&recipient), 2_000);
+}
+
+#[test]
+fn test_auth_matrix_against_wasm_build() {
+    let env = setup_env();
+    let protocol = deploy_protocol(&env);
+    let outsider = Address::generate(&env);
+
+    assert_auth_matrix(
+        &env,
+        &protocol.upgradable.address,
+        &[
+            (
+                &outsider,
+                "propose_admin",
+                (protocol.admin.clone(), outsider.clone()).into_val(&env),
+                Outcome::Denied,
+            ),
+            (
+                &protocol.admin,
+                "propose_admin",
+                (protocol.admin.clone(), outsider.clone()).into_val(&env),
+                Outcome::Allowed,
+            ),
+        ],
+    );
+}
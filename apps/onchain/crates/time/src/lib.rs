@@ -0,0 +1,250 @@
+#![no_std]
+//! Time math on Soroban ledgers for Lumenpulse contracts.
+//!
+//! Soroban gives a contract two clocks: the ledger sequence and the
+//! ledger's close timestamp. Sequences are what storage TTLs and
+//! expiration ledgers are counted in, but ledgers only close roughly every
+//! few seconds, so a duration converted to ledgers is an estimate that
+//! drifts with the real close times. Timestamps are exact but coarse, one
+//! per ledger. Schedules and deadlines here work on timestamps; `Network`
+//! converts durations to ledger counts where a ledger is what the host
+//! asks for.
+
+use soroban_sdk::{contracttype, Env};
+
+pub const MINUTE: u64 = 60;
+pub const HOUR: u64 = 60 * MINUTE;
+pub const DAY: u64 = 24 * HOUR;
+pub const WEEK: u64 = 7 * DAY;
+
+/// Network a contract is deployed to, which sets how often ledgers close
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Futurenet,
+    /// The quickstart image's local network, which closes a ledger every
+    /// second
+    Standalone,
+}
+
+impl Network {
+    /// Seconds the network aims to take closing each ledger
+    pub const fn ledger_close_time(self) -> u64 {
+        match self {
+            Network::Mainnet | Network::Testnet | Network::Futurenet => 5,
+            Network::Standalone => 1,
+        }
+    }
+
+    /// Ledgers that close over `seconds` at the target close time, rounded
+    /// up so the count never covers less than `seconds`
+    pub const fn ledgers_in(self, seconds: u64) -> u32 {
+        let ledgers = seconds.div_ceil(self.ledger_close_time());
+        if ledgers > u32::MAX as u64 {
+            u32::MAX
+        } else {
+            ledgers as u32
+        }
+    }
+
+    /// Seconds `ledgers` take to close at the target close time
+    pub const fn seconds_in(self, ledgers: u32) -> u64 {
+        ledgers as u64 * self.ledger_close_time()
+    }
+}
+
+/// How often a schedule recurs
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Cadence {
+    /// A fixed number of seconds
+    Every(u64),
+    Weekly,
+    /// The anchor's day of the month, or the month's last day when it is
+    /// shorter, at the anchor's time of day (UTC)
+    Monthly,
+}
+
+/// Timestamps recurring at `cadence` from `anchor`, the schedule's first
+/// occurrence
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Schedule {
+    pub anchor: u64,
+    pub cadence: Cadence,
+}
+
+impl Schedule {
+    pub const fn every(anchor: u64, seconds: u64) -> Self {
+        Schedule {
+            anchor,
+            cadence: Cadence::Every(seconds),
+        }
+    }
+
+    pub const fn weekly(anchor: u64) -> Self {
+        Schedule {
+            anchor,
+            cadence: Cadence::Weekly,
+        }
+    }
+
+    pub const fn monthly(anchor: u64) -> Self {
+        Schedule {
+            anchor,
+            cadence: Cadence::Monthly,
+        }
+    }
+
+    /// Whether the schedule moves forward at all
+    pub fn is_valid(&self) -> bool {
+        self.cadence != Cadence::Every(0)
+    }
+
+    /// Timestamp of the `n`th occurrence, the anchor being the 0th, or
+    /// `None` past the end of time
+    pub fn occurrence(&self, n: u32) -> Option<u64> {
+        match self.cadence {
+            Cadence::Every(seconds) => self.anchor.checked_add((n as u64).checked_mul(seconds)?),
+            Cadence::Weekly => self.anchor.checked_add(n as u64 * WEEK),
+            Cadence::Monthly => {
+                let (year, month, day) = civil_from_days(self.anchor / DAY);
+                let months = month as u64 - 1 + n as u64;
+                let year = year + months / 12;
+                let month = (months % 12) as u32 + 1;
+                let day = day.min(days_in_month(year, month));
+                days_from_civil(year, month, day)
+                    .checked_mul(DAY)?
+                    .checked_add(self.anchor % DAY)
+            }
+        }
+    }
+
+    /// Occurrences after the anchor that have been reached by `now`, i.e.
+    /// whole periods elapsed
+    pub fn periods_elapsed(&self, now: u64) -> u32 {
+        if now < self.anchor || !self.is_valid() {
+            return 0;
+        }
+        // Estimate from the elapsed time, then step to the exact count
+        let estimate = match self.cadence {
+            Cadence::Every(seconds) => (now - self.anchor) / seconds,
+            Cadence::Weekly => (now - self.anchor) / WEEK,
+            Cadence::Monthly => {
+                let (from_year, from_month, _) = civil_from_days(self.anchor / DAY);
+                let (to_year, to_month, _) = civil_from_days(now / DAY);
+                (to_year * 12 + to_month as u64) - (from_year * 12 + from_month as u64)
+            }
+        };
+        let mut periods = estimate.min(u32::MAX as u64) as u32;
+        while periods > 0 && self.occurrence(periods).is_none_or(|at| at > now) {
+            periods -= 1;
+        }
+        periods
+    }
+
+    /// First occurrence strictly after `now`, if any
+    pub fn next_after(&self, now: u64) -> Option<u64> {
+        if now < self.anchor {
+            return Some(self.anchor);
+        }
+        if !self.is_valid() {
+            return None;
+        }
+        self.occurrence(self.periods_elapsed(now).checked_add(1)?)
+    }
+}
+
+/// Seconds since `start` as of `now`, zero before it
+pub fn since(start: u64, now: u64) -> u64 {
+    now.saturating_sub(start)
+}
+
+/// Seconds of the window from `start` to `end` that have passed by `now`
+pub fn elapsed_within(start: u64, end: u64, now: u64) -> u64 {
+    since(start, now.min(end))
+}
+
+/// Timestamp `seconds` from the current ledger's, or `None` if it
+/// overflows
+pub fn deadline(env: &Env, seconds: u64) -> Option<u64> {
+    env.ledger().timestamp().checked_add(seconds)
+}
+
+/// Whether the current ledger closed at or after `at`
+pub fn reached(env: &Env, at: u64) -> bool {
+    env.ledger().timestamp() >= at
+}
+
+/// Seconds left until `at`, zero once it has been reached
+pub fn remaining(env: &Env, at: u64) -> u64 {
+    at.saturating_sub(env.ledger().timestamp())
+}
+
+/// Ledger that closes no sooner than `seconds` from now while ledgers keep
+/// to `network`'s close time. Ledgers that close faster than the target
+/// bring it forward, so use a timestamp deadline where an exact time
+/// matters and this where the host wants a ledger.
+pub fn expiration_ledger(env: &Env, network: Network, seconds: u64) -> u32 {
+    env.ledger()
+        .sequence()
+        .saturating_add(network.ledgers_in(seconds))
+}
+
+/// Estimated close time of `ledger`, counting from the current ledger at
+/// `network`'s close time. Past ledgers read as the current timestamp.
+pub fn estimated_time_at(env: &Env, network: Network, ledger: u32) -> u64 {
+    let ledgers = ledger.saturating_sub(env.ledger().sequence());
+    env.ledger()
+        .timestamp()
+        .saturating_add(network.seconds_in(ledgers))
+}
+
+fn is_leap_year(year: u64) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since the Unix epoch of a proleptic Gregorian date, after Howard
+/// Hinnant's `days_from_civil`. Dates before 1970 never occur here.
+fn days_from_civil(year: u64, month: u32, day: u32) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let month = month as u64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as u64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Year, month and day of `days` since the Unix epoch
+fn civil_from_days(days: u64) -> (u64, u32, u32) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test;
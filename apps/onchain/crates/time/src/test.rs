@@ -0,0 +1,88 @@
+use crate::{Network, Schedule, DAY, HOUR, WEEK};
+use soroban_sdk::{testutils::Ledger, Env};
+
+/// 2023-01-31 09:30 UTC
+const JAN_31_2023: u64 = 1_675_157_400;
+
+#[test]
+fn test_ledger_counts() {
+    assert_eq!(Network::Mainnet.ledgers_in(DAY), 17_280);
+    assert_eq!(Network::Testnet.ledgers_in(0), 0);
+    // A partial ledger rounds up so the count covers the whole duration
+    assert_eq!(Network::Testnet.ledgers_in(11), 3);
+    assert_eq!(Network::Standalone.ledgers_in(HOUR), 3_600);
+    assert_eq!(Network::Mainnet.ledgers_in(u64::MAX), u32::MAX);
+    assert_eq!(Network::Futurenet.seconds_in(17_280), DAY);
+}
+
+#[test]
+fn test_monthly_and_weekly_anchors() {
+    let monthly = Schedule::monthly(JAN_31_2023);
+    // Short months clamp to their last day without moving later months
+    assert_eq!(monthly.occurrence(1), Some(1_677_576_600)); // 2023-02-28
+    assert_eq!(monthly.occurrence(2), Some(1_680_255_000)); // 2023-03-31
+    assert_eq!(monthly.occurrence(3), Some(1_682_847_000)); // 2023-04-30
+    assert_eq!(monthly.occurrence(13), Some(1_709_199_000)); // 2024-02-29
+    assert_eq!(monthly.occurrence(12), Some(1_706_693_400)); // 2024-01-31
+
+    assert_eq!(monthly.periods_elapsed(JAN_31_2023 - 1), 0);
+    assert_eq!(monthly.periods_elapsed(1_677_576_600 - 1), 0);
+    assert_eq!(monthly.periods_elapsed(1_677_576_600), 1);
+    // Early March is still in the period that started on February 28th
+    assert_eq!(monthly.periods_elapsed(1_677_576_600 + 3 * DAY), 1);
+    assert_eq!(
+        monthly.next_after(1_677_576_600 + 3 * DAY),
+        Some(1_680_255_000)
+    );
+
+    let weekly = Schedule::weekly(JAN_31_2023);
+    assert_eq!(weekly.periods_elapsed(JAN_31_2023 + 3 * WEEK - 1), 2);
+    assert_eq!(weekly.next_after(JAN_31_2023 - 1), Some(JAN_31_2023));
+    assert_eq!(weekly.next_after(JAN_31_2023), Some(JAN_31_2023 + WEEK));
+    assert!(!Schedule::every(JAN_31_2023, 0).is_valid());
+    assert_eq!(
+        Schedule::every(JAN_31_2023, 0).next_after(JAN_31_2023),
+        None
+    );
+}
+
+#[test]
+fn test_deadlines_under_close_time_drift() {
+    let env = Env::default();
+    env.ledger().with_mut(|ledger| {
+        ledger.sequence_number = 1_000;
+        ledger.timestamp = JAN_31_2023;
+    });
+    let expiration = crate::expiration_ledger(&env, Network::Mainnet, HOUR);
+    let deadline = crate::deadline(&env, HOUR).unwrap();
+    assert_eq!(expiration, 1_720);
+    assert_eq!(
+        crate::estimated_time_at(&env, Network::Mainnet, expiration),
+        deadline
+    );
+
+    // Ledgers closing slower than the target push the expiration ledger
+    // past the deadline; the timestamp deadline is met on time regardless
+    env.ledger().with_mut(|ledger| {
+        ledger.sequence_number += 600;
+        ledger.timestamp += 600 * 6;
+    });
+    assert!(crate::reached(&env, deadline));
+    assert!(env.ledger().sequence() < expiration);
+    assert_eq!(crate::remaining(&env, deadline), 0);
+
+    // Ledgers closing faster bring the expiration ledger in early, which
+    // the estimate from the new position reflects
+    env.ledger().with_mut(|ledger| {
+        ledger.sequence_number = 1_000 + 360;
+        ledger.timestamp = JAN_31_2023 + 360 * 4;
+    });
+    assert_eq!(crate::remaining(&env, deadline), HOUR - 360 * 4);
+    assert_eq!(
+        crate::estimated_time_at(&env, Network::Mainnet, expiration),
+        deadline - 360
+    );
+    // Periods follow timestamps, so a late ledger catches up every missed one
+    let schedule = Schedule::every(JAN_31_2023, 600);
+    assert_eq!(schedule.periods_elapsed(env.ledger().timestamp()), 2);
+}
@@ -0,0 +1,145 @@
+#![no_std]
+//! Client and types of the Lumenpulse token contract.
+//!
+//! Contracts that call the token, and off-chain Rust that talks to it,
+//! depend on this crate instead of the contract itself, so none of its
+//! implementation ends up in their WASM. The token contract uses these
+//! same types, so the two cannot drift apart.
+//!
+//! The token also serves the standard SEP-41 interface, reached through
+//! `soroban_sdk::token::TokenClient`; only its own entrypoints are listed
+//! here. It fails by panicking rather than with an error enum.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, String};
+
+/// Fee taken out of every transfer and paid to `treasury`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TransferFee {
+    pub bps: u32,
+    pub treasury: Address,
+}
+
+/// What a transfer of `amount` actually delivers
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TransferQuote {
+    pub amount: i128,
+    pub fee: i128,
+    pub received: i128,
+}
+
+/// Where a hook sits in the transfer: the sender hook typically screens
+/// outgoing funds, the recipient hook screens who may receive them
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HookKind {
+    Sender,
+    Recipient,
+}
+
+/// Capabilities the admin can delegate to other accounts
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    Minter,
+    Burner,
+}
+
+/// Contract consulted before every transfer. Returning `false` vetoes it.
+/// It runs on the transfer's budget, so it should be cheap: running out
+/// fails the transfer's whole transaction.
+#[contractclient(name = "TransferHookClient")]
+pub trait TransferHook {
+    fn on_transfer(env: Env, token: Address, from: Address, to: Address, amount: i128) -> bool;
+}
+
+/// Entrypoints of the token contract besides SEP-41 and the standard
+/// upgrade entrypoints
+#[contractclient(name = "LumenTokenClient")]
+pub trait LumenToken {
+    fn initialize(e: Env, admin: Address, decimal: u32, name: String, symbol: String);
+
+    /// Mint new tokens. `minter` must hold the minter role, and the total
+    /// supply may not exceed the cap.
+    fn mint(e: Env, minter: Address, to: Address, amount: i128);
+
+    fn grant_role(e: Env, role: Role, account: Address);
+
+    fn revoke_role(e: Env, role: Role, account: Address);
+
+    /// Take tokens back from `from` and destroy them (admin only). Like a
+    /// Stellar asset clawback this works on frozen accounts and needs no
+    /// consent from the holder.
+    fn clawback(e: Env, from: Address, amount: i128);
+
+    /// Approve `spender` on the owner's behalf using an authorization the
+    /// owner signed off-chain over `(spender, amount, nonce, expiration_ledger)`.
+    /// Anyone may submit it, so a relayer can bundle the approval with the
+    /// call that spends it. Each nonce is usable once, in order, and the
+    /// permit is void after `expiration_ledger`.
+    fn permit(
+        e: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        nonce: u64,
+        expiration_ledger: u32,
+    );
+
+    /// Next nonce the owner must sign into a permit
+    fn permit_nonce(e: Env, owner: Address) -> u64;
+
+    /// Register or clear the hook consulted on every transfer
+    fn set_transfer_hook(e: Env, kind: HookKind, hook: Option<Address>);
+
+    fn transfer_hook(e: Env, kind: HookKind) -> Option<Address>;
+
+    fn has_role(e: Env, role: Role, account: Address) -> bool;
+
+    /// Cap the total supply. The cap cannot be set below the current supply.
+    fn set_supply_cap(e: Env, cap: i128);
+
+    fn supply_cap(e: Env) -> Option<i128>;
+
+    fn total_supply(e: Env) -> i128;
+
+    /// Take a fee on every transfer, paid to the fee's treasury out of the
+    /// transferred amount (admin only). `None` turns the fee off.
+    fn set_transfer_fee(e: Env, fee: Option<TransferFee>);
+
+    fn transfer_fee(e: Env) -> Option<TransferFee>;
+
+    /// Split of a transfer of `amount` between fee and recipient. The sender
+    /// is always debited the full `amount`; the recipient receives
+    /// `received`, so integrators that need an exact delivered amount must
+    /// gross it up before transferring.
+    fn quote_transfer(e: Env, amount: i128) -> TransferQuote;
+
+    /// Balance of `id` as of the end of a past `ledger`, for vote weighting
+    fn balance_at(e: Env, id: Address, ledger: u32) -> i128;
+
+    /// Total supply as of the end of a past `ledger`
+    fn total_supply_at(e: Env, ledger: u32) -> i128;
+
+    /// Hand `delegator`'s voting power to `delegatee`. Delegating to
+    /// oneself takes it back.
+    fn delegate(e: Env, delegator: Address, delegatee: Address);
+
+    /// Address currently voting with `account`'s balance
+    fn delegates(e: Env, account: Address) -> Address;
+
+    /// Voting power currently delegated to `account`
+    fn votes(e: Env, account: Address) -> i128;
+
+    /// Voting power delegated to `account` as of the end of a past `ledger`
+    fn votes_at(e: Env, account: Address, ledger: u32) -> i128;
+
+    fn set_admin(e: Env, new_admin: Address);
+
+    fn admin(e: Env) -> Address;
+
+    fn freeze(e: Env, id: Address);
+
+    fn unfreeze(e: Env, id: Address);
+}
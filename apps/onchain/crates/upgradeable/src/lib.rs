@@ -0,0 +1,388 @@
+#![no_std]
+//! Timelocked WASM upgrades for Lumenpulse contracts.
+//!
+//! A contract implements `Upgradeable` to name who manages its upgrades,
+//! then invokes `upgradeable!(Contract)` once to gain the standard upgrade
+//! entrypoints:
+//!
+//! - `propose_upgrade` queues a new WASM hash and release version, which
+//!   can be installed once the upgrade delay has passed
+//! - `execute_upgrade` installs the queued WASM
+//! - `cancel_upgrade` drops the queued upgrade
+//! - `rollback_upgrade` reinstalls the release the last upgrade replaced,
+//!   without waiting, for when a release misbehaves
+//! - `current_version`, `current_release`, `previous_release` and
+//!   `pending_upgrade` report where the contract stands
+//!
+//! Storage is kept across upgrades. Releases are only known once they
+//! have been installed through `execute_upgrade` or `install_next`, so a
+//! contract that has never been upgraded this way reports version 0 and
+//! has nothing to roll back to.
+//!
+//! `install_next` skips the delay, for a contract's own entrypoint serving
+//! an upgrader it trusts, such as the factory rolling out new children.
+//!
+//! `UpgradeableClient` calls these entrypoints from another contract, for
+//! contracts that coordinate upgrades across several others.
+
+use soroban_sdk::{
+    contractclient, contracterror, contractevent, contracttype, Address, BytesN, Env,
+};
+
+/// Seconds between proposing and executing an upgrade, unless the
+/// contract sets its own (two days)
+pub const DEFAULT_UPGRADE_DELAY: u64 = 2 * 24 * 60 * 60;
+
+/// How a contract's upgrades are governed
+pub trait Upgradeable {
+    /// Address allowed to propose, execute, cancel and roll back upgrades,
+    /// if the contract has been set up
+    fn upgrade_admin(env: &Env) -> Option<Address>;
+
+    /// Seconds an upgrade must wait between being proposed and executed
+    fn upgrade_delay(_env: &Env) -> u64 {
+        DEFAULT_UPGRADE_DELAY
+    }
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum UpgradeKey {
+    PendingUpgrade,  // -> PendingUpgrade
+    CurrentRelease,  // -> Release
+    PreviousRelease, // -> Release
+}
+
+/// A WASM release of the contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Release {
+    pub wasm_hash: BytesN<32>,
+    pub version: u32,
+}
+
+/// An upgrade waiting out its delay
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingUpgrade {
+    pub release: Release,
+    /// Earliest time the upgrade can be executed
+    pub eta: u64,
+}
+
+/// Codes start at 1000 so they never overlap a contract's own errors
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum UpgradeError {
+    /// The caller is not the upgrade admin, or there is none yet
+    Unauthorized = 1000,
+    NoPendingUpgrade = 1001,
+    /// Another upgrade is already queued
+    UpgradePending = 1002,
+    /// The upgrade's delay has not passed
+    TooEarly = 1003,
+    /// Releases must be newer than the installed one
+    InvalidVersion = 1004,
+    NothingToRollBack = 1005,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeProposedEvent {
+    #[topic]
+    pub version: u32,
+    pub wasm_hash: BytesN<32>,
+    pub eta: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeCancelledEvent {
+    #[topic]
+    pub version: u32,
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeExecutedEvent {
+    #[topic]
+    pub version: u32,
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeRolledBackEvent {
+    #[topic]
+    pub version: u32,
+    pub wasm_hash: BytesN<32>,
+    /// Version rolled back from
+    pub from_version: u32,
+}
+
+/// Queue `wasm_hash` as release `version`. Returns the time it can be
+/// executed at.
+pub fn propose<T: Upgradeable>(
+    env: &Env,
+    caller: &Address,
+    wasm_hash: &BytesN<32>,
+    version: u32,
+) -> Result<u64, UpgradeError> {
+    require_upgrade_admin::<T>(env, caller)?;
+
+    if pending_upgrade(env).is_some() {
+        return Err(UpgradeError::UpgradePending);
+    }
+    if version <= current_version(env) {
+        return Err(UpgradeError::InvalidVersion);
+    }
+    let eta = env.ledger().timestamp() + T::upgrade_delay(env);
+    env.storage().instance().set(
+        &UpgradeKey::PendingUpgrade,
+        &PendingUpgrade {
+            release: Release {
+                wasm_hash: wasm_hash.clone(),
+                version,
+            },
+            eta,
+        },
+    );
+
+    UpgradeProposedEvent {
+        version,
+        wasm_hash: wasm_hash.clone(),
+        eta,
+    }
+    .publish(env);
+
+    Ok(eta)
+}
+
+/// Install the queued upgrade once its delay has passed
+pub fn execute<T: Upgradeable>(env: &Env, caller: &Address) -> Result<(), UpgradeError> {
+    require_upgrade_admin::<T>(env, caller)?;
+
+    let pending = pending_upgrade(env).ok_or(UpgradeError::NoPendingUpgrade)?;
+    if env.ledger().timestamp() < pending.eta {
+        return Err(UpgradeError::TooEarly);
+    }
+    env.storage().instance().remove(&UpgradeKey::PendingUpgrade);
+    if let Some(current) = current_release(env) {
+        env.storage()
+            .instance()
+            .set(&UpgradeKey::PreviousRelease, &current);
+    }
+    install(env, &pending.release);
+
+    UpgradeExecutedEvent {
+        version: pending.release.version,
+        wasm_hash: pending.release.wasm_hash,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Install `wasm_hash` at once as the release after the current one,
+/// keeping the current one to roll back to. Returns the new version.
+///
+/// This skips the delay, so it is only for callers the contract already
+/// trusts with immediate upgrades, which it must authorize itself. It
+/// refuses while an upgrade is queued, which must be executed or
+/// cancelled first.
+pub fn install_next(env: &Env, wasm_hash: &BytesN<32>) -> Result<u32, UpgradeError> {
+    if pending_upgrade(env).is_some() {
+        return Err(UpgradeError::UpgradePending);
+    }
+    let release = Release {
+        wasm_hash: wasm_hash.clone(),
+        version: current_version(env) + 1,
+    };
+    if let Some(current) = current_release(env) {
+        env.storage()
+            .instance()
+            .set(&UpgradeKey::PreviousRelease, &current);
+    }
+    install(env, &release);
+
+    UpgradeExecutedEvent {
+        version: release.version,
+        wasm_hash: release.wasm_hash,
+    }
+    .publish(env);
+
+    Ok(release.version)
+}
+
+/// Drop the queued upgrade
+pub fn cancel<T: Upgradeable>(env: &Env, caller: &Address) -> Result<(), UpgradeError> {
+    require_upgrade_admin::<T>(env, caller)?;
+
+    let pending = pending_upgrade(env).ok_or(UpgradeError::NoPendingUpgrade)?;
+    env.storage().instance().remove(&UpgradeKey::PendingUpgrade);
+
+    UpgradeCancelledEvent {
+        version: pending.release.version,
+        wasm_hash: pending.release.wasm_hash,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Reinstall the release the last upgrade replaced, dropping any queued
+/// upgrade. Only one step back is kept.
+pub fn rollback<T: Upgradeable>(env: &Env, caller: &Address) -> Result<(), UpgradeError> {
+    require_upgrade_admin::<T>(env, caller)?;
+
+    let previous = previous_release(env).ok_or(UpgradeError::NothingToRollBack)?;
+    let from_version = current_version(env);
+    env.storage().instance().remove(&UpgradeKey::PendingUpgrade);
+    env.storage()
+        .instance()
+        .remove(&UpgradeKey::PreviousRelease);
+    install(env, &previous);
+
+    UpgradeRolledBackEvent {
+        version: previous.version,
+        wasm_hash: previous.wasm_hash,
+        from_version,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Version of the installed release, or 0 if it was never installed
+/// through `execute` or `install_next`
+pub fn current_version(env: &Env) -> u32 {
+    current_release(env).map_or(0, |release| release.version)
+}
+
+/// Release installed by the last upgrade or rollback, if any
+pub fn current_release(env: &Env) -> Option<Release> {
+    env.storage().instance().get(&UpgradeKey::CurrentRelease)
+}
+
+/// Release a rollback would reinstall, if any
+pub fn previous_release(env: &Env) -> Option<Release> {
+    env.storage().instance().get(&UpgradeKey::PreviousRelease)
+}
+
+/// Upgrade waiting out its delay, if any
+pub fn pending_upgrade(env: &Env) -> Option<PendingUpgrade> {
+    env.storage().instance().get(&UpgradeKey::PendingUpgrade)
+}
+
+fn require_upgrade_admin<T: Upgradeable>(env: &Env, caller: &Address) -> Result<(), UpgradeError> {
+    if T::upgrade_admin(env).as_ref() != Some(caller) {
+        return Err(UpgradeError::Unauthorized);
+    }
+    caller.require_auth();
+    Ok(())
+}
+
+fn install(env: &Env, release: &Release) {
+    env.storage()
+        .instance()
+        .set(&UpgradeKey::CurrentRelease, release);
+    env.deployer()
+        .update_current_contract_wasm(release.wasm_hash.clone());
+}
+
+/// Entrypoints `upgradeable!` adds, as seen by a calling contract
+#[contractclient(name = "UpgradeableClient")]
+pub trait UpgradeableInterface {
+    fn propose_upgrade(
+        env: Env,
+        caller: Address,
+        wasm_hash: BytesN<32>,
+        version: u32,
+    ) -> Result<u64, UpgradeError>;
+
+    fn execute_upgrade(env: Env, caller: Address) -> Result<(), UpgradeError>;
+
+    fn cancel_upgrade(env: Env, caller: Address) -> Result<(), UpgradeError>;
+
+    fn rollback_upgrade(env: Env, caller: Address) -> Result<(), UpgradeError>;
+
+    fn current_version(env: Env) -> u32;
+
+    fn current_release(env: Env) -> Option<Release>;
+
+    fn previous_release(env: Env) -> Option<Release>;
+
+    fn pending_upgrade(env: Env) -> Option<PendingUpgrade>;
+}
+
+/// Add the standard upgrade entrypoints to a contract implementing
+/// `Upgradeable`
+#[macro_export]
+macro_rules! upgradeable {
+    ($contract:ident) => {
+        #[soroban_sdk::contractimpl]
+        impl $contract {
+            /// Queue `wasm_hash` as release `version` (upgrade admin
+            /// only). Returns the time it can be executed at.
+            pub fn propose_upgrade(
+                env: soroban_sdk::Env,
+                caller: soroban_sdk::Address,
+                wasm_hash: soroban_sdk::BytesN<32>,
+                version: u32,
+            ) -> Result<u64, $crate::UpgradeError> {
+                $crate::propose::<$contract>(&env, &caller, &wasm_hash, version)
+            }
+
+            /// Install the queued upgrade once its delay has passed
+            /// (upgrade admin only). Storage is kept.
+            pub fn execute_upgrade(
+                env: soroban_sdk::Env,
+                caller: soroban_sdk::Address,
+            ) -> Result<(), $crate::UpgradeError> {
+                $crate::execute::<$contract>(&env, &caller)
+            }
+
+            /// Drop the queued upgrade (upgrade admin only)
+            pub fn cancel_upgrade(
+                env: soroban_sdk::Env,
+                caller: soroban_sdk::Address,
+            ) -> Result<(), $crate::UpgradeError> {
+                $crate::cancel::<$contract>(&env, &caller)
+            }
+
+            /// Reinstall the release the last upgrade replaced (upgrade
+            /// admin only)
+            pub fn rollback_upgrade(
+                env: soroban_sdk::Env,
+                caller: soroban_sdk::Address,
+            ) -> Result<(), $crate::UpgradeError> {
+                $crate::rollback::<$contract>(&env, &caller)
+            }
+
+            /// Version of the installed release, 0 before the first upgrade
+            pub fn current_version(env: soroban_sdk::Env) -> u32 {
+                $crate::current_version(&env)
+            }
+
+            /// Release installed by the last upgrade or rollback, if any
+            pub fn current_release(env: soroban_sdk::Env) -> Option<$crate::Release> {
+                $crate::current_release(&env)
+            }
+
+            /// Release a rollback would reinstall, if any
+            pub fn previous_release(env: soroban_sdk::Env) -> Option<$crate::Release> {
+                $crate::previous_release(&env)
+            }
+
+            /// Upgrade waiting out its delay, if any
+            pub fn pending_upgrade(env: soroban_sdk::Env) -> Option<$crate::PendingUpgrade> {
+                $crate::pending_upgrade(&env)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,106 @@
+use crate::{Release, UpgradeError, Upgradeable, DEFAULT_UPGRADE_DELAY};
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+/// Contract the factory's fixtures upgrade to; it exposes `hello`
+const HELLO_WASM: &[u8] = include_bytes!("../../../contracts/factory/testdata/hello_world.wasm");
+
+#[contracttype]
+enum DataKey {
+    Admin,
+}
+
+#[contract]
+struct Managed;
+
+#[contractimpl]
+impl Managed {
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+    }
+}
+
+impl Upgradeable for Managed {
+    fn upgrade_admin(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Admin)
+    }
+}
+
+upgradeable!(Managed);
+
+#[test]
+fn test_timelocked_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let client = ManagedClient::new(&env, &env.register(Managed, (&admin,)));
+    let wasm_hash = env.deployer().upload_contract_wasm(HELLO_WASM);
+
+    let result = client.try_propose_upgrade(&Address::generate(&env), &wasm_hash, &1);
+    assert_eq!(result, Err(Ok(UpgradeError::Unauthorized)));
+    let result = client.try_rollback_upgrade(&admin);
+    assert_eq!(result, Err(Ok(UpgradeError::NothingToRollBack)));
+
+    // One upgrade is queued at a time, and can be dropped
+    assert_eq!(
+        client.propose_upgrade(&admin, &wasm_hash, &1),
+        DEFAULT_UPGRADE_DELAY
+    );
+    let result = client.try_propose_upgrade(&admin, &wasm_hash, &2);
+    assert_eq!(result, Err(Ok(UpgradeError::UpgradePending)));
+    client.cancel_upgrade(&admin);
+    assert_eq!(client.pending_upgrade(), None);
+
+    // The delay has to pass before the WASM is replaced
+    client.propose_upgrade(&admin, &wasm_hash, &1);
+    env.ledger().set_timestamp(DEFAULT_UPGRADE_DELAY - 1);
+    let result = client.try_execute_upgrade(&admin);
+    assert_eq!(result, Err(Ok(UpgradeError::TooEarly)));
+    assert_eq!(client.current_version(), 0);
+    env.ledger().set_timestamp(DEFAULT_UPGRADE_DELAY);
+    client.execute_upgrade(&admin);
+
+    // The contract now runs the new release, with its storage kept
+    let release = Release {
+        wasm_hash,
+        version: 1,
+    };
+    let stored: Option<Release> = env.as_contract(&client.address, || crate::current_release(&env));
+    assert_eq!(stored, Some(release));
+    let admin_kept: Option<Address> =
+        env.as_contract(&client.address, || Managed::upgrade_admin(&env));
+    assert_eq!(admin_kept, Some(admin));
+}
+
+#[test]
+fn test_install_next_records_the_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let client = ManagedClient::new(&env, &env.register(Managed, (&admin,)));
+    let wasm_hash = env.deployer().upload_contract_wasm(HELLO_WASM);
+
+    // A queued upgrade has to be settled first
+    client.propose_upgrade(&admin, &wasm_hash, &1);
+    let result = env.as_contract(&client.address, || crate::install_next(&env, &wasm_hash));
+    assert_eq!(result, Err(UpgradeError::UpgradePending));
+    client.cancel_upgrade(&admin);
+
+    let first = env.as_contract(&client.address, || crate::install_next(&env, &wasm_hash));
+    let second = env.as_contract(&client.address, || crate::install_next(&env, &wasm_hash));
+    assert_eq!((first, second), (Ok(1), Ok(2)));
+
+    // The release it replaced is kept for a rollback
+    let previous: Option<Release> =
+        env.as_contract(&client.address, || crate::previous_release(&env));
+    assert_eq!(
+        previous,
+        Some(Release {
+            wasm_hash,
+            version: 1,
+        })
+    );
+}
@@ -0,0 +1,89 @@
+#![no_main]
+
+//! Random sequences of stream operations with arbitrary amounts and
+//! times. Every failure must be one of the contract's defined errors; a
+//! trap (panic, overflow) or an accounting mismatch is a bug.
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use lumenpulse_fuzz::{check, quiet_env};
+use lumenpulse_test_utils::{advance_time, create_token, funded_accounts};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::Address;
+use streaming::{StreamingContract, StreamingContractClient};
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Create {
+        amount: i128,
+        start_time: u64,
+        end_time: u64,
+    },
+    Withdraw(u8),
+    Cancel(u8),
+    Deposit(i128),
+    WithdrawDeposit(i128),
+    Advance(u32),
+}
+
+fuzz_target!(|ops: Vec<Op>| {
+    let env = quiet_env();
+    let admin = Address::generate(&env);
+    let token = create_token(&env, &admin);
+    let [sender, recipient] = funded_accounts(&env, &token, 1_000_000_000);
+    let client = StreamingContractClient::new(&env, &env.register(StreamingContract, ()));
+    client.initialize(&admin);
+    let minted = 2_000_000_000;
+
+    let mut ids = Vec::new();
+    for op in ops.into_iter().take(64) {
+        match op {
+            Op::Create {
+                amount,
+                start_time,
+                end_time,
+            } => {
+                let result = client.try_create_stream(
+                    &sender,
+                    &recipient,
+                    &token.address,
+                    &amount,
+                    &start_time,
+                    &end_time,
+                );
+                if let Some(id) = check(result) {
+                    ids.push(id);
+                }
+            }
+            Op::Withdraw(i) if !ids.is_empty() => {
+                check(client.try_withdraw(&ids[i as usize % ids.len()]));
+            }
+            Op::Cancel(i) if !ids.is_empty() => {
+                check(client.try_cancel(&ids[i as usize % ids.len()]));
+            }
+            Op::Deposit(amount) => {
+                check(client.try_deposit(&sender, &token.address, &amount));
+            }
+            Op::WithdrawDeposit(amount) => {
+                check(client.try_withdraw_deposit(&sender, &token.address, &amount));
+            }
+            Op::Advance(seconds) => advance_time(&env, seconds as u64),
+            _ => {}
+        }
+
+        // The contract holds exactly what open streams and deposits owe
+        let mut held = client.get_deposit_balance(&sender, &token.address);
+        for id in &ids {
+            let stream = client.get_stream(id);
+            assert!(0 <= stream.withdrawn && stream.withdrawn <= stream.deposit);
+            if !stream.cancelled {
+                held += stream.deposit - stream.withdrawn;
+            }
+        }
+        assert_eq!(token.balance(&client.address), held);
+        assert_eq!(
+            token.balance(&sender) + token.balance(&recipient) + held,
+            minted
+        );
+    }
+});
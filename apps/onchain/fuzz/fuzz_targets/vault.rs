@@ -0,0 +1,73 @@
+#![no_main]
+
+//! Random deposits, mints, withdrawals, redemptions and share transfers
+//! with arbitrary amounts between two holders. Every failure must be one
+//! of the vault's defined errors, and shares and assets must stay backed.
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use lumenpulse_fuzz::{check, quiet_env};
+use lumenpulse_test_utils::{create_token, funded_accounts};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::Address;
+use vault::{VaultContract, VaultContractClient};
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Deposit { alice: bool, assets: i128 },
+    Mint { alice: bool, shares: i128 },
+    Withdraw { alice: bool, assets: i128 },
+    Redeem { alice: bool, shares: i128 },
+    TransferShares { alice: bool, shares: i128 },
+}
+
+fuzz_target!(|ops: Vec<Op>| {
+    let env = quiet_env();
+    let admin = Address::generate(&env);
+    let token = create_token(&env, &admin);
+    let [alice, bob] = funded_accounts(&env, &token, 1_000_000_000);
+    let client = VaultContractClient::new(&env, &env.register(VaultContract, ()));
+    client.initialize(&admin, &token.address);
+
+    let pick = |is_alice: bool| {
+        if is_alice {
+            (&alice, &bob)
+        } else {
+            (&bob, &alice)
+        }
+    };
+    for op in ops.into_iter().take(64) {
+        match op {
+            Op::Deposit { alice, assets } => {
+                check(client.try_deposit(pick(alice).0, &assets));
+            }
+            Op::Mint { alice, shares } => {
+                check(client.try_mint(pick(alice).0, &shares));
+            }
+            Op::Withdraw { alice, assets } => {
+                let (owner, _) = pick(alice);
+                check(client.try_withdraw(owner, owner, &assets));
+            }
+            Op::Redeem { alice, shares } => {
+                let (owner, _) = pick(alice);
+                check(client.try_redeem(owner, owner, &shares));
+            }
+            Op::TransferShares { alice, shares } => {
+                let (from, to) = pick(alice);
+                check(client.try_transfer_shares(from, to, &shares));
+            }
+        }
+
+        // Every share is held by someone, and shares only exist while the
+        // vault holds assets to redeem them for
+        let shares = client.balance_of(&alice) + client.balance_of(&bob);
+        assert_eq!(client.total_shares(), shares);
+        let assets = client.total_assets();
+        assert_eq!(token.balance(&client.address), assets);
+        assert!(shares == 0 || assets > 0 || client.convert_to_assets(&shares) == 0);
+        assert_eq!(
+            token.balance(&alice) + token.balance(&bob) + assets,
+            2_000_000_000
+        );
+    }
+});
@@ -0,0 +1,34 @@
+//! Shared pieces of the fuzz targets.
+//!
+//! Run a target with `cargo +nightly fuzz run <target>` from this
+//! directory.
+
+use core::fmt::Debug;
+use core::ops::Range;
+use soroban_sdk::{testutils::EnvTestConfig, Env, InvokeError};
+
+/// Codes of the errors the shared crates raise from inside any contract:
+/// upgrades (1000s), pausing (1100s) and token transfers (1200s)
+const SHARED_ERRORS: Range<u32> = 1000..1300;
+
+/// Environment with auth mocked and no test snapshot written on drop
+pub fn quiet_env() -> Env {
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+    env
+}
+
+/// Value of a `try_` call, or `None` if it failed with a defined error.
+/// Anything else, such as a panic or an arithmetic overflow trapping the
+/// contract, fails the fuzz run.
+pub fn check<T, C: Debug, E>(result: Result<Result<T, C>, Result<E, InvokeError>>) -> Option<T> {
+    match result {
+        Ok(Ok(value)) => Some(value),
+        Ok(Err(error)) => panic!("return value did not convert: {error:?}"),
+        Err(Ok(_)) => None,
+        Err(Err(InvokeError::Contract(code))) if SHARED_ERRORS.contains(&code) => None,
+        Err(Err(error)) => panic!("call failed outside the defined errors: {error:?}"),
+    }
+}